@@ -75,6 +75,15 @@ impl BlockFilter {
     /// build block filter data to the latest block
     fn build_filter_data(&self) {
         let snapshot = self.shared.snapshot();
+        // the filter data chains the parent filter hash from the genesis, so it's built after all
+        // the block bodies imported from a cell-set snapshot are back-filled
+        if let Some((start, end)) = snapshot.get_backfill_range() {
+            debug!(
+                "Block bodies in [{}, {}) are not back-filled yet, skip building filter data",
+                start, end
+            );
+            return;
+        }
         let tip_header = snapshot.get_tip_header().expect("tip stored");
         let start_number = match snapshot.get_latest_built_filter_data_block_hash() {
             Some(block_hash) => {
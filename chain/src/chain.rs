@@ -8,6 +8,7 @@ use ckb_logger::{
     self, debug, error, info, log_enabled, log_enabled_target, trace, trace_target, warn,
 };
 use ckb_merkle_mountain_range::leaf_index_to_mmr_size;
+use ckb_notify::{CanonicalChainChanged, TransactionStatusEvent};
 use ckb_proposal_table::ProposalTable;
 #[cfg(debug_assertions)]
 use ckb_rust_unstable_port::IsSorted;
@@ -207,6 +208,7 @@ impl GlobalIndex {
 pub struct ChainService {
     shared: Shared,
     proposal_table: ProposalTable,
+    parallel_script_verification: bool,
 }
 
 impl ChainService {
@@ -215,9 +217,15 @@ impl ChainService {
         ChainService {
             shared,
             proposal_table,
+            parallel_script_verification: false,
         }
     }
 
+    /// Verify the independent script groups of each block transaction in parallel
+    pub fn enable_parallel_script_verification(&mut self) {
+        self.parallel_script_verification = true;
+    }
+
     /// start background single-threaded service with specified thread_name.
     pub fn start<S: ToString>(mut self, thread_name: Option<S>) -> ChainController {
         let signal_receiver = new_crossbeam_exit_rx();
@@ -511,9 +519,22 @@ impl ChainService {
             }
 
             let block_ref: &BlockView = &block;
-            self.shared
-                .notify_controller()
-                .notify_new_block(block_ref.clone());
+            let notify_controller = self.shared.notify_controller();
+            notify_controller.notify_new_block(block_ref.clone());
+            notify_controller.notify_transaction_status(TransactionStatusEvent::ChainChanged(
+                Arc::new(CanonicalChainChanged {
+                    detached_headers: fork
+                        .detached_blocks()
+                        .iter()
+                        .map(BlockView::header)
+                        .collect(),
+                    attached_headers: fork
+                        .attached_blocks()
+                        .iter()
+                        .map(BlockView::header)
+                        .collect(),
+                }),
+            ));
             if log_enabled!(ckb_logger::Level::Debug) {
                 self.print_chain(10);
             }
@@ -771,13 +792,16 @@ impl ChainService {
                     match resolved {
                         Ok(resolved) => {
                             let verified = {
-                                let contextual_block_verifier = ContextualBlockVerifier::new(
+                                let mut contextual_block_verifier = ContextualBlockVerifier::new(
                                     verify_context.clone(),
                                     async_handle,
                                     switch,
                                     Arc::clone(&txs_verify_cache),
                                     &mmr,
                                 );
+                                if self.parallel_script_verification {
+                                    contextual_block_verifier.enable_parallel_script_verification();
+                                }
                                 contextual_block_verifier.verify(&resolved, b)
                             };
                             match verified {
@@ -244,6 +244,7 @@ fn _test_load_code_with_snapshot_after_hardfork(script_type: ScriptHashType) {
             .build()
             .unwrap(),
         ckb2023: CKB2023::new_mirana().as_builder().build().unwrap(),
+        syscall_extensions: Default::default(),
     };
     let consensus = ConsensusBuilder::default()
         .cellbase_maturity(EpochNumberWithFraction::new(0, 0, 1))
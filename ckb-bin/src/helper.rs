@@ -1,3 +1,5 @@
+use ckb_app_config::ExitCode;
+use ckb_chain_spec::consensus::Consensus;
 use ckb_logger::debug;
 
 use std::io::{stdin, stdout, Write};
@@ -76,3 +78,12 @@ pub fn raise_fd_limit() {
         debug!("raise_fd_limit newly-increased limit: {}", limit);
     }
 }
+
+/// Fails the subcommands verifying the scripts if the chain spec enables a syscall extension
+/// which is not installed by `ckb_script::setup_syscall_extensions`.
+pub fn check_syscall_extensions(consensus: &Consensus) -> Result<(), ExitCode> {
+    ckb_script::check_syscall_extensions(consensus.hardfork_switch()).map_err(|err| {
+        eprintln!("Config Error: {err}");
+        ExitCode::Config
+    })
+}
@@ -134,6 +134,9 @@ fn run_app_inner(
         cli::CMD_EXPORT => subcommand::export(setup.export(matches)?, handle.clone()),
         cli::CMD_IMPORT => subcommand::import(setup.import(matches)?, handle.clone()),
         cli::CMD_STATS => subcommand::stats(setup.stats(matches)?, handle.clone()),
+        cli::CMD_DEBUG_SCRIPT => {
+            subcommand::debug_script(setup.debug_script(matches)?, handle.clone())
+        }
         cli::CMD_RESET_DATA => subcommand::reset_data(setup.reset_data(matches)?),
         cli::CMD_MIGRATE => subcommand::migrate(setup.migrate(matches)?),
         #[cfg(not(target_os = "windows"))]
@@ -170,6 +173,7 @@ fn is_silent_logging(cmd: &str) -> Silent {
         cli::CMD_EXPORT
             | cli::CMD_IMPORT
             | cli::CMD_STATS
+            | cli::CMD_DEBUG_SCRIPT
             | cli::CMD_MIGRATE
             | cli::CMD_RESET_DATA
             | cli::CMD_DAEMON
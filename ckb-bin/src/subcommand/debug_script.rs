@@ -0,0 +1,148 @@
+use crate::helper::check_syscall_extensions;
+use ckb_app_config::{DebugScriptArgs, DebugScriptTransaction, ExitCode};
+use ckb_async_runtime::Handle;
+use ckb_jsonrpc_types::ScriptGroupType as JsonScriptGroupType;
+use ckb_script::{GdbStub, ScriptGroupType, TransactionScriptsVerifier, TxVerifyEnv};
+use ckb_shared::{SharedBuilder, Snapshot};
+use ckb_store::{data_loader_wrapper::AsDataLoader, ChainStore};
+use ckb_types::{
+    core::cell::{resolve_transaction, CellMetaBuilder, CellProvider, CellStatus, HeaderChecker},
+    core::error::OutPointError,
+    packed,
+    prelude::*,
+};
+use std::collections::HashSet;
+use std::net::TcpListener;
+use std::sync::Arc;
+
+pub fn debug_script(args: DebugScriptArgs, async_handle: Handle) -> Result<(), ExitCode> {
+    check_syscall_extensions(&args.consensus)?;
+    let shared_builder = SharedBuilder::new(
+        &args.config.bin_name,
+        args.config.root_dir.as_path(),
+        &args.config.db,
+        None,
+        async_handle,
+        args.consensus,
+    )?;
+    let (shared, _) = shared_builder.build()?;
+    let snapshot = shared.cloned_snapshot();
+
+    let (transaction, tx_env) = match args.transaction {
+        DebugScriptTransaction::Hash(tx_hash) => {
+            let (transaction, info) = snapshot
+                .get_transaction_with_info(&tx_hash.pack())
+                .ok_or_else(|| {
+                    eprintln!("Transaction {tx_hash:#x} is not found in the database");
+                    ExitCode::Cli
+                })?;
+            let header = snapshot
+                .get_block_header(&info.block_hash)
+                .ok_or(ExitCode::IO)?;
+            (transaction, TxVerifyEnv::new_commit(&header))
+        }
+        DebugScriptTransaction::File(path) => {
+            let content = std::fs::read_to_string(&path).map_err(|err| {
+                eprintln!("Failed to read {}: {err}", path.display());
+                ExitCode::IO
+            })?;
+            let transaction: ckb_jsonrpc_types::Transaction = serde_json::from_str(&content)
+                .map_err(|err| {
+                    eprintln!("Failed to parse {}: {err}", path.display());
+                    ExitCode::Cli
+                })?;
+            (
+                packed::Transaction::from(transaction).into_view(),
+                TxVerifyEnv::new_submit(snapshot.tip_header()),
+            )
+        }
+    };
+    let resolved = resolve_transaction(
+        transaction,
+        &mut HashSet::new(),
+        &StoreCellProvider(&snapshot),
+        &StoreCellProvider(&snapshot),
+    )
+    .map_err(|err| {
+        eprintln!("Failed to resolve the transaction: {err}");
+        ExitCode::Failure
+    })?;
+
+    let consensus = snapshot.cloned_consensus();
+    let max_cycles = args.max_cycles.unwrap_or(consensus.max_block_cycles);
+    let verifier = TransactionScriptsVerifier::new(
+        Arc::new(resolved),
+        snapshot.as_data_loader(),
+        consensus,
+        Arc::new(tx_env),
+    );
+    let script_group_type = match args.script_group_type {
+        JsonScriptGroupType::Lock => ScriptGroupType::Lock,
+        JsonScriptGroupType::Type => ScriptGroupType::Type,
+    };
+    let script_hash = args.script_hash.pack();
+    if verifier
+        .find_script_group(script_group_type, &script_hash)
+        .is_none()
+    {
+        eprintln!(
+            "The {script_group_type} script group {:#x} is not found in the transaction",
+            args.script_hash
+        );
+        return Err(ExitCode::Cli);
+    }
+
+    let listener = TcpListener::bind(args.listen).map_err(|err| {
+        eprintln!("Failed to listen on {}: {err}", args.listen);
+        ExitCode::IO
+    })?;
+    println!("Waiting for the GDB connection on {}", args.listen);
+    let (stream, peer) = listener.accept().map_err(|err| {
+        eprintln!("Failed to accept the GDB connection: {err}");
+        ExitCode::IO
+    })?;
+    println!("GDB connected from {peer}");
+
+    match verifier.debug_single(
+        script_group_type,
+        &script_hash,
+        max_cycles,
+        GdbStub::new(stream),
+    ) {
+        Ok(cycles) => {
+            println!("The script group passed, consumed cycles: {cycles}");
+            Ok(())
+        }
+        Err(err) => {
+            println!("The script group failed: {err}");
+            Err(ExitCode::Failure)
+        }
+    }
+}
+
+// Resolves the cells from the transactions creating them, so the inputs and the cell deps of a
+// committed transaction can be resolved after they are spent.
+struct StoreCellProvider<'a>(&'a Snapshot);
+
+impl<'a> CellProvider for StoreCellProvider<'a> {
+    fn cell(&self, out_point: &packed::OutPoint, _eager_load: bool) -> CellStatus {
+        let index: u32 = out_point.index().unpack();
+        self.0
+            .get_transaction_with_info(&out_point.tx_hash())
+            .and_then(|(transaction, info)| {
+                let (output, data) = transaction.output_with_data(index as usize)?;
+                let cell_meta = CellMetaBuilder::from_cell_output(output, data)
+                    .out_point(out_point.clone())
+                    .transaction_info(info)
+                    .build();
+                Some(CellStatus::live_cell(cell_meta))
+            }) // treat as live cell, regardless of live or dead
+            .unwrap_or(CellStatus::Unknown)
+    }
+}
+
+impl<'a> HeaderChecker for StoreCellProvider<'a> {
+    fn check_valid(&self, block_hash: &packed::Byte32) -> Result<(), OutPointError> {
+        self.0.check_valid(block_hash)
+    }
+}
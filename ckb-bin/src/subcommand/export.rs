@@ -1,6 +1,6 @@
 use ckb_app_config::{ExitCode, ExportArgs};
 use ckb_async_runtime::Handle;
-use ckb_instrument::Export;
+use ckb_instrument::{Export, ExportSnapshot};
 use ckb_shared::SharedBuilder;
 
 pub fn export(args: ExportArgs, async_handle: Handle) -> Result<(), ExitCode> {
@@ -13,6 +13,14 @@ pub fn export(args: ExportArgs, async_handle: Handle) -> Result<(), ExitCode> {
         args.consensus,
     )?;
     let (shared, _) = builder.build()?;
+    if args.snapshot {
+        return ExportSnapshot::new(shared, args.target)
+            .execute()
+            .map_err(|err| {
+                eprintln!("Export error: {err:?}");
+                ExitCode::Failure
+            });
+    }
     Export::new(shared, args.target).execute().map_err(|err| {
         eprintln!("Export error: {err:?}");
         ExitCode::Failure
@@ -1,10 +1,12 @@
+use crate::helper::check_syscall_extensions;
 use ckb_app_config::{ExitCode, ImportArgs};
 use ckb_async_runtime::Handle;
 use ckb_chain::chain::ChainService;
-use ckb_instrument::Import;
+use ckb_instrument::{Import, ImportSnapshot};
 use ckb_shared::SharedBuilder;
 
 pub fn import(args: ImportArgs, async_handle: Handle) -> Result<(), ExitCode> {
+    check_syscall_extensions(&args.consensus)?;
     let builder = SharedBuilder::new(
         &args.config.bin_name,
         args.config.root_dir.as_path(),
@@ -15,6 +17,15 @@ pub fn import(args: ImportArgs, async_handle: Handle) -> Result<(), ExitCode> {
     )?;
     let (shared, mut pack) = builder.build()?;
 
+    if args.snapshot {
+        return ImportSnapshot::new(shared, args.source)
+            .execute()
+            .map_err(|err| {
+                eprintln!("Import error: {err:?}");
+                ExitCode::Failure
+            });
+    }
+
     let chain_service = ChainService::new(shared, pack.take_proposal_table());
     let chain_controller = chain_service.start::<&str>(Some("ImportChainService"));
 
@@ -1,5 +1,6 @@
 #[cfg(not(target_os = "windows"))]
 mod daemon;
+mod debug_script;
 mod export;
 mod import;
 mod init;
@@ -14,6 +15,7 @@ mod stats;
 
 #[cfg(not(target_os = "windows"))]
 pub use self::daemon::{check_process, daemon};
+pub use self::debug_script::debug_script;
 pub use self::export::export;
 pub use self::import::import;
 pub use self::init::init;
@@ -1,3 +1,4 @@
+use crate::helper::check_syscall_extensions;
 use ckb_app_config::{ExitCode, ReplayArgs};
 use ckb_async_runtime::Handle;
 use ckb_chain::chain::ChainService;
@@ -11,6 +12,7 @@ use std::sync::Arc;
 const MIN_PROFILING_TIME: u64 = 5;
 
 pub fn replay(args: ReplayArgs, async_handle: Handle) -> Result<(), ExitCode> {
+    check_syscall_extensions(&args.consensus)?;
     let shared_builder = SharedBuilder::new(
         &args.config.bin_name,
         args.config.root_dir.as_path(),
@@ -1,4 +1,4 @@
-use crate::helper::deadlock_detection;
+use crate::helper::{check_syscall_extensions, deadlock_detection};
 use ckb_app_config::{ExitCode, RunArgs};
 use ckb_async_runtime::Handle;
 use ckb_build_info::Version;
@@ -10,6 +10,7 @@ use ckb_types::core::cell::setup_system_cell_cache;
 
 pub fn run(args: RunArgs, version: Version, async_handle: Handle) -> Result<(), ExitCode> {
     deadlock_detection();
+    check_syscall_extensions(&args.consensus)?;
 
     info!("ckb version: {}", version);
     let mut launcher = Launcher::new(args, version, async_handle);
@@ -20,7 +21,7 @@ pub fn run(args: RunArgs, version: Version, async_handle: Handle) -> Result<(),
     let (shared, mut pack) = launcher.build_shared(block_assembler_config)?;
 
     // spawn freezer background process
-    let _freezer = shared.spawn_freeze();
+    let _freezer = shared.spawn_freeze(launcher.args.config.store.prune_retention_depth);
 
     setup_system_cell_cache(
         shared.consensus().genesis_block(),
@@ -148,6 +148,20 @@ impl Freezer {
         self.number.load(Ordering::SeqCst)
     }
 
+    /// Return the earliest block number which is not pruned
+    pub fn tail(&self) -> BlockNumber {
+        self.inner.lock().files.tail()
+    }
+
+    /// Prunes the blocks before the provided block number, the latest frozen block is always kept.
+    pub fn prune(&self, threshold: BlockNumber) -> Result<(), Error> {
+        self.inner
+            .lock()
+            .files
+            .prune(threshold)
+            .map_err(internal_error)
+    }
+
     /// Truncate discards any recent data above the provided threshold number.
     pub fn truncate(&self, item: u64) -> Result<(), Error> {
         if item > 0 && ((item + 1) < self.number()) {
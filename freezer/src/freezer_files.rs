@@ -49,6 +49,8 @@ pub struct FreezerFiles {
     max_size: u64,
     // number of the earliest file
     pub(crate) tail_id: FileId,
+    // number of the earliest item which is not pruned
+    pub(crate) tail: u64,
     // number of the currently active head file
     pub(crate) head_id: FileId,
     // data file path
@@ -109,6 +111,12 @@ impl FreezerFiles {
         self.number.load(Ordering::SeqCst)
     }
 
+    /// Return the earliest item number which is not pruned
+    #[inline]
+    pub fn tail(&self) -> u64 {
+        self.tail
+    }
+
     /// Append item into freezer files
     pub fn append(&mut self, number: u64, input: &[u8]) -> Result<(), IoError> {
         let expected = self.number.load(Ordering::SeqCst);
@@ -167,7 +175,7 @@ impl FreezerFiles {
 
     /// Retrieve frozen item by number
     pub fn retrieve(&mut self, item: u64) -> Result<Option<Vec<u8>>, IoError> {
-        if item < 1 {
+        if item < 1 || item < self.tail {
             return Ok(None);
         }
         if self.number.load(Ordering::SeqCst) <= item {
@@ -259,6 +267,12 @@ impl FreezerFiles {
         if item < 1 || ((item + 1) >= self.number()) {
             return Ok(());
         }
+        if item < self.tail {
+            return Err(IoError::new(
+                IoErrorKind::Other,
+                format!("truncating pruned item {item}, tail {}", self.tail),
+            ));
+        }
         ckb_logger::trace!("Freezer truncate items {}", item);
 
         let mut buffer = [0; INDEX_ENTRY_SIZE as usize];
@@ -284,6 +298,47 @@ impl FreezerFiles {
         Ok(())
     }
 
+    /// Prunes the items before the provided item number, the data files whose items are all pruned
+    /// are deleted.
+    ///
+    /// The first entry of the index, which is never used to locate an item, records the earliest
+    /// file id and the earliest item which is not pruned.
+    pub fn prune(&mut self, item: u64) -> Result<(), IoError> {
+        // the latest item is never pruned
+        let item = item.min(self.number().saturating_sub(1));
+        if item <= self.tail {
+            return Ok(());
+        }
+        ckb_logger::trace!("Freezer prune items before {}", item);
+
+        let mut buffer = [0; INDEX_ENTRY_SIZE as usize];
+        self.index.seek(SeekFrom::Start(item * INDEX_ENTRY_SIZE))?;
+        self.index.read_exact(&mut buffer)?;
+        let tail_id = IndexEntry::decode(&buffer)?.file_id;
+
+        // update the index before deleting the files, an interruption leaves dangling files only
+        let tail_index = IndexEntry {
+            file_id: tail_id,
+            offset: item,
+        };
+        self.index.seek(SeekFrom::Start(0))?;
+        self.index.write_all(&tail_index.encode())?;
+        self.index.sync_all()?;
+
+        for id in self.tail_id..tail_id {
+            self.release(id);
+            let path = self.file_path.join(helper::file_name(id));
+            if let Err(e) = fs::remove_file(path) {
+                if e.kind() != IoErrorKind::NotFound {
+                    return Err(e);
+                }
+            }
+        }
+        self.tail_id = tail_id;
+        self.tail = item;
+        Ok(())
+    }
+
     /// Attempts to open files, initialize fd map
     pub fn preopen(&mut self) -> Result<(), IoError> {
         self.release_all();
@@ -418,6 +473,7 @@ impl FreezerFilesBuilder {
         index.read_exact(&mut buffer)?;
         let tail_index = IndexEntry::decode(&buffer)?;
         let tail_id = tail_index.file_id;
+        let tail = tail_index.offset;
 
         index.seek(SeekFrom::Start(index_size - INDEX_ENTRY_SIZE))?;
         index.read_exact(&mut buffer)?;
@@ -479,6 +535,7 @@ impl FreezerFilesBuilder {
             files: LruCache::new(self.open_files_limit),
             head: Head::new(head, head_size),
             tail_id,
+            tail,
             number: Arc::new(AtomicU64::new(number)),
             max_size: self.max_file_size,
             head_id: head_index.file_id,
@@ -1,4 +1,4 @@
-use crate::freezer_files::helper::truncate_file;
+use crate::freezer_files::helper::{file_name, truncate_file};
 use crate::freezer_files::{FreezerFilesBuilder, INDEX_ENTRY_SIZE};
 
 fn make_bytes(size: usize, byte: u8) -> Vec<u8> {
@@ -256,6 +256,57 @@ fn truncate() {
     assert_eq!(None, retrieve_out_of_bound);
 }
 
+#[test]
+fn prune() {
+    let tempdir = tempfile::Builder::new().tempdir().unwrap();
+    let file_exists = |id| tempdir.path().join(file_name(id)).exists();
+    {
+        let mut freezer = FreezerFilesBuilder::new(tempdir.path().to_path_buf())
+            .enable_compression(false)
+            .max_file_size(50)
+            .build()
+            .unwrap();
+        freezer.preopen().unwrap();
+        for i in 1..30 {
+            let data = make_bytes(15, i);
+            freezer.append(i.into(), &data).unwrap();
+        }
+
+        // 3 items per file, the item 10 is in the file 3
+        freezer.prune(10).unwrap();
+        assert_eq!(freezer.tail(), 10);
+        assert!(!file_exists(2));
+        assert!(file_exists(3));
+        assert_eq!(freezer.retrieve(9).unwrap(), None);
+        assert_eq!(freezer.retrieve(10).unwrap(), Some(make_bytes(15, 10)));
+
+        // pruning backward has no effect
+        freezer.prune(5).unwrap();
+        assert_eq!(freezer.tail(), 10);
+    }
+
+    let mut freezer = FreezerFilesBuilder::new(tempdir.path().to_path_buf())
+        .enable_compression(false)
+        .max_file_size(50)
+        .build()
+        .unwrap();
+    freezer.preopen().unwrap();
+    assert_eq!(freezer.tail(), 10);
+    assert_eq!(freezer.retrieve(9).unwrap(), None);
+    for i in 10..30 {
+        assert_eq!(freezer.retrieve(i).unwrap(), Some(make_bytes(15, i as u8)));
+    }
+    assert!(freezer.truncate(5).is_err());
+
+    freezer.append(30, &make_bytes(15, 30)).unwrap();
+    // the latest item is always kept
+    freezer.prune(100).unwrap();
+    assert_eq!(freezer.tail(), 30);
+    assert!(!file_exists(8));
+    assert_eq!(freezer.retrieve(29).unwrap(), None);
+    assert_eq!(freezer.retrieve(30).unwrap(), Some(make_bytes(15, 30)));
+}
+
 #[test]
 fn open_files_limit_retrieve() {
     let tempdir = tempfile::Builder::new().tempdir().unwrap();
@@ -0,0 +1,395 @@
+use crate::{ProtocolId, ServiceControl, SessionId, SupportProtocols};
+use ckb_app_config::{BandwidthConfig, SupportProtocol};
+use ckb_logger::{debug, trace};
+use ckb_util::Mutex;
+use p2p::{bytes::Bytes, service::ServiceAsyncControl};
+use std::collections::HashMap;
+use std::sync::{
+    atomic::{AtomicU64, AtomicUsize, Ordering},
+    Arc,
+};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::{channel, error::TrySendError, Receiver, Sender};
+
+/// The maximum number of the messages delayed for a peer
+pub(crate) const MAX_DELAYED_MESSAGES: usize = 1024;
+/// The maximum bytes of the messages delayed for a peer, a larger message is still queued alone
+pub(crate) const MAX_DELAYED_BYTES: usize = 32 * 1024 * 1024;
+/// The maximum debt of a rate limiter, in seconds of its rate
+const MAX_DEBT_SECS: f64 = 10.0;
+
+/// Bytes of the messages sent to and received from a peer
+#[derive(Debug, Default)]
+pub struct Traffic {
+    sent: AtomicU64,
+    received: AtomicU64,
+}
+
+impl Traffic {
+    /// Bytes sent to the peer
+    pub fn sent_bytes(&self) -> u64 {
+        self.sent.load(Ordering::Relaxed)
+    }
+
+    /// Bytes received from the peer
+    pub fn received_bytes(&self) -> u64 {
+        self.received.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn add_sent(&self, bytes: u64) {
+        self.sent.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_received(&self, bytes: u64) {
+        self.received.fetch_add(bytes, Ordering::Relaxed);
+    }
+}
+
+/// A message delayed by the upload limits
+#[derive(Debug)]
+struct DelayedMessage {
+    proto_id: ProtocolId,
+    data: Bytes,
+    quick: bool,
+    due: Instant,
+}
+
+/// The messages to a peer delayed by the upload limits
+///
+/// They are sent in order by a task of the peer, so waiting for the bandwidth blocks neither the
+/// protocol handlers nor the other peers. Once a message is delayed, the later ones to the peer are
+/// queued behind it to keep the order. The queue is bounded by `MAX_DELAYED_MESSAGES` and
+/// `MAX_DELAYED_BYTES`.
+#[derive(Debug, Default)]
+pub(crate) struct UploadQueue {
+    sender: Mutex<Option<Sender<DelayedMessage>>>,
+    pending: Arc<Pending>,
+    last_due: Mutex<Option<Instant>>,
+}
+
+#[derive(Debug, Default)]
+struct Pending {
+    messages: AtomicUsize,
+    bytes: AtomicUsize,
+}
+
+impl Pending {
+    fn add(&self, bytes: usize) {
+        self.messages.fetch_add(1, Ordering::AcqRel);
+        self.bytes.fetch_add(bytes, Ordering::AcqRel);
+    }
+
+    fn sub(&self, bytes: usize) {
+        self.bytes.fetch_sub(bytes, Ordering::AcqRel);
+        self.messages.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+impl UploadQueue {
+    /// Whether some messages are waiting in the queue
+    pub(crate) fn is_busy(&self) -> bool {
+        self.pending.messages.load(Ordering::Acquire) > 0
+    }
+
+    /// The time the last queued message is due, None if the queue is empty
+    pub(crate) fn backlog_due(&self) -> Option<Instant> {
+        if self.is_busy() {
+            *self.last_due.lock()
+        } else {
+            None
+        }
+    }
+
+    /// Queue the message to be sent at `due`, the task of the peer is spawned on the first one.
+    ///
+    /// Returns false if the queue is full, the message is dropped then.
+    pub(crate) fn push(
+        &self,
+        control: &ServiceControl,
+        session_id: SessionId,
+        proto_id: ProtocolId,
+        data: Bytes,
+        quick: bool,
+        due: Instant,
+    ) -> bool {
+        let bytes = data.len();
+        if self.is_busy() && self.pending.bytes.load(Ordering::Acquire) + bytes > MAX_DELAYED_BYTES
+        {
+            return false;
+        }
+        let mut sender = self.sender.lock();
+        let sender = sender.get_or_insert_with(|| {
+            let (sender, receiver) = channel(MAX_DELAYED_MESSAGES);
+            let task = send_delayed_messages(
+                control.clone().into(),
+                session_id,
+                receiver,
+                Arc::clone(&self.pending),
+            );
+            if let Err(err) = control.future_task(task) {
+                debug!("Spawn the upload queue of {} failed: {:?}", session_id, err);
+            }
+            sender
+        });
+        self.pending.add(bytes);
+        let message = DelayedMessage {
+            proto_id,
+            data,
+            quick,
+            due,
+        };
+        match sender.try_send(message) {
+            Ok(()) => {
+                *self.last_due.lock() = Some(due);
+                true
+            }
+            Err(TrySendError::Full(_)) => {
+                self.pending.sub(bytes);
+                false
+            }
+            Err(TrySendError::Closed(_)) => {
+                self.pending.sub(bytes);
+                debug!("Drop the delayed message to {}", session_id);
+                true
+            }
+        }
+    }
+}
+
+// The task ends once the peer is removed from the registry, which drops the sender.
+async fn send_delayed_messages(
+    control: ServiceAsyncControl,
+    session_id: SessionId,
+    mut receiver: Receiver<DelayedMessage>,
+    pending: Arc<Pending>,
+) {
+    while let Some(message) = receiver.recv().await {
+        tokio::time::sleep_until(message.due.into()).await;
+        trace!(
+            "[send delayed message]: {}, to={}, length={}",
+            message.proto_id,
+            session_id,
+            message.data.len()
+        );
+        let bytes = message.data.len();
+        let result = if message.quick {
+            control
+                .quick_send_message_to(session_id, message.proto_id, message.data)
+                .await
+        } else {
+            control
+                .send_message_to(session_id, message.proto_id, message.data)
+                .await
+        };
+        pending.sub(bytes);
+        if let Err(err) = result {
+            debug!("Send delayed message to {} failed: {:?}", session_id, err);
+        }
+    }
+}
+
+/// Token bucket holding at most one second of the rate
+///
+/// Consuming never fails, the bucket goes into debt instead, and the consumer waits `delay` to pay
+/// it off before transferring more data. The debt is capped at `MAX_DEBT_SECS` of the rate, so the
+/// delay is bounded, and the queues bounding the delayed data take over the back pressure.
+pub(crate) struct RateLimiter {
+    rate: f64,
+    bucket: Mutex<Bucket>,
+}
+
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl RateLimiter {
+    /// The rate is in bytes per second, returns None for zero which means unlimited
+    pub(crate) fn new(rate: u64, now: Instant) -> Option<Self> {
+        (rate > 0).then(|| RateLimiter {
+            rate: rate as f64,
+            bucket: Mutex::new(Bucket {
+                tokens: rate as f64,
+                updated_at: now,
+            }),
+        })
+    }
+
+    pub(crate) fn consume(&self, bytes: u64, now: Instant) {
+        let mut bucket = self.bucket.lock();
+        self.refill(&mut bucket, now);
+        bucket.tokens = (bucket.tokens - bytes as f64).max(-self.rate * MAX_DEBT_SECS);
+    }
+
+    /// The duration to wait until the debt is paid off
+    pub(crate) fn delay(&self, now: Instant) -> Duration {
+        let mut bucket = self.bucket.lock();
+        self.refill(&mut bucket, now);
+        if bucket.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-bucket.tokens / self.rate)
+        }
+    }
+
+    fn refill(&self, bucket: &mut Bucket, now: Instant) {
+        let elapsed = now.saturating_duration_since(bucket.updated_at);
+        bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * self.rate).min(self.rate);
+        bucket.updated_at = now;
+    }
+}
+
+struct ProtocolBandwidth {
+    name: String,
+    upload: Option<RateLimiter>,
+    download: Option<RateLimiter>,
+}
+
+/// The global and per protocol bandwidth limits, it also counts the bytes by protocol in metrics
+pub(crate) struct Bandwidth {
+    upload: Option<RateLimiter>,
+    download: Option<RateLimiter>,
+    protocols: HashMap<ProtocolId, ProtocolBandwidth>,
+}
+
+impl Bandwidth {
+    pub(crate) fn new(config: &BandwidthConfig, now: Instant) -> Self {
+        let limiter = |rate: Option<u64>| rate.and_then(|rate| RateLimiter::new(rate, now));
+        let protocols = all_protocols()
+            .into_iter()
+            .map(|(protocol, key)| {
+                let limits = config.protocols.get(&key);
+                let bandwidth = ProtocolBandwidth {
+                    name: protocol.name(),
+                    upload: limiter(
+                        limits.and_then(|limits| limits.max_upload_rate.map(|rate| rate.as_u64())),
+                    ),
+                    download: limiter(
+                        limits
+                            .and_then(|limits| limits.max_download_rate.map(|rate| rate.as_u64())),
+                    ),
+                };
+                (protocol.protocol_id(), bandwidth)
+            })
+            .collect();
+        Bandwidth {
+            upload: limiter(config.max_upload_rate.map(|rate| rate.as_u64())),
+            download: limiter(config.max_download_rate.map(|rate| rate.as_u64())),
+            protocols,
+        }
+    }
+
+    /// Returns the duration to delay the message until the debt is paid off
+    pub(crate) fn sent(&self, proto_id: ProtocolId, bytes: u64, now: Instant) -> Duration {
+        let protocol = self.protocols.get(&proto_id);
+        for limiter in self
+            .upload
+            .iter()
+            .chain(protocol.and_then(|protocol| protocol.upload.as_ref()))
+        {
+            limiter.consume(bytes, now);
+        }
+        metric_protocol_bytes(proto_id, protocol, "out", bytes);
+        self.upload_delay(proto_id, now)
+    }
+
+    pub(crate) fn received(&self, proto_id: ProtocolId, bytes: u64, now: Instant) {
+        let protocol = self.protocols.get(&proto_id);
+        for limiter in self
+            .download
+            .iter()
+            .chain(protocol.and_then(|protocol| protocol.download.as_ref()))
+        {
+            limiter.consume(bytes, now);
+        }
+        metric_protocol_bytes(proto_id, protocol, "in", bytes);
+    }
+
+    /// Whether the upload of any protocol is limited, the messages to a peer may be delayed then
+    pub(crate) fn is_upload_limited(&self) -> bool {
+        self.upload.is_some()
+            || self
+                .protocols
+                .values()
+                .any(|protocol| protocol.upload.is_some())
+    }
+
+    /// Whether the download of the protocol is limited
+    pub(crate) fn is_download_limited(&self, proto_id: ProtocolId) -> bool {
+        self.download.is_some()
+            || self
+                .protocols
+                .get(&proto_id)
+                .map(|protocol| protocol.download.is_some())
+                .unwrap_or(false)
+    }
+
+    /// The duration to wait before the protocol sends more messages
+    pub(crate) fn upload_delay(&self, proto_id: ProtocolId, now: Instant) -> Duration {
+        self.upload
+            .iter()
+            .chain(
+                self.protocols
+                    .get(&proto_id)
+                    .and_then(|protocol| protocol.upload.as_ref()),
+            )
+            .map(|limiter| limiter.delay(now))
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// The duration to wait before the protocol reads more messages
+    pub(crate) fn download_delay(&self, proto_id: ProtocolId, now: Instant) -> Duration {
+        self.download
+            .iter()
+            .chain(
+                self.protocols
+                    .get(&proto_id)
+                    .and_then(|protocol| protocol.download.as_ref()),
+            )
+            .map(|limiter| limiter.delay(now))
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+fn metric_protocol_bytes(
+    proto_id: ProtocolId,
+    protocol: Option<&ProtocolBandwidth>,
+    direction: &str,
+    bytes: u64,
+) {
+    if let Some(metrics) = ckb_metrics::handle() {
+        let name = protocol
+            .map(|protocol| protocol.name.clone())
+            .unwrap_or_else(|| proto_id.to_string());
+        metrics
+            .ckb_network_protocol_bytes
+            .with_label_values(&[&name, direction])
+            .inc_by(bytes);
+    }
+}
+
+fn all_protocols() -> Vec<(SupportProtocols, SupportProtocol)> {
+    vec![
+        (SupportProtocols::Ping, SupportProtocol::Ping),
+        (SupportProtocols::Discovery, SupportProtocol::Discovery),
+        (SupportProtocols::Identify, SupportProtocol::Identify),
+        (SupportProtocols::Feeler, SupportProtocol::Feeler),
+        (
+            SupportProtocols::DisconnectMessage,
+            SupportProtocol::DisconnectMessage,
+        ),
+        (SupportProtocols::Sync, SupportProtocol::Sync),
+        (SupportProtocols::RelayV2, SupportProtocol::Relay),
+        (SupportProtocols::RelayV3, SupportProtocol::Relay),
+        (SupportProtocols::Time, SupportProtocol::Time),
+        (SupportProtocols::Alert, SupportProtocol::Alert),
+        (SupportProtocols::LightClient, SupportProtocol::LightClient),
+        (SupportProtocols::Filter, SupportProtocol::Filter),
+        (
+            SupportProtocols::TxReconciliation,
+            SupportProtocol::TxReconciliation,
+        ),
+    ]
+}
@@ -1,12 +1,35 @@
 use crate::Score;
+use std::time::{Duration, Instant};
+
+/// The misbehavior score halves every this duration
+pub(crate) const MISBEHAVIOR_HALF_LIFE: Duration = Duration::from_secs(10 * 60);
+/// Protocols of a peer with a misbehavior score reaching this are throttled
+pub(crate) const THROTTLE_SCORE: u32 = 20;
+/// Throttled protocols get at most one message processed during this interval
+pub(crate) const THROTTLED_MESSAGE_INTERVAL: Duration = Duration::from_millis(500);
+/// Peers with a misbehavior score reaching this are disconnected
+pub(crate) const DISCONNECT_SCORE: u32 = 50;
+/// Peers with a misbehavior score reaching this are banned
+pub(crate) const BAN_SCORE: u32 = 100;
+/// The ban duration of peers reaching `BAN_SCORE`, it grows in proportion to the score
+pub(crate) const MISBEHAVIOR_BAN_TIME: Duration = Duration::from_secs(60 * 60);
 
 /// Peers behaviours
-/// we maintain a score to each peer
-/// report peer behaviour will affects peer's score
 ///
-/// Currently this feature is disabled, maybe someday we will add it back or totally remove it.
+/// The protocol handlers report the offenses of peers, each one adds its weight to the decaying
+/// misbehavior score of the peer, see `MisbehaviorScore`.
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum Behaviour {
+    /// Sent a request or an announcement exceeding the rate limit
+    TooManyRequests,
+    /// Sent a message not expected in the current state, e.g. a duplicated or unsolicited one
+    UnexpectedMessage,
+    /// Sent a transaction failed to be verified
+    InvalidTransaction,
+    /// Sent a message which can't be parsed, is inconsistent or exceeds the protocol limits
+    MalformedMessage,
+    /// Sent a block or header failed to be verified
+    InvalidBlock,
     #[cfg(test)]
     TestGood,
     #[cfg(test)]
@@ -14,14 +37,97 @@ pub enum Behaviour {
 }
 
 impl Behaviour {
-    /// Behaviour score
+    /// Behaviour score of the address in the peer store
     pub fn score(self) -> Score {
-        #[cfg(test)]
         match self {
+            #[cfg(test)]
             Behaviour::TestGood => 10,
+            #[cfg(test)]
             Behaviour::TestBad => -10,
+            _ => -(self.weight() as Score),
+        }
+    }
+
+    /// The weight added to the misbehavior score
+    pub fn weight(self) -> u32 {
+        match self {
+            Behaviour::TooManyRequests => 5,
+            Behaviour::UnexpectedMessage => 10,
+            Behaviour::InvalidTransaction => 5,
+            Behaviour::MalformedMessage => BAN_SCORE,
+            Behaviour::InvalidBlock => BAN_SCORE,
+            #[cfg(test)]
+            Behaviour::TestGood => 0,
+            #[cfg(test)]
+            Behaviour::TestBad => 10,
+        }
+    }
+}
+
+/// The penalty graduated by the misbehavior score
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Penalty {
+    /// No penalty
+    None,
+    /// Drop the messages of the offending protocol exceeding the throttled rate
+    Throttle,
+    /// Disconnect the peer
+    Disconnect,
+    /// Ban the peer
+    Ban,
+}
+
+/// Misbehavior score decaying exponentially by `MISBEHAVIOR_HALF_LIFE`
+#[derive(Debug, Clone, Copy)]
+pub struct MisbehaviorScore {
+    score: f64,
+    updated_at: Instant,
+}
+
+impl Default for MisbehaviorScore {
+    fn default() -> Self {
+        MisbehaviorScore {
+            score: 0.0,
+            updated_at: Instant::now(),
         }
-        #[cfg(not(test))]
-        0
+    }
+}
+
+impl MisbehaviorScore {
+    /// The score decayed until now
+    pub fn score(&self, now: Instant) -> u32 {
+        self.decayed(now).round() as u32
+    }
+
+    /// The penalty of the current score
+    pub fn penalty(&self, now: Instant) -> Penalty {
+        let score = self.score(now);
+        if score >= BAN_SCORE {
+            Penalty::Ban
+        } else if score >= DISCONNECT_SCORE {
+            Penalty::Disconnect
+        } else if score >= THROTTLE_SCORE {
+            Penalty::Throttle
+        } else {
+            Penalty::None
+        }
+    }
+
+    /// Add the weight of the behaviour, returns the penalty of the new score
+    pub fn report(&mut self, behaviour: Behaviour, now: Instant) -> Penalty {
+        self.score = self.decayed(now) + f64::from(behaviour.weight());
+        self.updated_at = now;
+        self.penalty(now)
+    }
+
+    /// The ban duration of the current score
+    pub fn ban_time(&self, now: Instant) -> Duration {
+        MISBEHAVIOR_BAN_TIME
+            .mul_f64(self.decayed(now).max(f64::from(BAN_SCORE)) / f64::from(BAN_SCORE))
+    }
+
+    fn decayed(&self, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(self.updated_at);
+        self.score * 0.5f64.powf(elapsed.as_secs_f64() / MISBEHAVIOR_HALF_LIFE.as_secs_f64())
     }
 }
@@ -62,6 +62,8 @@ pub enum PeerError {
     PeerIdExists(PeerId),
     /// Non-reserved peers
     NonReserved,
+    /// The peer id and the IP don't match the pinned trusted peers
+    UntrustedPeerId(PeerId),
     /// Peer is banned
     Banned,
     /// Reach max inbound limit
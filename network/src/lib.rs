@@ -6,6 +6,7 @@
 //! And implemented several basic protocols: identify, discovery, ping, feeler, disconnect_message
 //!
 
+mod bandwidth;
 mod behaviour;
 /// compress module
 pub mod compress;
@@ -22,18 +23,21 @@ mod services;
 mod tests;
 
 pub use crate::{
-    behaviour::Behaviour,
+    bandwidth::Traffic,
+    behaviour::{Behaviour, MisbehaviorScore, Penalty},
     errors::Error,
     network::{
         DefaultExitHandler, EventHandler, ExitHandler, NetworkController, NetworkService,
         NetworkState,
     },
+    network_group::{AsnMap, Group},
     peer::{Peer, PeerIdentifyInfo},
     peer_registry::PeerRegistry,
     peer_store::Score,
     protocols::{
-        identify::Flags, support_protocols::SupportProtocols, CKBProtocol, CKBProtocolContext,
-        CKBProtocolHandler, PeerIndex,
+        identify::Flags,
+        support_protocols::{SupportProtocols, RELAY_V4_VERSION},
+        CKBProtocol, CKBProtocolContext, CKBProtocolHandler, PeerIndex,
     },
 };
 pub use p2p::{
@@ -1,5 +1,8 @@
 //! Global state struct and start function
+use crate::bandwidth::Bandwidth;
+use crate::behaviour::Penalty;
 use crate::errors::{Error, P2PError};
+use crate::network_group::AsnMap;
 use crate::peer_registry::{ConnectionStatus, PeerRegistry};
 use crate::peer_store::{
     types::{AddrInfo, BannedAddr},
@@ -50,6 +53,7 @@ use std::{
     borrow::Cow,
     cmp::max,
     collections::{HashMap, HashSet},
+    io::{Error as IoError, ErrorKind},
     pin::Pin,
     sync::{
         atomic::{AtomicBool, Ordering},
@@ -88,6 +92,7 @@ pub struct NetworkState {
     pub(crate) required_flags: Flags,
 
     pub(crate) ckb2023: AtomicBool,
+    pub(crate) bandwidth: Bandwidth,
 }
 
 impl NetworkState {
@@ -114,18 +119,40 @@ impl NetworkState {
             })
             .collect();
         info!("Loading the peer store. This process may take a few seconds to complete.");
-        let peer_store = Mutex::new(PeerStore::load_from_dir_or_default(
-            config.peer_store_path(),
-        ));
+        let mut peer_store = PeerStore::load_from_dir_or_default(config.peer_store_path());
+        if let Some(path) = config.asn_map_path.as_ref() {
+            let asn_map = AsnMap::load(path)?;
+            info!(
+                "Loaded {} ASN prefixes from {}",
+                asn_map.len(),
+                path.display()
+            );
+            peer_store.set_asn_map(asn_map);
+        }
+        let peer_store = Mutex::new(peer_store);
         let bootnodes = config.bootnodes();
+        if let Some(addr) = config
+            .trusted_peers
+            .iter()
+            .find(|addr| extract_peer_id(addr).is_none() || multiaddr_to_socketaddr(addr).is_none())
+        {
+            return Err(IoError::new(
+                ErrorKind::InvalidInput,
+                format!("trusted peer {addr} must be an IP address ending with its peer id"),
+            )
+            .into());
+        }
 
         let peer_registry = PeerRegistry::new(
             config.max_inbound_peers(),
             config.max_outbound_peers(),
             config.whitelist_only,
             config.whitelist_peers(),
+            config.trusted_peers.clone(),
         );
 
+        let bandwidth = Bandwidth::new(&config.bandwidth, Instant::now());
+
         Ok(NetworkState {
             peer_store,
             config,
@@ -141,6 +168,7 @@ impl NetworkState {
             protocols: RwLock::new(Vec::new()),
             required_flags: Flags::SYNC | Flags::DISCOVERY | Flags::RELAY,
             ckb2023: AtomicBool::new(false),
+            bandwidth,
         })
     }
 
@@ -161,26 +189,137 @@ impl NetworkState {
         &self,
         p2p_control: &ServiceControl,
         session_id: SessionId,
+        proto_id: ProtocolId,
         behaviour: Behaviour,
     ) {
-        if let Some(addr) = self.with_peer_registry(|reg| {
-            reg.get_peer(session_id)
-                .filter(|peer| !peer.is_whitelist)
-                .map(|peer| peer.connected_addr.clone())
-        }) {
-            trace!("Report {:?} because {:?}", addr, behaviour);
-            let report_result = self.peer_store.lock().report(&addr, behaviour);
-            if report_result.is_banned() {
-                if let Err(err) = disconnect_with_message(p2p_control, session_id, "banned") {
+        let now = Instant::now();
+        let report_result = self.with_peer_registry_mut(|reg| {
+            reg.report(session_id, proto_id, behaviour, now)
+                .and_then(|penalty| {
+                    reg.get_peer(session_id).map(|peer| {
+                        (
+                            penalty,
+                            peer.misbehavior.score(now),
+                            peer.misbehavior.ban_time(now),
+                        )
+                    })
+                })
+        });
+        match report_result {
+            Some((Penalty::Ban, score, ban_time)) => {
+                self.ban_session(
+                    p2p_control,
+                    session_id,
+                    ban_time,
+                    format!("misbehavior score {score}, last reported {behaviour:?}"),
+                );
+            }
+            Some((Penalty::Disconnect, score, _)) => {
+                debug!(
+                    "Disconnect session {} with misbehavior score {}, last reported {:?}",
+                    session_id, score, behaviour
+                );
+                if let Err(err) = disconnect_with_message(p2p_control, session_id, "misbehavior") {
                     debug!("Disconnect failed {:?}, error: {:?}", session_id, err);
                 }
             }
-        } else {
+            Some((penalty, score, _)) => {
+                trace!(
+                    "Report session {} because {:?}, misbehavior score {}, penalty {:?}",
+                    session_id,
+                    behaviour,
+                    score,
+                    penalty
+                );
+            }
+            None => {
+                debug!(
+                    "Report {} failure: not found in peer registry or it is on the whitelist",
+                    session_id
+                );
+            }
+        }
+    }
+
+    /// Count the bytes of a message sent to the sessions, in the protocol and the peers traffic, and
+    /// queue it to the peers whose upload is delayed by the bandwidth limits. The peers whose queue
+    /// is full are disconnected.
+    ///
+    /// Returns the sessions to send the message right away.
+    pub(crate) fn schedule_sent(
+        &self,
+        p2p_control: &ServiceControl,
+        proto_id: ProtocolId,
+        session_ids: Vec<SessionId>,
+        data: &Bytes,
+        quick: bool,
+    ) -> Vec<SessionId> {
+        let bytes = data.len() as u64;
+        let now = Instant::now();
+        let delay = self
+            .bandwidth
+            .sent(proto_id, bytes * session_ids.len() as u64, now);
+        let mut overflowed = Vec::new();
+        let sent = self.with_peer_registry(|reg| {
+            session_ids
+                .into_iter()
+                .filter(|session_id| match reg.get_peer(*session_id) {
+                    Some(peer) => {
+                        peer.traffic.add_sent(bytes);
+                        if delay.is_zero() && !peer.upload_queue.is_busy() {
+                            true
+                        } else {
+                            trace!(
+                                "[delay message]: {}, to={}, delay={}ms",
+                                proto_id,
+                                session_id,
+                                delay.as_millis()
+                            );
+                            if !peer.upload_queue.push(
+                                p2p_control,
+                                *session_id,
+                                proto_id,
+                                data.clone(),
+                                quick,
+                                now + delay,
+                            ) {
+                                overflowed.push(*session_id);
+                            }
+                            false
+                        }
+                    }
+                    // let the sending report it
+                    None => true,
+                })
+                .collect()
+        });
+        for session_id in overflowed {
             debug!(
-                "Report {} failure: not found in peer registry or it is on the whitelist",
-                session_id
+                "Disconnect {} because its upload queue is full, drop the message of {}",
+                session_id, proto_id
             );
+            if let Err(err) = disconnect_with_message(p2p_control, session_id, "upload queue full")
+            {
+                debug!("Disconnect failed {:?}, error: {:?}", session_id, err);
+            }
         }
+        sent
+    }
+
+    /// Count the bytes of a message received from the session
+    pub(crate) fn record_received(
+        &self,
+        proto_id: ProtocolId,
+        session_id: SessionId,
+        bytes: usize,
+    ) {
+        let bytes = bytes as u64;
+        self.with_peer_registry(|reg| {
+            if let Some(peer) = reg.get_peer(session_id) {
+                peer.traffic.add_received(bytes);
+            }
+        });
+        self.bandwidth.received(proto_id, bytes, Instant::now());
     }
 
     pub(crate) fn ban_session(
@@ -1316,10 +1455,28 @@ impl NetworkController {
         data: Bytes,
     ) -> Result<(), SendErrorKind> {
         let now = Instant::now();
+        let session_ids = match target {
+            Some(session_id) => vec![session_id],
+            None => self.network_state.with_peer_registry(|reg| {
+                reg.peers()
+                    .iter()
+                    .filter(|(_, peer)| peer.protocols.contains_key(&proto_id))
+                    .map(|(session_id, _)| *session_id)
+                    .collect()
+            }),
+        };
+        let session_ids = self.network_state.schedule_sent(
+            &self.p2p_control,
+            proto_id,
+            session_ids,
+            &data,
+            quick,
+        );
+        if session_ids.is_empty() {
+            return Ok(());
+        }
         loop {
-            let target = target
-                .map(TargetSession::Single)
-                .unwrap_or(TargetSession::All);
+            let target = TargetSession::Multi(Box::new(session_ids.clone().into_iter()));
             let result = if quick {
                 self.p2p_control
                     .quick_filter_broadcast(target, proto_id, data.clone())
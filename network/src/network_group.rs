@@ -1,12 +1,44 @@
 use crate::{multiaddr::Multiaddr, multiaddr_to_socketaddr};
+use ipnetwork::IpNetwork;
+use std::collections::{BTreeSet, HashMap};
+use std::io::{Error as IoError, ErrorKind};
 use std::net::IpAddr;
+use std::path::Path;
 
-#[derive(Hash, Eq, PartialEq, Debug)]
+/// Network group of an address, peers in the same group are likely controlled by the same operator
+#[derive(Hash, Eq, PartialEq, Debug, Clone, Copy)]
 pub enum Group {
+    /// Can't group the address
     None,
+    /// Loopback address
     LocalNetwork,
+    /// IPv4 /16 prefix
     IP4([u8; 2]),
+    /// IPv6 /32 prefix
     IP6([u8; 4]),
+    /// Autonomous system number, only used when an ASN map is loaded
+    ASN(u32),
+}
+
+impl Group {
+    /// Group an address by the autonomous system announcing it, falls back to the
+    /// IPv4 /16 or IPv6 /32 prefix when the ASN map is absent or misses the address.
+    pub fn new(multiaddr: &Multiaddr, asn_map: Option<&AsnMap>) -> Group {
+        if let Some(asn_map) = asn_map {
+            if let Some(socket_addr) = multiaddr_to_socketaddr(multiaddr) {
+                let ip_addr = match socket_addr.ip() {
+                    IpAddr::V6(ipv6) => ipv6.to_ipv4().map(IpAddr::V4).unwrap_or(IpAddr::V6(ipv6)),
+                    ip_addr => ip_addr,
+                };
+                if !ip_addr.is_loopback() {
+                    if let Some(asn) = asn_map.lookup(ip_addr) {
+                        return Group::ASN(asn);
+                    }
+                }
+            }
+        }
+        multiaddr.into()
+    }
 }
 
 impl From<&Multiaddr> for Group {
@@ -41,3 +73,82 @@ impl From<&Multiaddr> for Group {
         Group::None
     }
 }
+
+/// Map from IP prefixes to the autonomous system number announcing them
+///
+/// The file format is one prefix per line, `<cidr> <asn>`, e.g. `1.1.1.0/24 13335`,
+/// the asn may be prefixed by `AS`. Empty lines and lines starting with `#` are ignored.
+#[derive(Default, Debug)]
+pub struct AsnMap {
+    prefixes: HashMap<IpNetwork, u32>,
+    // prefix lengths present in the map, lookup tries the longest one first
+    v4_prefix_lens: BTreeSet<u8>,
+    v6_prefix_lens: BTreeSet<u8>,
+}
+
+impl AsnMap {
+    /// Load the map from a file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, IoError> {
+        let content = std::fs::read_to_string(path)?;
+        let mut asn_map = AsnMap::default();
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = || {
+                IoError::new(
+                    ErrorKind::InvalidData,
+                    format!("invalid asn map entry at line {}: {}", index + 1, line),
+                )
+            };
+            let mut fields = line.split_whitespace();
+            let network = fields
+                .next()
+                .and_then(|network| network.parse::<IpNetwork>().ok())
+                .ok_or_else(invalid)?;
+            let asn = fields
+                .next()
+                .map(|asn| asn.trim_start_matches("AS"))
+                .and_then(|asn| asn.parse::<u32>().ok())
+                .ok_or_else(invalid)?;
+            asn_map.insert(network, asn);
+        }
+        Ok(asn_map)
+    }
+
+    /// Insert a prefix
+    pub fn insert(&mut self, network: IpNetwork, asn: u32) {
+        let prefix = network.prefix();
+        let network = IpNetwork::new(network.network(), prefix).expect("valid prefix");
+        match network {
+            IpNetwork::V4(_) => self.v4_prefix_lens.insert(prefix),
+            IpNetwork::V6(_) => self.v6_prefix_lens.insert(prefix),
+        };
+        self.prefixes.insert(network, asn);
+    }
+
+    /// Find the asn of the longest prefix containing the ip
+    pub fn lookup(&self, ip: IpAddr) -> Option<u32> {
+        let prefix_lens = match ip {
+            IpAddr::V4(_) => &self.v4_prefix_lens,
+            IpAddr::V6(_) => &self.v6_prefix_lens,
+        };
+        prefix_lens.iter().rev().find_map(|&prefix| {
+            IpNetwork::new(ip, prefix)
+                .and_then(|network| IpNetwork::new(network.network(), prefix))
+                .ok()
+                .and_then(|network| self.prefixes.get(&network).cloned())
+        })
+    }
+
+    /// The count of prefixes
+    pub fn len(&self) -> usize {
+        self.prefixes.len()
+    }
+
+    /// Whether the map is empty
+    pub fn is_empty(&self) -> bool {
+        self.prefixes.is_empty()
+    }
+}
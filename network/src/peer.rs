@@ -1,9 +1,14 @@
-use crate::network_group::Group;
+use crate::network_group::{AsnMap, Group};
 use crate::{
-    multiaddr::Multiaddr, protocols::identify::Flags, ProtocolId, ProtocolVersion, SessionType,
+    bandwidth::{Traffic, UploadQueue},
+    behaviour::{MisbehaviorScore, Penalty},
+    multiaddr::Multiaddr,
+    protocols::identify::Flags,
+    ProtocolId, ProtocolVersion, SessionType,
 };
 use p2p::SessionId;
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 /// Peer info from identify protocol message
@@ -28,6 +33,8 @@ pub struct Peer {
     pub last_ping_protocol_message_received_at: Option<Instant>,
     /// ping pong rtt
     pub ping_rtt: Option<Duration>,
+    /// Last time this peer relayed a new block to us
+    pub last_block_relayed_at: Option<Instant>,
     /// Indicates whether it is a probe connection of the fleer protocol
     pub is_feeler: bool,
     /// Peer connected time
@@ -42,6 +49,17 @@ pub struct Peer {
     pub is_whitelist: bool,
     /// Whether the remote peer is a light client, and it subscribes the chain state.
     pub if_lightclient_subscribed: bool,
+    /// Decaying score of the offenses reported by the protocol handlers, the peer is
+    /// disconnected or banned by it
+    pub misbehavior: MisbehaviorScore,
+    /// Decaying scores of the offenses by protocol, the protocol is throttled by its own score
+    pub protocol_misbehavior: HashMap<ProtocolId, MisbehaviorScore>,
+    /// Last time a message was let through by throttled protocol
+    pub last_throttled_message_at: HashMap<ProtocolId, Instant>,
+    /// Bytes of the messages exchanged with the peer
+    pub traffic: Arc<Traffic>,
+    /// Messages to the peer delayed by the upload limits
+    pub(crate) upload_queue: Arc<UploadQueue>,
 }
 
 impl Peer {
@@ -58,6 +76,7 @@ impl Peer {
             identify_info: None,
             ping_rtt: None,
             last_ping_protocol_message_received_at: None,
+            last_block_relayed_at: None,
             connected_time: Instant::now(),
             is_feeler: false,
             session_id,
@@ -65,9 +84,22 @@ impl Peer {
             protocols: HashMap::with_capacity_and_hasher(1, Default::default()),
             is_whitelist,
             if_lightclient_subscribed: false,
+            misbehavior: MisbehaviorScore::default(),
+            protocol_misbehavior: HashMap::default(),
+            last_throttled_message_at: HashMap::default(),
+            traffic: Arc::new(Traffic::default()),
+            upload_queue: Arc::new(UploadQueue::default()),
         }
     }
 
+    /// Whether the protocol is throttled for the misbehavior of the peer in it
+    pub fn is_throttled(&self, proto_id: ProtocolId, now: Instant) -> bool {
+        self.protocol_misbehavior
+            .get(&proto_id)
+            .map(|misbehavior| misbehavior.penalty(now) >= Penalty::Throttle)
+            .unwrap_or(false)
+    }
+
     /// Whether outbound session
     pub fn is_outbound(&self) -> bool {
         self.session_type.is_outbound()
@@ -79,8 +111,8 @@ impl Peer {
     }
 
     /// Get net group
-    pub fn network_group(&self) -> Group {
-        (&self.connected_addr).into()
+    pub fn network_group(&self, asn_map: Option<&AsnMap>) -> Group {
+        Group::new(&self.connected_addr, asn_map)
     }
 
     /// Opened protocol version
@@ -1,16 +1,23 @@
 //! Peer registry
+use crate::behaviour::{Penalty, THROTTLED_MESSAGE_INTERVAL};
 use crate::peer_store::PeerStore;
 use crate::{
     errors::{Error, PeerError},
-    extract_peer_id, Peer, PeerId, SessionType,
+    extract_peer_id, multiaddr_to_socketaddr, Behaviour, MisbehaviorScore, Peer, PeerId,
+    ProtocolId, SessionType,
 };
 use ckb_logger::debug;
 use p2p::{multiaddr::Multiaddr, SessionId};
 use rand::seq::SliceRandom;
 use rand::thread_rng;
 use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
+use std::time::Instant;
 
 pub(crate) const EVICTION_PROTECT_PEERS: usize = 8;
+pub(crate) const EVICTION_PROTECT_BLOCK_RELAY_PEERS: usize = 4;
+// The decayed misbehavior scores of disconnected peers are kept up to this count
+const MAX_DISCONNECTED_MISBEHAVIOR_SCORES: usize = 1024;
 
 /// Memory records of opened session information
 pub struct PeerRegistry {
@@ -21,8 +28,14 @@ pub struct PeerRegistry {
     max_outbound: u32,
     // Only whitelist peers or allow all peers.
     whitelist_only: bool,
+    // The trusted peers are included
     whitelist_peers: HashSet<PeerId>,
+    // The peer ids of the trusted peers pinned to their IPs, and the IPs pinned to the peer ids
+    trusted_peer_ids: HashMap<IpAddr, HashSet<PeerId>>,
+    trusted_peer_ips: HashMap<PeerId, HashSet<IpAddr>>,
     feeler_peers: HashSet<PeerId>,
+    // The misbehavior scores of disconnected peers, restored when they connect again
+    misbehavior_scores: HashMap<IpAddr, MisbehaviorScore>,
 }
 
 /// Global network connection status
@@ -57,17 +70,51 @@ impl PeerRegistry {
         max_outbound: u32,
         whitelist_only: bool,
         whitelist_peers: Vec<Multiaddr>,
+        trusted_peers: Vec<Multiaddr>,
     ) -> Self {
+        let mut trusted_peer_ids: HashMap<IpAddr, HashSet<PeerId>> = HashMap::default();
+        let mut trusted_peer_ips: HashMap<PeerId, HashSet<IpAddr>> = HashMap::default();
+        for addr in &trusted_peers {
+            if let (Some(socket_addr), Some(peer_id)) =
+                (multiaddr_to_socketaddr(addr), extract_peer_id(addr))
+            {
+                trusted_peer_ids
+                    .entry(socket_addr.ip())
+                    .or_default()
+                    .insert(peer_id.clone());
+                trusted_peer_ips
+                    .entry(peer_id)
+                    .or_default()
+                    .insert(socket_addr.ip());
+            }
+        }
         PeerRegistry {
             peers: HashMap::with_capacity_and_hasher(20, Default::default()),
             whitelist_peers: whitelist_peers.iter().filter_map(extract_peer_id).collect(),
+            trusted_peer_ids,
+            trusted_peer_ips,
             feeler_peers: HashSet::default(),
+            misbehavior_scores: HashMap::default(),
             max_inbound,
             max_outbound,
             whitelist_only,
         }
     }
 
+    // A connection from the IP of a trusted peer must have a peer id pinned to the IP, and a trusted
+    // peer must connect from an IP pinned to its peer id
+    fn is_trusted_peer_pinned(&self, remote_addr: &Multiaddr, peer_id: &PeerId) -> bool {
+        let ip = multiaddr_to_socketaddr(remote_addr).map(|socket_addr| socket_addr.ip());
+        let ip_pinned = ip
+            .and_then(|ip| self.trusted_peer_ids.get(&ip))
+            .map_or(true, |peer_ids| peer_ids.contains(peer_id));
+        let peer_id_pinned = self
+            .trusted_peer_ips
+            .get(peer_id)
+            .map_or(true, |ips| ip.map_or(false, |ip| ips.contains(&ip)));
+        ip_pinned && peer_id_pinned
+    }
+
     pub(crate) fn accept_peer(
         &mut self,
         remote_addr: Multiaddr,
@@ -82,6 +129,9 @@ impl PeerRegistry {
         if self.get_key_by_peer_id(&peer_id).is_some() {
             return Err(PeerError::PeerIdExists(peer_id).into());
         }
+        if !self.is_trusted_peer_pinned(&remote_addr, &peer_id) {
+            return Err(PeerError::UntrustedPeerId(peer_id).into());
+        }
 
         let is_whitelist = self.whitelist_peers.contains(&peer_id);
         let mut evicted_peer: Option<Peer> = None;
@@ -109,13 +159,18 @@ impl PeerRegistry {
             }
         }
         peer_store.add_connected_peer(remote_addr.clone(), session_type);
-        let peer = Peer::new(session_id, session_type, remote_addr, is_whitelist);
+        let mut peer = Peer::new(session_id, session_type, remote_addr, is_whitelist);
+        if let Some(misbehavior) = multiaddr_to_socketaddr(&peer.connected_addr)
+            .and_then(|socket_addr| self.misbehavior_scores.remove(&socket_addr.ip()))
+        {
+            peer.misbehavior = misbehavior;
+        }
         self.peers.insert(session_id, peer);
         Ok(evicted_peer)
     }
 
     // try to evict an inbound peer
-    fn try_evict_inbound_peer(&self, _peer_store: &PeerStore) -> Option<SessionId> {
+    fn try_evict_inbound_peer(&self, peer_store: &PeerStore) -> Option<SessionId> {
         let mut candidate_peers = {
             self.peers
                 .values()
@@ -157,6 +212,21 @@ impl PeerRegistry {
                 peer2_last_message.cmp(&peer1_last_message)
             },
         );
+        // Protect peers which most recently relayed new blocks to us
+        let block_relay_peers: HashSet<SessionId> = {
+            let mut peers = candidate_peers
+                .iter()
+                .filter_map(|peer| peer.last_block_relayed_at.map(|t| (t, peer.session_id)))
+                .collect::<Vec<_>>();
+            peers.sort_by(|peer1, peer2| peer2.0.cmp(&peer1.0));
+            peers
+                .into_iter()
+                .take(EVICTION_PROTECT_BLOCK_RELAY_PEERS)
+                .map(|(_, session_id)| session_id)
+                .collect()
+        };
+        candidate_peers.retain(|peer| !block_relay_peers.contains(&peer.session_id));
+
         // Protect half peers which have the longest connection time
         let protect_peers = candidate_peers.len() >> 1;
         sort_then_drop(&mut candidate_peers, protect_peers, |peer1, peer2| {
@@ -168,7 +238,7 @@ impl PeerRegistry {
             .into_iter()
             .fold(HashMap::new(), |mut groups, peer| {
                 groups
-                    .entry(peer.network_group())
+                    .entry(peer.network_group(peer_store.addr_manager().asn_map()))
                     .or_insert_with(Vec::new)
                     .push(peer);
                 groups
@@ -218,7 +288,72 @@ impl PeerRegistry {
     }
 
     pub(crate) fn remove_peer(&mut self, session_id: SessionId) -> Option<Peer> {
-        self.peers.remove(&session_id)
+        let peer = self.peers.remove(&session_id)?;
+        let now = Instant::now();
+        if peer.misbehavior.score(now) > 0 {
+            if let Some(socket_addr) = multiaddr_to_socketaddr(&peer.connected_addr) {
+                if self.misbehavior_scores.len() >= MAX_DISCONNECTED_MISBEHAVIOR_SCORES {
+                    self.misbehavior_scores
+                        .retain(|_, misbehavior| misbehavior.score(now) > 0);
+                }
+                if self.misbehavior_scores.len() < MAX_DISCONNECTED_MISBEHAVIOR_SCORES {
+                    self.misbehavior_scores
+                        .insert(socket_addr.ip(), peer.misbehavior);
+                }
+            }
+        }
+        Some(peer)
+    }
+
+    /// Add the offense to the misbehavior scores of the peer and of the protocol, returns the
+    /// penalty of the new scores: the peer is disconnected or banned by its score, while only the
+    /// offending protocol is throttled by the protocol score.
+    ///
+    /// Returns None if the peer is not found or whitelisted.
+    pub fn report(
+        &mut self,
+        session_id: SessionId,
+        proto_id: ProtocolId,
+        behaviour: Behaviour,
+        now: Instant,
+    ) -> Option<Penalty> {
+        self.peers
+            .get_mut(&session_id)
+            .filter(|peer| !peer.is_whitelist)
+            .map(|peer| {
+                let penalty = peer.misbehavior.report(behaviour, now);
+                let protocol_penalty = peer
+                    .protocol_misbehavior
+                    .entry(proto_id)
+                    .or_default()
+                    .report(behaviour, now);
+                if penalty >= Penalty::Disconnect {
+                    penalty
+                } else {
+                    protocol_penalty
+                }
+            })
+    }
+
+    /// Whether to drop the message of the throttled protocol from the peer, the protocol gets at
+    /// most one message through every `THROTTLED_MESSAGE_INTERVAL`.
+    pub fn throttle(&mut self, session_id: SessionId, proto_id: ProtocolId, now: Instant) -> bool {
+        match self.peers.get_mut(&session_id) {
+            Some(peer) if peer.is_throttled(proto_id, now) => {
+                match peer.last_throttled_message_at.get(&proto_id) {
+                    Some(last)
+                        if now.saturating_duration_since(*last) < THROTTLED_MESSAGE_INTERVAL =>
+                    {
+                        true
+                    }
+                    _ => {
+                        peer.last_throttled_message_at.insert(proto_id, now);
+                        false
+                    }
+                }
+            }
+            _ => false,
+        }
     }
 
     /// Get session id by peer id
@@ -1,47 +1,208 @@
 //! Address manager
+//!
+//! Addresses are kept in two tables, like bitcoin's addrman: the `new` table holds addresses
+//! we have only heard of, bucketed by the network group of the peer that told us about them,
+//! and the `tried` table holds addresses we have connected to, bucketed by their own group.
+//! A single source group can only fill a few `new` buckets and a single network group a few
+//! `tried` buckets, so an attacker controlling a few subnets can't flush out honest addresses.
+//! The addresses without a source, which are learnt by ourselves or persisted before the sources
+//! were recorded, are not limited by the bucket size.
+use crate::network_group::{AsnMap, Group};
 use crate::peer_store::types::AddrInfo;
 use p2p::{multiaddr::Multiaddr, utils::multiaddr_to_socketaddr};
 use rand::Rng;
-use std::collections::{HashMap, HashSet};
+use std::collections::{hash_map::DefaultHasher, HashMap, HashSet};
+use std::hash::{Hash, Hasher};
 use std::net::SocketAddr;
 
+/// Buckets count of the new table
+pub(crate) const NEW_BUCKET_COUNT: u64 = 1024;
+/// Buckets count of the tried table
+pub(crate) const TRIED_BUCKET_COUNT: u64 = 256;
+/// Max addresses in a bucket
+pub(crate) const BUCKET_SIZE: usize = 64;
+/// How many new buckets the addresses told by a single source group can spread over
+const NEW_BUCKETS_PER_SOURCE_GROUP: u64 = 64;
+/// How many tried buckets the addresses of a single network group can spread over
+const TRIED_BUCKETS_PER_GROUP: u64 = 8;
+
+/// Address table
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq)]
+pub enum Table {
+    /// Addresses never connected
+    New,
+    /// Addresses connected at least once
+    Tried,
+}
+
 /// Address manager
-#[derive(Default)]
 pub struct AddrManager {
     next_id: u64,
     addr_to_id: HashMap<SocketAddr, u64>,
     id_to_info: HashMap<u64, AddrInfo>,
     random_ids: Vec<u64>,
+    // secret key of the bucket hash, so remote peers can't predict the bucket positions
+    key: u64,
+    buckets: HashMap<(Table, u64), HashSet<u64>>,
+    id_to_bucket: HashMap<u64, (Table, u64)>,
+    asn_map: Option<AsnMap>,
+}
+
+impl Default for AddrManager {
+    fn default() -> Self {
+        AddrManager {
+            next_id: 0,
+            addr_to_id: HashMap::default(),
+            id_to_info: HashMap::default(),
+            random_ids: Vec::new(),
+            key: rand::random(),
+            buckets: HashMap::default(),
+            id_to_bucket: HashMap::default(),
+            asn_map: None,
+        }
+    }
 }
 
 impl AddrManager {
+    /// Init an address manager with the secret key of the bucket hash
+    pub fn with_key(key: u64) -> Self {
+        AddrManager {
+            key,
+            ..Default::default()
+        }
+    }
+
+    /// The secret key of the bucket hash, it's persisted so the buckets are kept after restarts
+    pub fn key(&self) -> u64 {
+        self.key
+    }
+
     /// Add an address information to address manager
     pub fn add(&mut self, mut addr_info: AddrInfo) {
         if let Some(key) = multiaddr_to_socketaddr(&addr_info.addr) {
             if let Some(&id) = self.addr_to_id.get(&key) {
-                let (exist_last_connected_at_ms, random_id_pos) = {
+                let (exist_last_connected_at_ms, random_id_pos, exist_source) = {
                     let info = self.id_to_info.get(&id).expect("must exists");
-                    (info.last_connected_at_ms, info.random_id_pos)
+                    (
+                        info.last_connected_at_ms,
+                        info.random_id_pos,
+                        info.source.clone(),
+                    )
                 };
                 // Get time earlier than record time, return directly
                 if addr_info.last_connected_at_ms >= exist_last_connected_at_ms {
                     addr_info.random_id_pos = random_id_pos;
+                    if addr_info.source.is_none() {
+                        addr_info.source = exist_source;
+                    }
+                    let connected = addr_info.last_connected_at_ms > 0;
                     self.id_to_info.insert(id, addr_info);
+                    if connected {
+                        self.move_to_tried(id);
+                    }
                 }
                 return;
             }
 
+            let (table, bucket) = if addr_info.last_connected_at_ms > 0 {
+                (Table::Tried, self.tried_bucket(&key, &addr_info.addr))
+            } else {
+                (Table::New, self.new_bucket(&addr_info))
+            };
+            if !self.make_room(table, bucket) && addr_info.source.is_some() {
+                return;
+            }
+
             let id = self.next_id;
             self.addr_to_id.insert(key, id);
             addr_info.random_id_pos = self.random_ids.len();
             self.id_to_info.insert(id, addr_info);
             self.random_ids.push(id);
+            self.insert_to_bucket(id, table, bucket);
             self.next_id += 1;
         }
     }
 
+    /// Mark an address as connected, which moves it into the tried table
+    pub fn mark_connected(&mut self, addr: &Multiaddr, connected_at_ms: u64) {
+        if let Some(&id) = multiaddr_to_socketaddr(addr).and_then(|addr| self.addr_to_id.get(&addr))
+        {
+            self.id_to_info
+                .get_mut(&id)
+                .expect("exists")
+                .mark_connected(connected_at_ms);
+            self.move_to_tried(id);
+        }
+    }
+
+    /// Set the ASN map used to group addresses, all addresses are re-bucketed
+    pub fn set_asn_map(&mut self, asn_map: AsnMap) {
+        self.asn_map = Some(asn_map);
+        let mut infos: Vec<_> = self.id_to_info.drain().collect();
+        infos.sort_unstable_by_key(|(id, _)| *id);
+        self.addr_to_id.clear();
+        self.random_ids.clear();
+        self.buckets.clear();
+        self.id_to_bucket.clear();
+        for (_, info) in infos {
+            self.add(info);
+        }
+    }
+
+    /// Get the ASN map
+    pub fn asn_map(&self) -> Option<&AsnMap> {
+        self.asn_map.as_ref()
+    }
+
+    /// The network group of an address
+    pub fn group(&self, addr: &Multiaddr) -> Group {
+        Group::new(addr, self.asn_map.as_ref())
+    }
+
+    /// The table an address is in
+    pub fn table(&self, addr: &Multiaddr) -> Option<Table> {
+        multiaddr_to_socketaddr(addr)
+            .and_then(|addr| self.addr_to_id.get(&addr))
+            .and_then(|id| self.id_to_bucket.get(id))
+            .map(|(table, _)| *table)
+    }
+
+    /// The count of address in the table
+    pub fn table_count(&self, table: Table) -> usize {
+        self.id_to_bucket
+            .values()
+            .filter(|(t, _)| *t == table)
+            .count()
+    }
+
     /// Randomly return addrs that worth to try or connect.
     pub fn fetch_random<F>(&mut self, count: usize, filter: F) -> Vec<AddrInfo>
+    where
+        F: Fn(&AddrInfo) -> bool,
+    {
+        self.fetch_random_inner(count, None, filter)
+    }
+
+    /// Randomly return addrs like `fetch_random`, but at most one addr per network group,
+    /// and none in `excluded_groups`.
+    pub fn fetch_random_diverse<F>(
+        &mut self,
+        count: usize,
+        excluded_groups: HashSet<Group>,
+        filter: F,
+    ) -> Vec<AddrInfo>
+    where
+        F: Fn(&AddrInfo) -> bool,
+    {
+        self.fetch_random_inner(count, Some(excluded_groups), filter)
+    }
+
+    fn fetch_random_inner<F>(
+        &mut self,
+        count: usize,
+        mut used_groups: Option<HashSet<Group>>,
+        filter: F,
+    ) -> Vec<AddrInfo>
     where
         F: Fn(&AddrInfo) -> bool,
     {
@@ -58,14 +219,22 @@ impl AddrManager {
             if let Some(socket_addr) = multiaddr_to_socketaddr(&addr_info.addr) {
                 let ip = socket_addr.ip();
                 let is_unique_ip = !duplicate_ips.contains(&ip);
+                let group = self.group(&addr_info.addr);
+                let is_unique_group = used_groups
+                    .as_ref()
+                    .map(|groups| !groups.contains(&group))
+                    .unwrap_or(true);
                 // A trick to make our tests work
                 // TODO remove this after fix the network tests.
                 let is_test_ip = ip.is_unspecified() || ip.is_loopback();
-                if (is_test_ip || is_unique_ip)
+                if (is_test_ip || (is_unique_ip && is_unique_group))
                     && addr_info.is_connectable(now_ms)
                     && filter(&addr_info)
                 {
                     duplicate_ips.insert(ip);
+                    if let Some(groups) = used_groups.as_mut() {
+                        groups.insert(group);
+                    }
                     addr_infos.push(addr_info);
                 }
                 if addr_infos.len() == count {
@@ -89,16 +258,26 @@ impl AddrManager {
     /// Remove an address by ip and port
     pub fn remove(&mut self, addr: &Multiaddr) -> Option<AddrInfo> {
         multiaddr_to_socketaddr(addr).and_then(|addr| {
-            self.addr_to_id.remove(&addr).and_then(|id| {
-                let random_id_pos = self.id_to_info.get(&id).expect("exists").random_id_pos;
-                // swap with last index, then remove the last index
-                self.swap_random_id(random_id_pos, self.random_ids.len() - 1);
-                self.random_ids.pop();
-                self.id_to_info.remove(&id)
-            })
+            self.addr_to_id
+                .get(&addr)
+                .cloned()
+                .and_then(|id| self.remove_by_id(id))
         })
     }
 
+    fn remove_by_id(&mut self, id: u64) -> Option<AddrInfo> {
+        let info = self.id_to_info.get(&id)?;
+        if let Some(addr) = multiaddr_to_socketaddr(&info.addr) {
+            self.addr_to_id.remove(&addr);
+        }
+        let random_id_pos = info.random_id_pos;
+        // swap with last index, then remove the last index
+        self.swap_random_id(random_id_pos, self.random_ids.len() - 1);
+        self.random_ids.pop();
+        self.remove_from_bucket(id);
+        self.id_to_info.remove(&id)
+    }
+
     /// Get an address information by ip and port
     pub fn get(&self, addr: &Multiaddr) -> Option<&AddrInfo> {
         multiaddr_to_socketaddr(addr).and_then(|addr| {
@@ -121,6 +300,103 @@ impl AddrManager {
         }
     }
 
+    /// Compute the new bucket of an address from its group and the group of its source
+    fn new_bucket(&self, addr_info: &AddrInfo) -> u64 {
+        let group = self.group(&addr_info.addr);
+        let source_group = addr_info
+            .source
+            .as_ref()
+            .map(|source| self.group(source))
+            .unwrap_or(group);
+        let slot = self.keyed_hash((group, source_group)) % NEW_BUCKETS_PER_SOURCE_GROUP;
+        self.keyed_hash((source_group, slot)) % NEW_BUCKET_COUNT
+    }
+
+    /// Compute the tried bucket of an address from its ip, port and group
+    fn tried_bucket(&self, socket_addr: &SocketAddr, addr: &Multiaddr) -> u64 {
+        let slot = self.keyed_hash(socket_addr) % TRIED_BUCKETS_PER_GROUP;
+        self.keyed_hash((self.group(addr), slot)) % TRIED_BUCKET_COUNT
+    }
+
+    fn keyed_hash<T: Hash>(&self, value: T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.key.hash(&mut hasher);
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Make sure the bucket has a free slot, return false if the new address should be dropped.
+    ///
+    /// An address which is no longer connectable is evicted first. Otherwise a full tried bucket
+    /// moves its oldest connected address back to the new table, while a full new bucket keeps
+    /// its addresses and drops the incoming one.
+    fn make_room(&mut self, table: Table, bucket: u64) -> bool {
+        let ids = match self.buckets.get(&(table, bucket)) {
+            Some(ids) if ids.len() >= BUCKET_SIZE => ids,
+            _ => return true,
+        };
+        let now_ms = ckb_systemtime::unix_time_as_millis();
+        let terrible = ids
+            .iter()
+            .find(|id| !self.id_to_info[id].is_connectable(now_ms))
+            .cloned();
+        if let Some(id) = terrible {
+            self.remove_by_id(id);
+            return true;
+        }
+        if table == Table::New {
+            return false;
+        }
+        let oldest = ids
+            .iter()
+            .min_by_key(|id| self.id_to_info[id].last_connected_at_ms)
+            .cloned()
+            .expect("bucket is full");
+        let new_bucket = self.new_bucket(&self.id_to_info[&oldest]);
+        let has_room = self
+            .buckets
+            .get(&(Table::New, new_bucket))
+            .map(|ids| ids.len() < BUCKET_SIZE)
+            .unwrap_or(true);
+        if has_room {
+            self.remove_from_bucket(oldest);
+            self.insert_to_bucket(oldest, Table::New, new_bucket);
+        } else {
+            self.remove_by_id(oldest);
+        }
+        true
+    }
+
+    fn move_to_tried(&mut self, id: u64) {
+        if let Some((Table::Tried, _)) = self.id_to_bucket.get(&id) {
+            return;
+        }
+        let info = &self.id_to_info[&id];
+        let bucket = match multiaddr_to_socketaddr(&info.addr) {
+            Some(socket_addr) => self.tried_bucket(&socket_addr, &info.addr),
+            None => return,
+        };
+        self.remove_from_bucket(id);
+        self.make_room(Table::Tried, bucket);
+        self.insert_to_bucket(id, Table::Tried, bucket);
+    }
+
+    fn insert_to_bucket(&mut self, id: u64, table: Table, bucket: u64) {
+        self.buckets.entry((table, bucket)).or_default().insert(id);
+        self.id_to_bucket.insert(id, (table, bucket));
+    }
+
+    fn remove_from_bucket(&mut self, id: u64) {
+        if let Some(position) = self.id_to_bucket.remove(&id) {
+            if let Some(ids) = self.buckets.get_mut(&position) {
+                ids.remove(&id);
+                if ids.is_empty() {
+                    self.buckets.remove(&position);
+                }
+            }
+        }
+    }
+
     /// swap random_id i and j,
     /// this function keep random_id_pos in consistency
     fn swap_random_id(&mut self, i: usize, j: usize) {
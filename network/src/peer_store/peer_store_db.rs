@@ -10,18 +10,19 @@ use crate::{
 use ckb_logger::{debug, error};
 use std::path::Path;
 use std::{
-    fs::{copy, create_dir_all, remove_file, rename, File, OpenOptions},
+    fs::{copy, create_dir_all, read_to_string, remove_file, rename, File, OpenOptions},
     io::{Read, Write},
 };
 
 const DEFAULT_ADDR_MANAGER_DB: &str = "addr_manager.db";
+const DEFAULT_ADDR_MANAGER_KEY: &str = "addr_manager.key";
 const DEFAULT_BAN_LIST_DB: &str = "ban_list.db";
 
 impl AddrManager {
-    /// Load address list from disk
-    pub fn load<R: Read>(r: R) -> Result<Self, Error> {
+    /// Load address list from disk, the addresses are bucketed by the persisted key if any
+    pub fn load<R: Read>(r: R, key: Option<u64>) -> Result<Self, Error> {
         let addrs: Vec<AddrInfo> = serde_json::from_reader(r).map_err(PeerStoreError::Serde)?;
-        let mut addr_manager = AddrManager::default();
+        let mut addr_manager = key.map(AddrManager::with_key).unwrap_or_default();
         addrs.into_iter().for_each(|addr| addr_manager.add(addr));
         Ok(addr_manager)
     }
@@ -37,6 +38,14 @@ impl AddrManager {
             .and_then(|_| file.sync_all())
             .map_err(Into::into)
     }
+
+    /// Dump the key of the bucket hash to disk
+    pub fn dump_key(&self, mut file: File) -> Result<(), Error> {
+        file.set_len(0)
+            .and_then(|_| file.write_all(self.key().to_string().as_bytes()))
+            .and_then(|_| file.sync_all())
+            .map_err(Into::into)
+    }
 }
 
 impl BanList {
@@ -68,8 +77,12 @@ impl PeerStore {
     /// Init peer store from disk
     pub fn load_from_dir_or_default<P: AsRef<Path>>(path: P) -> Self {
         let addr_manager_path = path.as_ref().join(DEFAULT_ADDR_MANAGER_DB);
+        let addr_manager_key_path = path.as_ref().join(DEFAULT_ADDR_MANAGER_KEY);
         let ban_list_path = path.as_ref().join(DEFAULT_BAN_LIST_DB);
 
+        let addr_manager_key = read_to_string(addr_manager_key_path)
+            .ok()
+            .and_then(|key| key.trim().parse().ok());
         let addr_manager = File::open(&addr_manager_path)
             .map_err(|err| {
                 debug!(
@@ -78,7 +91,7 @@ impl PeerStore {
                 )
             })
             .and_then(|file| {
-                AddrManager::load(std::io::BufReader::new(file)).map_err(|err| {
+                AddrManager::load(std::io::BufReader::new(file), addr_manager_key).map_err(|err| {
                     error!(
                         "Failed to load AddrManager db, file: {:?}, error: {:?}",
                         addr_manager_path, err
@@ -115,7 +128,19 @@ impl PeerStore {
         let tmp_dir = path.as_ref().join("tmp");
         create_dir_all(&tmp_dir)?;
         let tmp_addr_manager = tmp_dir.join(DEFAULT_ADDR_MANAGER_DB);
+        let tmp_addr_manager_key = tmp_dir.join(DEFAULT_ADDR_MANAGER_KEY);
         let tmp_ban_list = tmp_dir.join(DEFAULT_BAN_LIST_DB);
+        self.addr_manager().dump_key(
+            OpenOptions::new()
+                .write(true)
+                .create(true)
+                .append(false)
+                .open(&tmp_addr_manager_key)?,
+        )?;
+        move_file(
+            tmp_addr_manager_key,
+            path.as_ref().join(DEFAULT_ADDR_MANAGER_KEY),
+        )?;
         self.addr_manager().dump(
             OpenOptions::new()
                 .write(true)
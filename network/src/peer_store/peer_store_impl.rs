@@ -1,7 +1,7 @@
 use crate::{
     errors::{PeerStoreError, Result},
     extract_peer_id, multiaddr_to_socketaddr,
-    network_group::Group,
+    network_group::{AsnMap, Group},
     peer_store::{
         addr_manager::AddrManager,
         ban_list::BanList,
@@ -71,6 +71,24 @@ impl PeerStore {
         Ok(())
     }
 
+    /// Add a peer address relayed by another peer, the address is bucketed by the source's
+    /// network group so a single source can't fill the peer store.
+    pub fn add_addr_from(
+        &mut self,
+        addr: Multiaddr,
+        flags: Flags,
+        source: Multiaddr,
+    ) -> Result<()> {
+        if self.ban_list.is_addr_banned(&addr) {
+            return Ok(());
+        }
+        self.check_purge()?;
+        let score = self.score_config.default_score;
+        self.addr_manager
+            .add(AddrInfo::new(addr, 0, score, flags.bits()).with_source(source));
+        Ok(())
+    }
+
     /// Add outbound peer address
     pub fn add_outbound_addr(&mut self, addr: Multiaddr, flags: Flags) {
         if self.ban_list.is_addr_banned(&addr) {
@@ -90,9 +108,13 @@ impl PeerStore {
         if self.ban_list.is_addr_banned(&addr) {
             return;
         }
-        if let Some(info) = self.addr_manager.get_mut(&addr) {
-            info.last_connected_at_ms = ckb_systemtime::unix_time_as_millis()
-        }
+        self.addr_manager
+            .mark_connected(&addr, ckb_systemtime::unix_time_as_millis());
+    }
+
+    /// Group addresses by the autonomous system announcing them
+    pub fn set_asn_map(&mut self, asn_map: AsnMap) {
+        self.addr_manager.set_asn_map(asn_map);
     }
 
     /// Get address manager
@@ -141,13 +163,19 @@ impl PeerStore {
         // Get info:
         // 1. Not already connected
         // 2. Connected within 3 days
+        // 3. Not in the same network group with other outbound peers
 
         let now_ms = ckb_systemtime::unix_time_as_millis();
         let peers = &self.connected_peers;
         let addr_expired_ms = now_ms.saturating_sub(ADDR_TRY_TIMEOUT_MS);
+        let outbound_groups = peers
+            .values()
+            .filter(|peer| peer.session_type.is_outbound())
+            .map(|peer| self.addr_manager.group(&peer.connected_addr))
+            .collect();
         // get addrs that can attempt.
         self.addr_manager
-            .fetch_random(count, |peer_addr: &AddrInfo| {
+            .fetch_random_diverse(count, outbound_groups, |peer_addr: &AddrInfo| {
                 extract_peer_id(&peer_addr.addr)
                     .map(|peer_id| !peers.contains_key(&peer_id))
                     .unwrap_or_default()
@@ -274,7 +302,7 @@ impl PeerStore {
                 let mut peers_by_network_group: HashMap<Group, Vec<_>> = HashMap::default();
                 for addr in self.addr_manager.addrs_iter() {
                     peers_by_network_group
-                        .entry((&addr.addr).into())
+                        .entry(self.addr_manager.group(&addr.addr))
                         .or_default()
                         .push(addr);
                 }
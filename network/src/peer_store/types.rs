@@ -52,6 +52,9 @@ pub struct AddrInfo {
     /// Flags
     #[serde(default = "default_flags")]
     pub flags: u64,
+    /// The peer that told us about this address, `None` if we learnt it ourselves
+    #[serde(default)]
+    pub source: Option<Multiaddr>,
 }
 
 fn default_flags() -> u64 {
@@ -69,9 +72,16 @@ impl AddrInfo {
             attempts_count: 0,
             random_id_pos: 0,
             flags,
+            source: None,
         }
     }
 
+    /// Set the peer that told us about this address
+    pub fn with_source(mut self, source: Multiaddr) -> Self {
+        self.source = Some(source);
+        self
+    }
+
     /// Connection information
     pub fn connected<F: FnOnce(u64) -> bool>(&self, f: F) -> bool {
         f(self.last_connected_at_ms)
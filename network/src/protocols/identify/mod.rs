@@ -522,7 +522,9 @@ impl Callback for IdentifyCallback {
         });
         self.network_state.with_peer_store_mut(|peer_store| {
             for addr in addrs {
-                if let Err(err) = peer_store.add_addr(addr.clone(), flags) {
+                if let Err(err) =
+                    peer_store.add_addr_from(addr.clone(), flags, session.address.clone())
+                {
                     error!("IdentifyProtocol failed to add address to peer store, address: {}, error: {:?}", addr, err);
                 }
             }
@@ -639,5 +641,8 @@ bitflags::bitflags! {
         const LIGHT_CLIENT = 0b10000;
         /// Client-side block filter protocol can provide BlockFilter download service
         const BLOCK_FILTER = 0b100000;
+        /// Pruned node, which only provides the bodies of the latest blocks within
+        /// `MIN_PRUNE_RETENTION_DEPTH` for download
+        const PRUNED = 0b1000000;
     }
 }
@@ -16,14 +16,14 @@ use p2p::{
     bytes::Bytes,
     context::{ProtocolContext, ProtocolContextMutRef},
     service::{ProtocolHandle, ProtocolMeta, ServiceAsyncControl, ServiceControl, TargetSession},
-    traits::ServiceProtocol,
+    traits::{ServiceProtocol, SessionProtocol},
     ProtocolId, SessionId,
 };
 use std::{
     pin::Pin,
     sync::Arc,
     task::{Context, Poll},
-    time::Duration,
+    time::{Duration, Instant},
 };
 use tokio_util::codec::length_delimited;
 
@@ -236,6 +236,10 @@ impl CKBProtocol {
     pub fn build(self) -> ProtocolMeta {
         let protocol_name = self.protocol_name();
         let max_frame_length = self.max_frame_length;
+        let proto_id = self.id;
+        let network_state = Arc::clone(&self.network_state);
+        let download_limited = network_state.bandwidth.is_download_limited(proto_id);
+        let upload_limited = network_state.bandwidth.is_upload_limited();
         let supported_versions = self
             .supported_versions
             .iter()
@@ -259,6 +263,16 @@ impl CKBProtocol {
                     handler: self.handler,
                 }))
             })
+            .session_handle(move || {
+                if download_limited || upload_limited {
+                    ProtocolHandle::Callback(Box::new(BandwidthGate {
+                        proto_id,
+                        network_state: Arc::clone(&network_state),
+                    }))
+                } else {
+                    ProtocolHandle::None
+                }
+            })
             .before_send(compress)
             .before_receive(|| Some(Box::new(decompress)))
             .build()
@@ -292,6 +306,8 @@ impl ServiceProtocol for CKBHandler {
             .load(std::sync::atomic::Ordering::SeqCst)
             && version != crate::protocols::support_protocols::LASTEST_VERSION
             && context.proto_id != SupportProtocols::RelayV2.protocol_id()
+            && !(context.proto_id == SupportProtocols::RelayV3.protocol_id()
+                && version == crate::protocols::support_protocols::RELAY_V4_VERSION)
         {
             debug!(
                 "The version of session {}, protocol {} is {}, not 3. It will be disconnected.",
@@ -356,6 +372,25 @@ impl ServiceProtocol for CKBHandler {
             context.session.id,
             data.len()
         );
+        self.network_state
+            .record_received(self.proto_id, context.session.id, data.len());
+        let now = Instant::now();
+        // check by the read lock first, since few peers are throttled
+        if self.network_state.with_peer_registry(|reg| {
+            reg.get_peer(context.session.id)
+                .map(|peer| peer.is_throttled(self.proto_id, now))
+                .unwrap_or(false)
+        }) && self
+            .network_state
+            .with_peer_registry_mut(|reg| reg.throttle(context.session.id, self.proto_id, now))
+        {
+            trace!(
+                "[drop throttled message]: {}, {}",
+                self.proto_id,
+                context.session.id
+            );
+            return;
+        }
         let nc = DefaultCKBProtocolContext {
             proto_id: self.proto_id,
             network_state: Arc::clone(&self.network_state),
@@ -390,6 +425,55 @@ impl ServiceProtocol for CKBHandler {
     }
 }
 
+// Interval to check again whether the upload backlog of a peer is sent
+const UPLOAD_BACKLOG_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+// Pause reading the messages of a session until the download limits allow more transfer, or until
+// the messages delayed to the peer by the upload limits are sent, so a peer is not served more
+// requests while the replies are backlogged. It's a session level handle so the other sessions are
+// not blocked. The service handle still processes the messages already read, while the peer is
+// back pressured once the session buffer fills up.
+struct BandwidthGate {
+    proto_id: ProtocolId,
+    network_state: Arc<NetworkState>,
+}
+
+#[async_trait]
+impl SessionProtocol for BandwidthGate {
+    async fn received(&mut self, context: ProtocolContextMutRef<'_>, _data: Bytes) {
+        let session_id = context.session.id;
+        let delay = self
+            .network_state
+            .bandwidth
+            .download_delay(self.proto_id, Instant::now());
+        if !delay.is_zero() {
+            trace!(
+                "[wait for bandwidth]: {}, {}, delay={}ms",
+                self.proto_id,
+                session_id,
+                delay.as_millis()
+            );
+            tokio::time::sleep(delay).await;
+        }
+
+        let upload_queue = self.network_state.with_peer_registry(|reg| {
+            reg.get_peer(session_id)
+                .map(|peer| Arc::clone(&peer.upload_queue))
+        });
+        if let Some(upload_queue) = upload_queue {
+            while let Some(due) = upload_queue.backlog_due() {
+                trace!(
+                    "[wait for upload backlog]: {}, {}",
+                    self.proto_id,
+                    session_id
+                );
+                let now = Instant::now();
+                tokio::time::sleep_until(due.max(now + UPLOAD_BACKLOG_POLL_INTERVAL).into()).await;
+            }
+        }
+    }
+}
+
 struct DefaultCKBProtocolContext {
     proto_id: ProtocolId,
     network_state: Arc<NetworkState>,
@@ -397,6 +481,35 @@ struct DefaultCKBProtocolContext {
     async_p2p_control: ServiceAsyncControl,
 }
 
+impl DefaultCKBProtocolContext {
+    // Count the message and queue it to the peers whose upload is delayed, returns the peers to send
+    // it right away
+    fn schedule_sent(
+        &self,
+        proto_id: ProtocolId,
+        peers: Vec<PeerIndex>,
+        data: &Bytes,
+        quick: bool,
+    ) -> Vec<PeerIndex> {
+        self.network_state
+            .schedule_sent(&self.p2p_control, proto_id, peers, data, quick)
+    }
+
+    // Resolve the sessions of a broadcast to count the bytes sent to each one
+    fn broadcast_targets(&self, target: TargetSession) -> Vec<PeerIndex> {
+        match target {
+            TargetSession::All => self.connected_peers(),
+            TargetSession::Single(peer_index) => vec![peer_index],
+            TargetSession::Multi(peers) => peers.collect(),
+            TargetSession::Filter(mut filter) => self
+                .connected_peers()
+                .into_iter()
+                .filter(|peer_index| filter(peer_index))
+                .collect(),
+        }
+    }
+}
+
 #[async_trait]
 impl CKBProtocolContext for DefaultCKBProtocolContext {
     fn ckb2023(&self) -> bool {
@@ -428,6 +541,12 @@ impl CKBProtocolContext for DefaultCKBProtocolContext {
             peer_index,
             data.len()
         );
+        if self
+            .schedule_sent(proto_id, vec![peer_index], &data, true)
+            .is_empty()
+        {
+            return Ok(());
+        }
         self.async_p2p_control
             .quick_send_message_to(peer_index, proto_id, data)
             .await?;
@@ -444,6 +563,12 @@ impl CKBProtocolContext for DefaultCKBProtocolContext {
             peer_index,
             data.len()
         );
+        if self
+            .schedule_sent(self.proto_id, vec![peer_index], &data, true)
+            .is_empty()
+        {
+            return Ok(());
+        }
         self.async_p2p_control
             .quick_send_message_to(peer_index, self.proto_id, data)
             .await?;
@@ -454,8 +579,13 @@ impl CKBProtocolContext for DefaultCKBProtocolContext {
         target: TargetSession,
         data: Bytes,
     ) -> Result<(), Error> {
+        let peers = self.schedule_sent(self.proto_id, self.broadcast_targets(target), &data, true);
         self.async_p2p_control
-            .quick_filter_broadcast(target, self.proto_id, data)
+            .quick_filter_broadcast(
+                TargetSession::Multi(Box::new(peers.into_iter())),
+                self.proto_id,
+                data,
+            )
             .await?;
         Ok(())
     }
@@ -480,6 +610,12 @@ impl CKBProtocolContext for DefaultCKBProtocolContext {
             peer_index,
             data.len()
         );
+        if self
+            .schedule_sent(proto_id, vec![peer_index], &data, false)
+            .is_empty()
+        {
+            return Ok(());
+        }
         self.async_p2p_control
             .send_message_to(peer_index, proto_id, data)
             .await?;
@@ -492,6 +628,12 @@ impl CKBProtocolContext for DefaultCKBProtocolContext {
             peer_index,
             data.len()
         );
+        if self
+            .schedule_sent(self.proto_id, vec![peer_index], &data, false)
+            .is_empty()
+        {
+            return Ok(());
+        }
         self.async_p2p_control
             .send_message_to(peer_index, self.proto_id, data)
             .await?;
@@ -502,8 +644,13 @@ impl CKBProtocolContext for DefaultCKBProtocolContext {
         target: TargetSession,
         data: Bytes,
     ) -> Result<(), Error> {
+        let peers = self.schedule_sent(self.proto_id, self.broadcast_targets(target), &data, false);
         self.async_p2p_control
-            .filter_broadcast(target, self.proto_id, data)
+            .filter_broadcast(
+                TargetSession::Multi(Box::new(peers.into_iter())),
+                self.proto_id,
+                data,
+            )
             .await?;
         Ok(())
     }
@@ -524,6 +671,12 @@ impl CKBProtocolContext for DefaultCKBProtocolContext {
             peer_index,
             data.len()
         );
+        if self
+            .schedule_sent(proto_id, vec![peer_index], &data, true)
+            .is_empty()
+        {
+            return Ok(());
+        }
         self.p2p_control
             .quick_send_message_to(peer_index, proto_id, data)?;
         Ok(())
@@ -535,13 +688,23 @@ impl CKBProtocolContext for DefaultCKBProtocolContext {
             peer_index,
             data.len()
         );
+        if self
+            .schedule_sent(self.proto_id, vec![peer_index], &data, true)
+            .is_empty()
+        {
+            return Ok(());
+        }
         self.p2p_control
             .quick_send_message_to(peer_index, self.proto_id, data)?;
         Ok(())
     }
     fn quick_filter_broadcast(&self, target: TargetSession, data: Bytes) -> Result<(), Error> {
-        self.p2p_control
-            .quick_filter_broadcast(target, self.proto_id, data)?;
+        let peers = self.schedule_sent(self.proto_id, self.broadcast_targets(target), &data, true);
+        self.p2p_control.quick_filter_broadcast(
+            TargetSession::Multi(Box::new(peers.into_iter())),
+            self.proto_id,
+            data,
+        )?;
         Ok(())
     }
     fn future_task(&self, task: BoxedFutureTask, blocking: bool) -> Result<(), Error> {
@@ -565,6 +728,12 @@ impl CKBProtocolContext for DefaultCKBProtocolContext {
             peer_index,
             data.len()
         );
+        if self
+            .schedule_sent(proto_id, vec![peer_index], &data, false)
+            .is_empty()
+        {
+            return Ok(());
+        }
         self.p2p_control
             .send_message_to(peer_index, proto_id, data)?;
         Ok(())
@@ -576,13 +745,23 @@ impl CKBProtocolContext for DefaultCKBProtocolContext {
             peer_index,
             data.len()
         );
+        if self
+            .schedule_sent(self.proto_id, vec![peer_index], &data, false)
+            .is_empty()
+        {
+            return Ok(());
+        }
         self.p2p_control
             .send_message_to(peer_index, self.proto_id, data)?;
         Ok(())
     }
     fn filter_broadcast(&self, target: TargetSession, data: Bytes) -> Result<(), Error> {
-        self.p2p_control
-            .filter_broadcast(target, self.proto_id, data)?;
+        let peers = self.schedule_sent(self.proto_id, self.broadcast_targets(target), &data, false);
+        self.p2p_control.filter_broadcast(
+            TargetSession::Multi(Box::new(peers.into_iter())),
+            self.proto_id,
+            data,
+        )?;
         Ok(())
     }
     fn disconnect(&self, peer_index: PeerIndex, message: &str) -> Result<(), Error> {
@@ -618,7 +797,7 @@ impl CKBProtocolContext for DefaultCKBProtocolContext {
     }
     fn report_peer(&self, peer_index: PeerIndex, behaviour: Behaviour) {
         self.network_state
-            .report_session(&self.p2p_control, peer_index, behaviour);
+            .report_session(&self.p2p_control, peer_index, self.proto_id, behaviour);
     }
     fn ban_peer(&self, peer_index: PeerIndex, duration: Duration, reason: String) {
         self.network_state
@@ -7,6 +7,8 @@ use p2p::{
 use tokio_util::codec::length_delimited;
 
 pub const LASTEST_VERSION: &str = "3";
+/// Relay protocol version with salted compact block short ids and high-bandwidth mode
+pub const RELAY_V4_VERSION: &str = "4";
 
 /// All supported protocols
 ///
@@ -56,6 +58,11 @@ pub enum SupportProtocols {
     LightClient,
     /// Filter: A protocol used for client side block data filtering.
     Filter,
+    /// TxReconciliation: A protocol used for announcing transactions by set reconciliation
+    /// instead of flooding, the peers that don't open it are still flooded.
+    ///
+    /// [Erlay: Efficient Transaction Relay for Bitcoin](https://arxiv.org/abs/1905.10518)
+    TxReconciliation,
 }
 
 impl SupportProtocols {
@@ -74,6 +81,7 @@ impl SupportProtocols {
             SupportProtocols::Alert => 110,
             SupportProtocols::LightClient => 120,
             SupportProtocols::Filter => 121,
+            SupportProtocols::TxReconciliation => 104,
         }
         .into()
     }
@@ -93,6 +101,7 @@ impl SupportProtocols {
             SupportProtocols::Alert => "/ckb/alt",
             SupportProtocols::LightClient => "/ckb/lightclient",
             SupportProtocols::Filter => "/ckb/filter",
+            SupportProtocols::TxReconciliation => "/ckb/txrcncl",
         }
         .to_owned()
     }
@@ -114,9 +123,14 @@ impl SupportProtocols {
             SupportProtocols::Time => vec!["2".to_owned(), LASTEST_VERSION.to_owned()],
             SupportProtocols::Alert => vec!["2".to_owned(), LASTEST_VERSION.to_owned()],
             SupportProtocols::RelayV2 => vec!["2".to_owned(), LASTEST_VERSION.to_owned()],
-            SupportProtocols::RelayV3 => vec!["2".to_owned(), LASTEST_VERSION.to_owned()],
+            SupportProtocols::RelayV3 => vec![
+                "2".to_owned(),
+                LASTEST_VERSION.to_owned(),
+                RELAY_V4_VERSION.to_owned(),
+            ],
             SupportProtocols::LightClient => vec!["2".to_owned(), LASTEST_VERSION.to_owned()],
             SupportProtocols::Filter => vec!["2".to_owned(), LASTEST_VERSION.to_owned()],
+            SupportProtocols::TxReconciliation => vec![LASTEST_VERSION.to_owned()],
         }
     }
 
@@ -134,6 +148,7 @@ impl SupportProtocols {
             SupportProtocols::Alert => 128 * 1024,       // 128 KB
             SupportProtocols::LightClient => 2 * 1024 * 1024, // 2 MB
             SupportProtocols::Filter => 2 * 1024 * 1024, // 2   MB
+            SupportProtocols::TxReconciliation => 1024 * 1024, // 1   MB
         }
     }
 
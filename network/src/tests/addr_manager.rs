@@ -1,9 +1,14 @@
 use crate::{
     multiaddr::Multiaddr,
-    peer_store::{addr_manager::AddrManager, types::AddrInfo},
+    network_group::{AsnMap, Group},
+    peer_store::{
+        addr_manager::{AddrManager, Table, BUCKET_SIZE},
+        types::AddrInfo,
+    },
     PeerId,
 };
 use proptest::prelude::*;
+use std::io::Write;
 use std::net::Ipv4Addr;
 
 const MAX_FETCHED_ADDRS: usize = 1000;
@@ -40,3 +45,119 @@ proptest! {
         assert_eq!(addrs.len(), count);
     }
 }
+
+fn addr(ip: &str, port: u16) -> Multiaddr {
+    format!(
+        "/ip4/{}/tcp/{}/p2p/{}",
+        ip,
+        port,
+        PeerId::random().to_base58()
+    )
+    .parse()
+    .unwrap()
+}
+
+#[test]
+fn test_new_bucket_limited_by_source_group() {
+    let mut addr_manager = AddrManager::default();
+    let source: Multiaddr = "/ip4/226.1.0.1/tcp/8115".parse().unwrap();
+    // a single source can only fill one bucket with addrs of a single group
+    for port in 0..(BUCKET_SIZE as u16 * 2) {
+        addr_manager
+            .add(AddrInfo::new(addr("225.0.0.1", port), 0, 0, 0).with_source(source.clone()));
+    }
+    assert_eq!(addr_manager.count(), BUCKET_SIZE);
+
+    // addrs learnt from other sources are still accepted, except the few ones hashed into the
+    // full bucket by chance
+    for port in 0..(BUCKET_SIZE as u16) {
+        let source: Multiaddr = format!("/ip4/{}.0.0.1/tcp/8115", port + 1).parse().unwrap();
+        addr_manager.add(AddrInfo::new(addr("225.0.0.2", port), 0, 0, 0).with_source(source));
+    }
+    assert!(addr_manager.count() > BUCKET_SIZE * 3 / 2);
+    assert_eq!(addr_manager.table_count(Table::New), addr_manager.count());
+}
+
+#[test]
+fn test_new_bucket_not_limited_without_source() {
+    let mut addr_manager = AddrManager::default();
+    // the addrs learnt by ourselves or persisted before the sources were recorded are kept
+    for port in 0..(BUCKET_SIZE as u16 * 2) {
+        addr_manager.add(AddrInfo::new(addr("225.0.0.1", port), 0, 0, 0));
+    }
+    assert_eq!(addr_manager.count(), BUCKET_SIZE * 2);
+    assert_eq!(addr_manager.table_count(Table::New), BUCKET_SIZE * 2);
+
+    // the addrs of a full bucket told by a peer are still dropped
+    let source: Multiaddr = "/ip4/225.0.0.1/tcp/8115".parse().unwrap();
+    addr_manager.add(AddrInfo::new(addr("225.0.0.1", 8115), 0, 0, 0).with_source(source));
+    assert_eq!(addr_manager.count(), BUCKET_SIZE * 2);
+}
+
+#[test]
+fn test_move_to_tried_table() {
+    let mut addr_manager = AddrManager::default();
+    let new_addr = addr("225.0.0.1", 42);
+    let connected_addr = addr("225.0.0.2", 42);
+    addr_manager.add(AddrInfo::new(new_addr.clone(), 0, 0, 0));
+    addr_manager.add(AddrInfo::new(connected_addr.clone(), 1, 0, 0));
+    assert_eq!(addr_manager.table(&new_addr), Some(Table::New));
+    assert_eq!(addr_manager.table(&connected_addr), Some(Table::Tried));
+
+    addr_manager.mark_connected(&new_addr, 2);
+    assert_eq!(addr_manager.table(&new_addr), Some(Table::Tried));
+    assert_eq!(addr_manager.get(&new_addr).unwrap().last_connected_at_ms, 2);
+    assert_eq!(addr_manager.table_count(Table::Tried), 2);
+
+    addr_manager.remove(&new_addr);
+    assert_eq!(addr_manager.table(&new_addr), None);
+    assert_eq!(addr_manager.table_count(Table::Tried), 1);
+}
+
+#[test]
+fn test_group_by_asn() {
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    writeln!(file, "# prefix asn").unwrap();
+    writeln!(file, "10.0.0.0/8 AS100").unwrap();
+    writeln!(file).unwrap();
+    writeln!(file, "10.1.0.0/16 200").unwrap();
+    writeln!(file, "2001:db8::/32 300").unwrap();
+    let asn_map = AsnMap::load(file.path()).unwrap();
+    assert_eq!(asn_map.len(), 3);
+
+    let group = |addr: &str| Group::new(&addr.parse().unwrap(), Some(&asn_map));
+    assert_eq!(group("/ip4/10.1.2.3/tcp/42"), Group::ASN(200));
+    assert_eq!(group("/ip4/10.2.2.3/tcp/42"), Group::ASN(100));
+    assert_eq!(group("/ip6/2001:db8::1/tcp/42"), Group::ASN(300));
+    assert_eq!(group("/ip4/11.2.2.3/tcp/42"), Group::IP4([11, 2]));
+    assert_eq!(group("/ip4/127.0.0.1/tcp/42"), Group::LocalNetwork);
+
+    writeln!(file, "10.2.0.0/16 ASN").unwrap();
+    assert!(AsnMap::load(file.path()).is_err());
+}
+
+#[test]
+fn test_set_asn_map_rebuckets_addrs() {
+    let mut addr_manager = AddrManager::default();
+    for i in 0..10u16 {
+        addr_manager.add(AddrInfo::new(
+            addr(&format!("{}.0.0.1", i + 1), 42),
+            0,
+            0,
+            0,
+        ));
+    }
+    let mut asn_map = AsnMap::default();
+    asn_map.insert("0.0.0.0/0".parse().unwrap(), 1);
+    addr_manager.set_asn_map(asn_map);
+    assert_eq!(addr_manager.count(), 10);
+    assert_eq!(addr_manager.table_count(Table::New), 10);
+    assert_eq!(addr_manager.group(&addr("3.0.0.1", 42)), Group::ASN(1));
+    // all addrs are in the same group now, only one can be returned at a time
+    assert_eq!(
+        addr_manager
+            .fetch_random_diverse(10, Default::default(), |_| true)
+            .len(),
+        1
+    );
+}
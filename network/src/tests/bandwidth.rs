@@ -0,0 +1,85 @@
+use crate::{
+    bandwidth::{Bandwidth, RateLimiter},
+    SupportProtocols,
+};
+use ckb_app_config::BandwidthConfig;
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_rate_limiter() {
+    let now = Instant::now();
+    assert!(RateLimiter::new(0, now).is_none());
+
+    let limiter = RateLimiter::new(1000, now).unwrap();
+    // a burst of one second is allowed
+    limiter.consume(1000, now);
+    assert_eq!(limiter.delay(now), Duration::ZERO);
+
+    // the debt is paid off at the rate
+    limiter.consume(500, now);
+    assert_eq!(limiter.delay(now), Duration::from_millis(500));
+    assert_eq!(
+        limiter.delay(now + Duration::from_millis(200)),
+        Duration::from_millis(300)
+    );
+    assert_eq!(limiter.delay(now + Duration::from_secs(1)), Duration::ZERO);
+
+    // the idle time accumulates at most one second of the rate
+    let later = now + Duration::from_secs(10);
+    limiter.consume(2000, later);
+    assert_eq!(limiter.delay(later), Duration::from_secs(1));
+
+    // the debt is capped
+    let later = later + Duration::from_secs(10);
+    limiter.consume(1_000_000, later);
+    assert_eq!(limiter.delay(later), Duration::from_secs(10));
+}
+
+#[test]
+fn test_protocol_bandwidth() {
+    let config: BandwidthConfig = serde_json::from_str(
+        r#"{"max_download_rate": 1000, "protocols": {"Sync": {"max_upload_rate": 100}}}"#,
+    )
+    .unwrap();
+    let now = Instant::now();
+    let bandwidth = Bandwidth::new(&config, now);
+    let sync = SupportProtocols::Sync.protocol_id();
+    let relay = SupportProtocols::RelayV3.protocol_id();
+
+    // only sync is limited on upload, the message exceeding the limit is delayed
+    assert_eq!(bandwidth.sent(sync, 100, now), Duration::ZERO);
+    assert_eq!(bandwidth.sent(sync, 100, now), Duration::from_secs(1));
+    assert_eq!(bandwidth.sent(relay, 100_000, now), Duration::ZERO);
+    assert_eq!(bandwidth.upload_delay(sync, now), Duration::from_secs(1));
+    assert_eq!(bandwidth.upload_delay(relay, now), Duration::ZERO);
+
+    // the global download limit is shared by all protocols
+    assert!(bandwidth.is_download_limited(sync));
+    assert!(bandwidth.is_download_limited(relay));
+    bandwidth.received(relay, 2000, now);
+    assert_eq!(bandwidth.download_delay(sync, now), Duration::from_secs(1));
+    assert_eq!(bandwidth.download_delay(relay, now), Duration::from_secs(1));
+}
+
+#[test]
+fn test_download_limited_by_protocol() {
+    let config: BandwidthConfig = serde_json::from_str(
+        r#"{"max_upload_rate": 1000, "protocols": {"Sync": {"max_download_rate": 100}}}"#,
+    )
+    .unwrap();
+    let bandwidth = Bandwidth::new(&config, Instant::now());
+    // only the limited protocols gate the sessions reading
+    assert!(bandwidth.is_download_limited(SupportProtocols::Sync.protocol_id()));
+    assert!(!bandwidth.is_download_limited(SupportProtocols::RelayV3.protocol_id()));
+    assert!(bandwidth.is_upload_limited());
+}
+
+#[test]
+fn test_upload_limited_by_protocol() {
+    let config: BandwidthConfig =
+        serde_json::from_str(r#"{"protocols": {"Sync": {"max_upload_rate": 100}}}"#).unwrap();
+    assert!(Bandwidth::new(&config, Instant::now()).is_upload_limited());
+
+    let config: BandwidthConfig = serde_json::from_str(r#"{"max_download_rate": 1000}"#).unwrap();
+    assert!(!Bandwidth::new(&config, Instant::now()).is_upload_limited());
+}
@@ -1,4 +1,5 @@
 mod addr_manager;
+mod bandwidth;
 mod compress;
 mod peer_registry;
 mod peer_store;
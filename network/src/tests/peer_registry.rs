@@ -2,12 +2,13 @@
 
 use super::random_addr;
 use crate::{
+    behaviour::{Penalty, MISBEHAVIOR_BAN_TIME, MISBEHAVIOR_HALF_LIFE, THROTTLED_MESSAGE_INTERVAL},
     errors::{Error, PeerError},
     extract_peer_id,
     multiaddr::Multiaddr,
-    peer_registry::{PeerRegistry, EVICTION_PROTECT_PEERS},
+    peer_registry::{PeerRegistry, EVICTION_PROTECT_BLOCK_RELAY_PEERS, EVICTION_PROTECT_PEERS},
     peer_store::PeerStore,
-    PeerId, SessionType,
+    Behaviour, MisbehaviorScore, PeerId, ProtocolId, SessionType,
 };
 use std::time::{Duration, Instant};
 
@@ -20,7 +21,7 @@ fn test_accept_inbound_peer_in_reserve_only_mode() {
     let session_id = 1.into();
 
     // whitelist_only mode: only accept whitelist_peer
-    let mut peers = PeerRegistry::new(3, 3, true, vec![whitelist_addr.clone()]);
+    let mut peers = PeerRegistry::new(3, 3, true, vec![whitelist_addr.clone()], Vec::new());
     let err = peers
         .accept_peer(
             random_addr(),
@@ -51,7 +52,7 @@ fn test_accept_inbound_peer_until_full() {
         .parse::<Multiaddr>()
         .unwrap();
     // accept node until inbound connections is full
-    let mut peers = PeerRegistry::new(3, 3, false, vec![whitelist_addr.clone()]);
+    let mut peers = PeerRegistry::new(3, 3, false, vec![whitelist_addr.clone()], Vec::new());
     for session_id in 1..=3 {
         peers
             .accept_peer(
@@ -114,6 +115,89 @@ fn test_accept_inbound_peer_until_full() {
     );
 }
 
+#[test]
+fn test_accept_trusted_peer() {
+    let mut peer_store = PeerStore::default();
+    let trusted_peer_id = PeerId::random();
+    let trusted_addr = format!("/ip4/10.0.0.1/tcp/43/p2p/{}", trusted_peer_id.to_base58())
+        .parse::<Multiaddr>()
+        .unwrap();
+    // the trusted peers are whitelisted by `whitelist_peers` as well
+    let mut peers = PeerRegistry::new(
+        1,
+        1,
+        false,
+        vec![trusted_addr.clone()],
+        vec![trusted_addr.clone()],
+    );
+
+    // a connection from the trusted IP with another key is refused
+    let impostor_peer_id = PeerId::random();
+    let impostor_addr = format!("/ip4/10.0.0.1/tcp/44/p2p/{}", impostor_peer_id.to_base58())
+        .parse::<Multiaddr>()
+        .unwrap();
+    let err = peers
+        .accept_peer(
+            impostor_addr,
+            1.into(),
+            SessionType::Inbound,
+            &mut peer_store,
+        )
+        .unwrap_err();
+    assert_eq!(
+        format!("{err}"),
+        format!(
+            "{}",
+            Error::Peer(PeerError::UntrustedPeerId(impostor_peer_id))
+        ),
+    );
+
+    // the trusted peer id from another IP is refused
+    let roaming_addr = format!("/ip4/10.0.0.2/tcp/43/p2p/{}", trusted_peer_id.to_base58())
+        .parse::<Multiaddr>()
+        .unwrap();
+    let err = peers
+        .accept_peer(
+            roaming_addr,
+            2.into(),
+            SessionType::Inbound,
+            &mut peer_store,
+        )
+        .unwrap_err();
+    assert_eq!(
+        format!("{err}"),
+        format!(
+            "{}",
+            Error::Peer(PeerError::UntrustedPeerId(trusted_peer_id.clone()))
+        ),
+    );
+
+    // trusted peers bypass the limitation
+    peers
+        .accept_peer(
+            random_addr(),
+            3.into(),
+            SessionType::Outbound,
+            &mut peer_store,
+        )
+        .expect("accept");
+    let inbound_addr = format!(
+        "/ip4/10.0.0.1/tcp/50000/p2p/{}",
+        trusted_peer_id.to_base58()
+    )
+    .parse::<Multiaddr>()
+    .unwrap();
+    peers
+        .accept_peer(
+            inbound_addr,
+            4.into(),
+            SessionType::Outbound,
+            &mut peer_store,
+        )
+        .expect("accept");
+    assert!(peers.get_peer(4.into()).unwrap().is_whitelist);
+}
+
 #[test]
 fn test_accept_inbound_peer_eviction() {
     // eviction inbound peer
@@ -134,6 +218,7 @@ fn test_accept_inbound_peer_eviction() {
         3,
         false,
         vec![whitelist_addr],
+        Vec::new(),
     );
     // prepare all peers
     for session_id in 0..protected_peers_count {
@@ -221,3 +306,186 @@ fn test_accept_inbound_peer_eviction() {
     // should evict from one of evict_targets
     assert_eq!(len_after_eviction, evict_targets.len() - 1);
 }
+
+#[test]
+fn test_accept_inbound_peer_protect_block_relay_peers() {
+    let mut peer_store = PeerStore::default();
+    let evict_targets_count = 2;
+    let peers_count =
+        2 * EVICTION_PROTECT_PEERS + EVICTION_PROTECT_BLOCK_RELAY_PEERS + evict_targets_count;
+    let mut peers_registry =
+        PeerRegistry::new(peers_count as u32, 3, false, Vec::new(), Vec::new());
+    for session_id in 0..peers_count {
+        assert!(peers_registry
+            .accept_peer(
+                random_addr(),
+                session_id.into(),
+                SessionType::Inbound,
+                &mut peer_store,
+            )
+            .is_ok());
+    }
+    let mut sessions: Vec<_> = peers_registry.peers().keys().cloned().collect();
+    sessions.sort();
+    let mut sessions_iter = sessions.into_iter();
+
+    // to prevent time error, we set now to 10ago.
+    let now = Instant::now() - Duration::from_secs(10);
+    for session_id in sessions_iter.by_ref().take(EVICTION_PROTECT_PEERS) {
+        let peer = peers_registry.get_peer_mut(session_id).unwrap();
+        peer.ping_rtt = Some(Duration::from_secs(0));
+        peer.connected_time = now - Duration::from_secs(10);
+    }
+    for session_id in sessions_iter.by_ref().take(EVICTION_PROTECT_PEERS) {
+        let peer = peers_registry.get_peer_mut(session_id).unwrap();
+        peer.last_ping_protocol_message_received_at = Some(now + Duration::from_secs(10));
+        peer.connected_time = now - Duration::from_secs(10);
+    }
+    // block relay peers connected recently, they are not protected by connection time
+    let block_relay_peers: Vec<_> = sessions_iter
+        .by_ref()
+        .take(EVICTION_PROTECT_BLOCK_RELAY_PEERS)
+        .collect();
+    for session_id in block_relay_peers.iter() {
+        let peer = peers_registry.get_peer_mut(*session_id).unwrap();
+        peer.last_block_relayed_at = Some(now + Duration::from_secs(10));
+        peer.connected_time = now + Duration::from_secs(10);
+    }
+    for session_id in sessions_iter {
+        let peer = peers_registry.get_peer_mut(session_id).unwrap();
+        peer.connected_time = now;
+    }
+
+    let evicted = peers_registry
+        .accept_peer(
+            random_addr(),
+            2000.into(),
+            SessionType::Inbound,
+            &mut peer_store,
+        )
+        .expect("accept")
+        .expect("evict a peer");
+    assert!(!block_relay_peers.contains(&evicted.session_id));
+    for session_id in block_relay_peers {
+        assert!(peers_registry.get_peer(session_id).is_some());
+    }
+}
+
+#[test]
+fn test_report_misbehavior() {
+    let mut peer_store = PeerStore::default();
+    let whitelist_addr = format!("/ip4/127.0.0.2/tcp/43/p2p/{}", PeerId::random().to_base58())
+        .parse::<Multiaddr>()
+        .unwrap();
+    let mut peers = PeerRegistry::new(3, 3, false, vec![whitelist_addr.clone()], Vec::new());
+    let addr = "/ip4/10.0.0.1/tcp/43/p2p/QmXS4Kbc9HEeykHUTJCm2tNmqghbvWyYpUp6BtE5b6VrAU"
+        .parse::<Multiaddr>()
+        .unwrap();
+    peers
+        .accept_peer(
+            addr.clone(),
+            1.into(),
+            SessionType::Inbound,
+            &mut peer_store,
+        )
+        .expect("accept");
+    peers
+        .accept_peer(
+            whitelist_addr,
+            2.into(),
+            SessionType::Inbound,
+            &mut peer_store,
+        )
+        .expect("accept");
+
+    let now = Instant::now();
+    let relay: ProtocolId = 1.into();
+    let sync: ProtocolId = 2.into();
+    assert_eq!(
+        peers.report(1.into(), relay, Behaviour::UnexpectedMessage, now),
+        Some(Penalty::None)
+    );
+    assert!(!peers.throttle(1.into(), relay, now));
+    assert_eq!(
+        peers.report(1.into(), relay, Behaviour::UnexpectedMessage, now),
+        Some(Penalty::Throttle)
+    );
+    // throttled protocols get one message through every interval
+    assert!(!peers.throttle(1.into(), relay, now));
+    assert!(peers.throttle(1.into(), relay, now));
+    assert!(!peers.throttle(1.into(), relay, now + THROTTLED_MESSAGE_INTERVAL));
+    // the other protocols are not throttled
+    assert!(!peers.throttle(1.into(), sync, now));
+    assert!(!peers.throttle(1.into(), sync, now));
+    // but the offenses in all protocols add up to disconnect or ban the peer
+    assert_eq!(
+        peers.report(1.into(), sync, Behaviour::UnexpectedMessage, now),
+        Some(Penalty::None)
+    );
+    assert_eq!(
+        peers.report(1.into(), sync, Behaviour::UnexpectedMessage, now),
+        Some(Penalty::Throttle)
+    );
+    assert_eq!(
+        peers.report(1.into(), sync, Behaviour::UnexpectedMessage, now),
+        Some(Penalty::Disconnect)
+    );
+    assert_eq!(
+        peers.report(1.into(), relay, Behaviour::InvalidBlock, now),
+        Some(Penalty::Ban)
+    );
+
+    // the score is kept on reconnection
+    peers.remove_peer(1.into());
+    peers
+        .accept_peer(addr, 3.into(), SessionType::Inbound, &mut peer_store)
+        .expect("accept");
+    assert_eq!(
+        peers
+            .get_peer(3.into())
+            .unwrap()
+            .misbehavior
+            .penalty(Instant::now()),
+        Penalty::Ban
+    );
+
+    // whitelist peers are never penalized
+    assert_eq!(
+        peers.report(2.into(), relay, Behaviour::InvalidBlock, now),
+        None
+    );
+    assert!(!peers.throttle(2.into(), relay, now));
+}
+
+#[test]
+fn test_misbehavior_score_decay() {
+    let mut misbehavior = MisbehaviorScore::default();
+    let now = Instant::now();
+    assert_eq!(
+        misbehavior.report(Behaviour::InvalidBlock, now),
+        Penalty::Ban
+    );
+    assert_eq!(misbehavior.score(now), 100);
+    assert_eq!(misbehavior.score(now + MISBEHAVIOR_HALF_LIFE), 50);
+    assert_eq!(
+        misbehavior.penalty(now + MISBEHAVIOR_HALF_LIFE),
+        Penalty::Disconnect
+    );
+    assert_eq!(
+        misbehavior.penalty(now + MISBEHAVIOR_HALF_LIFE * 2),
+        Penalty::Throttle
+    );
+    assert_eq!(
+        misbehavior.penalty(now + MISBEHAVIOR_HALF_LIFE * 3),
+        Penalty::None
+    );
+
+    assert_eq!(
+        misbehavior.report(Behaviour::InvalidBlock, now + MISBEHAVIOR_HALF_LIFE),
+        Penalty::Ban
+    );
+    assert_eq!(
+        misbehavior.ban_time(now + MISBEHAVIOR_HALF_LIFE),
+        MISBEHAVIOR_BAN_TIME.mul_f64(1.5)
+    );
+}
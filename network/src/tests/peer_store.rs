@@ -186,6 +186,42 @@ fn test_fetch_addrs_to_attempt() {
         .is_empty());
 }
 
+#[test]
+fn test_fetch_addrs_to_attempt_group_diversity() {
+    let _faketime_guard = ckb_systemtime::faketime();
+    _faketime_guard.set_faketime(1);
+
+    let mut peer_store: PeerStore = Default::default();
+    let new_addr = |ip: &str| -> Multiaddr {
+        format!("/ip4/{}/tcp/42/p2p/{}", ip, PeerId::random().to_base58())
+            .parse()
+            .unwrap()
+    };
+    let outbound_addr = new_addr("226.0.0.1");
+    for addr in [
+        new_addr("225.0.0.1"),
+        new_addr("225.0.0.2"),
+        new_addr("226.0.0.2"),
+    ] {
+        peer_store.add_outbound_addr(addr, Flags::COMPATIBILITY);
+    }
+    _faketime_guard.set_faketime(100_000);
+
+    // at most one addr per network group
+    assert_eq!(
+        peer_store
+            .fetch_addrs_to_attempt(3, Flags::COMPATIBILITY)
+            .len(),
+        2
+    );
+
+    // skip the groups of connected outbound peers
+    peer_store.add_connected_peer(outbound_addr, SessionType::Outbound);
+    let addrs = peer_store.fetch_addrs_to_attempt(3, Flags::COMPATIBILITY);
+    assert_eq!(addrs.len(), 1);
+    assert!(addrs[0].addr.to_string().starts_with("/ip4/225.0.0."));
+}
+
 #[test]
 fn test_fetch_addrs_to_attempt_or_feeler() {
     let _faketime_guard = ckb_systemtime::faketime();
@@ -487,7 +523,13 @@ fn test_eviction() {
         )
         .parse()
         .unwrap();
-        peer_store.add_addr(addr, Flags::COMPATIBILITY).unwrap();
+        // learn them from different sources, otherwise they are limited by the bucket size
+        let source: Multiaddr = format!("/ip4/{}.{}.0.1/tcp/8115", 1 + (i >> 8), i & 0xff)
+            .parse()
+            .unwrap();
+        peer_store
+            .add_addr_from(addr, Flags::COMPATIBILITY, source)
+            .unwrap();
     }
     let addr: Multiaddr = format!(
         "/ip4/192.163.1.1/tcp/43/p2p/{}",
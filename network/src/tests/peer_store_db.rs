@@ -69,6 +69,8 @@ fn test_peer_store_persistent() {
 
     // check addr manager
     let addr_manager2 = peer_store2.addr_manager();
+    // the addrs are bucketed by the same key after reloading
+    assert_eq!(addr_manager2.key(), peer_store.addr_manager().key());
     // set random_id_pos to default, this field is internal used only
     let addrs = addr_manager2.addrs_iter().cloned().map(|mut paddr| {
         paddr.random_id_pos = 0;
@@ -5,13 +5,14 @@ use ckb_logger::{debug, error, info, trace};
 use ckb_stop_handler::{new_tokio_exit_rx, CancellationToken};
 use ckb_types::packed::Byte32;
 use ckb_types::{
-    core::{tx_pool::Reject, BlockView},
-    packed::Alert,
+    core::{tx_pool::Reject, BlockNumber, BlockView, HeaderView},
+    packed::{Alert, Bytes, CellOutput, OutPoint},
 };
-use std::{collections::HashMap, time::Duration};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 use tokio::process::Command;
 use tokio::sync::watch;
 use tokio::sync::{
+    broadcast,
     mpsc::{self, Receiver, Sender},
     oneshot,
 };
@@ -19,6 +20,73 @@ use tokio::time::timeout;
 
 pub use ckb_types::core::service::PoolTransactionEntry;
 
+/// A cell created or consumed by a block indexed by the indexer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangedCell {
+    /// The cell out point
+    pub out_point: OutPoint,
+    /// The cell output
+    pub output: CellOutput,
+    /// The cell data
+    pub output_data: Bytes,
+    /// The number of the block in which the cell is created
+    pub block_number: BlockNumber,
+    /// The index of the transaction which creates the cell in the block
+    pub tx_index: u32,
+}
+
+/// The live cells changed when the indexer appends or rolls back a block
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexerCellsChanged {
+    /// The appended or rolled back block number
+    pub block_number: BlockNumber,
+    /// The appended or rolled back block hash
+    pub block_hash: Byte32,
+    /// Whether the block is rolled back, `created` cells are removed and `consumed` cells are
+    /// live again if it is true
+    pub rollback: bool,
+    /// The cells created by the block
+    pub created: Vec<ChangedCell>,
+    /// The cells consumed by the block
+    pub consumed: Vec<ChangedCell>,
+}
+
+impl IndexerCellsChanged {
+    /// Returns true if the block changes no cells
+    pub fn is_empty(&self) -> bool {
+        self.created.is_empty() && self.consumed.is_empty()
+    }
+}
+
+/// The blocks detached from and attached to the canonical chain when the tip block is changed,
+/// the subscribers load the block transactions from the store by the headers
+#[derive(Debug, Clone)]
+pub struct CanonicalChainChanged {
+    /// The headers of the blocks detached from the canonical chain, in ascending order of block
+    /// number
+    pub detached_headers: Vec<HeaderView>,
+    /// The headers of the blocks attached to the canonical chain, in ascending order of block
+    /// number
+    pub attached_headers: Vec<HeaderView>,
+}
+
+/// A status transition of transactions, broadcast in order to the `transaction_status`
+/// subscribers
+#[derive(Debug, Clone)]
+pub enum TransactionStatusEvent {
+    /// The transaction is added to the pool and not proposed yet
+    Pending(Byte32),
+    /// The in-pool transaction is proposed
+    Proposed(Byte32),
+    /// The transaction is abandoned by the pool, or rejected on submission and never added to it
+    Rejected(Byte32, Reject),
+    /// The in-pool transaction is replaced by a conflicting transaction by RBF
+    Replaced(Byte32, Reject),
+    /// The canonical chain is changed, the transactions of the attached blocks are committed and
+    /// those of the detached blocks are not any more
+    ChainChanged(Arc<CanonicalChainChanged>),
+}
+
 /// Asynchronous request sent to the service.
 pub struct Request<A, R> {
     /// Oneshot channel for the service to send back the response.
@@ -47,6 +115,9 @@ pub const SIGNAL_CHANNEL_SIZE: usize = 1;
 pub const REGISTER_CHANNEL_SIZE: usize = 2;
 /// TODO(doc): @quake
 pub const NOTIFY_CHANNEL_SIZE: usize = 128;
+/// The capacity of the transaction status channel, a subscriber lagging behind more messages is
+/// notified by `RecvError::Lagged`
+pub const TRANSACTION_STATUS_CHANNEL_SIZE: usize = 1024;
 
 /// TODO(doc): @quake
 pub type NotifyRegister<M> = Sender<Request<String, Receiver<M>>>;
@@ -99,6 +170,8 @@ pub struct NotifyController {
     reject_transaction_notifier: Sender<(PoolTransactionEntry, Reject)>,
     network_alert_register: NotifyRegister<Alert>,
     network_alert_notifier: Sender<Alert>,
+    indexer_cells_changed_notifier: broadcast::Sender<IndexerCellsChanged>,
+    transaction_status_notifier: broadcast::Sender<TransactionStatusEvent>,
     handle: Handle,
 }
 
@@ -163,6 +236,14 @@ impl NotifyService {
             mpsc::channel(REGISTER_CHANNEL_SIZE);
         let (network_alert_sender, mut network_alert_receiver) = mpsc::channel(NOTIFY_CHANNEL_SIZE);
 
+        // The cells changes are broadcast in order by the indexer, a subscriber lagging behind
+        // more than `NOTIFY_CHANNEL_SIZE` messages gets `RecvError::Lagged`.
+        let (indexer_cells_changed_sender, _) = broadcast::channel(NOTIFY_CHANNEL_SIZE);
+
+        // The transaction status events are sent by the tx-pool and the chain service in the
+        // order they happen, so a subscriber receives the transitions of a transaction in order.
+        let (transaction_status_sender, _) = broadcast::channel(TRANSACTION_STATUS_CHANNEL_SIZE);
+
         handle.spawn(async move {
             loop {
                 tokio::select! {
@@ -198,6 +279,8 @@ impl NotifyService {
             reject_transaction_notifier: reject_transaction_sender,
             network_alert_register,
             network_alert_notifier: network_alert_sender,
+            indexer_cells_changed_notifier: indexer_cells_changed_sender,
+            transaction_status_notifier: transaction_status_sender,
             handle,
         }
     }
@@ -509,4 +592,42 @@ impl NotifyController {
             }
         });
     }
+
+    /// Subscribes the live cells changes of the blocks appended to or rolled back from the indexer
+    ///
+    /// The changes are received in the order the indexer applies the blocks. The receiver gets
+    /// `RecvError::Lagged` when it falls behind more than `NOTIFY_CHANNEL_SIZE` messages.
+    pub fn subscribe_indexer_cells_changed<S: ToString>(
+        &self,
+        name: S,
+    ) -> broadcast::Receiver<IndexerCellsChanged> {
+        debug!("Register indexer_cells_changed {:?}", name.to_string());
+        self.indexer_cells_changed_notifier.subscribe()
+    }
+
+    /// Notifies the live cells changes of a block appended to or rolled back from the indexer
+    pub fn notify_indexer_cells_changed(&self, cells_changed: IndexerCellsChanged) {
+        trace!("Indexer cells changed event {:?}", cells_changed);
+        // Err means there is no subscriber
+        let _ = self.indexer_cells_changed_notifier.send(cells_changed);
+    }
+
+    /// Subscribes the status transitions of the transactions
+    ///
+    /// The events are received in the order they happen. The receiver gets `RecvError::Lagged`
+    /// when it falls behind more than `TRANSACTION_STATUS_CHANNEL_SIZE` messages.
+    pub fn subscribe_transaction_status<S: ToString>(
+        &self,
+        name: S,
+    ) -> broadcast::Receiver<TransactionStatusEvent> {
+        debug!("Register transaction_status {:?}", name.to_string());
+        self.transaction_status_notifier.subscribe()
+    }
+
+    /// Notifies a status transition of transactions
+    pub fn notify_transaction_status(&self, event: TransactionStatusEvent) {
+        trace!("Transaction status event {:?}", event);
+        // Err means there is no subscriber
+        let _ = self.transaction_status_notifier.send(event);
+    }
 }
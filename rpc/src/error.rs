@@ -76,6 +76,11 @@ pub enum RPCError {
     /// This is a fatal error usually caused by the underlying database used by CKB. Please back up
     /// the data directory and re-sync the chain from scratch.
     DatabaseIsCorrupt = -202,
+    /// (-203): The block body is unavailable.
+    ///
+    /// The body is deleted in the pruned mode, or it is not back-filled yet after the node is
+    /// bootstrapped from a cell set snapshot.
+    BlockBodyUnavailable = -203,
     /// (-301): Failed to resolve the referenced cells and headers used in the transaction, as inputs or
     /// dependencies.
     TransactionFailedToResolve = -301,
@@ -114,8 +119,18 @@ pub enum RPCError {
     PoolRejectedTransactionBySizeLimit = -1110,
     /// (-1111): The transaction is rejected for RBF checking.
     PoolRejectedRBF = -1111,
+    /// (-1112): The transaction is rejected because the in-pool transactions spending one of its
+    /// input lock scripts reached the limits.
+    ///
+    /// The limits are the config options `tx_pool.max_tx_count_per_lock` and
+    /// `tx_pool.max_tx_size_per_lock`.
+    PoolRejectedTransactionByLockScriptLimit = -1112,
     /// (-1200): The indexer error.
     Indexer = -1200,
+    /// (-1300): The subscriber falls behind and misses messages, the subscription is closed.
+    ///
+    /// The subscriber should subscribe the topic again and re-sync the states it tracks.
+    SubscriptionLagged = -1300,
 }
 
 impl RPCError {
@@ -180,6 +195,9 @@ impl RPCError {
                 RPCError::PoolRejectedTransactionBySizeLimit
             }
             Reject::Expiry(_) => RPCError::TransactionExpired,
+            Reject::ExceededLockScriptLimit(..) => {
+                RPCError::PoolRejectedTransactionByLockScriptLimit
+            }
         };
         RPCError::custom_with_error(code, reject)
     }
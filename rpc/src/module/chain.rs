@@ -2,10 +2,12 @@ use crate::error::RPCError;
 use crate::util::FeeRateCollector;
 use async_trait::async_trait;
 use ckb_jsonrpc_types::{
-    BlockEconomicState, BlockFilter, BlockNumber, BlockResponse, BlockView, CellWithStatus,
-    Consensus, EpochNumber, EpochView, EstimateCycles, FeeRateStatistics, HeaderView, OutPoint,
-    ResponseFormat, ResponseFormatInnerType, Timestamp, Transaction, TransactionAndWitnessProof,
-    TransactionProof, TransactionWithStatusResponse, Uint32, Uint64,
+    BlockEconomicState, BlockFilter, BlockNumber, BlockNumberOrHash, BlockResponse, BlockView,
+    CellData, CellInfo, CellStatusAtBlock, CellWithStatus, CellWithStatusAtBlock,
+    CellsWithStatusAtBlock, Consensus, EpochNumber, EpochView, EstimateCycles, FeeRateStatistics,
+    HeaderView, JsonBytes, OutPoint, ResponseFormat, ResponseFormatInnerType, Timestamp,
+    Transaction, TransactionAndWitnessProof, TransactionProof, TransactionWithStatusResponse,
+    Uint32, Uint64,
 };
 use ckb_logger::error;
 use ckb_reward_calculator::RewardCalculator;
@@ -18,17 +20,17 @@ use ckb_types::{
         self,
         cell::{resolve_transaction, CellProvider, CellStatus, HeaderChecker},
         error::OutPointError,
+        TransactionMeta,
     },
     packed,
     prelude::*,
     utilities::{merkle_root, MerkleProof, CBMT},
     H256,
 };
-use ckb_verification::ScriptVerifier;
-use ckb_verification::TxVerifyEnv;
+use ckb_verification::{ScriptGroupTrace, ScriptGroupType, ScriptVerifier, TxVerifyEnv};
 use jsonrpc_core::Result;
 use jsonrpc_utils::rpc;
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::sync::Arc;
 
 /// RPC Module Chain for methods related to the canonical chain.
@@ -832,6 +834,185 @@ pub trait ChainRpc {
     #[rpc(name = "get_live_cell")]
     fn get_live_cell(&self, out_point: OutPoint, with_data: bool) -> Result<CellWithStatus>;
 
+    /// Returns the status of a cell as of the specified block in the [canonical chain](#canonical-chain).
+    ///
+    /// Unlike `get_live_cell`, which only answers against the current tip, this RPC tells whether
+    /// the cell was live right after the specified block was committed.
+    ///
+    /// ## Params
+    ///
+    /// * `out_point` - Reference to the cell by transaction hash and output index.
+    /// * `block` - The block number or the block hash of the block to query against.
+    /// * `with_data` - Whether the RPC should return cell data. Cell data can be huge, if the client
+    /// does not need the data, it should set this to `false` to save bandwidth.
+    ///
+    /// ## Returns
+    ///
+    /// The RPC returns null if the specified block is not in the canonical chain.
+    ///
+    /// Otherwise the cell status is one of:
+    ///
+    /// * `live` - The cell is created at or before the specified block and is not consumed by any
+    /// transaction at or before it.
+    /// * `dead` - The cell is consumed at or before the specified block.
+    /// * `unknown` - The cell is not created at or before the specified block.
+    ///
+    /// The cell information is returned for both `live` and `dead` cells.
+    ///
+    /// Finding out where a cell that is no longer live got consumed requires scanning the block
+    /// bodies between the block creating the cell and the specified block, or the ones after the
+    /// specified block, whichever is within the limit of 1000 blocks. In the latter case
+    /// `consumed_block_number` is null when the cell is `dead`.
+    ///
+    /// ## Errors
+    ///
+    /// * [`InvalidParams (-32602)`](#error-invalidparams) - Resolving the status requires scanning
+    /// more than 1000 block bodies.
+    /// * [`BlockBodyUnavailable (-203)`](#error-blockbodyunavailable) - A block body to scan is
+    /// pruned or not back-filled yet.
+    ///
+    /// ## Examples
+    ///
+    /// Request
+    ///
+    /// ```json
+    /// {
+    ///   "id": 42,
+    ///   "jsonrpc": "2.0",
+    ///   "method": "get_cell_at_block",
+    ///   "params": [
+    ///     {
+    ///       "index": "0x0",
+    ///       "tx_hash": "0xa4037a893eb48e18ed4ef61034ce26eba9c585f15c9cee102ae58505565eccc3"
+    ///     },
+    ///     "0x400",
+    ///     false
+    ///   ]
+    /// }
+    /// ```
+    ///
+    /// Response
+    ///
+    /// ```json
+    /// {
+    ///   "id": 42,
+    ///   "jsonrpc": "2.0",
+    ///   "result": {
+    ///     "cell": {
+    ///       "data": null,
+    ///       "output": {
+    ///         "capacity": "0x802665800",
+    ///         "lock": {
+    ///           "code_hash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+    ///           "hash_type": "data",
+    ///           "args": "0x"
+    ///         },
+    ///         "type": null
+    ///       }
+    ///     },
+    ///     "consumed_block_number": null,
+    ///     "created_block_number": "0x0",
+    ///     "status": "live"
+    ///   }
+    /// }
+    /// ```
+    #[rpc(name = "get_cell_at_block")]
+    fn get_cell_at_block(
+        &self,
+        out_point: OutPoint,
+        block: BlockNumberOrHash,
+        with_data: bool,
+    ) -> Result<Option<CellWithStatusAtBlock>>;
+
+    /// Returns the statuses of a batch of cells as of the specified block in the
+    /// [canonical chain](#canonical-chain).
+    ///
+    /// This is the batch version of [`get_cell_at_block`](#tymethod.get_cell_at_block). The block
+    /// bodies are scanned only once for the whole batch, and the limit of 1000 scanned blocks
+    /// applies to the whole batch.
+    ///
+    /// ## Params
+    ///
+    /// * `out_points` - References to the cells, at most 1000 out points per request.
+    /// * `block` - The block number or the block hash of the block to query against.
+    /// * `with_data` - Whether the RPC should return cell data.
+    ///
+    /// ## Returns
+    ///
+    /// The RPC returns null if the specified block is not in the canonical chain. Otherwise it
+    /// returns the specified block number and hash, and the cells in the same order as `out_points`.
+    ///
+    /// ## Examples
+    ///
+    /// Request
+    ///
+    /// ```json
+    /// {
+    ///   "id": 42,
+    ///   "jsonrpc": "2.0",
+    ///   "method": "get_cells_at_block",
+    ///   "params": [
+    ///     [
+    ///       {
+    ///         "index": "0x0",
+    ///         "tx_hash": "0xa4037a893eb48e18ed4ef61034ce26eba9c585f15c9cee102ae58505565eccc3"
+    ///       },
+    ///       {
+    ///         "index": "0x0",
+    ///         "tx_hash": "0xa0ef4eb5f4ceeb08a4c8524d84c5da95dce2f608e0ca2ec8091191b0f330c6e3"
+    ///       }
+    ///     ],
+    ///     "0xa5f5c85987a15de25661e5a214f2c1449cd803f071acc7999820f25246471f40",
+    ///     false
+    ///   ]
+    /// }
+    /// ```
+    ///
+    /// Response
+    ///
+    /// ```json
+    /// {
+    ///   "id": 42,
+    ///   "jsonrpc": "2.0",
+    ///   "result": {
+    ///     "block_hash": "0xa5f5c85987a15de25661e5a214f2c1449cd803f071acc7999820f25246471f40",
+    ///     "block_number": "0x400",
+    ///     "cells": [
+    ///       {
+    ///         "cell": {
+    ///           "data": null,
+    ///           "output": {
+    ///             "capacity": "0x802665800",
+    ///             "lock": {
+    ///               "code_hash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+    ///               "hash_type": "data",
+    ///               "args": "0x"
+    ///             },
+    ///             "type": null
+    ///           }
+    ///         },
+    ///         "consumed_block_number": null,
+    ///         "created_block_number": "0x0",
+    ///         "status": "live"
+    ///       },
+    ///       {
+    ///         "cell": null,
+    ///         "consumed_block_number": null,
+    ///         "created_block_number": null,
+    ///         "status": "unknown"
+    ///       }
+    ///     ]
+    ///   }
+    /// }
+    /// ```
+    #[rpc(name = "get_cells_at_block")]
+    fn get_cells_at_block(
+        &self,
+        out_points: Vec<OutPoint>,
+        block: BlockNumberOrHash,
+        with_data: bool,
+    ) -> Result<Option<CellsWithStatusAtBlock>>;
+
     /// Returns the highest block number in the [canonical chain](#canonical-chain).
     ///
     /// Because of [chain reorganization](#chain-reorganization), the returned block number may be
@@ -1616,6 +1797,8 @@ pub(crate) struct ChainRpcImpl {
 const DEFAULT_BLOCK_VERBOSITY_LEVEL: u32 = 2;
 const DEFAULT_HEADER_VERBOSITY_LEVEL: u32 = 1;
 const DEFAULT_GET_TRANSACTION_VERBOSITY_LEVEL: u32 = 2;
+const MAX_CELLS_AT_BLOCK: usize = 1000;
+const MAX_CELLS_AT_BLOCK_SCANNED_BLOCKS: u64 = 1000;
 
 #[async_trait]
 impl ChainRpc for ChainRpcImpl {
@@ -1820,6 +2003,40 @@ impl ChainRpc for ChainRpcImpl {
         Ok(cell_status.into())
     }
 
+    fn get_cell_at_block(
+        &self,
+        out_point: OutPoint,
+        block: BlockNumberOrHash,
+        with_data: bool,
+    ) -> Result<Option<CellWithStatusAtBlock>> {
+        let snapshot = self.shared.snapshot();
+        Ok(self
+            .cells_at_block(&snapshot, vec![out_point.into()], block, with_data)?
+            .map(|(_, mut cells)| cells.remove(0)))
+    }
+
+    fn get_cells_at_block(
+        &self,
+        out_points: Vec<OutPoint>,
+        block: BlockNumberOrHash,
+        with_data: bool,
+    ) -> Result<Option<CellsWithStatusAtBlock>> {
+        if out_points.len() > MAX_CELLS_AT_BLOCK {
+            return Err(RPCError::invalid_params(format!(
+                "Too many out points, at most {MAX_CELLS_AT_BLOCK} out points are allowed"
+            )));
+        }
+        let snapshot = self.shared.snapshot();
+        let out_points = out_points.into_iter().map(Into::into).collect();
+        Ok(self
+            .cells_at_block(&snapshot, out_points, block, with_data)?
+            .map(|(header, cells)| CellsWithStatusAtBlock {
+                block_number: header.number().into(),
+                block_hash: header.hash().unpack(),
+                cells,
+            }))
+    }
+
     fn get_tip_block_number(&self) -> Result<BlockNumber> {
         Ok(self.shared.snapshot().tip_header().number().into())
     }
@@ -2102,7 +2319,7 @@ impl ChainRpc for ChainRpcImpl {
 
     fn estimate_cycles(&self, tx: Transaction) -> Result<EstimateCycles> {
         let tx: packed::Transaction = tx.into();
-        CyclesEstimator::new(&self.shared).run(tx)
+        CyclesEstimator::new(&self.shared).run(tx, false)
     }
 
     fn get_fee_rate_statics(&self, target: Option<Uint64>) -> Result<Option<FeeRateStatistics>> {
@@ -2258,6 +2475,143 @@ impl ChainRpcImpl {
         }))
     }
 
+    // Resolves the statuses of the cells right after the specified block is committed.
+    //
+    // The live cell set tells whether an output is still live at the tip, see
+    // `transaction_meta_at_tip`, and a cell live at the tip is also live at any block after the
+    // one creating it. For the other cells, the block bodies up to the specified block, or the
+    // ones after it when that range is too long, are scanned to find out where they are consumed.
+    // The scanned block bodies are limited to `MAX_CELLS_AT_BLOCK_SCANNED_BLOCKS` per call.
+    fn cells_at_block(
+        &self,
+        snapshot: &Snapshot,
+        out_points: Vec<packed::OutPoint>,
+        block: BlockNumberOrHash,
+        with_data: bool,
+    ) -> Result<Option<(core::HeaderView, Vec<CellWithStatusAtBlock>)>> {
+        let block_hash = match block {
+            BlockNumberOrHash::Number(number) => match snapshot.get_block_hash(number.into()) {
+                Some(block_hash) => block_hash,
+                None => return Ok(None),
+            },
+            BlockNumberOrHash::Hash(hash) => hash.pack(),
+        };
+        if !snapshot.is_main_chain(&block_hash) {
+            return Ok(None);
+        }
+        let header = snapshot.get_block_header(&block_hash).ok_or_else(|| {
+            RPCError::custom(
+                RPCError::ChainIndexIsInconsistent,
+                format!("Block header {block_hash:#x} is in the main chain but not found"),
+            )
+        })?;
+        let block_number = header.number();
+        let tip_number = snapshot.tip_number();
+
+        let mut metas: HashMap<packed::Byte32, TransactionMeta> = HashMap::new();
+        let mut cells = Vec::with_capacity(out_points.len());
+        // The cells consumed before the tip, whose consuming blocks are looked up by the scan
+        let mut pending: HashMap<packed::OutPoint, Vec<usize>> = HashMap::new();
+        let mut scanned_numbers: BTreeSet<core::BlockNumber> = BTreeSet::new();
+        for out_point in out_points {
+            let index: u32 = out_point.index().unpack();
+            let created = snapshot
+                .get_transaction_with_info(&out_point.tx_hash())
+                .filter(|(_, tx_info)| tx_info.block_number <= block_number)
+                .and_then(|(tx, tx_info)| {
+                    tx.output_with_data(index as usize)
+                        .map(|(output, data)| (tx, tx_info, output, data))
+                });
+            let (tx, tx_info, output, data) = match created {
+                Some(created) => created,
+                None => {
+                    cells.push(CellWithStatusAtBlock {
+                        cell: None,
+                        status: CellStatusAtBlock::Unknown,
+                        created_block_number: None,
+                        consumed_block_number: None,
+                    });
+                    continue;
+                }
+            };
+            let data = if with_data {
+                Some(CellData {
+                    hash: packed::CellOutput::calc_data_hash(&data).unpack(),
+                    content: JsonBytes::from_bytes(data),
+                })
+            } else {
+                None
+            };
+            let created_number = tx_info.block_number;
+            let mut cell = CellWithStatusAtBlock {
+                cell: Some(CellInfo {
+                    output: output.into(),
+                    data,
+                }),
+                status: CellStatusAtBlock::Live,
+                created_block_number: Some(created_number.into()),
+                consumed_block_number: None,
+            };
+
+            let meta = metas
+                .entry(out_point.tx_hash())
+                .or_insert_with(|| transaction_meta_at_tip(snapshot, &tx, &tx_info));
+            let dead_at_tip = meta.is_dead(index as usize).unwrap_or(true);
+            if dead_at_tip {
+                // Scans the blocks up to the specified block for the consuming one. If the range
+                // is too long, scans the blocks after the specified block instead, and the cell is
+                // dead at the specified block unless the consuming block is found there.
+                let forward_len = block_number - created_number + 1;
+                let backward_len = tip_number - block_number;
+                if forward_len <= MAX_CELLS_AT_BLOCK_SCANNED_BLOCKS {
+                    scanned_numbers.extend(created_number..=block_number);
+                } else if backward_len <= MAX_CELLS_AT_BLOCK_SCANNED_BLOCKS {
+                    cell.status = CellStatusAtBlock::Dead;
+                    scanned_numbers.extend(block_number + 1..=tip_number);
+                } else {
+                    return Err(too_many_scanned_blocks(&out_point));
+                }
+                if scanned_numbers.len() as u64 > MAX_CELLS_AT_BLOCK_SCANNED_BLOCKS {
+                    return Err(too_many_scanned_blocks(&out_point));
+                }
+                pending.entry(out_point).or_default().push(cells.len());
+            }
+            cells.push(cell);
+        }
+
+        for number in scanned_numbers {
+            if pending.is_empty() {
+                break;
+            }
+            let block = snapshot
+                .get_block_hash(number)
+                .and_then(|hash| snapshot.get_block(&hash))
+                .ok_or_else(|| {
+                    RPCError::custom(
+                        RPCError::BlockBodyUnavailable,
+                        format!("The body of block #{number} is unavailable"),
+                    )
+                })?;
+            for input in block
+                .transactions()
+                .iter()
+                .skip(1)
+                .flat_map(|tx| tx.input_pts_iter())
+            {
+                for i in pending.remove(&input).unwrap_or_default() {
+                    if number <= block_number {
+                        cells[i].status = CellStatusAtBlock::Dead;
+                        cells[i].consumed_block_number = Some(number.into());
+                    } else {
+                        cells[i].status = CellStatusAtBlock::Live;
+                    }
+                }
+            }
+        }
+
+        Ok(Some((header, cells)))
+    }
+
     fn get_tx_indices(
         &self,
         tx_hashes: Vec<H256>,
@@ -2353,7 +2707,8 @@ impl<'a> CyclesEstimator<'a> {
         Self { shared }
     }
 
-    pub(crate) fn run(&self, tx: packed::Transaction) -> Result<EstimateCycles> {
+    // In the trace mode, the failing script group is reported in the trace instead of an error.
+    pub(crate) fn run(&self, tx: packed::Transaction, trace: bool) -> Result<EstimateCycles> {
         let snapshot = self.shared.cloned_snapshot();
         let consensus = snapshot.cloned_consensus();
         match resolve_transaction(tx.into_view(), &mut HashSet::new(), self, self) {
@@ -2361,16 +2716,25 @@ impl<'a> CyclesEstimator<'a> {
                 let max_cycles = consensus.max_block_cycles;
                 let tip_header = snapshot.tip_header();
                 let tx_env = TxVerifyEnv::new_submit(tip_header);
-                match ScriptVerifier::new(
+                let mut verifier = ScriptVerifier::new(
                     Arc::new(resolved),
                     snapshot.as_data_loader(),
                     consensus,
                     Arc::new(tx_env),
-                )
-                .verify(max_cycles)
-                {
+                );
+                if trace {
+                    verifier.enable_trace();
+                    let traces = verifier.trace(max_cycles);
+                    let cycles: core::Cycle = traces.iter().filter_map(|trace| trace.cycles).sum();
+                    return Ok(EstimateCycles {
+                        cycles: cycles.into(),
+                        trace: Some(traces.into_iter().map(script_group_trace).collect()),
+                    });
+                }
+                match verifier.verify(max_cycles) {
                     Ok(cycles) => Ok(EstimateCycles {
                         cycles: cycles.into(),
+                        trace: None,
                     }),
                     Err(err) => Err(RPCError::custom_with_error(
                         RPCError::TransactionFailedToVerify,
@@ -2385,3 +2749,70 @@ impl<'a> CyclesEstimator<'a> {
         }
     }
 }
+
+fn script_group_trace(trace: ScriptGroupTrace) -> ckb_jsonrpc_types::ScriptGroupTrace {
+    ckb_jsonrpc_types::ScriptGroupTrace {
+        group_type: match trace.group_type {
+            ScriptGroupType::Lock => ckb_jsonrpc_types::ScriptGroupType::Lock,
+            ScriptGroupType::Type => ckb_jsonrpc_types::ScriptGroupType::Type,
+        },
+        script_hash: trace.script_hash.unpack(),
+        exit_code: trace.exit_code,
+        cycles: trace.cycles.map(Into::into),
+        error: trace.error.map(|error| error.to_string()),
+        syscalls: trace
+            .syscalls
+            .into_iter()
+            .map(|syscall| ckb_jsonrpc_types::SyscallTrace {
+                vm_id: syscall.vm_id.into(),
+                number: syscall.number.into(),
+                args: syscall.args.iter().map(|arg| (*arg).into()).collect(),
+                return_code: syscall.return_code.into(),
+                cycles: syscall.cycles.into(),
+            })
+            .collect(),
+        debug_messages: trace.debug_messages,
+        vms: trace
+            .vms
+            .into_iter()
+            .map(|vm| ckb_jsonrpc_types::VmTrace {
+                id: vm.id.into(),
+                parent: vm.parent.map(Into::into),
+                exit_code: vm.exit_code,
+                cycles: vm.cycles.map(Into::into),
+            })
+            .collect(),
+        dropped_events: trace.dropped_events.into(),
+    }
+}
+
+// Builds the transaction meta from the live cell set, whose dead bits tell which outputs are
+// consumed at the tip.
+fn transaction_meta_at_tip(
+    snapshot: &Snapshot,
+    tx: &core::TransactionView,
+    tx_info: &core::TransactionInfo,
+) -> TransactionMeta {
+    let mut meta = TransactionMeta::new(
+        tx_info.block_number,
+        tx_info.block_epoch.number(),
+        tx_info.block_hash.clone(),
+        tx.outputs().len(),
+        true,
+    );
+    let tx_hash = tx.hash();
+    for index in 0..tx.outputs().len() {
+        if snapshot.have_cell(&packed::OutPoint::new(tx_hash.clone(), index as u32)) {
+            meta.unset_dead(index);
+        }
+    }
+    meta
+}
+
+fn too_many_scanned_blocks(out_point: &packed::OutPoint) -> jsonrpc_core::Error {
+    RPCError::invalid_params(format!(
+        "Resolving the status of the cell {out_point} requires scanning more than \
+        {MAX_CELLS_AT_BLOCK_SCANNED_BLOCKS} block bodies, query a block closer to the one \
+        creating or consuming the cell"
+    ))
+}
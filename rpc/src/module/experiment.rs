@@ -26,10 +26,22 @@ pub trait ExperimentRpc {
     ///
     /// It is used to debug transaction scripts and query how many cycles the scripts consume.
     ///
+    /// ## Params
+    ///
+    /// * `tx` - The transaction.
+    /// * `trace` - Whether to return the execution traces of the script groups, default is false.
+    ///
+    /// In the trace mode, the result contains the field `trace`, which lists the script groups
+    /// have run, see [`ScriptGroupTrace`](../../ckb_jsonrpc_types/struct.ScriptGroupTrace.html).
+    /// Each one records the syscalls with the arguments and the return codes, the debug messages,
+    /// the exit code, and the cycles consumed by the group and by each spawned VM. The run stops
+    /// at the first failing group, which is reported in the trace with the error instead of
+    /// returning `TransactionFailedToVerify`, and `cycles` sums up the groups that have succeeded.
+    ///
     /// ## Errors
     ///
     /// * [`TransactionFailedToResolve (-301)`](../enum.RPCError.html#variant.TransactionFailedToResolve) - Failed to resolve the referenced cells and headers used in the transaction, as inputs or dependencies.
-    /// * [`TransactionFailedToVerify (-302)`](../enum.RPCError.html#variant.TransactionFailedToVerify) - There is a script returns with an error.
+    /// * [`TransactionFailedToVerify (-302)`](../enum.RPCError.html#variant.TransactionFailedToVerify) - There is a script returns with an error, only when the trace mode is off.
     ///
     /// ## Examples
     ///
@@ -100,7 +112,7 @@ pub trait ExperimentRpc {
         note = "Please use the RPC method [`estimate_cycles`](#tymethod.estimate_cycles) instead"
     )]
     #[rpc(name = "dry_run_transaction")]
-    fn dry_run_transaction(&self, tx: Transaction) -> Result<EstimateCycles>;
+    fn dry_run_transaction(&self, tx: Transaction, trace: Option<bool>) -> Result<EstimateCycles>;
 
     /// Calculates the maximum withdrawal one can get, given a referenced DAO cell, and
     /// a withdrawing block hash.
@@ -171,9 +183,9 @@ pub(crate) struct ExperimentRpcImpl {
 
 #[async_trait]
 impl ExperimentRpc for ExperimentRpcImpl {
-    fn dry_run_transaction(&self, tx: Transaction) -> Result<EstimateCycles> {
+    fn dry_run_transaction(&self, tx: Transaction, trace: Option<bool>) -> Result<EstimateCycles> {
         let tx: packed::Transaction = tx.into();
-        CyclesEstimator::new(&self.shared).run(tx)
+        CyclesEstimator::new(&self.shared).run(tx, trace.unwrap_or(false))
     }
 
     fn calculate_dao_maximum_withdraw(
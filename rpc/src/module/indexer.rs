@@ -2,9 +2,11 @@ use crate::error::RPCError;
 use async_trait::async_trait;
 use ckb_indexer::IndexerHandle;
 use ckb_jsonrpc_types::{
-    IndexerCell, IndexerCellsCapacity, IndexerOrder, IndexerPagination, IndexerSearchKey,
-    IndexerTip, IndexerTx, JsonBytes, Uint32,
+    BlockNumber, IndexerCell, IndexerCellsCapacity, IndexerCellsCapacityAtBlock,
+    IndexerCellsUdtAmount, IndexerOrder, IndexerPagination, IndexerSearchKey, IndexerTip,
+    IndexerTx, JsonBytes, Uint32,
 };
+use ckb_shared::shared::Shared;
 use jsonrpc_core::Result;
 use jsonrpc_utils::rpc;
 
@@ -58,6 +60,14 @@ pub trait IndexerRpc {
     ///          - output_data_len_range: [u64; 2], filter cells by output data len range, [inclusive, exclusive]
     ///          - output_capacity_range: [u64; 2], filter cells by output capacity range, [inclusive, exclusive]
     ///          - block_range: [u64; 2], filter cells by block number range, [inclusive, exclusive]
+    ///     - filter_expression - filter cells by a boolean expression, optional, combined with `filter` by `and`
+    ///          - and: [expression], matches if all the sub-expressions match
+    ///          - or: [expression], matches if any of the sub-expressions matches
+    ///          - not: expression, matches if the sub-expression does not match
+    ///          - script: { script_type, script, script_search_mode }, filter cells by lock or type script, null script matches cells without the script
+    ///          - output_data_len_range: [u64; 2], filter cells by output data len range, [inclusive, exclusive]
+    ///          - output_capacity_range: [u64; 2], filter cells by output capacity range, [inclusive, exclusive]
+    ///          - block_range: [u64; 2], filter cells by block number range, [inclusive, exclusive]
     ///     - with_data - bool, optional default is `true`, if with_data is set to false, the field of returning cell.output_data is null in the result
     /// * order: enum, asc | desc
     /// * limit: result size limit
@@ -390,6 +400,83 @@ pub trait IndexerRpc {
     ///     "id": 2
     /// }
     /// ```
+    ///
+    /// * get cells by lock script and filter by a boolean expression: type script is sUDT `0x5e7a..`
+    /// or null, and capacity is at least 100 CKB
+    ///
+    /// Request
+    ///
+    /// ```json
+    /// {
+    ///     "id": 2,
+    ///     "jsonrpc": "2.0",
+    ///     "method": "get_cells",
+    ///     "params": [
+    ///         {
+    ///             "script": {
+    ///                 "code_hash": "0x9bd7e06f3ecf4be0f2fcd2188b23f1b9fcc88e5d4b65a8637b17723bbda3cce8",
+    ///                 "hash_type": "type",
+    ///                 "args": "0x5989ae415bb667931a99896e5fbbfad9ba53a223"
+    ///             },
+    ///             "script_type": "lock",
+    ///             "filter_expression": {
+    ///                 "and": [
+    ///                     {
+    ///                         "or": [
+    ///                             {
+    ///                                 "script": {
+    ///                                     "script_type": "type",
+    ///                                     "script": {
+    ///                                         "code_hash": "0x5e7a36a77e68eecc013dfa2fe6a23f3b6c344b04005808694ae6dd45eea4cfd5",
+    ///                                         "hash_type": "type",
+    ///                                         "args": "0x"
+    ///                                     }
+    ///                                 }
+    ///                             },
+    ///                             { "script": { "script_type": "type", "script": null } }
+    ///                         ]
+    ///                     },
+    ///                     { "output_capacity_range": ["0x2540be400", "0xffffffffffffffff"] }
+    ///                 ]
+    ///             }
+    ///         },
+    ///         "asc",
+    ///         "0x64"
+    ///     ]
+    /// }
+    /// ```
+    ///
+    /// Response
+    ///
+    /// ```json
+    /// {
+    ///     "jsonrpc": "2.0",
+    ///     "result": {
+    ///       "last_cursor": "0x409bd7e06f3ecf4be0f2fcd2188b23f1b9fcc88e5d4b65a8637b17723bbda3cce8015989ae415bb667931a99896e5fbbfad9ba53a22300000000005b59df0000000100000001",
+    ///       "objects": [
+    ///         {
+    ///           "block_number": "0x5b59df",
+    ///           "out_point": {
+    ///             "index": "0x1",
+    ///             "tx_hash": "0x21c4632a41140b828e9347ff80480b3e07be4e0a0b8d577565e7421fd5473194"
+    ///           },
+    ///           "output": {
+    ///             "capacity": "0xe815b81c0",
+    ///             "lock": {
+    ///               "args": "0x5989ae415bb667931a99896e5fbbfad9ba53a223",
+    ///               "code_hash": "0x9bd7e06f3ecf4be0f2fcd2188b23f1b9fcc88e5d4b65a8637b17723bbda3cce8",
+    ///               "hash_type": "type"
+    ///             },
+    ///             "type": null
+    ///           },
+    ///           "output_data": "0x",
+    ///           "tx_index": "0x1"
+    ///         }
+    ///       ]
+    ///     },
+    ///     "id": 2
+    /// }
+    /// ```
     #[rpc(name = "get_cells")]
     fn get_cells(
         &self,
@@ -407,6 +494,12 @@ pub trait IndexerRpc {
     ///     - filter - filter cells by following conditions, all conditions are optional
     ///         - script: if search script type is lock, filter cells by type script, and vice versa
     ///         - block_range: [u64; 2], filter cells by block number range, [inclusive, exclusive]
+    ///     - filter_expression - filter cells by a boolean expression, optional, combined with `filter` by `and`
+    ///         - and: [expression], matches if all the sub-expressions match
+    ///         - or: [expression], matches if any of the sub-expressions matches
+    ///         - not: expression, matches if the sub-expression does not match
+    ///         - script: { script_type, script, script_search_mode }, filter cells by lock or type script, only non-null script with `exact` search mode is supported
+    ///         - block_range: [u64; 2], filter cells by block number range, [inclusive, exclusive]
     ///     - group_by_transaction - bool, optional default is `false`, if group_by_transaction is set to true, the returning objects will be grouped by the tx hash
     /// * order: enum, asc | desc
     /// * limit: result size limit
@@ -826,6 +919,7 @@ pub trait IndexerRpc {
     ///         - output_data_len_range: [u64; 2], filter cells by output data len range, [inclusive, exclusive]
     ///         - output_capacity_range: [u64; 2], filter cells by output capacity range, [inclusive, exclusive]
     ///         - block_range: [u64; 2], filter cells by block number range, [inclusive, exclusive]
+    ///     - filter_expression - filter cells by a boolean expression, optional, combined with `filter` by `and`, see `get_cells`
     ///
     /// ## Returns
     ///
@@ -873,16 +967,172 @@ pub trait IndexerRpc {
         &self,
         search_key: IndexerSearchKey,
     ) -> Result<Option<IndexerCellsCapacity>>;
+
+    /// Returns the live cells capacity by the lock or type script at a past block.
+    ///
+    /// The live cells at the block are reconstructed from the indexed transactions of the script,
+    /// so the cost grows with the number of transactions of the script. A script with more than
+    /// 100,000 indexed input and output records is rejected with an error.
+    ///
+    /// The consumed cells are resolved from the indexer rollback data of the latest blocks, or from
+    /// the transactions in the chain store once the rollback data has been pruned.
+    ///
+    /// ## Parameters
+    ///
+    /// * search_key:
+    ///     - script - Script
+    ///     - script_type - enum, lock | type
+    ///     - script_search_mode - enum, prefix | exact
+    ///     - filter and filter_expression are not supported
+    /// * block_number - the block number, should not be higher than the indexed tip
+    /// * with_cells - bool, optional default is `false`, returns the out points of the live cells if it is set to true
+    ///
+    /// ## Returns
+    ///
+    ///  * capacity - total capacity at the block
+    ///  * cells - out points of the live cells at the block, null if `with_cells` is not set
+    ///  * block_hash - the block hash
+    ///  * block_number - the block number
+    ///
+    /// ## Examples
+    ///
+    /// Request
+    ///
+    /// ```json
+    /// {
+    ///     "id": 2,
+    ///     "jsonrpc": "2.0",
+    ///     "method": "get_cells_capacity_at_block",
+    ///     "params": [
+    ///         {
+    ///             "script": {
+    ///                 "code_hash": "0x9bd7e06f3ecf4be0f2fcd2188b23f1b9fcc88e5d4b65a8637b17723bbda3cce8",
+    ///                 "hash_type": "type",
+    ///                 "args": "0x5989ae415bb667931a99896e5fbbfad9ba53a223"
+    ///             },
+    ///             "script_type": "lock",
+    ///             "script_search_mode": "exact"
+    ///         },
+    ///         "0x5b59df",
+    ///         true
+    ///     ]
+    /// }
+    /// ```
+    ///
+    /// Response
+    ///
+    /// ```json
+    /// {
+    ///     "jsonrpc": "2.0",
+    ///     "result": {
+    ///       "block_hash": "0x3cb4d7a9e3a5bb2b8da6b3b1e3a6f6ed2a2c5d5b2f0c0e1d8e6c5c4b3a291807",
+    ///       "block_number": "0x5b59df",
+    ///       "capacity": "0xe815b81c0",
+    ///       "cells": [
+    ///         {
+    ///           "index": "0x1",
+    ///           "tx_hash": "0x21c4632a41140b828e9347ff80480b3e07be4e0a0b8d577565e7421fd5473194"
+    ///         }
+    ///       ]
+    ///     },
+    ///     "id": 2
+    /// }
+    /// ```
+    #[rpc(name = "get_cells_capacity_at_block")]
+    fn get_cells_capacity_at_block(
+        &self,
+        search_key: IndexerSearchKey,
+        block_number: BlockNumber,
+        with_cells: Option<bool>,
+    ) -> Result<Option<IndexerCellsCapacityAtBlock>>;
+
+    /// Returns the total UDT amounts of the live cells by the lock or type script, grouped by type script.
+    ///
+    /// The amount of a cell is the first 16 bytes of its data as little-endian u128, which is shared
+    /// by sUDT and xUDT. Cells without type script or with less than 16 bytes data are skipped.
+    ///
+    /// ## Parameters
+    ///
+    /// * search_key:
+    ///     - script - Script
+    ///     - script_type - enum, lock | type
+    ///     - filter - filter cells by following conditions, all conditions are optional
+    ///         - script: if search script type is lock, filter cells by type script prefix, and vice versa
+    ///         - output_data_len_range: [u64; 2], filter cells by output data len range, [inclusive, exclusive]
+    ///         - output_capacity_range: [u64; 2], filter cells by output capacity range, [inclusive, exclusive]
+    ///         - block_range: [u64; 2], filter cells by block number range, [inclusive, exclusive]
+    ///     - filter_expression - filter cells by a boolean expression, optional, combined with `filter` by `and`, see `get_cells`
+    ///
+    /// ## Returns
+    ///
+    ///  * udt_amounts - UDT amounts sorted by type script
+    ///     - type_script - the type script of the UDT
+    ///     - amount - total amount, the call fails with an `Indexer` error if the total of any UDT exceeds the u128 max value
+    ///     - cells_count - the number of cells summed up
+    ///  * block_hash - indexed tip block hash
+    ///  * block_number - indexed tip block number
+    ///
+    /// ## Examples
+    ///
+    /// Request
+    ///
+    /// ```json
+    /// {
+    ///     "id": 2,
+    ///     "jsonrpc": "2.0",
+    ///     "method": "get_cells_udt_amount",
+    ///     "params": [
+    ///         {
+    ///             "script": {
+    ///                 "code_hash": "0x9bd7e06f3ecf4be0f2fcd2188b23f1b9fcc88e5d4b65a8637b17723bbda3cce8",
+    ///                 "hash_type": "type",
+    ///                 "args": "0x5989ae415bb667931a99896e5fbbfad9ba53a223"
+    ///             },
+    ///             "script_type": "lock"
+    ///         }
+    ///     ]
+    /// }
+    /// ```
+    ///
+    /// Response
+    ///
+    /// ```json
+    /// {
+    ///     "jsonrpc": "2.0",
+    ///     "result": {
+    ///       "block_hash": "0xbc52444952dc5eb01a7826aaf6bb1b660db01797414e259e7a6e6d636de8fc7c",
+    ///       "block_number": "0x5b727a",
+    ///       "udt_amounts": [
+    ///         {
+    ///           "amount": "0x2540be400",
+    ///           "cells_count": "0x2",
+    ///           "type_script": {
+    ///             "args": "0x7c7f0ee1d582c385342367792946cff3767fe02f26fd7f07dba23ae3c65b28bc",
+    ///             "code_hash": "0x5e7a36a77e68eecc013dfa2fe6a23f3b6c344b04005808694ae6dd45eea4cfd5",
+    ///             "hash_type": "type"
+    ///           }
+    ///         }
+    ///       ]
+    ///     },
+    ///     "id": 2
+    /// }
+    /// ```
+    #[rpc(name = "get_cells_udt_amount")]
+    fn get_cells_udt_amount(
+        &self,
+        search_key: IndexerSearchKey,
+    ) -> Result<Option<IndexerCellsUdtAmount>>;
 }
 
 #[derive(Clone)]
 pub(crate) struct IndexerRpcImpl {
     pub(crate) handle: IndexerHandle,
+    pub(crate) shared: Shared,
 }
 
 impl IndexerRpcImpl {
-    pub fn new(handle: IndexerHandle) -> Self {
-        IndexerRpcImpl { handle }
+    pub fn new(handle: IndexerHandle, shared: Shared) -> Self {
+        IndexerRpcImpl { handle, shared }
     }
 }
 
@@ -926,4 +1176,30 @@ impl IndexerRpc for IndexerRpcImpl {
             .get_cells_capacity(search_key)
             .map_err(|e| RPCError::custom(RPCError::Indexer, e))
     }
+
+    fn get_cells_capacity_at_block(
+        &self,
+        search_key: IndexerSearchKey,
+        block_number: BlockNumber,
+        with_cells: Option<bool>,
+    ) -> Result<Option<IndexerCellsCapacityAtBlock>> {
+        let snapshot = self.shared.snapshot();
+        self.handle
+            .get_cells_capacity_at_block(
+                &**snapshot,
+                search_key,
+                block_number.into(),
+                with_cells.unwrap_or_default(),
+            )
+            .map_err(|e| RPCError::custom(RPCError::Indexer, e))
+    }
+
+    fn get_cells_udt_amount(
+        &self,
+        search_key: IndexerSearchKey,
+    ) -> Result<Option<IndexerCellsUdtAmount>> {
+        self.handle
+            .get_cells_udt_amount(search_key)
+            .map_err(|e| RPCError::custom(RPCError::Indexer, e))
+    }
 }
@@ -119,7 +119,7 @@ mod miner;
 mod net;
 pub(crate) mod pool;
 mod stats;
-mod subscription;
+pub(crate) mod subscription;
 mod test;
 
 pub(crate) use self::alert::AlertRpcImpl;
@@ -1,8 +1,8 @@
 use crate::error::RPCError;
 use async_trait::async_trait;
 use ckb_jsonrpc_types::{
-    BannedAddr, LocalNode, LocalNodeProtocol, NodeAddress, PeerSyncState, RemoteNode,
-    RemoteNodeProtocol, SyncState, Timestamp,
+    BannedAddr, LocalNode, LocalNodeProtocol, NodeAddress, PeerDownloadStats, PeerSyncState,
+    RemoteNode, RemoteNodeProtocol, SyncState, Timestamp,
 };
 use ckb_network::{extract_peer_id, multiaddr::Multiaddr, NetworkController};
 use ckb_sync::SyncShared;
@@ -114,6 +114,7 @@ pub trait NetRpc {
     ///       "connected_duration": "0x2f",
     ///       "is_outbound": true,
     ///       "last_ping_duration": "0x1a",
+    ///       "misbehavior_score": "0x0",
     ///       "node_id": "QmXwUgF48ULy6hkgfqrEwEfuHW7WyWyWauueRDAYQHNDfN",
     ///       "protocols": [
     ///         {
@@ -149,6 +150,8 @@ pub trait NetRpc {
     ///           "version": "0.0.1"
     ///         }
     ///       ],
+    ///       "received_bytes": "0x2b7c",
+    ///       "sent_bytes": "0x1e4a",
     ///       "sync_state": {
     ///         "best_known_header_hash": null,
     ///         "best_known_header_number": null,
@@ -170,6 +173,7 @@ pub trait NetRpc {
     ///       "connected_duration": "0x95",
     ///       "is_outbound": true,
     ///       "last_ping_duration": "0x41",
+    ///       "misbehavior_score": "0x0",
     ///       "node_id": "QmSrkzhdBMmfCGx8tQGwgXxzBg8kLtX8qMcqECMuKWsxDV",
     ///       "protocols": [
     ///         {
@@ -205,6 +209,8 @@ pub trait NetRpc {
     ///           "version": "0.0.1"
     ///         }
     ///       ],
+    ///       "received_bytes": "0x2b7c",
+    ///       "sent_bytes": "0x1e4a",
     ///       "sync_state": {
     ///         "best_known_header_hash": "0x2157c72b3eddd41a7a14c361173cd22ef27d7e0a29eda2e511ee0b3598c0b895",
     ///         "best_known_header_number": "0xdb835",
@@ -363,6 +369,7 @@ pub trait NetRpc {
     ///   "result": {
     ///     "best_known_block_number": "0x400",
     ///     "best_known_block_timestamp": "0x5cd2b117",
+    ///     "download_peers": [],
     ///     "fast_time": "0x3e8",
     ///     "ibd": true,
     ///     "inflight_blocks_count": "0x0",
@@ -613,6 +620,10 @@ impl NetRpc for NetRpcImpl {
                     last_ping_duration: peer
                         .ping_rtt
                         .map(|duration| (duration.as_millis() as u64).into()),
+                    misbehavior_score: u64::from(peer.misbehavior.score(std::time::Instant::now()))
+                        .into(),
+                    received_bytes: peer.traffic.received_bytes().into(),
+                    sent_bytes: peer.traffic.sent_bytes().into(),
                     sync_state: self.sync_shared.state().peers().state.get(peer_index).map(
                         |state| PeerSyncState {
                             best_known_header_hash: state
@@ -718,6 +729,29 @@ impl NetRpc for NetRpcImpl {
         let state = chain.shared().state();
         let (fast_time, normal_time, low_time) = state.read_inflight_blocks().division_point();
         let best_known = state.shared_best_header();
+        let download_peers = {
+            let connected_peers = self.network_controller.connected_peers();
+            let inflight_blocks = state.read_inflight_blocks();
+            inflight_blocks
+                .download_schedulers_iter()
+                .filter_map(|(peer_index, scheduler)| {
+                    let (_, peer) = connected_peers
+                        .iter()
+                        .find(|(index, _)| index == peer_index)?;
+                    Some(PeerDownloadStats {
+                        node_id: extract_peer_id(&peer.connected_addr)
+                            .map(|peer_id| peer_id.to_base58())
+                            .unwrap_or_default(),
+                        inflight_count: (scheduler.inflight_count() as u64).into(),
+                        task_count: (scheduler.task_count() as u64).into(),
+                        received_count: scheduler.received_count().into(),
+                        stalled_count: scheduler.stalled_count().into(),
+                        avg_response_time: scheduler.avg_response_time().map(Into::into),
+                        throughput: scheduler.throughput().map(Into::into),
+                    })
+                })
+                .collect()
+        };
         let sync_state = SyncState {
             ibd: chain.is_initial_block_download(),
             best_known_block_number: best_known.number().into(),
@@ -728,6 +762,7 @@ impl NetRpc for NetRpcImpl {
             fast_time: fast_time.into(),
             normal_time: normal_time.into(),
             low_time: low_time.into(),
+            download_peers,
         };
 
         Ok(sync_state)
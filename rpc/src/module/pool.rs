@@ -3,10 +3,12 @@ use async_trait::async_trait;
 use ckb_chain_spec::consensus::Consensus;
 use ckb_constant::hardfork::{mainnet, testnet};
 use ckb_jsonrpc_types::{
-    OutputsValidator, PoolTxDetailInfo, RawTxPool, Script, Transaction, TxPoolInfo,
+    EstimateMode, OutputsValidator, PoolTxDetailInfo, RawTxPool, Script, Transaction, TxPoolInfo,
+    Uint64,
 };
 use ckb_logger::error;
 use ckb_shared::shared::Shared;
+use ckb_tx_pool::MAX_TARGET_BLOCKS;
 use ckb_types::{core, packed, prelude::*, H256};
 use ckb_verification::{Since, SinceMetric};
 use jsonrpc_core::Result;
@@ -34,6 +36,7 @@ pub trait PoolRpc {
     /// * [`PoolRejectedTransactionByOutputsValidator (-1102)`](../enum.RPCError.html#variant.PoolRejectedTransactionByOutputsValidator) - The transaction is rejected by the validator specified by `outputs_validator`. If you really want to send transactions with advanced scripts, please set `outputs_validator` to "passthrough".
     /// * [`PoolRejectedTransactionByMinFeeRate (-1104)`](../enum.RPCError.html#variant.PoolRejectedTransactionByMinFeeRate) - The transaction fee rate must be greater than or equal to the config option `tx_pool.min_fee_rate`.
     /// * [`PoolRejectedTransactionByMaxAncestorsCountLimit (-1105)`](../enum.RPCError.html#variant.PoolRejectedTransactionByMaxAncestorsCountLimit) - The ancestors count must be greater than or equal to the config option `tx_pool.max_ancestors_count`.
+    /// * [`PoolRejectedTransactionByLockScriptLimit (-1112)`](../enum.RPCError.html#variant.PoolRejectedTransactionByLockScriptLimit) - The in-pool transactions spending one of the input lock scripts reached the limits.
     /// * [`PoolIsFull (-1106)`](../enum.RPCError.html#variant.PoolIsFull) - Pool is full.
     /// * [`PoolRejectedDuplicatedTransaction (-1107)`](../enum.RPCError.html#variant.PoolRejectedDuplicatedTransaction) - The transaction is already in the pool.
     /// * [`TransactionFailedToResolve (-301)`](../enum.RPCError.html#variant.TransactionFailedToResolve) - Failed to resolve the referenced cells and headers used in the transaction, as inputs or dependencies.
@@ -109,6 +112,113 @@ pub trait PoolRpc {
         outputs_validator: Option<OutputsValidator>,
     ) -> Result<H256>;
 
+    /// Submits a package of transactions into the transaction pool atomically.
+    ///
+    /// The transactions in the package must be topologically ordered, which means a transaction
+    /// can only spend the outputs of the transactions before it in the package. The package must
+    /// also be connected, which means every transaction except the last one, the child, is an
+    /// ancestor of the child. The package is admitted only if all the transactions are valid. The fee rate is evaluated for the whole
+    /// package instead of each transaction, so a parent transaction whose fee rate is lower than
+    /// `tx_pool.min_fee_rate` can be paid by its children in the same package. The transactions
+    /// already in the pool are skipped, only the rest are evaluated. If RBF is enabled, a
+    /// transaction in the package can replace the pool transactions spending the same inputs,
+    /// and it must pay for the replaced transactions by the same rules as `send_transaction`.
+    ///
+    /// ## Params
+    ///
+    /// * `transactions` - The topologically ordered transactions, at most 25 transactions.
+    /// * `outputs_validator` - Validates the outputs of every transaction before entering the tx-pool. (**Optional**, default is "passthrough").
+    ///
+    /// ## Returns
+    ///
+    /// The hashes of the transactions in the package.
+    ///
+    /// ## Errors
+    ///
+    /// * [`PoolRejectedTransactionByOutputsValidator (-1102)`](../enum.RPCError.html#variant.PoolRejectedTransactionByOutputsValidator) - A transaction is rejected by the validator specified by `outputs_validator`.
+    /// * [`PoolRejectedTransactionByMinFeeRate (-1104)`](../enum.RPCError.html#variant.PoolRejectedTransactionByMinFeeRate) - The package fee rate must be greater than or equal to the config option `tx_pool.min_fee_rate`.
+    /// * [`PoolRejectedTransactionByMaxAncestorsCountLimit (-1105)`](../enum.RPCError.html#variant.PoolRejectedTransactionByMaxAncestorsCountLimit) - The ancestors count must be greater than or equal to the config option `tx_pool.max_ancestors_count`.
+    /// * [`PoolRejectedTransactionByLockScriptLimit (-1112)`](../enum.RPCError.html#variant.PoolRejectedTransactionByLockScriptLimit) - The in-pool transactions spending one of the input lock scripts reached the limits.
+    /// * [`PoolRejectedDuplicatedTransaction (-1107)`](../enum.RPCError.html#variant.PoolRejectedDuplicatedTransaction) - All the transactions are already in the pool, or a transaction appears twice in the package.
+    /// * [`PoolRejectedRBF (-1111)`](../enum.RPCError.html#variant.PoolRejectedRBF) - A transaction in the package doesn't pay enough to replace its conflicts, or the package spends the outputs of the replaced transactions.
+    /// * [`PoolRejectedMalformedTransaction (-1108)`](../enum.RPCError.html#variant.PoolRejectedMalformedTransaction) - The package is empty, has too many transactions, or is not connected.
+    /// * [`TransactionFailedToResolve (-301)`](../enum.RPCError.html#variant.TransactionFailedToResolve) - Failed to resolve the referenced cells and headers used in a transaction, e.g., the package is not topologically ordered.
+    /// * [`TransactionFailedToVerify (-302)`](../enum.RPCError.html#variant.TransactionFailedToVerify) - Failed to verify a transaction.
+    ///
+    /// ## Examples
+    ///
+    /// Request
+    ///
+    /// ```json
+    /// {
+    ///   "id": 42,
+    ///   "jsonrpc": "2.0",
+    ///   "method": "send_package",
+    ///   "params": [
+    ///     [
+    ///       {
+    ///         "cell_deps": [
+    ///           {
+    ///             "dep_type": "code",
+    ///             "out_point": {
+    ///               "index": "0x0",
+    ///               "tx_hash": "0xa4037a893eb48e18ed4ef61034ce26eba9c585f15c9cee102ae58505565eccc3"
+    ///             }
+    ///           }
+    ///         ],
+    ///         "header_deps": [
+    ///           "0x7978ec7ce5b507cfb52e149e36b1a23f6062ed150503c85bbf825da3599095ed"
+    ///         ],
+    ///         "inputs": [
+    ///           {
+    ///             "previous_output": {
+    ///               "index": "0x0",
+    ///               "tx_hash": "0x365698b50ca0da75dca2c87f9e7b563811d3b5813736b8cc62cc3b106faceb17"
+    ///             },
+    ///             "since": "0x0"
+    ///           }
+    ///         ],
+    ///         "outputs": [
+    ///           {
+    ///             "capacity": "0x2540be400",
+    ///             "lock": {
+    ///               "code_hash": "0x28e83a1277d48add8e72fadaa9248559e1b632bab2bd60b27955ebc4c03800a5",
+    ///               "hash_type": "data",
+    ///               "args": "0x"
+    ///             },
+    ///             "type": null
+    ///           }
+    ///         ],
+    ///         "outputs_data": [
+    ///           "0x"
+    ///         ],
+    ///         "version": "0x0",
+    ///         "witnesses": []
+    ///       }
+    ///     ],
+    ///     "passthrough"
+    ///   ]
+    /// }
+    /// ```
+    ///
+    /// Response
+    ///
+    /// ```json
+    /// {
+    ///   "id": 42,
+    ///   "jsonrpc": "2.0",
+    ///   "result": [
+    ///     "0xa0ef4eb5f4ceeb08a4c8524d84c5da95dce2f608e0ca2ec8091191b0f330c6e3"
+    ///   ]
+    /// }
+    /// ```
+    #[rpc(name = "send_package")]
+    fn send_package(
+        &self,
+        transactions: Vec<Transaction>,
+        outputs_validator: Option<OutputsValidator>,
+    ) -> Result<Vec<H256>>;
+
     /// Removes a transaction and all transactions which depends on it from tx pool if it exists.
     ///
     /// ## Params
@@ -303,6 +413,55 @@ pub trait PoolRpc {
     #[rpc(name = "get_pool_tx_detail_info")]
     fn get_pool_tx_detail_info(&self, tx_hash: H256) -> Result<PoolTxDetailInfo>;
 
+    /// Estimates the fee rate required to get a transaction committed within the target blocks.
+    ///
+    /// The tx-pool tracks the fee rates of the transactions entering the pool and how many blocks
+    /// they take to be committed. Unlike [`get_fee_rate_statistics`](trait.ChainRpc.html#tymethod.get_fee_rate_statistics),
+    /// the estimation takes the transactions which are still waiting in the pool into account,
+    /// so it reflects the congestion.
+    ///
+    /// ## Params
+    ///
+    /// * `target_blocks` - The number of blocks the transaction is expected to be committed within, in the range `[1, 64]`.
+    /// * `estimate_mode` - The estimate mode. (**Optional**, default is "economical").
+    ///
+    /// ## Returns
+    ///
+    /// The estimated fee rate in shannons per 1000 bytes transaction serialization size in the block,
+    /// or null if there is not sufficient data.
+    ///
+    /// ## Examples
+    ///
+    /// Request
+    ///
+    /// ```json
+    /// {
+    ///   "id": 42,
+    ///   "jsonrpc": "2.0",
+    ///   "method": "estimate_fee_rate",
+    ///   "params": [
+    ///     "0xa",
+    ///     "economical"
+    ///   ]
+    /// }
+    /// ```
+    ///
+    /// Response
+    ///
+    /// ```json
+    /// {
+    ///   "id": 42,
+    ///   "jsonrpc": "2.0",
+    ///   "result": null
+    /// }
+    /// ```
+    #[rpc(name = "estimate_fee_rate")]
+    fn estimate_fee_rate(
+        &self,
+        target_blocks: Uint64,
+        estimate_mode: Option<EstimateMode>,
+    ) -> Result<Option<Uint64>>;
+
     /// Returns whether tx-pool service is started, ready for request.
     ///
     /// ## Examples
@@ -358,6 +517,33 @@ impl PoolRpcImpl {
             well_known_type_scripts,
         }
     }
+
+    fn validate_outputs(
+        &self,
+        tx: &core::TransactionView,
+        outputs_validator: Option<OutputsValidator>,
+    ) -> Result<()> {
+        if let Err(e) = match outputs_validator {
+            None | Some(OutputsValidator::Passthrough) => Ok(()),
+            Some(OutputsValidator::WellKnownScriptsOnly) => WellKnownScriptsOnlyValidator::new(
+                self.shared.consensus(),
+                &self.well_known_lock_scripts,
+                &self.well_known_type_scripts,
+            )
+            .validate(tx),
+        } {
+            return Err(RPCError::custom_with_data(
+                RPCError::PoolRejectedTransactionByOutputsValidator,
+                format!(
+                    "The transaction is rejected by OutputsValidator set in params[1]: {}. \
+                    Please check the related information in https://github.com/nervosnetwork/ckb/wiki/Transaction-%C2%BB-Default-Outputs-Validator",
+                    outputs_validator.unwrap_or(OutputsValidator::WellKnownScriptsOnly).json_display()
+                ),
+                e,
+            ));
+        }
+        Ok(())
+    }
 }
 
 /// Build well known lock scripts
@@ -451,25 +637,7 @@ impl PoolRpc for PoolRpcImpl {
         let tx: packed::Transaction = tx.into();
         let tx: core::TransactionView = tx.into_view();
 
-        if let Err(e) = match outputs_validator {
-            None | Some(OutputsValidator::Passthrough) => Ok(()),
-            Some(OutputsValidator::WellKnownScriptsOnly) => WellKnownScriptsOnlyValidator::new(
-                self.shared.consensus(),
-                &self.well_known_lock_scripts,
-                &self.well_known_type_scripts,
-            )
-            .validate(&tx),
-        } {
-            return Err(RPCError::custom_with_data(
-                RPCError::PoolRejectedTransactionByOutputsValidator,
-                format!(
-                    "The transaction is rejected by OutputsValidator set in params[1]: {}. \
-                    Please check the related information in https://github.com/nervosnetwork/ckb/wiki/Transaction-%C2%BB-Default-Outputs-Validator",
-                    outputs_validator.unwrap_or(OutputsValidator::WellKnownScriptsOnly).json_display()
-                ),
-                e,
-            ));
-        }
+        self.validate_outputs(&tx, outputs_validator)?;
 
         let tx_pool = self.shared.tx_pool_controller();
         let submit_tx = tx_pool.submit_local_tx(tx.clone());
@@ -486,6 +654,32 @@ impl PoolRpc for PoolRpcImpl {
         }
     }
 
+    fn send_package(
+        &self,
+        transactions: Vec<Transaction>,
+        outputs_validator: Option<OutputsValidator>,
+    ) -> Result<Vec<H256>> {
+        let txs: Vec<core::TransactionView> = transactions
+            .into_iter()
+            .map(|tx| packed::Transaction::from(tx).into_view())
+            .collect();
+
+        for tx in &txs {
+            self.validate_outputs(tx, outputs_validator.clone())?;
+        }
+
+        let tx_hashes: Vec<H256> = txs.iter().map(|tx| tx.hash().unpack()).collect();
+        let tx_pool = self.shared.tx_pool_controller();
+        match tx_pool.submit_local_package(txs) {
+            Ok(Ok(_)) => Ok(tx_hashes),
+            Ok(Err(reject)) => Err(RPCError::from_submit_transaction_reject(&reject)),
+            Err(e) => {
+                error!("Send submit_package request error {}", e);
+                Err(RPCError::ckb_internal_error(e))
+            }
+        }
+    }
+
     fn remove_transaction(&self, tx_hash: H256) -> Result<bool> {
         let tx_pool = self.shared.tx_pool_controller();
 
@@ -542,6 +736,24 @@ impl PoolRpc for PoolRpcImpl {
             .map_err(|err| RPCError::custom(RPCError::CKBInternalError, err.to_string()))?;
         Ok(tx_detail.into())
     }
+
+    fn estimate_fee_rate(
+        &self,
+        target_blocks: Uint64,
+        estimate_mode: Option<EstimateMode>,
+    ) -> Result<Option<Uint64>> {
+        let target_blocks: u64 = target_blocks.into();
+        if !(1..=MAX_TARGET_BLOCKS).contains(&target_blocks) {
+            return Err(RPCError::invalid_params(format!(
+                "Expected target_blocks in range [1, {MAX_TARGET_BLOCKS}], got {target_blocks}"
+            )));
+        }
+        let tx_pool = self.shared.tx_pool_controller();
+        let fee_rate = tx_pool
+            .estimate_fee_rate(target_blocks, estimate_mode.unwrap_or_default().into())
+            .map_err(|err| RPCError::custom(RPCError::CKBInternalError, err.to_string()))?;
+        Ok(fee_rate.map(|fee_rate| fee_rate.as_u64().into()))
+    }
 }
 
 pub(crate) struct WellKnownScriptsOnlyValidator<'a> {
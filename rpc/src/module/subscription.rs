@@ -1,12 +1,28 @@
+use crate::error::RPCError;
 use async_trait::async_trait;
 use ckb_async_runtime::Handle;
-use ckb_jsonrpc_types::Topic;
-use ckb_notify::NotifyController;
+use ckb_jsonrpc_types::{
+    IndexerCell, IndexerCellsChanged, IndexerCellsChangedFilter, IndexerScriptSearchMode,
+    IndexerScriptType, SubscriptionFilter, Topic, TransactionStatusChanged,
+    TransactionStatusFilter, TransactionStatusTransition,
+};
+use ckb_notify::{CanonicalChainChanged, ChangedCell, NotifyController, TransactionStatusEvent};
+use ckb_shared::shared::Shared;
 use ckb_stop_handler::new_tokio_exit_rx;
+use ckb_store::ChainStore;
+use ckb_types::{
+    core::{tx_pool::Reject, HeaderView},
+    packed,
+    prelude::*,
+    H256,
+};
 use futures_util::{stream::BoxStream, Stream};
 use jsonrpc_core::Result;
 use jsonrpc_utils::{pub_sub::PublishMsg, rpc};
-use tokio::sync::broadcast;
+use std::collections::HashSet;
+use tokio::sync::broadcast::{self, error::RecvError};
+
+const SUBSCRIBER_NAME: &str = "TcpSubscription";
 
 /// RPC Module Subscription that CKB node will push new messages to subscribers.
 ///
@@ -56,7 +72,14 @@ pub trait SubscriptionRpc {
     ///
     /// ## Params
     ///
-    /// * `topic` - Subscription topic (enum: new_tip_header | new_tip_block | new_transaction | proposed_transaction | rejected_transaction)
+    /// * `topic` - Subscription topic (enum: new_tip_header | new_tip_block | new_transaction | proposed_transaction | rejected_transaction | cells_changed | transaction_status)
+    /// * `filter` - Required by the `cells_changed` and `transaction_status` topics and must be null for the others
+    ///     - For `cells_changed`, the script filter
+    ///         - script - Script
+    ///         - script_type - enum, lock | type
+    ///         - script_search_mode - enum, prefix | exact, optional default is `prefix`
+    ///     - For `transaction_status`, the transactions filter
+    ///         - tx_hashes - the hashes of the transactions to watch
     ///
     /// ## Returns
     ///
@@ -116,6 +139,34 @@ pub trait SubscriptionRpc {
     /// -   the first item type is [`PoolTransactionEntry`](../../ckb_jsonrpc_types/struct.PoolTransactionEntry.html), and
     /// -   the second item type is [`PoolTransactionReject`](../../ckb_jsonrpc_types/struct.PoolTransactionReject.html).
     ///
+    /// ### `cells_changed`
+    ///
+    /// Subscribers will get notified when the built-in indexer appends or rolls back a block which
+    /// creates or consumes cells of the script in `filter`. A rolled back block is notified with
+    /// `rollback` set to true, which means its `created` cells are removed and its `consumed`
+    /// cells are live again. The indexer must be enabled to receive the messages.
+    ///
+    /// The type of the `params.result` in the push message is [`IndexerCellsChanged`](../../ckb_jsonrpc_types/struct.IndexerCellsChanged.html).
+    ///
+    /// The messages are pushed in the order the indexer applies the blocks. If the subscriber
+    /// falls behind and misses messages, the CKB node pushes a `SubscriptionLagged` error in
+    /// `params.error` and closes the subscription, the subscriber should re-sync the cells and
+    /// subscribe again.
+    ///
+    /// ### `transaction_status`
+    ///
+    /// Subscribers will get notified on every status transition of the transactions in
+    /// `filter.tx_hashes`: added to the pool as `pending`, `proposed`, `committed` in a block,
+    /// abandoned by the pool or rejected on submission as `rejected`, removed from the pool by an
+    /// RBF transaction as `replaced`, and `detached` when the block which commits the transaction
+    /// is rolled back by a chain reorganization.
+    ///
+    /// The transitions are pushed in the order they happen. If the subscriber falls behind and
+    /// misses messages, the CKB node pushes a `SubscriptionLagged` error in `params.error` and
+    /// closes the subscription.
+    ///
+    /// The type of the `params.result` in the push message is [`TransactionStatusChanged`](../../ckb_jsonrpc_types/struct.TransactionStatusChanged.html).
+    ///
     /// ## Examples
     ///
     /// Subscribe Request
@@ -165,7 +216,7 @@ pub trait SubscriptionRpc {
     /// ```
     ///
     #[rpc(pub_sub(notify = "subscribe", unsubscribe = "unsubscribe"))]
-    fn subscribe(&self, topic: Topic) -> Result<Self::S>;
+    fn subscribe(&self, topic: Topic, filter: Option<SubscriptionFilter>) -> Result<Self::S>;
 }
 
 #[derive(Clone)]
@@ -175,6 +226,8 @@ pub struct SubscriptionRpcImpl {
     pub new_transaction_sender: broadcast::Sender<PublishMsg<String>>,
     pub proposed_transaction_sender: broadcast::Sender<PublishMsg<String>>,
     pub new_reject_transaction_sender: broadcast::Sender<PublishMsg<String>>,
+    pub notify_controller: NotifyController,
+    pub shared: Shared,
 }
 
 macro_rules! publiser_send {
@@ -188,27 +241,109 @@ macro_rules! publiser_send {
 #[async_trait]
 impl SubscriptionRpc for SubscriptionRpcImpl {
     type S = BoxStream<'static, PublishMsg<String>>;
-    fn subscribe(&self, topic: Topic) -> Result<Self::S> {
-        let tx = match topic {
-            Topic::NewTipHeader => self.new_tip_header_sender.clone(),
-            Topic::NewTipBlock => self.new_tip_block_sender.clone(),
-            Topic::NewTransaction => self.new_transaction_sender.clone(),
-            Topic::ProposedTransaction => self.proposed_transaction_sender.clone(),
-            Topic::RejectedTransaction => self.new_reject_transaction_sender.clone(),
+    fn subscribe(&self, topic: Topic, filter: Option<SubscriptionFilter>) -> Result<Self::S> {
+        let tx = match (topic, filter) {
+            (Topic::CellsChanged, Some(SubscriptionFilter::CellsChanged(filter))) => {
+                return self.subscribe_cells_changed(filter);
+            }
+            (Topic::TransactionStatus, Some(SubscriptionFilter::TransactionStatus(filter))) => {
+                return self.subscribe_transaction_status(filter);
+            }
+            (Topic::CellsChanged, _) => {
+                return Err(RPCError::invalid_params(
+                    "a script filter is required by the cells_changed topic",
+                ));
+            }
+            (Topic::TransactionStatus, _) => {
+                return Err(RPCError::invalid_params(
+                    "a tx_hashes filter is required by the transaction_status topic",
+                ));
+            }
+            (_, Some(_)) => {
+                return Err(RPCError::invalid_params(
+                    "filter is only supported by the cells_changed and transaction_status topics",
+                ));
+            }
+            (Topic::NewTipHeader, None) => self.new_tip_header_sender.clone(),
+            (Topic::NewTipBlock, None) => self.new_tip_block_sender.clone(),
+            (Topic::NewTransaction, None) => self.new_transaction_sender.clone(),
+            (Topic::ProposedTransaction, None) => self.proposed_transaction_sender.clone(),
+            (Topic::RejectedTransaction, None) => self.new_reject_transaction_sender.clone(),
         };
         let mut rx = tx.subscribe();
         Ok(Box::pin(async_stream::stream! {
-               while let Ok(msg) = rx.recv().await {
-                    yield msg;
+               loop {
+                    match rx.recv().await {
+                        Ok(msg) => yield msg,
+                        Err(RecvError::Lagged(skipped)) => {
+                            yield lagged_error(skipped);
+                            break;
+                        }
+                        Err(RecvError::Closed) => break,
+                    }
                }
         }))
     }
 }
 
 impl SubscriptionRpcImpl {
-    pub fn new(notify_controller: NotifyController, handle: Handle) -> Self {
-        const SUBSCRIBER_NAME: &str = "TcpSubscription";
+    fn subscribe_cells_changed(
+        &self,
+        filter: IndexerCellsChangedFilter,
+    ) -> Result<BoxStream<'static, PublishMsg<String>>> {
+        let filter = CellsChangedFilter::new(filter);
+        let mut rx = self
+            .notify_controller
+            .subscribe_indexer_cells_changed(SUBSCRIBER_NAME);
+        Ok(Box::pin(async_stream::stream! {
+               loop {
+                    match rx.recv().await {
+                        Ok(cells_changed) => {
+                            if let Some(msg) = filter.filter(&cells_changed) {
+                                let json_string = serde_json::to_string(&msg).expect("serialization should be ok");
+                                yield PublishMsg::result(&json_string);
+                            }
+                        }
+                        Err(RecvError::Lagged(skipped)) => {
+                            yield lagged_error(skipped);
+                            break;
+                        }
+                        Err(RecvError::Closed) => break,
+                    }
+               }
+        }))
+    }
+
+    fn subscribe_transaction_status(
+        &self,
+        filter: TransactionStatusFilter,
+    ) -> Result<BoxStream<'static, PublishMsg<String>>> {
+        let tx_hashes: HashSet<H256> = filter.tx_hashes.into_iter().collect();
+        let shared = self.shared.clone();
+        let mut rx = self
+            .notify_controller
+            .subscribe_transaction_status(SUBSCRIBER_NAME);
+        Ok(Box::pin(async_stream::stream! {
+               loop {
+                    match rx.recv().await {
+                        Ok(event) => {
+                            for change in transaction_status_changed(shared.store(), &event).iter().filter(|change| tx_hashes.contains(&change.tx_hash)) {
+                                let json_string = serde_json::to_string(change).expect("serialization should be ok");
+                                yield PublishMsg::result(&json_string);
+                            }
+                        }
+                        Err(RecvError::Lagged(skipped)) => {
+                            yield lagged_error(skipped);
+                            break;
+                        }
+                        Err(RecvError::Closed) => break,
+                    }
+               }
+        }))
+    }
 
+    pub fn new(shared: Shared, handle: Handle) -> Self {
+        let notify_controller = shared.notify_controller().clone();
         let mut new_block_receiver =
             handle.block_on(notify_controller.subscribe_new_block(SUBSCRIBER_NAME.to_string()));
         let mut new_transaction_receiver = handle
@@ -249,7 +384,7 @@ impl SubscriptionRpcImpl {
                             publiser_send!((ckb_jsonrpc_types::PoolTransactionEntry, ckb_jsonrpc_types::PoolTransactionReject),
                                             (tx_entry.into(), reject.into()),
                                             new_reject_transaction_sender);
-                        }
+                        },
                         _ = stop_rx.cancelled() => {
                             break;
                         },
@@ -265,6 +400,160 @@ impl SubscriptionRpcImpl {
             new_transaction_sender,
             proposed_transaction_sender,
             new_reject_transaction_sender,
+            notify_controller,
+            shared,
+        }
+    }
+}
+
+fn lagged_error(skipped: u64) -> PublishMsg<String> {
+    PublishMsg::error(&RPCError::custom(
+        RPCError::SubscriptionLagged,
+        format!("the subscriber falls behind and misses {skipped} messages, subscribe again"),
+    ))
+}
+
+fn transaction_status_changed<CS: ChainStore>(
+    store: &CS,
+    event: &TransactionStatusEvent,
+) -> Vec<TransactionStatusChanged> {
+    match event {
+        TransactionStatusEvent::Pending(tx_hash) => vec![pool_status_changed(
+            tx_hash,
+            TransactionStatusTransition::Pending,
+            None,
+        )],
+        TransactionStatusEvent::Proposed(tx_hash) => vec![pool_status_changed(
+            tx_hash,
+            TransactionStatusTransition::Proposed,
+            None,
+        )],
+        TransactionStatusEvent::Rejected(tx_hash, reject) => vec![pool_status_changed(
+            tx_hash,
+            TransactionStatusTransition::Rejected,
+            Some(reject.clone()),
+        )],
+        TransactionStatusEvent::Replaced(tx_hash, reject) => vec![pool_status_changed(
+            tx_hash,
+            TransactionStatusTransition::Replaced,
+            Some(reject.clone()),
+        )],
+        TransactionStatusEvent::ChainChanged(chain_changed) => {
+            chain_status_changed(store, chain_changed)
+        }
+    }
+}
+
+fn pool_status_changed(
+    tx_hash: &packed::Byte32,
+    status: TransactionStatusTransition,
+    reject: Option<Reject>,
+) -> TransactionStatusChanged {
+    TransactionStatusChanged {
+        tx_hash: tx_hash.unpack(),
+        status,
+        block_hash: None,
+        block_number: None,
+        reason: reject.map(Into::into),
+    }
+}
+
+// The detached blocks are still in the store, only the canonical chain indexes are removed
+fn chain_status_changed<CS: ChainStore>(
+    store: &CS,
+    chain_changed: &CanonicalChainChanged,
+) -> Vec<TransactionStatusChanged> {
+    let block_status_changed = |header: &HeaderView, status| {
+        let block_hash: H256 = header.hash().unpack();
+        let block_number = header.number();
+        store
+            .get_block_txs_hashes(&header.hash())
+            .into_iter()
+            .map(move |tx_hash| TransactionStatusChanged {
+                tx_hash: tx_hash.unpack(),
+                status,
+                block_hash: Some(block_hash.clone()),
+                block_number: Some(block_number.into()),
+                reason: None,
+            })
+    };
+    chain_changed
+        .detached_headers
+        .iter()
+        .rev()
+        .flat_map(|header| block_status_changed(header, TransactionStatusTransition::Detached))
+        .chain(chain_changed.attached_headers.iter().flat_map(|header| {
+            block_status_changed(header, TransactionStatusTransition::Committed)
+        }))
+        .collect()
+}
+
+pub(crate) struct CellsChangedFilter {
+    script: packed::Script,
+    script_type: IndexerScriptType,
+    exact: bool,
+}
+
+impl CellsChangedFilter {
+    pub(crate) fn new(filter: IndexerCellsChangedFilter) -> Self {
+        CellsChangedFilter {
+            script: filter.script.into(),
+            script_type: filter.script_type,
+            exact: matches!(
+                filter.script_search_mode,
+                Some(IndexerScriptSearchMode::Exact)
+            ),
+        }
+    }
+
+    fn is_match(&self, cell: &ChangedCell) -> bool {
+        let script = match self.script_type {
+            IndexerScriptType::Lock => cell.output.lock(),
+            IndexerScriptType::Type => match cell.output.type_().to_opt() {
+                Some(script) => script,
+                None => return false,
+            },
+        };
+        if self.exact {
+            script.as_slice() == self.script.as_slice()
+        } else {
+            script.code_hash() == self.script.code_hash()
+                && script.hash_type() == self.script.hash_type()
+                && script
+                    .args()
+                    .raw_data()
+                    .starts_with(&self.script.args().raw_data())
+        }
+    }
+
+    pub(crate) fn filter(
+        &self,
+        cells_changed: &ckb_notify::IndexerCellsChanged,
+    ) -> Option<IndexerCellsChanged> {
+        let filter_cells = |cells: &[ChangedCell]| {
+            cells
+                .iter()
+                .filter(|cell| self.is_match(cell))
+                .map(|cell| IndexerCell {
+                    output: cell.output.clone().into(),
+                    output_data: Some(cell.output_data.clone().into()),
+                    out_point: cell.out_point.clone().into(),
+                    block_number: cell.block_number.into(),
+                    tx_index: cell.tx_index.into(),
+                })
+                .collect::<Vec<_>>()
+        };
+        let created = filter_cells(&cells_changed.created);
+        let consumed = filter_cells(&cells_changed.consumed);
+        if created.is_empty() && consumed.is_empty() {
+            return None;
         }
+        Some(IndexerCellsChanged {
+            block_number: cells_changed.block_number.into(),
+            block_hash: cells_changed.block_hash.unpack(),
+            rollback: cells_changed.rollback,
+            created,
+            consumed,
+        })
     }
 }
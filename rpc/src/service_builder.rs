@@ -190,7 +190,7 @@ impl<'a> ServiceBuilder<'a> {
     ) -> Self {
         let indexer = IndexerService::new(db_config, indexer_config, shared.async_handle().clone());
         let indexer_handle = indexer.handle();
-        let methods = IndexerRpcImpl::new(indexer_handle);
+        let methods = IndexerRpcImpl::new(indexer_handle, shared.clone());
         if self.config.indexer_enable() {
             start_indexer(&shared, indexer, indexer_config.index_tx_pool);
         }
@@ -205,10 +205,8 @@ impl<'a> ServiceBuilder<'a> {
 
     pub fn enable_subscription(&mut self, shared: Shared) {
         if self.config.subscription_enable() {
-            let methods = SubscriptionRpcImpl::new(
-                shared.notify_controller().clone(),
-                shared.async_handle().clone(),
-            );
+            let handle = shared.async_handle().clone();
+            let methods = SubscriptionRpcImpl::new(shared, handle);
             let mut meta_io = MetaIoHandler::default();
             add_subscription_rpc_methods(&mut meta_io, methods);
             self.add_methods(meta_io);
@@ -58,6 +58,15 @@ fn test_submit_transaction_error() {
         "PoolRejectedTransactionBySizeLimit: Transaction size 10 exceeded maximum limit 9",
         RPCError::from_submit_transaction_reject(&reject).message
     );
+
+    let reject = Reject::ExceededLockScriptLimit(
+        Byte32::new([0; 32]),
+        "the count of in-pool transactions reached the limit 10".to_owned(),
+    );
+    assert_eq!(
+        "PoolRejectedTransactionByLockScriptLimit: Transaction exceeded the limits of the input lock script 0x0000000000000000000000000000000000000000000000000000000000000000, the count of in-pool transactions reached the limit 10",
+        RPCError::from_submit_transaction_reject(&reject).message
+    );
 }
 
 #[test]
@@ -411,6 +411,7 @@ fn before_rpc_example(suite: &RpcTestSuite, example: &mut RpcTestExample) -> boo
         ("generate_block_with_template", 42) => return false,
         ("process_block_without_verify", 42) => return false,
         ("notify_transaction", 42) => return false,
+        ("send_package", 42) => return false,
         ("truncate", 42) => return false,
         ("get_block_template", 42) => suite.wait_block_template_update(),
         _ => return true,
@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use ckb_store::ChainStore;
+use ckb_test_chain_utils::{always_success_cell, always_success_consensus};
+use ckb_types::{
+    core::{Capacity, TransactionBuilder},
+    packed::{CellDep, CellInput, CellOutputBuilder, OutPoint},
+    prelude::*,
+};
+use ckb_verification_traits::Switch;
+use serde_json::json;
+
+use crate::tests::{always_success_transaction, next_block, setup, RpcTestRequest};
+
+#[test]
+fn test_get_cells_at_block() {
+    let suite = setup(always_success_consensus());
+
+    let store = suite.shared.store();
+    let tip = store.get_tip_header().unwrap();
+    let cellbase = store
+        .get_block(&store.get_block_hash(1).unwrap())
+        .unwrap()
+        .transactions()[0]
+        .clone();
+    let consumed_out_point = OutPoint::new(cellbase.hash(), 0);
+
+    // commit a transaction which consumes the cellbase output of block 1
+    let tx = TransactionBuilder::default()
+        .input(CellInput::new(consumed_out_point.clone(), 0))
+        .output(
+            CellOutputBuilder::default()
+                .capacity(Capacity::bytes(1000).unwrap().pack())
+                .lock(always_success_cell().2.clone())
+                .build(),
+        )
+        .output_data(Default::default())
+        .cell_dep(
+            CellDep::new_builder()
+                .out_point(OutPoint::new(always_success_transaction().hash(), 0))
+                .build(),
+        )
+        .build();
+    let block = next_block(&suite.shared, &tip)
+        .as_advanced_builder()
+        .transaction(tx.clone())
+        .build();
+    suite
+        .chain_controller
+        .internal_process_block(Arc::new(block.clone()), Switch::DISABLE_ALL)
+        .expect("process block");
+    assert_eq!(store.get_tip_header().unwrap().hash(), block.hash());
+
+    let out_points = [
+        consumed_out_point,
+        OutPoint::new(tx.hash(), 0),
+        OutPoint::new(tx.hash(), 1),
+    ]
+    .into_iter()
+    .map(ckb_jsonrpc_types::OutPoint::from)
+    .collect::<Vec<_>>();
+    let get_cells_at_block = |block: serde_json::Value| {
+        suite
+            .rpc(&RpcTestRequest {
+                id: 42,
+                jsonrpc: "2.0".to_string(),
+                method: "get_cells_at_block".to_string(),
+                params: vec![json!(out_points), block, json!(false)],
+            })
+            .result
+    };
+    let statuses = |result: &serde_json::Value| {
+        result["cells"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|cell| cell["status"].as_str().unwrap().to_string())
+            .collect::<Vec<_>>()
+    };
+
+    // before the cellbase is created
+    let result = get_cells_at_block(json!("0x0"));
+    assert_eq!(statuses(&result), vec!["unknown", "unknown", "unknown"]);
+
+    // before the cellbase is consumed
+    let result = get_cells_at_block(json!(format!("{:#x}", tip.number())));
+    assert_eq!(statuses(&result), vec!["live", "unknown", "unknown"]);
+    assert_eq!(result["cells"][0]["created_block_number"], json!("0x1"));
+    assert!(result["cells"][0]["consumed_block_number"].is_null());
+
+    // after the cellbase is consumed, query by block hash
+    let result = get_cells_at_block(json!(format!("{:#x}", block.hash())));
+    assert_eq!(statuses(&result), vec!["dead", "live", "unknown"]);
+    assert_eq!(
+        result["cells"][0]["consumed_block_number"],
+        json!(format!("{:#x}", block.number()))
+    );
+    assert!(!result["cells"][0]["cell"].is_null());
+
+    // a block not in the canonical chain
+    let result = get_cells_at_block(json!(format!("{:#x}", block.number() + 1)));
+    assert!(result.is_null());
+}
@@ -1,3 +1,5 @@
+mod chain;
 mod miner;
 mod pool;
+mod subscription;
 mod test;
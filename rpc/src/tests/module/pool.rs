@@ -200,6 +200,222 @@ fn test_send_transaction_exceeded_maximum_ancestors_count() {
     );
 }
 
+#[test]
+fn test_send_package_child_pays_for_parent() {
+    let suite = setup(always_success_consensus());
+
+    let store = suite.shared.store();
+    let tip = store.get_tip_header().unwrap();
+    let tip_block = store.get_block(&tip.hash()).unwrap();
+    let cellbase = tip_block.transactions().get(0).unwrap().clone();
+    let capacity: Capacity = cellbase.outputs().get(0).unwrap().capacity().unpack();
+
+    // the parent pays no fee, the child pays for both
+    let parent = build_spend_tx(cellbase.hash(), capacity);
+    let child = build_spend_tx(
+        parent.hash(),
+        capacity.safe_sub(Capacity::bytes(1).unwrap()).unwrap(),
+    );
+    let rpc = |method: &str, params: Vec<serde_json::Value>| {
+        suite.rpc(&RpcTestRequest {
+            id: 42,
+            jsonrpc: "2.0".to_string(),
+            method: method.to_string(),
+            params,
+        })
+    };
+
+    // the parent alone is rejected by the min fee rate
+    let response = rpc(
+        "send_transaction",
+        vec![json_tx(&parent), json!("passthrough")],
+    );
+    assert_eq!(response.error["code"], json!(-1104));
+
+    // the package must be topologically ordered
+    let response = rpc(
+        "send_package",
+        vec![
+            json!([json_tx(&child), json_tx(&parent)]),
+            json!("passthrough"),
+        ],
+    );
+    assert!(!response.error.is_null());
+
+    // every transaction in the package must be an ancestor of the child
+    let unrelated = build_spend_tx(always_success_transaction().hash(), capacity);
+    let response = rpc(
+        "send_package",
+        vec![
+            json!([json_tx(&unrelated), json_tx(&parent), json_tx(&child)]),
+            json!("passthrough"),
+        ],
+    );
+    assert_eq!(response.error["code"], json!(-1108));
+
+    let response = rpc(
+        "send_package",
+        vec![
+            json!([json_tx(&parent), json_tx(&child)]),
+            json!("passthrough"),
+        ],
+    );
+    assert_eq!(
+        response.result,
+        json!([
+            format!("{:#x}", parent.hash()),
+            format!("{:#x}", child.hash())
+        ])
+    );
+}
+
+#[test]
+fn test_send_package_skips_txs_in_pool() {
+    let suite = setup(always_success_consensus());
+
+    let store = suite.shared.store();
+    let tip = store.get_tip_header().unwrap();
+    let tip_block = store.get_block(&tip.hash()).unwrap();
+    let cellbase = tip_block.transactions().get(0).unwrap().clone();
+    let capacity: Capacity = cellbase.outputs().get(0).unwrap().capacity().unpack();
+
+    let parent = build_spend_tx(
+        cellbase.hash(),
+        capacity.safe_sub(Capacity::bytes(1).unwrap()).unwrap(),
+    );
+    let child = build_spend_tx(
+        parent.hash(),
+        capacity.safe_sub(Capacity::bytes(2).unwrap()).unwrap(),
+    );
+    let rpc = |method: &str, params: Vec<serde_json::Value>| {
+        suite.rpc(&RpcTestRequest {
+            id: 42,
+            jsonrpc: "2.0".to_string(),
+            method: method.to_string(),
+            params,
+        })
+    };
+
+    let response = rpc(
+        "send_transaction",
+        vec![json_tx(&parent), json!("passthrough")],
+    );
+    assert_eq!(response.result, json!(format!("{:#x}", parent.hash())));
+
+    // the parent in the pool is skipped and the child is evaluated alone
+    let response = rpc(
+        "send_package",
+        vec![
+            json!([json_tx(&parent), json_tx(&child)]),
+            json!("passthrough"),
+        ],
+    );
+    assert_eq!(
+        response.result,
+        json!([
+            format!("{:#x}", parent.hash()),
+            format!("{:#x}", child.hash())
+        ])
+    );
+
+    // every transaction is in the pool already
+    let response = rpc(
+        "send_package",
+        vec![
+            json!([json_tx(&parent), json_tx(&child)]),
+            json!("passthrough"),
+        ],
+    );
+    assert_eq!(response.error["code"], json!(-1107));
+}
+
+#[test]
+fn test_send_package_replaces_conflicts_by_rbf() {
+    let suite = setup(always_success_consensus());
+
+    let store = suite.shared.store();
+    let tip = store.get_tip_header().unwrap();
+    let tip_block = store.get_block(&tip.hash()).unwrap();
+    let cellbase = tip_block.transactions().get(0).unwrap().clone();
+    let capacity: Capacity = cellbase.outputs().get(0).unwrap().capacity().unpack();
+    let ckb = |n: u64| Capacity::bytes(n as usize).unwrap();
+
+    let rpc = |method: &str, params: Vec<serde_json::Value>| {
+        suite.rpc(&RpcTestRequest {
+            id: 42,
+            jsonrpc: "2.0".to_string(),
+            method: method.to_string(),
+            params,
+        })
+    };
+
+    // the pool tx spends the cellbase with 1 CKB fee
+    let old = build_spend_tx(cellbase.hash(), capacity.safe_sub(ckb(1)).unwrap());
+    let response = rpc(
+        "send_transaction",
+        vec![json_tx(&old), json!("passthrough")],
+    );
+    assert_eq!(response.result, json!(format!("{:#x}", old.hash())));
+
+    // the parent spending the same cellbase pays less than the replaced tx
+    let parent = build_spend_tx(
+        cellbase.hash(),
+        capacity.safe_sub(Capacity::shannons(1)).unwrap(),
+    );
+    let child = build_spend_tx(parent.hash(), capacity.safe_sub(ckb(3)).unwrap());
+    let response = rpc(
+        "send_package",
+        vec![
+            json!([json_tx(&parent), json_tx(&child)]),
+            json!("passthrough"),
+        ],
+    );
+    assert_eq!(response.error["code"], json!(-1111));
+
+    // the parent pays for the replaced tx
+    let parent = build_spend_tx(cellbase.hash(), capacity.safe_sub(ckb(2)).unwrap());
+    let child = build_spend_tx(parent.hash(), capacity.safe_sub(ckb(3)).unwrap());
+    let response = rpc(
+        "send_package",
+        vec![
+            json!([json_tx(&parent), json_tx(&child)]),
+            json!("passthrough"),
+        ],
+    );
+    assert_eq!(
+        response.result,
+        json!([
+            format!("{:#x}", parent.hash()),
+            format!("{:#x}", child.hash())
+        ])
+    );
+    let response = rpc("get_transaction", vec![json!(format!("{:#x}", old.hash()))]);
+    // the replaced tx is removed from the pool
+    assert_ne!(response.result["tx_status"]["status"], json!("pending"));
+}
+
+fn build_spend_tx(previous_tx_hash: packed::Byte32, capacity: Capacity) -> core::TransactionView {
+    TransactionBuilder::default()
+        .input(CellInput::new(OutPoint::new(previous_tx_hash, 0), 0))
+        .output(
+            CellOutputBuilder::default()
+                .capacity(capacity.pack())
+                .lock(always_success_cell().2.clone())
+                .build(),
+        )
+        .output_data(Default::default())
+        .cell_dep(
+            CellDep::new_builder()
+                .out_point(OutPoint::new(always_success_transaction().hash(), 0))
+                .build(),
+        )
+        .build()
+}
+
+fn json_tx(tx: &core::TransactionView) -> serde_json::Value {
+    json!(ckb_jsonrpc_types::Transaction::from(tx.data()))
+}
+
 fn build_tx(
     code_hash: &packed::Byte32,
     hash_type: core::ScriptHashType,
@@ -0,0 +1,114 @@
+use ckb_jsonrpc_types::{IndexerCellsChangedFilter, IndexerScriptSearchMode, IndexerScriptType};
+use ckb_notify::{ChangedCell, IndexerCellsChanged};
+use ckb_types::{
+    bytes::Bytes,
+    core::{capacity_bytes, Capacity, ScriptHashType},
+    h256,
+    packed::{CellOutputBuilder, OutPoint, Script, ScriptBuilder},
+    prelude::*,
+};
+
+use crate::module::subscription::CellsChangedFilter;
+
+fn script(args: &[u8]) -> Script {
+    ScriptBuilder::default()
+        .code_hash(h256!("0x1").pack())
+        .hash_type(ScriptHashType::Type.into())
+        .args(Bytes::from(args.to_vec()).pack())
+        .build()
+}
+
+fn changed_cell(index: u32, lock: Script, type_: Option<Script>) -> ChangedCell {
+    ChangedCell {
+        out_point: OutPoint::new(h256!("0x2").pack(), index),
+        output: CellOutputBuilder::default()
+            .capacity(capacity_bytes!(1000).pack())
+            .lock(lock)
+            .type_(type_.pack())
+            .build(),
+        output_data: Default::default(),
+        block_number: 1,
+        tx_index: 1,
+    }
+}
+
+fn cells_changed() -> IndexerCellsChanged {
+    IndexerCellsChanged {
+        block_number: 2,
+        block_hash: h256!("0x3").pack(),
+        rollback: false,
+        // created by the lock `0x0102` and the type `0x0102`
+        created: vec![changed_cell(0, script(&[1, 2]), Some(script(&[1, 2])))],
+        // consumed from the lock `0x01` and the type `0x01`
+        consumed: vec![changed_cell(1, script(&[1]), Some(script(&[1])))],
+    }
+}
+
+fn filter(
+    args: &[u8],
+    script_type: IndexerScriptType,
+    script_search_mode: Option<IndexerScriptSearchMode>,
+) -> CellsChangedFilter {
+    CellsChangedFilter::new(IndexerCellsChangedFilter {
+        script: script(args).into(),
+        script_type,
+        script_search_mode,
+    })
+}
+
+// `IndexerScriptType` is not `Clone`
+const SCRIPT_TYPES: [fn() -> IndexerScriptType; 2] =
+    [|| IndexerScriptType::Lock, || IndexerScriptType::Type];
+
+fn filtered_indexes(filter: &CellsChangedFilter) -> Option<(Vec<u32>, Vec<u32>)> {
+    filter.filter(&cells_changed()).map(|cells_changed| {
+        let indexes = |cells: &[ckb_jsonrpc_types::IndexerCell]| {
+            cells
+                .iter()
+                .map(|cell| cell.out_point.index.value())
+                .collect()
+        };
+        (
+            indexes(&cells_changed.created),
+            indexes(&cells_changed.consumed),
+        )
+    })
+}
+
+#[test]
+fn test_cells_changed_filter_by_prefix() {
+    for script_type in SCRIPT_TYPES {
+        let prefix = filter(&[1], script_type(), None);
+        assert_eq!(filtered_indexes(&prefix), Some((vec![0], vec![1])));
+
+        let prefix = filter(&[1, 2], script_type(), None);
+        assert_eq!(filtered_indexes(&prefix), Some((vec![0], vec![])));
+
+        let prefix = filter(&[2], script_type(), Some(IndexerScriptSearchMode::Prefix));
+        assert_eq!(filtered_indexes(&prefix), None);
+    }
+}
+
+#[test]
+fn test_cells_changed_filter_by_exact_script() {
+    for script_type in SCRIPT_TYPES {
+        let exact = filter(&[1], script_type(), Some(IndexerScriptSearchMode::Exact));
+        assert_eq!(filtered_indexes(&exact), Some((vec![], vec![1])));
+
+        let exact = filter(&[1, 2], script_type(), Some(IndexerScriptSearchMode::Exact));
+        assert_eq!(filtered_indexes(&exact), Some((vec![0], vec![])));
+
+        let exact = filter(&[], script_type(), Some(IndexerScriptSearchMode::Exact));
+        assert_eq!(filtered_indexes(&exact), None);
+    }
+}
+
+#[test]
+fn test_cells_changed_filter_by_type_skips_cells_without_type() {
+    let mut cells_changed = cells_changed();
+    cells_changed.created = vec![changed_cell(0, script(&[1]), None)];
+    let filter = filter(&[1], IndexerScriptType::Type, None);
+    let filtered = filter.filter(&cells_changed).unwrap();
+    assert!(filtered.created.is_empty());
+    assert_eq!(filtered.consumed.len(), 1);
+}
@@ -104,6 +104,7 @@ fn run(data: &[u8]) {
             .rfc_0049(0)
             .build()
             .unwrap(),
+        syscall_extensions: Default::default(),
     };
     let consensus = ConsensusBuilder::default()
         .hardfork_switch(hardfork_switch)
@@ -1,6 +1,7 @@
 //! CKB VM cost model.
 //!
 //! The cost model assign cycles to instructions.
+use ckb_vm::instructions::{extract_opcode, insts, Instruction};
 
 /// How many bytes can transfer when VM costs one cycle.
 // 0.25 cycles per byte
@@ -11,3 +12,101 @@ pub fn transferred_byte_cycles(bytes: u64) -> u64 {
     // Compiler will optimize the divisin here to shifts.
     (bytes + BYTES_PER_CYCLE - 1) / BYTES_PER_CYCLE
 }
+
+/// The instruction classes distinguished by the cycles the cost model charges.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum InstructionClass {
+    /// Arithmetic, logic and bit manipulation instructions.
+    Alu,
+    /// Memory load instructions.
+    Load,
+    /// Memory store instructions.
+    Store,
+    /// Conditional branches.
+    Branch,
+    /// Jumps, calls and returns.
+    Jump,
+    /// Multiplications.
+    Multiply,
+    /// Divisions and remainders.
+    Divide,
+    /// Atomic memory operations.
+    Atomic,
+    /// `ecall` and `ebreak`, the cycles of the syscalls are not included.
+    System,
+}
+
+/// Returns the class of the instruction, see `ckb_vm::cost_model::estimate_cycles` for the
+/// cycles charged.
+pub fn instruction_class(instruction: Instruction) -> InstructionClass {
+    match extract_opcode(instruction) {
+        insts::OP_LD_VERSION0
+        | insts::OP_LD_VERSION1
+        | insts::OP_LW_VERSION0
+        | insts::OP_LW_VERSION1
+        | insts::OP_LH_VERSION0
+        | insts::OP_LH_VERSION1
+        | insts::OP_LB_VERSION0
+        | insts::OP_LB_VERSION1
+        | insts::OP_LWU_VERSION0
+        | insts::OP_LWU_VERSION1
+        | insts::OP_LHU_VERSION0
+        | insts::OP_LHU_VERSION1
+        | insts::OP_LBU_VERSION0
+        | insts::OP_LBU_VERSION1 => InstructionClass::Load,
+        insts::OP_SB | insts::OP_SH | insts::OP_SW | insts::OP_SD => InstructionClass::Store,
+        insts::OP_BEQ
+        | insts::OP_BGE
+        | insts::OP_BGEU
+        | insts::OP_BLT
+        | insts::OP_BLTU
+        | insts::OP_BNE => InstructionClass::Branch,
+        insts::OP_JAL
+        | insts::OP_JALR_VERSION0
+        | insts::OP_JALR_VERSION1
+        | insts::OP_FAR_JUMP_REL
+        | insts::OP_FAR_JUMP_ABS => InstructionClass::Jump,
+        insts::OP_MUL
+        | insts::OP_MULW
+        | insts::OP_MULH
+        | insts::OP_MULHU
+        | insts::OP_MULHSU
+        | insts::OP_WIDE_MUL
+        | insts::OP_WIDE_MULU
+        | insts::OP_WIDE_MULSU => InstructionClass::Multiply,
+        insts::OP_DIV
+        | insts::OP_DIVW
+        | insts::OP_DIVU
+        | insts::OP_DIVUW
+        | insts::OP_REM
+        | insts::OP_REMW
+        | insts::OP_REMU
+        | insts::OP_REMUW
+        | insts::OP_WIDE_DIV
+        | insts::OP_WIDE_DIVU => InstructionClass::Divide,
+        insts::OP_LR_W
+        | insts::OP_SC_W
+        | insts::OP_AMOSWAP_W
+        | insts::OP_AMOADD_W
+        | insts::OP_AMOXOR_W
+        | insts::OP_AMOAND_W
+        | insts::OP_AMOOR_W
+        | insts::OP_AMOMIN_W
+        | insts::OP_AMOMAX_W
+        | insts::OP_AMOMINU_W
+        | insts::OP_AMOMAXU_W
+        | insts::OP_LR_D
+        | insts::OP_SC_D
+        | insts::OP_AMOSWAP_D
+        | insts::OP_AMOADD_D
+        | insts::OP_AMOXOR_D
+        | insts::OP_AMOAND_D
+        | insts::OP_AMOOR_D
+        | insts::OP_AMOMIN_D
+        | insts::OP_AMOMAX_D
+        | insts::OP_AMOMINU_D
+        | insts::OP_AMOMAXU_D => InstructionClass::Atomic,
+        insts::OP_ECALL | insts::OP_EBREAK => InstructionClass::System,
+        _ => InstructionClass::Alu,
+    }
+}
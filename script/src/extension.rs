@@ -0,0 +1,94 @@
+use crate::types::{CoreMachine, ScriptGroup, ScriptVersion};
+use ckb_traits::{CellDataProvider, ExtensionProvider, HeaderProvider};
+use ckb_types::core::{cell::ResolvedTransaction, hardfork::HardForks, EpochNumber};
+use ckb_vm::Syscalls;
+use once_cell::sync::OnceCell;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// The syscall extensions of the process by name, see `setup_syscall_extensions`.
+static SYSCALL_EXTENSIONS: OnceCell<BTreeMap<String, Arc<dyn SyscallExtension>>> = OnceCell::new();
+
+/// The data loader of the verifier, as seen by the syscall extensions.
+pub trait ScriptDataLoader:
+    CellDataProvider + HeaderProvider + ExtensionProvider + Send + Sync
+{
+}
+
+impl<DL: CellDataProvider + HeaderProvider + ExtensionProvider + Send + Sync> ScriptDataLoader
+    for DL
+{
+}
+
+/// The state a syscall extension builds its syscalls from.
+pub struct SyscallExtensionContext<'a> {
+    /// The data loader of the verifier, to load the cells, the headers and the block extensions.
+    pub data_loader: Arc<dyn ScriptDataLoader>,
+    /// The transaction being verified.
+    pub rtx: &'a Arc<ResolvedTransaction>,
+    /// The script group the VM runs.
+    pub script_group: &'a ScriptGroup,
+    /// The version of the VM.
+    pub script_version: ScriptVersion,
+}
+
+/// Extra syscalls of a custom chain, installed for the whole process by
+/// `setup_syscall_extensions`.
+///
+/// An extension only takes effect since the epoch configured under its name in the
+/// `syscall_extensions` of the hard fork switch, which is always empty on mainnet and testnet.
+pub trait SyscallExtension: Send + Sync {
+    /// Builds the syscalls for a new VM, the root VM of a script group or a spawned one.
+    ///
+    /// The built-in syscalls are tried first, so the extension can not override them.
+    fn build_syscalls(
+        &self,
+        context: &SyscallExtensionContext,
+    ) -> Vec<Box<(dyn Syscalls<CoreMachine>)>>;
+}
+
+/// Installs the syscall extensions of the process by the names the hard fork switch enables them
+/// with.
+///
+/// A custom chain installs them once at startup, before the node checks the chain spec by
+/// `check_syscall_extensions`. Returns the extensions back if some have been installed already.
+pub fn setup_syscall_extensions(
+    extensions: BTreeMap<String, Arc<dyn SyscallExtension>>,
+) -> Result<(), BTreeMap<String, Arc<dyn SyscallExtension>>> {
+    SYSCALL_EXTENSIONS.set(extensions)
+}
+
+/// Checks that every syscall extension enabled by the hard fork switch is installed, otherwise
+/// the node would verify the scripts differently from the chain.
+pub fn check_syscall_extensions(hardfork_switch: &HardForks) -> Result<(), String> {
+    let installed = SYSCALL_EXTENSIONS.get();
+    match hardfork_switch.syscall_extensions.keys().find(|name| {
+        !installed
+            .map(|extensions| extensions.contains_key(*name))
+            .unwrap_or(false)
+    }) {
+        Some(name) => Err(format!(
+            "the syscall extension \"{name}\" enabled by the chain spec is not installed"
+        )),
+        None => Ok(()),
+    }
+}
+
+// The installed syscall extensions enabled at the epoch, in the order of their names.
+pub(crate) fn enabled_syscall_extensions(
+    hardfork_switch: &HardForks,
+    epoch_number: EpochNumber,
+) -> Vec<Arc<dyn SyscallExtension>> {
+    SYSCALL_EXTENSIONS
+        .get()
+        .map(|extensions| {
+            extensions
+                .iter()
+                .filter(|(name, _)| {
+                    hardfork_switch.is_syscall_extension_enabled(name, epoch_number)
+                })
+                .map(|(_, extension)| Arc::clone(extension))
+                .collect()
+        })
+        .unwrap_or_default()
+}
@@ -0,0 +1,406 @@
+use crate::types::Machine;
+use ckb_vm::{
+    decoder::build_decoder, instructions::REGISTER_ABI_NAMES, machine::VERSION0, CoreMachine,
+    Error as VMError, Memory, Register, SupportMachine, ISA_MOP,
+};
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::sync::{Arc, Mutex};
+
+// The byte sent by the debugger to interrupt the running VM
+const INTERRUPT: u8 = 0x03;
+// The instructions run between two checks of the interrupt byte
+const INTERRUPT_POLL_INTERVAL: u64 = 0x10000;
+// The maximum bytes read by a single memory packet, so the reply fits in `PACKET_SIZE`
+const MAX_MEMORY_READ: u64 = 0x7f0;
+const PACKET_SIZE: usize = 0x1000;
+const SIGINT: u8 = 2;
+const SIGTRAP: u8 = 5;
+const SIGABRT: u8 = 6;
+// The register number of the PC in the target description, after the 32 general registers
+const PC_REGISTER: usize = 32;
+
+/// A GDB remote serial protocol stub serving one debugger connection.
+///
+/// The stub stops the root VM before its first instruction, and every VM spawned by it or
+/// replaced by the exec syscall before their first instructions. The breakpoints are set by
+/// address and apply to all the VMs.
+pub struct GdbStub {
+    stream: TcpStream,
+    breakpoints: HashSet<u64>,
+    stepping: bool,
+    attached: bool,
+    detached: bool,
+    // The VMs running, the root one and the spawned ones in progress
+    vms: usize,
+}
+
+pub(crate) type SharedGdbStub = Arc<Mutex<GdbStub>>;
+
+impl GdbStub {
+    /// Creates a stub on an accepted debugger connection.
+    pub fn new(stream: TcpStream) -> Self {
+        GdbStub {
+            stream,
+            breakpoints: HashSet::new(),
+            stepping: false,
+            attached: false,
+            detached: false,
+            vms: 0,
+        }
+    }
+
+    fn read_byte(&mut self) -> Result<u8, VMError> {
+        let mut byte = [0u8];
+        self.stream
+            .read_exact(&mut byte)
+            .map_err(|err| VMError::Unexpected(format!("debugger connection: {err}")))?;
+        Ok(byte[0])
+    }
+
+    fn read_packet(&mut self) -> Result<Vec<u8>, VMError> {
+        loop {
+            // Skips the acknowledgments and the interrupts while the VM is stopped
+            while self.read_byte()? != b'$' {}
+            let mut packet = Vec::new();
+            loop {
+                match self.read_byte()? {
+                    b'#' => break,
+                    byte => packet.push(byte),
+                }
+            }
+            let checksum = [self.read_byte()?, self.read_byte()?];
+            let expected = packet.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte));
+            if decode_hex(&checksum).as_deref() == Some(&[expected]) {
+                self.write(b"+")?;
+                return Ok(packet);
+            }
+            self.write(b"-")?;
+        }
+    }
+
+    fn send(&mut self, data: &str) -> Result<(), VMError> {
+        let checksum = data.bytes().fold(0u8, |sum, byte| sum.wrapping_add(byte));
+        self.write(format!("${data}#{checksum:02x}").as_bytes())
+    }
+
+    fn write(&mut self, data: &[u8]) -> Result<(), VMError> {
+        self.stream
+            .write_all(data)
+            .map_err(|err| VMError::Unexpected(format!("debugger connection: {err}")))
+    }
+
+    // Prints a message in the debugger console
+    fn notify(&mut self, message: &str) -> Result<(), VMError> {
+        self.send(&format!("O{}", faster_hex::hex_string(message.as_bytes())))
+    }
+
+    // Consumes the interrupt byte if the debugger has sent one, without blocking
+    fn interrupted(&mut self) -> bool {
+        let mut byte = [0u8];
+        if self.stream.set_nonblocking(true).is_err() {
+            return false;
+        }
+        let peeked = self.stream.peek(&mut byte);
+        let _ = self.stream.set_nonblocking(false);
+        if matches!(peeked, Ok(1)) && byte[0] == INTERRUPT {
+            let _ = self.stream.read_exact(&mut byte);
+            true
+        } else {
+            false
+        }
+    }
+
+    // Serves the packets until the debugger resumes the VM
+    fn serve<Mac: CoreMachine>(&mut self, machine: &mut Mac, signal: u8) -> Result<(), VMError> {
+        // The debugger queries the stop reason itself when it attaches
+        if self.attached {
+            self.send(&format!("S{signal:02x}"))?;
+        }
+        self.attached = true;
+        loop {
+            let packet = self.read_packet()?;
+            let packet = String::from_utf8_lossy(&packet).into_owned();
+            let (command, args) = packet.split_at(packet.len().min(1));
+            let reply = match command {
+                "?" => format!("S{signal:02x}"),
+                "g" => {
+                    let mut reply = String::new();
+                    for value in machine
+                        .registers()
+                        .iter()
+                        .chain(std::iter::once(machine.pc()))
+                    {
+                        reply.push_str(&faster_hex::hex_string(&value.to_u64().to_le_bytes()));
+                    }
+                    reply
+                }
+                "G" => match decode_hex(args.as_bytes()) {
+                    Some(bytes) if bytes.len() >= (PC_REGISTER + 1) * 8 => {
+                        for (index, value) in bytes.chunks(8).take(PC_REGISTER + 1).enumerate() {
+                            write_register(machine, index, le_u64(value));
+                        }
+                        "OK".to_owned()
+                    }
+                    _ => "E01".to_owned(),
+                },
+                "p" => match usize::from_str_radix(args, 16) {
+                    Ok(index) if index < PC_REGISTER => {
+                        faster_hex::hex_string(&machine.registers()[index].to_u64().to_le_bytes())
+                    }
+                    Ok(PC_REGISTER) => faster_hex::hex_string(&machine.pc().to_u64().to_le_bytes()),
+                    _ => "E01".to_owned(),
+                },
+                "P" => {
+                    let register = args.split_once('=').and_then(|(index, value)| {
+                        let index = usize::from_str_radix(index, 16).ok()?;
+                        let value =
+                            decode_hex(value.as_bytes()).filter(|value| value.len() == 8)?;
+                        (index <= PC_REGISTER).then(|| (index, le_u64(&value)))
+                    });
+                    match register {
+                        Some((index, value)) => {
+                            write_register(machine, index, value);
+                            "OK".to_owned()
+                        }
+                        None => "E01".to_owned(),
+                    }
+                }
+                "m" => match parse_address_length(args) {
+                    Some((addr, length)) => {
+                        let mut reply = String::new();
+                        for offset in 0..length.min(MAX_MEMORY_READ) {
+                            let addr = Mac::REG::from_u64(addr.wrapping_add(offset));
+                            match machine.memory_mut().load8(&addr) {
+                                Ok(byte) => {
+                                    let _ = write!(reply, "{:02x}", byte.to_u8());
+                                }
+                                Err(_) => break,
+                            }
+                        }
+                        if reply.is_empty() && length > 0 {
+                            "E01".to_owned()
+                        } else {
+                            reply
+                        }
+                    }
+                    None => "E01".to_owned(),
+                },
+                "M" => {
+                    let written = args.split_once(':').and_then(|(range, data)| {
+                        let (addr, length) = parse_address_length(range)?;
+                        let data = decode_hex(data.as_bytes())?;
+                        if data.len() as u64 != length {
+                            return None;
+                        }
+                        machine.memory_mut().store_bytes(addr, &data).ok()
+                    });
+                    match written {
+                        Some(()) => "OK".to_owned(),
+                        None => "E01".to_owned(),
+                    }
+                }
+                "Z" | "z" => match parse_breakpoint(args) {
+                    Some(addr) => {
+                        if command == "Z" {
+                            self.breakpoints.insert(addr);
+                        } else {
+                            self.breakpoints.remove(&addr);
+                        }
+                        "OK".to_owned()
+                    }
+                    // Watchpoints are not supported
+                    None => String::new(),
+                },
+                "c" => {
+                    self.stepping = false;
+                    return Ok(());
+                }
+                "s" => {
+                    self.stepping = true;
+                    return Ok(());
+                }
+                "D" => {
+                    self.send("OK")?;
+                    self.breakpoints.clear();
+                    self.stepping = false;
+                    self.detached = true;
+                    return Ok(());
+                }
+                "k" => return Err(VMError::Unexpected("killed by the debugger".to_owned())),
+                "H" => "OK".to_owned(),
+                "q" => self.query(args),
+                _ => String::new(),
+            };
+            self.send(&reply)?;
+        }
+    }
+
+    fn query(&self, query: &str) -> String {
+        if query.starts_with("Supported") {
+            format!("PacketSize={PACKET_SIZE:x};qXfer:features:read+")
+        } else if let Some(range) = query.strip_prefix("Xfer:features:read:target.xml:") {
+            match parse_address_length(range) {
+                Some((offset, length)) => {
+                    let description = target_description();
+                    let start = (offset as usize).min(description.len());
+                    let end = start.saturating_add(length as usize).min(description.len());
+                    let marker = if end == description.len() { 'l' } else { 'm' };
+                    format!("{marker}{}", &description[start..end])
+                }
+                None => "E01".to_owned(),
+            }
+        } else if query == "Attached" {
+            "1".to_owned()
+        } else if query == "fThreadInfo" {
+            "m1".to_owned()
+        } else if query == "sThreadInfo" {
+            "l".to_owned()
+        } else if query == "C" {
+            "QC1".to_owned()
+        } else {
+            String::new()
+        }
+    }
+}
+
+fn lock(stub: &SharedGdbStub) -> std::sync::MutexGuard<'_, GdbStub> {
+    stub.lock().expect("gdb stub lock")
+}
+
+/// Runs the machine instruction by instruction like the plain interpreter, and stops it for the
+/// debugger at the breakpoints, the single steps and the interrupts.
+///
+/// The machine must have loaded the program.
+pub(crate) fn run(machine: &mut Machine, stub: &SharedGdbStub) -> Result<i8, VMError> {
+    let spawned = {
+        let mut stub = lock(stub);
+        stub.vms += 1;
+        stub.vms > 1
+    };
+    let result = debug(&mut machine.machine, stub, spawned);
+    let mut stub = lock(stub);
+    stub.vms -= 1;
+    if stub.vms == 0 && !stub.detached {
+        // The debugger may have gone, the result is returned anyway
+        let _ = match &result {
+            Ok(exit_code) => stub.send(&format!("W{:02x}", *exit_code as u8)),
+            Err(_) => stub.send(&format!("X{SIGABRT:02x}")),
+        };
+    }
+    result
+}
+
+fn debug<Mac: SupportMachine>(
+    machine: &mut ckb_vm::DefaultMachine<Mac>,
+    stub: &SharedGdbStub,
+    spawned: bool,
+) -> Result<i8, VMError> {
+    if machine.isa() & ISA_MOP != 0 && machine.version() == VERSION0 {
+        return Err(VMError::InvalidVersion);
+    }
+    let mut decoder = build_decoder::<Mac::REG>(machine.isa(), machine.version());
+    let mut notice = spawned.then_some("entering a spawned VM\n");
+    let mut entering = true;
+    let mut steps: u64 = 0;
+
+    machine.reset_signal();
+    machine.set_running(true);
+    while machine.running() {
+        {
+            let mut stub = lock(stub);
+            if !stub.detached {
+                let pc = machine.pc().to_u64();
+                steps = steps.wrapping_add(1);
+                let signal = if entering || stub.stepping || stub.breakpoints.contains(&pc) {
+                    Some(SIGTRAP)
+                } else if steps % INTERRUPT_POLL_INTERVAL == 0 && stub.interrupted() {
+                    Some(SIGINT)
+                } else {
+                    None
+                };
+                if let Some(signal) = signal {
+                    if let Some(notice) = notice.take() {
+                        stub.notify(notice)?;
+                    }
+                    stub.serve(machine, signal)?;
+                }
+            }
+        }
+        entering = false;
+
+        machine.step(&mut decoder)?;
+
+        if machine.reset_signal() {
+            // The exec syscall has replaced the program
+            decoder.reset_instructions_cache();
+            entering = true;
+            notice = Some("entering the program loaded by exec\n");
+        }
+    }
+    Ok(machine.exit_code())
+}
+
+fn write_register<Mac: CoreMachine>(machine: &mut Mac, index: usize, value: u64) {
+    let value = Mac::REG::from_u64(value);
+    if index == PC_REGISTER {
+        machine.update_pc(value);
+        machine.commit_pc();
+    } else if index > 0 {
+        // The zero register is hardwired
+        machine.set_register(index, value);
+    }
+}
+
+fn target_description() -> String {
+    let mut description = String::from(concat!(
+        r#"<?xml version="1.0"?><!DOCTYPE target SYSTEM "gdb-target.dtd"><target version="1.0">"#,
+        r#"<architecture>riscv:rv64</architecture><feature name="org.gnu.gdb.riscv.cpu">"#
+    ));
+    for (regnum, name) in REGISTER_ABI_NAMES.iter().enumerate() {
+        let _ = write!(
+            description,
+            r#"<reg name="{name}" bitsize="64" type="int" regnum="{regnum}"/>"#
+        );
+    }
+    let _ = write!(
+        description,
+        r#"<reg name="pc" bitsize="64" type="code_ptr" regnum="{PC_REGISTER}"/></feature></target>"#
+    );
+    description
+}
+
+// Parses `addr,length` in hex
+fn parse_address_length(args: &str) -> Option<(u64, u64)> {
+    let (addr, length) = args.split_once(',')?;
+    Some((
+        u64::from_str_radix(addr, 16).ok()?,
+        u64::from_str_radix(length, 16).ok()?,
+    ))
+}
+
+// Parses `0,addr,kind` and `1,addr,kind` of the software and hardware breakpoints
+fn parse_breakpoint(args: &str) -> Option<u64> {
+    let (kind, range) = args.split_once(',')?;
+    if kind != "0" && kind != "1" {
+        return None;
+    }
+    parse_address_length(range).map(|(addr, _)| addr)
+}
+
+fn decode_hex(hex: &[u8]) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    let mut bytes = vec![0u8; hex.len() / 2];
+    faster_hex::hex_decode(hex, &mut bytes).ok()?;
+    Some(bytes)
+}
+
+fn le_u64(bytes: &[u8]) -> u64 {
+    let mut value = [0u8; 8];
+    value.copy_from_slice(&bytes[..8]);
+    u64::from_le_bytes(value)
+}
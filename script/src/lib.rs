@@ -1,14 +1,27 @@
 //! CKB component to run the type/lock scripts.
 pub mod cost_model;
 mod error;
+mod extension;
+mod gdb;
+mod profile;
 mod syscalls;
+mod trace;
 mod type_id;
 mod types;
 mod verify;
 mod verify_env;
 
 pub use crate::error::{ScriptError, TransactionScriptError};
+pub use crate::extension::{
+    check_syscall_extensions, setup_syscall_extensions, ScriptDataLoader, SyscallExtension,
+    SyscallExtensionContext,
+};
+pub use crate::gdb::GdbStub;
+pub use crate::profile::{
+    syscall_name, CycleCount, ProfileFrame, ScriptGroupProfile, MAX_PROFILED_STACK_DEPTH,
+};
 pub use crate::syscalls::spawn::update_caller_machine;
+pub use crate::trace::{ScriptGroupTrace, SyscallTrace, VmTrace, MAX_TRACED_EVENTS};
 pub use crate::types::{
     CoreMachine, MachineContext, ResumableMachine, ScriptGroup, ScriptGroupType, ScriptVersion,
     TransactionSnapshot, TransactionState, VerifyResult, VmIsa, VmVersion,
@@ -0,0 +1,390 @@
+use crate::{
+    cost_model::{instruction_class, InstructionClass},
+    error::ScriptError,
+    syscalls::{
+        CURRENT_CYCLES, CURRENT_MEMORY, DEBUG_PRINT_SYSCALL_NUMBER, EXEC, GET_MEMORY_LIMIT,
+        LOAD_BLOCK_EXTENSION, LOAD_CELL_BY_FIELD_SYSCALL_NUMBER,
+        LOAD_CELL_DATA_AS_CODE_SYSCALL_NUMBER, LOAD_CELL_DATA_SYSCALL_NUMBER,
+        LOAD_CELL_SYSCALL_NUMBER, LOAD_HEADER_BY_FIELD_SYSCALL_NUMBER, LOAD_HEADER_SYSCALL_NUMBER,
+        LOAD_INPUT_BY_FIELD_SYSCALL_NUMBER, LOAD_INPUT_SYSCALL_NUMBER,
+        LOAD_SCRIPT_HASH_SYSCALL_NUMBER, LOAD_SCRIPT_SYSCALL_NUMBER,
+        LOAD_TRANSACTION_SYSCALL_NUMBER, LOAD_TX_HASH_SYSCALL_NUMBER, LOAD_WITNESS_SYSCALL_NUMBER,
+        SET_CONTENT, SPAWN, VM_VERSION,
+    },
+    types::{Machine, ScriptGroupType},
+};
+use ckb_types::{bytes::Bytes, core::Cycle, packed::Byte32};
+use ckb_vm::{
+    cost_model::estimate_cycles,
+    decoder::build_decoder,
+    instructions::{extract_opcode, insts, Itype, Utype},
+    machine::VERSION0,
+    registers::{A7, RA, ZERO},
+    CoreMachine as _, Error as VMError, Register, SupportMachine, ISA_MOP,
+};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use std::sync::{Arc, Mutex};
+
+/// The maximum depth of the call stacks recorded in the samples, the deeper calls are
+/// attributed to their ancestor at this depth.
+pub const MAX_PROFILED_STACK_DEPTH: usize = 256;
+
+const EXIT: u64 = 93;
+
+/// The count and the cycles of an instruction class or a syscall.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CycleCount {
+    /// The times it is executed.
+    pub count: u64,
+    /// The cycles consumed in total.
+    pub cycles: Cycle,
+}
+
+/// A frame in the sampled call stacks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ProfileFrame {
+    /// The index of the program in `ScriptGroupProfile::programs`.
+    pub program: usize,
+    /// The PC of the call site, or of the executing instruction for the last frame.
+    pub pc: u64,
+}
+
+/// The cycles profile of a script group.
+///
+/// The cycles are split without overlapping, so the loading cycles, the instruction classes and
+/// the syscalls sum up to the cycles of the group, and so do the loading cycles and the samples.
+#[derive(Clone, Debug)]
+pub struct ScriptGroupProfile {
+    /// The script group type.
+    pub group_type: ScriptGroupType,
+    /// The script hash of the group.
+    pub script_hash: Byte32,
+    /// The cycles consumed by the group, None if the group fails.
+    pub cycles: Option<Cycle>,
+    /// The error failing the group.
+    pub error: Option<ScriptError>,
+    /// The cycles charged for loading the program of the root VM.
+    pub loading_cycles: Cycle,
+    /// The instructions executed by all the VMs by class, charged by the cost model.
+    pub instructions: BTreeMap<InstructionClass, CycleCount>,
+    /// The syscalls by number, see `syscall_name`.
+    ///
+    /// The cycles exclude the `ecall` instruction and the instructions and syscalls of the
+    /// spawned VMs, but include the cycles charged for loading the programs they run.
+    pub syscalls: BTreeMap<u64, CycleCount>,
+    /// The cycles by call stack, the outermost frame first.
+    ///
+    /// The stacks of a spawned VM are nested in the stack of the spawn syscall.
+    pub samples: HashMap<Vec<ProfileFrame>, Cycle>,
+    /// The programs run by the root VM, the spawned VMs and the exec syscall.
+    pub programs: Vec<Bytes>,
+}
+
+impl ScriptGroupProfile {
+    pub(crate) fn new(group_type: ScriptGroupType, script_hash: Byte32) -> Self {
+        ScriptGroupProfile {
+            group_type,
+            script_hash,
+            cycles: None,
+            error: None,
+            loading_cycles: 0,
+            instructions: BTreeMap::new(),
+            syscalls: BTreeMap::new(),
+            samples: HashMap::new(),
+            programs: Vec::new(),
+        }
+    }
+
+    /// Renders the samples in the folded stacks format accepted by flamegraph tools, one stack
+    /// per line sorted by the stack, such as `main;verify;memcpy 1024`.
+    ///
+    /// The frames are resolved by the function symbols of the program, falling back to the hex
+    /// PC when the program is stripped.
+    pub fn folded_stacks(&self) -> String {
+        let symbols: Vec<Symbols> = self
+            .programs
+            .iter()
+            .map(|program| Symbols::parse(program))
+            .collect();
+        let mut stacks: BTreeMap<String, Cycle> = BTreeMap::new();
+        for (frames, cycles) in &self.samples {
+            let stack = frames
+                .iter()
+                .map(|frame| {
+                    symbols
+                        .get(frame.program)
+                        .and_then(|symbols| symbols.resolve(frame.pc))
+                        .map(ToOwned::to_owned)
+                        .unwrap_or_else(|| format!("{:#x}", frame.pc))
+                })
+                .collect::<Vec<_>>()
+                .join(";");
+            *stacks.entry(stack).or_default() += cycles;
+        }
+        let mut folded = String::new();
+        for (stack, cycles) in stacks {
+            let _ = writeln!(folded, "{stack} {cycles}");
+        }
+        folded
+    }
+}
+
+/// Returns the name of a syscall, such as `load_cell_data` for 2092.
+pub fn syscall_name(number: u64) -> Option<&'static str> {
+    let name = match number {
+        EXIT => "exit",
+        VM_VERSION => "vm_version",
+        CURRENT_CYCLES => "current_cycles",
+        EXEC => "exec",
+        LOAD_TRANSACTION_SYSCALL_NUMBER => "load_transaction",
+        LOAD_SCRIPT_SYSCALL_NUMBER => "load_script",
+        LOAD_TX_HASH_SYSCALL_NUMBER => "load_tx_hash",
+        LOAD_SCRIPT_HASH_SYSCALL_NUMBER => "load_script_hash",
+        LOAD_CELL_SYSCALL_NUMBER => "load_cell",
+        LOAD_HEADER_SYSCALL_NUMBER => "load_header",
+        LOAD_INPUT_SYSCALL_NUMBER => "load_input",
+        LOAD_WITNESS_SYSCALL_NUMBER => "load_witness",
+        LOAD_CELL_BY_FIELD_SYSCALL_NUMBER => "load_cell_by_field",
+        LOAD_HEADER_BY_FIELD_SYSCALL_NUMBER => "load_header_by_field",
+        LOAD_INPUT_BY_FIELD_SYSCALL_NUMBER => "load_input_by_field",
+        LOAD_CELL_DATA_AS_CODE_SYSCALL_NUMBER => "load_cell_data_as_code",
+        LOAD_CELL_DATA_SYSCALL_NUMBER => "load_cell_data",
+        SPAWN => "spawn",
+        GET_MEMORY_LIMIT => "get_memory_limit",
+        SET_CONTENT => "set_content",
+        LOAD_BLOCK_EXTENSION => "load_block_extension",
+        CURRENT_MEMORY => "current_memory",
+        DEBUG_PRINT_SYSCALL_NUMBER => "debug",
+        _ => return None,
+    };
+    Some(name)
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Profiler {
+    instructions: BTreeMap<InstructionClass, CycleCount>,
+    syscalls: BTreeMap<u64, CycleCount>,
+    samples: HashMap<Vec<ProfileFrame>, Cycle>,
+    programs: Vec<Bytes>,
+    // The sum of the cycles recorded, used to exclude the cycles of the spawned VMs from the
+    // spawn syscall
+    recorded_cycles: Cycle,
+    // The stack of the spawn syscall in progress, the parent frames of the spawned VM
+    spawning_frames: Vec<ProfileFrame>,
+    // The program loaded by the exec syscall in progress
+    exec_program: Option<Bytes>,
+}
+
+pub(crate) type SharedProfiler = Arc<Mutex<Profiler>>;
+
+impl Profiler {
+    pub(crate) fn set_exec_program(&mut self, program: Bytes) {
+        self.exec_program = Some(program);
+    }
+
+    pub(crate) fn finish(self, profile: &mut ScriptGroupProfile) {
+        profile.instructions = self.instructions;
+        profile.syscalls = self.syscalls;
+        profile.samples = self.samples;
+        profile.programs = self.programs;
+    }
+
+    fn add_program(&mut self, program: Bytes) -> usize {
+        self.programs.push(program);
+        self.programs.len() - 1
+    }
+
+    fn record(
+        &mut self,
+        frames: &[ProfileFrame],
+        class: InstructionClass,
+        instruction_cycles: Cycle,
+        syscall: Option<(u64, Cycle)>,
+    ) {
+        let instruction = self.instructions.entry(class).or_default();
+        instruction.count += 1;
+        instruction.cycles += instruction_cycles;
+        let mut cycles = instruction_cycles;
+        if let Some((number, syscall_cycles)) = syscall {
+            let syscall = self.syscalls.entry(number).or_default();
+            syscall.count += 1;
+            syscall.cycles += syscall_cycles;
+            cycles += syscall_cycles;
+        }
+        match self.samples.get_mut(frames) {
+            Some(sample) => *sample += cycles,
+            None => {
+                self.samples.insert(frames.to_vec(), cycles);
+            }
+        }
+        self.recorded_cycles += cycles;
+    }
+}
+
+fn lock(profiler: &SharedProfiler) -> std::sync::MutexGuard<'_, Profiler> {
+    profiler.lock().expect("profiler lock")
+}
+
+/// Runs the machine instruction by instruction like the plain interpreter, and records the
+/// cycles of each one in the profiler.
+///
+/// The machine must have loaded the program.
+pub(crate) fn run(
+    machine: &mut Machine,
+    program: Bytes,
+    profiler: &SharedProfiler,
+) -> Result<i8, VMError> {
+    let machine = &mut machine.machine;
+    if machine.isa() & ISA_MOP != 0 && machine.version() == VERSION0 {
+        return Err(VMError::InvalidVersion);
+    }
+    let mut decoder = build_decoder::<u64>(machine.isa(), machine.version());
+    let (mut frames, mut program) = {
+        let mut profiler = lock(profiler);
+        let frames = std::mem::take(&mut profiler.spawning_frames);
+        (frames, profiler.add_program(program))
+    };
+    // The frames of the parent VMs are kept when the exec syscall replaces the program
+    let base = frames.len();
+    // The calls deeper than `MAX_PROFILED_STACK_DEPTH` which are not pushed into the frames
+    let mut untracked_calls = 0usize;
+
+    machine.reset_signal();
+    machine.set_running(true);
+    while machine.running() {
+        let pc = machine.pc().to_u64();
+        let instruction = decoder.decode(machine.memory_mut(), pc)?;
+        let instruction_cycles = estimate_cycles(instruction);
+        let opcode = extract_opcode(instruction);
+        let syscall = (opcode == insts::OP_ECALL).then(|| machine.registers()[A7].to_u64());
+        let current = ProfileFrame { program, pc };
+        let (cycles, recorded_cycles) = {
+            let mut profiler = lock(profiler);
+            if syscall == Some(SPAWN) {
+                profiler.spawning_frames = frames.clone();
+                profiler.spawning_frames.push(current);
+            }
+            (machine.cycles(), profiler.recorded_cycles)
+        };
+
+        machine.step(&mut decoder)?;
+
+        let mut profiler = lock(profiler);
+        let syscall = syscall.map(|number| {
+            let spawned_cycles = profiler.recorded_cycles - recorded_cycles;
+            let syscall_cycles = machine
+                .cycles()
+                .saturating_sub(cycles)
+                .saturating_sub(instruction_cycles)
+                .saturating_sub(spawned_cycles);
+            (number, syscall_cycles)
+        });
+        frames.push(current);
+        profiler.record(
+            &frames,
+            instruction_class(instruction),
+            instruction_cycles,
+            syscall,
+        );
+        frames.pop();
+
+        if machine.reset_signal() {
+            // The exec syscall has replaced the program
+            decoder.reset_instructions_cache();
+            let exec_program = profiler.exec_program.take().unwrap_or_default();
+            program = profiler.add_program(exec_program);
+            frames.truncate(base);
+            untracked_calls = 0;
+            continue;
+        }
+        match call_or_return(instruction, opcode) {
+            Some(true) => {
+                if frames.len() - base < MAX_PROFILED_STACK_DEPTH {
+                    frames.push(current);
+                } else {
+                    untracked_calls += 1;
+                }
+            }
+            Some(false) => {
+                if untracked_calls > 0 {
+                    untracked_calls -= 1;
+                } else if frames.len() > base {
+                    frames.pop();
+                }
+            }
+            None => {}
+        }
+    }
+    Ok(machine.exit_code())
+}
+
+// Returns Some(true) for a call which links the return address to `ra`, and Some(false) for a
+// return which jumps to `ra`.
+fn call_or_return(instruction: u64, opcode: u16) -> Option<bool> {
+    match opcode {
+        insts::OP_JAL | insts::OP_FAR_JUMP_REL | insts::OP_FAR_JUMP_ABS => {
+            (Utype(instruction).rd() == RA).then_some(true)
+        }
+        insts::OP_JALR_VERSION0 | insts::OP_JALR_VERSION1 => {
+            let i = Itype(instruction);
+            if i.rd() == RA {
+                Some(true)
+            } else if i.rd() == ZERO && i.rs1() == RA {
+                Some(false)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+// The function symbols sorted by the address
+struct Symbols(Vec<(u64, u64, String)>);
+
+impl Symbols {
+    fn parse(program: &[u8]) -> Self {
+        let elf = match goblin::elf::Elf::parse(program) {
+            Ok(elf) => elf,
+            Err(_) => return Symbols(Vec::new()),
+        };
+        let mut symbols: Vec<_> = elf
+            .syms
+            .iter()
+            .filter(|sym| sym.is_function() && sym.st_value > 0)
+            .filter_map(|sym| {
+                elf.strtab
+                    .get(sym.st_name)
+                    .and_then(Result::ok)
+                    .map(|name| (sym.st_value, sym.st_size, name.to_owned()))
+            })
+            .collect();
+        symbols.sort();
+        // The symbols of the hand written assembly may have no size, they end at the next symbol
+        let starts: Vec<u64> = symbols.iter().map(|(start, _, _)| *start).collect();
+        let symbols = symbols
+            .into_iter()
+            .enumerate()
+            .map(|(index, (start, size, name))| {
+                let end = if size > 0 {
+                    start.saturating_add(size)
+                } else {
+                    starts[index + 1..]
+                        .iter()
+                        .find(|next| **next > start)
+                        .copied()
+                        .unwrap_or(start)
+                };
+                (start, end, name)
+            })
+            .collect();
+        Symbols(symbols)
+    }
+
+    fn resolve(&self, pc: u64) -> Option<&str> {
+        let index = self.0.partition_point(|(start, _, _)| *start <= pc);
+        let (_, end, name) = self.0.get(index.checked_sub(1)?)?;
+        (pc < *end).then_some(name.as_str())
+    }
+}
@@ -1,4 +1,5 @@
 use crate::cost_model::transferred_byte_cycles;
+use crate::profile::SharedProfiler;
 use crate::syscalls::utils::load_c_string;
 use crate::syscalls::{
     Place, Source, SourceEntry, EXEC, INDEX_OUT_OF_BOUND, SLICE_OUT_OF_BOUND, WRONG_FORMAT,
@@ -22,15 +23,17 @@ pub struct Exec<DL> {
     outputs: Arc<Vec<CellMeta>>,
     group_inputs: Indices,
     group_outputs: Indices,
+    profiler: Option<SharedProfiler>,
 }
 
 impl<DL: CellDataProvider> Exec<DL> {
-    pub fn new(
+    pub(crate) fn new(
         data_loader: DL,
         rtx: Arc<ResolvedTransaction>,
         outputs: Arc<Vec<CellMeta>>,
         group_inputs: Indices,
         group_outputs: Indices,
+        profiler: Option<SharedProfiler>,
     ) -> Exec<DL> {
         Exec {
             data_loader,
@@ -38,6 +41,7 @@ impl<DL: CellDataProvider> Exec<DL> {
             outputs,
             group_inputs,
             group_outputs,
+            profiler,
         }
     }
 
@@ -176,6 +180,12 @@ impl<Mac: SupportMachine, DL: CellDataProvider + Send + Sync> Syscalls<Mac> for
         machine.reset(max_cycles);
         machine.set_cycles(cycles);
 
+        if let Some(profiler) = &self.profiler {
+            profiler
+                .lock()
+                .expect("profiler lock")
+                .set_exec_program(data.clone());
+        }
         match machine.load_elf(&data, true) {
             Ok(size) => {
                 machine.add_cycles_no_checking(transferred_byte_cycles(size))?;
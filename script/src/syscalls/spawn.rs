@@ -10,6 +10,7 @@ use crate::types::{
     set_vm_max_cycles, CoreMachineType, Machine, MachineContext, ResumableMachine, SpawnData,
 };
 use crate::TransactionScriptsSyscallsGenerator;
+use crate::{gdb, profile};
 use crate::{ScriptGroup, ScriptVersion};
 use ckb_traits::{CellDataProvider, ExtensionProvider, HeaderProvider};
 use ckb_types::core::cell::CellMeta;
@@ -229,7 +230,15 @@ where
             }
         }
         // Run the child machine and check result.
-        match machine_child.run() {
+        let result = match (
+            &self.syscalls_generator.gdb,
+            &self.syscalls_generator.profiler,
+        ) {
+            (Some(stub), _) => gdb::run(&mut machine_child, stub),
+            (None, Some(profiler)) => profile::run(&mut machine_child, program, profiler),
+            (None, None) => machine_child.run(),
+        };
+        match result {
             Ok(data) => {
                 update_caller_machine(machine, data, machine_child.machine.cycles(), &spawn_data)?;
                 Ok(true)
@@ -278,29 +287,25 @@ pub fn build_child_machine<
     );
     let machine_builder =
         DefaultMachineBuilder::new(machine_core).instruction_cycle_func(Box::new(estimate_cycles));
-    let machine_syscalls = syscalls_generator.generate_same_syscalls(script_version, script_group);
-    let machine_builder = machine_syscalls
+    let mut machine_syscalls =
+        syscalls_generator.generate_same_syscalls(script_version, script_group);
+    machine_syscalls.append(&mut vec![
+        Box::new(syscalls_generator.build_current_cycles(*cycles_base)),
+        Box::new(syscalls_generator.build_get_memory_limit(*callee_memory_limit)),
+        Box::new(syscalls_generator.build_set_content(Arc::clone(content), *content_length)),
+        Box::new(syscalls_generator.build_spawn(
+            script_version,
+            script_group,
+            *callee_peak_memory,
+            *cycles_base,
+            Arc::clone(context),
+        )),
+        Box::new(syscalls_generator.build_current_memory(*callee_peak_memory)),
+    ]);
+    let machine_builder = syscalls_generator
+        .trace_syscalls(machine_syscalls)
         .into_iter()
         .fold(machine_builder, |builder, syscall| builder.syscall(syscall));
-    let machine_builder = machine_builder.syscall(Box::new(
-        syscalls_generator.build_current_cycles(*cycles_base),
-    ));
-    let machine_builder = machine_builder.syscall(Box::new(
-        syscalls_generator.build_get_memory_limit(*callee_memory_limit),
-    ));
-    let machine_builder = machine_builder.syscall(Box::new(
-        syscalls_generator.build_set_content(Arc::clone(content), *content_length),
-    ));
-    let machine_builder = machine_builder.syscall(Box::new(syscalls_generator.build_spawn(
-        script_version,
-        script_group,
-        *callee_peak_memory,
-        *cycles_base,
-        Arc::clone(context),
-    )));
-    let machine_builder = machine_builder.syscall(Box::new(
-        syscalls_generator.build_current_memory(*callee_peak_memory),
-    ));
     let mut machine_child = Machine::new(machine_builder.build());
     set_vm_max_cycles(&mut machine_child, cycles_limit);
     Ok(machine_child)
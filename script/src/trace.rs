@@ -0,0 +1,183 @@
+use crate::{error::ScriptError, syscalls::SPAWN, types::ScriptGroupType};
+use ckb_types::{core::Cycle, packed::Byte32};
+use ckb_vm::{
+    registers::{A0, A1, A2, A3, A4, A5, A7},
+    Error as VMError, Memory, Register, SupportMachine, Syscalls,
+};
+use std::sync::{Arc, Mutex};
+
+/// The maximum number of syscalls and debug messages recorded for a script group, the rest are
+/// only counted.
+pub const MAX_TRACED_EVENTS: usize = 10_000;
+
+/// A syscall invoked by a script.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SyscallTrace {
+    /// The VM invoking the syscall, see `VmTrace::id`.
+    pub vm_id: u64,
+    /// The syscall number in register A7.
+    pub number: u64,
+    /// The arguments in registers A0 to A5.
+    pub args: [u64; 6],
+    /// The return code in register A0 after the syscall.
+    pub return_code: u64,
+    /// The cycles consumed by the syscall, including the ones of the VM it spawns.
+    pub cycles: Cycle,
+}
+
+/// A VM created to run a script group, the root one or a spawned one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VmTrace {
+    /// The index of the VM in the group, the root VM is 0.
+    pub id: u64,
+    /// The VM which spawns this one, None for the root VM.
+    pub parent: Option<u64>,
+    /// The exit code, None if the VM is aborted.
+    pub exit_code: Option<i8>,
+    /// The cycles consumed by the VM, including the ones of the VMs it spawns. None if the VM is
+    /// aborted.
+    pub cycles: Option<Cycle>,
+}
+
+/// The execution trace of a script group.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScriptGroupTrace {
+    /// The script group type.
+    pub group_type: ScriptGroupType,
+    /// The script hash of the group.
+    pub script_hash: Byte32,
+    /// The exit code of the root VM, None if the group is aborted or it is not run in a VM.
+    pub exit_code: Option<i8>,
+    /// The cycles consumed by the group, None if the group is aborted.
+    pub cycles: Option<Cycle>,
+    /// The error failing the group.
+    pub error: Option<ScriptError>,
+    /// The syscalls in the returning order, so the ones of a spawned VM precede the spawn syscall.
+    pub syscalls: Vec<SyscallTrace>,
+    /// The messages printed by the debug syscall.
+    pub debug_messages: Vec<String>,
+    /// The VMs in the creating order.
+    pub vms: Vec<VmTrace>,
+    /// The number of syscalls and debug messages exceeding `MAX_TRACED_EVENTS`.
+    pub dropped_events: u64,
+}
+
+#[derive(Default)]
+pub(crate) struct TraceRecorder {
+    pub(crate) syscalls: Vec<SyscallTrace>,
+    pub(crate) debug_messages: Vec<String>,
+    pub(crate) vms: Vec<VmTrace>,
+    pub(crate) dropped_events: u64,
+    // The VMs in the middle of a spawn syscall, the last one is the parent of a new VM
+    spawning: Vec<u64>,
+}
+
+pub(crate) type SharedTraceRecorder = Arc<Mutex<TraceRecorder>>;
+
+impl TraceRecorder {
+    pub(crate) fn new_vm(&mut self) -> u64 {
+        let id = self.vms.len() as u64;
+        self.vms.push(VmTrace {
+            id,
+            parent: self.spawning.last().copied(),
+            exit_code: None,
+            cycles: None,
+        });
+        id
+    }
+
+    pub(crate) fn add_debug_message(&mut self, message: &str) {
+        if self.debug_messages.len() < MAX_TRACED_EVENTS {
+            self.debug_messages.push(message.to_owned());
+        } else {
+            self.dropped_events += 1;
+        }
+    }
+
+    fn add_syscall(&mut self, syscall: SyscallTrace) {
+        if self.syscalls.len() < MAX_TRACED_EVENTS {
+            self.syscalls.push(syscall);
+        } else {
+            self.dropped_events += 1;
+        }
+    }
+}
+
+/// Records the syscalls handled by the inner syscalls.
+pub(crate) struct TracedSyscalls<Mac> {
+    vm_id: u64,
+    inner: Box<dyn Syscalls<Mac>>,
+    recorder: SharedTraceRecorder,
+}
+
+impl<Mac> TracedSyscalls<Mac> {
+    pub(crate) fn new(
+        vm_id: u64,
+        inner: Box<dyn Syscalls<Mac>>,
+        recorder: SharedTraceRecorder,
+    ) -> Self {
+        TracedSyscalls {
+            vm_id,
+            inner,
+            recorder,
+        }
+    }
+}
+
+impl<Mac: SupportMachine> Syscalls<Mac> for TracedSyscalls<Mac> {
+    fn initialize(&mut self, machine: &mut Mac) -> Result<(), VMError> {
+        self.inner.initialize(machine)
+    }
+
+    fn ecall(&mut self, machine: &mut Mac) -> Result<bool, VMError> {
+        let number = machine.registers()[A7].to_u64();
+        let args = [A0, A1, A2, A3, A4, A5].map(|register| machine.registers()[register].to_u64());
+        let cycles = machine.cycles();
+        let is_spawn = number == SPAWN;
+        let first_new_vm = if is_spawn {
+            let mut recorder = self.recorder.lock().expect("trace recorder lock");
+            recorder.spawning.push(self.vm_id);
+            recorder.vms.len()
+        } else {
+            0
+        };
+
+        let result = self.inner.ecall(machine);
+
+        let mut recorder = self.recorder.lock().expect("trace recorder lock");
+        if is_spawn {
+            recorder.spawning.pop();
+        }
+        if let Ok(true) = result {
+            let return_code = machine.registers()[A0].to_u64();
+            let syscall_cycles = machine.cycles().saturating_sub(cycles);
+            if is_spawn && return_code == 0 {
+                // The exit code of the spawned VM is written to the address stored at the
+                // second field of the spawn arguments
+                let exit_code = machine
+                    .memory_mut()
+                    .load64(&Mac::REG::from_u64(args[5].wrapping_add(8)))
+                    .and_then(|addr| machine.memory_mut().load8(&addr))
+                    .map(|code| code.to_u8() as i8)
+                    .ok();
+                let vm_id = self.vm_id;
+                if let Some(vm) = recorder
+                    .vms
+                    .get_mut(first_new_vm)
+                    .filter(|vm| vm.parent == Some(vm_id))
+                {
+                    vm.exit_code = exit_code;
+                    vm.cycles = Some(syscall_cycles);
+                }
+            }
+            recorder.add_syscall(SyscallTrace {
+                vm_id: self.vm_id,
+                number,
+                args,
+                return_code,
+                cycles: syscall_cycles,
+            });
+        }
+        result
+    }
+}
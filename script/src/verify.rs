@@ -3,12 +3,16 @@ use crate::syscalls::Pause;
 use crate::{
     cost_model::transferred_byte_cycles,
     error::{ScriptError, TransactionScriptError},
+    extension::{enabled_syscall_extensions, SyscallExtension, SyscallExtensionContext},
+    gdb::{self, GdbStub, SharedGdbStub},
+    profile::{self, ScriptGroupProfile, SharedProfiler},
     syscalls::{
         spawn::{build_child_machine, update_caller_machine},
         CurrentCycles, CurrentMemory, Debugger, Exec, GetMemoryLimit, LoadBlockExtension, LoadCell,
         LoadCellData, LoadHeader, LoadInput, LoadScript, LoadScriptHash, LoadTx, LoadWitness,
         SetContent, Spawn, VMVersion,
     },
+    trace::{ScriptGroupTrace, SharedTraceRecorder, TracedSyscalls},
     type_id::TypeIdSystemScript,
     types::{
         CoreMachine, DebugPrinter, Indices, Machine, MachineContext, ResumableMachine, ResumePoint,
@@ -31,17 +35,20 @@ use ckb_types::{
     packed::{Byte32, CellOutput, OutPoint, Script},
     prelude::*,
 };
+use ckb_vm::machine::Pause as VMPause;
 use ckb_vm::{
     cost_model::estimate_cycles,
     snapshot::{resume, Snapshot},
     DefaultMachineBuilder, Error as VMInternalError, SupportMachine, Syscalls,
 };
-use std::cell::RefCell;
+use rayon::prelude::*;
 use std::collections::{BTreeMap, HashMap};
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, RwLock};
+
+use std::sync::atomic::{AtomicU64, Ordering};
 
 #[cfg(test)]
-use core::sync::atomic::{AtomicBool, Ordering};
+use core::sync::atomic::AtomicBool;
 
 #[cfg(test)]
 mod tests;
@@ -68,25 +75,27 @@ enum DataGuard {
 }
 
 /// LazyData wrapper make sure not-loaded data will be loaded only after one access
-#[derive(Debug, PartialEq, Eq, Clone)]
-struct LazyData(RefCell<DataGuard>);
+///
+/// It is shared by the script groups verified in parallel.
+#[derive(Debug, Clone)]
+struct LazyData(Arc<RwLock<DataGuard>>);
 
 impl LazyData {
     fn from_cell_meta(cell_meta: &CellMeta) -> LazyData {
         match &cell_meta.mem_cell_data {
-            Some(data) => LazyData(RefCell::new(DataGuard::Loaded(data.to_owned()))),
-            None => LazyData(RefCell::new(DataGuard::NotLoaded(
+            Some(data) => LazyData(Arc::new(RwLock::new(DataGuard::Loaded(data.to_owned())))),
+            None => LazyData(Arc::new(RwLock::new(DataGuard::NotLoaded(
                 cell_meta.out_point.clone(),
-            ))),
+            )))),
         }
     }
 
     fn access<DL: CellDataProvider>(&self, data_loader: &DL) -> Bytes {
-        let guard = self.0.borrow().to_owned();
+        let guard = self.0.read().expect("lazy data lock").to_owned();
         match guard {
             DataGuard::NotLoaded(out_point) => {
                 let data = data_loader.get_cell_data(&out_point).expect("cell data");
-                self.0.replace(DataGuard::Loaded(data.to_owned()));
+                *self.0.write().expect("lazy data lock") = DataGuard::Loaded(data.to_owned());
                 data
             }
             DataGuard::Loaded(bytes) => bytes,
@@ -94,7 +103,7 @@ impl LazyData {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, Clone)]
 enum Binaries {
     Unique(Byte32, LazyData),
     Duplicate(Byte32, LazyData),
@@ -127,6 +136,10 @@ impl Binaries {
 pub struct TransactionScriptsSyscallsGenerator<DL> {
     pub(crate) data_loader: DL,
     debug_printer: DebugPrinter,
+    trace_recorder: Option<SharedTraceRecorder>,
+    pub(crate) profiler: Option<SharedProfiler>,
+    pub(crate) gdb: Option<SharedGdbStub>,
+    syscall_extensions: Vec<Arc<dyn SyscallExtension>>,
     pub(crate) outputs: Arc<Vec<CellMeta>>,
     pub(crate) rtx: Arc<ResolvedTransaction>,
     #[cfg(test)]
@@ -154,6 +167,7 @@ impl<DL: CellDataProvider + HeaderProvider + ExtensionProvider + Send + Sync + C
             Arc::clone(&self.outputs),
             group_inputs,
             group_outputs,
+            self.profiler.clone(),
         )
     }
 
@@ -313,9 +327,40 @@ impl<DL: CellDataProvider + HeaderProvider + ExtensionProvider + Send + Sync + C
                 self.build_load_block_extension(Arc::clone(&script_group_input_indices)),
             ));
         }
+        if !self.syscall_extensions.is_empty() {
+            let context = SyscallExtensionContext {
+                data_loader: Arc::new(self.data_loader.clone()),
+                rtx: &self.rtx,
+                script_group,
+                script_version,
+            };
+            for extension in &self.syscall_extensions {
+                syscalls.append(&mut extension.build_syscalls(&context));
+            }
+        }
         syscalls
     }
 
+    /// Wraps the syscalls of a new VM to record them when the trace is enabled.
+    pub fn trace_syscalls(
+        &self,
+        syscalls: Vec<Box<(dyn Syscalls<CoreMachine>)>>,
+    ) -> Vec<Box<(dyn Syscalls<CoreMachine>)>> {
+        match &self.trace_recorder {
+            Some(recorder) => {
+                let vm_id = recorder.lock().expect("trace recorder lock").new_vm();
+                syscalls
+                    .into_iter()
+                    .map(|syscall| {
+                        Box::new(TracedSyscalls::new(vm_id, syscall, Arc::clone(recorder)))
+                            as Box<(dyn Syscalls<CoreMachine>)>
+                    })
+                    .collect()
+            }
+            None => syscalls,
+        }
+    }
+
     /// Generate root syscalls.
     pub fn generate_root_syscalls(
         &self,
@@ -345,6 +390,64 @@ impl<DL: CellDataProvider + HeaderProvider + ExtensionProvider + Send + Sync + C
     }
 }
 
+/// The result of a script group run ahead in parallel, see `enable_parallel_verification`.
+#[derive(Debug, Clone, Copy)]
+enum ParallelRun {
+    /// The group passes with the cycles, which are the same under any sufficient cycles limit.
+    Completed(Cycle),
+    /// The group fails under the cycles limit, it may pass under a higher limit.
+    Failed(Cycle),
+}
+
+/// The cycles budget shared by the script groups run ahead together.
+///
+/// The groups not started yet are skipped and the running ones are interrupted once the cycles
+/// consumed reach the budget, so the work run ahead is bounded by the cycles limit of the
+/// sequential verification instead of growing with the number of groups.
+struct RunAheadBudget {
+    max_cycles: Cycle,
+    consumed: AtomicU64,
+    running: Mutex<Vec<VMPause>>,
+}
+
+impl RunAheadBudget {
+    fn new(max_cycles: Cycle) -> Self {
+        RunAheadBudget {
+            max_cycles,
+            consumed: AtomicU64::new(0),
+            running: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn remaining(&self) -> Cycle {
+        self.max_cycles
+            .saturating_sub(self.consumed.load(Ordering::Acquire))
+    }
+
+    // Tracks the machine to interrupt it once the budget is exhausted, returns false if it is
+    // exhausted already.
+    fn start(&self, pause: VMPause) -> bool {
+        let mut running = self.running.lock().expect("run ahead budget lock");
+        if self.remaining() == 0 {
+            return false;
+        }
+        running.push(pause);
+        true
+    }
+
+    fn consume(&self, cycles: Cycle) {
+        let consumed = self
+            .consumed
+            .fetch_add(cycles, Ordering::AcqRel)
+            .saturating_add(cycles);
+        if consumed >= self.max_cycles {
+            for pause in self.running.lock().expect("run ahead budget lock").iter() {
+                pause.interrupt();
+            }
+        }
+    }
+}
+
 /// This struct leverages CKB VM to verify transaction inputs.
 ///
 /// FlatBufferBuilder owned `Vec<u8>` that grows as needed, in the
@@ -367,6 +470,10 @@ pub struct TransactionScriptsVerifier<DL> {
     tx_env: Arc<TxVerifyEnv>,
 
     generator: TransactionScriptsSyscallsGenerator<DL>,
+
+    // The script groups run ahead in parallel by the group index, None if the groups are
+    // verified sequentially
+    parallel_runs: Option<Mutex<HashMap<usize, ParallelRun>>>,
 }
 
 impl<DL: CellDataProvider + HeaderProvider + ExtensionProvider + Send + Sync + Clone + 'static>
@@ -465,6 +572,14 @@ impl<DL: CellDataProvider + HeaderProvider + ExtensionProvider + Send + Sync + C
         let generator = TransactionScriptsSyscallsGenerator {
             data_loader: data_loader.clone(),
             debug_printer: Arc::clone(&debug_printer),
+            trace_recorder: None,
+            profiler: None,
+            gdb: None,
+            // Like the VM versions, the proposal window is not allowed to prejudge the extensions
+            syscall_extensions: enabled_syscall_extensions(
+                consensus.hardfork_switch(),
+                tx_env.epoch_number_without_proposal_window(),
+            ),
             outputs: Arc::clone(&outputs),
             rtx: Arc::clone(&rtx),
             #[cfg(test)]
@@ -483,6 +598,7 @@ impl<DL: CellDataProvider + HeaderProvider + ExtensionProvider + Send + Sync + C
             consensus,
             tx_env,
             generator,
+            parallel_runs: None,
         }
     }
 
@@ -499,6 +615,157 @@ impl<DL: CellDataProvider + HeaderProvider + ExtensionProvider + Send + Sync + C
         self.generator.debug_printer = Arc::new(func);
     }
 
+    /// Enables recording the syscalls, the debug messages and the spawned VMs of the script
+    /// groups, see `trace`.
+    ///
+    /// It replaces the debug printer.
+    pub fn enable_trace(&mut self) {
+        let recorder: SharedTraceRecorder = Default::default();
+        let debug_recorder = Arc::clone(&recorder);
+        self.set_debug_printer(move |_hash: &Byte32, message: &str| {
+            debug_recorder
+                .lock()
+                .expect("trace recorder lock")
+                .add_debug_message(message);
+        });
+        self.generator.trace_recorder = Some(recorder);
+    }
+
+    /// Enables verifying the script groups in parallel in `verify`, `complete` and the resumable
+    /// verification.
+    ///
+    /// The groups are run ahead in parallel, each one under the cycles limit left when it starts,
+    /// and the groups run ahead together stop once the cycles they consume reach the limit. The
+    /// groups then complete in order with the cycles they have taken, and a group which fails,
+    /// stops or takes more cycles than the limit actually left to it is verified again under the
+    /// limit, so the cycles, the suspended states and the errors are the same as the sequential
+    /// verification.
+    pub fn enable_parallel_verification(&mut self) {
+        self.parallel_runs = Some(Default::default());
+    }
+
+    // Runs the groups from `skip` ahead in parallel, except the ones whose result is known.
+    fn run_ahead(&self, skip: usize, max_cycles: Cycle) {
+        let runs = match &self.parallel_runs {
+            Some(runs) => runs,
+            None => return,
+        };
+        let pending: Vec<(usize, &ScriptGroup)> = {
+            let runs = runs.lock().expect("parallel runs lock");
+            self.groups()
+                .enumerate()
+                .skip(skip)
+                .filter(|(idx, _)| match runs.get(idx) {
+                    Some(ParallelRun::Completed(_)) => false,
+                    Some(ParallelRun::Failed(limit)) => *limit < max_cycles,
+                    None => true,
+                })
+                .map(|(idx, (_hash, group))| (idx, group))
+                .collect()
+        };
+        // The sequential verification runs the next group anyway
+        if pending.len() < 2 {
+            return;
+        }
+        let budget = RunAheadBudget::new(max_cycles);
+        let results: Vec<(usize, ParallelRun)> = pending
+            .into_par_iter()
+            .filter_map(|(idx, group)| self.run_group_ahead(group, &budget).map(|run| (idx, run)))
+            .collect();
+        runs.lock().expect("parallel runs lock").extend(results);
+    }
+
+    // Runs the group under the cycles left in the budget. Returns None if the budget is exhausted
+    // before the group completes, the sequential verification runs it then.
+    fn run_group_ahead(&self, group: &ScriptGroup, budget: &RunAheadBudget) -> Option<ParallelRun> {
+        let max_cycles = budget.remaining();
+        if max_cycles == 0 {
+            return None;
+        }
+        let result = if group.script.code_hash() == TYPE_ID_CODE_HASH.pack()
+            && Into::<u8>::into(group.script.hash_type()) == Into::<u8>::into(ScriptHashType::Type)
+        {
+            let result = self.verify_script_group(group, max_cycles);
+            budget.consume(*result.as_ref().unwrap_or(&0));
+            result
+        } else {
+            self.run_with_budget(group, max_cycles, budget)?
+        };
+        Some(match result {
+            Ok(cycles) => ParallelRun::Completed(cycles),
+            Err(_) => ParallelRun::Failed(max_cycles),
+        })
+    }
+
+    // Like `run`, but the machine is interrupted once the budget is exhausted, returns None then.
+    fn run_with_budget(
+        &self,
+        group: &ScriptGroup,
+        max_cycles: Cycle,
+        budget: &RunAheadBudget,
+    ) -> Option<Result<Cycle, ScriptError>> {
+        let program = match self.extract_script(&group.script) {
+            Ok(program) => program,
+            Err(err) => return Some(Err(err)),
+        };
+        let mut machine = match self.build_machine(group, max_cycles, Default::default()) {
+            Ok(machine) => machine,
+            Err(err) => return Some(Err(err)),
+        };
+        if !budget.start(machine.machine.pause()) {
+            return None;
+        }
+
+        let map_vm_internal_error = |error: VMInternalError| match error {
+            VMInternalError::CyclesExceeded => ScriptError::ExceededMaximumCycles(max_cycles),
+            _ => ScriptError::VMInternalError(error),
+        };
+        let result = machine
+            .load_program(&program, &[])
+            .and_then(|bytes| {
+                machine
+                    .machine
+                    .add_cycles_no_checking(transferred_byte_cycles(bytes))
+            })
+            .and_then(|_| machine.run());
+        let cycles = machine.machine.cycles();
+        budget.consume(cycles);
+
+        match result {
+            Ok(0) => Some(Ok(cycles)),
+            Ok(code) => Some(Err(ScriptError::validation_failure(&group.script, code))),
+            Err(VMInternalError::Pause) => None,
+            Err(err) => Some(Err(map_vm_internal_error(err))),
+        }
+    }
+
+    // Returns the cycles of the group run ahead if it has passed within the cycles limit.
+    fn completed_ahead(&self, idx: usize, max_cycles: Cycle) -> Option<Cycle> {
+        let runs = self
+            .parallel_runs
+            .as_ref()?
+            .lock()
+            .expect("parallel runs lock");
+        match runs.get(&idx) {
+            Some(ParallelRun::Completed(cycles)) if *cycles <= max_cycles => Some(*cycles),
+            _ => None,
+        }
+    }
+
+    // Verifies the group like `verify_group_with_chunk` from the start, or takes the result run
+    // ahead.
+    fn verify_group_with_chunk_ahead(
+        &self,
+        idx: usize,
+        group: &ScriptGroup,
+        max_cycles: Cycle,
+    ) -> Result<ChunkState, ScriptError> {
+        match self.completed_ahead(idx, max_cycles) {
+            Some(cycles) => Ok(ChunkState::Completed(cycles)),
+            None => self.verify_group_with_chunk(group, max_cycles, &[]),
+        }
+    }
+
     #[cfg(test)]
     pub(crate) fn set_skip_pause(&self, skip_pause: bool) {
         self.skip_pause.store(skip_pause, Ordering::SeqCst);
@@ -607,22 +874,212 @@ impl<DL: CellDataProvider + HeaderProvider + ExtensionProvider + Send + Sync + C
     pub fn verify(&self, max_cycles: Cycle) -> Result<Cycle, Error> {
         let mut cycles: Cycle = 0;
 
+        self.run_ahead(0, max_cycles);
         // Now run each script group
-        for (_hash, group) in self.groups() {
+        for (idx, (_hash, group)) in self.groups().enumerate() {
             // max_cycles must reduce by each group exec
-            let used_cycles = self
-                .verify_script_group(group, max_cycles - cycles)
-                .map_err(|e| {
-                    #[cfg(feature = "logging")]
-                    logging::on_script_error(_hash, &self.hash(), &e);
-                    e.source(group)
-                })?;
+            let used_cycles = match self.completed_ahead(idx, max_cycles - cycles) {
+                Some(used_cycles) => Ok(used_cycles),
+                None => self.verify_script_group(group, max_cycles - cycles),
+            }
+            .map_err(|e| {
+                #[cfg(feature = "logging")]
+                logging::on_script_error(_hash, &self.hash(), &e);
+                e.source(group)
+            })?;
 
             cycles = wrapping_cycles_add(cycles, used_cycles, group)?;
         }
         Ok(cycles)
     }
 
+    /// Verifies the transaction like `verify`, and returns the execution traces of the script
+    /// groups have run.
+    ///
+    /// The verification stops at the first failing group, whose trace carries the error. The
+    /// syscalls, the debug messages and the spawned VMs are only recorded after `enable_trace`.
+    pub fn trace(&self, max_cycles: Cycle) -> Vec<ScriptGroupTrace> {
+        let mut cycles: Cycle = 0;
+        let mut traces = Vec::new();
+        for (group_type, hash, group) in self.groups_with_type() {
+            if let Some(recorder) = &self.generator.trace_recorder {
+                *recorder.lock().expect("trace recorder lock") = Default::default();
+            }
+            let remaining_cycles = max_cycles.saturating_sub(cycles);
+            let (exit_code, result) = if group.script.code_hash() == TYPE_ID_CODE_HASH.pack()
+                && Into::<u8>::into(group.script.hash_type())
+                    == Into::<u8>::into(ScriptHashType::Type)
+            {
+                (None, self.verify_script_group(group, remaining_cycles))
+            } else {
+                match self.detailed_run(group, remaining_cycles) {
+                    Ok((0, machine)) => (Some(0), Ok(machine.machine.cycles())),
+                    Ok((code, _)) => (
+                        Some(code),
+                        Err(ScriptError::validation_failure(&group.script, code)),
+                    ),
+                    Err(err) => (None, Err(err)),
+                }
+            };
+            let mut trace = ScriptGroupTrace {
+                group_type,
+                script_hash: hash.clone(),
+                exit_code,
+                cycles: result.as_ref().ok().copied(),
+                error: result.as_ref().err().cloned(),
+                syscalls: Vec::new(),
+                debug_messages: Vec::new(),
+                vms: Vec::new(),
+                dropped_events: 0,
+            };
+            if let Some(recorder) = &self.generator.trace_recorder {
+                let recorder = std::mem::take(&mut *recorder.lock().expect("trace recorder lock"));
+                trace.syscalls = recorder.syscalls;
+                trace.debug_messages = recorder.debug_messages;
+                trace.vms = recorder.vms;
+                trace.dropped_events = recorder.dropped_events;
+                if let Some(root) = trace.vms.first_mut() {
+                    root.exit_code = exit_code;
+                    root.cycles = trace.cycles;
+                }
+            }
+            traces.push(trace);
+            match result {
+                Ok(used_cycles) => cycles = cycles.saturating_add(used_cycles),
+                Err(_) => break,
+            }
+        }
+        traces
+    }
+
+    /// Verifies the transaction like `verify`, and returns the cycles profiles of the script
+    /// groups have run.
+    ///
+    /// The VMs run instruction by instruction to attribute the cycles, which is much slower than
+    /// `verify`. The verification stops at the first failing group, whose profile carries the
+    /// error.
+    pub fn profile(&self, max_cycles: Cycle) -> Vec<ScriptGroupProfile> {
+        let mut cycles: Cycle = 0;
+        let mut profiles = Vec::new();
+        for (group_type, hash, group) in self.groups_with_type() {
+            let mut profile = ScriptGroupProfile::new(group_type, hash.clone());
+            let remaining_cycles = max_cycles.saturating_sub(cycles);
+            let result = if group.script.code_hash() == TYPE_ID_CODE_HASH.pack()
+                && Into::<u8>::into(group.script.hash_type())
+                    == Into::<u8>::into(ScriptHashType::Type)
+            {
+                self.verify_script_group(group, remaining_cycles)
+            } else {
+                self.profiled_run(group, remaining_cycles, &mut profile)
+            };
+            profile.cycles = result.as_ref().ok().copied();
+            profile.error = result.as_ref().err().cloned();
+            profiles.push(profile);
+            match result {
+                Ok(used_cycles) => cycles = cycles.saturating_add(used_cycles),
+                Err(_) => break,
+            }
+        }
+        profiles
+    }
+
+    fn profiled_run(
+        &self,
+        script_group: &ScriptGroup,
+        max_cycles: Cycle,
+        profile: &mut ScriptGroupProfile,
+    ) -> Result<Cycle, ScriptError> {
+        let program = self.extract_script(&script_group.script)?;
+        let profiler: SharedProfiler = Default::default();
+        let mut generator = self.generator.clone();
+        generator.profiler = Some(Arc::clone(&profiler));
+        let mut machine = self.build_machine_with_generator(
+            &generator,
+            script_group,
+            max_cycles,
+            Default::default(),
+        )?;
+
+        let map_vm_internal_error = |error: VMInternalError| match error {
+            VMInternalError::CyclesExceeded => ScriptError::ExceededMaximumCycles(max_cycles),
+            _ => ScriptError::VMInternalError(error),
+        };
+
+        let bytes = machine
+            .load_program(&program, &[])
+            .map_err(map_vm_internal_error)?;
+        machine
+            .machine
+            .add_cycles_no_checking(transferred_byte_cycles(bytes))
+            .map_err(map_vm_internal_error)?;
+        profile.loading_cycles = machine.machine.cycles();
+        let result = profile::run(&mut machine, program, &profiler);
+        std::mem::take(&mut *profiler.lock().expect("profiler lock")).finish(profile);
+        let code = result.map_err(map_vm_internal_error)?;
+
+        if code == 0 {
+            Ok(machine.machine.cycles())
+        } else {
+            Err(ScriptError::validation_failure(&script_group.script, code))
+        }
+    }
+
+    /// Runs a single script group like `verify_single`, under the debugger connected to the GDB
+    /// stub.
+    ///
+    /// The VMs run instruction by instruction, so the debugger can set breakpoints, step and
+    /// inspect the registers and the memory of the root VM and the VMs it spawns. The type id
+    /// script is built in the node, it can not be debugged.
+    pub fn debug_single(
+        &self,
+        script_group_type: ScriptGroupType,
+        script_hash: &Byte32,
+        max_cycles: Cycle,
+        stub: GdbStub,
+    ) -> Result<Cycle, ScriptError> {
+        let script_group = self
+            .find_script_group(script_group_type, script_hash)
+            .ok_or_else(|| ScriptError::ScriptNotFound(script_hash.clone()))?;
+        if script_group.script.code_hash() == TYPE_ID_CODE_HASH.pack()
+            && Into::<u8>::into(script_group.script.hash_type())
+                == Into::<u8>::into(ScriptHashType::Type)
+        {
+            return Err(ScriptError::Other(
+                "the type id script can not be debugged".to_owned(),
+            ));
+        }
+        let program = self.extract_script(&script_group.script)?;
+        let stub: SharedGdbStub = Arc::new(Mutex::new(stub));
+        let mut generator = self.generator.clone();
+        generator.gdb = Some(Arc::clone(&stub));
+        let mut machine = self.build_machine_with_generator(
+            &generator,
+            script_group,
+            max_cycles,
+            Default::default(),
+        )?;
+
+        let map_vm_internal_error = |error: VMInternalError| match error {
+            VMInternalError::CyclesExceeded => ScriptError::ExceededMaximumCycles(max_cycles),
+            _ => ScriptError::VMInternalError(error),
+        };
+
+        let bytes = machine
+            .load_program(&program, &[])
+            .map_err(map_vm_internal_error)?;
+        machine
+            .machine
+            .add_cycles_no_checking(transferred_byte_cycles(bytes))
+            .map_err(map_vm_internal_error)?;
+        let code = gdb::run(&mut machine, &stub).map_err(map_vm_internal_error)?;
+
+        if code == 0 {
+            Ok(machine.machine.cycles())
+        } else {
+            Err(ScriptError::validation_failure(&script_group.script, code))
+        }
+    }
+
     /// Performing a resumable verification on the transaction scripts.
     ///
     /// ## Params
@@ -637,6 +1094,7 @@ impl<DL: CellDataProvider + HeaderProvider + ExtensionProvider + Send + Sync + C
     pub fn resumable_verify(&self, limit_cycles: Cycle) -> Result<VerifyResult, Error> {
         let mut cycles = 0;
 
+        self.run_ahead(0, limit_cycles);
         let groups: Vec<_> = self.groups().collect();
         for (idx, (_hash, group)) in groups.iter().enumerate() {
             // vm should early return invalid cycles
@@ -645,7 +1103,7 @@ impl<DL: CellDataProvider + HeaderProvider + ExtensionProvider + Send + Sync + C
                     .source(group)
             })?;
 
-            match self.verify_group_with_chunk(group, remain_cycles, &[]) {
+            match self.verify_group_with_chunk_ahead(idx, group, remain_cycles) {
                 Ok(ChunkState::Completed(used_cycles)) => {
                     cycles = wrapping_cycles_add(cycles, used_cycles, group)?;
                 }
@@ -715,13 +1173,14 @@ impl<DL: CellDataProvider + HeaderProvider + ExtensionProvider + Send + Sync + C
         }
 
         let skip = snap.current + 1;
+        self.run_ahead(skip, limit_cycles.saturating_sub(current_used));
         for (idx, (_hash, group)) in self.groups().enumerate().skip(skip) {
             let remain_cycles = limit_cycles.checked_sub(current_used).ok_or_else(|| {
                 ScriptError::Other(format!("expect invalid cycles {limit_cycles} {cycles}"))
                     .source(group)
             })?;
 
-            match self.verify_group_with_chunk(group, remain_cycles, &[]) {
+            match self.verify_group_with_chunk_ahead(idx, group, remain_cycles) {
                 Ok(ChunkState::Completed(used_cycles)) => {
                     current_used = wrapping_cycles_add(current_used, used_cycles, group)?;
                     cycles = wrapping_cycles_add(cycles, used_cycles, group)?;
@@ -798,13 +1257,14 @@ impl<DL: CellDataProvider + HeaderProvider + ExtensionProvider + Send + Sync + C
             }
         }
 
+        self.run_ahead(current + 1, limit_cycles.saturating_sub(current_used));
         for (idx, (_hash, group)) in self.groups().enumerate().skip(current + 1) {
             let remain_cycles = limit_cycles.checked_sub(current_used).ok_or_else(|| {
                 ScriptError::Other(format!("expect invalid cycles {limit_cycles} {cycles}"))
                     .source(group)
             })?;
 
-            match self.verify_group_with_chunk(group, remain_cycles, &[]) {
+            match self.verify_group_with_chunk_ahead(idx, group, remain_cycles) {
                 Ok(ChunkState::Completed(used_cycles)) => {
                     current_used = wrapping_cycles_add(current_used, used_cycles, group)?;
                     cycles = wrapping_cycles_add(cycles, used_cycles, group)?;
@@ -869,13 +1329,14 @@ impl<DL: CellDataProvider + HeaderProvider + ExtensionProvider + Send + Sync + C
             }
         }
 
-        for (_hash, group) in self.groups().skip(snap.current + 1) {
+        self.run_ahead(snap.current + 1, max_cycles.saturating_sub(cycles));
+        for (idx, (_hash, group)) in self.groups().enumerate().skip(snap.current + 1) {
             let remain_cycles = max_cycles.checked_sub(cycles).ok_or_else(|| {
                 ScriptError::Other(format!("expect invalid cycles {max_cycles} {cycles}"))
                     .source(group)
             })?;
 
-            match self.verify_group_with_chunk(group, remain_cycles, &[]) {
+            match self.verify_group_with_chunk_ahead(idx, group, remain_cycles) {
                 Ok(ChunkState::Completed(used_cycles)) => {
                     cycles = wrapping_cycles_add(cycles, used_cycles, current_group)?;
                 }
@@ -998,12 +1459,26 @@ impl<DL: CellDataProvider + HeaderProvider + ExtensionProvider + Send + Sync + C
         script_group: &ScriptGroup,
         max_cycles: Cycle,
         context: Arc<Mutex<MachineContext>>,
+    ) -> Result<Machine, ScriptError> {
+        self.build_machine_with_generator(&self.generator, script_group, max_cycles, context)
+    }
+
+    fn build_machine_with_generator(
+        &self,
+        generator: &TransactionScriptsSyscallsGenerator<DL>,
+        script_group: &ScriptGroup,
+        max_cycles: Cycle,
+        context: Arc<Mutex<MachineContext>>,
     ) -> Result<Machine, ScriptError> {
         let script_version = self.select_version(&script_group.script)?;
         let core_machine = script_version.init_core_machine(max_cycles);
         let machine_builder = DefaultMachineBuilder::<CoreMachine>::new(core_machine)
             .instruction_cycle_func(Box::new(estimate_cycles));
-        let syscalls = self.generate_syscalls(script_version, script_group, context);
+        let syscalls = generator.trace_syscalls(generator.generate_root_syscalls(
+            script_version,
+            script_group,
+            context,
+        ));
         let machine_builder = syscalls
             .into_iter()
             .fold(machine_builder, |builder, syscall| builder.syscall(syscall));
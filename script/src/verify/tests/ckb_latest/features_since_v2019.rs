@@ -5,15 +5,22 @@ use ckb_error::assert_error_eq;
 use ckb_hash::{blake2b_256, new_blake2b};
 use ckb_test_chain_utils::always_success_cell;
 use ckb_types::{
-    core::{capacity_bytes, cell::CellMetaBuilder, Capacity, ScriptHashType, TransactionBuilder},
+    core::{
+        capacity_bytes, cell::CellMetaBuilder, Capacity, EpochNumber, ScriptHashType,
+        TransactionBuilder,
+    },
     h256,
     packed::{self, CellDep, CellInput, CellOutputBuilder, OutPoint, Script},
 };
 use ckb_vm::Error as VmError;
+use std::collections::BTreeMap;
 use std::io::Read;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use super::SCRIPT_VERSION;
 use crate::{
+    extension::{check_syscall_extensions, setup_syscall_extensions},
+    syscalls::DEBUG_PRINT_SYSCALL_NUMBER,
     type_id::TYPE_ID_CYCLES,
     verify::{tests::utils::*, *},
 };
@@ -52,6 +59,88 @@ fn check_always_success_hash() {
     assert!(result.is_ok());
 }
 
+struct CountingExtension(Arc<AtomicUsize>);
+
+impl SyscallExtension for CountingExtension {
+    fn build_syscalls(
+        &self,
+        _context: &SyscallExtensionContext,
+    ) -> Vec<Box<(dyn Syscalls<CoreMachine>)>> {
+        self.0.fetch_add(1, Ordering::SeqCst);
+        vec![Box::new(CountingSyscall)]
+    }
+}
+
+struct CountingSyscall;
+
+impl Syscalls<CoreMachine> for CountingSyscall {
+    fn initialize(&mut self, _machine: &mut CoreMachine) -> Result<(), VmError> {
+        Ok(())
+    }
+
+    fn ecall(&mut self, _machine: &mut CoreMachine) -> Result<bool, VmError> {
+        Ok(false)
+    }
+}
+
+#[test]
+fn check_syscall_extension() {
+    let script_version = SCRIPT_VERSION;
+
+    let (always_success_cell, always_success_cell_data, always_success_script) =
+        always_success_cell();
+    let output = CellOutputBuilder::default()
+        .capacity(capacity_bytes!(100).pack())
+        .lock(always_success_script.clone())
+        .build();
+    let input = CellInput::new(OutPoint::null(), 0);
+
+    let transaction = TransactionBuilder::default().input(input).build();
+    let dummy_cell = create_dummy_cell(output);
+
+    let always_success_cell = CellMetaBuilder::from_cell_output(
+        always_success_cell.clone(),
+        always_success_cell_data.to_owned(),
+    )
+    .transaction_info(default_transaction_info())
+    .build();
+
+    let rtx = ResolvedTransaction {
+        transaction,
+        resolved_cell_deps: vec![always_success_cell],
+        resolved_inputs: vec![dummy_cell],
+        resolved_dep_groups: vec![],
+    };
+
+    let mut verifier = TransactionScriptsVerifierWithEnv::new();
+    let cycles = verifier.verify_without_limit(script_version, &rtx).unwrap();
+
+    // The extensions are installed for the whole process, and no other test enables them.
+    let enabled = Arc::new(AtomicUsize::new(0));
+    let pending = Arc::new(AtomicUsize::new(0));
+    let mut extensions: BTreeMap<String, Arc<dyn SyscallExtension>> = BTreeMap::new();
+    extensions.insert(
+        "enabled".to_owned(),
+        Arc::new(CountingExtension(Arc::clone(&enabled))),
+    );
+    extensions.insert(
+        "pending".to_owned(),
+        Arc::new(CountingExtension(Arc::clone(&pending))),
+    );
+    assert!(setup_syscall_extensions(extensions).is_ok());
+
+    verifier.enable_syscall_extension("enabled", 0);
+    verifier.enable_syscall_extension("pending", EpochNumber::MAX);
+    assert!(check_syscall_extensions(verifier.consensus().hardfork_switch()).is_ok());
+    let result = verifier.verify_without_limit(script_version, &rtx);
+    assert_eq!(result.unwrap(), cycles);
+    assert_eq!(enabled.load(Ordering::SeqCst), 1);
+    assert_eq!(pending.load(Ordering::SeqCst), 0);
+
+    verifier.enable_syscall_extension("unknown", EpochNumber::MAX);
+    assert!(check_syscall_extensions(verifier.consensus().hardfork_switch()).is_err());
+}
+
 #[test]
 fn check_signature() {
     let script_version = SCRIPT_VERSION;
@@ -1163,6 +1252,91 @@ fn check_debugger() {
     let verifier = TransactionScriptsVerifierWithEnv::new();
     let result = verifier.verify_without_limit(script_version, &rtx);
     assert!(result.is_ok(), "result {result:?}");
+
+    let traces = verifier.verify_map(script_version, &rtx, |mut verifier| {
+        verifier.enable_trace();
+        verifier.trace(u64::MAX)
+    });
+    assert_eq!(traces.len(), 2);
+    let total_cycles: Cycle = traces.iter().map(|trace| trace.cycles.unwrap()).sum();
+    assert_eq!(total_cycles, result.unwrap());
+    let trace = traces
+        .iter()
+        .find(|trace| trace.group_type == ScriptGroupType::Type)
+        .unwrap();
+    assert_eq!(trace.exit_code, Some(0));
+    assert_eq!(trace.error, None);
+    assert_eq!(trace.debug_messages, vec!["debugger print utf-8 string"]);
+    assert!(trace.syscalls.iter().any(|syscall| syscall.vm_id == 0
+        && syscall.number == DEBUG_PRINT_SYSCALL_NUMBER
+        && syscall.return_code == 0));
+    assert_eq!(trace.vms.len(), 1);
+    assert_eq!(trace.vms[0].cycles, trace.cycles);
+}
+
+#[test]
+fn check_typical_secp256k1_blake160_2_in_2_out_tx_in_parallel() {
+    let script_version = SCRIPT_VERSION;
+    let rtx = random_2_in_2_out_rtx();
+    let verifier = TransactionScriptsVerifierWithEnv::new();
+
+    let cycles = verifier
+        .verify_map(script_version, &rtx, |verifier| {
+            verifier.verify(TWO_IN_TWO_OUT_CYCLES)
+        })
+        .unwrap();
+    let parallel_cycles = verifier
+        .verify_map(script_version, &rtx, |mut verifier| {
+            verifier.enable_parallel_verification();
+            verifier.verify(TWO_IN_TWO_OUT_CYCLES)
+        })
+        .unwrap();
+    assert_eq!(cycles, parallel_cycles);
+
+    // The second group fails in the sequential verification, while it passes when run ahead
+    let error = verifier
+        .verify_map(script_version, &rtx, |verifier| verifier.verify(cycles - 1))
+        .unwrap_err();
+    let parallel_error = verifier
+        .verify_map(script_version, &rtx, |mut verifier| {
+            verifier.enable_parallel_verification();
+            verifier.verify(cycles - 1)
+        })
+        .unwrap_err();
+    assert_eq!(error.to_string(), parallel_error.to_string());
+}
+
+#[test]
+fn check_typical_secp256k1_blake160_2_in_2_out_resume_in_parallel() {
+    let script_version = SCRIPT_VERSION;
+    let rtx = random_2_in_2_out_rtx();
+    let step_cycles = TWO_IN_TWO_OUT_CYCLES / 3;
+    let verifier = TransactionScriptsVerifierWithEnv::new();
+
+    let cycles_once = verifier
+        .verify_map(script_version, &rtx, |verifier| {
+            verifier.verify(TWO_IN_TWO_OUT_CYCLES)
+        })
+        .unwrap();
+    let cycles = verifier.verify_map(script_version, &rtx, |mut verifier| {
+        verifier.enable_parallel_verification();
+        let mut init_state = match verifier.resumable_verify(step_cycles).unwrap() {
+            VerifyResult::Suspended(state) => state,
+            VerifyResult::Completed(cycles) => return cycles,
+        };
+        loop {
+            let (limit_cycles, _last) =
+                init_state.next_limit_cycles(step_cycles, TWO_IN_TWO_OUT_CYCLES);
+            match verifier
+                .resume_from_state(init_state, limit_cycles)
+                .unwrap()
+            {
+                VerifyResult::Suspended(state) => init_state = state,
+                VerifyResult::Completed(cycles) => return cycles,
+            }
+        }
+    });
+    assert_eq!(cycles, cycles_once);
 }
 
 #[test]
@@ -1215,3 +1389,77 @@ fn _check_typical_secp256k1_blake160_2_in_2_out_resume_load_cycles(step_cycles:
     }
     assert_eq!(cycles, cycles_once, "step_cycles {step_cycles}");
 }
+
+#[test]
+fn check_many_groups_in_parallel_bounded_by_max_cycles() {
+    let script_version = SCRIPT_VERSION;
+    let groups = 256u8;
+    let (always_success_cell, always_success_cell_data, always_success_script) =
+        always_success_cell();
+    let always_success_out_point = OutPoint::new(h256!("0x11").pack(), 0);
+
+    let mut builder = TransactionBuilder::default().cell_dep(
+        CellDep::new_builder()
+            .out_point(always_success_out_point.clone())
+            .build(),
+    );
+    let mut resolved_inputs = Vec::new();
+    for i in 0..groups {
+        // each input is a group of its own by the args
+        let input = CellInput::new(OutPoint::new(h256!("0x1234").pack(), i.into()), 0);
+        let lock = always_success_script
+            .as_builder()
+            .args(Bytes::from(vec![i]).pack())
+            .build();
+        let input_cell = CellOutputBuilder::default()
+            .capacity(capacity_bytes!(100).pack())
+            .lock(lock)
+            .build();
+        resolved_inputs.push(
+            CellMetaBuilder::from_cell_output(input_cell, Bytes::new())
+                .out_point(input.previous_output())
+                .build(),
+        );
+        builder = builder.input(input);
+    }
+    let resolved_always_success_cell = CellMetaBuilder::from_cell_output(
+        always_success_cell.clone(),
+        always_success_cell_data.to_owned(),
+    )
+    .out_point(always_success_out_point)
+    .build();
+    let rtx = ResolvedTransaction {
+        transaction: builder.build(),
+        resolved_cell_deps: vec![resolved_always_success_cell],
+        resolved_inputs,
+        resolved_dep_groups: vec![],
+    };
+
+    // the cycles of a few groups only
+    let max_cycles = ALWAYS_SUCCESS_SCRIPT_CYCLE * 8;
+    let verifier = TransactionScriptsVerifierWithEnv::new();
+    let error = verifier
+        .verify(script_version, &rtx, max_cycles)
+        .unwrap_err();
+    let (parallel_error, completed, run_cycles) =
+        verifier.verify_map(script_version, &rtx, |mut verifier| {
+            verifier.enable_parallel_verification();
+            let result = verifier.verify(max_cycles);
+            let runs = verifier.parallel_runs.as_ref().unwrap().lock().unwrap();
+            let run_cycles: Cycle = runs
+                .values()
+                .filter_map(|run| match run {
+                    ParallelRun::Completed(cycles) => Some(*cycles),
+                    ParallelRun::Failed(_) => None,
+                })
+                .sum();
+            (result.unwrap_err(), runs.len(), run_cycles)
+        });
+    assert_eq!(error.to_string(), parallel_error.to_string());
+
+    // the groups stop being run ahead once the cycles limit is consumed, except the ones already
+    // running on the other threads
+    let threads = rayon::current_num_threads() as Cycle;
+    assert!(run_cycles <= max_cycles + ALWAYS_SUCCESS_SCRIPT_CYCLE * threads);
+    assert!(completed < groups.into());
+}
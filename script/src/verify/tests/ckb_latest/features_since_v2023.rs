@@ -4,6 +4,8 @@ use ckb_types::{
 };
 
 use super::SCRIPT_VERSION;
+use crate::syscall_name;
+use crate::syscalls::SPAWN;
 use crate::verify::{tests::utils::*, *};
 
 // check_vm_version: vm_version() returns 2.
@@ -11,6 +13,8 @@ use crate::verify::{tests::utils::*, *};
 // check_set_content: set_content() succeed in prime script but write length is 0.
 // check_spawn_strcat: a smoking test for spawn().
 // check_spawn_strcat_data_hash: position child script by data hash.
+// check_spawn_strcat_trace: the trace records the spawned VM and the spawn syscall.
+// check_spawn_strcat_profile: the profile splits the cycles of the caller and the callee.
 // check_spawn_get_memory_limit: call get_memory_limit() in child script.
 // check_spawn_set_content: set_content() with content < length, = length and > length.
 // check_spawn_out_of_cycles: child script out-of-cycles.
@@ -159,6 +163,121 @@ fn check_spawn_strcat() {
     assert_eq!(result.is_ok(), script_version >= ScriptVersion::V2);
 }
 
+#[test]
+fn check_spawn_strcat_trace() {
+    let script_version = SCRIPT_VERSION;
+
+    let (spawn_caller_cell, spawn_caller_data_hash) =
+        load_cell_from_path("testdata/spawn_caller_strcat");
+    let (spawn_callee_cell, _spawn_callee_data_hash) =
+        load_cell_from_path("testdata/spawn_callee_strcat");
+
+    let spawn_caller_script = Script::new_builder()
+        .hash_type(script_version.data_hash_type().into())
+        .code_hash(spawn_caller_data_hash)
+        .build();
+    let output = CellOutputBuilder::default()
+        .capacity(capacity_bytes!(100).pack())
+        .lock(spawn_caller_script)
+        .build();
+    let input = CellInput::new(OutPoint::null(), 0);
+
+    let transaction = TransactionBuilder::default().input(input).build();
+    let dummy_cell = create_dummy_cell(output);
+
+    let rtx = ResolvedTransaction {
+        transaction,
+        resolved_cell_deps: vec![spawn_caller_cell, spawn_callee_cell],
+        resolved_inputs: vec![dummy_cell],
+        resolved_dep_groups: vec![],
+    };
+    let verifier = TransactionScriptsVerifierWithEnv::new();
+    let traces = verifier.verify_map(script_version, &rtx, |mut verifier| {
+        verifier.enable_trace();
+        verifier.trace(u64::MAX)
+    });
+    assert_eq!(traces.len(), 1);
+    let trace = &traces[0];
+    if script_version >= ScriptVersion::V2 {
+        assert_eq!(trace.exit_code, Some(0));
+        assert_eq!(trace.vms.len(), 2);
+        assert_eq!(trace.vms[0].cycles, trace.cycles);
+        assert_eq!(trace.vms[1].parent, Some(0));
+        assert_eq!(trace.vms[1].exit_code, Some(0));
+        let spawn = trace
+            .syscalls
+            .iter()
+            .find(|syscall| syscall.number == SPAWN)
+            .unwrap();
+        assert_eq!(spawn.vm_id, 0);
+        assert_eq!(spawn.return_code, 0);
+        assert_eq!(Some(spawn.cycles), trace.vms[1].cycles);
+        assert!(trace.syscalls.iter().any(|syscall| syscall.vm_id == 1));
+    } else {
+        assert!(trace.error.is_some());
+    }
+}
+
+#[test]
+fn check_spawn_strcat_profile() {
+    let script_version = SCRIPT_VERSION;
+
+    let (spawn_caller_cell, spawn_caller_data_hash) =
+        load_cell_from_path("testdata/spawn_caller_strcat");
+    let (spawn_callee_cell, _spawn_callee_data_hash) =
+        load_cell_from_path("testdata/spawn_callee_strcat");
+
+    let spawn_caller_script = Script::new_builder()
+        .hash_type(script_version.data_hash_type().into())
+        .code_hash(spawn_caller_data_hash)
+        .build();
+    let output = CellOutputBuilder::default()
+        .capacity(capacity_bytes!(100).pack())
+        .lock(spawn_caller_script)
+        .build();
+    let input = CellInput::new(OutPoint::null(), 0);
+
+    let transaction = TransactionBuilder::default().input(input).build();
+    let dummy_cell = create_dummy_cell(output);
+
+    let rtx = ResolvedTransaction {
+        transaction,
+        resolved_cell_deps: vec![spawn_caller_cell, spawn_callee_cell],
+        resolved_inputs: vec![dummy_cell],
+        resolved_dep_groups: vec![],
+    };
+    let verifier = TransactionScriptsVerifierWithEnv::new();
+    let result = verifier.verify_without_limit(script_version, &rtx);
+    let profiles = verifier.verify_map(script_version, &rtx, |verifier| verifier.profile(u64::MAX));
+    assert_eq!(profiles.len(), 1);
+    let profile = &profiles[0];
+    if script_version >= ScriptVersion::V2 {
+        assert_eq!(profile.cycles, Some(result.unwrap()));
+        let instruction_cycles: Cycle = profile.instructions.values().map(|c| c.cycles).sum();
+        let syscall_cycles: Cycle = profile.syscalls.values().map(|c| c.cycles).sum();
+        let sample_cycles: Cycle = profile.samples.values().sum();
+        assert_eq!(
+            profile.cycles,
+            Some(profile.loading_cycles + instruction_cycles + syscall_cycles)
+        );
+        assert_eq!(profile.cycles, Some(profile.loading_cycles + sample_cycles));
+        assert_eq!(profile.syscalls[&SPAWN].count, 1);
+        assert_eq!(syscall_name(SPAWN), Some("spawn"));
+        assert_eq!(profile.programs.len(), 2);
+        // The stacks of the callee are nested in the stack of the spawn syscall
+        assert!(profile
+            .samples
+            .keys()
+            .any(
+                |frames| frames.first().map(|frame| frame.program) == Some(0)
+                    && frames.last().map(|frame| frame.program) == Some(1)
+            ));
+        assert!(!profile.folded_stacks().is_empty());
+    } else {
+        assert!(profile.error.is_some());
+    }
+}
+
 #[test]
 fn check_spawn_strcat_data_hash() {
     let script_version = SCRIPT_VERSION;
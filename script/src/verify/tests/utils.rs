@@ -150,6 +150,7 @@ impl TransactionScriptsVerifierWithEnv {
                 .rfc_0049(version_2_enabled_at)
                 .build()
                 .unwrap(),
+            syscall_extensions: Default::default(),
         };
         let consensus = Arc::new(
             ConsensusBuilder::default()
@@ -165,6 +166,22 @@ impl TransactionScriptsVerifierWithEnv {
         }
     }
 
+    pub(crate) fn consensus(&self) -> &Consensus {
+        &self.consensus
+    }
+
+    pub(crate) fn enable_syscall_extension(&mut self, name: &str, start: EpochNumber) {
+        let mut hardfork_switch = self.consensus.hardfork_switch().clone();
+        hardfork_switch
+            .syscall_extensions
+            .insert(name.to_owned(), start);
+        self.consensus = Arc::new(
+            ConsensusBuilder::default()
+                .hardfork_switch(hardfork_switch)
+                .build(),
+        );
+    }
+
     pub(crate) fn verify_without_limit(
         &self,
         version: ScriptVersion,
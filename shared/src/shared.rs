@@ -81,16 +81,21 @@ impl Shared {
         }
     }
     /// Spawn freeze background thread that periodically checks and moves ancient data from the kv database into the freezer.
-    pub fn spawn_freeze(&self) -> Option<FreezerClose> {
+    ///
+    /// When `prune_retention_depth` is set, the frozen blocks out of this depth are pruned then.
+    pub fn spawn_freeze(&self, prune_retention_depth: Option<BlockNumber>) -> Option<FreezerClose> {
         if let Some(freezer) = self.store.freezer() {
             ckb_logger::info!("Freezer enabled");
+            if let Some(depth) = prune_retention_depth {
+                ckb_logger::info!("Pruned mode enabled, retention depth {}", depth);
+            }
             let signal_receiver = new_crossbeam_exit_rx();
             let shared = self.clone();
             let freeze_jh = thread::Builder::new()
                 .spawn(move || loop {
                     match signal_receiver.recv_timeout(FREEZER_INTERVAL) {
                         Err(_) => {
-                            if let Err(e) = shared.freeze() {
+                            if let Err(e) = shared.freeze(prune_retention_depth) {
                                 ckb_logger::error!("Freezer error {}", e);
                                 break;
                             }
@@ -112,7 +117,7 @@ impl Shared {
         None
     }
 
-    fn freeze(&self) -> Result<(), Error> {
+    fn freeze(&self, prune_retention_depth: Option<BlockNumber>) -> Result<(), Error> {
         let freezer = self.store.freezer().expect("freezer inited");
         let snapshot = self.snapshot();
         let current_epoch = snapshot.epoch_ext().number();
@@ -135,12 +140,16 @@ impl Shared {
 
         let frozen_number = freezer.number();
 
-        let threshold = cmp::min(
+        let mut threshold = cmp::min(
             snapshot
                 .get_block_number(&limit_block_hash)
                 .expect("get_block_number"),
             frozen_number + MAX_FREEZE_LIMIT,
         );
+        // the blocks whose bodies are not back-filled yet can't be frozen
+        if let Some((backfill_start, _)) = snapshot.get_backfill_range() {
+            threshold = cmp::min(threshold, backfill_start);
+        }
 
         ckb_logger::trace!(
             "Freezer current_epoch {} number {} threshold {}",
@@ -163,6 +172,10 @@ impl Shared {
         // Wipe out frozen data
         self.wipe_out_frozen_data(&snapshot, ret, stopped)?;
 
+        if let Some(depth) = prune_retention_depth {
+            self.prune(&snapshot, depth)?;
+        }
+
         ckb_logger::trace!("Freezer completed");
 
         Ok(())
@@ -243,6 +256,37 @@ impl Shared {
         Ok(())
     }
 
+    /// Prunes the frozen blocks out of the retention depth, the headers, the block exts and the
+    /// filter hashes are kept, while the bodies in the freezer and the filter data are dropped.
+    fn prune(&self, snapshot: &Snapshot, depth: BlockNumber) -> Result<(), Error> {
+        let freezer = self.store.freezer().expect("freezer inited");
+        let tail = freezer.tail();
+        // the latest frozen block is always kept
+        let threshold = cmp::min(
+            snapshot.tip_number().saturating_sub(depth),
+            freezer.number().saturating_sub(1),
+        );
+        if threshold <= tail {
+            return Ok(());
+        }
+
+        ckb_logger::trace!("Freezer prune blocks {} to {}", tail, threshold);
+        // drop the filters first, an interruption before pruning the freezer leaves the range to
+        // be pruned again
+        let mut batch = self.store.new_write_batch();
+        for number in cmp::max(tail, 1)..threshold {
+            if let Some(hash) = snapshot.get_block_hash(number) {
+                batch.delete_block_filter(&hash)?;
+            }
+        }
+        self.store.write(&batch).map_err(|e| {
+            ckb_logger::error!("Freezer write_batch delete filters failed {}", e);
+            e
+        })?;
+
+        freezer.prune(threshold)
+    }
+
     fn compact_block_body(&self, start: &packed::Byte32, end: &packed::Byte32) {
         let start_t = packed::TransactionKey::new_builder()
             .block_hash(start.clone())
@@ -21,13 +21,14 @@ use ckb_db_schema::COLUMNS;
 use ckb_error::{Error, InternalErrorKind};
 use ckb_logger::{error, info};
 use ckb_migrate::migrate::Migrate;
-use ckb_notify::{NotifyController, NotifyService};
+use ckb_notify::{NotifyController, NotifyService, TransactionStatusEvent};
 use ckb_store::{ChainDB, ChainStore, Freezer};
 use ckb_types::core::hardfork::HardForks;
 use ckb_types::core::service::PoolTransactionEntry;
 use ckb_types::core::tx_pool::Reject;
 use ckb_types::core::EpochExt;
 use ckb_types::core::HeaderView;
+use ckb_types::packed::Byte32;
 use ckb_verification::cache::init_cache;
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
@@ -401,6 +402,8 @@ fn register_tx_pool_callback(tx_pool_builder: &mut TxPoolServiceBuilder, notify:
     };
     tx_pool_builder.register_pending(Box::new(move |entry: &TxEntry| {
         // notify
+        notify_pending
+            .notify_transaction_status(TransactionStatusEvent::Pending(entry.transaction().hash()));
         let notify_tx_entry = create_notify_entry(entry);
         notify_pending.notify_new_transaction(notify_tx_entry);
     }));
@@ -408,10 +411,21 @@ fn register_tx_pool_callback(tx_pool_builder: &mut TxPoolServiceBuilder, notify:
     let notify_proposed = notify.clone();
     tx_pool_builder.register_proposed(Box::new(move |entry: &TxEntry| {
         // notify
+        notify_proposed.notify_transaction_status(TransactionStatusEvent::Proposed(
+            entry.transaction().hash(),
+        ));
         let notify_tx_entry = create_notify_entry(entry);
         notify_proposed.notify_proposed_transaction(notify_tx_entry);
     }));
 
+    let notify_submit_reject = notify.clone();
+    tx_pool_builder.register_submit_reject(Box::new(move |tx_hash: &Byte32, reject: &Reject| {
+        notify_submit_reject.notify_transaction_status(TransactionStatusEvent::Rejected(
+            tx_hash.clone(),
+            reject.clone(),
+        ));
+    }));
+
     let notify_reject = notify;
     tx_pool_builder.register_reject(Box::new(
         move |tx_pool: &mut TxPool, entry: &TxEntry, reject: Reject| {
@@ -431,7 +445,14 @@ fn register_tx_pool_callback(tx_pool_builder: &mut TxPoolServiceBuilder, notify:
                 }
             }
 
-            // notify
+            // notify, the pool only abandons an in-pool transaction with `RBFRejected` when a
+            // conflicting transaction replaces it
+            let tx_hash = entry.transaction().hash();
+            notify_reject.notify_transaction_status(if matches!(reject, Reject::RBFRejected(..)) {
+                TransactionStatusEvent::Replaced(tx_hash, reject.clone())
+            } else {
+                TransactionStatusEvent::Rejected(tx_hash, reject.clone())
+            });
             let notify_tx_entry = create_notify_entry(entry);
             notify_reject.notify_reject_transaction(notify_tx_entry, reject);
         },
@@ -9,7 +9,7 @@ use crate::{
         self, Deployment, DeploymentPos, ThresholdState, Versionbits, VersionbitsCache,
         VersionbitsConditionChecker, VersionbitsIndexer,
     },
-    OUTPUT_INDEX_DAO, OUTPUT_INDEX_SECP256K1_BLAKE160_MULTISIG_ALL,
+    AssumeUtxo, OUTPUT_INDEX_DAO, OUTPUT_INDEX_SECP256K1_BLAKE160_MULTISIG_ALL,
     OUTPUT_INDEX_SECP256K1_BLAKE160_SIGHASH_ALL,
 };
 use ckb_constant::{
@@ -297,6 +297,7 @@ impl ConsensusBuilder {
                 versionbits_caches: VersionbitsCache::default(),
                 starting_block_limiting_dao_withdrawing_lock:
                     STARTING_BLOCK_LIMITING_DAO_WITHDRAWING_LOCK,
+                assume_utxo: None,
             },
         }
     }
@@ -502,6 +503,12 @@ impl ConsensusBuilder {
             starting_block_limiting_dao_withdrawing_lock;
         self
     }
+
+    /// Sets the cell set snapshot pinned in the chain spec for the new Consensus.
+    pub fn assume_utxo(mut self, assume_utxo: Option<AssumeUtxo>) -> Self {
+        self.inner.assume_utxo = assume_utxo;
+        self
+    }
 }
 
 /// Struct Consensus defines various parameters that influence chain consensus
@@ -584,6 +591,8 @@ pub struct Consensus {
     pub versionbits_caches: VersionbitsCache,
     /// Starting block where DAO withdrawing lock is limited in size
     pub starting_block_limiting_dao_withdrawing_lock: u64,
+    /// The cell set snapshot which a new node can bootstrap from
+    pub assume_utxo: Option<AssumeUtxo>,
 }
 
 // genesis difficulty should not be zero
@@ -768,6 +777,11 @@ impl Consensus {
         self.starting_block_limiting_dao_withdrawing_lock
     }
 
+    /// The cell set snapshot pinned in the chain spec
+    pub fn assume_utxo(&self) -> Option<&AssumeUtxo> {
+        self.assume_utxo.as_ref()
+    }
+
     // Apply the dampening filter on hash_rate estimation calculate
     fn bounding_hash_rate(
         &self,
@@ -6,6 +6,7 @@ use ckb_types::core::{
     EpochNumber,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Hard forks parameters for spec.
 #[derive(Default, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
@@ -13,12 +14,16 @@ use serde::{Deserialize, Serialize};
 pub struct HardForkConfig {
     /// ckb 2023 epoch
     pub ckb2023: Option<EpochNumber>,
+    /// The first epochs of the syscall extensions by the extension names, only for custom chains
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub syscall_extensions: BTreeMap<String, EpochNumber>,
 }
 
 impl HardForkConfig {
     /// If all parameters which have been set are correct for mainnet, then
     /// sets all `None` to default values, otherwise, return an `Err`.
     pub fn complete_mainnet(&self) -> Result<HardForks, String> {
+        self.check_no_syscall_extensions()?;
         let mut ckb2021 = CKB2021::new_builder();
         ckb2021 = self.update_2021(
             ckb2021,
@@ -29,12 +34,14 @@ impl HardForkConfig {
         Ok(HardForks {
             ckb2021: ckb2021.build()?,
             ckb2023: CKB2023::new_mirana().as_builder().build()?,
+            syscall_extensions: BTreeMap::new(),
         })
     }
 
     /// If all parameters which have been set are correct for testnet, then
     /// sets all `None` to default values, otherwise, return an `Err`.
     pub fn complete_testnet(&self) -> Result<HardForks, String> {
+        self.check_no_syscall_extensions()?;
         let mut ckb2021 = CKB2021::new_builder();
         ckb2021 = self.update_2021(
             ckb2021,
@@ -47,9 +54,18 @@ impl HardForkConfig {
         Ok(HardForks {
             ckb2021: ckb2021.build()?,
             ckb2023: ckb2023.build()?,
+            syscall_extensions: BTreeMap::new(),
         })
     }
 
+    fn check_no_syscall_extensions(&self) -> Result<(), String> {
+        if self.syscall_extensions.is_empty() {
+            Ok(())
+        } else {
+            Err("The syscall extensions are only allowed on custom chains.".to_owned())
+        }
+    }
+
     fn update_2021(
         &self,
         builder: CKB2021Builder,
@@ -88,6 +104,10 @@ impl HardForkConfig {
             CKB2023::new_dev_default()
         };
 
-        Ok(HardForks { ckb2021, ckb2023 })
+        Ok(HardForks {
+            ckb2021,
+            ckb2023,
+            syscall_extensions: self.syscall_extensions.clone(),
+        })
     }
 }
@@ -248,6 +248,26 @@ pub struct Params {
     /// See [`hardfork_switch`](consensus/struct.Consensus.html#structfield.hardfork_switch)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub hardfork: Option<HardForkConfig>,
+    /// The cell set snapshot which a new node can bootstrap from.
+    ///
+    /// See [`assume_utxo`](consensus/struct.Consensus.html#structfield.assume_utxo)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assume_utxo: Option<AssumeUtxo>,
+}
+
+/// The cell set snapshot taken at a checkpoint block, which is pinned in the chain spec.
+///
+/// A new node can import the snapshot and start syncing from the checkpoint immediately, the
+/// historical blocks are back-filled in the background.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AssumeUtxo {
+    /// The number of the checkpoint block
+    pub number: BlockNumber,
+    /// The hash of the checkpoint block
+    pub block_hash: H256,
+    /// The blake2b-256 hash of the snapshot file
+    pub snapshot_hash: H256,
 }
 
 impl Params {
@@ -606,7 +626,8 @@ impl ChainSpec {
             .starting_block_limiting_dao_withdrawing_lock(
                 self.params.starting_block_limiting_dao_withdrawing_lock(),
             )
-            .hardfork_switch(hardfork_switch);
+            .hardfork_switch(hardfork_switch)
+            .assume_utxo(self.params.assume_utxo.clone());
 
         if let Some(deployments) = self.softfork_deployments() {
             builder = builder.softfork_deployments(deployments);
@@ -1,9 +1,9 @@
 use ckb_resource::Resource;
-use ckb_types::{core::Capacity, packed, prelude::*, H256};
+use ckb_types::{core::Capacity, h256, packed, prelude::*, H256};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-use crate::{build_genesis_epoch_ext, ChainSpec, Params};
+use crate::{build_genesis_epoch_ext, AssumeUtxo, ChainSpec, Params};
 
 mod consensus;
 mod versionbits;
@@ -225,6 +225,48 @@ fn test_default_params() {
     };
 
     assert_eq!(params, expected);
+
+    let test_params: &str = r#"
+            [assume_utxo]
+            number = 100
+            block_hash = "0x0000000000000000000000000000000000000000000000000000000000000001"
+            snapshot_hash = "0x0000000000000000000000000000000000000000000000000000000000000002"
+        "#;
+
+    let params: Params = toml::from_str(test_params).unwrap();
+    let expected = Params {
+        assume_utxo: Some(AssumeUtxo {
+            number: 100,
+            block_hash: h256!("0x1"),
+            snapshot_hash: h256!("0x2"),
+        }),
+        ..Default::default()
+    };
+
+    assert_eq!(params, expected);
+}
+
+#[test]
+fn test_syscall_extensions() {
+    let test_params: &str = r#"
+            [hardfork]
+            ckb2023 = 0
+
+            [hardfork.syscall_extensions]
+            oracle = 10
+        "#;
+
+    let params: Params = toml::from_str(test_params).unwrap();
+    let config = params.hardfork.unwrap();
+    assert_eq!(config.syscall_extensions.get("oracle"), Some(&10));
+
+    let hardfork_switch = config.complete_with_dev_default().unwrap();
+    assert!(!hardfork_switch.is_syscall_extension_enabled("oracle", 9));
+    assert!(hardfork_switch.is_syscall_extension_enabled("oracle", 10));
+    assert!(!hardfork_switch.is_syscall_extension_enabled("other", 10));
+
+    assert!(config.complete_mainnet().is_err());
+    assert!(config.complete_testnet().is_err());
 }
 
 #[test]
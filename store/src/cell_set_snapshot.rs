@@ -0,0 +1,545 @@
+//! The cell set snapshot, which lets a new node bootstrap from the checkpoint pinned by
+//! `assume_utxo` in the chain spec instead of verifying the whole chain history.
+//!
+//! A snapshot is taken at the tip, it consists of
+//!
+//! * all the epochs,
+//! * the headers, block exts and cellbases of all the blocks, the cellbases are required to count
+//!   the versionbits signals,
+//! * the full recent blocks, which are required to verify the following blocks, and
+//! * the live cells.
+//!
+//! The file starts with a magic and a version, followed by the records, each record is encoded as
+//! `kind (u8) | fields count (u8) | (length (u32, little endian) | molecule field)*`. The snapshot
+//! hash is the blake2b-256 hash of the whole file.
+//!
+//! After the import, the bodies of the older blocks are back-filled from the peers in the
+//! background, see [`ChainStore::get_backfill_range`]. The block filters are not built, nor
+//! advertised to the peers, until the back-fill finishes.
+use crate::{ChainDB, ChainStore, StoreTransaction};
+use ckb_chain_spec::consensus::Consensus;
+use ckb_db::iter::IteratorMode;
+use ckb_db_schema::{
+    COLUMN_BLOCK_BODY, COLUMN_BLOCK_HEADER, COLUMN_CELL, COLUMN_CELL_DATA, COLUMN_INDEX,
+};
+use ckb_error::{Error, InternalErrorKind};
+use ckb_hash::{new_blake2b, Blake2b};
+use ckb_types::{
+    core::{BlockExt, BlockNumber, BlockView, EpochExt, EpochNumber, HeaderView, TransactionView},
+    packed::{self, Byte32},
+    prelude::*,
+    utilities::merkle_mountain_range::ChainRootMMR,
+};
+use std::cmp;
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+const MAGIC: &[u8] = b"CKBCELLS";
+const VERSION: u32 = 1;
+
+const RECORD_EPOCH: u8 = 0;
+const RECORD_HEADER: u8 = 1;
+const RECORD_BLOCK: u8 = 2;
+const RECORD_CELL: u8 = 3;
+
+/// The max size of a field in a record, which is far larger than a block.
+const MAX_FIELD_SIZE: usize = 32 * 1024 * 1024;
+
+/// The count of records written in a db transaction when importing a snapshot.
+const IMPORT_BATCH_SIZE: usize = 10_000;
+
+/// The checkpoint at which a cell set snapshot is taken.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotCheckpoint {
+    /// The number of the checkpoint block
+    pub number: BlockNumber,
+    /// The hash of the checkpoint block
+    pub block_hash: Byte32,
+    /// The blake2b-256 hash of the snapshot file
+    pub snapshot_hash: Byte32,
+}
+
+/// Exports the cell set snapshot at the tip into the file `path`, returns the checkpoint which
+/// should be pinned by `assume_utxo` in the chain spec.
+pub fn export_cell_set_snapshot<S: ChainStore>(
+    store: &S,
+    consensus: &Consensus,
+    path: &Path,
+) -> Result<SnapshotCheckpoint, Error> {
+    if store.get_backfill_range().is_some() {
+        return Err(InternalErrorKind::Database
+            .other(
+                "the block bodies are not back-filled yet, the cell set snapshot can't be exported",
+            )
+            .into());
+    }
+    let tip = store
+        .get_tip_header()
+        .ok_or_else(|| InternalErrorKind::Database.other("tip block is not found"))?;
+    let file = OpenOptions::new()
+        .create_new(true)
+        .write(true)
+        .open(path)
+        .map_err(|err| io_error(path, err))?;
+    let mut writer = SnapshotWriter {
+        inner: BufWriter::new(file),
+        hasher: new_blake2b(),
+    };
+    writer.write(MAGIC)?;
+    writer.write(&VERSION.to_le_bytes())?;
+
+    for number in 0..=tip.epoch().number() {
+        let epoch = store
+            .get_epoch_index(number)
+            .and_then(|index| store.get_epoch_ext(&index))
+            .ok_or_else(|| not_found(format!("epoch#{number}")))?;
+        writer.write_record(RECORD_EPOCH, &[epoch.pack().as_slice()])?;
+    }
+
+    for number in 0..=tip.number() {
+        let hash = store
+            .get_block_hash(number)
+            .ok_or_else(|| not_found(format!("hash for block#{number}")))?;
+        let header = store
+            .get_block_header(&hash)
+            .ok_or_else(|| not_found(format!("header for block#{number}")))?;
+        let ext = store
+            .get_block_ext(&hash)
+            .ok_or_else(|| not_found(format!("block ext for block#{number}")))?;
+        // the bodies of the frozen blocks are only kept in the freezer
+        let cellbase = store
+            .get_cellbase(&hash)
+            .or_else(|| {
+                store
+                    .get_block(&hash)
+                    .and_then(|block| block.transactions().first().cloned())
+            })
+            .ok_or_else(|| not_found(format!("cellbase for block#{number}")))?;
+        let ext: packed::BlockExtV1 = ext.pack();
+        writer.write_record(
+            RECORD_HEADER,
+            &[
+                header.data().as_slice(),
+                ext.as_slice(),
+                cellbase.data().as_slice(),
+            ],
+        )?;
+    }
+
+    for number in recent_start(consensus, &tip)..=tip.number() {
+        let block = store
+            .get_block_hash(number)
+            .and_then(|hash| store.get_block(&hash))
+            .ok_or_else(|| not_found(format!("block#{number}")))?;
+        writer.write_record(RECORD_BLOCK, &[block.data().as_slice()])?;
+    }
+
+    for (key, value) in store.get_iter(COLUMN_CELL, IteratorMode::Start) {
+        let out_point = out_point_from_cell_key(&key);
+        let data = store
+            .get(COLUMN_CELL_DATA, &key)
+            .map(|slice| slice.as_ref().to_vec())
+            .unwrap_or_default();
+        if data.is_empty() {
+            writer.write_record(RECORD_CELL, &[out_point.as_slice(), &value[..]])?;
+        } else {
+            writer.write_record(RECORD_CELL, &[out_point.as_slice(), &value[..], &data[..]])?;
+        }
+    }
+
+    Ok(SnapshotCheckpoint {
+        number: tip.number(),
+        block_hash: tip.hash(),
+        snapshot_hash: writer.finish(path)?,
+    })
+}
+
+/// Imports the cell set snapshot in the file `path` into a new database. The snapshot is verified
+/// against the checkpoint pinned by `assume_utxo` in the chain spec while it is written, the tip is
+/// only updated if the snapshot matches.
+pub fn import_cell_set_snapshot(
+    db: &ChainDB,
+    consensus: &Consensus,
+    path: &Path,
+) -> Result<SnapshotCheckpoint, Error> {
+    let assume_utxo = consensus.assume_utxo().ok_or_else(|| {
+        InternalErrorKind::Config
+            .other("the chain spec doesn't pin a cell set snapshot by `assume_utxo`")
+    })?;
+    let tip = db
+        .get_tip_header()
+        .ok_or_else(|| InternalErrorKind::Database.other("tip block is not found"))?;
+    if tip.number() != 0 {
+        return Err(InternalErrorKind::Config
+            .other("the cell set snapshot can only be imported into a new database")
+            .into());
+    }
+
+    write(
+        db,
+        consensus,
+        path,
+        &SnapshotCheckpoint {
+            number: assume_utxo.number,
+            block_hash: assume_utxo.block_hash.pack(),
+            snapshot_hash: assume_utxo.snapshot_hash.pack(),
+        },
+    )
+}
+
+/// The first block which is exported in full, the blocks after it are required to verify the
+/// uncles, the proposals and the rewards of the following blocks.
+fn recent_start(consensus: &Consensus, tip: &HeaderView) -> BlockNumber {
+    let window =
+        consensus.finalization_delay_length() + 2 * consensus.tx_proposal_window().farthest();
+    cmp::min(
+        tip.number().saturating_sub(window),
+        tip.number() - tip.epoch().index(),
+    )
+}
+
+/// Writes the snapshot into the database in batches, the file is read only once. The headers are
+/// checked to be chained from the genesis and the checkpoint is computed while reading, the tip is
+/// updated at last, only if the checkpoint matches `expected`, so a failed or interrupted import
+/// can be retried.
+fn write(
+    db: &ChainDB,
+    consensus: &Consensus,
+    path: &Path,
+    expected: &SnapshotCheckpoint,
+) -> Result<SnapshotCheckpoint, Error> {
+    let mut reader = SnapshotReader::open(path)?;
+
+    // the genesis cells are attached when the database is initialized, and the cells of a failed
+    // import may be left
+    loop {
+        let out_points: Vec<_> = db
+            .get_iter(COLUMN_CELL, IteratorMode::Start)
+            .take(IMPORT_BATCH_SIZE)
+            .map(|(key, _value)| out_point_from_cell_key(&key))
+            .collect();
+        if out_points.is_empty() {
+            break;
+        }
+        let txn = db.begin_transaction();
+        txn.delete_cells(out_points.into_iter())?;
+        txn.commit()?;
+    }
+
+    let mut epochs: HashMap<EpochNumber, EpochExt> = HashMap::new();
+    let mut tip: Option<HeaderView> = None;
+    let mut recent_start = None;
+    let mut last_block: Option<BlockNumber> = None;
+    // the genesis digest is pushed when the database is initialized
+    let mut mmr_size = 1;
+    let mut finished = false;
+    while !finished {
+        let txn = db.begin_transaction();
+        let mut mmr = ChainRootMMR::new(mmr_size, &txn);
+        for _ in 0..IMPORT_BATCH_SIZE {
+            let record = match reader.read_record()? {
+                Some(record) => record,
+                None => {
+                    finished = true;
+                    break;
+                }
+            };
+            match record {
+                Record::Epoch(epoch) => {
+                    txn.insert_epoch_ext(&epoch.last_block_hash_in_previous_epoch(), &epoch)?;
+                    epochs.insert(epoch.number(), epoch);
+                }
+                Record::Header(header, ext, cellbase) => {
+                    let chained = match tip {
+                        None => header.hash() == consensus.genesis_hash(),
+                        Some(ref parent) => {
+                            header.number() == parent.number() + 1
+                                && header.parent_hash() == parent.hash()
+                        }
+                    };
+                    if !chained || last_block.is_some() {
+                        return Err(corrupted(format!(
+                            "unexpected header of block#{}",
+                            header.number()
+                        )));
+                    }
+                    if header.number() > 0 {
+                        let epoch = epochs.get(&header.epoch().number()).ok_or_else(|| {
+                            corrupted(format!("epoch of block#{} not found", header.number()))
+                        })?;
+                        insert_header(&txn, &header, &ext, &cellbase, epoch)?;
+                        mmr.push(header.digest())
+                            .map_err(|e| InternalErrorKind::MMR.other(e))?;
+                    }
+                    tip = Some(header);
+                }
+                Record::Block(block) => {
+                    let expected = last_block.map(|number| number + 1);
+                    if tip.is_none() || expected.map_or(false, |number| block.number() != number) {
+                        return Err(corrupted(format!("unexpected block#{}", block.number())));
+                    }
+                    if txn.get_block_hash(block.number()) != Some(block.hash()) {
+                        return Err(corrupted(format!(
+                            "block#{} doesn't match the header",
+                            block.number()
+                        )));
+                    }
+                    if block.number() > 0 {
+                        txn.insert_block(&block)?;
+                        txn.attach_block(&block)?;
+                    }
+                    recent_start.get_or_insert(block.number());
+                    last_block = Some(block.number());
+                }
+                Record::Cell(out_point, entry, data) => {
+                    txn.insert_cells(std::iter::once((out_point, entry, data)))?;
+                }
+            }
+        }
+        mmr_size = mmr.mmr_size();
+        mmr.commit().map_err(|e| InternalErrorKind::MMR.other(e))?;
+        txn.commit()?;
+    }
+
+    let tip = tip.ok_or_else(|| corrupted("no headers"))?;
+    if last_block != Some(tip.number()) {
+        return Err(corrupted("the recent blocks don't end at the tip"));
+    }
+    let checkpoint = SnapshotCheckpoint {
+        number: tip.number(),
+        block_hash: tip.hash(),
+        snapshot_hash: reader.finish(),
+    };
+    if checkpoint != *expected {
+        return Err(InternalErrorKind::DataCorrupted
+            .other(format!(
+                "the cell set snapshot (number: {}, block hash: {:#x}, snapshot hash: {:#x}) doesn't match the one pinned in the chain spec",
+                checkpoint.number, checkpoint.block_hash, checkpoint.snapshot_hash
+            ))
+            .into());
+    }
+
+    let epoch = epochs
+        .get(&tip.epoch().number())
+        .ok_or_else(|| corrupted("the current epoch not found"))?;
+    let txn = db.begin_transaction();
+    txn.insert_tip_header(&tip)?;
+    txn.insert_current_epoch_ext(epoch)?;
+    if let Some(end) = recent_start.filter(|end| *end > 1) {
+        txn.insert_backfill_range(1, end)?;
+    }
+    txn.commit()?;
+    Ok(checkpoint)
+}
+
+/// Inserts a block whose body will be back-filled later.
+fn insert_header(
+    txn: &StoreTransaction,
+    header: &HeaderView,
+    ext: &BlockExt,
+    cellbase: &TransactionView,
+    epoch: &EpochExt,
+) -> Result<(), Error> {
+    let hash = header.hash();
+    let number: packed::Uint64 = header.number().pack();
+    txn.insert_raw(
+        COLUMN_BLOCK_HEADER,
+        hash.as_slice(),
+        header.pack().as_slice(),
+    )?;
+    txn.insert_block_ext(&hash, ext)?;
+    txn.insert_block_epoch_index(&hash, &epoch.last_block_hash_in_previous_epoch())?;
+    txn.insert_raw(COLUMN_INDEX, number.as_slice(), hash.as_slice())?;
+    txn.insert_raw(COLUMN_INDEX, hash.as_slice(), number.as_slice())?;
+    let key = packed::TransactionKey::new_builder()
+        .block_hash(hash)
+        .build();
+    txn.insert_raw(
+        COLUMN_BLOCK_BODY,
+        key.as_slice(),
+        cellbase.pack().as_slice(),
+    )
+}
+
+fn out_point_from_cell_key(key: &[u8]) -> packed::OutPoint {
+    let mut index = [0u8; 4];
+    index.copy_from_slice(&key[32..36]);
+    packed::OutPoint::new_builder()
+        .tx_hash(packed::Byte32Reader::from_slice_should_be_ok(&key[..32]).to_entity())
+        .index(u32::from_be_bytes(index).pack())
+        .build()
+}
+
+fn io_error(path: &Path, err: std::io::Error) -> Error {
+    InternalErrorKind::System
+        .other(format!("cell set snapshot [{path:?}], cause: {err}"))
+        .into()
+}
+
+fn not_found(what: String) -> Error {
+    InternalErrorKind::Database
+        .other(format!("{what} is not found"))
+        .into()
+}
+
+fn corrupted<S: std::fmt::Display>(reason: S) -> Error {
+    InternalErrorKind::DataCorrupted
+        .other(format!("invalid cell set snapshot, {reason}"))
+        .into()
+}
+
+enum Record {
+    Epoch(EpochExt),
+    Header(HeaderView, BlockExt, TransactionView),
+    Block(BlockView),
+    Cell(
+        packed::OutPoint,
+        packed::CellEntry,
+        Option<packed::CellDataEntry>,
+    ),
+}
+
+impl Record {
+    fn decode(kind: u8, fields: &[Vec<u8>]) -> Option<Record> {
+        let record = match (kind, fields) {
+            (RECORD_EPOCH, [epoch]) => {
+                Record::Epoch(packed::EpochExtReader::from_slice(epoch).ok()?.unpack())
+            }
+            (RECORD_HEADER, [header, ext, cellbase]) => Record::Header(
+                packed::HeaderReader::from_slice(header)
+                    .ok()?
+                    .to_entity()
+                    .into_view(),
+                packed::BlockExtV1Reader::from_slice(ext).ok()?.unpack(),
+                packed::TransactionReader::from_slice(cellbase)
+                    .ok()?
+                    .to_entity()
+                    .into_view(),
+            ),
+            (RECORD_BLOCK, [block]) => Record::Block(
+                packed::BlockReader::from_compatible_slice(block)
+                    .ok()?
+                    .to_entity()
+                    .into_view(),
+            ),
+            (RECORD_CELL, [out_point, entry, data @ ..]) if data.len() <= 1 => {
+                let data = match data.first() {
+                    Some(data) => Some(
+                        packed::CellDataEntryReader::from_slice(data)
+                            .ok()?
+                            .to_entity(),
+                    ),
+                    None => None,
+                };
+                Record::Cell(
+                    packed::OutPointReader::from_slice(out_point)
+                        .ok()?
+                        .to_entity(),
+                    packed::CellEntryReader::from_slice(entry).ok()?.to_entity(),
+                    data,
+                )
+            }
+            _ => return None,
+        };
+        Some(record)
+    }
+}
+
+struct SnapshotWriter<W> {
+    inner: W,
+    hasher: Blake2b,
+}
+
+impl<W: Write> SnapshotWriter<W> {
+    fn write(&mut self, data: &[u8]) -> Result<(), Error> {
+        self.hasher.update(data);
+        self.inner.write_all(data).map_err(|err| {
+            InternalErrorKind::System
+                .other(format!(
+                    "failed to write the cell set snapshot, cause: {err}"
+                ))
+                .into()
+        })
+    }
+
+    fn write_record(&mut self, kind: u8, fields: &[&[u8]]) -> Result<(), Error> {
+        self.write(&[kind, fields.len() as u8])?;
+        for field in fields {
+            self.write(&(field.len() as u32).to_le_bytes())?;
+            self.write(field)?;
+        }
+        Ok(())
+    }
+
+    fn finish(mut self, path: &Path) -> Result<Byte32, Error> {
+        self.inner.flush().map_err(|err| io_error(path, err))?;
+        let mut hash = [0u8; 32];
+        self.hasher.finalize(&mut hash);
+        Ok(Byte32::new(hash))
+    }
+}
+
+struct SnapshotReader {
+    inner: BufReader<File>,
+    hasher: Blake2b,
+}
+
+impl SnapshotReader {
+    fn open(path: &Path) -> Result<SnapshotReader, Error> {
+        let file = File::open(path).map_err(|err| io_error(path, err))?;
+        let mut reader = SnapshotReader {
+            inner: BufReader::new(file),
+            hasher: new_blake2b(),
+        };
+        let mut magic = [0u8; 8];
+        let mut version = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+        reader.read_exact(&mut version)?;
+        if magic != MAGIC || u32::from_le_bytes(version) != VERSION {
+            return Err(corrupted("unknown format"));
+        }
+        Ok(reader)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), Error> {
+        self.inner
+            .read_exact(buf)
+            .map_err(|err| corrupted(format!("failed to read, cause: {err}")))?;
+        self.hasher.update(buf);
+        Ok(())
+    }
+
+    fn read_record(&mut self) -> Result<Option<Record>, Error> {
+        let mut kind = [0u8; 1];
+        match self.inner.read(&mut kind) {
+            Ok(0) => return Ok(None),
+            Ok(_) => self.hasher.update(&kind),
+            Err(err) => return Err(corrupted(format!("failed to read, cause: {err}"))),
+        }
+        let mut count = [0u8; 1];
+        self.read_exact(&mut count)?;
+        let mut fields = Vec::with_capacity(count[0] as usize);
+        for _ in 0..count[0] {
+            let mut len = [0u8; 4];
+            self.read_exact(&mut len)?;
+            let len = u32::from_le_bytes(len) as usize;
+            if len > MAX_FIELD_SIZE {
+                return Err(corrupted(format!("the field of {len} bytes is too large")));
+            }
+            let mut field = vec![0u8; len];
+            self.read_exact(&mut field)?;
+            fields.push(field);
+        }
+        Record::decode(kind[0], &fields)
+            .map(Some)
+            .ok_or_else(|| corrupted(format!("malformed record of kind {}", kind[0])))
+    }
+
+    fn finish(self) -> Byte32 {
+        let mut hash = [0u8; 32];
+        self.hasher.finalize(&mut hash);
+        Byte32::new(hash)
+    }
+}
@@ -5,6 +5,7 @@
 
 mod cache;
 mod cell;
+mod cell_set_snapshot;
 pub mod data_loader_wrapper;
 mod db;
 mod snapshot;
@@ -17,6 +18,9 @@ mod tests;
 
 pub use cache::StoreCache;
 pub use cell::{attach_block_cell, detach_block_cell};
+pub use cell_set_snapshot::{
+    export_cell_set_snapshot, import_cell_set_snapshot, SnapshotCheckpoint,
+};
 pub use db::ChainDB;
 pub use snapshot::StoreSnapshot;
 pub use store::ChainStore;
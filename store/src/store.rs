@@ -9,7 +9,8 @@ use ckb_db_schema::{
     COLUMN_BLOCK_FILTER, COLUMN_BLOCK_FILTER_HASH, COLUMN_BLOCK_HEADER, COLUMN_BLOCK_PROPOSAL_IDS,
     COLUMN_BLOCK_UNCLE, COLUMN_CELL, COLUMN_CELL_DATA, COLUMN_CELL_DATA_HASH,
     COLUMN_CHAIN_ROOT_MMR, COLUMN_EPOCH, COLUMN_INDEX, COLUMN_META, COLUMN_TRANSACTION_INFO,
-    COLUMN_UNCLES, META_CURRENT_EPOCH_KEY, META_LATEST_BUILT_FILTER_DATA_KEY, META_TIP_HEADER_KEY,
+    COLUMN_UNCLES, META_BACKFILL_RANGE_KEY, META_CURRENT_EPOCH_KEY,
+    META_LATEST_BUILT_FILTER_DATA_KEY, META_TIP_HEADER_KEY,
 };
 use ckb_freezer::Freezer;
 use ckb_types::{
@@ -49,13 +50,10 @@ pub trait ChainStore: Send + Sync + Sized {
                 return Some(raw_block.into_view());
             }
         }
+        // the body of a block imported from a cell set snapshot may not be back-filled yet
+        let uncles = self.get_block_uncles(h)?;
+        let proposals = self.get_block_proposal_txs_ids(h)?;
         let body = self.get_block_body(h);
-        let uncles = self
-            .get_block_uncles(h)
-            .expect("block uncles must be stored");
-        let proposals = self
-            .get_block_proposal_txs_ids(h)
-            .expect("block proposal_ids must be stored");
         let extension_opt = self.get_block_extension(h);
 
         let block = if let Some(extension) = extension_opt {
@@ -112,24 +110,20 @@ pub trait ChainStore: Send + Sync + Sized {
                 Unpack::<HeaderView>::unpack(&reader)
             })?;
 
-        let body = self.get_block_body(hash);
-
-        let uncles = self
-            .get(COLUMN_BLOCK_UNCLE, hash.as_slice())
-            .map(|slice| {
-                let reader =
-                    packed::UncleBlockVecViewReader::from_slice_should_be_ok(slice.as_ref());
-                Unpack::<UncleBlockVecView>::unpack(&reader)
-            })
-            .expect("block uncles must be stored");
+        // the body of a block imported from a cell set snapshot may not be back-filled yet
+        let uncles = self.get(COLUMN_BLOCK_UNCLE, hash.as_slice()).map(|slice| {
+            let reader = packed::UncleBlockVecViewReader::from_slice_should_be_ok(slice.as_ref());
+            Unpack::<UncleBlockVecView>::unpack(&reader)
+        })?;
 
         let proposals = self
             .get(COLUMN_BLOCK_PROPOSAL_IDS, hash.as_slice())
             .map(|slice| {
                 packed::ProposalShortIdVecReader::from_slice_should_be_ok(slice.as_ref())
                     .to_entity()
-            })
-            .expect("block proposal_ids must be stored");
+            })?;
+
+        let body = self.get_block_body(hash);
 
         let extension_opt = self
             .get(COLUMN_BLOCK_EXTENSION, hash.as_slice())
@@ -499,6 +493,16 @@ pub trait ChainStore: Send + Sync + Sized {
             .map(|raw| packed::Byte32Reader::from_slice_should_be_ok(raw.as_ref()).to_entity())
     }
 
+    /// Gets the range `[start, end)` of blocks whose bodies are not back-filled yet after importing
+    /// a cell set snapshot
+    fn get_backfill_range(&self) -> Option<(BlockNumber, BlockNumber)> {
+        self.get(COLUMN_META, META_BACKFILL_RANGE_KEY).map(|raw| {
+            let range: Vec<BlockNumber> =
+                packed::Uint64VecReader::from_slice_should_be_ok(raw.as_ref()).unpack();
+            (range[0], range[1])
+        })
+    }
+
     /// Gets block filter data by block hash
     fn get_block_filter(&self, hash: &packed::Byte32) -> Option<packed::Bytes> {
         self.get(COLUMN_BLOCK_FILTER, hash.as_slice())
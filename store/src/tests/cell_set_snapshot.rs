@@ -0,0 +1,224 @@
+use ckb_chain_spec::{
+    consensus::{Consensus, ConsensusBuilder},
+    AssumeUtxo,
+};
+use ckb_db::{iter::IteratorMode, RocksDB};
+use ckb_db_schema::{COLUMNS, COLUMN_CELL, COLUMN_CELL_DATA};
+use ckb_types::{
+    bytes::Bytes,
+    core::{
+        capacity_bytes, BlockBuilder, BlockExt, BlockView, Capacity, EpochExt,
+        EpochNumberWithFraction, HeaderView, TransactionBuilder,
+    },
+    packed::{self, CellInput, CellOutput},
+    prelude::*,
+    U256,
+};
+use tempfile::TempDir;
+
+use crate::{
+    attach_block_cell, export_cell_set_snapshot, import_cell_set_snapshot, ChainDB, ChainStore,
+};
+
+const EPOCH_LENGTH: u64 = 40;
+
+fn open_db(tmp_dir: &TempDir, consensus: &Consensus) -> ChainDB {
+    let db = RocksDB::open_in(tmp_dir, COLUMNS);
+    let store = ChainDB::new(db, Default::default());
+    store.init(consensus).unwrap();
+    store
+}
+
+fn build_block(parent: &HeaderView, spent: Option<packed::OutPoint>) -> BlockView {
+    let number = parent.number() + 1;
+    let epoch = EpochNumberWithFraction::new(number / EPOCH_LENGTH, number % EPOCH_LENGTH, 100);
+    let cellbase = TransactionBuilder::default()
+        .input(CellInput::new_cellbase_input(number))
+        .output(
+            CellOutput::new_builder()
+                .capacity(capacity_bytes!(100).pack())
+                .build(),
+        )
+        .output_data(Bytes::new().pack())
+        .build();
+    let mut builder = BlockBuilder::default()
+        .parent_hash(parent.hash())
+        .number(number.pack())
+        .epoch(epoch.pack())
+        .transaction(cellbase);
+    if let Some(out_point) = spent {
+        let tx = TransactionBuilder::default()
+            .input(CellInput::new(out_point, 0))
+            .output(
+                CellOutput::new_builder()
+                    .capacity(capacity_bytes!(50).pack())
+                    .build(),
+            )
+            .output_data(Bytes::from_static(b"data").pack())
+            .build();
+        builder = builder.transaction(tx);
+    }
+    builder.build()
+}
+
+/// Builds a chain of 2 epochs on the genesis, returns the blocks.
+fn build_chain(store: &ChainDB, consensus: &Consensus) -> Vec<BlockView> {
+    let genesis_epoch = consensus.genesis_epoch_ext();
+    let mut blocks = vec![consensus.genesis_block().clone()];
+    let mut epoch_index = genesis_epoch.last_block_hash_in_previous_epoch();
+    let mut epoch: EpochExt = genesis_epoch.clone();
+
+    let txn = store.begin_transaction();
+    for number in 1..2 * EPOCH_LENGTH {
+        let parent = blocks.last().unwrap().header();
+        let spent = if number == 5 {
+            Some(packed::OutPoint::new(blocks[1].transactions()[0].hash(), 0))
+        } else {
+            None
+        };
+        let block = build_block(&parent, spent);
+        if number == EPOCH_LENGTH {
+            epoch_index = parent.hash();
+            epoch = genesis_epoch
+                .clone()
+                .into_builder()
+                .number(1)
+                .start_number(EPOCH_LENGTH)
+                .length(EPOCH_LENGTH)
+                .last_block_hash_in_previous_epoch(epoch_index.clone())
+                .build();
+            txn.insert_epoch_ext(&epoch_index, &epoch).unwrap();
+        }
+        let ext = BlockExt {
+            received_at: block.timestamp(),
+            total_difficulty: U256::from(number),
+            total_uncles_count: 0,
+            verified: Some(true),
+            txs_fees: vec![],
+            cycles: None,
+            txs_sizes: None,
+        };
+        txn.insert_block(&block).unwrap();
+        txn.insert_block_ext(&block.hash(), &ext).unwrap();
+        txn.insert_block_epoch_index(&block.hash(), &epoch_index)
+            .unwrap();
+        txn.attach_block(&block).unwrap();
+        attach_block_cell(&txn, &block).unwrap();
+        blocks.push(block);
+    }
+    txn.insert_tip_header(&blocks.last().unwrap().header())
+        .unwrap();
+    txn.insert_current_epoch_ext(&epoch).unwrap();
+    txn.commit().unwrap();
+    blocks
+}
+
+fn cells(store: &ChainDB) -> Vec<(Box<[u8]>, Box<[u8]>, Option<Vec<u8>>)> {
+    store
+        .get_iter(COLUMN_CELL, IteratorMode::Start)
+        .map(|(key, value)| {
+            let data = store
+                .get(COLUMN_CELL_DATA, &key)
+                .map(|slice| slice.as_ref().to_vec());
+            (key, value, data)
+        })
+        .collect()
+}
+
+#[test]
+fn test_export_and_import() {
+    let consensus = ConsensusBuilder::default().build();
+    let source_dir = TempDir::new().unwrap();
+    let source = open_db(&source_dir, &consensus);
+    let blocks = build_chain(&source, &consensus);
+    let tip = blocks.last().unwrap().header();
+
+    let snapshot_dir = TempDir::new().unwrap();
+    let path = snapshot_dir.path().join("snapshot");
+    let checkpoint = export_cell_set_snapshot(&source, &consensus, &path).unwrap();
+    assert_eq!(checkpoint.number, tip.number());
+    assert_eq!(checkpoint.block_hash, tip.hash());
+
+    let consensus = ConsensusBuilder::default()
+        .assume_utxo(Some(AssumeUtxo {
+            number: checkpoint.number,
+            block_hash: checkpoint.block_hash.unpack(),
+            snapshot_hash: checkpoint.snapshot_hash.unpack(),
+        }))
+        .build();
+    let target_dir = TempDir::new().unwrap();
+    let target = open_db(&target_dir, &consensus);
+    assert_eq!(
+        import_cell_set_snapshot(&target, &consensus, &path).unwrap(),
+        checkpoint
+    );
+
+    assert_eq!(target.get_tip_header(), Some(tip));
+    assert_eq!(
+        target.get_current_epoch_ext(),
+        source.get_current_epoch_ext()
+    );
+    assert_eq!(cells(&target), cells(&source));
+
+    // the blocks of the current epoch are imported in full, the bodies of the older ones are
+    // back-filled later
+    assert_eq!(target.get_backfill_range(), Some((1, EPOCH_LENGTH)));
+    for block in &blocks {
+        let hash = block.hash();
+        assert_eq!(
+            target.get_block_header(&hash),
+            Some(block.header()),
+            "block#{}",
+            block.number()
+        );
+        assert_eq!(target.get_block_ext(&hash), source.get_block_ext(&hash));
+        assert_eq!(target.get_block_epoch(&hash), source.get_block_epoch(&hash));
+        assert_eq!(target.get_cellbase(&hash), source.get_cellbase(&hash));
+        if block.number() == 0 || block.number() >= EPOCH_LENGTH {
+            assert_eq!(target.get_block(&hash).as_ref(), Some(block));
+        } else {
+            assert_eq!(target.get_block(&hash), None);
+        }
+    }
+}
+
+#[test]
+fn test_import_unpinned_snapshot() {
+    let consensus = ConsensusBuilder::default().build();
+    let source_dir = TempDir::new().unwrap();
+    let source = open_db(&source_dir, &consensus);
+    build_chain(&source, &consensus);
+
+    let snapshot_dir = TempDir::new().unwrap();
+    let path = snapshot_dir.path().join("snapshot");
+    let checkpoint = export_cell_set_snapshot(&source, &consensus, &path).unwrap();
+
+    let target_dir = TempDir::new().unwrap();
+    let target = open_db(&target_dir, &consensus);
+    assert!(import_cell_set_snapshot(&target, &consensus, &path).is_err());
+
+    let consensus = ConsensusBuilder::default()
+        .assume_utxo(Some(AssumeUtxo {
+            number: checkpoint.number,
+            block_hash: checkpoint.block_hash.unpack(),
+            snapshot_hash: packed::Byte32::zero().unpack(),
+        }))
+        .build();
+    assert!(import_cell_set_snapshot(&target, &consensus, &path).is_err());
+    assert_eq!(target.get_tip_header().unwrap().number(), 0);
+
+    // the tip is not updated by the failed import, it can be retried
+    let consensus = ConsensusBuilder::default()
+        .assume_utxo(Some(AssumeUtxo {
+            number: checkpoint.number,
+            block_hash: checkpoint.block_hash.unpack(),
+            snapshot_hash: checkpoint.snapshot_hash.unpack(),
+        }))
+        .build();
+    assert_eq!(
+        import_cell_set_snapshot(&target, &consensus, &path).unwrap(),
+        checkpoint
+    );
+    assert_eq!(target.get_tip_header().unwrap().number(), checkpoint.number);
+    assert_eq!(cells(&target), cells(&source));
+}
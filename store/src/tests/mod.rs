@@ -1 +1,2 @@
+mod cell_set_snapshot;
 mod db;
@@ -10,7 +10,7 @@ use ckb_db_schema::{
     COLUMN_BLOCK_FILTER, COLUMN_BLOCK_FILTER_HASH, COLUMN_BLOCK_HEADER, COLUMN_BLOCK_PROPOSAL_IDS,
     COLUMN_BLOCK_UNCLE, COLUMN_CELL, COLUMN_CELL_DATA, COLUMN_CELL_DATA_HASH,
     COLUMN_CHAIN_ROOT_MMR, COLUMN_EPOCH, COLUMN_INDEX, COLUMN_META, COLUMN_NUMBER_HASH,
-    COLUMN_TRANSACTION_INFO, COLUMN_UNCLES, META_CURRENT_EPOCH_KEY,
+    COLUMN_TRANSACTION_INFO, COLUMN_UNCLES, META_BACKFILL_RANGE_KEY, META_CURRENT_EPOCH_KEY,
     META_LATEST_BUILT_FILTER_DATA_KEY, META_TIP_HEADER_KEY,
 };
 use ckb_error::Error;
@@ -19,7 +19,7 @@ use ckb_merkle_mountain_range::{Error as MMRError, MMRStore, Result as MMRResult
 use ckb_types::{
     core::{
         cell::{CellChecker, CellProvider, CellStatus},
-        BlockExt, BlockView, EpochExt, HeaderView, TransactionView,
+        BlockExt, BlockNumber, BlockView, EpochExt, HeaderView, TransactionView,
     },
     packed::{self, Byte32, OutPoint},
     prelude::*,
@@ -406,6 +406,17 @@ impl StoreTransaction {
             block_hash.as_slice(),
         )
     }
+
+    /// Inserts the range `[start, end)` of blocks whose bodies are not back-filled yet
+    pub fn insert_backfill_range(&self, start: BlockNumber, end: BlockNumber) -> Result<(), Error> {
+        let range: packed::Uint64Vec = vec![start, end].pack();
+        self.insert_raw(COLUMN_META, META_BACKFILL_RANGE_KEY, range.as_slice())
+    }
+
+    /// Deletes the back-fill range once all the block bodies are back-filled
+    pub fn delete_backfill_range(&self) -> Result<(), Error> {
+        self.delete(COLUMN_META, META_BACKFILL_RANGE_KEY)
+    }
 }
 
 impl MMRStore<packed::HeaderDigest> for &StoreTransaction {
@@ -1,7 +1,8 @@
 use ckb_db::RocksDBWriteBatch;
 use ckb_db_schema::{
-    Col, COLUMN_BLOCK_BODY, COLUMN_BLOCK_EXTENSION, COLUMN_BLOCK_HEADER, COLUMN_BLOCK_PROPOSAL_IDS,
-    COLUMN_BLOCK_UNCLE, COLUMN_CELL, COLUMN_CELL_DATA, COLUMN_CELL_DATA_HASH, COLUMN_NUMBER_HASH,
+    Col, COLUMN_BLOCK_BODY, COLUMN_BLOCK_EXTENSION, COLUMN_BLOCK_FILTER, COLUMN_BLOCK_HEADER,
+    COLUMN_BLOCK_PROPOSAL_IDS, COLUMN_BLOCK_UNCLE, COLUMN_CELL, COLUMN_CELL_DATA,
+    COLUMN_CELL_DATA_HASH, COLUMN_NUMBER_HASH,
 };
 use ckb_error::Error;
 use ckb_types::{core::BlockNumber, packed, prelude::*};
@@ -117,6 +118,11 @@ impl StoreWriteBatch {
         Ok(())
     }
 
+    /// Removes the filter data of the block with corresponding hash, the filter hash is kept
+    pub fn delete_block_filter(&mut self, hash: &packed::Byte32) -> Result<(), Error> {
+        self.inner.delete(COLUMN_BLOCK_FILTER, hash.as_slice())
+    }
+
     /// Removes the entire block from database with corresponding hash, number and txs number
     pub fn delete_block(
         &mut self,
@@ -8,10 +8,10 @@ use get_block_filter_hashes_process::GetBlockFilterHashesProcess;
 use get_block_filters_process::GetBlockFiltersProcess;
 
 use crate::utils::{metric_ckb_message_bytes, MetricDirection};
-use ckb_constant::sync::BAD_MESSAGE_BAN_TIME;
-use ckb_logger::{debug_target, error_target, info_target, warn_target};
+use ckb_logger::{debug_target, info_target, warn_target};
 use ckb_network::{
-    async_trait, bytes::Bytes, CKBProtocolContext, CKBProtocolHandler, PeerIndex, SupportProtocols,
+    async_trait, bytes::Bytes, Behaviour, CKBProtocolContext, CKBProtocolHandler, PeerIndex,
+    SupportProtocols,
 };
 use ckb_types::{packed, prelude::*};
 use std::sync::Arc;
@@ -79,16 +79,16 @@ impl BlockFilter {
             item_bytes,
         );
 
-        if let Some(ban_time) = status.should_ban() {
-            error_target!(
-                crate::LOG_TARGET_RELAY,
-                "receive {} from {}, ban {:?} for {}",
+        if let Some(behaviour) = status.should_report() {
+            debug_target!(
+                crate::LOG_TARGET_FILTER,
+                "receive {} from {}, report {:?} for {}",
                 item_name,
                 peer,
-                ban_time,
+                behaviour,
                 status
             );
-            nc.ban_peer(peer, ban_time, status.to_string());
+            nc.report_peer(peer, behaviour);
         } else if status.should_warn() {
             warn_target!(
                 crate::LOG_TARGET_RELAY,
@@ -127,11 +127,7 @@ impl CKBProtocolHandler for BlockFilter {
                     "Peer {} sends us a malformed message",
                     peer_index
                 );
-                nc.ban_peer(
-                    peer_index,
-                    BAD_MESSAGE_BAN_TIME,
-                    String::from("send us a malformed message"),
-                );
+                nc.report_peer(peer_index, Behaviour::MalformedMessage);
                 return;
             }
         };
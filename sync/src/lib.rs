@@ -10,6 +10,7 @@ pub(crate) mod orphan_block_pool;
 mod relayer;
 mod status;
 mod synchronizer;
+mod tx_reconciliation;
 mod types;
 mod utils;
 
@@ -21,6 +22,7 @@ pub use crate::net_time_checker::NetTimeProtocol;
 pub use crate::relayer::Relayer;
 pub use crate::status::{Status, StatusCode};
 pub use crate::synchronizer::Synchronizer;
+pub use crate::tx_reconciliation::TxReconciliation;
 pub use crate::types::{ActiveChain, SyncShared};
 use ckb_constant::sync::MAX_BLOCKS_IN_TRANSIT_PER_PEER;
 
@@ -37,3 +39,5 @@ pub(crate) const LOW_INDEX: usize = TIME_TRACE_SIZE * 9 / 10;
 pub(crate) const LOG_TARGET_RELAY: &str = "ckb_relay";
 
 pub(crate) const LOG_TARGET_FILTER: &str = "ckb_filter";
+
+pub(crate) const LOG_TARGET_TX_RECONCILIATION: &str = "ckb_tx_reconciliation";
@@ -5,7 +5,7 @@ use crate::utils::send_message_to;
 use crate::{attempt, Status, StatusCode};
 use ckb_network::{CKBProtocolContext, PeerIndex};
 use ckb_types::{core, packed, prelude::*};
-use std::collections::hash_map::Entry;
+use std::collections::{hash_map::Entry, HashSet};
 use std::mem;
 use std::sync::Arc;
 
@@ -67,7 +67,8 @@ impl<'a> BlockTransactionsProcess<'a> {
             .pending_compact_blocks()
             .entry(block_hash.clone())
         {
-            let (compact_block, peers_map, _) = pending.get_mut();
+            let (compact_block, short_id_key, peers_map, _) = pending.get_mut();
+            let short_id_key = *short_id_key;
             if let Entry::Occupied(mut value) = peers_map.entry(self.peer) {
                 let (expected_transaction_indexes, expected_uncle_indexes) = value.get_mut();
                 ckb_logger::info!(
@@ -78,6 +79,7 @@ impl<'a> BlockTransactionsProcess<'a> {
 
                 attempt!(BlockTransactionsVerifier::verify(
                     compact_block,
+                    short_id_key.as_ref(),
                     expected_transaction_indexes,
                     &received_transactions,
                 ));
@@ -87,9 +89,10 @@ impl<'a> BlockTransactionsProcess<'a> {
                     &received_uncles,
                 ));
 
-                let ret = self.relayer.reconstruct_block(
+                let ret = self.relayer.reconstruct_salted_block(
                     &active_chain,
                     compact_block,
+                    short_id_key,
                     received_transactions,
                     expected_uncle_indexes,
                     &received_uncles,
@@ -115,9 +118,19 @@ impl<'a> BlockTransactionsProcess<'a> {
 
                 match ret {
                     ReconstructionResult::Block(block) => {
+                        // the fetched transactions are likely missing in other peers too
+                        let prefilled_transactions_indexes: HashSet<usize> =
+                            expected_transaction_indexes
+                                .iter()
+                                .map(|index| *index as usize)
+                                .collect();
                         pending.remove();
-                        self.relayer
-                            .accept_block(self.nc.as_ref(), self.peer, block);
+                        self.relayer.accept_block(
+                            self.nc.as_ref(),
+                            self.peer,
+                            block,
+                            &prefilled_transactions_indexes,
+                        );
                         return Status::ok();
                     }
                     ReconstructionResult::Missing(transactions, uncles) => {
@@ -1,11 +1,12 @@
 use crate::{Status, StatusCode};
-use ckb_types::{core, packed, prelude::*};
+use ckb_types::{core, packed, prelude::*, utilities::ShortIdKey};
 
 pub struct BlockTransactionsVerifier {}
 
 impl BlockTransactionsVerifier {
     pub(crate) fn verify(
         block: &packed::CompactBlock,
+        short_id_key: Option<&ShortIdKey>,
         indexes: &[u32],
         transactions: &[core::TransactionView],
     ) -> Status {
@@ -30,7 +31,10 @@ impl BlockTransactionsVerifier {
         }
 
         for (expected_short_id, tx) in missing_short_ids.into_iter().zip(transactions) {
-            let short_id = tx.proposal_short_id();
+            let short_id = match short_id_key {
+                Some(key) => key.short_id(&tx.hash()).to_proposal_short_id(),
+                None => tx.proposal_short_id(),
+            };
             if expected_short_id != short_id {
                 return StatusCode::BlockTransactionsShortIdsAreUnmatchedWithPendingCompactBlock
                     .with_context(format!(
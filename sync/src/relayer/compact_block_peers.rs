@@ -0,0 +1,115 @@
+//! Per-peer state of compact block relay v4.
+//!
+//! Peers speaking relay protocol v4 announce a random salt and their high-bandwidth preference
+//! by `SendCompactBlock`. Compact blocks exchanged with such a peer are sent as
+//! `SaltedCompactBlock`, whose 6 bytes short ids are salted by both salts, so a short id collision
+//! crafted by one peer doesn't hit the other connections.
+//!
+//! Like BIP152, we ask a few peers which delivered new blocks to us first to push compact blocks
+//! in high-bandwidth mode, that is, ahead of the other peers as soon as the block is accepted.
+use ckb_network::PeerIndex;
+use ckb_types::{packed::Byte32, utilities::ShortIdKey};
+use std::collections::{HashMap, VecDeque};
+
+/// Max count of peers we ask to push compact blocks in high-bandwidth mode
+pub(crate) const MAX_HIGH_BANDWIDTH_PEERS: usize = 3;
+
+#[derive(Debug)]
+struct PeerState {
+    local_salt: u64,
+    remote_salt: Option<u64>,
+    // the peer asked us to push compact blocks in high-bandwidth mode
+    high_bandwidth: bool,
+}
+
+#[derive(Default, Debug)]
+pub(crate) struct CompactBlockPeers {
+    peers: HashMap<PeerIndex, PeerState>,
+    // peers we asked to push compact blocks in high-bandwidth mode, the earliest selected first
+    high_bandwidth_peers: VecDeque<PeerIndex>,
+}
+
+impl CompactBlockPeers {
+    /// Register a peer connected by relay protocol v4 with the local salt announced to it
+    pub fn connected(&mut self, peer: PeerIndex, local_salt: u64) {
+        self.peers.insert(
+            peer,
+            PeerState {
+                local_salt,
+                remote_salt: None,
+                high_bandwidth: false,
+            },
+        );
+    }
+
+    /// Remove a disconnected peer, returns whether it was selected as a high-bandwidth peer
+    pub fn disconnected(&mut self, peer: PeerIndex) -> bool {
+        self.peers.remove(&peer);
+        let len = self.high_bandwidth_peers.len();
+        self.high_bandwidth_peers.retain(|p| *p != peer);
+        self.high_bandwidth_peers.len() != len
+    }
+
+    /// The local salt announced to a v4 peer
+    pub fn local_salt(&self, peer: PeerIndex) -> Option<u64> {
+        self.peers.get(&peer).map(|state| state.local_salt)
+    }
+
+    /// Handle `SendCompactBlock` from peer, returns false if the peer doesn't speak v4
+    ///
+    /// The salt is fixed by the first announcement, the later ones only toggle high-bandwidth mode.
+    pub fn received(&mut self, peer: PeerIndex, salt: u64, high_bandwidth: bool) -> bool {
+        match self.peers.get_mut(&peer) {
+            Some(state) => {
+                state.remote_salt.get_or_insert(salt);
+                state.high_bandwidth = high_bandwidth;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The salt shared by both sides of the connection, it's only available after the peer
+    /// announced its salt, before that the unsalted `CompactBlock` is used in both directions.
+    pub fn salt(&self, peer: PeerIndex) -> Option<u64> {
+        self.peers
+            .get(&peer)
+            .and_then(|state| state.remote_salt.map(|salt| salt ^ state.local_salt))
+    }
+
+    /// The key of the short ids in compact blocks exchanged with the peer
+    pub fn short_id_key(&self, peer: PeerIndex, block_hash: &Byte32) -> Option<ShortIdKey> {
+        self.salt(peer)
+            .map(|salt| ShortIdKey::new(block_hash, salt))
+    }
+
+    /// Peers which asked us to push compact blocks in high-bandwidth mode
+    pub fn high_bandwidth_to(&self) -> Vec<PeerIndex> {
+        self.peers
+            .iter()
+            .filter(|(_, state)| state.high_bandwidth && state.remote_salt.is_some())
+            .map(|(peer, _)| *peer)
+            .collect()
+    }
+
+    /// Peers we asked to push compact blocks in high-bandwidth mode
+    pub fn high_bandwidth_from(&self) -> impl Iterator<Item = &PeerIndex> {
+        self.high_bandwidth_peers.iter()
+    }
+
+    /// Select the peer which delivered a new block first as a high-bandwidth peer.
+    ///
+    /// Returns None if the peer is already selected or doesn't speak v4, otherwise returns the
+    /// peer demoted to make room.
+    pub fn select_high_bandwidth(&mut self, peer: PeerIndex) -> Option<Option<PeerIndex>> {
+        if self.salt(peer).is_none() || self.high_bandwidth_peers.contains(&peer) {
+            return None;
+        }
+        self.high_bandwidth_peers.push_back(peer);
+        if self.high_bandwidth_peers.len() > MAX_HIGH_BANDWIDTH_PEERS {
+            Some(self.high_bandwidth_peers.pop_front())
+        } else {
+            Some(None)
+        }
+    }
+}
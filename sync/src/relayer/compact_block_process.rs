@@ -14,12 +14,13 @@ use ckb_types::{
     core::{EpochNumberWithFraction, HeaderView},
     packed::{self, Byte32, CompactBlock},
     prelude::*,
+    utilities::ShortIdKey,
 };
 use ckb_util::shrink_to_fit;
 use ckb_util::MutexGuard;
 use ckb_verification::{HeaderError, HeaderVerifier};
 use ckb_verification_traits::Verifier;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::time::Instant;
 
@@ -32,7 +33,9 @@ use std::time::Instant;
 // 2. otherwise, there may be short_id collision in transaction pool,
 // the node retreat to request all the short_ids from the peer.
 pub struct CompactBlockProcess<'a> {
-    message: packed::CompactBlockReader<'a>,
+    compact_block: CompactBlock,
+    // whether the short ids are salted, they are widened to `ProposalShortId` in `compact_block`
+    salted: bool,
     relayer: &'a Relayer,
     nc: Arc<dyn CKBProtocolContext>,
     peer: PeerIndex,
@@ -46,7 +49,23 @@ impl<'a> CompactBlockProcess<'a> {
         peer: PeerIndex,
     ) -> Self {
         CompactBlockProcess {
-            message,
+            compact_block: message.to_entity(),
+            salted: false,
+            nc,
+            relayer,
+            peer,
+        }
+    }
+
+    pub fn new_salted(
+        message: packed::SaltedCompactBlockReader<'a>,
+        relayer: &'a Relayer,
+        nc: Arc<dyn CKBProtocolContext>,
+        peer: PeerIndex,
+    ) -> Self {
+        CompactBlockProcess {
+            compact_block: message.to_entity().to_compact_block(),
+            salted: true,
             nc,
             relayer,
             peer,
@@ -57,10 +76,22 @@ impl<'a> CompactBlockProcess<'a> {
         let instant = Instant::now();
         let shared = self.relayer.shared();
         let active_chain = shared.active_chain();
-        let compact_block = self.message.to_entity();
+        let compact_block = self.compact_block;
         let header = compact_block.header().into_view();
         let block_hash = header.hash();
 
+        let short_id_key = if self.salted {
+            match self.relayer.short_id_key(self.peer, &block_hash) {
+                Some(key) => Some(key),
+                None => {
+                    return StatusCode::ProtocolMessageIsMalformed
+                        .with_context("SaltedCompactBlock before the salts are exchanged")
+                }
+            }
+        } else {
+            None
+        };
+
         let status =
             non_contextual_check(&compact_block, &header, shared.consensus(), &active_chain);
         if !status.is_ok() {
@@ -89,9 +120,27 @@ impl<'a> CompactBlockProcess<'a> {
         let mut pending_compact_blocks = shared.state().pending_compact_blocks();
 
         // Reconstruct block
-        let ret = self
-            .relayer
-            .reconstruct_block(&active_chain, &compact_block, vec![], &[], &[]);
+        let ret = self.relayer.reconstruct_salted_block(
+            &active_chain,
+            &compact_block,
+            short_id_key,
+            vec![],
+            &[],
+            &[],
+        );
+        if let Some(metrics) = ckb_metrics::handle() {
+            let reconstruct = if short_id_key.is_some() {
+                &metrics.ckb_relay_cb_reconstruct.salted
+            } else {
+                &metrics.ckb_relay_cb_reconstruct.legacy
+            };
+            match ret {
+                ReconstructionResult::Block(_) => reconstruct.ok.inc(),
+                ReconstructionResult::Missing(..) => reconstruct.missing.inc(),
+                ReconstructionResult::Collided => reconstruct.collided.inc(),
+                ReconstructionResult::Error(_) => (),
+            }
+        }
 
         // Accept block
         // `relayer.accept_block` will make sure the validity of block before persisting
@@ -110,14 +159,14 @@ impl<'a> CompactBlockProcess<'a> {
                 //
                 // use epoch as the judgment condition because we accept
                 // all block in current epoch as uncle block
-                pending_compact_blocks.retain(|_, (v, _, _)| {
+                pending_compact_blocks.retain(|_, (v, _, _, _)| {
                     Unpack::<EpochNumberWithFraction>::unpack(&v.header().as_reader().raw().epoch())
                         .number()
                         >= block.epoch().number()
                 });
                 shrink_to_fit!(pending_compact_blocks, 20);
                 self.relayer
-                    .accept_block(self.nc.as_ref(), self.peer, block);
+                    .accept_block(self.nc.as_ref(), self.peer, block, &HashSet::new());
 
                 if let Some(metrics) = ckb_metrics::handle() {
                     metrics
@@ -140,6 +189,7 @@ impl<'a> CompactBlockProcess<'a> {
                 let missing_uncles: Vec<u32> = uncles.into_iter().map(|i| i as u32).collect();
                 missing_or_collided_post_process(
                     compact_block,
+                    short_id_key,
                     block_hash.clone(),
                     pending_compact_blocks,
                     self.nc,
@@ -159,6 +209,7 @@ impl<'a> CompactBlockProcess<'a> {
                 let missing_uncles: Vec<u32> = vec![];
                 missing_or_collided_post_process(
                     compact_block,
+                    short_id_key,
                     block_hash.clone(),
                     pending_compact_blocks,
                     self.nc,
@@ -283,7 +334,7 @@ fn contextual_check(
     let pending_compact_blocks = shared.state().pending_compact_blocks();
     if pending_compact_blocks
         .get(&block_hash)
-        .map(|(_, peers_map, _)| peers_map.contains_key(&peer))
+        .map(|(_, _, peers_map, _)| peers_map.contains_key(&peer))
         .unwrap_or(false)
     {
         return StatusCode::CompactBlockIsAlreadyPending.with_context(block_hash);
@@ -294,7 +345,7 @@ fn contextual_check(
         |block_hash| {
             pending_compact_blocks
                 .get(&block_hash)
-                .map(|(compact_block, _, _)| {
+                .map(|(compact_block, _, _, _)| {
                     let header = compact_block.header().into_view();
                     HeaderFields {
                         hash: header.hash(),
@@ -341,8 +392,10 @@ fn contextual_check(
 }
 
 /// request missing txs and uncles from peer
+#[allow(clippy::too_many_arguments)]
 fn missing_or_collided_post_process(
     compact_block: CompactBlock,
+    short_id_key: Option<ShortIdKey>,
     block_hash: Byte32,
     mut pending_compact_blocks: MutexGuard<PendingCompactBlockMap>,
     nc: Arc<dyn CKBProtocolContext>,
@@ -352,8 +405,15 @@ fn missing_or_collided_post_process(
 ) {
     pending_compact_blocks
         .entry(block_hash.clone())
-        .or_insert_with(|| (compact_block, HashMap::default(), unix_time_as_millis()))
-        .1
+        .or_insert_with(|| {
+            (
+                compact_block,
+                short_id_key,
+                HashMap::default(),
+                unix_time_as_millis(),
+            )
+        })
+        .2
         .insert(peer, (missing_transactions.clone(), missing_uncles.clone()));
 
     let content = packed::GetBlockTransactions::new_builder()
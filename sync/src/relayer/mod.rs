@@ -2,11 +2,13 @@ mod block_proposal_process;
 mod block_transactions_process;
 mod block_transactions_verifier;
 mod block_uncles_verifier;
+mod compact_block_peers;
 mod compact_block_process;
 mod compact_block_verifier;
 mod get_block_proposal_process;
 mod get_block_transactions_process;
 mod get_transactions_process;
+mod send_compact_block_process;
 #[cfg(test)]
 mod tests;
 mod transaction_hashes_process;
@@ -14,10 +16,12 @@ mod transactions_process;
 
 use self::block_proposal_process::BlockProposalProcess;
 use self::block_transactions_process::BlockTransactionsProcess;
+use self::compact_block_peers::CompactBlockPeers;
 use self::compact_block_process::CompactBlockProcess;
 use self::get_block_proposal_process::GetBlockProposalProcess;
 use self::get_block_transactions_process::GetBlockTransactionsProcess;
 use self::get_transactions_process::GetTransactionsProcess;
+use self::send_compact_block_process::SendCompactBlockProcess;
 use self::transaction_hashes_process::TransactionHashesProcess;
 use self::transactions_process::TransactionsProcess;
 use crate::block_status::BlockStatus;
@@ -25,11 +29,10 @@ use crate::types::{ActiveChain, BlockNumberAndHash, SyncShared};
 use crate::utils::{metric_ckb_message_bytes, send_message_to, MetricDirection};
 use crate::{Status, StatusCode};
 use ckb_chain::chain::ChainController;
-use ckb_constant::sync::BAD_MESSAGE_BAN_TIME;
 use ckb_logger::{debug_target, error_target, info_target, trace_target, warn_target};
 use ckb_network::{
-    async_trait, bytes::Bytes, tokio, CKBProtocolContext, CKBProtocolHandler, PeerIndex,
-    SupportProtocols, TargetSession,
+    async_trait, bytes::Bytes, tokio, Behaviour, CKBProtocolContext, CKBProtocolHandler, PeerIndex,
+    SupportProtocols, TargetSession, RELAY_V4_VERSION,
 };
 use ckb_systemtime::unix_time_as_millis;
 use ckb_tx_pool::service::TxVerificationResult;
@@ -37,6 +40,7 @@ use ckb_types::{
     core::{self, BlockView},
     packed::{self, Byte32, ProposalShortId},
     prelude::*,
+    utilities::ShortIdKey,
 };
 use ckb_util::Mutex;
 use itertools::Itertools;
@@ -73,6 +77,7 @@ pub struct Relayer {
     chain: ChainController,
     pub(crate) shared: Arc<SyncShared>,
     rate_limiter: Arc<Mutex<RateLimiter<(PeerIndex, u32)>>>,
+    pub(crate) compact_block_peers: Arc<Mutex<CompactBlockPeers>>,
     v3: bool,
 }
 
@@ -89,6 +94,7 @@ impl Relayer {
             chain,
             shared,
             rate_limiter,
+            compact_block_peers: Arc::new(Mutex::new(CompactBlockPeers::default())),
             v3: false,
         }
     }
@@ -96,6 +102,8 @@ impl Relayer {
     /// Set relay to v3
     pub fn v3(mut self) -> Self {
         self.v3 = true;
+        // only relay v3 negotiates compact block salts, don't share them with the v2 handle
+        self.compact_block_peers = Arc::new(Mutex::new(CompactBlockPeers::default()));
         self
     }
 
@@ -111,8 +119,11 @@ impl Relayer {
         message: packed::RelayMessageUnionReader<'_>,
     ) -> Status {
         // CompactBlock will be verified by POW, it's OK to skip rate limit checking.
-        let should_check_rate =
-            !matches!(message, packed::RelayMessageUnionReader::CompactBlock(_));
+        let should_check_rate = !matches!(
+            message,
+            packed::RelayMessageUnionReader::CompactBlock(_)
+                | packed::RelayMessageUnionReader::SaltedCompactBlock(_)
+        );
 
         if should_check_rate
             && self
@@ -183,6 +194,12 @@ impl Relayer {
             packed::RelayMessageUnionReader::BlockProposal(reader) => {
                 BlockProposalProcess::new(reader, self).execute()
             }
+            packed::RelayMessageUnionReader::SendCompactBlock(reader) => {
+                SendCompactBlockProcess::new(reader, self, peer).execute()
+            }
+            packed::RelayMessageUnionReader::SaltedCompactBlock(reader) => {
+                CompactBlockProcess::new_salted(reader, self, nc, peer).execute()
+            }
         }
     }
 
@@ -204,16 +221,16 @@ impl Relayer {
             item_bytes,
         );
 
-        if let Some(ban_time) = status.should_ban() {
-            error_target!(
+        if let Some(behaviour) = status.should_report() {
+            debug_target!(
                 crate::LOG_TARGET_RELAY,
-                "receive {} from {}, ban {:?} for {}",
+                "receive {} from {}, report {:?} for {}",
                 item_name,
                 peer,
-                ban_time,
+                behaviour,
                 status
             );
-            nc.ban_peer(peer, ban_time, status.to_string());
+            nc.report_peer(peer, behaviour);
         } else if status.should_warn() {
             warn_target!(
                 crate::LOG_TARGET_RELAY,
@@ -277,13 +294,92 @@ impl Relayer {
         }
     }
 
+    /// The key of the salted short ids in `SaltedCompactBlock`s exchanged with a relay v4 peer,
+    /// returns None if the peer hasn't exchanged the salts yet
+    pub fn short_id_key(&self, peer: PeerIndex, block_hash: &Byte32) -> Option<ShortIdKey> {
+        self.compact_block_peers
+            .lock()
+            .short_id_key(peer, block_hash)
+    }
+
+    fn send_compact_block_announcement(
+        &self,
+        nc: &dyn CKBProtocolContext,
+        peer: PeerIndex,
+        salt: u64,
+        high_bandwidth: bool,
+    ) {
+        let content = packed::SendCompactBlock::new_builder()
+            .salt(salt.pack())
+            .high_bandwidth(high_bandwidth.pack())
+            .build();
+        let message = packed::RelayMessage::new_builder().set(content).build();
+        let status = send_message_to(nc, peer, &message);
+        if !status.is_ok() {
+            debug_target!(
+                crate::LOG_TARGET_RELAY,
+                "relayer send SendCompactBlock to {} error: {}",
+                peer,
+                status,
+            );
+        }
+    }
+
+    // Ask the peer which delivered a new block first to push compact blocks in high-bandwidth mode
+    fn select_high_bandwidth_peer(&self, nc: &dyn CKBProtocolContext, peer: PeerIndex) {
+        let mut compact_block_peers = self.compact_block_peers.lock();
+        if let Some(demoted) = compact_block_peers.select_high_bandwidth(peer) {
+            let announcements = std::iter::once((peer, true)).chain(demoted.map(|p| (p, false)));
+            for (target, high_bandwidth) in announcements {
+                if let Some(salt) = compact_block_peers.local_salt(target) {
+                    self.send_compact_block_announcement(nc, target, salt, high_bandwidth);
+                }
+            }
+            if let Some(metrics) = ckb_metrics::handle() {
+                metrics
+                    .ckb_relay_cb_high_bandwidth_peers
+                    .set(compact_block_peers.high_bandwidth_from().count() as i64);
+            }
+        }
+    }
+
+    // Send the `SaltedCompactBlock` with short ids salted for the v4 peer
+    fn send_salted_compact_block(
+        &self,
+        nc: &dyn CKBProtocolContext,
+        peer: PeerIndex,
+        salt: u64,
+        block: &core::BlockView,
+        prefilled_transactions_indexes: &HashSet<usize>,
+    ) {
+        let cb = packed::SaltedCompactBlock::build_salted_from_block(
+            block,
+            prefilled_transactions_indexes,
+            salt,
+        );
+        let message = packed::RelayMessage::new_builder().set(cb).build();
+        if let Err(err) = nc.quick_send_message_to(peer, message.as_bytes()) {
+            debug_target!(
+                crate::LOG_TARGET_RELAY,
+                "relayer send salted compact block to {} error: {:?}",
+                peer,
+                err,
+            );
+        }
+    }
+
     /// Accept a new block from network
+    ///
+    /// The transactions at `prefilled_transactions_indexes` were missing locally when the block
+    /// arrived, they are prefilled in the compact blocks relayed to v4 peers, since the peers
+    /// are likely missing them too.
     #[allow(clippy::needless_collect)]
     pub fn accept_block(
         &self,
         nc: &dyn CKBProtocolContext,
         peer: PeerIndex,
         block: core::BlockView,
+        prefilled_transactions_indexes: &HashSet<usize>,
     ) {
         if self
             .shared()
@@ -305,17 +401,77 @@ impl Relayer {
                 boxed.header().hash(),
                 unix_time_as_millis()
             );
+
+            // Peers in high-bandwidth mode get the compact block ahead of the others, but only
+            // once the block is verified, so a peer can't make us push invalid blocks
+            let high_bandwidth_peers: HashMap<PeerIndex, u64> = {
+                let compact_block_peers = self.compact_block_peers.lock();
+                compact_block_peers
+                    .high_bandwidth_to()
+                    .into_iter()
+                    .filter(|target_peer| *target_peer != peer)
+                    .filter_map(|target_peer| {
+                        compact_block_peers
+                            .salt(target_peer)
+                            .map(|salt| (target_peer, salt))
+                    })
+                    .collect()
+            };
+            for (target_peer, salt) in &high_bandwidth_peers {
+                self.send_salted_compact_block(
+                    nc,
+                    *target_peer,
+                    *salt,
+                    &boxed,
+                    prefilled_transactions_indexes,
+                );
+            }
+
             let block_hash = boxed.hash();
             self.shared().state().remove_header_view(&block_hash);
+            // peers relaying new blocks to us are protected from inbound eviction
+            nc.with_peer_mut(
+                peer,
+                Box::new(|peer| peer.last_block_relayed_at = Some(Instant::now())),
+            );
+            self.select_high_bandwidth_peer(nc, peer);
+
             let cb = packed::CompactBlock::build_from_block(&boxed, &HashSet::new());
             let message = packed::RelayMessage::new_builder().set(cb).build();
 
             let selected_peers: Vec<PeerIndex> = nc
                 .connected_peers()
                 .into_iter()
-                .filter(|target_peer| peer != *target_peer)
+                .filter(|target_peer| {
+                    peer != *target_peer && !high_bandwidth_peers.contains_key(target_peer)
+                })
                 .take(MAX_RELAY_PEERS)
                 .collect();
+            // v4 peers get the compact blocks salted for their connections
+            let salted_peers: Vec<(PeerIndex, u64)> = {
+                let compact_block_peers = self.compact_block_peers.lock();
+                selected_peers
+                    .iter()
+                    .filter_map(|target_peer| {
+                        compact_block_peers
+                            .salt(*target_peer)
+                            .map(|salt| (*target_peer, salt))
+                    })
+                    .collect()
+            };
+            for (target_peer, salt) in &salted_peers {
+                self.send_salted_compact_block(
+                    nc,
+                    *target_peer,
+                    *salt,
+                    &boxed,
+                    prefilled_transactions_indexes,
+                );
+            }
+            let selected_peers: Vec<PeerIndex> = selected_peers
+                .into_iter()
+                .filter(|target_peer| !salted_peers.iter().any(|(p, _)| p == target_peer))
+                .collect();
             if let Err(err) = nc.quick_filter_broadcast(
                 TargetSession::Multi(Box::new(selected_peers.into_iter())),
                 message.as_bytes(),
@@ -396,6 +552,27 @@ impl Relayer {
         received_transactions: Vec<core::TransactionView>,
         uncles_index: &[u32],
         received_uncles: &[core::UncleBlockView],
+    ) -> ReconstructionResult {
+        self.reconstruct_salted_block(
+            active_chain,
+            compact_block,
+            None,
+            received_transactions,
+            uncles_index,
+            received_uncles,
+        )
+    }
+
+    /// Reorganize the full block according to the compact block whose short ids are salted by
+    /// `short_id_key`, the short ids are the unsalted `ProposalShortId` if it's None.
+    pub fn reconstruct_salted_block(
+        &self,
+        active_chain: &ActiveChain,
+        compact_block: &packed::CompactBlock,
+        short_id_key: Option<ShortIdKey>,
+        received_transactions: Vec<core::TransactionView>,
+        uncles_index: &[u32],
+        received_uncles: &[core::UncleBlockView],
     ) -> ReconstructionResult {
         let block_txs_len = received_transactions.len();
         let compact_block_hash = compact_block.calc_header_hash();
@@ -412,7 +589,10 @@ impl Relayer {
         let mut txs_map: HashMap<ProposalShortId, core::TransactionView> = received_transactions
             .into_iter()
             .filter_map(|tx| {
-                let short_id = tx.proposal_short_id();
+                let short_id = match short_id_key {
+                    Some(ref key) => key.short_id(&tx.hash()).to_proposal_short_id(),
+                    None => tx.proposal_short_id(),
+                };
                 if short_ids_set.remove(&short_id) {
                     Some((short_id, tx))
                 } else {
@@ -424,7 +604,10 @@ impl Relayer {
         if !short_ids_set.is_empty() {
             let tx_pool = self.shared.shared().tx_pool_controller();
 
-            let fetch_txs = tx_pool.fetch_txs(short_ids_set);
+            let fetch_txs = match short_id_key {
+                Some(key) => tx_pool.fetch_txs_by_salted_short_ids(key, short_ids_set),
+                None => tx_pool.fetch_txs(short_ids_set),
+            };
             if let Err(e) = fetch_txs {
                 return ReconstructionResult::Error(StatusCode::TxPool.with_context(e));
             }
@@ -692,6 +875,9 @@ impl Relayer {
             .take_relay_tx_verify_results(MAX_RELAY_TXS_NUM_PER_BATCH);
         let mut selected: HashMap<PeerIndex, Vec<Byte32>> = HashMap::default();
         {
+            // peers opened the transaction reconciliation protocol get the hashes by
+            // reconciliation rather than flooding
+            let mut tx_reconciliation = self.shared.state().tx_reconciliation();
             for tx_verify_result in tx_verify_results {
                 match tx_verify_result {
                     TxVerificationResult::Ok {
@@ -707,7 +893,9 @@ impl Relayer {
                             match original_peer {
                                 Some(peer) => {
                                     // broadcast tx hash to all connected peers except original peer
-                                    if peer != *target {
+                                    if peer != *target
+                                        && !tx_reconciliation.add_tx(*target, &tx_hash)
+                                    {
                                         let hashes = selected
                                             .entry(*target)
                                             .or_insert_with(|| Vec::with_capacity(BUFFER_SIZE));
@@ -716,15 +904,42 @@ impl Relayer {
                                 }
                                 None => {
                                     // since this tx is submitted through local rpc, it is assumed to be a new tx for all connected peers
-                                    let hashes = selected
-                                        .entry(*target)
-                                        .or_insert_with(|| Vec::with_capacity(BUFFER_SIZE));
-                                    hashes.push(tx_hash.clone());
+                                    if !tx_reconciliation.add_tx(*target, &tx_hash) {
+                                        let hashes = selected
+                                            .entry(*target)
+                                            .or_insert_with(|| Vec::with_capacity(BUFFER_SIZE));
+                                        hashes.push(tx_hash.clone());
+                                    }
                                     self.shared.state().mark_as_known_tx(tx_hash.clone());
                                 }
                             }
                         }
                     }
+                    TxVerificationResult::Package {
+                        original_peer,
+                        with_vm_2023,
+                        tx_hashes,
+                    } => {
+                        // must all fork or all no-fork
+                        if ckb2023 != with_vm_2023 {
+                            continue;
+                        }
+                        // the package is always flooded in one announcement, so the peers
+                        // request the low fee parents together with the children paying for them
+                        for target in &connected_peers {
+                            if original_peer != Some(*target) {
+                                selected
+                                    .entry(*target)
+                                    .or_insert_with(|| Vec::with_capacity(BUFFER_SIZE))
+                                    .extend(tx_hashes.iter().cloned());
+                            }
+                        }
+                        if original_peer.is_none() {
+                            self.shared
+                                .state()
+                                .mark_as_known_txs(tx_hashes.iter().cloned());
+                        }
+                    }
                     TxVerificationResult::Reject { tx_hash } => {
                         self.shared.state().remove_from_known_txs(&tx_hash);
                     }
@@ -743,6 +958,11 @@ impl Relayer {
                     "relayer send TransactionHashes error: {:?}",
                     err,
                 );
+            } else if let Some(metrics) = ckb_metrics::handle() {
+                metrics
+                    .ckb_relay_tx_announcement_bytes
+                    .flooding
+                    .inc_by(message.as_slice().len() as u64);
             }
         }
     }
@@ -788,14 +1008,7 @@ impl CKBProtocolHandler for Relayer {
                              too many fields in CompactBlock",
                             peer_index
                         );
-                        nc.ban_peer(
-                            peer_index,
-                            BAD_MESSAGE_BAN_TIME,
-                            String::from(
-                                "send us a malformed message: \
-                                 too many fields in CompactBlock",
-                            ),
-                        );
+                        nc.report_peer(peer_index, Behaviour::MalformedMessage);
                         return;
                     } else {
                         item
@@ -810,14 +1023,7 @@ impl CKBProtocolHandler for Relayer {
                                  too many fields",
                                 peer_index
                             );
-                            nc.ban_peer(
-                                peer_index,
-                                BAD_MESSAGE_BAN_TIME,
-                                String::from(
-                                    "send us a malformed message \
-                                     too many fields",
-                                ),
-                            );
+                            nc.report_peer(peer_index, Behaviour::MalformedMessage);
                             return;
                         }
                     }
@@ -829,11 +1035,7 @@ impl CKBProtocolHandler for Relayer {
                     "Peer {} sends us a malformed message",
                     peer_index
                 );
-                nc.ban_peer(
-                    peer_index,
-                    BAD_MESSAGE_BAN_TIME,
-                    String::from("send us a malformed message"),
-                );
+                nc.report_peer(peer_index, Behaviour::MalformedMessage);
                 return;
             }
         };
@@ -867,11 +1069,16 @@ impl CKBProtocolHandler for Relayer {
 
     async fn connected(
         &mut self,
-        _nc: Arc<dyn CKBProtocolContext + Sync>,
+        nc: Arc<dyn CKBProtocolContext + Sync>,
         peer_index: PeerIndex,
         version: &str,
     ) {
         self.shared().state().peers().relay_connected(peer_index);
+        if version == RELAY_V4_VERSION {
+            let salt = rand::random();
+            self.compact_block_peers.lock().connected(peer_index, salt);
+            self.send_compact_block_announcement(nc.as_ref(), peer_index, salt, false);
+        }
         info_target!(
             crate::LOG_TARGET_RELAY,
             "RelayProtocol({}).connected peer={}",
@@ -892,6 +1099,14 @@ impl CKBProtocolHandler for Relayer {
         );
         // Retains all keys in the rate limiter that were used recently enough.
         self.rate_limiter.lock().retain_recent();
+        let mut compact_block_peers = self.compact_block_peers.lock();
+        if compact_block_peers.disconnected(peer_index) {
+            if let Some(metrics) = ckb_metrics::handle() {
+                metrics
+                    .ckb_relay_cb_high_bandwidth_peers
+                    .set(compact_block_peers.high_bandwidth_from().count() as i64);
+            }
+        }
     }
 
     async fn notify(&mut self, nc: Arc<dyn CKBProtocolContext + Sync>, token: u64) {
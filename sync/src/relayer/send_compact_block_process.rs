@@ -0,0 +1,46 @@
+use crate::relayer::Relayer;
+use crate::Status;
+use ckb_logger::debug_target;
+use ckb_network::PeerIndex;
+use ckb_types::{packed, prelude::*};
+
+pub struct SendCompactBlockProcess<'a> {
+    message: packed::SendCompactBlockReader<'a>,
+    relayer: &'a Relayer,
+    peer: PeerIndex,
+}
+
+impl<'a> SendCompactBlockProcess<'a> {
+    pub fn new(
+        message: packed::SendCompactBlockReader<'a>,
+        relayer: &'a Relayer,
+        peer: PeerIndex,
+    ) -> Self {
+        SendCompactBlockProcess {
+            message,
+            relayer,
+            peer,
+        }
+    }
+
+    pub fn execute(self) -> Status {
+        let salt: u64 = self.message.salt().unpack();
+        let high_bandwidth: bool = self.message.high_bandwidth().unpack();
+        // peers connected by relay protocol before v4 can't negotiate the salt
+        if !self
+            .relayer
+            .compact_block_peers
+            .lock()
+            .received(self.peer, salt, high_bandwidth)
+        {
+            return Status::ignored();
+        }
+        debug_target!(
+            crate::LOG_TARGET_RELAY,
+            "peer {} announces compact block salt, high-bandwidth: {}",
+            self.peer,
+            high_bandwidth
+        );
+        Status::ok()
+    }
+}
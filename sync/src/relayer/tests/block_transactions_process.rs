@@ -59,6 +59,7 @@ fn test_accept_block() {
             hash.clone(),
             (
                 compact_block,
+                None,
                 HashMap::from_iter(vec![
                     (peer_index, (vec![1], vec![0])),
                     (other_peer_index, (vec![1], vec![])),
@@ -125,6 +126,7 @@ fn test_unknown_request() {
             compact_block.header().calc_header_hash(),
             (
                 compact_block,
+                None,
                 HashMap::from_iter(vec![(foo_peer_index, (vec![1], vec![]))]),
                 ckb_systemtime::unix_time_as_millis(),
             ),
@@ -190,6 +192,7 @@ fn test_invalid_transaction_root() {
             block_hash.clone(),
             (
                 compact_block,
+                None,
                 HashMap::from_iter(vec![(peer_index, (vec![1], vec![]))]),
                 ckb_systemtime::unix_time_as_millis(),
             ),
@@ -286,6 +289,7 @@ fn test_collision_and_send_missing_indexes() {
             hash.clone(),
             (
                 compact_block,
+                None,
                 vec![(peer_index, (vec![1], vec![]))].into_iter().collect(),
                 ckb_systemtime::unix_time_as_millis(),
             ),
@@ -328,7 +332,7 @@ fn test_collision_and_send_missing_indexes() {
             pending_compact_blocks
                 .get(&hash)
                 .unwrap()
-                .1
+                .2
                 .get(&peer_index),
             Some(&(vec![1, 2], vec![]))
         );
@@ -400,6 +404,7 @@ fn test_missing() {
             compact_block.header().calc_header_hash(),
             (
                 compact_block,
+                None,
                 HashMap::from_iter(vec![(peer_index, (vec![1], vec![]))]),
                 ckb_systemtime::unix_time_as_millis(),
             ),
@@ -31,7 +31,7 @@ fn test_invalid() {
         .collect();
 
     assert_eq!(
-        BlockTransactionsVerifier::verify(&block, &indexes, block_txs.as_slice()),
+        BlockTransactionsVerifier::verify(&block, None, &indexes, block_txs.as_slice()),
         StatusCode::BlockTransactionsLengthIsUnmatchedWithPendingCompactBlock.into(),
     );
 
@@ -41,7 +41,7 @@ fn test_invalid() {
         .map(|i| new_index_transaction(i).transaction().into_view())
         .collect();
     assert_eq!(
-        BlockTransactionsVerifier::verify(&block, &indexes, &block_txs),
+        BlockTransactionsVerifier::verify(&block, None, &indexes, &block_txs),
         StatusCode::BlockTransactionsShortIdsAreUnmatchedWithPendingCompactBlock.into(),
     );
 }
@@ -57,7 +57,7 @@ fn test_ok() {
         .collect();
 
     assert_eq!(
-        BlockTransactionsVerifier::verify(&block, &indexes, &block_txs),
+        BlockTransactionsVerifier::verify(&block, None, &indexes, &block_txs),
         Status::ok()
     );
 }
@@ -0,0 +1,64 @@
+use crate::relayer::compact_block_peers::{CompactBlockPeers, MAX_HIGH_BANDWIDTH_PEERS};
+use ckb_network::PeerIndex;
+
+#[test]
+fn test_salt_negotiation() {
+    let mut peers = CompactBlockPeers::default();
+    let peer: PeerIndex = 1.into();
+
+    // peers not connected by v4 can't announce a salt
+    assert!(!peers.received(peer, 2, false));
+    assert_eq!(peers.salt(peer), None);
+
+    peers.connected(peer, 1);
+    assert_eq!(peers.local_salt(peer), Some(1));
+    assert_eq!(peers.salt(peer), None);
+
+    assert!(peers.received(peer, 2, false));
+    assert_eq!(peers.salt(peer), Some(1 ^ 2));
+    assert!(peers.high_bandwidth_to().is_empty());
+
+    // the salt is fixed by the first announcement
+    assert!(peers.received(peer, 3, true));
+    assert_eq!(peers.salt(peer), Some(1 ^ 2));
+    assert_eq!(peers.high_bandwidth_to(), vec![peer]);
+
+    peers.disconnected(peer);
+    assert_eq!(peers.salt(peer), None);
+    assert!(peers.high_bandwidth_to().is_empty());
+}
+
+#[test]
+fn test_select_high_bandwidth_peers() {
+    let mut peers = CompactBlockPeers::default();
+    let legacy_peer: PeerIndex = 100.into();
+    assert_eq!(peers.select_high_bandwidth(legacy_peer), None);
+
+    for i in 0..=MAX_HIGH_BANDWIDTH_PEERS {
+        let peer: PeerIndex = i.into();
+        peers.connected(peer, i as u64);
+        peers.received(peer, 0, false);
+    }
+    for i in 0..MAX_HIGH_BANDWIDTH_PEERS {
+        assert_eq!(peers.select_high_bandwidth(i.into()), Some(None));
+    }
+    // already selected
+    assert_eq!(peers.select_high_bandwidth(0.into()), None);
+
+    // the earliest selected peer is demoted
+    assert_eq!(
+        peers.select_high_bandwidth(MAX_HIGH_BANDWIDTH_PEERS.into()),
+        Some(Some(0.into()))
+    );
+    assert_eq!(
+        peers.high_bandwidth_from().count(),
+        MAX_HIGH_BANDWIDTH_PEERS
+    );
+
+    assert!(!peers.disconnected(0.into()));
+    assert!(peers.disconnected(1.into()));
+    assert_eq!(
+        peers.high_bandwidth_from().count(),
+        MAX_HIGH_BANDWIDTH_PEERS - 1
+    );
+}
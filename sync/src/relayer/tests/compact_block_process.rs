@@ -98,6 +98,42 @@ fn test_in_block_status_map() {
     assert_eq!(compact_block_process.execute(), Status::ignored());
 }
 
+#[test]
+fn test_salted_compact_block_without_salts() {
+    let (relayer, _) = build_chain(5);
+    let header = {
+        let shared = relayer.shared.shared();
+        let parent = shared
+            .store()
+            .get_block_hash(4)
+            .and_then(|block_hash| shared.store().get_block(&block_hash))
+            .unwrap();
+        new_header_builder(relayer.shared.shared(), &parent.header()).build()
+    };
+    let block = BlockBuilder::default()
+        .transaction(TransactionBuilder::default().build())
+        .header(header)
+        .build();
+    let compact_block =
+        packed::SaltedCompactBlock::build_salted_from_block(&block, &HashSet::new(), 42);
+
+    let mock_protocol_context = MockProtocolContext::new(SupportProtocols::RelayV3);
+    let nc = Arc::new(mock_protocol_context);
+    let peer_index: PeerIndex = 1.into();
+
+    // the peer hasn't announced its salt by `SendCompactBlock`
+    let compact_block_process = CompactBlockProcess::new_salted(
+        compact_block.as_reader(),
+        &relayer,
+        Arc::<MockProtocolContext>::clone(&nc),
+        peer_index,
+    );
+    assert_eq!(
+        compact_block_process.execute(),
+        StatusCode::ProtocolMessageIsMalformed.into(),
+    );
+}
+
 // send_getheaders_to_peer when UnknownParent
 #[test]
 fn test_unknow_parent() {
@@ -350,6 +386,7 @@ fn test_accept_block() {
             mock_block_1.header().hash(),
             (
                 mock_compact_block_1,
+                None,
                 HashMap::from_iter(vec![(1.into(), (vec![1], vec![0]))]),
                 ckb_systemtime::unix_time_as_millis(),
             ),
@@ -359,6 +396,7 @@ fn test_accept_block() {
             mock_block_2.header().hash(),
             (
                 mock_compact_block_2,
+                None,
                 HashMap::from_iter(vec![(1.into(), (vec![1], vec![0]))]),
                 ckb_systemtime::unix_time_as_millis(),
             ),
@@ -210,6 +210,7 @@ pub(crate) fn build_chain(tip: BlockNumber) -> (Relayer, OutPoint) {
 pub(crate) struct MockProtocolContext {
     protocol: SupportProtocols,
     sent_messages: RefCell<Vec<(ProtocolId, PeerIndex, P2pBytes)>>,
+    connected_peers: Vec<PeerIndex>,
 }
 
 // test mock context with single thread
@@ -221,9 +222,15 @@ impl MockProtocolContext {
         Self {
             protocol,
             sent_messages: Default::default(),
+            connected_peers: Default::default(),
         }
     }
 
+    pub(crate) fn with_connected_peers(mut self, peers: Vec<PeerIndex>) -> Self {
+        self.connected_peers = peers;
+        self
+    }
+
     pub(crate) fn has_sent(
         &self,
         protocol_id: ProtocolId,
@@ -343,8 +350,11 @@ impl CKBProtocolContext for MockProtocolContext {
         self.send_message(protocol_id, peer_index, data)
     }
 
-    fn filter_broadcast(&self, _target: TargetSession, _data: P2pBytes) -> Result<(), Error> {
-        unimplemented!();
+    fn filter_broadcast(&self, target: TargetSession, data: P2pBytes) -> Result<(), Error> {
+        match target {
+            TargetSession::Single(peer_index) => self.send_message_to(peer_index, data),
+            _ => unimplemented!(),
+        }
     }
     fn disconnect(&self, _peer_index: PeerIndex, _message: &str) -> Result<(), Error> {
         unimplemented!();
@@ -356,7 +366,7 @@ impl CKBProtocolContext for MockProtocolContext {
         unimplemented!();
     }
     fn connected_peers(&self) -> Vec<PeerIndex> {
-        unimplemented!();
+        self.connected_peers.clone()
     }
     fn report_peer(&self, _peer_index: PeerIndex, _behaviour: Behaviour) {
         unimplemented!();
@@ -2,9 +2,11 @@ mod block_proposal_process;
 mod block_transactions_process;
 mod block_transactions_verifier;
 mod compact_block;
+mod compact_block_peers;
 mod compact_block_process;
 mod compact_block_verifier;
 mod get_block_proposal_process;
 mod get_transactions_process;
 mod helper;
+mod package_relay;
 mod reconstruct_block;
@@ -0,0 +1,142 @@
+use crate::relayer::tests::helper::{build_chain, MockProtocolContext};
+use crate::relayer::transactions_process::split_packages;
+use ckb_network::{PeerIndex, SupportProtocols};
+use ckb_store::ChainStore;
+use ckb_test_chain_utils::always_success_cell;
+use ckb_types::{
+    bytes::Bytes,
+    core::{capacity_bytes, Capacity, TransactionBuilder, TransactionView},
+    packed::{self, CellDep, CellInput, CellOutputBuilder, OutPoint},
+    prelude::*,
+};
+
+fn build_spend_tx(
+    previous_outputs: &[OutPoint],
+    capacity: Capacity,
+    always_success_out_point: &OutPoint,
+) -> TransactionView {
+    TransactionBuilder::default()
+        .inputs(
+            previous_outputs
+                .iter()
+                .map(|pt| CellInput::new(pt.clone(), 0)),
+        )
+        .output(
+            CellOutputBuilder::default()
+                .capacity(capacity.pack())
+                .lock(always_success_cell().2.clone())
+                .build(),
+        )
+        .output_data(Bytes::new().pack())
+        .cell_dep(
+            CellDep::new_builder()
+                .out_point(always_success_out_point.to_owned())
+                .build(),
+        )
+        .build()
+}
+
+#[test]
+fn test_split_packages() {
+    let dep = OutPoint::new(Default::default(), 0);
+    let parent = build_spend_tx(
+        &[OutPoint::new([1u8; 32].pack(), 0)],
+        capacity_bytes!(3),
+        &dep,
+    );
+    let other_parent = build_spend_tx(
+        &[OutPoint::new([2u8; 32].pack(), 0)],
+        capacity_bytes!(2),
+        &dep,
+    );
+    let child = build_spend_tx(
+        &[
+            OutPoint::new(parent.hash(), 0),
+            OutPoint::new(other_parent.hash(), 0),
+        ],
+        capacity_bytes!(4),
+        &dep,
+    );
+    let grandchild = build_spend_tx(&[OutPoint::new(child.hash(), 0)], capacity_bytes!(3), &dep);
+    let unrelated = build_spend_tx(
+        &[OutPoint::new([3u8; 32].pack(), 0)],
+        capacity_bytes!(1),
+        &dep,
+    );
+
+    let hashes = |txs: &[(TransactionView, u64)]| -> Vec<packed::Byte32> {
+        txs.iter().map(|(tx, _)| tx.hash()).collect()
+    };
+    let (rest, packages) = split_packages(vec![
+        (grandchild.clone(), 4),
+        (unrelated.clone(), 5),
+        (child.clone(), 3),
+        (other_parent.clone(), 2),
+        (parent.clone(), 1),
+    ]);
+    assert_eq!(hashes(&rest), vec![unrelated.hash()]);
+    assert_eq!(packages.len(), 1);
+    let package = &packages[0];
+    assert_eq!(package.len(), 4);
+    // the package is topologically ordered and keeps the declared cycles
+    let position = |tx: &TransactionView| {
+        package
+            .iter()
+            .position(|(member, _)| member.hash() == tx.hash())
+            .unwrap()
+    };
+    assert!(position(&parent) < position(&child));
+    assert!(position(&other_parent) < position(&child));
+    assert_eq!(position(&grandchild), 3);
+    assert_eq!(package[position(&child)].1, 3);
+}
+
+#[test]
+fn test_relay_package_in_one_announcement() {
+    let (relayer, always_success_out_point) = build_chain(5);
+    let cellbase = {
+        let tip_hash = relayer.shared.active_chain().tip_header().hash();
+        let block = relayer
+            .shared
+            .shared()
+            .store()
+            .get_block(&tip_hash)
+            .expect("getting tip block");
+        block.transactions()[0].clone()
+    };
+    let capacity: Capacity = cellbase.outputs().get(0).unwrap().capacity().unpack();
+
+    // the parent pays no fee, it can only be relayed with the child paying for it
+    let parent = build_spend_tx(
+        &[cellbase.output_pts()[0].clone()],
+        capacity,
+        &always_success_out_point,
+    );
+    let child = build_spend_tx(
+        &[OutPoint::new(parent.hash(), 0)],
+        capacity.safe_sub(capacity_bytes!(1)).unwrap(),
+        &always_success_out_point,
+    );
+    let tx_pool = relayer.shared.shared().tx_pool_controller();
+    tx_pool
+        .submit_local_package(vec![parent.clone(), child.clone()])
+        .unwrap()
+        .unwrap();
+
+    let peers: Vec<PeerIndex> = vec![1.into(), 2.into()];
+    let nc =
+        MockProtocolContext::new(SupportProtocols::RelayV2).with_connected_peers(peers.clone());
+    relayer.send_bulk_of_tx_hashes(&nc);
+
+    let content = packed::RelayTransactionHashes::new_builder()
+        .tx_hashes(vec![parent.hash(), child.hash()].pack())
+        .build();
+    let message = packed::RelayMessage::new_builder().set(content).build();
+    for peer in peers {
+        assert!(nc.has_sent(
+            SupportProtocols::RelayV2.protocol_id(),
+            peer,
+            message.as_bytes()
+        ));
+    }
+}
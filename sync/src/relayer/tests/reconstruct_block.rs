@@ -7,6 +7,7 @@ use ckb_types::prelude::*;
 use ckb_types::{
     core::{BlockBuilder, Capacity, TransactionView},
     packed::{self, CompactBlockBuilder},
+    utilities::ShortIdKey,
 };
 use std::collections::HashSet;
 // There are more test cases in block_transactions_process and compact_block_process.rs
@@ -173,3 +174,63 @@ fn test_reconstruct_invalid_uncles() {
         ReconstructionResult::Error(StatusCode::CompactBlockHasInvalidUncle.into()),
     );
 }
+
+#[test]
+fn test_reconstruct_salted_block() {
+    let (relayer, always_success_out_point) = build_chain(5);
+    let prepare: Vec<TransactionView> = (0..10)
+        .map(|i| new_transaction(&relayer, i, &always_success_out_point))
+        .collect();
+    let block = BlockBuilder::default()
+        .transactions(prepare.clone())
+        .build();
+    let salt = 42;
+    let short_id_key = ShortIdKey::new(&block.hash(), salt);
+    let compact =
+        packed::SaltedCompactBlock::build_salted_from_block(&block, &HashSet::new(), salt)
+            .to_compact_block();
+    assert_eq!(
+        compact.short_ids().get(0),
+        Some(
+            short_id_key
+                .short_id(&prepare[0].hash())
+                .to_proposal_short_id()
+        )
+    );
+
+    // half of the txs are in pool, the others are received from peer
+    let (pool_transactions, received_transactions) = prepare.split_at(5);
+    let entries = pool_transactions
+        .iter()
+        .cloned()
+        .map(|tx| TxEntry::dummy_resolve(tx, 0, Capacity::shannons(0), 0))
+        .collect();
+    relayer
+        .shared
+        .shared()
+        .tx_pool_controller()
+        .plug_entry(entries, PlugTarget::Pending)
+        .unwrap();
+
+    // the salted short ids can't be matched without the key
+    assert_eq!(
+        relayer.reconstruct_block(
+            &relayer.shared().active_chain(),
+            &compact,
+            received_transactions.to_vec(),
+            &[],
+            &[]
+        ),
+        ReconstructionResult::Missing((0..10).collect(), vec![]),
+    );
+
+    let ret = relayer.reconstruct_salted_block(
+        &relayer.shared().active_chain(),
+        &compact,
+        Some(short_id_key),
+        received_transactions.to_vec(),
+        &[],
+        &[],
+    );
+    assert_eq!(ret, ReconstructionResult::Block(block), "{ret:?}");
+}
@@ -2,11 +2,13 @@ use crate::relayer::Relayer;
 use crate::Status;
 use ckb_logger::error;
 use ckb_network::{CKBProtocolContext, PeerIndex};
+use ckb_tx_pool::MAX_PACKAGE_TXS_COUNT;
 use ckb_types::{
     core::{Cycle, TransactionView},
-    packed,
+    packed::{self, Byte32},
     prelude::*,
 };
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -80,6 +82,7 @@ impl<'a> TransactionsProcess<'a> {
 
         shared_state.mark_as_known_txs(txs.iter().map(|(tx, _)| tx.hash()));
 
+        let (txs, packages) = split_packages(txs);
         let tx_pool = self.relayer.shared.shared().tx_pool_controller().clone();
         let peer = self.peer;
         self.relayer
@@ -95,8 +98,100 @@ impl<'a> TransactionsProcess<'a> {
                         error!("submit_tx error {}", e);
                     }
                 }
+                for package in packages {
+                    if let Err(e) = tx_pool.submit_remote_package(package, peer).await {
+                        error!("submit_package error {}", e);
+                    }
+                }
             });
 
         Status::ok()
     }
 }
+
+type TxWithCycles = (TransactionView, Cycle);
+
+/// Splits the relayed transactions into the packages and the rest. A package is a transaction
+/// spending the outputs of the other transactions in the batch, which isn't spent in the batch
+/// itself, together with its ancestors in the batch, topologically ordered. The peers relay a
+/// package in one batch, so the low fee parents are evaluated with the children paying for them.
+pub(crate) fn split_packages(
+    txs: Vec<TxWithCycles>,
+) -> (Vec<TxWithCycles>, Vec<Vec<TxWithCycles>>) {
+    let positions: HashMap<Byte32, usize> = txs
+        .iter()
+        .enumerate()
+        .map(|(i, (tx, _))| (tx.hash(), i))
+        .collect();
+    let parents: Vec<HashSet<usize>> = txs
+        .iter()
+        .map(|(tx, _)| {
+            tx.input_pts_iter()
+                .filter_map(|pt| positions.get(&pt.tx_hash()).copied())
+                .collect()
+        })
+        .collect();
+    let mut children: Vec<Vec<usize>> = vec![Vec::new(); txs.len()];
+    for (child, child_parents) in parents.iter().enumerate() {
+        for parent in child_parents {
+            children[*parent].push(child);
+        }
+    }
+
+    // the transaction hashes commit to their inputs, so the spending graph has no cycles
+    let mut rank = vec![0; txs.len()];
+    let mut pending_parents: Vec<usize> = parents.iter().map(HashSet::len).collect();
+    let mut ready: Vec<usize> = (0..txs.len())
+        .filter(|i| pending_parents[*i] == 0)
+        .collect();
+    let mut next_rank = 0;
+    while let Some(i) = ready.pop() {
+        rank[i] = next_rank;
+        next_rank += 1;
+        for child in &children[i] {
+            pending_parents[*child] -= 1;
+            if pending_parents[*child] == 0 {
+                ready.push(*child);
+            }
+        }
+    }
+
+    let mut in_package = vec![false; txs.len()];
+    let mut packages = Vec::new();
+    for child in 0..txs.len() {
+        if parents[child].is_empty() || !children[child].is_empty() {
+            continue;
+        }
+        // the oversized packages are rejected by the tx-pool, relay their txs one by one
+        let mut members = HashSet::new();
+        let mut stack = vec![child];
+        while let Some(i) = stack.pop() {
+            if members.len() > MAX_PACKAGE_TXS_COUNT {
+                break;
+            }
+            if members.insert(i) {
+                stack.extend(parents[i].iter().copied());
+            }
+        }
+        if members.len() > MAX_PACKAGE_TXS_COUNT {
+            continue;
+        }
+        let mut members: Vec<usize> = members.into_iter().collect();
+        members.sort_by_key(|i| rank[*i]);
+        for i in &members {
+            in_package[*i] = true;
+        }
+        packages.push(members);
+    }
+
+    let packages = packages
+        .into_iter()
+        .map(|members| members.into_iter().map(|i| txs[i].clone()).collect())
+        .collect();
+    let rest = txs
+        .into_iter()
+        .zip(in_package)
+        .filter_map(|(tx, in_package)| (!in_package).then_some(tx))
+        .collect();
+    (rest, packages)
+}
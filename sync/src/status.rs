@@ -1,4 +1,5 @@
-use ckb_constant::sync::{BAD_MESSAGE_BAN_TIME, SYNC_USELESS_BAN_TIME};
+use ckb_constant::sync::SYNC_USELESS_BAN_TIME;
+use ckb_network::Behaviour;
 use std::fmt::{self, Display, Formatter};
 use std::time::Duration;
 
@@ -160,14 +161,29 @@ impl Status {
         self.code == StatusCode::OK
     }
 
-    /// Whether should ban session
+    /// Whether should ban session regardless of its misbehavior score
     pub fn should_ban(&self) -> Option<Duration> {
-        if !(400..500).contains(&(self.code as u16)) {
-            return None;
-        }
         match self.code {
             StatusCode::GetHeadersMissCommonAncestors => Some(SYNC_USELESS_BAN_TIME),
-            _ => Some(BAD_MESSAGE_BAN_TIME),
+            _ => None,
+        }
+    }
+
+    /// The misbehavior to report to the network if the peer causes the status
+    pub fn should_report(&self) -> Option<Behaviour> {
+        match self.code {
+            StatusCode::TooManyRequests => Some(Behaviour::TooManyRequests),
+            StatusCode::BlockIsInvalid
+            | StatusCode::CompactBlockHasInvalidHeader
+            | StatusCode::CompactBlockHasInvalidUncle
+            | StatusCode::HeadersIsInvalid => Some(Behaviour::InvalidBlock),
+            // banned regardless of the score, see `should_ban`
+            StatusCode::GetHeadersMissCommonAncestors => None,
+            StatusCode::RequestGenesis | StatusCode::RequestDuplicate => {
+                Some(Behaviour::UnexpectedMessage)
+            }
+            code if (400..500).contains(&(code as u16)) => Some(Behaviour::MalformedMessage),
+            _ => None,
         }
     }
 
@@ -0,0 +1,173 @@
+//! Back-fills the bodies of the historical blocks after the node is bootstrapped from a cell set
+//! snapshot.
+//!
+//! The blocks are requested from the peers which have announced the end of the back-fill range.
+//! The bodies are checked against the stored headers and by the non-contextual verifiers, the
+//! scripts are not executed again since the cell set is pinned in the chain spec.
+use crate::types::SyncShared;
+use crate::utils::send_message_to;
+use crate::{Status, StatusCode};
+use ckb_logger::{debug, info};
+use ckb_network::{CKBProtocolContext, PeerIndex};
+use ckb_store::ChainStore;
+use ckb_types::{
+    core::{BlockNumber, BlockView},
+    packed,
+    prelude::*,
+};
+use ckb_util::Mutex;
+use ckb_verification::BlockVerifier;
+use ckb_verification_traits::Verifier;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// The max count of blocks requested from a peer at a time.
+const BACKFILL_BLOCKS_PER_PEER: usize = 16;
+/// The blocks are requested within this window after the start of the back-fill range.
+const BACKFILL_WINDOW: BlockNumber = 1024;
+/// The requests which are not responded within this timeout are sent to other peers.
+const BACKFILL_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Default)]
+pub(crate) struct Backfill {
+    /// The blocks requested and the time when they were requested
+    inflight: Mutex<HashMap<BlockNumber, (PeerIndex, Instant)>>,
+}
+
+impl Backfill {
+    /// Requests the missing block bodies from the peers.
+    pub(crate) fn fetch(&self, shared: &SyncShared, nc: &dyn CKBProtocolContext) {
+        let store = shared.store();
+        let (start, end) = match store.get_backfill_range() {
+            Some(range) => range,
+            None => return,
+        };
+
+        let mut inflight = self.inflight.lock();
+        let now = Instant::now();
+        inflight.retain(|number, (_, requested_at)| {
+            *number >= start && now.saturating_duration_since(*requested_at) < BACKFILL_TIMEOUT
+        });
+
+        let peers: Vec<PeerIndex> = shared
+            .state()
+            .peers()
+            .state
+            .iter()
+            .filter(|kv_pair| {
+                let state = kv_pair.value();
+                // the pruned peers don't provide the historical blocks
+                !state.peer_flags.is_pruned
+                    && state
+                        .best_known_header
+                        .as_ref()
+                        .map_or(false, |header| header.number() >= end)
+            })
+            .map(|kv_pair| *kv_pair.key())
+            .collect();
+
+        let missing: Vec<(BlockNumber, packed::Byte32)> = (start..end.min(start + BACKFILL_WINDOW))
+            .filter(|number| !inflight.contains_key(number))
+            .filter_map(|number| {
+                let hash = store.get_block_hash(number)?;
+                // the bodies may be back-filled out of order
+                if store.get_block_proposal_txs_ids(&hash).is_some() {
+                    None
+                } else {
+                    Some((number, hash))
+                }
+            })
+            .take(peers.len() * BACKFILL_BLOCKS_PER_PEER)
+            .collect();
+        let mut numbers = missing.into_iter();
+        for peer in peers {
+            let fetch: Vec<(BlockNumber, packed::Byte32)> =
+                numbers.by_ref().take(BACKFILL_BLOCKS_PER_PEER).collect();
+            if fetch.is_empty() {
+                break;
+            }
+            for (number, _) in &fetch {
+                inflight.insert(*number, (peer, now));
+            }
+            debug!(
+                "Backfill request blocks {}-{} from peer {}",
+                fetch[0].0,
+                fetch[fetch.len() - 1].0,
+                peer
+            );
+            let content = packed::GetBlocks::new_builder()
+                .block_hashes(fetch.into_iter().map(|(_, hash)| hash).pack())
+                .build();
+            let message = packed::SyncMessage::new_builder().set(content).build();
+            let _status = send_message_to(nc, peer, &message);
+        }
+    }
+
+    /// Stores the body of a block in the back-fill range, returns `None` if the block is not in the
+    /// range, then it is processed as a new block.
+    pub(crate) fn accept(&self, shared: &SyncShared, block: &BlockView) -> Option<Status> {
+        let store = shared.store();
+        let (start, end) = store.get_backfill_range()?;
+        if !(start..end).contains(&block.number())
+            || store.get_block_hash(block.number())? != block.hash()
+        {
+            return None;
+        }
+
+        // the lock also serializes the updates of the back-fill range
+        let mut inflight = self.inflight.lock();
+        inflight.remove(&block.number());
+        if store.get_block_proposal_txs_ids(&block.hash()).is_some() {
+            return Some(Status::ok());
+        }
+
+        // the header hash commits to the transactions root and the proposals hash, which are
+        // checked by the block verifier, and the extra hash
+        if let Err(err) = BlockVerifier::new(shared.consensus()).verify(block) {
+            return Some(StatusCode::BlockIsInvalid.with_context(format!(
+                "{}, error: {}",
+                block.hash(),
+                err
+            )));
+        }
+        if block.calc_extra_hash().extra_hash() != block.extra_hash() {
+            return Some(StatusCode::BlockIsInvalid.with_context(format!(
+                "{}, error: the extra hash doesn't match",
+                block.hash()
+            )));
+        }
+
+        let txn = store.begin_transaction();
+        let result = txn
+            .insert_block(block)
+            .and_then(|_| txn.attach_block(block))
+            .and_then(|_| {
+                let mut next = start;
+                while next < end
+                    && txn
+                        .get_block_hash(next)
+                        .and_then(|hash| txn.get_block_proposal_txs_ids(&hash))
+                        .is_some()
+                {
+                    next += 1;
+                }
+                if next == end {
+                    info!("Backfill finished, all the block bodies are stored");
+                    txn.delete_backfill_range()
+                } else if next != start {
+                    txn.insert_backfill_range(next, end)
+                } else {
+                    Ok(())
+                }
+            })
+            .and_then(|_| txn.commit());
+        if let Err(err) = result {
+            return Some(StatusCode::Ignored.with_context(format!(
+                "failed to back-fill block {}, error: {}",
+                block.hash(),
+                err
+            )));
+        }
+        Some(Status::ok())
+    }
+}
@@ -1,8 +1,10 @@
 use crate::block_status::BlockStatus;
 use crate::types::{ActiveChain, BlockNumberAndHash, HeaderIndex, HeaderIndexView, IBDState};
 use crate::SyncShared;
+use ckb_constant::store::MIN_PRUNE_RETENTION_DEPTH;
 use ckb_constant::sync::{
-    BLOCK_DOWNLOAD_WINDOW, CHECK_POINT_WINDOW, INIT_BLOCKS_IN_TRANSIT_PER_PEER,
+    BLOCK_DOWNLOAD_FRONT_WINDOW, BLOCK_DOWNLOAD_WINDOW, CHECK_POINT_WINDOW,
+    INIT_BLOCKS_IN_TRANSIT_PER_PEER,
 };
 use ckb_logger::{debug, trace};
 use ckb_network::PeerIndex;
@@ -138,6 +140,22 @@ impl BlockFetcher {
             return None;
         }
 
+        // A pruned peer only provides the bodies of the latest blocks
+        let is_pruned = self
+            .sync_shared
+            .state()
+            .peers()
+            .get_flag(self.peer)
+            .map_or(false, |flags| flags.is_pruned);
+        if is_pruned && last_common.number() + MIN_PRUNE_RETENTION_DEPTH < best_known.number() {
+            debug!(
+                "Peer {} is pruned, ignore it for the blocks after {}",
+                self.peer,
+                last_common.number()
+            );
+            return None;
+        }
+
         let state = self.sync_shared.state();
         let mut inflight = state.write_inflight_blocks();
         let mut start = last_common.number() + 1;
@@ -148,6 +166,25 @@ impl BlockFetcher {
         );
         let mut fetch = Vec::with_capacity(n_fetch);
         let now = unix_time_as_millis();
+        let tip = self.active_chain.tip_number();
+
+        // The download scheduler is shared by all the peers. During IBD, the blocks right after the
+        // tip block the whole sync, so their stalled downloads are taken over by this peer if it is
+        // faster, and they are never assigned to a slow peer.
+        let mut avoid_front = false;
+        if let IBDState::In = self.ibd {
+            let reassigned = inflight.reassign_stalled(self.peer, tip, n_fetch, |block| {
+                self.active_chain
+                    .get_ancestor(&best_known.hash(), block.number())
+                    .map_or(false, |header| header.hash() == block.hash())
+            });
+            for block in reassigned {
+                if let Some(header) = self.sync_shared.get_header_index_view(&block.hash(), false) {
+                    fetch.push(header);
+                }
+            }
+            avoid_front = inflight.is_slow_peer(self.peer);
+        }
 
         while fetch.len() < n_fetch && start <= end {
             let span = min(end - start + 1, (n_fetch - fetch.len()) as u64);
@@ -174,6 +211,8 @@ impl BlockFetcher {
                     break;
                 } else if status.contains(BlockStatus::BLOCK_RECEIVED) {
                     // Do not download repeatedly
+                } else if avoid_front && header.number() <= tip + BLOCK_DOWNLOAD_FRONT_WINDOW {
+                    // Leave the front window to the faster peers
                 } else if (matches!(self.ibd, IBDState::In)
                     || state.compare_with_pending_compact(&hash, now))
                     && inflight.insert(self.peer, (header.number(), hash).into())
@@ -194,7 +233,6 @@ impl BlockFetcher {
         // The headers in `fetch` may be unordered. Sort them by number.
         fetch.sort_by_key(|header| header.number());
 
-        let tip = self.active_chain.tip_number();
         let should_mark = fetch.last().map_or(false, |header| {
             header.number().saturating_sub(CHECK_POINT_WINDOW) > tip
         });
@@ -32,6 +32,10 @@ impl<'a> BlockProcess<'a> {
         let shared = self.synchronizer.shared();
         let state = shared.state();
 
+        if let Some(status) = self.synchronizer.backfill.accept(shared, &block) {
+            return status;
+        }
+
         if state.new_block_received(&block) {
             if let Err(err) = self.synchronizer.process_new_block(block.clone()) {
                 if !is_internal_db_error(&err) {
@@ -6,6 +6,7 @@
 //! And CKB has a headers-first synchronization style like Bitcoin:
 //! <https://btcinformation.org/en/glossary/headers-first-sync>
 //!
+mod backfill;
 mod block_fetcher;
 mod block_process;
 mod get_blocks_process;
@@ -13,6 +14,7 @@ mod get_headers_process;
 mod headers_process;
 mod in_ibd_process;
 
+pub(crate) use self::backfill::Backfill;
 pub(crate) use self::block_fetcher::BlockFetcher;
 pub(crate) use self::block_process::BlockProcess;
 pub(crate) use self::get_blocks_process::GetBlocksProcess;
@@ -29,16 +31,17 @@ use ckb_chain::chain::ChainController;
 use ckb_channel as channel;
 use ckb_channel::{select, Receiver};
 use ckb_constant::sync::{
-    BAD_MESSAGE_BAN_TIME, CHAIN_SYNC_TIMEOUT, EVICTION_HEADERS_RESPONSE_TIME,
-    INIT_BLOCKS_IN_TRANSIT_PER_PEER, MAX_TIP_AGE,
+    CHAIN_SYNC_TIMEOUT, EVICTION_HEADERS_RESPONSE_TIME, INIT_BLOCKS_IN_TRANSIT_PER_PEER,
+    MAX_TIP_AGE,
 };
 use ckb_error::Error as CKBError;
 use ckb_logger::{debug, error, info, trace, warn};
 use ckb_network::{
-    async_trait, bytes::Bytes, tokio, CKBProtocolContext, CKBProtocolHandler, PeerIndex,
-    ServiceControl, SupportProtocols,
+    async_trait, bytes::Bytes, tokio, Behaviour, CKBProtocolContext, CKBProtocolHandler, Flags,
+    PeerIndex, ServiceControl, SupportProtocols,
 };
 use ckb_stop_handler::{new_crossbeam_exit_rx, register_thread};
+use ckb_store::ChainStore;
 use ckb_systemtime::unix_time_as_millis;
 use ckb_types::{
     core::{self, BlockNumber},
@@ -55,6 +58,7 @@ pub const SEND_GET_HEADERS_TOKEN: u64 = 0;
 pub const IBD_BLOCK_FETCH_TOKEN: u64 = 1;
 pub const NOT_IBD_BLOCK_FETCH_TOKEN: u64 = 2;
 pub const TIMEOUT_EVICTION_TOKEN: u64 = 3;
+pub const BACKFILL_TOKEN: u64 = 4;
 pub const NO_PEER_CHECK_TOKEN: u64 = 255;
 
 const SYNC_NOTIFY_INTERVAL: Duration = Duration::from_secs(1);
@@ -231,6 +235,8 @@ pub struct Synchronizer {
     /// Sync shared state
     pub shared: Arc<SyncShared>,
     fetch_channel: Option<channel::Sender<FetchCMD>>,
+    /// Back-fills the block bodies after bootstrapping from a cell set snapshot
+    pub(crate) backfill: Backfill,
 }
 
 impl Synchronizer {
@@ -242,6 +248,7 @@ impl Synchronizer {
             chain,
             shared,
             fetch_channel: None,
+            backfill: Backfill::default(),
         }
     }
 
@@ -301,6 +308,12 @@ impl Synchronizer {
                 item_name, peer, ban_time, status
             );
             nc.ban_peer(peer, ban_time, status.to_string());
+        } else if let Some(behaviour) = status.should_report() {
+            debug!(
+                "Receive {} from {}. Report {:?} for {}",
+                item_name, peer, behaviour, status
+            );
+            nc.report_peer(peer, behaviour);
         } else if status.should_warn() {
             warn!("Receive {} from {}, {}", item_name, peer, status);
         } else if !status.is_ok() {
@@ -363,19 +376,22 @@ impl Synchronizer {
 
     pub(crate) fn on_connected(&self, nc: &dyn CKBProtocolContext, peer: PeerIndex) {
         let pid = SupportProtocols::Sync.protocol_id();
-        let (is_outbound, is_whitelist, is_2023edition) = nc
+        let (is_outbound, is_whitelist, is_2023edition, is_pruned) = nc
             .get_peer(peer)
             .map(|peer| {
                 (
                     peer.is_outbound(),
                     peer.is_whitelist,
                     peer.protocols.get(&pid).map(|v| v == "3").unwrap_or(false),
+                    peer.identify_info
+                        .as_ref()
+                        .map_or(false, |info| info.flags.contains(Flags::PRUNED)),
                 )
             })
-            .unwrap_or((false, false, false));
+            .unwrap_or((false, false, false, false));
 
         self.peers()
-            .sync_connected(peer, is_outbound, is_whitelist, is_2023edition);
+            .sync_connected(peer, is_outbound, is_whitelist, is_2023edition, is_pruned);
     }
 
     /// Regularly check and eject some nodes that do not respond in time
@@ -706,6 +722,11 @@ impl CKBProtocolHandler for Synchronizer {
         nc.set_notify(Duration::from_secs(2), NO_PEER_CHECK_TOKEN)
             .await
             .expect("set_notify at init is ok");
+        if self.shared.store().get_backfill_range().is_some() {
+            nc.set_notify(SYNC_NOTIFY_INTERVAL, BACKFILL_TOKEN)
+                .await
+                .expect("set_notify at init is ok");
+        }
     }
 
     async fn received(
@@ -724,14 +745,7 @@ impl CKBProtocolHandler for Synchronizer {
                              excessive fields detected in SendBlock",
                             peer_index
                         );
-                        nc.ban_peer(
-                            peer_index,
-                            BAD_MESSAGE_BAN_TIME,
-                            String::from(
-                                "send us a malformed message: \
-                                 too many fields in SendBlock",
-                            ),
-                        );
+                        nc.report_peer(peer_index, Behaviour::MalformedMessage);
                         return;
                     } else {
                         item
@@ -745,14 +759,7 @@ impl CKBProtocolHandler for Synchronizer {
                                  excessive fields",
                                 peer_index
                             );
-                            nc.ban_peer(
-                                peer_index,
-                                BAD_MESSAGE_BAN_TIME,
-                                String::from(
-                                    "send us a malformed message: \
-                                     too many fields",
-                                ),
-                            );
+                            nc.report_peer(peer_index, Behaviour::MalformedMessage);
                             return;
                         }
                     }
@@ -760,11 +767,7 @@ impl CKBProtocolHandler for Synchronizer {
             }
             _ => {
                 info!("A malformed message from peer {}", peer_index);
-                nc.ban_peer(
-                    peer_index,
-                    BAD_MESSAGE_BAN_TIME,
-                    String::from("send us a malformed message"),
-                );
+                nc.report_peer(peer_index, Behaviour::MalformedMessage);
                 return;
             }
         };
@@ -838,6 +841,13 @@ impl CKBProtocolHandler for Synchronizer {
                 TIMEOUT_EVICTION_TOKEN => {
                     self.eviction(nc.as_ref());
                 }
+                BACKFILL_TOKEN => {
+                    if self.shared.store().get_backfill_range().is_some() {
+                        self.backfill.fetch(&self.shared, nc.as_ref());
+                    } else if nc.remove_notify(BACKFILL_TOKEN).await.is_err() {
+                        trace!("Backfill token removal failed");
+                    }
+                }
                 // Here is just for NO_PEER_CHECK_TOKEN token, only handle it when there is no peer.
                 _ => {}
             }
@@ -195,3 +195,87 @@ fn inflight_trace_number_state() {
     assert_eq!(inflight_blocks.peer_can_fetch_count(3.into()), 32 >> 1);
     assert_eq!(inflight_blocks.peer_can_fetch_count(4.into()), 32 >> 1);
 }
+
+#[test]
+fn inflight_blocks_reassign_stalled() {
+    let _faketime_guard = ckb_systemtime::faketime();
+    _faketime_guard.set_faketime(0);
+
+    let mut inflight_blocks = InflightBlocks::default();
+    inflight_blocks.protect_num = 0;
+
+    assert!(inflight_blocks.insert(1.into(), (1, h256!("0x1").pack()).into()));
+    assert!(inflight_blocks.insert(2.into(), (2, h256!("0x2").pack()).into()));
+
+    _faketime_guard.set_faketime(100);
+    inflight_blocks.remove_by_block((2, h256!("0x2").pack()).into());
+
+    // not stalled yet
+    assert!(inflight_blocks
+        .reassign_stalled(2.into(), 0, 8, |_| true)
+        .is_empty());
+
+    _faketime_guard.set_faketime(3001);
+    // the peer doesn't have the block
+    assert!(inflight_blocks
+        .reassign_stalled(2.into(), 0, 8, |_| false)
+        .is_empty());
+    assert_eq!(
+        inflight_blocks.reassign_stalled(2.into(), 0, 8, |_| true),
+        vec![BlockNumberAndHash::from((1, h256!("0x1").pack()))]
+    );
+    assert_eq!(
+        inflight_blocks
+            .inflight_state_by_block(&(1, h256!("0x1").pack()).into())
+            .map(|state| state.peer),
+        Some(2.into())
+    );
+    assert_eq!(inflight_blocks.peer_inflight_count(1.into()), 0);
+    assert_eq!(inflight_blocks.peer_inflight_count(2.into()), 1);
+
+    let stats: Vec<_> = inflight_blocks
+        .download_schedulers_iter()
+        .map(|(peer, scheduler)| {
+            (
+                *peer,
+                scheduler.received_count(),
+                scheduler.stalled_count(),
+                scheduler.avg_response_time(),
+            )
+        })
+        .collect();
+    assert!(stats.contains(&(1.into(), 0, 1, None)));
+    assert!(stats.contains(&(2.into(), 1, 0, Some(100))));
+
+    // an unmeasured peer never takes over the download from a measured one
+    _faketime_guard.set_faketime(6002);
+    assert!(inflight_blocks
+        .reassign_stalled(3.into(), 0, 8, |_| true)
+        .is_empty());
+}
+
+#[test]
+fn inflight_blocks_slow_peer() {
+    let _faketime_guard = ckb_systemtime::faketime();
+    _faketime_guard.set_faketime(0);
+
+    let mut inflight_blocks = InflightBlocks::default();
+    assert!(inflight_blocks.insert(1.into(), (1, h256!("0x1").pack()).into()));
+    assert!(inflight_blocks.insert(2.into(), (2, h256!("0x2").pack()).into()));
+    assert!(inflight_blocks.insert(3.into(), (3, h256!("0x3").pack()).into()));
+
+    _faketime_guard.set_faketime(100);
+    inflight_blocks.remove_by_block((1, h256!("0x1").pack()).into());
+    // only one peer is measured
+    assert!(!inflight_blocks.is_slow_peer(1.into()));
+
+    inflight_blocks.remove_by_block((2, h256!("0x2").pack()).into());
+    _faketime_guard.set_faketime(1200);
+    inflight_blocks.remove_by_block((3, h256!("0x3").pack()).into());
+
+    assert!(!inflight_blocks.is_slow_peer(1.into()));
+    assert!(!inflight_blocks.is_slow_peer(2.into()));
+    assert!(inflight_blocks.is_slow_peer(3.into()));
+    // unmeasured peer
+    assert!(!inflight_blocks.is_slow_peer(4.into()));
+}
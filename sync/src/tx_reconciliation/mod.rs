@@ -0,0 +1,302 @@
+//! Transaction reconciliation protocol, an Erlay-style alternative to flooding transaction hashes.
+//!
+//! The relayer collects the transactions to announce to the peers which open this protocol into
+//! their reconciliation sets, see `TxReconciliationState`. The announcements still go through
+//! `RelayTransactionHashes` of the relay protocol, so the transactions are fetched as usual.
+mod request_tx_reconciliation_process;
+mod send_tx_reconciliation_process;
+mod sketch;
+mod state;
+mod tx_reconciliation_difference_process;
+mod tx_reconciliation_sketch_process;
+
+#[cfg(test)]
+mod tests;
+
+use request_tx_reconciliation_process::RequestTxReconciliationProcess;
+use send_tx_reconciliation_process::SendTxReconciliationProcess;
+use tx_reconciliation_difference_process::TxReconciliationDifferenceProcess;
+use tx_reconciliation_sketch_process::TxReconciliationSketchProcess;
+
+pub(crate) use state::TxReconciliationState;
+
+use crate::utils::{metric_ckb_message_bytes, send_message, send_message_to, MetricDirection};
+use crate::{types::SyncShared, Status};
+use ckb_logger::{debug_target, info_target, warn_target};
+use ckb_network::{
+    async_trait, bytes::Bytes, Behaviour, CKBProtocolContext, CKBProtocolHandler, PeerIndex,
+    SupportProtocols,
+};
+use ckb_types::{packed, packed::Byte32, prelude::*};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+pub const RECONCILIATION_TOKEN: u64 = 0;
+
+// Interval of the initiators requesting reconciliations
+const RECONCILIATION_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Transaction reconciliation protocol handle
+#[derive(Clone)]
+pub struct TxReconciliation {
+    /// Sync shared state
+    shared: Arc<SyncShared>,
+}
+
+impl TxReconciliation {
+    /// Create a new transaction reconciliation protocol handler
+    pub fn new(shared: Arc<SyncShared>) -> Self {
+        Self { shared }
+    }
+
+    fn try_process(
+        &mut self,
+        nc: Arc<dyn CKBProtocolContext + Sync>,
+        peer: PeerIndex,
+        message: packed::TxReconciliationMessageUnionReader<'_>,
+    ) -> Status {
+        match message {
+            packed::TxReconciliationMessageUnionReader::SendTxReconciliation(msg) => {
+                SendTxReconciliationProcess::new(msg, self, peer).execute()
+            }
+            packed::TxReconciliationMessageUnionReader::RequestTxReconciliation(msg) => {
+                RequestTxReconciliationProcess::new(msg, self, nc, peer).execute()
+            }
+            packed::TxReconciliationMessageUnionReader::TxReconciliationSketch(msg) => {
+                TxReconciliationSketchProcess::new(msg, self, nc, peer).execute()
+            }
+            packed::TxReconciliationMessageUnionReader::TxReconciliationDifference(msg) => {
+                TxReconciliationDifferenceProcess::new(msg, self, nc, peer).execute()
+            }
+        }
+    }
+
+    fn process(
+        &mut self,
+        nc: Arc<dyn CKBProtocolContext + Sync>,
+        peer: PeerIndex,
+        message: packed::TxReconciliationMessageUnionReader<'_>,
+    ) {
+        let item_name = message.item_name();
+        let item_bytes = message.as_slice().len() as u64;
+        let status = self.try_process(Arc::clone(&nc), peer, message);
+
+        metric_ckb_message_bytes(
+            MetricDirection::In,
+            &SupportProtocols::TxReconciliation.name(),
+            message.item_name(),
+            Some(status.code()),
+            item_bytes,
+        );
+
+        if let Some(behaviour) = status.should_report() {
+            debug_target!(
+                crate::LOG_TARGET_TX_RECONCILIATION,
+                "receive {} from {}, report {:?} for {}",
+                item_name,
+                peer,
+                behaviour,
+                status
+            );
+            nc.report_peer(peer, behaviour);
+        } else if status.should_warn() {
+            warn_target!(
+                crate::LOG_TARGET_TX_RECONCILIATION,
+                "receive {} from {}, {}",
+                item_name,
+                peer,
+                status
+            );
+        } else if !status.is_ok() {
+            debug_target!(
+                crate::LOG_TARGET_TX_RECONCILIATION,
+                "receive {} from {}, {}",
+                item_name,
+                peer,
+                status
+            );
+        }
+    }
+
+    /// Send a message of this protocol, the bytes are accounted to reconciliation
+    pub(crate) fn send_to<T>(
+        &self,
+        nc: &dyn CKBProtocolContext,
+        peer: PeerIndex,
+        content: T,
+    ) -> Status
+    where
+        T: Into<packed::TxReconciliationMessageUnion>,
+    {
+        let message = packed::TxReconciliationMessage::new_builder()
+            .set(content)
+            .build();
+        let status = send_message_to(nc, peer, &message);
+        if status.is_ok() {
+            if let Some(metrics) = ckb_metrics::handle() {
+                metrics
+                    .ckb_relay_tx_announcement_bytes
+                    .reconciliation
+                    .inc_by(message.as_slice().len() as u64);
+            }
+        }
+        status
+    }
+
+    /// Announce the transactions by `RelayTransactionHashes` of the relay protocol
+    pub(crate) fn announce(
+        &self,
+        nc: &dyn CKBProtocolContext,
+        peer: PeerIndex,
+        tx_hashes: Vec<Byte32>,
+    ) -> Status {
+        if tx_hashes.is_empty() {
+            return Status::ok();
+        }
+        let protocol_id = if nc.ckb2023() {
+            SupportProtocols::RelayV3.protocol_id()
+        } else {
+            SupportProtocols::RelayV2.protocol_id()
+        };
+        let content = packed::RelayTransactionHashes::new_builder()
+            .tx_hashes(tx_hashes.pack())
+            .build();
+        let message = packed::RelayMessage::new_builder().set(content).build();
+        let status = send_message(protocol_id, nc, peer, &message);
+        if status.is_ok() {
+            if let Some(metrics) = ckb_metrics::handle() {
+                metrics
+                    .ckb_relay_tx_announcement_bytes
+                    .reconciliation
+                    .inc_by(message.as_slice().len() as u64);
+            }
+        }
+        status
+    }
+
+    fn reconcile_periodically(&self, nc: &dyn CKBProtocolContext) {
+        let now = Instant::now();
+        let (expired, requests) = {
+            let mut state = self.shared.state().tx_reconciliation();
+            (state.expire(now), state.start_rounds(now))
+        };
+        for (peer, tx_hashes) in expired {
+            debug_target!(
+                crate::LOG_TARGET_TX_RECONCILIATION,
+                "reconciliation with peer {} timed out, flood {} transactions",
+                peer,
+                tx_hashes.len()
+            );
+            let _ignore = self.announce(nc, peer, tx_hashes);
+        }
+        for (peer, set_size) in requests {
+            let content = packed::RequestTxReconciliation::new_builder()
+                .set_size(set_size.pack())
+                .build();
+            let _ignore = self.send_to(nc, peer, content);
+        }
+    }
+}
+
+#[async_trait]
+impl CKBProtocolHandler for TxReconciliation {
+    async fn init(&mut self, nc: Arc<dyn CKBProtocolContext + Sync>) {
+        nc.set_notify(RECONCILIATION_INTERVAL, RECONCILIATION_TOKEN)
+            .await
+            .expect("set_notify at init is ok");
+    }
+
+    async fn received(
+        &mut self,
+        nc: Arc<dyn CKBProtocolContext + Sync>,
+        peer_index: PeerIndex,
+        data: Bytes,
+    ) {
+        let msg = match packed::TxReconciliationMessageReader::from_compatible_slice(&data) {
+            Ok(msg) => msg.to_enum(),
+            _ => {
+                info_target!(
+                    crate::LOG_TARGET_TX_RECONCILIATION,
+                    "Peer {} sends us a malformed message",
+                    peer_index
+                );
+                nc.report_peer(peer_index, Behaviour::MalformedMessage);
+                return;
+            }
+        };
+
+        debug_target!(
+            crate::LOG_TARGET_TX_RECONCILIATION,
+            "received msg {} from {}",
+            msg.item_name(),
+            peer_index
+        );
+        let start_time = Instant::now();
+        self.process(nc, peer_index, msg);
+        debug_target!(
+            crate::LOG_TARGET_TX_RECONCILIATION,
+            "process message={}, peer={}, cost={:?}",
+            msg.item_name(),
+            peer_index,
+            Instant::now().saturating_duration_since(start_time),
+        );
+    }
+
+    async fn connected(
+        &mut self,
+        nc: Arc<dyn CKBProtocolContext + Sync>,
+        peer_index: PeerIndex,
+        version: &str,
+    ) {
+        // the side which opened the connection initiates the reconciliations
+        let initiator = nc
+            .get_peer(peer_index)
+            .map(|peer| peer.is_outbound())
+            .unwrap_or(false);
+        let salt = rand::random();
+        self.shared.state().tx_reconciliation().connected(
+            peer_index,
+            salt,
+            initiator,
+            Instant::now(),
+        );
+        let content = packed::SendTxReconciliation::new_builder()
+            .salt(salt.pack())
+            .build();
+        let _ignore = self.send_to(nc.as_ref(), peer_index, content);
+        info_target!(
+            crate::LOG_TARGET_TX_RECONCILIATION,
+            "TxReconciliationProtocol({}).connected peer={}, initiator={}",
+            version,
+            peer_index,
+            initiator
+        );
+    }
+
+    async fn disconnected(
+        &mut self,
+        _nc: Arc<dyn CKBProtocolContext + Sync>,
+        peer_index: PeerIndex,
+    ) {
+        self.shared
+            .state()
+            .tx_reconciliation()
+            .disconnected(peer_index);
+        info_target!(
+            crate::LOG_TARGET_TX_RECONCILIATION,
+            "TxReconciliationProtocol.disconnected peer={}",
+            peer_index
+        );
+    }
+
+    async fn notify(&mut self, nc: Arc<dyn CKBProtocolContext + Sync>, token: u64) {
+        // transactions are not relayed in the IBD state
+        if self.shared.active_chain().is_initial_block_download() {
+            return;
+        }
+        match token {
+            RECONCILIATION_TOKEN => self.reconcile_periodically(nc.as_ref()),
+            _ => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use crate::tx_reconciliation::TxReconciliation;
+use crate::{Status, StatusCode};
+use ckb_network::{CKBProtocolContext, PeerIndex};
+use ckb_types::{packed, prelude::*};
+use std::sync::Arc;
+use std::time::Instant;
+
+pub struct RequestTxReconciliationProcess<'a> {
+    message: packed::RequestTxReconciliationReader<'a>,
+    protocol: &'a TxReconciliation,
+    nc: Arc<dyn CKBProtocolContext>,
+    peer: PeerIndex,
+}
+
+impl<'a> RequestTxReconciliationProcess<'a> {
+    pub fn new(
+        message: packed::RequestTxReconciliationReader<'a>,
+        protocol: &'a TxReconciliation,
+        nc: Arc<dyn CKBProtocolContext>,
+        peer: PeerIndex,
+    ) -> Self {
+        RequestTxReconciliationProcess {
+            message,
+            protocol,
+            nc,
+            peer,
+        }
+    }
+
+    pub fn execute(self) -> Status {
+        let set_size: u32 = self.message.set_size().unpack();
+        let sketch = match self.protocol.shared.state().tx_reconciliation().sketch(
+            self.peer,
+            set_size,
+            Instant::now(),
+        ) {
+            Some(sketch) => sketch,
+            None => {
+                return StatusCode::Ignored.with_context("unexpected reconciliation request");
+            }
+        };
+        let content = packed::TxReconciliationSketch::new_builder()
+            .sketch(sketch.serialize().pack())
+            .build();
+        self.protocol.send_to(self.nc.as_ref(), self.peer, content)
+    }
+}
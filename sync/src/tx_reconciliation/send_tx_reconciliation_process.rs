@@ -0,0 +1,44 @@
+use crate::tx_reconciliation::TxReconciliation;
+use crate::{Status, StatusCode};
+use ckb_logger::debug_target;
+use ckb_network::PeerIndex;
+use ckb_types::{packed, prelude::*};
+
+pub struct SendTxReconciliationProcess<'a> {
+    message: packed::SendTxReconciliationReader<'a>,
+    protocol: &'a TxReconciliation,
+    peer: PeerIndex,
+}
+
+impl<'a> SendTxReconciliationProcess<'a> {
+    pub fn new(
+        message: packed::SendTxReconciliationReader<'a>,
+        protocol: &'a TxReconciliation,
+        peer: PeerIndex,
+    ) -> Self {
+        SendTxReconciliationProcess {
+            message,
+            protocol,
+            peer,
+        }
+    }
+
+    pub fn execute(self) -> Status {
+        let salt: u64 = self.message.salt().unpack();
+        if !self
+            .protocol
+            .shared
+            .state()
+            .tx_reconciliation()
+            .received_salt(self.peer, salt)
+        {
+            return StatusCode::Ignored.with_context("the salt is already announced");
+        }
+        debug_target!(
+            crate::LOG_TARGET_TX_RECONCILIATION,
+            "peer {} announces reconciliation salt",
+            self.peer
+        );
+        Status::ok()
+    }
+}
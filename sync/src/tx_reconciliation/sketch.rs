@@ -0,0 +1,306 @@
+//! PinSketch over GF(2^32), the set sketch exchanged by transaction reconciliation.
+//!
+//! A sketch of capacity `c` keeps the odd power sums `x^1, x^3, ..., x^(2c-1)` of the elements.
+//! Merging two sketches by xor gets the sketch of the symmetric difference of both sets, which
+//! can be decoded as long as the difference has no more than `c` elements.
+//!
+//! See [minisketch](https://github.com/sipa/minisketch) for the details.
+
+// x^32 + x^7 + x^3 + x^2 + 1
+const MODULUS: u64 = (1 << 32) | 0x8d;
+// attempts of splitting a polynomial by random trace maps, each one succeeds with probability 1/2
+const MAX_SPLIT_ATTEMPTS: usize = 64;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Sketch {
+    // the odd power sums of the elements
+    syndromes: Vec<u32>,
+}
+
+impl Sketch {
+    pub fn new(capacity: usize) -> Self {
+        Sketch {
+            syndromes: vec![0; capacity],
+        }
+    }
+
+    /// Max count of differences the sketch is able to decode
+    pub fn capacity(&self) -> usize {
+        self.syndromes.len()
+    }
+
+    /// Add an element, adding the same element again removes it. Zero is not a valid element.
+    pub fn add(&mut self, element: u32) {
+        debug_assert_ne!(element, 0);
+        let square = mul(element, element);
+        let mut power = element;
+        for syndrome in self.syndromes.iter_mut() {
+            *syndrome ^= power;
+            power = mul(power, square);
+        }
+    }
+
+    /// Merge into the sketch of symmetric difference, the capacity shrinks to the smaller one
+    pub fn merge(&mut self, other: &Sketch) {
+        self.syndromes.truncate(other.capacity());
+        for (syndrome, other) in self.syndromes.iter_mut().zip(other.syndromes.iter()) {
+            *syndrome ^= other;
+        }
+    }
+
+    pub fn serialize(&self) -> Vec<u8> {
+        self.syndromes
+            .iter()
+            .flat_map(|syndrome| syndrome.to_le_bytes())
+            .collect()
+    }
+
+    pub fn deserialize(bytes: &[u8]) -> Option<Self> {
+        if bytes.is_empty() || bytes.len() % 4 != 0 {
+            return None;
+        }
+        let syndromes = bytes
+            .chunks_exact(4)
+            .map(|chunk| u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+            .collect();
+        Some(Sketch { syndromes })
+    }
+
+    /// Recover the elements, returns None if there are more elements than the capacity
+    pub fn decode(&self) -> Option<Vec<u32>> {
+        let capacity = self.capacity();
+        // the power sums of all exponents, S(2k) = S(k)^2 in characteristic 2
+        let mut sums = Vec::with_capacity(capacity * 2);
+        for i in 0..capacity * 2 {
+            let sum = if i % 2 == 0 {
+                self.syndromes[i / 2]
+            } else {
+                let half: u32 = sums[i / 2];
+                mul(half, half)
+            };
+            sums.push(sum);
+        }
+
+        let locator = berlekamp_massey(&sums);
+        let degree = locator.len() - 1;
+        if degree == 0 {
+            return Some(Vec::new());
+        }
+        if degree > capacity {
+            return None;
+        }
+        // the roots of the reversed locator polynomial are the elements
+        let poly: Vec<u32> = locator.into_iter().rev().collect();
+        if poly[0] == 0 || !has_distinct_roots(&poly) {
+            return None;
+        }
+        let mut roots = Vec::with_capacity(degree);
+        let mut seed = 1;
+        if !find_roots(poly, &mut roots, &mut seed) || roots.len() != degree {
+            return None;
+        }
+
+        // more elements than the capacity may still produce a locator, double check it
+        let mut sketch = Sketch::new(capacity);
+        for root in &roots {
+            sketch.add(*root);
+        }
+        if sketch != *self {
+            return None;
+        }
+        Some(roots)
+    }
+}
+
+fn mul(a: u32, b: u32) -> u32 {
+    let (a, b) = (u64::from(a), u64::from(b));
+    let mut product = 0u64;
+    for i in 0..32 {
+        if (b >> i) & 1 == 1 {
+            product ^= a << i;
+        }
+    }
+    for i in (32..64).rev() {
+        if (product >> i) & 1 == 1 {
+            product ^= MODULUS << (i - 32);
+        }
+    }
+    product as u32
+}
+
+fn inv(a: u32) -> u32 {
+    // the multiplicative group has order 2^32 - 1, so a^(2^32 - 2) is the inverse
+    let mut result = 1;
+    let mut base = a;
+    let mut exp = u32::MAX - 1;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul(result, base);
+        }
+        base = mul(base, base);
+        exp >>= 1;
+    }
+    result
+}
+
+// The connection polynomial of the shortest linear recurrence generating the sequence, the
+// lowest coefficient comes first.
+fn berlekamp_massey(sequence: &[u32]) -> Vec<u32> {
+    let mut current = vec![1u32];
+    let mut previous = vec![1u32];
+    let mut length = 0;
+    let mut shift = 1;
+    let mut previous_discrepancy = 1u32;
+
+    for (n, element) in sequence.iter().enumerate() {
+        let mut discrepancy = *element;
+        for (i, coefficient) in current.iter().enumerate().take(length + 1).skip(1) {
+            discrepancy ^= mul(*coefficient, sequence[n - i]);
+        }
+        if discrepancy == 0 {
+            shift += 1;
+            continue;
+        }
+
+        let factor = mul(discrepancy, inv(previous_discrepancy));
+        let last = current.clone();
+        if current.len() < previous.len() + shift {
+            current.resize(previous.len() + shift, 0);
+        }
+        for (i, coefficient) in previous.iter().enumerate() {
+            current[i + shift] ^= mul(factor, *coefficient);
+        }
+        if 2 * length <= n {
+            length = n + 1 - length;
+            previous = last;
+            previous_discrepancy = discrepancy;
+            shift = 1;
+        } else {
+            shift += 1;
+        }
+    }
+
+    current.resize(length + 1, 0);
+    current
+}
+
+fn trim(poly: &mut Vec<u32>) {
+    while poly.last() == Some(&0) {
+        poly.pop();
+    }
+}
+
+// The remainder of `a` divided by the non-zero trimmed polynomial `b`
+fn rem(mut a: Vec<u32>, b: &[u32]) -> Vec<u32> {
+    let degree = b.len() - 1;
+    let lead_inv = inv(b[degree]);
+    trim(&mut a);
+    while a.len() > degree {
+        let top = a.len() - 1;
+        let factor = mul(a[top], lead_inv);
+        for (i, coefficient) in b.iter().enumerate() {
+            a[top - degree + i] ^= mul(factor, *coefficient);
+        }
+        trim(&mut a);
+    }
+    a
+}
+
+// The quotient of `a` divided by the non-zero trimmed polynomial `b`
+fn div(mut a: Vec<u32>, b: &[u32]) -> Vec<u32> {
+    let degree = b.len() - 1;
+    if a.len() <= degree {
+        return Vec::new();
+    }
+    let lead_inv = inv(b[degree]);
+    let mut quotient = vec![0; a.len() - degree];
+    for top in (degree..a.len()).rev() {
+        let factor = mul(a[top], lead_inv);
+        if factor == 0 {
+            continue;
+        }
+        quotient[top - degree] = factor;
+        for (i, coefficient) in b.iter().enumerate() {
+            a[top - degree + i] ^= mul(factor, *coefficient);
+        }
+    }
+    quotient
+}
+
+// The monic greatest common divisor
+fn gcd(mut a: Vec<u32>, mut b: Vec<u32>) -> Vec<u32> {
+    trim(&mut a);
+    trim(&mut b);
+    while !b.is_empty() {
+        let remainder = rem(a, &b);
+        a = b;
+        b = remainder;
+    }
+    let lead_inv = inv(a[a.len() - 1]);
+    a.iter()
+        .map(|coefficient| mul(*coefficient, lead_inv))
+        .collect()
+}
+
+// Squaring is linear in characteristic 2: (sum a_i x^i)^2 = sum a_i^2 x^2i
+fn square_rem(poly: &[u32], modulus: &[u32]) -> Vec<u32> {
+    let mut square = vec![0; poly.len() * 2];
+    for (i, coefficient) in poly.iter().enumerate() {
+        square[i * 2] = mul(*coefficient, *coefficient);
+    }
+    rem(square, modulus)
+}
+
+fn add(mut a: Vec<u32>, b: &[u32]) -> Vec<u32> {
+    if a.len() < b.len() {
+        a.resize(b.len(), 0);
+    }
+    for (i, coefficient) in b.iter().enumerate() {
+        a[i] ^= coefficient;
+    }
+    trim(&mut a);
+    a
+}
+
+// A polynomial has distinct roots all in GF(2^32) iff it divides x^(2^32) - x
+fn has_distinct_roots(poly: &[u32]) -> bool {
+    let x = rem(vec![0, 1], poly);
+    let mut power = x.clone();
+    for _ in 0..32 {
+        power = square_rem(&power, poly);
+    }
+    power == x
+}
+
+// Split the monic polynomial with distinct roots by the trace map Tr(βx), which is 0 on about
+// half of the roots and 1 on the others.
+fn find_roots(poly: Vec<u32>, roots: &mut Vec<u32>, seed: &mut u32) -> bool {
+    match poly.len() {
+        0 | 1 => return true,
+        2 => {
+            // x + c
+            roots.push(poly[0]);
+            return true;
+        }
+        _ => {}
+    }
+    for _ in 0..MAX_SPLIT_ATTEMPTS {
+        // xorshift, never produces zero from a non-zero seed
+        *seed ^= *seed << 13;
+        *seed ^= *seed >> 17;
+        *seed ^= *seed << 5;
+
+        let mut power = rem(vec![0, *seed], &poly);
+        let mut trace = power.clone();
+        for _ in 1..32 {
+            power = square_rem(&power, &poly);
+            trace = add(trace, &power);
+        }
+        let factor = gcd(poly.clone(), trace);
+        if factor.len() > 1 && factor.len() < poly.len() {
+            let cofactor = div(poly, &factor);
+            return find_roots(factor, roots, seed) && find_roots(cofactor, roots, seed);
+        }
+    }
+    false
+}
@@ -0,0 +1,269 @@
+//! Per-peer state of transaction reconciliation.
+//!
+//! Instead of flooding, the transactions to announce to a peer are collected into its
+//! reconciliation set. The side which opened the connection periodically requests a sketch of the
+//! peer's set, decodes the difference against its own set, announces what the peer lacks and asks
+//! the peer to announce what it lacks. When the difference can't be decoded, both sides fall back
+//! to flooding their whole sets.
+use super::sketch::Sketch;
+use ckb_network::PeerIndex;
+use ckb_types::{packed::Byte32, prelude::*};
+use siphasher::sip::SipHasher24;
+use std::collections::HashMap;
+use std::hash::Hasher;
+use std::time::{Duration, Instant};
+
+/// Max count of transactions waiting for reconciliation with a peer, the later ones are flooded
+pub(crate) const MAX_RECONCILIATION_SET_SIZE: usize = 3000;
+/// Max capacity of the sketches, it bounds the cost of decoding
+pub(crate) const MAX_SKETCH_CAPACITY: usize = 128;
+/// A reconciliation round not finished in time is given up and its transactions are flooded
+pub(crate) const RECONCILIATION_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug)]
+enum Round {
+    // no round since the instant, a responder floods its set if the initiator doesn't request
+    Idle(Instant),
+    // the initiator requested a sketch
+    Requested(Instant),
+    // the responder sent the sketch of the snapshot, waiting for the difference
+    Sketched(Instant, HashMap<u32, Byte32>),
+}
+
+#[derive(Debug)]
+struct PeerState {
+    local_salt: u64,
+    remote_salt: Option<u64>,
+    // whether we request the reconciliations, the side which opened the connection does
+    initiator: bool,
+    // transactions to announce to the peer, by short ids
+    set: HashMap<u32, Byte32>,
+    round: Round,
+}
+
+impl PeerState {
+    fn short_id(&self, tx_hash: &Byte32) -> Option<u32> {
+        self.remote_salt.map(|remote_salt| {
+            let mut hasher = SipHasher24::new_with_keys(
+                self.local_salt.min(remote_salt),
+                self.local_salt.max(remote_salt),
+            );
+            hasher.write(tx_hash.as_slice());
+            // zero is not a valid sketch element
+            (hasher.finish() as u32).max(1)
+        })
+    }
+}
+
+/// The result of decoding the difference by the initiator
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct Reconciled {
+    /// Whether the difference was decoded, or all the transactions are to be flooded
+    pub success: bool,
+    /// Transactions the peer lacks
+    pub announce: Vec<Byte32>,
+    /// Short ids of transactions we lack
+    pub missing: Vec<u32>,
+}
+
+#[derive(Default, Debug)]
+pub(crate) struct TxReconciliationState {
+    peers: HashMap<PeerIndex, PeerState>,
+}
+
+impl TxReconciliationState {
+    /// Register a peer with the local salt announced to it
+    pub fn connected(&mut self, peer: PeerIndex, local_salt: u64, initiator: bool, now: Instant) {
+        self.peers.insert(
+            peer,
+            PeerState {
+                local_salt,
+                remote_salt: None,
+                initiator,
+                set: HashMap::default(),
+                round: Round::Idle(now),
+            },
+        );
+    }
+
+    pub fn disconnected(&mut self, peer: PeerIndex) {
+        self.peers.remove(&peer);
+    }
+
+    /// Handle `SendTxReconciliation`, returns false if the peer is unknown or announced twice
+    pub fn received_salt(&mut self, peer: PeerIndex, salt: u64) -> bool {
+        match self.peers.get_mut(&peer) {
+            Some(state) if state.remote_salt.is_none() => {
+                state.remote_salt = Some(salt);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Add a transaction to announce to the peer by reconciliation.
+    ///
+    /// Returns false if the transaction should be flooded to the peer instead, that is, the peer
+    /// doesn't reconcile with us, its set is full or the short id collides.
+    pub fn add_tx(&mut self, peer: PeerIndex, tx_hash: &Byte32) -> bool {
+        let state = match self.peers.get_mut(&peer) {
+            Some(state) if state.set.len() < MAX_RECONCILIATION_SET_SIZE => state,
+            _ => return false,
+        };
+        match state.short_id(tx_hash) {
+            Some(short_id) => match state.set.get(&short_id) {
+                Some(hash) => hash == tx_hash,
+                None => {
+                    state.set.insert(short_id, tx_hash.clone());
+                    true
+                }
+            },
+            None => false,
+        }
+    }
+
+    /// Start a round with every idle peer we initiate reconciliations with, returns the peers and
+    /// the sizes of the local sets to request the sketches.
+    pub fn start_rounds(&mut self, now: Instant) -> Vec<(PeerIndex, u32)> {
+        self.peers
+            .iter_mut()
+            .filter(|(_, state)| {
+                state.initiator
+                    && state.remote_salt.is_some()
+                    && matches!(state.round, Round::Idle(_))
+            })
+            .map(|(peer, state)| {
+                state.round = Round::Requested(now);
+                (*peer, state.set.len() as u32)
+            })
+            .collect()
+    }
+
+    /// Handle `RequestTxReconciliation` as the responder, snapshot the set and sketch it.
+    ///
+    /// Returns None if the peer isn't expected to request now.
+    pub fn sketch(
+        &mut self,
+        peer: PeerIndex,
+        remote_set_size: u32,
+        now: Instant,
+    ) -> Option<Sketch> {
+        let state = self.peers.get_mut(&peer)?;
+        if state.initiator || state.remote_salt.is_none() || !matches!(state.round, Round::Idle(_))
+        {
+            return None;
+        }
+        let snapshot = std::mem::take(&mut state.set);
+        let mut sketch = Sketch::new(estimate_capacity(snapshot.len(), remote_set_size as usize));
+        for short_id in snapshot.keys() {
+            sketch.add(*short_id);
+        }
+        state.round = Round::Sketched(now, snapshot);
+        Some(sketch)
+    }
+
+    /// Handle `TxReconciliationSketch` as the initiator, decode the difference with the local set.
+    ///
+    /// Returns None if the peer isn't expected to send a sketch now.
+    pub fn reconcile(
+        &mut self,
+        peer: PeerIndex,
+        remote: &Sketch,
+        now: Instant,
+    ) -> Option<Reconciled> {
+        let state = self.peers.get_mut(&peer)?;
+        if !matches!(state.round, Round::Requested(_)) {
+            return None;
+        }
+        state.round = Round::Idle(now);
+        let set = std::mem::take(&mut state.set);
+
+        let mut sketch = Sketch::new(remote.capacity());
+        for short_id in set.keys() {
+            sketch.add(*short_id);
+        }
+        sketch.merge(remote);
+        let reconciled = match sketch.decode() {
+            Some(difference) => {
+                let (announce, missing) = difference
+                    .into_iter()
+                    .partition::<Vec<_>, _>(|short_id| set.contains_key(short_id));
+                Reconciled {
+                    success: true,
+                    announce: announce
+                        .iter()
+                        .map(|short_id| set[short_id].clone())
+                        .collect(),
+                    missing,
+                }
+            }
+            None => Reconciled {
+                success: false,
+                announce: set.into_values().collect(),
+                missing: Vec::new(),
+            },
+        };
+        Some(reconciled)
+    }
+
+    /// Handle `TxReconciliationDifference` as the responder, returns the transactions to announce
+    ///
+    /// Returns None if the peer isn't expected to send the difference now.
+    pub fn finish(
+        &mut self,
+        peer: PeerIndex,
+        success: bool,
+        missing: &[u32],
+        now: Instant,
+    ) -> Option<Vec<Byte32>> {
+        let state = self.peers.get_mut(&peer)?;
+        if !matches!(state.round, Round::Sketched(..)) {
+            return None;
+        }
+        let mut snapshot = match std::mem::replace(&mut state.round, Round::Idle(now)) {
+            Round::Sketched(_, snapshot) => snapshot,
+            _ => unreachable!(),
+        };
+        if success {
+            Some(
+                missing
+                    .iter()
+                    .filter_map(|short_id| snapshot.remove(short_id))
+                    .collect(),
+            )
+        } else {
+            Some(snapshot.into_values().collect())
+        }
+    }
+
+    /// Give up the rounds timed out, also the sets of responders which haven't been requested for
+    /// a while, returns the transactions to flood.
+    pub fn expire(&mut self, now: Instant) -> Vec<(PeerIndex, Vec<Byte32>)> {
+        let mut expired = Vec::new();
+        for (peer, state) in self.peers.iter_mut() {
+            let since = match state.round {
+                Round::Idle(_) if state.initiator => continue,
+                Round::Idle(since) | Round::Requested(since) | Round::Sketched(since, _) => since,
+            };
+            if now.saturating_duration_since(since) < RECONCILIATION_TIMEOUT {
+                continue;
+            }
+            let mut hashes: Vec<Byte32> = std::mem::take(&mut state.set).into_values().collect();
+            if let Round::Sketched(_, snapshot) =
+                std::mem::replace(&mut state.round, Round::Idle(now))
+            {
+                hashes.extend(snapshot.into_values());
+            }
+            if !hashes.is_empty() {
+                expired.push((*peer, hashes));
+            }
+        }
+        expired
+    }
+}
+
+/// The capacity of the sketch to reconcile sets of the sizes, that is, the expected size of the
+/// difference, `|local - remote| + q * min(local, remote) + 1` with `q = 0.25`.
+pub(crate) fn estimate_capacity(local: usize, remote: usize) -> usize {
+    (local.abs_diff(remote) + local.min(remote) / 4 + 1).min(MAX_SKETCH_CAPACITY)
+}
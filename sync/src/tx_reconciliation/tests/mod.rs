@@ -0,0 +1,2 @@
+mod sketch;
+mod state;
@@ -0,0 +1,57 @@
+use crate::tx_reconciliation::sketch::Sketch;
+
+fn sketch_of(capacity: usize, elements: &[u32]) -> Sketch {
+    let mut sketch = Sketch::new(capacity);
+    for element in elements {
+        sketch.add(*element);
+    }
+    sketch
+}
+
+#[test]
+fn test_decode_difference() {
+    let shared: Vec<u32> = (1..=200).map(|i| i * 7919).collect();
+    let local: Vec<u32> = shared.iter().copied().chain([11, 22, 33]).collect();
+    let remote: Vec<u32> = shared.iter().copied().chain([44, 55]).collect();
+
+    let mut sketch = sketch_of(8, &local);
+    sketch.merge(&sketch_of(8, &remote));
+    let mut difference = sketch.decode().expect("decode");
+    difference.sort_unstable();
+    assert_eq!(difference, vec![11, 22, 33, 44, 55]);
+}
+
+#[test]
+fn test_decode_empty_difference() {
+    let elements = [1, 2, u32::MAX];
+    let mut sketch = sketch_of(4, &elements);
+    sketch.merge(&sketch_of(4, &elements));
+    assert_eq!(sketch.decode(), Some(Vec::new()));
+}
+
+#[test]
+fn test_decode_overflow() {
+    let elements: Vec<u32> = (1..=10).collect();
+    let sketch = sketch_of(4, &elements);
+    assert_eq!(sketch.decode(), None);
+}
+
+#[test]
+fn test_merge_shrinks_capacity() {
+    let mut sketch = sketch_of(8, &[1, 2]);
+    sketch.merge(&sketch_of(3, &[3]));
+    assert_eq!(sketch.capacity(), 3);
+    let mut difference = sketch.decode().expect("decode");
+    difference.sort_unstable();
+    assert_eq!(difference, vec![1, 2, 3]);
+}
+
+#[test]
+fn test_serialize() {
+    let sketch = sketch_of(3, &[5, 6]);
+    let bytes = sketch.serialize();
+    assert_eq!(bytes.len(), 12);
+    assert_eq!(Sketch::deserialize(&bytes), Some(sketch));
+    assert_eq!(Sketch::deserialize(&[]), None);
+    assert_eq!(Sketch::deserialize(&bytes[..5]), None);
+}
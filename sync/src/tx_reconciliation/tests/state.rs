@@ -0,0 +1,148 @@
+use crate::tx_reconciliation::state::{
+    estimate_capacity, TxReconciliationState, MAX_SKETCH_CAPACITY, RECONCILIATION_TIMEOUT,
+};
+use ckb_network::PeerIndex;
+use ckb_types::{h256, packed::Byte32, prelude::*, H256};
+use std::time::Instant;
+
+fn tx_hash(i: u64) -> Byte32 {
+    let mut hash = H256::default();
+    hash.0[..8].copy_from_slice(&i.to_le_bytes());
+    hash.pack()
+}
+
+#[test]
+fn test_add_tx_without_reconciliation() {
+    let mut state = TxReconciliationState::default();
+    let peer: PeerIndex = 1.into();
+    let hash = h256!("0x1").pack();
+
+    // the peer doesn't open the protocol
+    assert!(!state.add_tx(peer, &hash));
+
+    // the peer hasn't announced its salt yet
+    state.connected(peer, 1, true, Instant::now());
+    assert!(!state.add_tx(peer, &hash));
+
+    assert!(state.received_salt(peer, 2));
+    assert!(!state.received_salt(peer, 3));
+    assert!(state.add_tx(peer, &hash));
+    // adding twice is fine
+    assert!(state.add_tx(peer, &hash));
+
+    state.disconnected(peer);
+    assert!(!state.add_tx(peer, &hash));
+}
+
+#[test]
+fn test_reconcile_round() {
+    let now = Instant::now();
+    let initiator_peer: PeerIndex = 1.into();
+    let responder_peer: PeerIndex = 2.into();
+
+    // the initiator side talks to `initiator_peer`, the responder side to `responder_peer`
+    let mut initiator = TxReconciliationState::default();
+    initiator.connected(initiator_peer, 1, true, now);
+    initiator.received_salt(initiator_peer, 2);
+    let mut responder = TxReconciliationState::default();
+    responder.connected(responder_peer, 2, false, now);
+    responder.received_salt(responder_peer, 1);
+
+    for i in 0..100 {
+        assert!(initiator.add_tx(initiator_peer, &tx_hash(i)));
+        assert!(responder.add_tx(responder_peer, &tx_hash(i)));
+    }
+    assert!(initiator.add_tx(initiator_peer, &tx_hash(1000)));
+    assert!(responder.add_tx(responder_peer, &tx_hash(2000)));
+    assert!(responder.add_tx(responder_peer, &tx_hash(2001)));
+
+    // the responder never requests
+    assert!(responder.start_rounds(now).is_empty());
+    assert_eq!(initiator.start_rounds(now), vec![(initiator_peer, 101)]);
+    assert!(initiator.start_rounds(now).is_empty());
+
+    assert!(initiator.sketch(initiator_peer, 0, now).is_none());
+    let sketch = responder.sketch(responder_peer, 101, now).expect("sketch");
+    assert!(responder.sketch(responder_peer, 101, now).is_none());
+
+    let reconciled = initiator
+        .reconcile(initiator_peer, &sketch, now)
+        .expect("reconcile");
+    assert!(reconciled.success);
+    assert_eq!(reconciled.announce, vec![tx_hash(1000)]);
+    assert_eq!(reconciled.missing.len(), 2);
+    assert!(initiator.reconcile(initiator_peer, &sketch, now).is_none());
+
+    let mut announce = responder
+        .finish(responder_peer, true, &reconciled.missing, now)
+        .expect("finish");
+    announce.sort_by_key(|hash| hash.as_slice().to_vec());
+    let mut expected = vec![tx_hash(2000), tx_hash(2001)];
+    expected.sort_by_key(|hash| hash.as_slice().to_vec());
+    assert_eq!(announce, expected);
+    assert!(responder
+        .finish(responder_peer, true, &reconciled.missing, now)
+        .is_none());
+}
+
+#[test]
+fn test_reconcile_failure_floods() {
+    let now = Instant::now();
+    let peer: PeerIndex = 1.into();
+    let mut initiator = TxReconciliationState::default();
+    initiator.connected(peer, 1, true, now);
+    initiator.received_salt(peer, 2);
+    for i in 0..10 {
+        initiator.add_tx(peer, &tx_hash(i));
+    }
+    initiator.start_rounds(now);
+
+    // the responder has an empty set, so the sketch of capacity 1 can't decode 10 differences
+    let mut responder = TxReconciliationState::default();
+    responder.connected(peer, 2, false, now);
+    responder.received_salt(peer, 1);
+    let sketch = responder.sketch(peer, 0, now).expect("sketch");
+    assert_eq!(sketch.capacity(), 1);
+
+    let reconciled = initiator.reconcile(peer, &sketch, now).expect("reconcile");
+    assert!(!reconciled.success);
+    assert_eq!(reconciled.announce.len(), 10);
+    assert!(reconciled.missing.is_empty());
+}
+
+#[test]
+fn test_expire() {
+    let now = Instant::now();
+    let later = now + RECONCILIATION_TIMEOUT;
+    let initiator_peer: PeerIndex = 1.into();
+    let responder_peer: PeerIndex = 2.into();
+    let mut state = TxReconciliationState::default();
+    state.connected(initiator_peer, 1, true, now);
+    state.received_salt(initiator_peer, 2);
+    state.connected(responder_peer, 1, false, now);
+    state.received_salt(responder_peer, 2);
+    state.add_tx(initiator_peer, &tx_hash(1));
+    state.add_tx(responder_peer, &tx_hash(2));
+
+    // an idle initiator keeps its set until it requests
+    assert!(state.expire(now).is_empty());
+    assert_eq!(
+        state.expire(later),
+        vec![(responder_peer, vec![tx_hash(2)])]
+    );
+
+    state.start_rounds(later);
+    assert!(state.expire(later).is_empty());
+    assert_eq!(
+        state.expire(later + RECONCILIATION_TIMEOUT),
+        vec![(initiator_peer, vec![tx_hash(1)])]
+    );
+}
+
+#[test]
+fn test_estimate_capacity() {
+    assert_eq!(estimate_capacity(0, 0), 1);
+    assert_eq!(estimate_capacity(10, 0), 11);
+    assert_eq!(estimate_capacity(100, 100), 26);
+    assert_eq!(estimate_capacity(10000, 0), MAX_SKETCH_CAPACITY);
+}
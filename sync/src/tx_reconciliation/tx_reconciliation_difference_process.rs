@@ -0,0 +1,54 @@
+use crate::tx_reconciliation::TxReconciliation;
+use crate::{Status, StatusCode};
+use ckb_network::{CKBProtocolContext, PeerIndex};
+use ckb_types::{packed, prelude::*};
+use std::sync::Arc;
+use std::time::Instant;
+
+pub struct TxReconciliationDifferenceProcess<'a> {
+    message: packed::TxReconciliationDifferenceReader<'a>,
+    protocol: &'a TxReconciliation,
+    nc: Arc<dyn CKBProtocolContext>,
+    peer: PeerIndex,
+}
+
+impl<'a> TxReconciliationDifferenceProcess<'a> {
+    pub fn new(
+        message: packed::TxReconciliationDifferenceReader<'a>,
+        protocol: &'a TxReconciliation,
+        nc: Arc<dyn CKBProtocolContext>,
+        peer: PeerIndex,
+    ) -> Self {
+        TxReconciliationDifferenceProcess {
+            message,
+            protocol,
+            nc,
+            peer,
+        }
+    }
+
+    pub fn execute(self) -> Status {
+        let success: bool = self.message.success().unpack();
+        let missing: Vec<u32> = self.message.missing().unpack();
+        let tx_hashes = match self.protocol.shared.state().tx_reconciliation().finish(
+            self.peer,
+            success,
+            &missing,
+            Instant::now(),
+        ) {
+            Some(tx_hashes) => tx_hashes,
+            None => {
+                return StatusCode::Ignored.with_context("unexpected reconciliation difference");
+            }
+        };
+        if let Some(metrics) = ckb_metrics::handle() {
+            if success {
+                metrics.ckb_tx_reconciliation.success.inc();
+            } else {
+                metrics.ckb_tx_reconciliation.failure.inc();
+            }
+        }
+        self.protocol
+            .announce(self.nc.as_ref(), self.peer, tx_hashes)
+    }
+}
@@ -0,0 +1,75 @@
+use crate::tx_reconciliation::{sketch::Sketch, state::MAX_SKETCH_CAPACITY, TxReconciliation};
+use crate::{attempt, Status, StatusCode};
+use ckb_logger::debug_target;
+use ckb_network::{CKBProtocolContext, PeerIndex};
+use ckb_types::{packed, prelude::*};
+use std::sync::Arc;
+use std::time::Instant;
+
+pub struct TxReconciliationSketchProcess<'a> {
+    message: packed::TxReconciliationSketchReader<'a>,
+    protocol: &'a TxReconciliation,
+    nc: Arc<dyn CKBProtocolContext>,
+    peer: PeerIndex,
+}
+
+impl<'a> TxReconciliationSketchProcess<'a> {
+    pub fn new(
+        message: packed::TxReconciliationSketchReader<'a>,
+        protocol: &'a TxReconciliation,
+        nc: Arc<dyn CKBProtocolContext>,
+        peer: PeerIndex,
+    ) -> Self {
+        TxReconciliationSketchProcess {
+            message,
+            protocol,
+            nc,
+            peer,
+        }
+    }
+
+    pub fn execute(self) -> Status {
+        let remote = match Sketch::deserialize(self.message.sketch().raw_data()) {
+            Some(sketch) if sketch.capacity() <= MAX_SKETCH_CAPACITY => sketch,
+            _ => {
+                return StatusCode::ProtocolMessageIsMalformed
+                    .with_context("invalid reconciliation sketch");
+            }
+        };
+        let reconciled = match self.protocol.shared.state().tx_reconciliation().reconcile(
+            self.peer,
+            &remote,
+            Instant::now(),
+        ) {
+            Some(reconciled) => reconciled,
+            None => {
+                return StatusCode::Ignored.with_context("unexpected reconciliation sketch");
+            }
+        };
+
+        debug_target!(
+            crate::LOG_TARGET_TX_RECONCILIATION,
+            "reconcile with peer {}, success: {}, announce: {}, missing: {}",
+            self.peer,
+            reconciled.success,
+            reconciled.announce.len(),
+            reconciled.missing.len()
+        );
+        if let Some(metrics) = ckb_metrics::handle() {
+            if reconciled.success {
+                metrics.ckb_tx_reconciliation.success.inc();
+            } else {
+                metrics.ckb_tx_reconciliation.failure.inc();
+            }
+        }
+
+        attempt!(self
+            .protocol
+            .announce(self.nc.as_ref(), self.peer, reconciled.announce));
+        let content = packed::TxReconciliationDifference::new_builder()
+            .success(reconciled.success.pack())
+            .missing(reconciled.missing.pack())
+            .build();
+        self.protocol.send_to(self.nc.as_ref(), self.peer, content)
+    }
+}
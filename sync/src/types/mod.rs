@@ -1,5 +1,6 @@
 use crate::block_status::BlockStatus;
 use crate::orphan_block_pool::OrphanBlockPool;
+use crate::tx_reconciliation::TxReconciliationState;
 use crate::utils::is_internal_db_error;
 use crate::{Status, StatusCode, FAST_INDEX, LOW_INDEX, NORMAL_INDEX, TIME_TRACE_SIZE};
 use ckb_app_config::SyncConfig;
@@ -7,11 +8,12 @@ use ckb_chain::chain::ChainController;
 use ckb_chain_spec::consensus::{Consensus, MAX_BLOCK_INTERVAL, MIN_BLOCK_INTERVAL};
 use ckb_channel::Receiver;
 use ckb_constant::sync::{
-    BLOCK_DOWNLOAD_TIMEOUT, HEADERS_DOWNLOAD_HEADERS_PER_SECOND, HEADERS_DOWNLOAD_INSPECT_WINDOW,
-    HEADERS_DOWNLOAD_TOLERABLE_BIAS_FOR_SINGLE_SAMPLE, INIT_BLOCKS_IN_TRANSIT_PER_PEER,
-    MAX_BLOCKS_IN_TRANSIT_PER_PEER, MAX_HEADERS_LEN, MAX_OUTBOUND_PEERS_TO_PROTECT_FROM_DISCONNECT,
-    MAX_UNKNOWN_TX_HASHES_SIZE, MAX_UNKNOWN_TX_HASHES_SIZE_PER_PEER, POW_INTERVAL,
-    RETRY_ASK_TX_TIMEOUT_INCREASE, SUSPEND_SYNC_TIME,
+    BLOCK_DOWNLOAD_FRONT_WINDOW, BLOCK_DOWNLOAD_TIMEOUT, HEADERS_DOWNLOAD_HEADERS_PER_SECOND,
+    HEADERS_DOWNLOAD_INSPECT_WINDOW, HEADERS_DOWNLOAD_TOLERABLE_BIAS_FOR_SINGLE_SAMPLE,
+    INIT_BLOCKS_IN_TRANSIT_PER_PEER, MAX_BLOCKS_IN_TRANSIT_PER_PEER, MAX_HEADERS_LEN,
+    MAX_OUTBOUND_PEERS_TO_PROTECT_FROM_DISCONNECT, MAX_UNKNOWN_TX_HASHES_SIZE,
+    MAX_UNKNOWN_TX_HASHES_SIZE_PER_PEER, POW_INTERVAL, RETRY_ASK_TX_TIMEOUT_INCREASE,
+    SUSPEND_SYNC_TIME,
 };
 use ckb_error::Error as CKBError;
 use ckb_logger::{debug, error, trace};
@@ -25,6 +27,7 @@ use ckb_types::{
     core::{self, BlockNumber, EpochExt},
     packed::{self, Byte32},
     prelude::*,
+    utilities::ShortIdKey,
     H256, U256,
 };
 use ckb_util::{shrink_to_fit, Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
@@ -135,6 +138,7 @@ pub struct PeerFlags {
     pub is_protect: bool,
     pub is_whitelist: bool,
     pub is_2023edition: bool,
+    pub is_pruned: bool,
 }
 
 #[derive(Clone, Default, Debug, Copy)]
@@ -534,6 +538,12 @@ pub struct DownloadScheduler {
     task_count: usize,
     timeout_count: usize,
     hashes: HashSet<BlockNumberAndHash>,
+    /// Count of blocks received from this peer
+    received_count: u64,
+    /// Count of blocks which timed out or were re-assigned to other peers
+    stalled_count: u64,
+    /// Moving average of the block response time, unit ms
+    avg_response_time: u64,
 }
 
 impl Default for DownloadScheduler {
@@ -542,12 +552,15 @@ impl Default for DownloadScheduler {
             hashes: HashSet::default(),
             task_count: INIT_BLOCKS_IN_TRANSIT_PER_PEER,
             timeout_count: 0,
+            received_count: 0,
+            stalled_count: 0,
+            avg_response_time: 0,
         }
     }
 }
 
 impl DownloadScheduler {
-    fn inflight_count(&self) -> usize {
+    pub fn inflight_count(&self) -> usize {
         self.hashes.len()
     }
 
@@ -555,10 +568,39 @@ impl DownloadScheduler {
         self.task_count.saturating_sub(self.hashes.len())
     }
 
-    pub(crate) const fn task_count(&self) -> usize {
+    pub const fn task_count(&self) -> usize {
         self.task_count
     }
 
+    pub const fn received_count(&self) -> u64 {
+        self.received_count
+    }
+
+    pub const fn stalled_count(&self) -> u64 {
+        self.stalled_count
+    }
+
+    /// The moving average of the block response time, `None` if no block has been received yet.
+    pub fn avg_response_time(&self) -> Option<u64> {
+        (self.received_count > 0).then_some(self.avg_response_time)
+    }
+
+    /// The measured throughput in blocks per second, estimated by the concurrent task count and the
+    /// average response time.
+    pub fn throughput(&self) -> Option<u64> {
+        self.avg_response_time()
+            .map(|time| (self.task_count as u64).saturating_mul(1000) / cmp::max(time, 1))
+    }
+
+    fn record_response(&mut self, elapsed: u64) {
+        self.avg_response_time = if self.received_count == 0 {
+            elapsed
+        } else {
+            (self.avg_response_time.saturating_mul(7)).saturating_add(elapsed) >> 3
+        };
+        self.received_count += 1;
+    }
+
     fn increase(&mut self, num: usize) {
         if self.task_count < MAX_BLOCKS_IN_TRANSIT_PER_PEER {
             self.task_count = ::std::cmp::min(
@@ -675,6 +717,105 @@ impl InflightBlocks {
         self.inflight_states.get(block)
     }
 
+    pub fn download_schedulers_iter(
+        &self,
+    ) -> impl Iterator<Item = (&PeerIndex, &DownloadScheduler)> {
+        self.download_schedulers.iter()
+    }
+
+    /// Whether the measured throughput of the peer is less than half of the average of all the
+    /// measured peers.
+    pub fn is_slow_peer(&self, peer: PeerIndex) -> bool {
+        let throughput = match self
+            .download_schedulers
+            .get(&peer)
+            .and_then(DownloadScheduler::throughput)
+        {
+            Some(throughput) => throughput,
+            None => return false,
+        };
+        let (sum, count) = self
+            .download_schedulers
+            .values()
+            .filter_map(DownloadScheduler::throughput)
+            .fold((0u64, 0u64), |(sum, count), throughput| {
+                (sum.saturating_add(throughput), count + 1)
+            });
+        count > 1 && throughput.saturating_mul(2).saturating_mul(count) < sum
+    }
+
+    /// Re-assigns to `peer` the stalled downloads of the blocks in the front window after the tip,
+    /// which are inflight from slower peers. Returns the re-assigned blocks, at most `limit`.
+    ///
+    /// A download is stalled when it lasts longer than twice of the low time of the time analyzer.
+    pub fn reassign_stalled<F>(
+        &mut self,
+        peer: PeerIndex,
+        tip: BlockNumber,
+        limit: usize,
+        has_block: F,
+    ) -> Vec<BlockNumberAndHash>
+    where
+        F: Fn(&BlockNumberAndHash) -> bool,
+    {
+        let now = unix_time_as_millis();
+        let stall_time = self.time_analyzer.low_time.saturating_mul(2);
+        let should_punish = self.download_schedulers.len() > self.protect_num;
+        let adjustment = self.adjustment;
+        let throughput = self
+            .download_schedulers
+            .get(&peer)
+            .and_then(DownloadScheduler::throughput);
+
+        let download_schedulers = &mut self.download_schedulers;
+        let trace = &mut self.trace_number;
+        let mut reassigned = Vec::new();
+        for (key, state) in self.inflight_states.iter_mut() {
+            if reassigned.len() >= limit || key.number > tip + BLOCK_DOWNLOAD_FRONT_WINDOW {
+                break;
+            }
+            if state.peer == peer || now.saturating_sub(state.timestamp) <= stall_time {
+                continue;
+            }
+            let stalled_throughput = download_schedulers
+                .get(&state.peer)
+                .and_then(DownloadScheduler::throughput);
+            // an unmeasured peer never takes over the download from a measured one
+            let faster = stalled_throughput.map_or(true, |stalled| {
+                throughput.map_or(false, |throughput| throughput > stalled)
+            });
+            if !faster || !has_block(key) {
+                continue;
+            }
+
+            if let Some(d) = download_schedulers.get_mut(&state.peer) {
+                d.hashes.remove(key);
+                d.stalled_count += 1;
+                if should_punish && adjustment {
+                    d.punish(1);
+                }
+            }
+            if !trace.is_empty() {
+                trace.remove(key);
+            }
+            debug!(
+                "Re-assign the stalled download of block {} from peer {} to peer {}",
+                key.number, state.peer, peer
+            );
+            *state = InflightState::new(peer);
+            reassigned.push(key.clone());
+        }
+
+        if !reassigned.is_empty() {
+            download_schedulers
+                .entry(peer)
+                .or_insert_with(DownloadScheduler::default)
+                .hashes
+                .extend(reassigned.iter().cloned());
+        }
+        reassigned
+    }
+
     pub fn mark_slow_block(&mut self, tip: BlockNumber) {
         let now = ckb_systemtime::unix_time_as_millis();
         for key in self.inflight_states.keys() {
@@ -716,6 +857,7 @@ impl InflightBlocks {
             if value.timestamp + BLOCK_DOWNLOAD_TIMEOUT < now {
                 if let Some(set) = download_schedulers.get_mut(&value.peer) {
                     set.hashes.remove(key);
+                    set.stalled_count += 1;
                     if should_punish && adjustment {
                         set.punish(2);
                     }
@@ -768,6 +910,7 @@ impl InflightBlocks {
                             d.punish(1);
                         }
                         d.hashes.remove(key);
+                        d.stalled_count += 1;
                     };
                 }
 
@@ -833,6 +976,7 @@ impl InflightBlocks {
                 let elapsed = unix_time_as_millis().saturating_sub(state.timestamp);
                 if let Some(set) = download_schedulers.get_mut(&state.peer) {
                     set.hashes.remove(&block);
+                    set.record_response(elapsed);
                     if adjustment {
                         match time_analyzer.push_time(elapsed) {
                             TimeQuantile::MinToFast => set.increase(2),
@@ -865,6 +1009,7 @@ impl Peers {
         is_outbound: bool,
         is_whitelist: bool,
         is_2023edition: bool,
+        is_pruned: bool,
     ) {
         let protect_outbound = is_outbound
             && self
@@ -882,6 +1027,7 @@ impl Peers {
             is_outbound,
             is_whitelist,
             is_2023edition,
+            is_pruned,
             is_protect: protect_outbound,
         };
         self.state
@@ -1266,11 +1412,12 @@ fn get_skip_height(height: BlockNumber) -> BlockNumber {
     }
 }
 
-// <CompactBlockHash, (CompactBlock, <PeerIndex, (Vec<TransactionsIndex>, Vec<UnclesIndex>)>, timestamp)>
+// <CompactBlockHash, (CompactBlock, Option<ShortIdKey>, <PeerIndex, (Vec<TransactionsIndex>, Vec<UnclesIndex>)>, timestamp)>
 pub(crate) type PendingCompactBlockMap = HashMap<
     Byte32,
     (
         packed::CompactBlock,
+        Option<ShortIdKey>,
         HashMap<PeerIndex, (Vec<u32>, Vec<u32>)>,
         u64,
     ),
@@ -1328,6 +1475,7 @@ impl SyncShared {
             tx_filter: Mutex::new(TtlFilter::default()),
             unknown_tx_hashes: Mutex::new(KeyedPriorityQueue::new()),
             peers: Peers::default(),
+            tx_reconciliation: Mutex::new(TxReconciliationState::default()),
             pending_get_block_proposals: DashMap::new(),
             pending_compact_blocks: Mutex::new(HashMap::default()),
             orphan_block_pool: OrphanBlockPool::with_capacity(ORPHAN_BLOCK_SIZE),
@@ -1682,6 +1830,7 @@ pub struct SyncState {
 
     /* Status relevant to peers */
     peers: Peers,
+    tx_reconciliation: Mutex<TxReconciliationState>,
 
     /* Cached items which we had received but not completely process */
     pending_get_block_proposals: DashMap<packed::ProposalShortId, HashSet<PeerIndex>>,
@@ -1728,10 +1877,14 @@ impl SyncState {
         pending.is_empty()
             || pending
                 .get(hash)
-                .map(|(_, _, time)| now > time + 2000)
+                .map(|(_, _, _, time)| now > time + 2000)
                 .unwrap_or(true)
     }
 
+    pub fn tx_reconciliation(&self) -> MutexGuard<TxReconciliationState> {
+        self.tx_reconciliation.lock()
+    }
+
     pub fn pending_compact_blocks(&self) -> MutexGuard<PendingCompactBlockMap> {
         self.pending_compact_blocks.lock()
     }
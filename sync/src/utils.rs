@@ -148,6 +148,7 @@ fn protocol_name(protocol_id: ProtocolId) -> String {
         101 => SupportProtocols::RelayV2.name(),
         102 => SupportProtocols::Time.name(),
         103 => SupportProtocols::RelayV3.name(),
+        104 => SupportProtocols::TxReconciliation.name(),
         110 => SupportProtocols::Alert.name(),
         120 => SupportProtocols::LightClient.name(),
         121 => SupportProtocols::Filter.name(),
@@ -438,6 +438,7 @@ fn all_specs() -> Vec<Box<dyn Spec>> {
         Box::new(InvalidHeaderDep),
         #[cfg(not(target_os = "windows"))]
         Box::new(PoolPersisted),
+        Box::new(PoolCrashRecovered),
         Box::new(TransactionRelayBasic),
         Box::new(TransactionRelayLowFeeRate),
         Box::new(TooManyUnknownTransactions),
@@ -62,3 +62,64 @@ impl Spec for PoolPersisted {
         );
     }
 }
+
+pub struct PoolCrashRecovered;
+
+impl Spec for PoolCrashRecovered {
+    crate::setup!(num_nodes: 1);
+
+    fn run(&self, nodes: &mut Vec<Node>) {
+        let node0 = &mut nodes[0];
+
+        info!("Generate 1 block on node0");
+        node0.mine_until_out_bootstrap_period();
+
+        info!("Generate 6 txs on node0");
+        let mut hash = node0.generate_transaction();
+
+        (0..5).for_each(|_| {
+            let tx = node0.new_transaction(hash.clone());
+            hash = node0.rpc_client().send_transaction(tx.data().into());
+        });
+
+        info!("Generate 1 more blocks on node0");
+        node0.mine(1);
+
+        info!("Generate 5 more txs on node0");
+        (0..5).for_each(|_| {
+            let tx = node0.new_transaction(hash.clone());
+            hash = node0.rpc_client().send_transaction(tx.data().into());
+        });
+
+        node0.wait_for_tx_pool();
+
+        let tx_pool_info_original = node0.get_tip_tx_pool_info();
+        let raw_tx_pool_original = node0.rpc_client().get_raw_tx_pool(Some(true));
+
+        info!("Kill node0");
+        node0.stop();
+
+        info!("Start node0");
+        node0.start();
+
+        let tx_pool_info_recovered = node0.get_tip_tx_pool_info();
+        let raw_tx_pool_recovered = node0.rpc_client().get_raw_tx_pool(Some(true));
+        info!("TxPool should be recovered from the journal");
+        info!("tx_pool_info_original: {:?}", tx_pool_info_original);
+        info!("tx_pool_info_recovered: {:?}", tx_pool_info_recovered);
+        assert_eq!(
+            tx_pool_info_original.proposed,
+            tx_pool_info_recovered.proposed
+        );
+        assert_eq!(
+            tx_pool_info_original.pending,
+            tx_pool_info_recovered.pending
+        );
+        assert_eq!(
+            tx_pool_info_original.total_tx_cycles,
+            tx_pool_info_recovered.total_tx_cycles
+        );
+        // the cycles and the entry times are recovered too
+        assert_eq!(raw_tx_pool_original, raw_tx_pool_recovered);
+    }
+}
@@ -1,6 +1,7 @@
 use super::component::TxEntry;
 use crate::error::Reject;
 use crate::pool::TxPool;
+use ckb_types::packed::Byte32;
 
 /// Callback boxed fn pointer wrapper
 pub type PendingCallback = Box<dyn Fn(&TxEntry) + Sync + Send>;
@@ -8,12 +9,15 @@ pub type PendingCallback = Box<dyn Fn(&TxEntry) + Sync + Send>;
 pub type ProposedCallback = Box<dyn Fn(&TxEntry) + Sync + Send>;
 /// Reject Callback boxed fn pointer wrapper
 pub type RejectCallback = Box<dyn Fn(&mut TxPool, &TxEntry, Reject) + Sync + Send>;
+/// Submit Reject Callback boxed fn pointer wrapper
+pub type SubmitRejectCallback = Box<dyn Fn(&Byte32, &Reject) + Sync + Send>;
 
 /// Struct hold callbacks
 pub struct Callbacks {
     pub(crate) pending: Option<PendingCallback>,
     pub(crate) proposed: Option<ProposedCallback>,
     pub(crate) reject: Option<RejectCallback>,
+    pub(crate) submit_reject: Option<SubmitRejectCallback>,
 }
 
 impl Default for Callbacks {
@@ -29,6 +33,7 @@ impl Callbacks {
             pending: None,
             proposed: None,
             reject: None,
+            submit_reject: None,
         }
     }
 
@@ -47,6 +52,11 @@ impl Callbacks {
         self.reject = Some(callback);
     }
 
+    /// Register a new submit reject callback
+    pub fn register_submit_reject(&mut self, callback: SubmitRejectCallback) {
+        self.submit_reject = Some(callback);
+    }
+
     /// Call on after pending
     pub fn call_pending(&self, entry: &TxEntry) {
         if let Some(call) = &self.pending {
@@ -67,4 +77,11 @@ impl Callbacks {
             call(tx_pool, entry, reject)
         }
     }
+
+    /// Call on after a submitted transaction is rejected without being added to the pool
+    pub fn call_submit_reject(&self, tx_hash: &Byte32, reject: &Reject) {
+        if let Some(call) = &self.submit_reject {
+            call(tx_hash, reject)
+        }
+    }
 }
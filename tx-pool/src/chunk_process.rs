@@ -138,7 +138,10 @@ impl ChunkProcess {
         consensus: Arc<Consensus>,
         tx_env: Arc<TxVerifyEnv>,
     ) -> Result<State, Reject> {
-        let script_verifier = ScriptVerifier::new(rtx, data_loader, consensus, tx_env);
+        let mut script_verifier = ScriptVerifier::new(rtx, data_loader, consensus, tx_env);
+        if self.service.tx_pool_config.parallel_script_verification {
+            script_verifier.enable_parallel_verification();
+        }
         let mut tmp_state: Option<ScriptVerifyState> = None;
 
         let completed: Cycle = loop {
@@ -0,0 +1,307 @@
+//! Fee estimator, which tracks how many blocks the transactions in the tx-pool take to be committed
+//! and estimates the fee rate required to get a transaction committed within the target blocks.
+//!
+//! The transactions are grouped into exponentially spaced fee rate buckets, and the statistics
+//! decay exponentially block by block, which is similar to the estimator of Bitcoin Core.
+use ckb_types::core::{tx_pool::EstimateMode, BlockNumber, FeeRate};
+use ckb_types::packed::ProposalShortId;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The max target blocks supported by the fee estimator.
+pub const MAX_TARGET_BLOCKS: BlockNumber = 64;
+
+/// The lower bound of the fee rate buckets, in shannons/KW.
+const MIN_BUCKET_FEE_RATE: f64 = 1_000f64;
+/// The upper bound of the fee rate buckets, in shannons/KW.
+const MAX_BUCKET_FEE_RATE: f64 = 10_000_000f64;
+/// The spacing of the fee rate buckets.
+const FEE_RATE_SPACING: f64 = 1.1;
+
+/// The decay of the short horizon statistics, the half-life is about 18 blocks.
+const SHORT_DECAY: f64 = 0.962;
+/// The decay of the long horizon statistics, the half-life is about 346 blocks.
+const LONG_DECAY: f64 = 0.998;
+
+/// The success rate required by `EstimateMode::Economical`.
+const ECONOMICAL_THRESHOLD: f64 = 0.85;
+/// The success rate required by `EstimateMode::Conservative`.
+const CONSERVATIVE_THRESHOLD: f64 = 0.95;
+
+/// The decayed count of transactions per block required to make an estimation.
+const SUFFICIENT_TXS_PER_BLOCK: f64 = 0.1;
+
+#[derive(Clone, Debug)]
+struct TrackedTx {
+    height: BlockNumber,
+    bucket: usize,
+    fee_rate: FeeRate,
+}
+
+/// The decayed statistics of the committed and failed transactions.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct TxConfirmStat {
+    decay: f64,
+    /// `confirmed[i][bucket]`: the count of transactions committed within `i + 1` blocks.
+    confirmed: Vec<Vec<f64>>,
+    /// `failed[i][bucket]`: the count of transactions which waited more than `i + 1` blocks and
+    /// then were removed from the tx-pool without being committed.
+    failed: Vec<Vec<f64>>,
+    /// The count of committed transactions in each bucket.
+    txs: Vec<f64>,
+    /// The sum of fee rates of the committed transactions in each bucket.
+    fee_rate_sum: Vec<f64>,
+}
+
+impl TxConfirmStat {
+    fn new(decay: f64, buckets: usize) -> Self {
+        let targets = MAX_TARGET_BLOCKS as usize;
+        TxConfirmStat {
+            decay,
+            confirmed: vec![vec![0f64; buckets]; targets],
+            failed: vec![vec![0f64; buckets]; targets],
+            txs: vec![0f64; buckets],
+            fee_rate_sum: vec![0f64; buckets],
+        }
+    }
+
+    fn is_compatible(&self, buckets: usize) -> bool {
+        let targets = MAX_TARGET_BLOCKS as usize;
+        self.confirmed.len() == targets
+            && self.failed.len() == targets
+            && self.confirmed.iter().all(|row| row.len() == buckets)
+            && self.failed.iter().all(|row| row.len() == buckets)
+            && self.txs.len() == buckets
+            && self.fee_rate_sum.len() == buckets
+    }
+
+    fn apply_decay(&mut self) {
+        let decay = self.decay;
+        for value in self
+            .confirmed
+            .iter_mut()
+            .chain(self.failed.iter_mut())
+            .flatten()
+            .chain(self.txs.iter_mut())
+            .chain(self.fee_rate_sum.iter_mut())
+        {
+            *value *= decay;
+        }
+    }
+
+    fn record_confirmed(&mut self, blocks: usize, bucket: usize, fee_rate: f64) {
+        for row in self.confirmed.iter_mut().skip(blocks.saturating_sub(1)) {
+            row[bucket] += 1f64;
+        }
+        self.txs[bucket] += 1f64;
+        self.fee_rate_sum[bucket] += fee_rate;
+    }
+
+    fn record_failed(&mut self, blocks: usize, bucket: usize) {
+        for row in self.failed.iter_mut().take(blocks) {
+            row[bucket] += 1f64;
+        }
+    }
+
+    /// Scans the buckets from the highest fee rate, groups the adjacent buckets until there are
+    /// sufficient transactions, and returns the average fee rate of the lowest group in which the
+    /// success rate reaches `threshold`.
+    fn estimate(&self, target: usize, threshold: f64, unconfirmed: &[f64]) -> Option<f64> {
+        let sufficient_txs = SUFFICIENT_TXS_PER_BLOCK / (1f64 - self.decay);
+        let mut passed = None;
+        let (mut confirmed, mut total, mut txs, mut fee_rate_sum) = (0f64, 0f64, 0f64, 0f64);
+
+        for bucket in (0..self.txs.len()).rev() {
+            confirmed += self.confirmed[target - 1][bucket];
+            total += self.txs[bucket] + self.failed[target - 1][bucket] + unconfirmed[bucket];
+            txs += self.txs[bucket];
+            fee_rate_sum += self.fee_rate_sum[bucket];
+
+            if total >= sufficient_txs {
+                if confirmed / total < threshold {
+                    break;
+                }
+                if txs > 0f64 {
+                    passed = Some(fee_rate_sum / txs);
+                }
+                confirmed = 0f64;
+                total = 0f64;
+                txs = 0f64;
+                fee_rate_sum = 0f64;
+            }
+        }
+        passed
+    }
+}
+
+/// The fee estimator of the tx-pool.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct FeeEstimator {
+    /// The highest block number processed by the estimator.
+    best_height: BlockNumber,
+    short_stat: TxConfirmStat,
+    long_stat: TxConfirmStat,
+    #[serde(skip)]
+    bucket_bounds: Vec<f64>,
+    /// The transactions in the tx-pool and the tip number when they entered.
+    #[serde(skip)]
+    tracked: HashMap<ProposalShortId, TrackedTx>,
+}
+
+impl Default for FeeEstimator {
+    fn default() -> Self {
+        let bucket_bounds = Self::build_bucket_bounds();
+        let buckets = bucket_bounds.len();
+        FeeEstimator {
+            best_height: 0,
+            short_stat: TxConfirmStat::new(SHORT_DECAY, buckets),
+            long_stat: TxConfirmStat::new(LONG_DECAY, buckets),
+            bucket_bounds,
+            tracked: HashMap::new(),
+        }
+    }
+}
+
+impl FeeEstimator {
+    /// The upper bounds of the buckets, the last bucket is unbounded.
+    fn build_bucket_bounds() -> Vec<f64> {
+        let mut bounds = Vec::new();
+        let mut bound = MIN_BUCKET_FEE_RATE;
+        while bound <= MAX_BUCKET_FEE_RATE {
+            bounds.push(bound);
+            bound *= FEE_RATE_SPACING;
+        }
+        bounds.push(f64::INFINITY);
+        bounds
+    }
+
+    /// Restores the estimator from the persisted statistics, returns `None` if the buckets are
+    /// incompatible with the current ones.
+    pub(crate) fn restore(mut persisted: FeeEstimator) -> Option<FeeEstimator> {
+        let bucket_bounds = Self::build_bucket_bounds();
+        let buckets = bucket_bounds.len();
+        if !persisted.short_stat.is_compatible(buckets)
+            || !persisted.long_stat.is_compatible(buckets)
+        {
+            return None;
+        }
+        persisted.bucket_bounds = bucket_bounds;
+        Some(persisted)
+    }
+
+    fn bucket_index(&self, fee_rate: FeeRate) -> usize {
+        let fee_rate = fee_rate.as_u64() as f64;
+        self.bucket_bounds
+            .iter()
+            .position(|bound| fee_rate <= *bound)
+            .unwrap_or(self.bucket_bounds.len() - 1)
+    }
+
+    /// Starts tracking a transaction which entered the tx-pool when the tip is `height`.
+    pub(crate) fn track_tx(&mut self, id: ProposalShortId, fee_rate: FeeRate, height: BlockNumber) {
+        let bucket = self.bucket_index(fee_rate);
+        self.tracked.entry(id).or_insert(TrackedTx {
+            height,
+            bucket,
+            fee_rate,
+        });
+    }
+
+    /// Stops tracking a transaction without recording it, e.g., it is rolled back right after
+    /// being added into the tx-pool.
+    pub(crate) fn untrack_tx(&mut self, id: &ProposalShortId) {
+        self.tracked.remove(id);
+    }
+
+    /// Records the transactions committed in a new block. The blocks which are not higher than
+    /// the best height processed are ignored, e.g., the blocks attached in a reorg.
+    pub(crate) fn process_block<'a>(
+        &mut self,
+        number: BlockNumber,
+        committed: impl Iterator<Item = &'a ProposalShortId>,
+    ) {
+        if number <= self.best_height {
+            return;
+        }
+        self.best_height = number;
+        self.short_stat.apply_decay();
+        self.long_stat.apply_decay();
+
+        for id in committed {
+            if let Some(tx) = self.tracked.remove(id) {
+                let blocks = number.saturating_sub(tx.height) as usize;
+                let fee_rate = tx.fee_rate.as_u64() as f64;
+                self.short_stat
+                    .record_confirmed(blocks, tx.bucket, fee_rate);
+                self.long_stat.record_confirmed(blocks, tx.bucket, fee_rate);
+            }
+        }
+    }
+
+    /// Stops tracking the transactions which are no longer in the tx-pool, and records them as
+    /// failed for the targets they have waited longer than.
+    pub(crate) fn remove_untracked<F>(&mut self, contains: F)
+    where
+        F: Fn(&ProposalShortId) -> bool,
+    {
+        let best_height = self.best_height;
+        let short_stat = &mut self.short_stat;
+        let long_stat = &mut self.long_stat;
+        self.tracked.retain(|id, tx| {
+            if contains(id) {
+                return true;
+            }
+            let blocks = best_height.saturating_sub(tx.height) as usize;
+            short_stat.record_failed(blocks, tx.bucket);
+            long_stat.record_failed(blocks, tx.bucket);
+            false
+        });
+    }
+
+    /// The count of tracked transactions in each bucket which have waited at least `target` blocks.
+    fn unconfirmed(&self, target: BlockNumber) -> Vec<f64> {
+        let mut unconfirmed = vec![0f64; self.bucket_bounds.len()];
+        for tx in self.tracked.values() {
+            if self.best_height.saturating_sub(tx.height) >= target {
+                unconfirmed[tx.bucket] += 1f64;
+            }
+        }
+        unconfirmed
+    }
+
+    /// Estimates the fee rate to get a transaction committed within `target` blocks, returns
+    /// `None` if there is not sufficient data or `target` is not in `1..=MAX_TARGET_BLOCKS`.
+    pub(crate) fn estimate_fee_rate(
+        &self,
+        target: BlockNumber,
+        mode: EstimateMode,
+    ) -> Option<FeeRate> {
+        if !(1..=MAX_TARGET_BLOCKS).contains(&target) {
+            return None;
+        }
+        let unconfirmed = self.unconfirmed(target);
+        let target = target as usize;
+        let estimated = match mode {
+            EstimateMode::Economical => self
+                .short_stat
+                .estimate(target, ECONOMICAL_THRESHOLD, &unconfirmed)
+                .or_else(|| {
+                    self.long_stat
+                        .estimate(target, ECONOMICAL_THRESHOLD, &unconfirmed)
+                }),
+            EstimateMode::Conservative => {
+                let short = self
+                    .short_stat
+                    .estimate(target, CONSERVATIVE_THRESHOLD, &unconfirmed);
+                let long = self
+                    .long_stat
+                    .estimate(target, CONSERVATIVE_THRESHOLD, &unconfirmed);
+                match (short, long) {
+                    (Some(short), Some(long)) => Some(short.max(long)),
+                    (short, long) => short.or(long),
+                }
+            }
+        };
+        estimated.map(|fee_rate| FeeRate::from_u64(fee_rate.round() as u64))
+    }
+}
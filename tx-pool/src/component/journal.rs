@@ -0,0 +1,442 @@
+//! The journal of the tx-pool, which persists the pool entries incrementally, so the pool can be
+//! recovered after the node crashed or was killed.
+//!
+//! The journal is an append-only file of records, each record is framed as
+//! `length (u32, little endian) | checksum (blake2b-256 of the payload) | payload`, and the
+//! payload is either
+//!
+//! * `0u8 | cycles (u64) | timestamp (u64) | transaction (molecule)`, when an entry is added, or
+//! * `1u8 | proposal short id`, when an entry is removed.
+//!
+//! The records are written without fsync, so they survive the crashes of the process. The replay
+//! stops at the first truncated or corrupted record, and the journal is compacted by rewriting the
+//! live entries into a new file, which is synced and renamed atomically.
+//!
+//! The file is written by a dedicated thread, so the tx-pool only queues the records while it
+//! holds its lock. The thread writes the queued records in batches and flushes them when the
+//! queue is drained.
+use crate::component::entry::TxEntry;
+use ckb_channel::{unbounded, Receiver, Sender};
+use ckb_error::{AnyError, OtherError};
+use ckb_hash::blake2b_256;
+use ckb_logger::{error, warn};
+use ckb_types::{
+    core::{Cycle, TransactionView},
+    packed::{Byte32, ProposalShortId, ProposalShortIdReader, TransactionReader},
+    prelude::*,
+};
+use ckb_util::LinkedHashMap;
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, Read as _, Write as _};
+use std::path::{Path, PathBuf};
+use std::thread::{self, JoinHandle};
+use tokio::sync::oneshot;
+
+const RECORD_ADD: u8 = 0;
+const RECORD_REMOVE: u8 = 1;
+
+/// The length and the checksum of a record.
+const RECORD_HEADER_SIZE: usize = 4 + 32;
+/// The kind, cycles and timestamp of an add record.
+const ADD_HEADER_SIZE: usize = 1 + 8 + 8;
+
+/// The journal is compacted when there are more than twice the live entries plus this count of
+/// records.
+const COMPACT_THRESHOLD: usize = 1_000;
+
+/// A tx-pool entry recovered from the journal.
+#[derive(Debug, Clone)]
+pub(crate) struct JournalEntry {
+    pub(crate) tx: TransactionView,
+    pub(crate) cycles: Cycle,
+    /// The unix timestamp when the tx entered the tx-pool, unit: Millisecond
+    pub(crate) timestamp: u64,
+}
+
+/// The commands queued to the journal writer thread.
+enum Command {
+    Add(JournalEntry),
+    Remove(ProposalShortId),
+    /// Rewrites the journal with the live entries
+    Compact(Vec<JournalEntry>),
+    /// Flushes the written records and syncs the file, then responds
+    Sync(oneshot::Sender<Result<(), AnyError>>),
+}
+
+/// The append-only journal of the tx-pool entries.
+pub(crate) struct Journal {
+    sender: Option<Sender<Command>>,
+    writer: Option<JoinHandle<()>>,
+    /// The count of records in the journal file, including the queued ones
+    records: usize,
+}
+
+impl Journal {
+    /// Replays the journal file and rewrites it with the recovered entries, returns the journal
+    /// and the entries in topological order. The records after the first corrupted one are
+    /// discarded.
+    pub(crate) fn open(path: PathBuf) -> Result<(Journal, Vec<JournalEntry>), AnyError> {
+        let mut buffer = Vec::new();
+        if path.exists() {
+            let mut file = OpenOptions::new().read(true).open(&path).map_err(|err| {
+                let errmsg =
+                    format!("Failed to open the tx-pool journal file [{path:?}], cause: {err}");
+                OtherError::new(errmsg)
+            })?;
+            file.read_to_end(&mut buffer).map_err(|err| {
+                let errmsg =
+                    format!("Failed to read the tx-pool journal file [{path:?}], cause: {err}");
+                OtherError::new(errmsg)
+            })?;
+        }
+
+        let (entries, corrupted) = replay(&buffer);
+        if let Some(offset) = corrupted {
+            warn!(
+                "The tx-pool journal file [{:?}] is corrupted at offset {}, the following records are discarded",
+                path, offset
+            );
+        }
+        let entries = sort_topologically(entries.into_iter().map(|(_, entry)| entry).collect());
+
+        let records = entries
+            .iter()
+            .map(|entry| encode_add(&entry.tx, entry.cycles, entry.timestamp))
+            .collect::<Vec<_>>();
+        let file = rewrite(&path, &records)?;
+        let (sender, receiver) = unbounded();
+        let writer = thread::Builder::new()
+            .name("TxPoolJournal".to_string())
+            .spawn(move || JournalWriter::new(path, file).run(receiver))
+            .map_err(|err| {
+                let errmsg = format!("Failed to start the tx-pool journal writer, cause: {err}");
+                OtherError::new(errmsg)
+            })?;
+        let journal = Journal {
+            sender: Some(sender),
+            writer: Some(writer),
+            records: records.len(),
+        };
+        Ok((journal, entries))
+    }
+
+    /// Appends the record of an added entry.
+    pub(crate) fn append_add(&mut self, entry: &TxEntry) -> Result<(), AnyError> {
+        self.send(Command::Add(JournalEntry {
+            tx: entry.transaction().clone(),
+            cycles: entry.cycles,
+            timestamp: entry.timestamp,
+        }))?;
+        self.records += 1;
+        Ok(())
+    }
+
+    /// Appends the record of a removed entry.
+    pub(crate) fn append_remove(&mut self, id: &ProposalShortId) -> Result<(), AnyError> {
+        self.send(Command::Remove(id.clone()))?;
+        self.records += 1;
+        Ok(())
+    }
+
+    /// Returns a receiver of the result, after the queued records are written and synced.
+    pub(crate) fn sync(&self) -> Result<oneshot::Receiver<Result<(), AnyError>>, AnyError> {
+        let (responder, response) = oneshot::channel();
+        self.send(Command::Sync(responder))?;
+        Ok(response)
+    }
+
+    fn send(&self, command: Command) -> Result<(), AnyError> {
+        self.sender
+            .as_ref()
+            .and_then(|sender| sender.send(command).ok())
+            .ok_or_else(|| OtherError::new("The tx-pool journal writer is stopped").into())
+    }
+
+    /// Whether the journal holds too many stale records for the count of the live entries.
+    pub(crate) fn needs_compaction(&self, entries: usize) -> bool {
+        self.records > entries.saturating_mul(2).saturating_add(COMPACT_THRESHOLD)
+    }
+
+    /// Rewrites the journal with only the live entries.
+    pub(crate) fn compact<'a>(
+        &mut self,
+        entries: impl Iterator<Item = &'a TxEntry>,
+    ) -> Result<(), AnyError> {
+        let entries = entries
+            .map(|entry| JournalEntry {
+                tx: entry.transaction().clone(),
+                cycles: entry.cycles,
+                timestamp: entry.timestamp,
+            })
+            .collect::<Vec<_>>();
+        let records = entries.len();
+        self.send(Command::Compact(entries))?;
+        self.records = records;
+        Ok(())
+    }
+}
+
+impl Drop for Journal {
+    /// Waits for the writer thread to write all the queued records.
+    fn drop(&mut self) {
+        // the writer thread exits after the channel is disconnected and drained
+        self.sender.take();
+        if let Some(writer) = self.writer.take() {
+            if writer.join().is_err() {
+                error!("The tx-pool journal writer panicked");
+            }
+        }
+    }
+}
+
+/// The journal file owned by the writer thread.
+struct JournalWriter {
+    path: PathBuf,
+    file: BufWriter<File>,
+}
+
+impl JournalWriter {
+    fn new(path: PathBuf, file: File) -> Self {
+        JournalWriter {
+            path,
+            file: BufWriter::new(file),
+        }
+    }
+
+    fn run(mut self, receiver: Receiver<Command>) {
+        while let Ok(command) = receiver.recv() {
+            self.handle(command);
+            // write the queued commands in a batch, then flush them
+            while let Ok(command) = receiver.try_recv() {
+                self.handle(command);
+            }
+            if let Err(err) = self.flush() {
+                error!("{}", err);
+            }
+        }
+    }
+
+    fn handle(&mut self, command: Command) {
+        let ret = match command {
+            Command::Add(entry) => {
+                self.append(&encode_add(&entry.tx, entry.cycles, entry.timestamp))
+            }
+            Command::Remove(id) => self.append(&encode_remove(&id)),
+            Command::Compact(entries) => self.compact(&entries),
+            Command::Sync(responder) => {
+                let _ = responder.send(self.sync());
+                Ok(())
+            }
+        };
+        if let Err(err) = ret {
+            error!("{}", err);
+        }
+    }
+
+    fn append(&mut self, record: &[u8]) -> Result<(), AnyError> {
+        self.file.write_all(record).map_err(|err| {
+            let errmsg = format!(
+                "Failed to append into the tx-pool journal file [{:?}], cause: {err}",
+                self.path
+            );
+            OtherError::new(errmsg).into()
+        })
+    }
+
+    fn compact(&mut self, entries: &[JournalEntry]) -> Result<(), AnyError> {
+        let records = entries
+            .iter()
+            .map(|entry| encode_add(&entry.tx, entry.cycles, entry.timestamp))
+            .collect::<Vec<_>>();
+        // the records appended before are replaced by the rewritten file
+        self.file = BufWriter::new(rewrite(&self.path, &records)?);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), AnyError> {
+        self.file.flush().map_err(|err| {
+            let errmsg = format!(
+                "Failed to flush the tx-pool journal file [{:?}], cause: {err}",
+                self.path
+            );
+            OtherError::new(errmsg).into()
+        })
+    }
+
+    fn sync(&mut self) -> Result<(), AnyError> {
+        self.flush()?;
+        self.file.get_ref().sync_all().map_err(|err| {
+            let errmsg = format!(
+                "Failed to sync the tx-pool journal file [{:?}], cause: {err}",
+                self.path
+            );
+            OtherError::new(errmsg).into()
+        })
+    }
+}
+
+/// Writes the records into a temporary file, then replaces the journal file with it atomically,
+/// returns the journal file opened for appending.
+fn rewrite(path: &Path, records: &[Vec<u8>]) -> Result<File, AnyError> {
+    let tmp_path = path.with_extension("tmp");
+    let mut tmp_file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(true)
+        .open(&tmp_path)
+        .map_err(|err| {
+            let errmsg =
+                format!("Failed to open the tx-pool journal file [{tmp_path:?}], cause: {err}");
+            OtherError::new(errmsg)
+        })?;
+    for record in records {
+        tmp_file.write_all(record).map_err(|err| {
+            let errmsg =
+                format!("Failed to write the tx-pool journal file [{tmp_path:?}], cause: {err}");
+            OtherError::new(errmsg)
+        })?;
+    }
+    tmp_file.sync_all().map_err(|err| {
+        let errmsg =
+            format!("Failed to sync the tx-pool journal file [{tmp_path:?}], cause: {err}");
+        OtherError::new(errmsg)
+    })?;
+    fs::rename(&tmp_path, path).map_err(|err| {
+        let errmsg = format!(
+            "Failed to replace the tx-pool journal file [{path:?}] with [{tmp_path:?}], cause: {err}"
+        );
+        OtherError::new(errmsg)
+    })?;
+    OpenOptions::new().append(true).open(path).map_err(|err| {
+        let errmsg = format!("Failed to open the tx-pool journal file [{path:?}], cause: {err}");
+        OtherError::new(errmsg).into()
+    })
+}
+
+fn frame(payload: Vec<u8>) -> Vec<u8> {
+    let mut record = Vec::with_capacity(RECORD_HEADER_SIZE + payload.len());
+    record.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    record.extend_from_slice(&blake2b_256(&payload));
+    record.extend(payload);
+    record
+}
+
+fn encode_add(tx: &TransactionView, cycles: Cycle, timestamp: u64) -> Vec<u8> {
+    let data = tx.data();
+    let mut payload = Vec::with_capacity(ADD_HEADER_SIZE + data.as_slice().len());
+    payload.push(RECORD_ADD);
+    payload.extend_from_slice(&cycles.to_le_bytes());
+    payload.extend_from_slice(&timestamp.to_le_bytes());
+    payload.extend_from_slice(data.as_slice());
+    frame(payload)
+}
+
+fn encode_remove(id: &ProposalShortId) -> Vec<u8> {
+    let mut payload = Vec::with_capacity(1 + id.as_slice().len());
+    payload.push(RECORD_REMOVE);
+    payload.extend_from_slice(id.as_slice());
+    frame(payload)
+}
+
+enum Record {
+    Add(JournalEntry),
+    Remove(ProposalShortId),
+}
+
+fn decode(payload: &[u8]) -> Option<Record> {
+    match payload.first() {
+        Some(&RECORD_ADD) if payload.len() >= ADD_HEADER_SIZE => {
+            let cycles = u64::from_le_bytes(payload[1..9].try_into().ok()?);
+            let timestamp = u64::from_le_bytes(payload[9..17].try_into().ok()?);
+            let tx = TransactionReader::from_slice(&payload[ADD_HEADER_SIZE..])
+                .ok()?
+                .to_entity()
+                .into_view();
+            Some(Record::Add(JournalEntry {
+                tx,
+                cycles,
+                timestamp,
+            }))
+        }
+        Some(&RECORD_REMOVE) => ProposalShortIdReader::from_slice(&payload[1..])
+            .ok()
+            .map(|id| Record::Remove(id.to_entity())),
+        _ => None,
+    }
+}
+
+/// Replays the records, returns the live entries in the order they were added and the offset of
+/// the first corrupted record if any.
+fn replay(buffer: &[u8]) -> (LinkedHashMap<ProposalShortId, JournalEntry>, Option<usize>) {
+    let mut entries = LinkedHashMap::new();
+    let mut offset = 0;
+    while offset < buffer.len() {
+        let record = buffer
+            .get(offset..offset + RECORD_HEADER_SIZE)
+            .and_then(|header| {
+                let len = u32::from_le_bytes(header[..4].try_into().ok()?) as usize;
+                let payload = buffer.get(offset + RECORD_HEADER_SIZE..)?.get(..len)?;
+                if blake2b_256(payload)[..] != header[4..] {
+                    return None;
+                }
+                decode(payload).map(|record| (record, RECORD_HEADER_SIZE + len))
+            });
+        match record {
+            Some((Record::Add(entry), len)) => {
+                entries.insert(entry.tx.proposal_short_id(), entry);
+                offset += len;
+            }
+            Some((Record::Remove(id), len)) => {
+                entries.remove(&id);
+                offset += len;
+            }
+            None => return (entries, Some(offset)),
+        }
+    }
+    (entries, None)
+}
+
+/// Sorts the entries so that the parents are ahead of their children, the entries which are
+/// re-added in a reorg may follow their children in the journal.
+fn sort_topologically(entries: Vec<JournalEntry>) -> Vec<JournalEntry> {
+    let indexes: HashMap<Byte32, usize> = entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| (entry.tx.hash(), index))
+        .collect();
+
+    let mut visited = HashSet::with_capacity(entries.len());
+    let mut sorted = Vec::with_capacity(entries.len());
+    for index in 0..entries.len() {
+        visit(index, &entries, &indexes, &mut visited, &mut sorted);
+    }
+
+    let mut entries: Vec<Option<JournalEntry>> = entries.into_iter().map(Some).collect();
+    sorted
+        .into_iter()
+        .filter_map(|index| entries[index].take())
+        .collect()
+}
+
+fn visit(
+    index: usize,
+    entries: &[JournalEntry],
+    indexes: &HashMap<Byte32, usize>,
+    visited: &mut HashSet<usize>,
+    sorted: &mut Vec<usize>,
+) {
+    if !visited.insert(index) {
+        return;
+    }
+    let tx = &entries[index].tx;
+    let parents = tx
+        .input_pts_iter()
+        .chain(tx.cell_deps_iter().map(|dep| dep.out_point()))
+        .filter_map(|out_point| indexes.get(&out_point.tx_hash()).copied())
+        .collect::<Vec<_>>();
+    for parent in parents {
+        visit(parent, entries, indexes, visited, sorted);
+    }
+    sorted.push(index);
+}
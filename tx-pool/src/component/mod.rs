@@ -3,6 +3,8 @@ pub mod entry;
 
 pub(crate) mod chunk;
 pub(crate) mod edges;
+pub(crate) mod fee_estimator;
+pub(crate) mod journal;
 pub(crate) mod links;
 pub(crate) mod orphan;
 pub(crate) mod pool_map;
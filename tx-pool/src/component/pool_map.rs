@@ -3,10 +3,12 @@ extern crate rustc_hash;
 extern crate slab;
 use super::links::TxLinks;
 use crate::component::edges::Edges;
+use crate::component::journal::Journal;
 use crate::component::links::{Relation, TxLinksMap};
 use crate::component::sort_key::{AncestorsScoreSortKey, EvictKey};
 use crate::error::Reject;
 use crate::TxEntry;
+use ckb_error::AnyError;
 use ckb_logger::{debug, error, trace};
 use ckb_types::core::error::OutPointError;
 use ckb_types::core::Cycle;
@@ -16,12 +18,22 @@ use ckb_types::{
     bytes::Bytes,
     core::TransactionView,
     packed::{Byte32, CellOutput, ProposalShortId},
+    utilities::ShortIdKey,
 };
+use lru::LruCache;
 use multi_index_map::MultiIndexMap;
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use tokio::sync::oneshot;
 
 type ConflictEntry = (TxEntry, Reject);
 
+// The max count of keys whose salted short ids are indexed, a key is used by the compact block of
+// one connection and the following block transactions
+const MAX_SALTED_SHORT_ID_INDEXES: usize = 16;
+// A lock script whose in-pool txs take more than a half of the per-lock limits is over its
+// eviction quota, its txs are evicted first when the pool is full
+const LOCK_EVICTION_QUOTA_DIVISOR: usize = 2;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Status {
     Pending,
@@ -59,6 +71,13 @@ pub struct PoolEntry {
     pub inner: TxEntry,
 }
 
+/// The in-pool txs spending the cells of a lock script
+#[derive(Default, Debug, Clone)]
+pub(crate) struct LockUsage {
+    pub(crate) txs: HashSet<ProposalShortId>,
+    pub(crate) size: usize,
+}
+
 pub struct PoolMap {
     /// The pool entries with different kinds of sort strategies
     pub(crate) entries: MultiIndexPoolEntryMap,
@@ -71,6 +90,22 @@ pub struct PoolMap {
     pub(crate) total_tx_size: usize,
     // sum of all tx_pool tx's cycles.
     pub(crate) total_tx_cycles: Cycle,
+    /// The usages of the input lock scripts, by lock script hash
+    pub(crate) lock_usages: HashMap<Byte32, LockUsage>,
+    /// The input lock scripts ordered by the size of their in-pool txs
+    pub(crate) lock_usages_by_size: BTreeSet<(usize, Byte32)>,
+    /// The input lock scripts ordered by the count of their in-pool txs
+    pub(crate) lock_usages_by_count: BTreeSet<(usize, Byte32)>,
+    /// The max count of in-pool txs spending a lock script, 0 means no limit
+    pub(crate) max_tx_count_per_lock: usize,
+    /// The max total size of in-pool txs spending a lock script, 0 means no limit
+    pub(crate) max_tx_size_per_lock: usize,
+    /// The journal persisting the entries incrementally, `None` means the pool is not persisted
+    pub(crate) journal: Option<Journal>,
+    /// The entries drained from the pool to be re-processed, which are kept in the journal
+    pub(crate) drained: HashMap<ProposalShortId, TxEntry>,
+    /// The ids of the entries by their salted short ids, for each key in use
+    pub(crate) salted_short_ids: LruCache<ShortIdKey, HashMap<ProposalShortId, ProposalShortId>>,
 }
 
 impl PoolMap {
@@ -82,9 +117,28 @@ impl PoolMap {
             max_ancestors_count,
             total_tx_size: 0,
             total_tx_cycles: 0,
+            lock_usages: HashMap::new(),
+            lock_usages_by_size: BTreeSet::new(),
+            lock_usages_by_count: BTreeSet::new(),
+            max_tx_count_per_lock: 0,
+            max_tx_size_per_lock: 0,
+            journal: None,
+            drained: HashMap::new(),
+            salted_short_ids: LruCache::new(MAX_SALTED_SHORT_ID_INDEXES),
         }
     }
 
+    /// Set the limits of the in-pool txs spending a lock script, 0 means no limit
+    pub(crate) fn with_lock_limits(
+        mut self,
+        max_tx_count_per_lock: usize,
+        max_tx_size_per_lock: usize,
+    ) -> Self {
+        self.max_tx_count_per_lock = max_tx_count_per_lock;
+        self.max_tx_size_per_lock = max_tx_size_per_lock;
+        self
+    }
+
     #[cfg(test)]
     pub(crate) fn header_deps_len(&self) -> usize {
         self.edges.header_deps_len()
@@ -198,7 +252,10 @@ impl PoolMap {
         self.check_and_record_ancestors(&mut entry)?;
         self.record_entry_edges(&entry)?;
         self.insert_entry(&entry, status);
+        self.index_salted_short_ids(&entry);
         self.record_entry_descendants(&entry);
+        self.record_lock_usages(&entry);
+        self.journal_add(&entry);
         self.track_entry_statics();
         self.update_stat_for_add_tx(entry.size, entry.cycles);
         Ok(true)
@@ -225,6 +282,9 @@ impl PoolMap {
             self.update_descendants_index_key(&entry.inner, EntryOp::Remove);
             self.remove_entry_edges(&entry.inner);
             self.remove_entry_links(id);
+            self.remove_lock_usages(&entry.inner);
+            self.remove_salted_short_ids(&entry.inner);
+            self.journal_remove(id);
             self.update_stat_for_remove_tx(entry.inner.size, entry.inner.cycles);
             entry.inner
         })
@@ -330,6 +390,39 @@ impl PoolMap {
         self.entries.iter().map(|(_, entry)| entry)
     }
 
+    /// Returns the entries whose salted short ids are in `short_ids`, by the salted short ids.
+    ///
+    /// The pool is indexed by the salted short ids the first time the key is used, the index is
+    /// updated when entries are added or removed, so the lookups of the same key don't scan the pool again.
+    pub(crate) fn get_by_salted_short_ids(
+        &mut self,
+        key: &ShortIdKey,
+        short_ids: &HashSet<ProposalShortId>,
+    ) -> HashMap<ProposalShortId, &PoolEntry> {
+        let entries = &self.entries;
+        let index = self.salted_short_ids.get_or_insert(*key, || {
+            entries
+                .iter()
+                .map(|(_, entry)| {
+                    let tx_hash = entry.inner.transaction().hash();
+                    (
+                        key.short_id(&tx_hash).to_proposal_short_id(),
+                        entry.id.clone(),
+                    )
+                })
+                .collect()
+        });
+        short_ids
+            .iter()
+            .filter_map(|short_id| {
+                index
+                    .and_then(|index| index.get(short_id))
+                    .and_then(|id| entries.get_by_id(id))
+                    .map(|entry| (short_id.clone(), entry))
+            })
+            .collect()
+    }
+
     pub(crate) fn next_evict_entry(&self, status: Status) -> Option<ProposalShortId> {
         self.entries
             .iter_by_evict_key()
@@ -337,14 +430,124 @@ impl PoolMap {
             .map(|entry| entry.id.clone())
     }
 
+    /// The entry to evict when the pool is over `max_tx_pool_size`.
+    ///
+    /// The txs spending the lock script which occupies the most bytes go first if it is over its
+    /// eviction quota, otherwise the entries are evicted by the fee rate order. Without the
+    /// per-lock limits, the entries are always evicted by the fee rate order.
+    pub(crate) fn next_evict_entry_for_size_limit(&self) -> Option<ProposalShortId> {
+        let (size_quota, count_quota) = self.lock_eviction_quota();
+        self.next_evict_entry_of_heaviest_lock(size_quota, count_quota)
+            .or_else(|| self.next_evict_entry(Status::Pending))
+            .or_else(|| self.next_evict_entry(Status::Gap))
+            .or_else(|| self.next_evict_entry(Status::Proposed))
+    }
+
+    /// The (size, count) eviction quota of a lock script, derived from the per-lock limits, so
+    /// that a lock script can be over its quota before its txs are rejected by the limits, a
+    /// disabled limit gives no quota
+    fn lock_eviction_quota(&self) -> (usize, usize) {
+        let size_quota = match self.max_tx_size_per_lock {
+            0 => usize::MAX,
+            limit => limit / LOCK_EVICTION_QUOTA_DIVISOR,
+        };
+        let count_quota = match self.max_tx_count_per_lock {
+            0 => usize::MAX,
+            limit => limit / LOCK_EVICTION_QUOTA_DIVISOR,
+        };
+        (size_quota, count_quota)
+    }
+
+    /// The entry to evict from the txs spending the lock script which occupies the most bytes if
+    /// it occupies more than `size_quota` bytes, or else from the txs spending the lock script
+    /// which has the most txs if it has more than `count_quota` txs, so that a single sender can
+    /// not push the others' txs out of a full pool.
+    pub(crate) fn next_evict_entry_of_heaviest_lock(
+        &self,
+        size_quota: usize,
+        count_quota: usize,
+    ) -> Option<ProposalShortId> {
+        let over_quota = |usages: &BTreeSet<(usize, Byte32)>, quota: usize| {
+            usages
+                .iter()
+                .next_back()
+                .filter(|(usage, _lock_hash)| *usage > quota)
+                .map(|(_usage, lock_hash)| lock_hash)
+        };
+        let lock_hash = over_quota(&self.lock_usages_by_size, size_quota)
+            .or_else(|| over_quota(&self.lock_usages_by_count, count_quota))?;
+        let usage = self.lock_usages.get(lock_hash)?;
+        [Status::Pending, Status::Gap, Status::Proposed]
+            .iter()
+            .find_map(|status| {
+                usage
+                    .txs
+                    .iter()
+                    .filter_map(|id| self.get_by_id(id))
+                    .filter(|entry| entry.status == *status)
+                    .min_by(|a, b| a.evict_key.cmp(&b.evict_key))
+                    .map(|entry| entry.id.clone())
+            })
+    }
+
     pub(crate) fn clear(&mut self) {
+        self.drained.clear();
+        self.clear_entries();
+        if let Err(err) = self.compact_journal() {
+            error!("Failed to clear the tx-pool journal, cause: {}", err);
+        }
+    }
+
+    /// Clears the pool and keeps the drained entries in the journal, until they are released
+    /// after being re-processed.
+    pub(crate) fn drain(&mut self, drained: Vec<TxEntry>) {
+        self.clear_entries();
+        self.drained.extend(
+            drained
+                .into_iter()
+                .map(|entry| (entry.proposal_short_id(), entry)),
+        );
+    }
+
+    /// Releases the drained entries, they're journaled again if they're re-added into the pool.
+    pub(crate) fn release_drained<'a>(&mut self, ids: impl Iterator<Item = &'a ProposalShortId>) {
+        for id in ids {
+            self.drained.remove(id);
+        }
+    }
+
+    fn clear_entries(&mut self) {
         self.entries = MultiIndexPoolEntryMap::default();
         self.edges.clear();
         self.links.clear();
+        self.lock_usages.clear();
+        self.lock_usages_by_size.clear();
+        self.lock_usages_by_count.clear();
+        self.salted_short_ids.clear();
         self.total_tx_size = 0;
         self.total_tx_cycles = 0;
     }
 
+    /// Rewrites the journal with only the entries in the pool and the drained ones
+    pub(crate) fn compact_journal(&mut self) -> Result<(), AnyError> {
+        match self.journal {
+            Some(ref mut journal) => journal.compact(
+                self.entries
+                    .iter()
+                    .map(|(_, e)| &e.inner)
+                    .chain(self.drained.values()),
+            ),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns a receiver of the result, after the journal is written and synced.
+    pub(crate) fn sync_journal(
+        &self,
+    ) -> Result<Option<oneshot::Receiver<Result<(), AnyError>>>, AnyError> {
+        self.journal.as_ref().map(Journal::sync).transpose()
+    }
+
     pub(crate) fn score_sorted_iter_by(
         &self,
         statuses: Vec<Status>,
@@ -507,6 +710,113 @@ impl PoolMap {
         Ok(true)
     }
 
+    /// Check whether the in-pool txs spending the input lock scripts reach the limits, the txs in
+    /// `replaced` are not counted as they're removed before the entry is added.
+    ///
+    /// Only the newly submitted txs are checked, the txs re-added on reorg are not.
+    pub(crate) fn check_lock_limits(
+        &self,
+        entry: &TxEntry,
+        replaced: &HashSet<ProposalShortId>,
+    ) -> Result<(), Reject> {
+        for lock_hash in input_lock_hashes(entry) {
+            let (count, size) = self.lock_usages.get(&lock_hash).map_or((0, 0), |usage| {
+                replaced
+                    .iter()
+                    .filter(|id| usage.txs.contains(id))
+                    .filter_map(|id| self.get(id))
+                    .fold((usage.txs.len(), usage.size), |(count, size), old| {
+                        (count.saturating_sub(1), size.saturating_sub(old.size))
+                    })
+            });
+            if self.max_tx_count_per_lock != 0 && count >= self.max_tx_count_per_lock {
+                return Err(Reject::ExceededLockScriptLimit(
+                    lock_hash,
+                    format!(
+                        "the count of in-pool transactions reached the limit {}",
+                        self.max_tx_count_per_lock
+                    ),
+                ));
+            }
+            let size = size.saturating_add(entry.size);
+            if self.max_tx_size_per_lock != 0 && size > self.max_tx_size_per_lock {
+                return Err(Reject::ExceededLockScriptLimit(
+                    lock_hash,
+                    format!(
+                        "the size of in-pool transactions {} exceeded the limit {}",
+                        size, self.max_tx_size_per_lock
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn record_lock_usages(&mut self, entry: &TxEntry) {
+        let id = entry.proposal_short_id();
+        for lock_hash in input_lock_hashes(entry) {
+            let usage = self.lock_usages.entry(lock_hash.clone()).or_default();
+            self.lock_usages_by_size
+                .remove(&(usage.size, lock_hash.clone()));
+            self.lock_usages_by_count
+                .remove(&(usage.txs.len(), lock_hash.clone()));
+            usage.txs.insert(id.clone());
+            usage.size = usage.size.saturating_add(entry.size);
+            self.lock_usages_by_size
+                .insert((usage.size, lock_hash.clone()));
+            self.lock_usages_by_count
+                .insert((usage.txs.len(), lock_hash));
+        }
+    }
+
+    fn remove_lock_usages(&mut self, entry: &TxEntry) {
+        let id = entry.proposal_short_id();
+        for lock_hash in input_lock_hashes(entry) {
+            if let Some(usage) = self.lock_usages.get_mut(&lock_hash) {
+                self.lock_usages_by_size
+                    .remove(&(usage.size, lock_hash.clone()));
+                self.lock_usages_by_count
+                    .remove(&(usage.txs.len(), lock_hash.clone()));
+                usage.txs.remove(&id);
+                usage.size = usage.size.saturating_sub(entry.size);
+                if usage.txs.is_empty() {
+                    self.lock_usages.remove(&lock_hash);
+                } else {
+                    self.lock_usages_by_size
+                        .insert((usage.size, lock_hash.clone()));
+                    self.lock_usages_by_count
+                        .insert((usage.txs.len(), lock_hash));
+                }
+            }
+        }
+    }
+
+    fn journal_add(&mut self, entry: &TxEntry) {
+        if let Some(ref mut journal) = self.journal {
+            if let Err(err) = journal.append_add(entry) {
+                error!("Failed to journal the added entry, cause: {}", err);
+            }
+            if journal.needs_compaction(self.entries.len() + self.drained.len()) {
+                let entries = self
+                    .entries
+                    .iter()
+                    .map(|(_, e)| &e.inner)
+                    .chain(self.drained.values());
+                if let Err(err) = journal.compact(entries) {
+                    error!("Failed to compact the tx-pool journal, cause: {}", err);
+                }
+            }
+        }
+    }
+
+    fn journal_remove(&mut self, id: &ProposalShortId) {
+        if let Some(ref mut journal) = self.journal {
+            if let Err(err) = journal.append_remove(id) {
+                error!("Failed to journal the removed entry, cause: {}", err);
+            }
+        }
+    }
+
     fn remove_entry_edges(&mut self, entry: &TxEntry) {
         for i in entry.transaction().input_pts_iter() {
             // release input record
@@ -520,6 +830,28 @@ impl PoolMap {
         self.edges.header_deps.remove(&id);
     }
 
+    fn index_salted_short_ids(&mut self, entry: &TxEntry) {
+        let tx_hash = entry.transaction().hash();
+        for (key, index) in self.salted_short_ids.iter_mut() {
+            index.insert(
+                key.short_id(&tx_hash).to_proposal_short_id(),
+                entry.proposal_short_id(),
+            );
+        }
+    }
+
+    fn remove_salted_short_ids(&mut self, entry: &TxEntry) {
+        let tx_hash = entry.transaction().hash();
+        let id = entry.proposal_short_id();
+        for (key, index) in self.salted_short_ids.iter_mut() {
+            let short_id = key.short_id(&tx_hash).to_proposal_short_id();
+            // keep the salted short id if it collides with another entry
+            if index.get(&short_id) == Some(&id) {
+                index.remove(&short_id);
+            }
+        }
+    }
+
     fn insert_entry(&mut self, entry: &TxEntry, status: Status) {
         let tx_short_id = entry.proposal_short_id();
         let score = entry.as_score_key();
@@ -591,3 +923,13 @@ impl PoolMap {
         self.total_tx_cycles = total_tx_cycles;
     }
 }
+
+/// The distinct lock script hashes of the input cells
+fn input_lock_hashes(entry: &TxEntry) -> HashSet<Byte32> {
+    entry
+        .rtx
+        .resolved_inputs
+        .iter()
+        .map(|cell| cell.cell_output.calc_lock_hash())
+        .collect()
+}
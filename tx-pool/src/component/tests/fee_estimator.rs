@@ -0,0 +1,130 @@
+use ckb_hash::blake2b_256;
+use ckb_types::{
+    core::{tx_pool::EstimateMode, FeeRate},
+    packed::{Byte32, ProposalShortId},
+};
+
+use crate::component::fee_estimator::{FeeEstimator, MAX_TARGET_BLOCKS};
+
+fn short_id(seed: u64) -> ProposalShortId {
+    ProposalShortId::from_tx_hash(&Byte32::new(blake2b_256(seed.to_le_bytes())))
+}
+
+#[test]
+fn test_insufficient_data() {
+    let estimator = FeeEstimator::default();
+    assert_eq!(
+        estimator.estimate_fee_rate(1, EstimateMode::Economical),
+        None
+    );
+    assert_eq!(
+        estimator.estimate_fee_rate(MAX_TARGET_BLOCKS, EstimateMode::Conservative),
+        None
+    );
+    assert_eq!(
+        estimator.estimate_fee_rate(0, EstimateMode::Economical),
+        None
+    );
+    assert_eq!(
+        estimator.estimate_fee_rate(MAX_TARGET_BLOCKS + 1, EstimateMode::Economical),
+        None
+    );
+}
+
+#[test]
+fn test_estimate_fee_rate() {
+    let mut estimator = FeeEstimator::default();
+    let high_fee_rate = FeeRate::from_u64(10_000);
+    let low_fee_rate = FeeRate::from_u64(1_000);
+    let txs_per_block = 10;
+
+    // the high fee rate txs are committed 2 blocks after they entered the pool,
+    // while the low fee rate txs keep waiting in the pool
+    let mut seed = 0u64;
+    let mut high_txs = Vec::new();
+    for number in 1..=100 {
+        let mut entered = Vec::new();
+        for _ in 0..txs_per_block {
+            let high = short_id(seed);
+            let low = short_id(seed + 1);
+            seed += 2;
+            estimator.track_tx(high.clone(), high_fee_rate, number - 1);
+            estimator.track_tx(low, low_fee_rate, number - 1);
+            entered.push(high);
+        }
+        high_txs.push(entered);
+
+        let committed = if high_txs.len() >= 2 {
+            high_txs[high_txs.len() - 2].clone()
+        } else {
+            Vec::new()
+        };
+        estimator.process_block(number, committed.iter());
+        estimator.remove_untracked(|_| true);
+    }
+
+    assert_eq!(
+        estimator.estimate_fee_rate(2, EstimateMode::Economical),
+        Some(high_fee_rate)
+    );
+    assert_eq!(
+        estimator.estimate_fee_rate(10, EstimateMode::Conservative),
+        Some(high_fee_rate)
+    );
+    // no tx is committed within 1 block
+    assert_eq!(
+        estimator.estimate_fee_rate(1, EstimateMode::Economical),
+        None
+    );
+}
+
+#[test]
+fn test_ignore_processed_blocks() {
+    let mut estimator = FeeEstimator::default();
+    let id = short_id(0);
+    estimator.track_tx(id.clone(), FeeRate::from_u64(5_000), 10);
+    estimator.process_block(12, Vec::new().iter());
+
+    // the blocks attached in a reorg are not higher than the best height
+    estimator.process_block(11, [id.clone()].iter());
+    estimator.process_block(12, [id].iter());
+    // the tx is still tracked, so it is dropped as failed
+    estimator.remove_untracked(|_| false);
+    assert_eq!(
+        estimator.estimate_fee_rate(1, EstimateMode::Economical),
+        None
+    );
+}
+
+#[test]
+fn test_untrack_tx() {
+    let stats = |untrack: bool| {
+        let mut estimator = FeeEstimator::default();
+        let id = short_id(0);
+        estimator.track_tx(id.clone(), FeeRate::from_u64(5_000), 0);
+        if untrack {
+            estimator.untrack_tx(&id);
+        }
+        estimator.process_block(10, Vec::new().iter());
+        estimator.remove_untracked(|_| false);
+        serde_json::to_value(&estimator).unwrap()
+    };
+    let untracked = {
+        let mut estimator = FeeEstimator::default();
+        estimator.process_block(10, Vec::new().iter());
+        serde_json::to_value(&estimator).unwrap()
+    };
+
+    // a tx dropped from the pool is recorded as failed
+    assert_ne!(stats(false), untracked);
+    // an untracked tx is not recorded at all
+    assert_eq!(stats(true), untracked);
+}
+
+#[test]
+fn test_restore() {
+    let estimator = FeeEstimator::default();
+    let json = serde_json::to_string(&estimator).unwrap();
+    let persisted: FeeEstimator = serde_json::from_str(&json).unwrap();
+    assert!(FeeEstimator::restore(persisted).is_some());
+}
@@ -0,0 +1,174 @@
+use ckb_types::{
+    core::{Capacity, TransactionView},
+    h256,
+    packed::Byte32,
+    prelude::*,
+};
+use std::fs::{self, OpenOptions};
+use std::io::Write as _;
+
+use crate::component::{
+    entry::TxEntry,
+    journal::{Journal, JournalEntry},
+    tests::util::build_tx,
+};
+
+fn build_entry(tx: TransactionView, cycles: u64, timestamp: u64) -> TxEntry {
+    let mut entry = TxEntry::dummy_resolve(tx, cycles, Capacity::zero(), 100);
+    entry.timestamp = timestamp;
+    entry
+}
+
+fn recovered_hashes(entries: &[JournalEntry]) -> Vec<Byte32> {
+    entries.iter().map(|entry| entry.tx.hash()).collect()
+}
+
+#[test]
+fn test_recover() {
+    let tmp_dir = tempfile::Builder::new().tempdir().unwrap();
+    let path = tmp_dir.path().join("journal");
+
+    let tx1 = build_tx(vec![(&h256!("0x1").pack(), 0)], 1);
+    let tx2 = build_tx(vec![(&h256!("0x2").pack(), 0)], 1);
+    let tx3 = build_tx(vec![(&h256!("0x3").pack(), 0)], 1);
+    {
+        let (mut journal, entries) = Journal::open(path.clone()).unwrap();
+        assert!(entries.is_empty());
+        journal
+            .append_add(&build_entry(tx1.clone(), 100, 1))
+            .unwrap();
+        journal
+            .append_add(&build_entry(tx2.clone(), 200, 2))
+            .unwrap();
+        journal
+            .append_add(&build_entry(tx3.clone(), 300, 3))
+            .unwrap();
+        journal.append_remove(&tx2.proposal_short_id()).unwrap();
+    }
+
+    let (_journal, entries) = Journal::open(path).unwrap();
+    assert_eq!(recovered_hashes(&entries), vec![tx1.hash(), tx3.hash()]);
+    assert_eq!(entries[0].cycles, 100);
+    assert_eq!(entries[0].timestamp, 1);
+    assert_eq!(entries[1].cycles, 300);
+    assert_eq!(entries[1].timestamp, 3);
+}
+
+#[test]
+fn test_recover_parents_first() {
+    let tmp_dir = tempfile::Builder::new().tempdir().unwrap();
+    let path = tmp_dir.path().join("journal");
+
+    let parent = build_tx(vec![(&h256!("0x1").pack(), 0)], 1);
+    let child = build_tx(vec![(&parent.hash(), 0)], 1);
+    {
+        // the parent is re-added after its child, e.g. in a reorg
+        let (mut journal, _) = Journal::open(path.clone()).unwrap();
+        journal
+            .append_add(&build_entry(child.clone(), 0, 0))
+            .unwrap();
+        journal
+            .append_add(&build_entry(parent.clone(), 0, 0))
+            .unwrap();
+    }
+
+    let (_journal, entries) = Journal::open(path).unwrap();
+    assert_eq!(
+        recovered_hashes(&entries),
+        vec![parent.hash(), child.hash()]
+    );
+}
+
+#[test]
+fn test_recover_corrupted() {
+    let tmp_dir = tempfile::Builder::new().tempdir().unwrap();
+    let path = tmp_dir.path().join("journal");
+
+    let tx1 = build_tx(vec![(&h256!("0x1").pack(), 0)], 1);
+    let tx2 = build_tx(vec![(&h256!("0x2").pack(), 0)], 1);
+    {
+        let (mut journal, _) = Journal::open(path.clone()).unwrap();
+        journal.append_add(&build_entry(tx1.clone(), 0, 0)).unwrap();
+    }
+    let valid_len = fs::metadata(&path).unwrap().len() as usize;
+    {
+        let (mut journal, _) = Journal::open(path.clone()).unwrap();
+        journal.append_add(&build_entry(tx2, 0, 0)).unwrap();
+    }
+
+    // flip a byte of the last record
+    let mut data = fs::read(&path).unwrap();
+    let last = data.len() - 1;
+    data[last] ^= 0xff;
+    fs::write(&path, &data).unwrap();
+    let (_journal, entries) = Journal::open(path.clone()).unwrap();
+    assert_eq!(recovered_hashes(&entries), vec![tx1.hash()]);
+
+    // the corrupted record is discarded when the journal is opened, so a truncated record
+    // appended later is also detected
+    assert_eq!(fs::metadata(&path).unwrap().len() as usize, valid_len);
+    OpenOptions::new()
+        .append(true)
+        .open(&path)
+        .unwrap()
+        .write_all(&[1, 0, 0])
+        .unwrap();
+    let (_journal, entries) = Journal::open(path).unwrap();
+    assert_eq!(recovered_hashes(&entries), vec![tx1.hash()]);
+}
+
+#[test]
+fn test_compact() {
+    let tmp_dir = tempfile::Builder::new().tempdir().unwrap();
+    let path = tmp_dir.path().join("journal");
+
+    let (mut journal, _) = Journal::open(path.clone()).unwrap();
+    let entries: Vec<_> = (0..10u64)
+        .map(|i| {
+            let tx = build_tx(vec![(&Byte32::new([i as u8; 32]), 0)], 1);
+            build_entry(tx, i, i)
+        })
+        .collect();
+    for entry in &entries {
+        journal.append_add(entry).unwrap();
+        journal.append_remove(&entry.proposal_short_id()).unwrap();
+        journal.append_add(entry).unwrap();
+    }
+    assert!(!journal.needs_compaction(entries.len()));
+    for entry in entries.iter().skip(5) {
+        for _ in 0..200 {
+            journal.append_remove(&entry.proposal_short_id()).unwrap();
+        }
+    }
+    assert!(journal.needs_compaction(entries.len()));
+
+    journal.compact(entries.iter().take(5)).unwrap();
+    assert!(!journal.needs_compaction(5));
+    drop(journal);
+
+    let (_journal, recovered) = Journal::open(path).unwrap();
+    assert_eq!(
+        recovered_hashes(&recovered),
+        entries
+            .iter()
+            .take(5)
+            .map(|entry| entry.transaction().hash())
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_sync() {
+    let tmp_dir = tempfile::Builder::new().tempdir().unwrap();
+    let path = tmp_dir.path().join("journal");
+
+    let tx1 = build_tx(vec![(&h256!("0x1").pack(), 0)], 1);
+    let (mut journal, _) = Journal::open(path.clone()).unwrap();
+    journal.append_add(&build_entry(tx1.clone(), 0, 0)).unwrap();
+    journal.sync().unwrap().blocking_recv().unwrap().unwrap();
+
+    // the queued records are written after the sync responds, while the journal is still open
+    let (_reopened, entries) = Journal::open(path).unwrap();
+    assert_eq!(recovered_hashes(&entries), vec![tx1.hash()]);
+    drop(journal);
+}
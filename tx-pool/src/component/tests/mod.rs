@@ -1,5 +1,7 @@
 mod chunk;
 mod entry;
+mod fee_estimator;
+mod journal;
 mod orphan;
 mod pending;
 mod proposed;
@@ -6,10 +6,15 @@ use crate::component::{
     entry::TxEntry,
     pool_map::{PoolMap, Status},
 };
-use ckb_types::core::Capacity;
-use ckb_types::packed::OutPoint;
-use ckb_types::{h256, packed::Byte32, prelude::*};
+use crate::error::Reject;
+use ckb_app_config::TxPoolConfig;
+use ckb_types::bytes::Bytes;
+use ckb_types::core::cell::{CellMetaBuilder, ResolvedTransaction};
+use ckb_types::core::{Capacity, TransactionView};
+use ckb_types::packed::{CellOutput, OutPoint, Script};
+use ckb_types::{h256, packed::Byte32, prelude::*, utilities::ShortIdKey, H256};
 use std::collections::HashSet;
+use std::sync::Arc;
 use std::time::Duration;
 
 #[test]
@@ -426,3 +431,293 @@ fn test_pool_min_descendants_evict() {
 
     assert!(pool.next_evict_entry(Status::Pending).is_none());
 }
+
+fn resolve_with_lock(tx: TransactionView, lock: &Script, size: usize) -> TxEntry {
+    resolve_with_lock_and_fee(tx, lock, size, Capacity::shannons(100))
+}
+
+fn resolve_with_lock_and_fee(
+    tx: TransactionView,
+    lock: &Script,
+    size: usize,
+    fee: Capacity,
+) -> TxEntry {
+    let resolved_inputs = tx
+        .input_pts_iter()
+        .map(|out_point| {
+            let output = CellOutput::new_builder().lock(lock.clone()).build();
+            CellMetaBuilder::from_cell_output(output, Bytes::new())
+                .out_point(out_point)
+                .build()
+        })
+        .collect();
+    let rtx = ResolvedTransaction {
+        transaction: tx,
+        resolved_cell_deps: vec![],
+        resolved_inputs,
+        resolved_dep_groups: vec![],
+    };
+    TxEntry::new(Arc::new(rtx), MOCK_CYCLES, fee, size)
+}
+
+#[test]
+fn test_lock_limits() {
+    let mut pool = PoolMap::new(1000).with_lock_limits(2, 250);
+    let lock1 = Script::new_builder().args(vec![1u8].pack()).build();
+    let lock2 = Script::new_builder().args(vec![2u8].pack()).build();
+
+    let tx1 = build_tx(vec![(&h256!("0x1").pack(), 0)], 1);
+    let tx2 = build_tx(vec![(&h256!("0x2").pack(), 0)], 1);
+    let tx3 = build_tx(vec![(&h256!("0x3").pack(), 0)], 1);
+    let tx4 = build_tx(vec![(&h256!("0x4").pack(), 0)], 1);
+    let tx5 = build_tx(vec![(&h256!("0x5").pack(), 0)], 1);
+    let no_replaced = HashSet::new();
+
+    assert!(pool
+        .add_entry(resolve_with_lock(tx1.clone(), &lock1, 100), Status::Pending)
+        .unwrap());
+    // exceeded the size limit
+    assert!(matches!(
+        pool.check_lock_limits(&resolve_with_lock(tx2.clone(), &lock1, 200), &no_replaced),
+        Err(Reject::ExceededLockScriptLimit(..))
+    ));
+    let entry2 = resolve_with_lock(tx2.clone(), &lock1, 100);
+    assert!(pool.check_lock_limits(&entry2, &no_replaced).is_ok());
+    assert!(pool.add_entry(entry2, Status::Pending).unwrap());
+    // exceeded the count limit
+    let entry3 = resolve_with_lock(tx3, &lock1, 10);
+    assert!(matches!(
+        pool.check_lock_limits(&entry3, &no_replaced),
+        Err(Reject::ExceededLockScriptLimit(..))
+    ));
+    // the replaced txs are not counted
+    let replaced = HashSet::from([tx2.proposal_short_id()]);
+    assert!(pool.check_lock_limits(&entry3, &replaced).is_ok());
+    // other lock scripts are not affected
+    assert!(pool
+        .check_lock_limits(&resolve_with_lock(tx4, &lock2, 100), &no_replaced)
+        .is_ok());
+
+    // the quota is released after the tx is removed
+    pool.remove_entry(&tx1.proposal_short_id());
+    assert!(pool.check_lock_limits(&entry3, &no_replaced).is_ok());
+    assert!(pool.add_entry(entry3, Status::Pending).unwrap());
+    assert_eq!(pool.lock_usages[&lock1.calc_script_hash()].size, 110);
+
+    // the limits are not checked when the txs are re-added, e.g. on reorg
+    assert!(pool
+        .add_entry(resolve_with_lock(tx1, &lock1, 100), Status::Proposed)
+        .unwrap());
+    assert_eq!(pool.lock_usages[&lock1.calc_script_hash()].txs.len(), 3);
+
+    // no limit
+    let pool = PoolMap::new(1000);
+    assert!(pool
+        .check_lock_limits(&resolve_with_lock(tx5, &lock1, 1000), &no_replaced)
+        .is_ok());
+}
+
+#[test]
+fn test_evict_heaviest_lock() {
+    let mut pool = PoolMap::new(1000);
+    let lock1 = Script::new_builder().args(vec![1u8].pack()).build();
+    let lock2 = Script::new_builder().args(vec![2u8].pack()).build();
+
+    let tx1 = build_tx(vec![(&h256!("0x1").pack(), 0)], 1);
+    let tx2 = build_tx(vec![(&h256!("0x2").pack(), 0)], 1);
+    let tx3 = build_tx(vec![(&h256!("0x3").pack(), 0)], 1);
+
+    // tx1 has the lowest fee rate, but lock2 occupies the most bytes
+    assert!(pool
+        .add_entry(resolve_with_lock(tx1.clone(), &lock1, 300), Status::Pending)
+        .unwrap());
+    assert!(pool
+        .add_entry(resolve_with_lock(tx2.clone(), &lock2, 200), Status::Pending)
+        .unwrap());
+    assert!(pool
+        .add_entry(resolve_with_lock(tx3.clone(), &lock2, 150), Status::Pending)
+        .unwrap());
+
+    // no lock script is over the quota
+    assert!(pool
+        .next_evict_entry_of_heaviest_lock(350, usize::MAX)
+        .is_none());
+
+    let e1 = pool
+        .next_evict_entry_of_heaviest_lock(300, usize::MAX)
+        .unwrap();
+    assert_eq!(e1, tx2.proposal_short_id());
+    pool.remove_entry(&e1);
+    assert!(pool
+        .next_evict_entry_of_heaviest_lock(300, usize::MAX)
+        .is_none());
+
+    let e2 = pool
+        .next_evict_entry_of_heaviest_lock(0, usize::MAX)
+        .unwrap();
+    assert_eq!(e2, tx1.proposal_short_id());
+    pool.remove_entry(&e2);
+
+    let e3 = pool
+        .next_evict_entry_of_heaviest_lock(0, usize::MAX)
+        .unwrap();
+    assert_eq!(e3, tx3.proposal_short_id());
+    pool.remove_entry(&e3);
+
+    assert!(pool
+        .next_evict_entry_of_heaviest_lock(0, usize::MAX)
+        .is_none());
+    assert!(pool.lock_usages.is_empty());
+    assert!(pool.lock_usages_by_size.is_empty());
+    assert!(pool.lock_usages_by_count.is_empty());
+}
+
+#[test]
+fn test_evict_lock_over_count_quota() {
+    let mut pool = PoolMap::new(1000);
+    let lock1 = Script::new_builder().args(vec![1u8].pack()).build();
+    let lock2 = Script::new_builder().args(vec![2u8].pack()).build();
+
+    let tx1 = build_tx(vec![(&h256!("0x1").pack(), 0)], 1);
+    let tx2 = build_tx(vec![(&h256!("0x2").pack(), 0)], 1);
+    let tx3 = build_tx(vec![(&h256!("0x3").pack(), 0)], 1);
+
+    // lock1 occupies the most bytes, but lock2 has the most txs
+    assert!(pool
+        .add_entry(resolve_with_lock(tx1, &lock1, 500), Status::Pending)
+        .unwrap());
+    assert!(pool
+        .add_entry(resolve_with_lock(tx2.clone(), &lock2, 100), Status::Pending)
+        .unwrap());
+    assert!(pool
+        .add_entry(resolve_with_lock(tx3.clone(), &lock2, 100), Status::Pending)
+        .unwrap());
+
+    assert!(pool.next_evict_entry_of_heaviest_lock(500, 2).is_none());
+
+    // lock1 is not over the size quota, lock2 is over the count quota
+    let e1 = pool.next_evict_entry_of_heaviest_lock(500, 1).unwrap();
+    assert!(e1 == tx2.proposal_short_id() || e1 == tx3.proposal_short_id());
+    pool.remove_entry(&e1);
+    assert!(pool.next_evict_entry_of_heaviest_lock(500, 1).is_none());
+}
+
+#[test]
+fn test_evict_heaviest_lock_of_full_pool_with_lock_limits() {
+    let config = TxPoolConfig::default();
+    let max_tx_size_per_lock = 18_000_000;
+    let mut pool =
+        PoolMap::new(config.max_ancestors_count).with_lock_limits(2_000, max_tx_size_per_lock);
+    let heavy_lock = Script::new_builder().args(vec![0u8].pack()).build();
+
+    // 19 lock scripts at their eviction quota, a half of `max_tx_size_per_lock`
+    let quota = max_tx_size_per_lock / 2;
+    for i in 1..20u8 {
+        let lock = Script::new_builder().args(vec![i].pack()).build();
+        let tx = build_tx(vec![(&H256([i; 32]).pack(), 0)], 1);
+        assert!(pool
+            .add_entry(resolve_with_lock(tx, &lock, quota), Status::Pending)
+            .unwrap());
+    }
+    // the heavy lock script is under `max_tx_size_per_lock` but over its eviction quota, and its
+    // txs pay higher fee rates than the others
+    let heavy_tx_size = quota * 4 / 9;
+    let mut heavy_txs = Vec::new();
+    for i in 20..23u8 {
+        let tx = build_tx(vec![(&H256([i; 32]).pack(), 0)], 1);
+        heavy_txs.push(tx.proposal_short_id());
+        assert!(pool
+            .add_entry(
+                resolve_with_lock(tx, &heavy_lock, heavy_tx_size),
+                Status::Pending
+            )
+            .unwrap());
+    }
+    assert!(pool.total_tx_size > config.max_tx_pool_size);
+
+    let mut evicted = Vec::new();
+    while pool.total_tx_size > config.max_tx_pool_size {
+        let id = pool.next_evict_entry_for_size_limit().unwrap();
+        pool.remove_entry(&id);
+        evicted.push(id);
+    }
+    assert_eq!(evicted.len(), 1);
+    assert!(heavy_txs.contains(&evicted[0]));
+
+    // no lock script is over its quota now, the txs are evicted by the fee rate order
+    let id = pool.next_evict_entry_for_size_limit().unwrap();
+    assert!(!heavy_txs.contains(&id));
+}
+
+#[test]
+fn test_evict_by_fee_rate_of_full_pool_with_default_config() {
+    let config = TxPoolConfig::default();
+    let mut pool = PoolMap::new(config.max_ancestors_count)
+        .with_lock_limits(config.max_tx_count_per_lock, config.max_tx_size_per_lock);
+    let heavy_lock = Script::new_builder().args(vec![0u8].pack()).build();
+
+    // 19 lock scripts take 9mb each
+    for i in 1..20u8 {
+        let lock = Script::new_builder().args(vec![i].pack()).build();
+        let tx = build_tx(vec![(&H256([i; 32]).pack(), 0)], 1);
+        let entry = resolve_with_lock_and_fee(tx, &lock, 9_000_000, Capacity::shannons(9_000_000));
+        assert!(pool.add_entry(entry, Status::Pending).unwrap());
+    }
+    // the heavy lock script takes 20mb, more than 1/10 of the pool, with higher fee rates
+    let mut heavy_txs = Vec::new();
+    for i in 20..25u8 {
+        let tx = build_tx(vec![(&H256([i; 32]).pack(), 0)], 1);
+        heavy_txs.push(tx.proposal_short_id());
+        let entry =
+            resolve_with_lock_and_fee(tx, &heavy_lock, 4_000_000, Capacity::shannons(8_000_000));
+        assert!(pool.add_entry(entry, Status::Pending).unwrap());
+    }
+    assert!(pool.total_tx_size > config.max_tx_pool_size);
+
+    // the per-lock limits are disabled by default, the txs are evicted by the fee rate order
+    let mut evicted = Vec::new();
+    while pool.total_tx_size > config.max_tx_pool_size {
+        let id = pool.next_evict_entry_for_size_limit().unwrap();
+        pool.remove_entry(&id);
+        evicted.push(id);
+    }
+    assert_eq!(evicted.len(), 2);
+    assert!(evicted.iter().all(|id| !heavy_txs.contains(id)));
+}
+
+#[test]
+fn test_get_by_salted_short_ids() {
+    let mut pool = PoolMap::new(100);
+    let tx1 = build_tx(vec![(&Byte32::zero(), 1)], 1);
+    let tx2 = build_tx(vec![(&h256!("0x2").pack(), 1)], 1);
+    let tx3 = build_tx(vec![(&h256!("0x3").pack(), 1)], 1);
+    let entry1 = TxEntry::dummy_resolve(tx1.clone(), MOCK_CYCLES, MOCK_FEE, MOCK_SIZE);
+    let entry2 = TxEntry::dummy_resolve(tx2.clone(), MOCK_CYCLES, MOCK_FEE, MOCK_SIZE);
+    let entry3 = TxEntry::dummy_resolve(tx3.clone(), MOCK_CYCLES, MOCK_FEE, MOCK_SIZE);
+    assert!(pool.add_entry(entry1, Status::Pending).unwrap());
+    assert!(pool.add_entry(entry2, Status::Pending).unwrap());
+
+    let key = ShortIdKey::new(&h256!("0x1").pack(), 42);
+    let short_id = |tx: &TransactionView| key.short_id(&tx.hash()).to_proposal_short_id();
+    let short_ids: HashSet<_> = [&tx1, &tx2, &tx3].into_iter().map(short_id).collect();
+
+    let found = pool.get_by_salted_short_ids(&key, &short_ids);
+    assert_eq!(found.len(), 2);
+    assert_eq!(found[&short_id(&tx1)].inner.transaction(), &tx1);
+    assert_eq!(found[&short_id(&tx2)].inner.transaction(), &tx2);
+    assert_eq!(pool.salted_short_ids.len(), 1);
+
+    // the index of the key is updated by the added and removed entries
+    assert!(pool.add_entry(entry3, Status::Pending).unwrap());
+    pool.remove_entry(&tx1.proposal_short_id());
+    let index = pool.salted_short_ids.peek(&key).unwrap();
+    assert_eq!(index.len(), 2);
+    assert!(!index.contains_key(&short_id(&tx1)));
+    let found = pool.get_by_salted_short_ids(&key, &short_ids);
+    assert_eq!(found.len(), 2);
+    assert_eq!(found[&short_id(&tx2)].inner.transaction(), &tx2);
+    assert_eq!(found[&short_id(&tx3)].inner.transaction(), &tx3);
+
+    pool.clear();
+    assert!(pool.salted_short_ids.is_empty());
+}
@@ -15,7 +15,9 @@ mod util;
 
 pub use ckb_jsonrpc_types::BlockTemplate;
 pub use component::entry::TxEntry;
+pub use component::fee_estimator::MAX_TARGET_BLOCKS;
 pub use pool::TxPool;
 pub use process::PlugTarget;
 pub use service::{TxPoolController, TxPoolServiceBuilder};
 pub use tokio::sync::RwLock as TokioRwLock;
+pub use util::MAX_PACKAGE_TXS_COUNT;
@@ -1,22 +1,36 @@
+use crate::component::fee_estimator::FeeEstimator;
+use crate::component::journal::{Journal, JournalEntry};
 use crate::TxPool;
 use ckb_error::{AnyError, OtherError};
-use ckb_types::{
-    core::TransactionView,
-    packed::{TransactionVec, TransactionVecReader},
-    prelude::*,
-};
+use ckb_types::{core::TransactionView, packed::TransactionVecReader, prelude::*};
 use std::{
-    fs::OpenOptions,
-    io::{Read as _, Write as _},
+    fs::{self, OpenOptions},
+    io::Read as _,
+    path::{Path, PathBuf},
 };
 
-/// The version of the persisted tx-pool data.
+/// The version of the persisted tx-pool data, which was dumped on shutdown before the journal
+/// was introduced.
 pub(crate) const VERSION: u32 = 1;
 
+/// The version of the tx-pool journal.
+pub(crate) const JOURNAL_VERSION: u32 = 1;
+
+/// The version of the persisted fee estimator data.
+pub(crate) const FEE_ESTIMATOR_VERSION: u32 = 1;
+
 impl TxPool {
-    pub(crate) fn load_from_file(&self) -> Result<Vec<TransactionView>, AnyError> {
+    /// Returns the path of the persisted data file, which was dumped before the journal was
+    /// introduced.
+    pub(crate) fn persisted_data_file(&self) -> PathBuf {
         let mut persisted_data_file = self.config.persisted_data.clone();
         persisted_data_file.set_extension(format!("v{VERSION}"));
+        persisted_data_file
+    }
+
+    /// Loads the txs from the persisted data file, the file is kept until they're journaled.
+    pub(crate) fn load_from_file(&self) -> Result<Vec<TransactionView>, AnyError> {
+        let persisted_data_file = self.persisted_data_file();
 
         if persisted_data_file.exists() {
             let mut file = OpenOptions::new()
@@ -54,9 +68,62 @@ impl TxPool {
         }
     }
 
-    pub(crate) fn save_into_file(&mut self) -> Result<(), AnyError> {
+    /// Opens the journal of the pool, returns the entries recovered from it.
+    pub(crate) fn open_journal(&mut self) -> Result<Vec<JournalEntry>, AnyError> {
+        let mut journal_file = self.config.persisted_data.clone();
+        journal_file.set_extension(format!("journal.v{JOURNAL_VERSION}"));
+
+        let (journal, entries) = Journal::open(journal_file)?;
+        self.pool_map.journal = Some(journal);
+        Ok(entries)
+    }
+
+    pub(crate) fn load_fee_estimator_from_file(&mut self) -> Result<(), AnyError> {
         let mut persisted_data_file = self.config.persisted_data.clone();
-        persisted_data_file.set_extension(format!("v{VERSION}"));
+        persisted_data_file.set_extension(format!("fee_estimator.v{FEE_ESTIMATOR_VERSION}"));
+
+        if persisted_data_file.exists() {
+            let file = OpenOptions::new()
+                .read(true)
+                .open(&persisted_data_file)
+                .map_err(|err| {
+                    let errmsg = format!(
+                        "Failed to open the fee estimator persisted data file [{persisted_data_file:?}], cause: {err}"
+                    );
+                    OtherError::new(errmsg)
+                })?;
+            let persisted: FeeEstimator = serde_json::from_reader(file).map_err(|err| {
+                let errmsg = format!(
+                    "The fee estimator persisted data file [{persisted_data_file:?}] is broken, cause: {err}"
+                );
+                OtherError::new(errmsg)
+            })?;
+            self.fee_estimator = FeeEstimator::restore(persisted).ok_or_else(|| {
+                let errmsg = format!(
+                    "The fee estimator persisted data file [{persisted_data_file:?}] is incompatible"
+                );
+                OtherError::new(errmsg)
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Removes the persisted data file, after its txs are journaled.
+    pub(crate) fn remove_persisted_data_file(persisted_data_file: &Path) -> Result<(), AnyError> {
+        if persisted_data_file.exists() {
+            fs::remove_file(persisted_data_file).map_err(|err| {
+                let errmsg = format!(
+                    "Failed to remove the tx-pool persisted data file [{persisted_data_file:?}], cause: {err}"
+                );
+                OtherError::new(errmsg)
+            })?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn save_fee_estimator_into_file(&self) -> Result<(), AnyError> {
+        let mut persisted_data_file = self.config.persisted_data.clone();
+        persisted_data_file.set_extension(format!("fee_estimator.v{FEE_ESTIMATOR_VERSION}"));
 
         let mut file = OpenOptions::new()
             .create(true)
@@ -65,24 +132,20 @@ impl TxPool {
             .open(&persisted_data_file)
             .map_err(|err| {
                 let errmsg = format!(
-                    "Failed to open the tx-pool persisted data file [{persisted_data_file:?}], cause: {err}"
+                    "Failed to open the fee estimator persisted data file [{persisted_data_file:?}], cause: {err}"
                 );
                 OtherError::new(errmsg)
             })?;
 
-        let txs = TransactionVec::new_builder()
-            .extend(self.drain_all_transactions().iter().map(|tx| tx.data()))
-            .build();
-
-        file.write_all(txs.as_slice()).map_err(|err| {
+        serde_json::to_writer(&mut file, &self.fee_estimator).map_err(|err| {
             let errmsg = format!(
-                "Failed to write the tx-pool persisted data into file [{persisted_data_file:?}], cause: {err}"
+                "Failed to write the fee estimator persisted data into file [{persisted_data_file:?}], cause: {err}"
             );
             OtherError::new(errmsg)
         })?;
         file.sync_all().map_err(|err| {
             let errmsg = format!(
-                "Failed to sync the tx-pool persisted data file [{persisted_data_file:?}], cause: {err}"
+                "Failed to sync the fee estimator persisted data file [{persisted_data_file:?}], cause: {err}"
             );
             OtherError::new(errmsg)
         })?;
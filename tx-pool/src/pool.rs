@@ -3,6 +3,7 @@ extern crate rustc_hash;
 extern crate slab;
 use super::component::{commit_txs_scanner::CommitTxsScanner, TxEntry};
 use crate::callback::Callbacks;
+use crate::component::fee_estimator::FeeEstimator;
 use crate::component::pool_map::{PoolEntry, PoolMap, Status};
 use crate::component::recent_reject::RecentReject;
 use crate::error::Reject;
@@ -18,12 +19,13 @@ use ckb_types::{
     core::{
         cell::{resolve_transaction, OverlayCellChecker, OverlayCellProvider, ResolvedTransaction},
         tx_pool::{TxPoolEntryInfo, TxPoolIds},
-        Capacity, Cycle, TransactionView, UncleBlockView,
+        BlockNumber, Capacity, Cycle, TransactionView, UncleBlockView,
     },
     packed::{Byte32, ProposalShortId},
+    utilities::ShortIdKey,
 };
 use lru::LruCache;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 const COMMITTED_HASH_CACHE_SIZE: usize = 100_000;
@@ -41,6 +43,8 @@ pub struct TxPool {
     pub recent_reject: Option<RecentReject>,
     // expiration milliseconds,
     pub(crate) expiry: u64,
+    /// fee estimator tracking the entries of the pool
+    pub(crate) fee_estimator: FeeEstimator,
 }
 
 impl TxPool {
@@ -49,12 +53,14 @@ impl TxPool {
         let recent_reject = Self::build_recent_reject(&config);
         let expiry = config.expiry_hours as u64 * 60 * 60 * 1000;
         TxPool {
-            pool_map: PoolMap::new(config.max_ancestors_count),
+            pool_map: PoolMap::new(config.max_ancestors_count)
+                .with_lock_limits(config.max_tx_count_per_lock, config.max_tx_size_per_lock),
             committed_txs_hash_cache: LruCache::new(COMMITTED_HASH_CACHE_SIZE),
             config,
             snapshot,
             recent_reject,
             expiry,
+            fee_estimator: FeeEstimator::default(),
         }
     }
 
@@ -184,6 +190,20 @@ impl TxPool {
         }
     }
 
+    /// Updates the fee estimator with the transactions committed in the attached blocks, and
+    /// stops tracking the transactions removed from the pool.
+    pub(crate) fn update_fee_estimator(
+        &mut self,
+        attached_blocks: &[(BlockNumber, Vec<ProposalShortId>)],
+    ) {
+        for (number, committed) in attached_blocks {
+            self.fee_estimator.process_block(*number, committed.iter());
+        }
+        let pool_map = &self.pool_map;
+        self.fee_estimator
+            .remove_untracked(|id| pool_map.get_by_id(id).is_some());
+    }
+
     fn resolve_conflict_header_dep(
         &mut self,
         detached_headers: &HashSet<Byte32>,
@@ -231,16 +251,11 @@ impl TxPool {
     }
 
     // Remove transactions from the pool until total size <= size_limit.
+    // If a lock script is over its eviction quota, a share of the per-lock limits, the txs
+    // spending it are evicted first, otherwise the txs are evicted by the fee rate order.
     pub(crate) fn limit_size(&mut self, callbacks: &Callbacks) {
         while self.pool_map.total_tx_size > self.config.max_tx_pool_size {
-            let next_evict_entry = || {
-                self.pool_map
-                    .next_evict_entry(Status::Pending)
-                    .or_else(|| self.pool_map.next_evict_entry(Status::Gap))
-                    .or_else(|| self.pool_map.next_evict_entry(Status::Proposed))
-            };
-
-            if let Some(id) = next_evict_entry() {
+            if let Some(id) = self.pool_map.next_evict_entry_for_size_limit() {
                 let removed = self.pool_map.remove_entry_and_descendants(&id);
                 for entry in removed {
                     let tx_hash = entry.transaction().hash();
@@ -376,6 +391,19 @@ impl TxPool {
         })
     }
 
+    /// Returns txs from tx-pool whose salted short ids are in `short_ids`.
+    pub(crate) fn get_txs_by_salted_short_ids(
+        &mut self,
+        key: &ShortIdKey,
+        short_ids: &HashSet<ProposalShortId>,
+    ) -> HashMap<ProposalShortId, TransactionView> {
+        self.pool_map
+            .get_by_salted_short_ids(key, short_ids)
+            .into_iter()
+            .map(|(short_id, entry)| (short_id, entry.inner.transaction().clone()))
+            .collect()
+    }
+
     pub(crate) fn get_ids(&self) -> TxPoolIds {
         let pending = self
             .pool_map
@@ -408,30 +436,33 @@ impl TxPool {
         TxPoolEntryInfo { pending, proposed }
     }
 
+    /// Drains all the txs to be re-processed, they're kept in the journal until they're released
+    /// by `PoolMap::release_drained`.
     pub(crate) fn drain_all_transactions(&mut self) -> Vec<TransactionView> {
-        let mut txs = CommitTxsScanner::new(&self.pool_map)
+        let mut entries = CommitTxsScanner::new(&self.pool_map)
             .txs_to_commit(usize::MAX, Cycle::MAX)
-            .0
-            .into_iter()
-            .map(|tx_entry| tx_entry.into_transaction())
-            .collect::<Vec<_>>();
+            .0;
         let mut pending = self
             .pool_map
             .entries
             .remove_by_status(&Status::Pending)
             .into_iter()
-            .map(|e| e.inner.into_transaction())
+            .map(|e| e.inner)
             .collect::<Vec<_>>();
-        txs.append(&mut pending);
+        entries.append(&mut pending);
         let mut gap = self
             .pool_map
             .entries
             .remove_by_status(&Status::Gap)
             .into_iter()
-            .map(|e| e.inner.into_transaction())
+            .map(|e| e.inner)
             .collect::<Vec<_>>();
-        txs.append(&mut gap);
-        self.pool_map.clear();
+        entries.append(&mut gap);
+        let txs = entries
+            .iter()
+            .map(|entry| entry.transaction().clone())
+            .collect();
+        self.pool_map.drain(entries);
         txs
     }
 
@@ -578,6 +609,47 @@ impl TxPool {
         Ok(conflict_ids)
     }
 
+    /// Check the RBF rules of every transaction in a package, each member must pay for its own
+    /// conflicts, and no member can spend or depend on the outputs of the replaced txs
+    pub(crate) fn check_package_rbf<'a>(
+        &self,
+        snapshot: &Snapshot,
+        entries: impl Iterator<Item = &'a TxEntry> + Clone,
+    ) -> Result<HashSet<ProposalShortId>, Reject> {
+        let mut conflict_ids = HashSet::new();
+        for entry in entries.clone() {
+            conflict_ids.extend(self.check_rbf(snapshot, entry)?);
+        }
+        if conflict_ids.is_empty() {
+            return Ok(conflict_ids);
+        }
+
+        let mut replaced_ids = HashSet::new();
+        for id in conflict_ids.iter() {
+            replaced_ids.extend(self.pool_map.calc_descendants(id));
+            replaced_ids.insert(id.clone());
+        }
+        let replaced_hashes: HashSet<Byte32> = replaced_ids
+            .iter()
+            .filter_map(|id| self.get_tx_from_pool(id))
+            .map(|tx| tx.hash())
+            .collect();
+        for entry in entries {
+            let tx = entry.transaction();
+            if tx
+                .input_pts_iter()
+                .chain(tx.cell_deps_iter().map(|dep| dep.out_point()))
+                .any(|pt| replaced_hashes.contains(&pt.tx_hash()))
+            {
+                return Err(Reject::RBFRejected(
+                    "package contains inputs or cell deps from to be replaced txs".to_string(),
+                ));
+            }
+        }
+
+        Ok(conflict_ids)
+    }
+
     /// query the details of a transaction in the pool, only for trouble shooting
     pub(crate) fn get_tx_detail(&self, id: &ProposalShortId) -> Option<PoolTxDetailInfo> {
         if let Some(entry) = self.pool_map.get_by_id(id) {
@@ -1,17 +1,20 @@
 use crate::callback::Callbacks;
 use crate::component::entry::TxEntry;
+use crate::component::journal::JournalEntry;
 use crate::component::orphan::Entry as OrphanEntry;
 use crate::component::pool_map::Status;
 use crate::error::Reject;
 use crate::pool::TxPool;
+use crate::pool_cell::PoolCell;
 use crate::service::{BlockAssemblerMessage, TxPoolService, TxVerificationResult};
 use crate::try_or_return_with_snapshot;
 use crate::util::{
-    after_delay_window, check_tx_fee, check_txid_collision, is_missing_input,
-    non_contextual_verify, time_relative_verify, verify_rtx,
+    after_delay_window, calculate_tx_fee, check_package, check_package_fee, check_tx_fee,
+    check_txid_collision, is_missing_input, non_contextual_verify, time_relative_verify,
+    verify_rtx,
 };
 use ckb_chain_spec::consensus::MAX_BLOCK_PROPOSALS_LIMIT;
-use ckb_error::{AnyError, InternalErrorKind};
+use ckb_error::{AnyError, InternalErrorKind, OtherError};
 use ckb_jsonrpc_types::BlockTemplate;
 use ckb_logger::Level::Trace;
 use ckb_logger::{debug, error, info, log_enabled_target, trace_target};
@@ -21,7 +24,12 @@ use ckb_store::data_loader_wrapper::AsDataLoader;
 use ckb_store::ChainStore;
 use ckb_types::core::error::OutPointError;
 use ckb_types::{
-    core::{cell::ResolvedTransaction, BlockView, Capacity, Cycle, HeaderView, TransactionView},
+    core::{
+        cell::{
+            resolve_transaction, OverlayCellProvider, ResolvedTransaction, TransactionsProvider,
+        },
+        BlockNumber, BlockView, Capacity, Cycle, HeaderView, TransactionView,
+    },
     packed::{Byte32, ProposalShortId},
 };
 use ckb_util::LinkedHashSet;
@@ -131,6 +139,16 @@ impl TxPoolService {
                     time_relative_verify(snapshot, Arc::clone(&entry.rtx), tx_env)?;
                 }
 
+                let replaced = conflicts
+                    .iter()
+                    .flat_map(|id| {
+                        let mut ids = tx_pool.pool_map.calc_descendants(id);
+                        ids.insert(id.clone());
+                        ids
+                    })
+                    .collect();
+                tx_pool.pool_map.check_lock_limits(&entry, &replaced)?;
+
                 // try to remove conflicted tx here
                 for id in conflicts.iter() {
                     let removed = tx_pool.pool_map.remove_entry_and_descendants(id);
@@ -291,7 +309,10 @@ impl TxPoolService {
         remote: Option<(Cycle, PeerIndex)>,
     ) -> Result<(), Reject> {
         // non contextual verify first
-        self.non_contextual_verify(&tx, None)?;
+        if let Err(reject) = self.non_contextual_verify(&tx, None) {
+            self.callbacks.call_submit_reject(&tx.hash(), &reject);
+            return Err(reject);
+        }
 
         if self.chunk_contains(&tx).await {
             return Err(Reject::Duplicated(tx.hash()));
@@ -328,7 +349,10 @@ impl TxPoolService {
         remote: Option<(Cycle, PeerIndex)>,
     ) -> Result<Completed, Reject> {
         // non contextual verify first
-        self.non_contextual_verify(&tx, remote)?;
+        if let Err(reject) = self.non_contextual_verify(&tx, remote) {
+            self.callbacks.call_submit_reject(&tx.hash(), &reject);
+            return Err(reject);
+        }
 
         if self.chunk_contains(&tx).await || self.orphan_contains(&tx).await {
             return Err(Reject::Duplicated(tx.hash()));
@@ -384,20 +408,7 @@ impl TxPoolService {
         ret: &Result<Completed, Reject>,
     ) {
         let tx_hash = tx.hash();
-
-        // The network protocol is switched after tx-pool confirms the cache,
-        // there will be no problem with the current state as the choice of the broadcast protocol.
-        let with_vm_2023 = {
-            let epoch = snapshot
-                .tip_header()
-                .epoch()
-                .minimum_epoch_number_after_n_blocks(1);
-
-            self.consensus
-                .hardfork_switch
-                .ckb2023
-                .is_vm_version_2_and_syscalls_3_enabled(epoch)
-        };
+        let with_vm_2023 = self.with_vm_2023(snapshot);
 
         // log tx verification result for monitor node
         if log_enabled_target!("ckb_tx_monitor", Trace) {
@@ -426,6 +437,9 @@ impl TxPoolService {
                     if is_missing_input(reject) && all_inputs_is_unknown(snapshot, &tx) {
                         self.add_orphan(tx, peer, declared_cycle).await;
                     } else {
+                        if !matches!(reject, Reject::Duplicated(_)) {
+                            self.callbacks.call_submit_reject(&tx_hash, reject);
+                        }
                         if reject.is_malformed_tx() {
                             self.ban_malformed(peer, format!("reject {reject}"));
                         }
@@ -466,6 +480,7 @@ impl TxPoolService {
                     }
                     Err(reject) => {
                         debug!("after_process {} reject: {} ", tx_hash, reject);
+                        self.callbacks.call_submit_reject(&tx_hash, reject);
                         if matches!(
                             reject,
                             Reject::Resolve(..)
@@ -480,6 +495,20 @@ impl TxPoolService {
         }
     }
 
+    // The network protocol is switched after tx-pool confirms the cache,
+    // there will be no problem with the current state as the choice of the broadcast protocol.
+    fn with_vm_2023(&self, snapshot: &Snapshot) -> bool {
+        let epoch = snapshot
+            .tip_header()
+            .epoch()
+            .minimum_epoch_number_after_n_blocks(1);
+
+        self.consensus
+            .hardfork_switch
+            .ckb2023
+            .is_vm_version_2_and_syscalls_3_enabled(epoch)
+    }
+
     pub(crate) async fn add_orphan(
         &self,
         tx: TransactionView,
@@ -795,6 +824,7 @@ impl TxPoolService {
             tx_env,
             &verify_cache,
             max_cycles,
+            self.tx_pool_config.parallel_script_verification,
         );
 
         let verified = try_or_return_with_snapshot!(verified_ret, snapshot);
@@ -827,6 +857,375 @@ impl TxPoolService {
         Some((Ok(verified), submit_snapshot))
     }
 
+    /// Recovers the topologically ordered entries replayed from the journal, returns the count of
+    /// the stale entries which are ignored.
+    pub(crate) async fn recover_journal(&self, entries: Vec<JournalEntry>) -> usize {
+        let expiry = self.tx_pool_config.expiry_hours as u64 * 60 * 60 * 1000;
+        let now_ms = ckb_systemtime::unix_time_as_millis();
+        let mut stale = 0;
+        for entry in entries {
+            let tx_hash = entry.tx.hash();
+            if expiry + entry.timestamp < now_ms {
+                debug!("skip recovering expired {:#x}", tx_hash);
+                stale += 1;
+                continue;
+            }
+            if let Err(err) = self.recover_tx(entry).await {
+                debug!("failed to recover {:#x}, error: {}", tx_hash, err);
+                stale += 1;
+            }
+        }
+
+        // the stale entries are still in the journal, and the recovered ones are appended again
+        let mut tx_pool = self.tx_pool.write().await;
+        if let Err(err) = tx_pool.pool_map.compact_journal() {
+            error!("failed to compact the tx-pool journal, error: {}", err);
+        }
+        stale
+    }
+
+    async fn recover_tx(&self, entry: JournalEntry) -> Result<(), Reject> {
+        let JournalEntry {
+            tx,
+            cycles,
+            timestamp,
+        } = entry;
+        let (ret, snapshot) = self.pre_check(&tx).await;
+        let (tip_hash, rtx, status, fee, tx_size) = ret?;
+
+        // the recorded cycles may be changed by the hardfork, verify the scripts again
+        if self.is_in_delay_window(&snapshot) {
+            return self.process_tx(tx, None).await.map(|_| ());
+        }
+
+        // the scripts are verified before the entry is journaled, so only the time relative
+        // rules are checked again, the same as a tx found in the verify cache
+        let tip_header = snapshot.tip_header();
+        let tx_env = Arc::new(status.with_env(tip_header));
+        let verified = verify_rtx(
+            Arc::clone(&snapshot),
+            Arc::clone(&rtx),
+            tx_env,
+            &Some(CacheEntry::completed(cycles, fee)),
+            self.consensus.max_block_cycles(),
+            self.tx_pool_config.parallel_script_verification,
+        )?;
+
+        let entry = TxEntry::new_with_timestamp(rtx, verified.cycles, fee, tx_size, timestamp);
+        let (ret, _) = self.submit_entry(tip_hash, entry, status).await;
+        ret?;
+        self.notify_block_assembler(status).await;
+        Ok(())
+    }
+
+    /// Process a topologically ordered package of local transactions atomically, the package is
+    /// admitted only if all the transactions are valid and the fee rate of the whole package
+    /// reaches `min_fee_rate`, so a parent with low fee can be paid by its children. The
+    /// transactions in the pool already are skipped, only the rest are evaluated.
+    pub(crate) async fn process_package(&self, txs: Vec<TransactionView>) -> Result<(), Reject> {
+        let tx_hashes: Vec<_> = txs.iter().map(|tx| tx.hash()).collect();
+        let ret = self._process_package(txs, None).await;
+        // the package is admitted or rejected as a whole
+        if let Err(reject) = &ret {
+            if !matches!(reject, Reject::Duplicated(_)) {
+                for tx_hash in &tx_hashes {
+                    self.callbacks.call_submit_reject(tx_hash, reject);
+                }
+            }
+        }
+        ret
+    }
+
+    /// Process a topologically ordered package relayed by a peer, the low fee parents are paid
+    /// by their children as the local packages. If the package is rejected, the transactions
+    /// are processed one by one, so the valid ones are still admitted by their own fee rates.
+    pub(crate) async fn process_remote_package(
+        &self,
+        txs: Vec<(TransactionView, Cycle)>,
+        peer: PeerIndex,
+    ) {
+        let max_tx_verify_cycles = self.tx_pool_config.max_tx_verify_cycles;
+        // the large cycles transactions are verified in the chunk queue
+        if txs
+            .iter()
+            .all(|(_, declared_cycles)| *declared_cycles <= max_tx_verify_cycles)
+        {
+            let declared_cycles: HashMap<Byte32, Cycle> = txs
+                .iter()
+                .map(|(tx, cycles)| (tx.hash(), *cycles))
+                .collect();
+            let package = txs.iter().map(|(tx, _)| tx.clone()).collect();
+            match self
+                ._process_package(package, Some((&declared_cycles, peer)))
+                .await
+            {
+                Ok(()) => return,
+                Err(reject) => {
+                    debug!(
+                        "process_remote_package {} from {} reject: {}",
+                        txs[txs.len() - 1].0.hash(),
+                        peer,
+                        reject
+                    );
+                }
+            }
+        }
+
+        for (tx, declared_cycles) in txs {
+            if declared_cycles > max_tx_verify_cycles {
+                let _ret = self
+                    .resumeble_process_tx(tx, Some((declared_cycles, peer)))
+                    .await;
+            } else {
+                let _ret = self.process_tx(tx, Some((declared_cycles, peer))).await;
+            }
+        }
+    }
+
+    async fn _process_package(
+        &self,
+        txs: Vec<TransactionView>,
+        remote: Option<(&HashMap<Byte32, Cycle>, PeerIndex)>,
+    ) -> Result<(), Reject> {
+        check_package(&txs)?;
+        let declared_cycles = |tx: &TransactionView| {
+            remote.and_then(|(declared_cycles, _)| declared_cycles.get(&tx.hash()).copied())
+        };
+        for tx in &txs {
+            let remote = remote.map(|(_, peer)| (declared_cycles(tx).unwrap_or_default(), peer));
+            self.non_contextual_verify(tx, remote)?;
+            // the orphans are admitted with their parents in the package
+            if self.chunk_contains(tx).await {
+                return Err(Reject::Duplicated(tx.hash()));
+            }
+        }
+
+        let (ret, snapshot) = self.pre_check_package(&txs).await;
+        let (tip_hash, checked) = ret?;
+        // every member is in the pool already
+        if checked.is_empty() {
+            let child = txs.last().expect("checked non-empty");
+            return Err(Reject::Duplicated(child.hash()));
+        }
+
+        let tip_header = snapshot.tip_header();
+        let mut entries = Vec::with_capacity(checked.len());
+        for (rtx, status, fee, tx_size) in checked {
+            let tx_hash = rtx.transaction.hash();
+            let verify_cache = self.fetch_tx_verify_cache(&tx_hash).await;
+            let declared = declared_cycles(&rtx.transaction);
+            let max_cycles = declared.unwrap_or_else(|| self.consensus.max_block_cycles());
+            let tx_env = Arc::new(status.with_env(tip_header));
+            let verified = verify_rtx(
+                Arc::clone(&snapshot),
+                Arc::clone(&rtx),
+                tx_env,
+                &verify_cache,
+                max_cycles,
+                self.tx_pool_config.parallel_script_verification,
+            )?;
+            if let Some(declared) = declared {
+                if declared != verified.cycles {
+                    return Err(Reject::DeclaredWrongCycles(declared, verified.cycles));
+                }
+            }
+            let entry = TxEntry::new(rtx, verified.cycles, fee, tx_size);
+            entries.push((entry, status, verified));
+        }
+
+        let (ret, submit_snapshot) = self.submit_package(tip_hash, &entries).await;
+        ret?;
+
+        let mut admitted = Vec::with_capacity(entries.len());
+        for (entry, status, verified) in entries {
+            self.notify_block_assembler(status).await;
+            {
+                let mut guard = self.txs_verify_cache.write().await;
+                guard.put(entry.transaction().hash(), CacheEntry::Completed(verified));
+            }
+            admitted.push((entry.into_transaction(), verified));
+        }
+        self.after_process_package(admitted, remote.map(|(_, peer)| peer), &submit_snapshot)
+            .await;
+        Ok(())
+    }
+
+    /// Relay the admitted package as a unit, if the transactions were relayed one by one, the
+    /// peers would reject the low fee parents before the children paying for them arrive.
+    async fn after_process_package(
+        &self,
+        txs: Vec<(TransactionView, Completed)>,
+        original_peer: Option<PeerIndex>,
+        snapshot: &Snapshot,
+    ) {
+        if log_enabled_target!("ckb_tx_monitor", Trace) {
+            for (tx, completed) in &txs {
+                trace_target!(
+                    "ckb_tx_monitor",
+                    r#"{{"tx_hash":"{:#x}","cycles":{}}}"#,
+                    tx.hash(),
+                    completed.cycles
+                );
+            }
+        }
+
+        self.send_result_to_relayer(TxVerificationResult::Package {
+            original_peer,
+            with_vm_2023: self.with_vm_2023(snapshot),
+            tx_hashes: txs.iter().map(|(tx, _)| tx.hash()).collect(),
+        });
+        for (tx, _) in &txs {
+            self.remove_orphan_tx(&tx.proposal_short_id()).await;
+            self.process_orphan_tx(tx).await;
+        }
+    }
+
+    async fn pre_check_package(
+        &self,
+        txs: &[TransactionView],
+    ) -> (
+        Result<(Byte32, Vec<PreCheckedPackageTx>), Reject>,
+        Arc<Snapshot>,
+    ) {
+        self.with_tx_pool_read_lock(|tx_pool, snapshot| {
+            let snapshot: &Snapshot = &snapshot;
+            let tip_hash = snapshot.tip_hash();
+            // the inputs spent by the pool txs are resolved as live if RBF is enabled, the
+            // conflicts are checked against the RBF rules when the package is submitted
+            let pool_cell = PoolCell::new(&tx_pool.pool_map, tx_pool.enable_rbf());
+            let pool_provider = OverlayCellProvider::new(&pool_cell, snapshot);
+            // a transaction can only spend the outputs of the transactions before it in the package
+            let mut package_cell = TransactionsProvider::default();
+            let mut seen_inputs = HashSet::new();
+            let mut package_fee = Capacity::zero();
+            let mut package_size = 0;
+            let mut checked = Vec::with_capacity(txs.len());
+
+            for tx in txs {
+                // the members in the pool already are skipped, the rest can spend their outputs
+                if tx_pool
+                    .get_tx_from_pool(&tx.proposal_short_id())
+                    .map_or(false, |pool_tx| pool_tx.hash() == tx.hash())
+                {
+                    continue;
+                }
+                check_txid_collision(tx_pool, tx)?;
+                let provider = OverlayCellProvider::new(&package_cell, &pool_provider);
+                let rtx = resolve_transaction(tx.clone(), &mut seen_inputs, &provider, snapshot)
+                    .map(Arc::new)
+                    .map_err(Reject::Resolve)?;
+                let tx_size = tx.data().serialized_size_in_block();
+                let fee = calculate_tx_fee(snapshot, &rtx)?;
+                package_fee = package_fee.safe_add(fee).map_err(|err| {
+                    Reject::Malformed(format!("{err}"), "package fee overflow".to_owned())
+                })?;
+                package_size += tx_size;
+                let status = get_tx_status(snapshot, &tx.proposal_short_id());
+                checked.push((rtx, status, fee, tx_size));
+                package_cell.insert(tx);
+            }
+
+            check_package_fee(tx_pool, package_fee, package_size)?;
+            Ok((tip_hash, checked))
+        })
+        .await
+    }
+
+    async fn submit_package(
+        &self,
+        pre_resolve_tip: Byte32,
+        entries: &[(TxEntry, TxStatus, Completed)],
+    ) -> (Result<(), Reject>, Arc<Snapshot>) {
+        self.with_tx_pool_write_lock(move |tx_pool, snapshot| {
+            // double confirm the RBF rules of every member before the pool is changed
+            let conflicts = if tx_pool.enable_rbf() {
+                tx_pool.check_package_rbf(&snapshot, entries.iter().map(|(entry, _, _)| entry))?
+            } else {
+                HashSet::new()
+            };
+            let child_hash = entries[entries.len() - 1].0.transaction().hash();
+
+            // the replaced txs are restored if the package is rolled back
+            let mut replaced = Vec::new();
+            for id in conflicts.iter() {
+                let mut ids = tx_pool.pool_map.calc_descendants(id);
+                ids.insert(id.clone());
+                let statuses: HashMap<_, _> = ids
+                    .iter()
+                    .filter_map(|id| tx_pool.pool_map.get_by_id(id))
+                    .map(|entry| (entry.id.clone(), entry.status))
+                    .collect();
+                for old in tx_pool.pool_map.remove_entry_and_descendants(id) {
+                    debug!(
+                        "remove conflict tx {} for RBF by package of tx {}",
+                        old.transaction().hash(),
+                        child_hash
+                    );
+                    let status = statuses[&old.proposal_short_id()];
+                    replaced.push((old, status));
+                }
+            }
+
+            let tip_hash = snapshot.tip_hash();
+            let mut added = Vec::with_capacity(entries.len());
+            let ret: Result<(), Reject> = entries.iter().try_for_each(|(entry, status, _)| {
+                let mut status = *status;
+                // the parents in the package are added already, so re-check against the pool
+                if pre_resolve_tip != tip_hash {
+                    status = check_rtx(tx_pool, &snapshot, &entry.rtx)?;
+                    let tx_env = status.with_env(snapshot.tip_header());
+                    time_relative_verify(Arc::clone(&snapshot), Arc::clone(&entry.rtx), tx_env)?;
+                }
+                // the conflicts are removed already
+                tx_pool.pool_map.check_lock_limits(entry, &HashSet::new())?;
+                if add_entry(tx_pool, status, entry.clone())? {
+                    added.push((entry, status));
+                }
+                Ok(())
+            });
+
+            if let Err(reject) = ret {
+                debug!(
+                    "submit_package {} rollback: {}",
+                    entries[0].0.transaction().hash(),
+                    reject
+                );
+                for (entry, _) in added.iter().rev() {
+                    let short_id = entry.proposal_short_id();
+                    tx_pool.pool_map.remove_entry(&short_id);
+                    tx_pool.fee_estimator.untrack_tx(&short_id);
+                }
+                // the parents are restored before their descendants
+                replaced.sort_by_key(|(old, _)| old.ancestors_count);
+                for (old, status) in replaced {
+                    let old = TxEntry::new_with_timestamp(
+                        old.rtx,
+                        old.cycles,
+                        old.fee,
+                        old.size,
+                        old.timestamp,
+                    );
+                    if let Err(err) = tx_pool.pool_map.add_entry(old, status) {
+                        error!("submit_package failed to restore a replaced tx: {}", err);
+                    }
+                }
+                return Err(reject);
+            }
+
+            for (old, _) in replaced {
+                let reject = Reject::RBFRejected(format!("replaced by tx {child_hash}"));
+                // remove old tx from tx_pool, not happened in service so we didn't call reject callbacks
+                // here we call them manually
+                self.callbacks.call_reject(tx_pool, &old, reject)
+            }
+            for (entry, status) in added {
+                call_submit_callbacks(&self.callbacks, status, entry);
+            }
+            Ok(())
+        })
+        .await
+    }
+
     pub(crate) async fn update_tx_pool_for_reorg(
         &self,
         detached_blocks: VecDeque<BlockView>,
@@ -846,6 +1245,19 @@ impl TxPoolService {
         let new_tip_after_delay = after_delay_window(&snapshot);
         let is_in_delay_window = self.is_in_delay_window(&snapshot);
 
+        let committed: Vec<(BlockNumber, Vec<ProposalShortId>)> = attached_blocks
+            .iter()
+            .map(|blk| {
+                let ids = blk
+                    .transactions()
+                    .iter()
+                    .skip(1)
+                    .map(|tx| tx.proposal_short_id())
+                    .collect();
+                (blk.number(), ids)
+            })
+            .collect();
+
         let detached_headers: HashSet<Byte32> = detached_blocks
             .iter()
             .map(|blk| blk.header().hash())
@@ -911,6 +1323,8 @@ impl TxPoolService {
             // notice: readd_detached_tx don't update cache
             self.readd_detached_tx(&mut tx_pool, retain, fetched_cache);
 
+            tx_pool.update_fee_estimator(&committed);
+
             txs_opt
         };
 
@@ -920,6 +1334,13 @@ impl TxPoolService {
                 for tx in txs {
                     delay.insert(tx.proposal_short_id(), tx);
                 }
+            } else {
+                let ids = txs
+                    .iter()
+                    .map(|tx| tx.proposal_short_id())
+                    .collect::<Vec<_>>();
+                let mut tx_pool = self.tx_pool.write().await;
+                tx_pool.pool_map.release_drained(ids.iter());
             }
         }
 
@@ -942,7 +1363,13 @@ impl TxPoolService {
                 None
             };
             if let Some(txs) = delay_txs {
+                let ids = txs
+                    .iter()
+                    .map(|tx| tx.proposal_short_id())
+                    .collect::<Vec<_>>();
                 self.try_process_txs(txs).await;
+                let mut tx_pool = self.tx_pool.write().await;
+                tx_pool.pool_map.release_drained(ids.iter());
             }
         }
 
@@ -983,6 +1410,7 @@ impl TxPoolService {
                         tx_env,
                         &verify_cache,
                         max_cycles,
+                        self.tx_pool_config.parallel_script_verification,
                     ) {
                         let entry = TxEntry::new(rtx, verified.cycles, fee, tx_size);
                         if let Err(e) = _submit_entry(tx_pool, status, entry, &self.callbacks) {
@@ -1012,13 +1440,30 @@ impl TxPoolService {
         }
     }
 
-    pub(crate) async fn save_pool(&self) {
-        let mut tx_pool = self.tx_pool.write().await;
-        if let Err(err) = tx_pool.save_into_file() {
-            error!("failed to save pool, error: {:?}", err)
-        } else {
-            info!("TxPool saved successfully")
+    pub(crate) async fn save_pool(&self) -> Result<(), AnyError> {
+        let synced = {
+            let mut tx_pool = self.tx_pool.write().await;
+            if let Err(err) = tx_pool.save_fee_estimator_into_file() {
+                error!("failed to save fee estimator, error: {:?}", err)
+            }
+            // the journal is rewritten and synced by its writer, without holding the lock
+            tx_pool
+                .pool_map
+                .compact_journal()
+                .and_then(|_| tx_pool.pool_map.sync_journal())
+        };
+        let ret = match synced {
+            Ok(Some(synced)) => synced.await.unwrap_or_else(|_| {
+                Err(OtherError::new("The tx-pool journal writer is stopped").into())
+            }),
+            Ok(None) => Ok(()),
+            Err(err) => Err(err),
+        };
+        match ret {
+            Ok(()) => info!("TxPool saved successfully"),
+            Err(ref err) => error!("failed to save pool, error: {:?}", err),
         }
+        ret
     }
 
     // # Notice
@@ -1056,6 +1501,13 @@ type PreCheckedTx = (
     usize,                    // tx size
 );
 
+type PreCheckedPackageTx = (
+    Arc<ResolvedTransaction>, // rtx
+    TxStatus,                 // status
+    Capacity,                 // tx fee
+    usize,                    // tx size
+);
+
 type ResolveResult = Result<(Arc<ResolvedTransaction>, TxStatus), Reject>;
 
 fn get_tx_status(snapshot: &Snapshot, short_id: &ProposalShortId) -> TxStatus {
@@ -1097,28 +1549,36 @@ fn _submit_entry(
     entry: TxEntry,
     callbacks: &Callbacks,
 ) -> Result<(), Reject> {
+    if add_entry(tx_pool, status, entry.clone())? {
+        call_submit_callbacks(callbacks, status, &entry);
+    }
+    Ok(())
+}
+
+fn add_entry(tx_pool: &mut TxPool, status: TxStatus, entry: TxEntry) -> Result<bool, Reject> {
     let tx_hash = entry.transaction().hash();
+    let short_id = entry.proposal_short_id();
+    let fee_rate = entry.fee_rate();
+    let added = match status {
+        TxStatus::Fresh => tx_pool.add_pending(entry)?,
+        TxStatus::Gap => tx_pool.add_gap(entry)?,
+        TxStatus::Proposed => tx_pool.add_proposed(entry)?,
+    };
+    if added {
+        debug!("submit_entry {:?} {}", status, tx_hash);
+        let tip_number = tx_pool.snapshot().tip_number();
+        tx_pool
+            .fee_estimator
+            .track_tx(short_id, fee_rate, tip_number);
+    }
+    Ok(added)
+}
+
+fn call_submit_callbacks(callbacks: &Callbacks, status: TxStatus, entry: &TxEntry) {
     match status {
-        TxStatus::Fresh => {
-            if tx_pool.add_pending(entry.clone())? {
-                debug!("submit_entry pending {}", tx_hash);
-                callbacks.call_pending(&entry);
-            }
-        }
-        TxStatus::Gap => {
-            if tx_pool.add_gap(entry.clone())? {
-                debug!("submit_entry gap {}", tx_hash);
-                callbacks.call_pending(&entry);
-            }
-        }
-        TxStatus::Proposed => {
-            if tx_pool.add_proposed(entry.clone())? {
-                debug!("submit_entry proposed {}", tx_hash);
-                callbacks.call_proposed(&entry);
-            }
-        }
+        TxStatus::Fresh | TxStatus::Gap => callbacks.call_pending(entry),
+        TxStatus::Proposed => callbacks.call_proposed(entry),
     }
-    Ok(())
 }
 
 fn _update_tx_pool_for_reorg(
@@ -1,8 +1,11 @@
 //! Tx-pool background service
 
 use crate::block_assembler::{self, BlockAssembler};
-use crate::callback::{Callbacks, PendingCallback, ProposedCallback, RejectCallback};
+use crate::callback::{
+    Callbacks, PendingCallback, ProposedCallback, RejectCallback, SubmitRejectCallback,
+};
 use crate::chunk_process::ChunkCommand;
+use crate::component::journal::JournalEntry;
 use crate::component::pool_map::{PoolEntry, Status};
 use crate::component::{chunk::ChunkQueue, orphan::OrphanPool};
 use crate::error::{handle_recv_error, handle_send_cmd_error, handle_try_send_error};
@@ -19,17 +22,19 @@ use ckb_logger::info;
 use ckb_network::{NetworkController, PeerIndex};
 use ckb_snapshot::Snapshot;
 use ckb_stop_handler::new_tokio_exit_rx;
-use ckb_types::core::tx_pool::{PoolTxDetailInfo, TransactionWithStatus, TxStatus};
+use ckb_types::core::tx_pool::{EstimateMode, PoolTxDetailInfo, TransactionWithStatus, TxStatus};
 use ckb_types::{
     core::{
         tx_pool::{Reject, TxPoolEntryInfo, TxPoolIds, TxPoolInfo, TRANSACTION_SIZE_LIMIT},
-        BlockView, Cycle, TransactionView, UncleBlockView, Version,
+        BlockNumber, BlockView, Cycle, FeeRate, TransactionView, UncleBlockView, Version,
     },
     packed::{Byte32, ProposalShortId},
+    utilities::ShortIdKey,
 };
 use ckb_util::{LinkedHashMap, LinkedHashSet};
 use ckb_verification::cache::TxVerificationCache;
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::PathBuf;
 use std::sync::{
     atomic::{AtomicBool, Ordering},
     Arc,
@@ -89,12 +94,17 @@ pub(crate) type ChainReorgArgs = (
 pub(crate) enum Message {
     BlockTemplate(Request<BlockTemplateArgs, BlockTemplateResult>),
     SubmitLocalTx(Request<TransactionView, SubmitTxResult>),
+    SubmitLocalPackage(Request<Vec<TransactionView>, SubmitTxResult>),
     RemoveLocalTx(Request<Byte32, bool>),
     SubmitRemoteTx(Request<(TransactionView, Cycle, PeerIndex), ()>),
+    SubmitRemotePackage(Request<(Vec<(TransactionView, Cycle)>, PeerIndex), ()>),
     NotifyTxs(Notify<Vec<TransactionView>>),
     FreshProposalsFilter(Request<Vec<ProposalShortId>, Vec<ProposalShortId>>),
     FetchTxs(Request<HashSet<ProposalShortId>, HashMap<ProposalShortId, TransactionView>>),
     FetchTxsWithCycles(Request<HashSet<ProposalShortId>, FetchTxsWithCyclesResult>),
+    FetchTxsBySaltedShortIds(
+        Request<(ShortIdKey, HashSet<ProposalShortId>), HashMap<ProposalShortId, TransactionView>>,
+    ),
     GetTxPoolInfo(Request<(), TxPoolInfo>),
     GetTxStatus(Request<Byte32, GetTxStatusResult>),
     GetTransactionWithStatus(Request<Byte32, GetTransactionWithStatusResult>),
@@ -102,8 +112,10 @@ pub(crate) enum Message {
     ClearPool(Request<Arc<Snapshot>, ()>),
     GetAllEntryInfo(Request<(), TxPoolEntryInfo>),
     GetAllIds(Request<(), TxPoolIds>),
-    SavePool(Request<(), ()>),
+    SavePool(Request<(), Result<(), AnyError>>),
+    RecoverJournal(Request<Vec<JournalEntry>, usize>),
     GetPoolTxDetails(Request<Byte32, PoolTxDetailInfo>),
+    EstimateFeeRate(Request<(BlockNumber, EstimateMode), Option<FeeRate>>),
 
     // test
     #[cfg(feature = "internal")]
@@ -226,6 +238,15 @@ impl TxPoolController {
         send_message!(self, SubmitLocalTx, tx)
     }
 
+    /// Submit a topologically ordered package of local txs to tx-pool, the txs are admitted
+    /// atomically by the fee rate of the whole package
+    pub fn submit_local_package(
+        &self,
+        txs: Vec<TransactionView>,
+    ) -> Result<SubmitTxResult, AnyError> {
+        send_message!(self, SubmitLocalPackage, txs)
+    }
+
     /// Remove tx from tx-pool
     pub fn remove_local_tx(&self, tx_hash: Byte32) -> Result<bool, AnyError> {
         send_message!(self, RemoveLocalTx, tx_hash)
@@ -241,6 +262,16 @@ impl TxPoolController {
         send_message!(self, SubmitRemoteTx, (tx, declared_cycles, peer))
     }
 
+    /// Submit a topologically ordered package of remote txs with declared cycles and origin to
+    /// tx-pool, the txs are processed one by one if the package is rejected
+    pub async fn submit_remote_package(
+        &self,
+        txs: Vec<(TransactionView, Cycle)>,
+        peer: PeerIndex,
+    ) -> Result<(), AnyError> {
+        send_message!(self, SubmitRemotePackage, (txs, peer))
+    }
+
     /// Receive txs from network, try to add txs to tx-pool
     pub fn notify_txs(&self, txs: Vec<TransactionView>) -> Result<(), AnyError> {
         send_notify!(self, NotifyTxs, txs)
@@ -288,6 +319,15 @@ impl TxPoolController {
         send_message!(self, FetchTxsWithCycles, short_ids)
     }
 
+    /// Return txs in the pool matching the salted short ids of compact block relay v4
+    pub fn fetch_txs_by_salted_short_ids(
+        &self,
+        key: ShortIdKey,
+        short_ids: HashSet<ProposalShortId>,
+    ) -> Result<HashMap<ProposalShortId, TransactionView>, AnyError> {
+        send_message!(self, FetchTxsBySaltedShortIds, (key, short_ids))
+    }
+
     /// Clears the tx-pool, removing all txs, update snapshot.
     pub fn clear_pool(&self, new_snapshot: Arc<Snapshot>) -> Result<(), AnyError> {
         send_message!(self, ClearPool, new_snapshot)
@@ -308,10 +348,21 @@ impl TxPoolController {
         send_message!(self, GetPoolTxDetails, tx_hash)
     }
 
+    /// Estimates the fee rate to get a transaction committed within `target_blocks` blocks.
+    ///
+    /// Returns `None` if there is not sufficient data or `target_blocks` is not in `1..=MAX_TARGET_BLOCKS`.
+    pub fn estimate_fee_rate(
+        &self,
+        target_blocks: BlockNumber,
+        mode: EstimateMode,
+    ) -> Result<Option<FeeRate>, AnyError> {
+        send_message!(self, EstimateFeeRate, (target_blocks, mode))
+    }
+
     /// Saves tx pool into disk.
     pub fn save_pool(&self) -> Result<(), AnyError> {
         info!("Please be patient, tx-pool are saving data into disk ...");
-        send_message!(self, SavePool, ())
+        send_message!(self, SavePool, ())?
     }
 
     /// Sends suspend chunk process cmd
@@ -330,8 +381,13 @@ impl TxPoolController {
             .map_err(Into::into)
     }
 
-    /// Load persisted txs into pool, assume that all txs are sorted
-    fn load_persisted_data(&self, txs: Vec<TransactionView>) -> Result<(), AnyError> {
+    /// Load persisted txs into pool, assume that all txs are sorted. The persisted data file is
+    /// removed after the txs are journaled.
+    fn load_persisted_data(
+        &self,
+        txs: Vec<TransactionView>,
+        persisted_data_file: PathBuf,
+    ) -> Result<(), AnyError> {
         if !txs.is_empty() {
             info!("Loading persistent tx-pool data, total {} txs", txs.len());
             let mut failed_txs = 0;
@@ -348,6 +404,27 @@ impl TxPoolController {
                     failed_txs
                 );
             }
+            self.save_pool()?;
+        }
+        TxPool::remove_persisted_data_file(&persisted_data_file)
+    }
+
+    /// Recover the entries replayed from the tx-pool journal, assume that all entries are sorted
+    fn recover_journal(&self, entries: Vec<JournalEntry>) -> Result<(), AnyError> {
+        if !entries.is_empty() {
+            info!(
+                "Recovering tx-pool from journal, total {} txs",
+                entries.len()
+            );
+            let stale = send_message!(self, RecoverJournal, entries)?;
+            if stale == 0 {
+                info!("Tx-pool is recovered from journal");
+            } else {
+                info!(
+                    "Tx-pool is recovered from journal, {} stale txs are ignored",
+                    stale
+                );
+            }
         }
         Ok(())
     }
@@ -456,12 +533,24 @@ impl TxPoolServiceBuilder {
         self.callbacks.register_reject(callback);
     }
 
+    /// Register new callback of the submitted transactions rejected without being added to the pool
+    pub fn register_submit_reject(&mut self, callback: SubmitRejectCallback) {
+        self.callbacks.register_submit_reject(callback);
+    }
+
     /// Start a background thread tx-pool service by taking ownership of the Builder, and returns a TxPoolController.
     pub fn start(self, network: NetworkController) {
         let consensus = self.snapshot.cloned_consensus();
         let after_delay_window = after_delay_window(&self.snapshot);
 
-        let tx_pool = TxPool::new(self.tx_pool_config, self.snapshot);
+        let mut tx_pool = TxPool::new(self.tx_pool_config, self.snapshot);
+        if let Err(e) = tx_pool.load_fee_estimator_from_file() {
+            error!("{}", e.to_string());
+            error!(
+                "Failed to load the fee estimator persistent data file, the statistics are reset"
+            );
+        }
+        let persisted_data_file = tx_pool.persisted_data_file();
         let txs = match tx_pool.load_from_file() {
             Ok(txs) => txs,
             Err(e) => {
@@ -470,6 +559,14 @@ impl TxPoolServiceBuilder {
                 Vec::new()
             }
         };
+        let journal_entries = match tx_pool.open_journal() {
+            Ok(entries) => entries,
+            Err(e) => {
+                error!("{}", e.to_string());
+                error!("Failed to open the tx-pool journal, the tx-pool will not be persisted");
+                Vec::new()
+            }
+        };
 
         let (block_assembler_sender, mut block_assembler_receiver) = self.block_assembler_channel;
         let service = TxPoolService {
@@ -511,7 +608,8 @@ impl TxPoolServiceBuilder {
                     },
                     _ = signal_receiver.cancelled() => {
                         info!("TxPool is saving, please wait...");
-                        process_service.save_pool().await;
+                        // the error is logged in saving
+                        let _ = process_service.save_pool().await;
                         info!("TxPool process_service exit now");
                         break
                     },
@@ -620,7 +718,13 @@ impl TxPoolServiceBuilder {
             }
         });
         self.started.store(true, Ordering::Relaxed);
-        if let Err(err) = self.tx_pool_controller.load_persisted_data(txs) {
+        if let Err(err) = self.tx_pool_controller.recover_journal(journal_entries) {
+            error!("Failed to recover tx-pool from journal, cause: {}", err);
+        }
+        if let Err(err) = self
+            .tx_pool_controller
+            .load_persisted_data(txs, persisted_data_file)
+        {
             error!("Failed to import persistent txs, cause: {}", err);
         }
     }
@@ -654,6 +758,16 @@ pub enum TxVerificationResult {
         /// transaction hash
         tx_hash: Byte32,
     },
+    /// the package is verified, the txs are relayed together so the low fee parents reach the
+    /// peers with the children paying for them
+    Package {
+        /// original peer
+        original_peer: Option<PeerIndex>,
+        /// verified by ckb vm version
+        with_vm_2023: bool,
+        /// topologically ordered transaction hashes
+        tx_hashes: Vec<Byte32>,
+    },
     /// tx is rejected
     Reject {
         /// transaction hash
@@ -688,6 +802,15 @@ async fn process(mut service: TxPoolService, message: Message) {
                 error!("Responder sending submit_tx result failed {:?}", e);
             };
         }
+        Message::SubmitLocalPackage(Request {
+            responder,
+            arguments: txs,
+        }) => {
+            let result = service.process_package(txs).await;
+            if let Err(e) = responder.send(result) {
+                error!("Responder sending submit_package result failed {:?}", e);
+            };
+        }
         Message::RemoveLocalTx(Request {
             responder,
             arguments: tx_hash,
@@ -715,6 +838,15 @@ async fn process(mut service: TxPoolService, message: Message) {
                 };
             }
         }
+        Message::SubmitRemotePackage(Request {
+            responder,
+            arguments: (txs, peer),
+        }) => {
+            service.process_remote_package(txs, peer).await;
+            if let Err(e) = responder.send(()) {
+                error!("Responder sending submit_package result failed {:?}", e);
+            };
+        }
         Message::NotifyTxs(Notify { arguments: txs }) => {
             for tx in txs {
                 let _ret = service.resumeble_process_tx(tx, None).await;
@@ -840,6 +972,19 @@ async fn process(mut service: TxPoolService, message: Message) {
                 error!("Responder sending fetch_txs_with_cycles failed {:?}", e);
             };
         }
+        Message::FetchTxsBySaltedShortIds(Request {
+            responder,
+            arguments: (key, short_ids),
+        }) => {
+            let mut tx_pool = service.tx_pool.write().await;
+            let txs = tx_pool.get_txs_by_salted_short_ids(&key, &short_ids);
+            if let Err(e) = responder.send(txs) {
+                error!(
+                    "Responder sending fetch_txs_by_salted_short_ids failed {:?}",
+                    e
+                );
+            };
+        }
         Message::NewUncle(Notify { arguments: uncle }) => {
             service.receive_candidate_uncle(uncle).await;
         }
@@ -879,12 +1024,31 @@ async fn process(mut service: TxPoolService, message: Message) {
                 error!("Responder sending get_ids failed {:?}", e)
             };
         }
+        Message::EstimateFeeRate(Request {
+            responder,
+            arguments: (target_blocks, mode),
+        }) => {
+            let tx_pool = service.tx_pool.read().await;
+            let fee_rate = tx_pool.fee_estimator.estimate_fee_rate(target_blocks, mode);
+            if let Err(e) = responder.send(fee_rate) {
+                error!("responder send estimate_fee_rate failed {:?}", e)
+            };
+        }
         Message::SavePool(Request { responder, .. }) => {
-            service.save_pool().await;
-            if let Err(e) = responder.send(()) {
+            let ret = service.save_pool().await;
+            if let Err(e) = responder.send(ret) {
                 error!("Responder sending save_pool failed {:?}", e)
             };
         }
+        Message::RecoverJournal(Request {
+            responder,
+            arguments: entries,
+        }) => {
+            let stale = service.recover_journal(entries).await;
+            if let Err(e) = responder.send(stale) {
+                error!("Responder sending recover_journal failed {:?}", e)
+            };
+        }
         #[cfg(feature = "internal")]
         Message::PlugEntry(Request {
             responder,
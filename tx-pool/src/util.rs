@@ -9,14 +9,19 @@ use ckb_types::core::{
     cell::ResolvedTransaction, tx_pool::TRANSACTION_SIZE_LIMIT, Capacity, Cycle, EpochNumber,
     TransactionView,
 };
+use ckb_types::packed::Byte32;
 use ckb_verification::{
     cache::{CacheEntry, Completed},
     ContextualTransactionVerifier, DaoScriptSizeVerifier, NonContextualTransactionVerifier,
     TimeRelativeTransactionVerifier, TxVerifyEnv,
 };
+use std::collections::HashSet;
 use std::sync::Arc;
 use tokio::task::block_in_place;
 
+/// The maximum number of transactions in a package
+pub const MAX_PACKAGE_TXS_COUNT: usize = 25;
+
 pub(crate) fn check_txid_collision(tx_pool: &TxPool, tx: &TransactionView) -> Result<(), Reject> {
     let short_id = tx.proposal_short_id();
     if tx_pool.contains_proposal_id(&short_id) {
@@ -25,20 +30,27 @@ pub(crate) fn check_txid_collision(tx_pool: &TxPool, tx: &TransactionView) -> Re
     Ok(())
 }
 
-pub(crate) fn check_tx_fee(
-    tx_pool: &TxPool,
+pub(crate) fn calculate_tx_fee(
     snapshot: &Snapshot,
     rtx: &ResolvedTransaction,
-    tx_size: usize,
 ) -> Result<Capacity, Reject> {
-    let fee = DaoCalculator::new(snapshot.consensus(), &snapshot.borrow_as_data_loader())
+    DaoCalculator::new(snapshot.consensus(), &snapshot.borrow_as_data_loader())
         .transaction_fee(rtx)
         .map_err(|err| {
             Reject::Malformed(
                 format!("{err}"),
                 "expect (outputs capacity) <= (inputs capacity)".to_owned(),
             )
-        })?;
+        })
+}
+
+pub(crate) fn check_tx_fee(
+    tx_pool: &TxPool,
+    snapshot: &Snapshot,
+    rtx: &ResolvedTransaction,
+    tx_size: usize,
+) -> Result<Capacity, Reject> {
+    let fee = calculate_tx_fee(snapshot, rtx)?;
     // Theoretically we cannot use size as weight directly to calculate fee_rate,
     // here min fee rate is used as a cheap check,
     // so we will use size to calculate fee_rate directly
@@ -53,6 +65,70 @@ pub(crate) fn check_tx_fee(
     Ok(fee)
 }
 
+/// Check the package fee rate, the fee of a transaction below `min_fee_rate` can be paid by the
+/// other transactions in the same package
+pub(crate) fn check_package_fee(
+    tx_pool: &TxPool,
+    package_fee: Capacity,
+    package_size: usize,
+) -> Result<(), Reject> {
+    let min_fee = tx_pool.config.min_fee_rate.fee(package_size as u64);
+    if package_fee < min_fee {
+        let reject = Reject::LowFeeRate(
+            tx_pool.config.min_fee_rate,
+            min_fee.as_u64(),
+            package_fee.as_u64(),
+        );
+        ckb_logger::debug!("Reject package {}", reject);
+        return Err(reject);
+    }
+    Ok(())
+}
+
+pub(crate) fn check_package(txs: &[TransactionView]) -> Result<(), Reject> {
+    if txs.is_empty() {
+        return Err(Reject::Malformed(
+            "empty package".to_owned(),
+            Default::default(),
+        ));
+    }
+    if txs.len() > MAX_PACKAGE_TXS_COUNT {
+        return Err(Reject::Malformed(
+            "oversized package".to_owned(),
+            format!(
+                "expect at most {} transactions, got {}",
+                MAX_PACKAGE_TXS_COUNT,
+                txs.len()
+            ),
+        ));
+    }
+    let mut seen = HashSet::with_capacity(txs.len());
+    for tx in txs {
+        if !seen.insert(tx.hash()) {
+            return Err(Reject::Duplicated(tx.hash()));
+        }
+    }
+    // The package must be connected, i.e., every transaction except the last one, the child,
+    // is an ancestor of the child. As the package is topologically ordered, walk it backward
+    // and collect the transactions the child depends on.
+    let (child, parents) = txs.split_last().expect("checked non-empty");
+    let mut ancestors: HashSet<Byte32> = child.input_pts_iter().map(|pt| pt.tx_hash()).collect();
+    for tx in parents.iter().rev() {
+        if !ancestors.contains(&tx.hash()) {
+            return Err(Reject::Malformed(
+                "unconnected package".to_owned(),
+                format!(
+                    "transaction {:#x} is not an ancestor of the child {:#x}",
+                    tx.hash(),
+                    child.hash()
+                ),
+            ));
+        }
+        ancestors.extend(tx.input_pts_iter().map(|pt| pt.tx_hash()));
+    }
+    Ok(())
+}
+
 pub(crate) fn non_contextual_verify(
     consensus: &Consensus,
     tx: &TransactionView,
@@ -88,6 +164,7 @@ pub(crate) fn verify_rtx(
     tx_env: Arc<TxVerifyEnv>,
     cache_entry: &Option<CacheEntry>,
     max_tx_verify_cycles: Cycle,
+    parallel_script_verification: bool,
 ) -> Result<Completed, Reject> {
     let consensus = snapshot.cloned_consensus();
     let data_loader = snapshot.as_data_loader();
@@ -101,7 +178,16 @@ pub(crate) fn verify_rtx(
                     .map_err(Reject::Verification)
             }
             CacheEntry::Suspended(suspended) => {
-                ContextualTransactionVerifier::new(Arc::clone(&rtx), consensus, data_loader, tx_env)
+                let mut verifier = ContextualTransactionVerifier::new(
+                    Arc::clone(&rtx),
+                    consensus,
+                    data_loader,
+                    tx_env,
+                );
+                if parallel_script_verification {
+                    verifier.enable_parallel_script_verification();
+                }
+                verifier
                     .complete(max_tx_verify_cycles, false, &suspended.snap)
                     .and_then(|result| {
                         DaoScriptSizeVerifier::new(
@@ -117,7 +203,16 @@ pub(crate) fn verify_rtx(
         }
     } else {
         block_in_place(|| {
-            ContextualTransactionVerifier::new(Arc::clone(&rtx), consensus, data_loader, tx_env)
+            let mut verifier = ContextualTransactionVerifier::new(
+                Arc::clone(&rtx),
+                consensus,
+                data_loader,
+                tx_env,
+            );
+            if parallel_script_verification {
+                verifier.enable_parallel_script_verification();
+            }
+            verifier
                 .verify(max_tx_verify_cycles, false)
                 .and_then(|result| {
                     DaoScriptSizeVerifier::new(
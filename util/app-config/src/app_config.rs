@@ -12,6 +12,7 @@ use std::path::{Path, PathBuf};
 use serde::{Deserialize, Serialize};
 
 use ckb_chain_spec::ChainSpec;
+use ckb_constant::store::MIN_PRUNE_RETENTION_DEPTH;
 pub use ckb_logger_config::Config as LogConfig;
 pub use ckb_metrics_config::Config as MetricsConfig;
 use ckb_resource::Resource;
@@ -285,6 +286,7 @@ impl CKBAppConfig {
         }))?;
 
         self.network.path = self.data_dir.join("network");
+        self.network.asn_map_path = self.network.asn_map_path.map(|path| root_dir.join(path));
         if self.tmp_dir.is_none() {
             self.tmp_dir = Some(self.data_dir.join("tmp"));
         }
@@ -301,6 +303,30 @@ impl CKBAppConfig {
             return Ok(self);
         }
 
+        if let Some(depth) = self.store.prune_retention_depth {
+            if !self.store.freezer_enable {
+                eprintln!(
+                    "The pruned mode requires the freezer, set `store.freezer_enable = true`."
+                );
+                return Err(ExitCode::Config);
+            }
+            if depth < MIN_PRUNE_RETENTION_DEPTH {
+                eprintln!(
+                    "`store.prune_retention_depth` must be at least {MIN_PRUNE_RETENTION_DEPTH}, got {depth}."
+                );
+                return Err(ExitCode::Config);
+            }
+            // the block filters and the light client proofs of the pruned blocks can't be served
+            for protocol in [SupportProtocol::Filter, SupportProtocol::LightClient] {
+                if self.network.support_protocols.contains(&protocol) {
+                    eprintln!(
+                        "The pruned mode doesn't support the protocol {protocol:?}, remove it from `network.support_protocols`."
+                    );
+                    return Err(ExitCode::Config);
+                }
+            }
+        }
+
         self.data_dir = mkdir(self.data_dir)?;
         self.db.path = mkdir(self.db.path)?;
         self.network.path = mkdir(self.network.path)?;
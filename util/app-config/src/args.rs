@@ -1,9 +1,10 @@
 use crate::{CKBAppConfig, MemoryTrackerConfig, MinerConfig};
 use ckb_chain_spec::consensus::Consensus;
-use ckb_jsonrpc_types::ScriptHashType;
+use ckb_jsonrpc_types::{ScriptGroupType, ScriptHashType};
 use ckb_pow::PowEngine;
 use ckb_systemtime::unix_time_as_millis;
-use ckb_types::packed::Byte32;
+use ckb_types::{packed::Byte32, H256};
+use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::sync::Arc;
 
@@ -15,6 +16,8 @@ pub struct ExportArgs {
     pub consensus: Consensus,
     /// The target directory to save the exported file.
     pub target: PathBuf,
+    /// Whether to export the cell set snapshot instead of the blocks.
+    pub snapshot: bool,
 }
 
 #[derive(Debug)]
@@ -36,6 +39,8 @@ pub struct ImportArgs {
     pub consensus: Consensus,
     /// The path to the file to be imported.
     pub source: PathBuf,
+    /// Whether to import a cell set snapshot instead of the blocks.
+    pub snapshot: bool,
 }
 
 /// Parsed command line arguments for `ckb run`.
@@ -105,6 +110,32 @@ pub struct StatsArgs {
     pub to: Option<u64>,
 }
 
+/// The transaction loaded by `ckb debug-script`.
+pub enum DebugScriptTransaction {
+    /// The hash of a committed transaction in the database.
+    Hash(H256),
+    /// The JSON file of a transaction in the RPC format.
+    File(PathBuf),
+}
+
+/// Parsed command line arguments for `ckb debug-script`.
+pub struct DebugScriptArgs {
+    /// Parsed `ckb.toml`.
+    pub config: Box<CKBAppConfig>,
+    /// Loaded consensus.
+    pub consensus: Consensus,
+    /// The transaction to debug.
+    pub transaction: DebugScriptTransaction,
+    /// The type of the script group to debug.
+    pub script_group_type: ScriptGroupType,
+    /// The script hash of the script group to debug.
+    pub script_hash: H256,
+    /// The address the GDB stub listens on.
+    pub listen: SocketAddr,
+    /// The cycles limit. The default is the max block cycles.
+    pub max_cycles: Option<u64>,
+}
+
 /// Parsed command line arguments for `ckb init`.
 pub struct InitArgs {
     /// Whether to prompt user inputs interactively.
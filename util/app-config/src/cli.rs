@@ -20,6 +20,8 @@ pub const CMD_INIT: &str = "init";
 pub const CMD_REPLAY: &str = "replay";
 /// Subcommand `stats`.
 pub const CMD_STATS: &str = "stats";
+/// Subcommand `debug-script`.
+pub const CMD_DEBUG_SCRIPT: &str = "debug-script";
 /// Subcommand `list-hashes`.
 pub const CMD_LIST_HASHES: &str = "list-hashes";
 /// Subcommand `reset-data`.
@@ -42,6 +44,8 @@ pub const ARG_FORMAT: &str = "format";
 pub const ARG_TARGET: &str = "target";
 /// Command line argument `--source`.
 pub const ARG_SOURCE: &str = "source";
+/// Command line argument `--snapshot`.
+pub const ARG_SNAPSHOT: &str = "snapshot";
 /// Command line argument `--data`.
 pub const ARG_DATA: &str = "data";
 /// Command line argument `--list-chains`.
@@ -114,6 +118,18 @@ pub const ARG_OVERWRITE_CHAIN_SPEC: &str = "overwrite-spec";
 pub const ARG_ASSUME_VALID_TARGET: &str = "assume-valid-target";
 /// Command line argument `--check`.
 pub const ARG_MIGRATE_CHECK: &str = "check";
+/// Command line argument `--tx-hash`.
+pub const ARG_TX_HASH: &str = "tx-hash";
+/// Command line argument `--tx-file`.
+pub const ARG_TX_FILE: &str = "tx-file";
+/// Command line argument `--script-group-type`.
+pub const ARG_SCRIPT_GROUP_TYPE: &str = "script-group-type";
+/// Command line argument `--script-hash`.
+pub const ARG_SCRIPT_HASH: &str = "script-hash";
+/// Command line argument `--listen`.
+pub const ARG_LISTEN: &str = "listen";
+/// Command line argument `--max-cycles`.
+pub const ARG_MAX_CYCLES: &str = "max-cycles";
 /// Command line argument `daemon --check`
 pub const ARG_DAEMON_CHECK: &str = "check";
 /// Command line argument `daemon --stop`
@@ -148,6 +164,7 @@ pub fn basic_app() -> Command {
         .subcommand(init())
         .subcommand(replay())
         .subcommand(stats())
+        .subcommand(debug_script())
         .subcommand(reset_data())
         .subcommand(peer_id())
         .subcommand(migrate());
@@ -315,6 +332,70 @@ pub(crate) fn stats() -> Command {
         )
 }
 
+pub(crate) fn debug_script() -> Command {
+    Command::new(CMD_DEBUG_SCRIPT)
+        .about(
+            "Run a script group of a transaction under a GDB remote stub\n\
+             Example:\n\
+             ckb -C <dir> debug-script --tx-hash <hash> --script-group-type lock --script-hash <hash>\n\
+             gdb -ex 'target remote 127.0.0.1:9999' <script binary>",
+        )
+        .arg(
+            Arg::new(ARG_TX_HASH)
+                .long(ARG_TX_HASH)
+                .value_name("hash")
+                .value_parser(is_h256)
+                .action(clap::ArgAction::Set)
+                .help("Load the committed transaction from the database"),
+        )
+        .arg(
+            Arg::new(ARG_TX_FILE)
+                .long(ARG_TX_FILE)
+                .value_name("path")
+                .value_parser(clap::builder::PathBufValueParser::new())
+                .action(clap::ArgAction::Set)
+                .help(
+                    "Load the transaction from a JSON file in the RPC format, \
+                    whose inputs and cell deps are resolved against the database",
+                ),
+        )
+        .group(
+            ArgGroup::new("transaction")
+                .args([ARG_TX_HASH, ARG_TX_FILE])
+                .required(true),
+        )
+        .arg(
+            Arg::new(ARG_SCRIPT_GROUP_TYPE)
+                .long(ARG_SCRIPT_GROUP_TYPE)
+                .value_parser(["lock", "type"])
+                .required(true)
+                .help("Specify the type of the script group to debug"),
+        )
+        .arg(
+            Arg::new(ARG_SCRIPT_HASH)
+                .long(ARG_SCRIPT_HASH)
+                .value_name("hash")
+                .value_parser(is_h256)
+                .required(true)
+                .help("Specify the script hash of the script group to debug"),
+        )
+        .arg(
+            Arg::new(ARG_LISTEN)
+                .long(ARG_LISTEN)
+                .value_name("address")
+                .value_parser(clap::value_parser!(std::net::SocketAddr))
+                .default_value("127.0.0.1:9999")
+                .help("Listen on the address for the GDB connection"),
+        )
+        .arg(
+            Arg::new(ARG_MAX_CYCLES)
+                .long(ARG_MAX_CYCLES)
+                .value_parser(clap::value_parser!(u64))
+                .action(clap::ArgAction::Set)
+                .help("Specify the cycles limit [default: the max block cycles]"),
+        )
+}
+
 fn replay() -> Command {
     Command::new(CMD_REPLAY)
         .about("Replay CKB process block")
@@ -352,26 +433,48 @@ fn replay() -> Command {
 }
 
 fn export() -> Command {
-    Command::new(CMD_EXPORT).about("Export CKB data").arg(
-        Arg::new(ARG_TARGET)
-            .short('t')
-            .long(ARG_TARGET)
-            .value_name("path")
-            .value_parser(clap::builder::PathBufValueParser::new())
-            .required(true)
-            .help("Specify the export target path"),
-    )
+    Command::new(CMD_EXPORT)
+        .about("Export CKB data")
+        .arg(
+            Arg::new(ARG_TARGET)
+                .short('t')
+                .long(ARG_TARGET)
+                .value_name("path")
+                .value_parser(clap::builder::PathBufValueParser::new())
+                .required(true)
+                .help("Specify the export target path"),
+        )
+        .arg(
+            Arg::new(ARG_SNAPSHOT)
+                .long(ARG_SNAPSHOT)
+                .action(clap::ArgAction::SetTrue)
+                .help(
+                    "Export the cell set snapshot at the tip instead of the blocks, \
+                    which can be pinned by `assume_utxo` in the chain spec",
+                ),
+        )
 }
 
 fn import() -> Command {
-    Command::new(CMD_IMPORT).about("Import CKB data").arg(
-        Arg::new(ARG_SOURCE)
-            .index(1)
-            .value_name("path")
-            .value_parser(clap::builder::PathBufValueParser::new())
-            .required(true)
-            .help("Specify the exported data path"),
-    )
+    Command::new(CMD_IMPORT)
+        .about("Import CKB data")
+        .arg(
+            Arg::new(ARG_SOURCE)
+                .index(1)
+                .value_name("path")
+                .value_parser(clap::builder::PathBufValueParser::new())
+                .required(true)
+                .help("Specify the exported data path"),
+        )
+        .arg(
+            Arg::new(ARG_SNAPSHOT)
+                .long(ARG_SNAPSHOT)
+                .action(clap::ArgAction::SetTrue)
+                .help(
+                    "Import the cell set snapshot pinned by `assume_utxo` in the chain spec \
+                    into a new node, the historical blocks are back-filled after the node starts",
+                ),
+        )
 }
 
 fn migrate() -> Command {
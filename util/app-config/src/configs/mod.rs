@@ -17,8 +17,8 @@ pub use miner::{
     ExtraHashFunction, WorkerConfig as MinerWorkerConfig,
 };
 pub use network::{
-    default_support_all_protocols, Config as NetworkConfig, HeaderMapConfig, SupportProtocol,
-    SyncConfig,
+    default_support_all_protocols, BandwidthConfig, Config as NetworkConfig, HeaderMapConfig,
+    ProtocolBandwidthConfig, SupportProtocol, SyncConfig,
 };
 pub use network_alert::Config as NetworkAlertConfig;
 pub use notify::Config as NotifyConfig;
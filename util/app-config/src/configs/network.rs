@@ -2,6 +2,7 @@ use ckb_types::{H256, U256};
 use multiaddr::Multiaddr;
 use rand::Rng;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::io::{Error, ErrorKind, Read, Write};
 use std::path::PathBuf;
@@ -66,6 +67,14 @@ pub struct Config {
     /// When `whitelist_only` is enabled, the node will only connect to peers in this list.
     #[serde(default)]
     pub whitelist_peers: Vec<Multiaddr>,
+    /// A list of trusted peers, each address must be an IP address ending with `/p2p/<peer id>`.
+    ///
+    /// Trusted peers are whitelisted, and their identities are pinned to their IPs: a connection
+    /// from the IP of a trusted peer is refused unless the public key of the remote peer matches
+    /// one of the peer ids configured for the IP, and a trusted peer id connecting from another
+    /// IP is refused as well.
+    #[serde(default)]
+    pub trusted_peers: Vec<Multiaddr>,
     /// Enable UPNP when the router supports it.
     #[serde(default)]
     pub upnp: bool,
@@ -88,6 +97,43 @@ pub struct Config {
     pub sync: SyncConfig,
     /// Tentacle inner channel_size.
     pub channel_size: Option<usize>,
+    /// Path of the file mapping IP prefixes to autonomous system numbers.
+    ///
+    /// When set, peers are grouped by the autonomous system instead of the IP prefix, so the
+    /// outbound peers are spread over more network operators.
+    #[serde(default)]
+    pub asn_map_path: Option<PathBuf>,
+    /// Bandwidth limits config options.
+    #[serde(default)]
+    pub bandwidth: BandwidthConfig,
+}
+
+/// Bandwidth limits config options.
+///
+/// The rates are in bytes per second. The limits of a protocol apply in addition to the global
+/// ones, so it is possible to cap the serving of historical blocks in `Sync` while leaving
+/// `Relay` unlimited. The messages exceeding the upload limits are delayed in the queue of each
+/// peer, and the peers exceeding the download limits are back pressured.
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct BandwidthConfig {
+    /// The maximum upload rate of all protocols
+    pub max_upload_rate: Option<ByteUnit>,
+    /// The maximum download rate of all protocols
+    pub max_download_rate: Option<ByteUnit>,
+    /// The limits of individual protocols
+    #[serde(default)]
+    pub protocols: HashMap<SupportProtocol, ProtocolBandwidthConfig>,
+}
+
+/// Bandwidth limits of a protocol.
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ProtocolBandwidthConfig {
+    /// The maximum upload rate of the protocol
+    pub max_upload_rate: Option<ByteUnit>,
+    /// The maximum download rate of the protocol
+    pub max_download_rate: Option<ByteUnit>,
 }
 
 /// Chain synchronization config options.
@@ -148,6 +194,7 @@ pub enum SupportProtocol {
     Alert,
     LightClient,
     Filter,
+    TxReconciliation,
 }
 
 #[allow(missing_docs)]
@@ -303,9 +350,13 @@ impl Config {
         }
     }
 
-    /// Gets the list of whitelist peers.
+    /// Gets the list of whitelist peers, the trusted peers included.
     pub fn whitelist_peers(&self) -> Vec<Multiaddr> {
-        self.whitelist_peers.clone()
+        self.whitelist_peers
+            .iter()
+            .chain(self.trusted_peers.iter())
+            .cloned()
+            .collect()
     }
 
     /// Gets a list of bootnodes.
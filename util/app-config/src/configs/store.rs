@@ -18,4 +18,11 @@ pub struct Config {
     pub block_extensions_cache_size: usize,
     /// whether enable freezer
     pub freezer_enable: bool,
+    /// Enables the pruned mode, only the bodies of the latest blocks within this depth are kept.
+    ///
+    /// The headers, the cell set and the chain root MMR are always kept. It requires the freezer,
+    /// and it doesn't support the `Filter` and `LightClient` protocols. The indexer can't catch up
+    /// with the node once it falls behind this depth, it must be rebuilt from a node which is not
+    /// pruned then.
+    pub prune_retention_depth: Option<u64>,
 }
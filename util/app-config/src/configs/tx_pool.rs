@@ -25,7 +25,8 @@ pub struct TxPoolConfig {
     pub keep_rejected_tx_hashes_days: u8,
     /// rejected tx count limit
     pub keep_rejected_tx_hashes_count: u64,
-    /// The file to persist the tx pool on the disk when tx pool have been shutdown.
+    /// The file to persist the tx pool on the disk, the tx pool is journaled into it
+    /// incrementally, so the pending txs are recovered after restart even if the node crashed.
     ///
     /// By default, it is a subdirectory of 'tx-pool' subdirectory under the data directory.
     #[serde(default)]
@@ -37,6 +38,13 @@ pub struct TxPoolConfig {
     pub recent_reject: PathBuf,
     /// The expiration time for pool transactions in hours
     pub expiry_hours: u8,
+    /// The max count of in-pool txs spending the cells of a lock script, 0 means no limit
+    pub max_tx_count_per_lock: usize,
+    /// The max total size of in-pool txs spending the cells of a lock script, 0 means no limit
+    pub max_tx_size_per_lock: usize,
+    /// Verify the independent script groups of a transaction in parallel, both for the txs
+    /// submitted to the pool and for the txs of the blocks, disabled by default
+    pub parallel_script_verification: bool,
 }
 
 /// Block assembler config options.
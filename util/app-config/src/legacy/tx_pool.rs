@@ -17,6 +17,10 @@ const DEFAULT_MAX_ANCESTORS_COUNT: usize = 125;
 const DEFAULT_EXPIRY_HOURS: u8 = 12;
 // Default max_tx_pool_size 180mb
 const DEFAULT_MAX_TX_POOL_SIZE: usize = 180_000_000;
+// Default max count of in-pool txs spending the cells of a lock script, no limit
+const DEFAULT_MAX_TX_COUNT_PER_LOCK: usize = 0;
+// Default max total size of in-pool txs spending the cells of a lock script, no limit
+const DEFAULT_MAX_TX_SIZE_PER_LOCK: usize = 0;
 
 #[derive(Clone, Debug, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -45,6 +49,12 @@ pub(crate) struct TxPoolConfig {
     recent_reject: PathBuf,
     #[serde(default = "default_expiry_hours")]
     expiry_hours: u8,
+    #[serde(default = "default_max_tx_count_per_lock")]
+    max_tx_count_per_lock: usize,
+    #[serde(default = "default_max_tx_size_per_lock")]
+    max_tx_size_per_lock: usize,
+    #[serde(default)]
+    parallel_script_verification: bool,
 }
 
 fn default_keep_rejected_tx_hashes_days() -> u8 {
@@ -67,6 +77,14 @@ fn default_min_rbf_rate() -> FeeRate {
     DEFAULT_MIN_RBF_RATE
 }
 
+fn default_max_tx_count_per_lock() -> usize {
+    DEFAULT_MAX_TX_COUNT_PER_LOCK
+}
+
+fn default_max_tx_size_per_lock() -> usize {
+    DEFAULT_MAX_TX_SIZE_PER_LOCK
+}
+
 impl Default for crate::TxPoolConfig {
     fn default() -> Self {
         TxPoolConfig::default().into()
@@ -91,6 +109,9 @@ impl Default for TxPoolConfig {
             persisted_data: Default::default(),
             recent_reject: Default::default(),
             expiry_hours: DEFAULT_EXPIRY_HOURS,
+            max_tx_count_per_lock: DEFAULT_MAX_TX_COUNT_PER_LOCK,
+            max_tx_size_per_lock: DEFAULT_MAX_TX_SIZE_PER_LOCK,
+            parallel_script_verification: false,
         }
     }
 }
@@ -113,6 +134,9 @@ impl From<TxPoolConfig> for crate::TxPoolConfig {
             persisted_data,
             recent_reject,
             expiry_hours,
+            max_tx_count_per_lock,
+            max_tx_size_per_lock,
+            parallel_script_verification,
         } = input;
 
         Self {
@@ -126,6 +150,9 @@ impl From<TxPoolConfig> for crate::TxPoolConfig {
             persisted_data,
             recent_reject,
             expiry_hours,
+            max_tx_count_per_lock,
+            max_tx_size_per_lock,
+            parallel_script_verification,
         }
     }
 }
@@ -15,8 +15,8 @@ pub use app_config::{
     AppConfig, CKBAppConfig, ChainConfig, LogConfig, MetricsConfig, MinerAppConfig,
 };
 pub use args::{
-    DaemonArgs, ExportArgs, ImportArgs, InitArgs, MigrateArgs, MinerArgs, PeerIDArgs, ReplayArgs,
-    ResetDataArgs, RunArgs, StatsArgs,
+    DaemonArgs, DebugScriptArgs, DebugScriptTransaction, ExportArgs, ImportArgs, InitArgs,
+    MigrateArgs, MinerArgs, PeerIDArgs, ReplayArgs, ResetDataArgs, RunArgs, StatsArgs,
 };
 pub use configs::*;
 pub use exit_code::ExitCode;
@@ -25,10 +25,10 @@ pub use sentry_config::SentryConfig;
 pub use url::Url;
 
 use ckb_chain_spec::{consensus::Consensus, ChainSpec};
-use ckb_jsonrpc_types::ScriptHashType;
+use ckb_jsonrpc_types::{ScriptGroupType, ScriptHashType};
 use ckb_types::{u256, H256, U256};
 use clap::ArgMatches;
-use std::{path::PathBuf, str::FromStr};
+use std::{net::SocketAddr, path::PathBuf, str::FromStr};
 
 // 500_000 total difficulty
 const MIN_CHAIN_WORK_500K: U256 = u256!("0x3314412053c82802a7");
@@ -182,6 +182,51 @@ impl Setup {
         })
     }
 
+    /// Executes `ckb debug-script`.
+    pub fn debug_script(self, matches: &ArgMatches) -> Result<DebugScriptArgs, ExitCode> {
+        let consensus = self.consensus()?;
+        let config = self.config.into_ckb()?;
+
+        let parse_h256 = |hex: &String| H256::from_str(&hex[2..]).map_err(|_| ExitCode::Cli);
+        let transaction = match (
+            matches.get_one::<String>(cli::ARG_TX_HASH),
+            matches.get_one::<PathBuf>(cli::ARG_TX_FILE),
+        ) {
+            (Some(tx_hash), _) => DebugScriptTransaction::Hash(parse_h256(tx_hash)?),
+            (None, Some(tx_file)) => DebugScriptTransaction::File(tx_file.clone()),
+            (None, None) => {
+                eprintln!(
+                    "Args Error: {:?} or {:?} no found",
+                    cli::ARG_TX_HASH,
+                    cli::ARG_TX_FILE
+                );
+                return Err(ExitCode::Cli);
+            }
+        };
+        let script_group_type = matches
+            .get_one::<String>(cli::ARG_SCRIPT_GROUP_TYPE)
+            .and_then(|group_type| serde_plain::from_str::<ScriptGroupType>(group_type).ok())
+            .ok_or(ExitCode::Cli)?;
+        let script_hash = matches
+            .get_one::<String>(cli::ARG_SCRIPT_HASH)
+            .ok_or(ExitCode::Cli)
+            .and_then(parse_h256)?;
+        let listen = *matches
+            .get_one::<SocketAddr>(cli::ARG_LISTEN)
+            .expect("has default value");
+        let max_cycles = matches.get_one::<u64>(cli::ARG_MAX_CYCLES).cloned();
+
+        Ok(DebugScriptArgs {
+            config,
+            consensus,
+            transaction,
+            script_group_type,
+            script_hash,
+            listen,
+            max_cycles,
+        })
+    }
+
     /// Executes `ckb import`.
     pub fn import(self, matches: &ArgMatches) -> Result<ImportArgs, ExitCode> {
         let consensus = self.consensus()?;
@@ -193,11 +238,13 @@ impl Setup {
                 ExitCode::Cli
             })?
             .clone();
+        let snapshot = matches.get_flag(cli::ARG_SNAPSHOT);
 
         Ok(ImportArgs {
             config,
             consensus,
             source,
+            snapshot,
         })
     }
 
@@ -212,11 +259,13 @@ impl Setup {
                 ExitCode::Cli
             })?
             .clone();
+        let snapshot = matches.get_flag(cli::ARG_SNAPSHOT);
 
         Ok(ExportArgs {
             config,
             consensus,
             target,
+            snapshot,
         })
     }
 
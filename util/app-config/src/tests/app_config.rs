@@ -274,3 +274,36 @@ fn test_export_dev_config_files_assembly() {
         assert_eq!(miner_config.miner.client.rpc_url, "http://127.0.0.1:7000/");
     }
 }
+
+#[test]
+fn test_pruned_mode_protocols() {
+    let dir = mkdir();
+    let context = TemplateContext::new(
+        "dev",
+        vec![
+            ("rpc_port", "7000"),
+            ("p2p_port", "8000"),
+            ("log_to_file", "false"),
+            ("log_to_stdout", "true"),
+            ("block_assembler", ""),
+            ("spec_source", "bundled"),
+        ],
+    );
+    Resource::bundled_ckb_config()
+        .export(&context, dir.path())
+        .expect("export config files");
+    let config_path = dir.path().join("ckb.toml");
+    let config = std::fs::read_to_string(&config_path)
+        .unwrap()
+        .replace("# freezer_enable = true", "freezer_enable = true")
+        .replace(
+            "# prune_retention_depth = 100000",
+            "prune_retention_depth = 100000",
+        );
+    std::fs::write(&config_path, config).unwrap();
+    // the bundled config supports the `LightClient` and `Filter` protocols
+    assert!(matches!(
+        AppConfig::load_for_subcommand(dir.path(), cli::CMD_RUN),
+        Err(crate::ExitCode::Config)
+    ));
+}
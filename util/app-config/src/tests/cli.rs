@@ -25,6 +25,79 @@ fn stats_args() {
     assert!(stats.is_ok());
 }
 
+#[test]
+fn debug_script_args() {
+    let app = Command::new("debug_script_args_test")
+        .arg_required_else_help(true)
+        .subcommand(debug_script());
+    let hash = "0x0000000000000000000000000000000000000000000000000000000000000001";
+
+    let debug_script = app.clone().try_get_matches_from(vec![
+        "",
+        CMD_DEBUG_SCRIPT,
+        "--tx-hash",
+        hash,
+        "--script-group-type",
+        "lock",
+        "--script-hash",
+        hash,
+    ]);
+    assert!(debug_script.is_ok());
+
+    let debug_script = app.clone().try_get_matches_from(vec![
+        "",
+        CMD_DEBUG_SCRIPT,
+        "--tx-file",
+        "tx.json",
+        "--script-group-type",
+        "type",
+        "--script-hash",
+        hash,
+        "--listen",
+        "127.0.0.1:2000",
+        "--max-cycles",
+        "1000",
+    ]);
+    assert!(debug_script.is_ok());
+
+    // Either the transaction hash or the file is required, but not both
+    let debug_script = app.clone().try_get_matches_from(vec![
+        "",
+        CMD_DEBUG_SCRIPT,
+        "--script-group-type",
+        "lock",
+        "--script-hash",
+        hash,
+    ]);
+    assert!(debug_script.is_err());
+
+    let debug_script = app.clone().try_get_matches_from(vec![
+        "",
+        CMD_DEBUG_SCRIPT,
+        "--tx-hash",
+        hash,
+        "--tx-file",
+        "tx.json",
+        "--script-group-type",
+        "lock",
+        "--script-hash",
+        hash,
+    ]);
+    assert!(debug_script.is_err());
+
+    let debug_script = app.try_get_matches_from(vec![
+        "",
+        CMD_DEBUG_SCRIPT,
+        "--tx-hash",
+        hash,
+        "--script-group-type",
+        "data",
+        "--script-hash",
+        hash,
+    ]);
+    assert!(debug_script.is_err());
+}
+
 #[test]
 fn ba_message_requires_ba_arg_or_ba_code_hash() {
     let ok_ba_arg = basic_app().try_get_matches_from([
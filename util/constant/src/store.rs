@@ -2,3 +2,7 @@
 /// so that db iterating can stop sooner, rather than walking over the whole range of tombstones.
 /// empty_tx_size = 72
 pub const TX_INDEX_UPPER_BOUND: u32 = 597 * 1000 / 72;
+
+/// The least retention depth of a pruned node, peers only request the bodies of the latest blocks
+/// within this depth from a pruned node.
+pub const MIN_PRUNE_RETENTION_DEPTH: u64 = 8192;
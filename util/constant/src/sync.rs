@@ -53,6 +53,10 @@ pub const BLOCK_DOWNLOAD_TIMEOUT: u64 = 30 * 1000; // 30s
 // potential degree of disordering of blocks.
 pub const BLOCK_DOWNLOAD_WINDOW: u64 = 1024 * 8; // 1024 * default_outbound_peers
 
+/// The blocks right after the tip are only downloaded from the peers with enough throughput, and
+/// their downloads are re-assigned to faster peers when stalled, since they block the whole sync.
+pub const BLOCK_DOWNLOAD_FRONT_WINDOW: u64 = INIT_BLOCKS_IN_TRANSIT_PER_PEER as u64;
+
 /// Interval between repeated inquiry transactions
 pub const RETRY_ASK_TX_TIMEOUT_INCREASE: Duration = Duration::from_secs(30);
 
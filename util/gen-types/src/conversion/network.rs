@@ -1,7 +1,15 @@
 use crate::{packed, prelude::*};
 
+impl Pack<packed::SaltedShortId> for [u8; 6] {
+    fn pack(&self) -> packed::SaltedShortId {
+        packed::SaltedShortId::from_slice(&self[..])
+            .expect("impossible: fail to pack to SaltedShortId")
+    }
+}
+
 impl_conversion_for_packed_iterator_pack!(IndexTransaction, IndexTransactionVec);
 impl_conversion_for_packed_iterator_pack!(RelayTransaction, RelayTransactionVec);
+impl_conversion_for_packed_iterator_pack!(SaltedShortId, SaltedShortIdVec);
 impl_conversion_for_packed_iterator_pack!(Uint256, Uint256Vec);
 impl_conversion_for_packed_iterator_pack!(HeaderDigest, HeaderDigestVec);
 impl_conversion_for_packed_iterator_pack!(VerifiableHeader, VerifiableHeaderVec);
@@ -27,6 +27,7 @@ impl_cmp_eq_and_hash!(Byte32);
 impl_cmp_eq_and_hash!(Bytes);
 impl_cmp_eq_and_hash!(BytesOpt);
 impl_cmp_eq_and_hash!(ProposalShortId);
+impl_cmp_eq_and_hash!(SaltedShortId);
 impl_cmp_eq_and_hash!(Script);
 impl_cmp_eq_and_hash!(ScriptOpt);
 impl_cmp_eq_and_hash!(CellDep);
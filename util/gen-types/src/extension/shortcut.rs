@@ -281,6 +281,47 @@ impl<'r> packed::CompactBlockV1Reader<'r> {
     }
 }
 
+impl packed::SaltedShortId {
+    /// Widens to a [`ProposalShortId`](struct.ProposalShortId.html) padded with zeros, so salted
+    /// short ids can be matched by the same code as the unsalted ones.
+    pub fn to_proposal_short_id(&self) -> packed::ProposalShortId {
+        let mut inner = [0u8; 10];
+        inner[..Self::TOTAL_SIZE].copy_from_slice(self.as_slice());
+        inner.pack()
+    }
+}
+
+impl packed::SaltedCompactBlock {
+    /// Converts to a [`CompactBlock`](struct.CompactBlock.html) whose short ids are widened by
+    /// [`SaltedShortId::to_proposal_short_id`](struct.SaltedShortId.html#method.to_proposal_short_id).
+    pub fn to_compact_block(&self) -> packed::CompactBlock {
+        let short_ids = self
+            .short_ids()
+            .into_iter()
+            .map(|short_id| short_id.to_proposal_short_id())
+            .pack();
+        if let Some(extension) = self.extension().to_opt() {
+            packed::CompactBlockV1::new_builder()
+                .header(self.header())
+                .short_ids(short_ids)
+                .prefilled_transactions(self.prefilled_transactions())
+                .uncles(self.uncles())
+                .proposals(self.proposals())
+                .extension(extension)
+                .build()
+                .as_v0()
+        } else {
+            packed::CompactBlock::new_builder()
+                .header(self.header())
+                .short_ids(short_ids)
+                .prefilled_transactions(self.prefilled_transactions())
+                .uncles(self.uncles())
+                .proposals(self.proposals())
+                .build()
+        }
+    }
+}
+
 impl AsRef<[u8]> for packed::TransactionKey {
     #[inline]
     fn as_ref(&self) -> &[u8] {
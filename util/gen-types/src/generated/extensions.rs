@@ -5892,7 +5892,7 @@ impl RelayMessage {
         0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
         0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
     ];
-    pub const ITEMS_COUNT: usize = 8;
+    pub const ITEMS_COUNT: usize = 10;
     pub fn item_id(&self) -> molecule::Number {
         molecule::unpack_number(self.as_slice())
     }
@@ -5907,6 +5907,8 @@ impl RelayMessage {
             5 => BlockTransactions::new_unchecked(inner).into(),
             6 => GetBlockProposal::new_unchecked(inner).into(),
             7 => BlockProposal::new_unchecked(inner).into(),
+            8 => SendCompactBlock::new_unchecked(inner).into(),
+            9 => SaltedCompactBlock::new_unchecked(inner).into(),
             _ => panic!("{}: invalid data", Self::NAME),
         }
     }
@@ -5963,7 +5965,7 @@ impl<'r> ::core::fmt::Display for RelayMessageReader<'r> {
     }
 }
 impl<'r> RelayMessageReader<'r> {
-    pub const ITEMS_COUNT: usize = 8;
+    pub const ITEMS_COUNT: usize = 10;
     pub fn item_id(&self) -> molecule::Number {
         molecule::unpack_number(self.as_slice())
     }
@@ -5978,6 +5980,8 @@ impl<'r> RelayMessageReader<'r> {
             5 => BlockTransactionsReader::new_unchecked(inner).into(),
             6 => GetBlockProposalReader::new_unchecked(inner).into(),
             7 => BlockProposalReader::new_unchecked(inner).into(),
+            8 => SendCompactBlockReader::new_unchecked(inner).into(),
+            9 => SaltedCompactBlockReader::new_unchecked(inner).into(),
             _ => panic!("{}: invalid data", Self::NAME),
         }
     }
@@ -6011,6 +6015,8 @@ impl<'r> molecule::prelude::Reader<'r> for RelayMessageReader<'r> {
             5 => BlockTransactionsReader::verify(inner_slice, compatible),
             6 => GetBlockProposalReader::verify(inner_slice, compatible),
             7 => BlockProposalReader::verify(inner_slice, compatible),
+            8 => SendCompactBlockReader::verify(inner_slice, compatible),
+            9 => SaltedCompactBlockReader::verify(inner_slice, compatible),
             _ => ve!(Self, UnknownItem, Self::ITEMS_COUNT, item_id),
         }?;
         Ok(())
@@ -6019,7 +6025,7 @@ impl<'r> molecule::prelude::Reader<'r> for RelayMessageReader<'r> {
 #[derive(Debug, Default)]
 pub struct RelayMessageBuilder(pub(crate) RelayMessageUnion);
 impl RelayMessageBuilder {
-    pub const ITEMS_COUNT: usize = 8;
+    pub const ITEMS_COUNT: usize = 10;
     pub fn set<I>(mut self, v: I) -> Self
     where
         I: ::core::convert::Into<RelayMessageUnion>,
@@ -6055,6 +6061,8 @@ pub enum RelayMessageUnion {
     BlockTransactions(BlockTransactions),
     GetBlockProposal(GetBlockProposal),
     BlockProposal(BlockProposal),
+    SendCompactBlock(SendCompactBlock),
+    SaltedCompactBlock(SaltedCompactBlock),
 }
 #[derive(Debug, Clone, Copy)]
 pub enum RelayMessageUnionReader<'r> {
@@ -6066,6 +6074,8 @@ pub enum RelayMessageUnionReader<'r> {
     BlockTransactions(BlockTransactionsReader<'r>),
     GetBlockProposal(GetBlockProposalReader<'r>),
     BlockProposal(BlockProposalReader<'r>),
+    SendCompactBlock(SendCompactBlockReader<'r>),
+    SaltedCompactBlock(SaltedCompactBlockReader<'r>),
 }
 impl ::core::default::Default for RelayMessageUnion {
     fn default() -> Self {
@@ -6117,6 +6127,12 @@ impl ::core::fmt::Display for RelayMessageUnion {
             RelayMessageUnion::BlockProposal(ref item) => {
                 write!(f, "{}::{}({})", Self::NAME, BlockProposal::NAME, item)
             }
+            RelayMessageUnion::SendCompactBlock(ref item) => {
+                write!(f, "{}::{}({})", Self::NAME, SendCompactBlock::NAME, item)
+            }
+            RelayMessageUnion::SaltedCompactBlock(ref item) => {
+                write!(f, "{}::{}({})", Self::NAME, SaltedCompactBlock::NAME, item)
+            }
         }
     }
 }
@@ -6165,6 +6181,12 @@ impl<'r> ::core::fmt::Display for RelayMessageUnionReader<'r> {
             RelayMessageUnionReader::BlockProposal(ref item) => {
                 write!(f, "{}::{}({})", Self::NAME, BlockProposal::NAME, item)
             }
+            RelayMessageUnionReader::SendCompactBlock(ref item) => {
+                write!(f, "{}::{}({})", Self::NAME, SendCompactBlock::NAME, item)
+            }
+            RelayMessageUnionReader::SaltedCompactBlock(ref item) => {
+                write!(f, "{}::{}({})", Self::NAME, SaltedCompactBlock::NAME, item)
+            }
         }
     }
 }
@@ -6179,6 +6201,8 @@ impl RelayMessageUnion {
             RelayMessageUnion::BlockTransactions(ref item) => write!(f, "{}", item),
             RelayMessageUnion::GetBlockProposal(ref item) => write!(f, "{}", item),
             RelayMessageUnion::BlockProposal(ref item) => write!(f, "{}", item),
+            RelayMessageUnion::SendCompactBlock(ref item) => write!(f, "{}", item),
+            RelayMessageUnion::SaltedCompactBlock(ref item) => write!(f, "{}", item),
         }
     }
 }
@@ -6193,6 +6217,8 @@ impl<'r> RelayMessageUnionReader<'r> {
             RelayMessageUnionReader::BlockTransactions(ref item) => write!(f, "{}", item),
             RelayMessageUnionReader::GetBlockProposal(ref item) => write!(f, "{}", item),
             RelayMessageUnionReader::BlockProposal(ref item) => write!(f, "{}", item),
+            RelayMessageUnionReader::SendCompactBlock(ref item) => write!(f, "{}", item),
+            RelayMessageUnionReader::SaltedCompactBlock(ref item) => write!(f, "{}", item),
         }
     }
 }
@@ -6236,6 +6262,16 @@ impl ::core::convert::From<BlockProposal> for RelayMessageUnion {
         RelayMessageUnion::BlockProposal(item)
     }
 }
+impl ::core::convert::From<SendCompactBlock> for RelayMessageUnion {
+    fn from(item: SendCompactBlock) -> Self {
+        RelayMessageUnion::SendCompactBlock(item)
+    }
+}
+impl ::core::convert::From<SaltedCompactBlock> for RelayMessageUnion {
+    fn from(item: SaltedCompactBlock) -> Self {
+        RelayMessageUnion::SaltedCompactBlock(item)
+    }
+}
 impl<'r> ::core::convert::From<CompactBlockReader<'r>> for RelayMessageUnionReader<'r> {
     fn from(item: CompactBlockReader<'r>) -> Self {
         RelayMessageUnionReader::CompactBlock(item)
@@ -6276,6 +6312,16 @@ impl<'r> ::core::convert::From<BlockProposalReader<'r>> for RelayMessageUnionRea
         RelayMessageUnionReader::BlockProposal(item)
     }
 }
+impl<'r> ::core::convert::From<SendCompactBlockReader<'r>> for RelayMessageUnionReader<'r> {
+    fn from(item: SendCompactBlockReader<'r>) -> Self {
+        RelayMessageUnionReader::SendCompactBlock(item)
+    }
+}
+impl<'r> ::core::convert::From<SaltedCompactBlockReader<'r>> for RelayMessageUnionReader<'r> {
+    fn from(item: SaltedCompactBlockReader<'r>) -> Self {
+        RelayMessageUnionReader::SaltedCompactBlock(item)
+    }
+}
 impl RelayMessageUnion {
     pub const NAME: &'static str = "RelayMessageUnion";
     pub fn as_bytes(&self) -> molecule::bytes::Bytes {
@@ -6288,6 +6334,8 @@ impl RelayMessageUnion {
             RelayMessageUnion::BlockTransactions(item) => item.as_bytes(),
             RelayMessageUnion::GetBlockProposal(item) => item.as_bytes(),
             RelayMessageUnion::BlockProposal(item) => item.as_bytes(),
+            RelayMessageUnion::SendCompactBlock(item) => item.as_bytes(),
+            RelayMessageUnion::SaltedCompactBlock(item) => item.as_bytes(),
         }
     }
     pub fn as_slice(&self) -> &[u8] {
@@ -6300,6 +6348,8 @@ impl RelayMessageUnion {
             RelayMessageUnion::BlockTransactions(item) => item.as_slice(),
             RelayMessageUnion::GetBlockProposal(item) => item.as_slice(),
             RelayMessageUnion::BlockProposal(item) => item.as_slice(),
+            RelayMessageUnion::SendCompactBlock(item) => item.as_slice(),
+            RelayMessageUnion::SaltedCompactBlock(item) => item.as_slice(),
         }
     }
     pub fn item_id(&self) -> molecule::Number {
@@ -6312,6 +6362,8 @@ impl RelayMessageUnion {
             RelayMessageUnion::BlockTransactions(_) => 5,
             RelayMessageUnion::GetBlockProposal(_) => 6,
             RelayMessageUnion::BlockProposal(_) => 7,
+            RelayMessageUnion::SendCompactBlock(_) => 8,
+            RelayMessageUnion::SaltedCompactBlock(_) => 9,
         }
     }
     pub fn item_name(&self) -> &str {
@@ -6324,6 +6376,8 @@ impl RelayMessageUnion {
             RelayMessageUnion::BlockTransactions(_) => "BlockTransactions",
             RelayMessageUnion::GetBlockProposal(_) => "GetBlockProposal",
             RelayMessageUnion::BlockProposal(_) => "BlockProposal",
+            RelayMessageUnion::SendCompactBlock(_) => "SendCompactBlock",
+            RelayMessageUnion::SaltedCompactBlock(_) => "SaltedCompactBlock",
         }
     }
     pub fn as_reader<'r>(&'r self) -> RelayMessageUnionReader<'r> {
@@ -6336,6 +6390,8 @@ impl RelayMessageUnion {
             RelayMessageUnion::BlockTransactions(item) => item.as_reader().into(),
             RelayMessageUnion::GetBlockProposal(item) => item.as_reader().into(),
             RelayMessageUnion::BlockProposal(item) => item.as_reader().into(),
+            RelayMessageUnion::SendCompactBlock(item) => item.as_reader().into(),
+            RelayMessageUnion::SaltedCompactBlock(item) => item.as_reader().into(),
         }
     }
 }
@@ -6351,6 +6407,8 @@ impl<'r> RelayMessageUnionReader<'r> {
             RelayMessageUnionReader::BlockTransactions(item) => item.as_slice(),
             RelayMessageUnionReader::GetBlockProposal(item) => item.as_slice(),
             RelayMessageUnionReader::BlockProposal(item) => item.as_slice(),
+            RelayMessageUnionReader::SendCompactBlock(item) => item.as_slice(),
+            RelayMessageUnionReader::SaltedCompactBlock(item) => item.as_slice(),
         }
     }
     pub fn item_id(&self) -> molecule::Number {
@@ -6363,6 +6421,8 @@ impl<'r> RelayMessageUnionReader<'r> {
             RelayMessageUnionReader::BlockTransactions(_) => 5,
             RelayMessageUnionReader::GetBlockProposal(_) => 6,
             RelayMessageUnionReader::BlockProposal(_) => 7,
+            RelayMessageUnionReader::SendCompactBlock(_) => 8,
+            RelayMessageUnionReader::SaltedCompactBlock(_) => 9,
         }
     }
     pub fn item_name(&self) -> &str {
@@ -6375,6 +6435,8 @@ impl<'r> RelayMessageUnionReader<'r> {
             RelayMessageUnionReader::BlockTransactions(_) => "BlockTransactions",
             RelayMessageUnionReader::GetBlockProposal(_) => "GetBlockProposal",
             RelayMessageUnionReader::BlockProposal(_) => "BlockProposal",
+            RelayMessageUnionReader::SendCompactBlock(_) => "SendCompactBlock",
+            RelayMessageUnionReader::SaltedCompactBlock(_) => "SaltedCompactBlock",
         }
     }
 }
@@ -10108,8 +10170,8 @@ impl<'t: 'r, 'r> ::core::iter::ExactSizeIterator for IndexTransactionVecReaderIt
     }
 }
 #[derive(Clone)]
-pub struct BlockFilterMessage(molecule::bytes::Bytes);
-impl ::core::fmt::LowerHex for BlockFilterMessage {
+pub struct SendCompactBlock(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for SendCompactBlock {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         use molecule::hex_string;
         if f.alternate() {
@@ -10118,51 +10180,45 @@ impl ::core::fmt::LowerHex for BlockFilterMessage {
         write!(f, "{}", hex_string(self.as_slice()))
     }
 }
-impl ::core::fmt::Debug for BlockFilterMessage {
+impl ::core::fmt::Debug for SendCompactBlock {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{}({:#x})", Self::NAME, self)
     }
 }
-impl ::core::fmt::Display for BlockFilterMessage {
+impl ::core::fmt::Display for SendCompactBlock {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-        write!(f, "{}(", Self::NAME)?;
-        self.to_enum().display_inner(f)?;
-        write!(f, ")")
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "salt", self.salt())?;
+        write!(f, ", {}: {}", "high_bandwidth", self.high_bandwidth())?;
+        write!(f, " }}")
     }
 }
-impl ::core::default::Default for BlockFilterMessage {
+impl ::core::default::Default for SendCompactBlock {
     fn default() -> Self {
         let v = molecule::bytes::Bytes::from_static(&Self::DEFAULT_VALUE);
-        BlockFilterMessage::new_unchecked(v)
+        SendCompactBlock::new_unchecked(v)
     }
 }
-impl BlockFilterMessage {
-    const DEFAULT_VALUE: [u8; 12] = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
-    pub const ITEMS_COUNT: usize = 6;
-    pub fn item_id(&self) -> molecule::Number {
-        molecule::unpack_number(self.as_slice())
+impl SendCompactBlock {
+    const DEFAULT_VALUE: [u8; 9] = [0, 0, 0, 0, 0, 0, 0, 0, 0];
+    pub const TOTAL_SIZE: usize = 9;
+    pub const FIELD_SIZES: [usize; 2] = [8, 1];
+    pub const FIELD_COUNT: usize = 2;
+    pub fn salt(&self) -> Uint64 {
+        Uint64::new_unchecked(self.0.slice(0..8))
     }
-    pub fn to_enum(&self) -> BlockFilterMessageUnion {
-        let inner = self.0.slice(molecule::NUMBER_SIZE..);
-        match self.item_id() {
-            0 => GetBlockFilters::new_unchecked(inner).into(),
-            1 => BlockFilters::new_unchecked(inner).into(),
-            2 => GetBlockFilterHashes::new_unchecked(inner).into(),
-            3 => BlockFilterHashes::new_unchecked(inner).into(),
-            4 => GetBlockFilterCheckPoints::new_unchecked(inner).into(),
-            5 => BlockFilterCheckPoints::new_unchecked(inner).into(),
-            _ => panic!("{}: invalid data", Self::NAME),
-        }
+    pub fn high_bandwidth(&self) -> Bool {
+        Bool::new_unchecked(self.0.slice(8..9))
     }
-    pub fn as_reader<'r>(&'r self) -> BlockFilterMessageReader<'r> {
-        BlockFilterMessageReader::new_unchecked(self.as_slice())
+    pub fn as_reader<'r>(&'r self) -> SendCompactBlockReader<'r> {
+        SendCompactBlockReader::new_unchecked(self.as_slice())
     }
 }
-impl molecule::prelude::Entity for BlockFilterMessage {
-    type Builder = BlockFilterMessageBuilder;
-    const NAME: &'static str = "BlockFilterMessage";
+impl molecule::prelude::Entity for SendCompactBlock {
+    type Builder = SendCompactBlockBuilder;
+    const NAME: &'static str = "SendCompactBlock";
     fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
-        BlockFilterMessage(data)
+        SendCompactBlock(data)
     }
     fn as_bytes(&self) -> molecule::bytes::Bytes {
         self.0.clone()
@@ -10171,21 +10227,23 @@ impl molecule::prelude::Entity for BlockFilterMessage {
         &self.0[..]
     }
     fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
-        BlockFilterMessageReader::from_slice(slice).map(|reader| reader.to_entity())
+        SendCompactBlockReader::from_slice(slice).map(|reader| reader.to_entity())
     }
     fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
-        BlockFilterMessageReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+        SendCompactBlockReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
     }
     fn new_builder() -> Self::Builder {
         ::core::default::Default::default()
     }
     fn as_builder(self) -> Self::Builder {
-        Self::new_builder().set(self.to_enum())
+        Self::new_builder()
+            .salt(self.salt())
+            .high_bandwidth(self.high_bandwidth())
     }
 }
 #[derive(Clone, Copy)]
-pub struct BlockFilterMessageReader<'r>(&'r [u8]);
-impl<'r> ::core::fmt::LowerHex for BlockFilterMessageReader<'r> {
+pub struct SendCompactBlockReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for SendCompactBlockReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         use molecule::hex_string;
         if f.alternate() {
@@ -10194,432 +10252,151 @@ impl<'r> ::core::fmt::LowerHex for BlockFilterMessageReader<'r> {
         write!(f, "{}", hex_string(self.as_slice()))
     }
 }
-impl<'r> ::core::fmt::Debug for BlockFilterMessageReader<'r> {
+impl<'r> ::core::fmt::Debug for SendCompactBlockReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{}({:#x})", Self::NAME, self)
     }
 }
-impl<'r> ::core::fmt::Display for BlockFilterMessageReader<'r> {
+impl<'r> ::core::fmt::Display for SendCompactBlockReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-        write!(f, "{}(", Self::NAME)?;
-        self.to_enum().display_inner(f)?;
-        write!(f, ")")
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "salt", self.salt())?;
+        write!(f, ", {}: {}", "high_bandwidth", self.high_bandwidth())?;
+        write!(f, " }}")
     }
 }
-impl<'r> BlockFilterMessageReader<'r> {
-    pub const ITEMS_COUNT: usize = 6;
-    pub fn item_id(&self) -> molecule::Number {
-        molecule::unpack_number(self.as_slice())
+impl<'r> SendCompactBlockReader<'r> {
+    pub const TOTAL_SIZE: usize = 9;
+    pub const FIELD_SIZES: [usize; 2] = [8, 1];
+    pub const FIELD_COUNT: usize = 2;
+    pub fn salt(&self) -> Uint64Reader<'r> {
+        Uint64Reader::new_unchecked(&self.as_slice()[0..8])
     }
-    pub fn to_enum(&self) -> BlockFilterMessageUnionReader<'r> {
-        let inner = &self.as_slice()[molecule::NUMBER_SIZE..];
-        match self.item_id() {
-            0 => GetBlockFiltersReader::new_unchecked(inner).into(),
-            1 => BlockFiltersReader::new_unchecked(inner).into(),
-            2 => GetBlockFilterHashesReader::new_unchecked(inner).into(),
-            3 => BlockFilterHashesReader::new_unchecked(inner).into(),
-            4 => GetBlockFilterCheckPointsReader::new_unchecked(inner).into(),
-            5 => BlockFilterCheckPointsReader::new_unchecked(inner).into(),
-            _ => panic!("{}: invalid data", Self::NAME),
-        }
+    pub fn high_bandwidth(&self) -> BoolReader<'r> {
+        BoolReader::new_unchecked(&self.as_slice()[8..9])
     }
 }
-impl<'r> molecule::prelude::Reader<'r> for BlockFilterMessageReader<'r> {
-    type Entity = BlockFilterMessage;
-    const NAME: &'static str = "BlockFilterMessageReader";
+impl<'r> molecule::prelude::Reader<'r> for SendCompactBlockReader<'r> {
+    type Entity = SendCompactBlock;
+    const NAME: &'static str = "SendCompactBlockReader";
     fn to_entity(&self) -> Self::Entity {
         Self::Entity::new_unchecked(self.as_slice().to_owned().into())
     }
     fn new_unchecked(slice: &'r [u8]) -> Self {
-        BlockFilterMessageReader(slice)
+        SendCompactBlockReader(slice)
     }
     fn as_slice(&self) -> &'r [u8] {
         self.0
     }
-    fn verify(slice: &[u8], compatible: bool) -> molecule::error::VerificationResult<()> {
+    fn verify(slice: &[u8], _compatible: bool) -> molecule::error::VerificationResult<()> {
         use molecule::verification_error as ve;
         let slice_len = slice.len();
-        if slice_len < molecule::NUMBER_SIZE {
-            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE, slice_len);
+        if slice_len != Self::TOTAL_SIZE {
+            return ve!(Self, TotalSizeNotMatch, Self::TOTAL_SIZE, slice_len);
         }
-        let item_id = molecule::unpack_number(slice);
-        let inner_slice = &slice[molecule::NUMBER_SIZE..];
-        match item_id {
-            0 => GetBlockFiltersReader::verify(inner_slice, compatible),
-            1 => BlockFiltersReader::verify(inner_slice, compatible),
-            2 => GetBlockFilterHashesReader::verify(inner_slice, compatible),
-            3 => BlockFilterHashesReader::verify(inner_slice, compatible),
-            4 => GetBlockFilterCheckPointsReader::verify(inner_slice, compatible),
-            5 => BlockFilterCheckPointsReader::verify(inner_slice, compatible),
-            _ => ve!(Self, UnknownItem, Self::ITEMS_COUNT, item_id),
-        }?;
         Ok(())
     }
 }
 #[derive(Debug, Default)]
-pub struct BlockFilterMessageBuilder(pub(crate) BlockFilterMessageUnion);
-impl BlockFilterMessageBuilder {
-    pub const ITEMS_COUNT: usize = 6;
-    pub fn set<I>(mut self, v: I) -> Self
-    where
-        I: ::core::convert::Into<BlockFilterMessageUnion>,
-    {
-        self.0 = v.into();
+pub struct SendCompactBlockBuilder {
+    pub(crate) salt: Uint64,
+    pub(crate) high_bandwidth: Bool,
+}
+impl SendCompactBlockBuilder {
+    pub const TOTAL_SIZE: usize = 9;
+    pub const FIELD_SIZES: [usize; 2] = [8, 1];
+    pub const FIELD_COUNT: usize = 2;
+    pub fn salt(mut self, v: Uint64) -> Self {
+        self.salt = v;
+        self
+    }
+    pub fn high_bandwidth(mut self, v: Bool) -> Self {
+        self.high_bandwidth = v;
         self
     }
 }
-impl molecule::prelude::Builder for BlockFilterMessageBuilder {
-    type Entity = BlockFilterMessage;
-    const NAME: &'static str = "BlockFilterMessageBuilder";
+impl molecule::prelude::Builder for SendCompactBlockBuilder {
+    type Entity = SendCompactBlock;
+    const NAME: &'static str = "SendCompactBlockBuilder";
     fn expected_length(&self) -> usize {
-        molecule::NUMBER_SIZE + self.0.as_slice().len()
+        Self::TOTAL_SIZE
     }
     fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
-        writer.write_all(&molecule::pack_number(self.0.item_id()))?;
-        writer.write_all(self.0.as_slice())
+        writer.write_all(self.salt.as_slice())?;
+        writer.write_all(self.high_bandwidth.as_slice())?;
+        Ok(())
     }
     fn build(&self) -> Self::Entity {
         let mut inner = Vec::with_capacity(self.expected_length());
         self.write(&mut inner)
             .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
-        BlockFilterMessage::new_unchecked(inner.into())
+        SendCompactBlock::new_unchecked(inner.into())
     }
 }
-#[derive(Debug, Clone)]
-pub enum BlockFilterMessageUnion {
-    GetBlockFilters(GetBlockFilters),
-    BlockFilters(BlockFilters),
-    GetBlockFilterHashes(GetBlockFilterHashes),
-    BlockFilterHashes(BlockFilterHashes),
-    GetBlockFilterCheckPoints(GetBlockFilterCheckPoints),
-    BlockFilterCheckPoints(BlockFilterCheckPoints),
-}
-#[derive(Debug, Clone, Copy)]
-pub enum BlockFilterMessageUnionReader<'r> {
-    GetBlockFilters(GetBlockFiltersReader<'r>),
-    BlockFilters(BlockFiltersReader<'r>),
-    GetBlockFilterHashes(GetBlockFilterHashesReader<'r>),
-    BlockFilterHashes(BlockFilterHashesReader<'r>),
-    GetBlockFilterCheckPoints(GetBlockFilterCheckPointsReader<'r>),
-    BlockFilterCheckPoints(BlockFilterCheckPointsReader<'r>),
-}
-impl ::core::default::Default for BlockFilterMessageUnion {
-    fn default() -> Self {
-        BlockFilterMessageUnion::GetBlockFilters(::core::default::Default::default())
+#[derive(Clone)]
+pub struct SaltedShortId(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for SaltedShortId {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
     }
 }
-impl ::core::fmt::Display for BlockFilterMessageUnion {
+impl ::core::fmt::Debug for SaltedShortId {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-        match self {
-            BlockFilterMessageUnion::GetBlockFilters(ref item) => {
-                write!(f, "{}::{}({})", Self::NAME, GetBlockFilters::NAME, item)
-            }
-            BlockFilterMessageUnion::BlockFilters(ref item) => {
-                write!(f, "{}::{}({})", Self::NAME, BlockFilters::NAME, item)
-            }
-            BlockFilterMessageUnion::GetBlockFilterHashes(ref item) => {
-                write!(
-                    f,
-                    "{}::{}({})",
-                    Self::NAME,
-                    GetBlockFilterHashes::NAME,
-                    item
-                )
-            }
-            BlockFilterMessageUnion::BlockFilterHashes(ref item) => {
-                write!(f, "{}::{}({})", Self::NAME, BlockFilterHashes::NAME, item)
-            }
-            BlockFilterMessageUnion::GetBlockFilterCheckPoints(ref item) => {
-                write!(
-                    f,
-                    "{}::{}({})",
-                    Self::NAME,
-                    GetBlockFilterCheckPoints::NAME,
-                    item
-                )
-            }
-            BlockFilterMessageUnion::BlockFilterCheckPoints(ref item) => {
-                write!(
-                    f,
-                    "{}::{}({})",
-                    Self::NAME,
-                    BlockFilterCheckPoints::NAME,
-                    item
-                )
-            }
-        }
+        write!(f, "{}({:#x})", Self::NAME, self)
     }
 }
-impl<'r> ::core::fmt::Display for BlockFilterMessageUnionReader<'r> {
+impl ::core::fmt::Display for SaltedShortId {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-        match self {
-            BlockFilterMessageUnionReader::GetBlockFilters(ref item) => {
-                write!(f, "{}::{}({})", Self::NAME, GetBlockFilters::NAME, item)
-            }
-            BlockFilterMessageUnionReader::BlockFilters(ref item) => {
-                write!(f, "{}::{}({})", Self::NAME, BlockFilters::NAME, item)
-            }
-            BlockFilterMessageUnionReader::GetBlockFilterHashes(ref item) => {
-                write!(
-                    f,
-                    "{}::{}({})",
-                    Self::NAME,
-                    GetBlockFilterHashes::NAME,
-                    item
-                )
-            }
-            BlockFilterMessageUnionReader::BlockFilterHashes(ref item) => {
-                write!(f, "{}::{}({})", Self::NAME, BlockFilterHashes::NAME, item)
-            }
-            BlockFilterMessageUnionReader::GetBlockFilterCheckPoints(ref item) => {
-                write!(
-                    f,
-                    "{}::{}({})",
-                    Self::NAME,
-                    GetBlockFilterCheckPoints::NAME,
-                    item
-                )
-            }
-            BlockFilterMessageUnionReader::BlockFilterCheckPoints(ref item) => {
-                write!(
-                    f,
-                    "{}::{}({})",
-                    Self::NAME,
-                    BlockFilterCheckPoints::NAME,
-                    item
-                )
-            }
-        }
+        use molecule::hex_string;
+        let raw_data = hex_string(&self.raw_data());
+        write!(f, "{}(0x{})", Self::NAME, raw_data)
     }
 }
-impl BlockFilterMessageUnion {
-    pub(crate) fn display_inner(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-        match self {
-            BlockFilterMessageUnion::GetBlockFilters(ref item) => write!(f, "{}", item),
-            BlockFilterMessageUnion::BlockFilters(ref item) => write!(f, "{}", item),
-            BlockFilterMessageUnion::GetBlockFilterHashes(ref item) => write!(f, "{}", item),
-            BlockFilterMessageUnion::BlockFilterHashes(ref item) => write!(f, "{}", item),
-            BlockFilterMessageUnion::GetBlockFilterCheckPoints(ref item) => write!(f, "{}", item),
-            BlockFilterMessageUnion::BlockFilterCheckPoints(ref item) => write!(f, "{}", item),
-        }
+impl ::core::default::Default for SaltedShortId {
+    fn default() -> Self {
+        let v = molecule::bytes::Bytes::from_static(&Self::DEFAULT_VALUE);
+        SaltedShortId::new_unchecked(v)
     }
 }
-impl<'r> BlockFilterMessageUnionReader<'r> {
-    pub(crate) fn display_inner(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-        match self {
-            BlockFilterMessageUnionReader::GetBlockFilters(ref item) => write!(f, "{}", item),
-            BlockFilterMessageUnionReader::BlockFilters(ref item) => write!(f, "{}", item),
-            BlockFilterMessageUnionReader::GetBlockFilterHashes(ref item) => write!(f, "{}", item),
-            BlockFilterMessageUnionReader::BlockFilterHashes(ref item) => write!(f, "{}", item),
-            BlockFilterMessageUnionReader::GetBlockFilterCheckPoints(ref item) => {
-                write!(f, "{}", item)
-            }
-            BlockFilterMessageUnionReader::BlockFilterCheckPoints(ref item) => {
-                write!(f, "{}", item)
-            }
-        }
-    }
-}
-impl ::core::convert::From<GetBlockFilters> for BlockFilterMessageUnion {
-    fn from(item: GetBlockFilters) -> Self {
-        BlockFilterMessageUnion::GetBlockFilters(item)
-    }
-}
-impl ::core::convert::From<BlockFilters> for BlockFilterMessageUnion {
-    fn from(item: BlockFilters) -> Self {
-        BlockFilterMessageUnion::BlockFilters(item)
-    }
-}
-impl ::core::convert::From<GetBlockFilterHashes> for BlockFilterMessageUnion {
-    fn from(item: GetBlockFilterHashes) -> Self {
-        BlockFilterMessageUnion::GetBlockFilterHashes(item)
-    }
-}
-impl ::core::convert::From<BlockFilterHashes> for BlockFilterMessageUnion {
-    fn from(item: BlockFilterHashes) -> Self {
-        BlockFilterMessageUnion::BlockFilterHashes(item)
-    }
-}
-impl ::core::convert::From<GetBlockFilterCheckPoints> for BlockFilterMessageUnion {
-    fn from(item: GetBlockFilterCheckPoints) -> Self {
-        BlockFilterMessageUnion::GetBlockFilterCheckPoints(item)
-    }
-}
-impl ::core::convert::From<BlockFilterCheckPoints> for BlockFilterMessageUnion {
-    fn from(item: BlockFilterCheckPoints) -> Self {
-        BlockFilterMessageUnion::BlockFilterCheckPoints(item)
-    }
-}
-impl<'r> ::core::convert::From<GetBlockFiltersReader<'r>> for BlockFilterMessageUnionReader<'r> {
-    fn from(item: GetBlockFiltersReader<'r>) -> Self {
-        BlockFilterMessageUnionReader::GetBlockFilters(item)
-    }
-}
-impl<'r> ::core::convert::From<BlockFiltersReader<'r>> for BlockFilterMessageUnionReader<'r> {
-    fn from(item: BlockFiltersReader<'r>) -> Self {
-        BlockFilterMessageUnionReader::BlockFilters(item)
-    }
-}
-impl<'r> ::core::convert::From<GetBlockFilterHashesReader<'r>>
-    for BlockFilterMessageUnionReader<'r>
-{
-    fn from(item: GetBlockFilterHashesReader<'r>) -> Self {
-        BlockFilterMessageUnionReader::GetBlockFilterHashes(item)
-    }
-}
-impl<'r> ::core::convert::From<BlockFilterHashesReader<'r>> for BlockFilterMessageUnionReader<'r> {
-    fn from(item: BlockFilterHashesReader<'r>) -> Self {
-        BlockFilterMessageUnionReader::BlockFilterHashes(item)
-    }
-}
-impl<'r> ::core::convert::From<GetBlockFilterCheckPointsReader<'r>>
-    for BlockFilterMessageUnionReader<'r>
-{
-    fn from(item: GetBlockFilterCheckPointsReader<'r>) -> Self {
-        BlockFilterMessageUnionReader::GetBlockFilterCheckPoints(item)
-    }
-}
-impl<'r> ::core::convert::From<BlockFilterCheckPointsReader<'r>>
-    for BlockFilterMessageUnionReader<'r>
-{
-    fn from(item: BlockFilterCheckPointsReader<'r>) -> Self {
-        BlockFilterMessageUnionReader::BlockFilterCheckPoints(item)
-    }
-}
-impl BlockFilterMessageUnion {
-    pub const NAME: &'static str = "BlockFilterMessageUnion";
-    pub fn as_bytes(&self) -> molecule::bytes::Bytes {
-        match self {
-            BlockFilterMessageUnion::GetBlockFilters(item) => item.as_bytes(),
-            BlockFilterMessageUnion::BlockFilters(item) => item.as_bytes(),
-            BlockFilterMessageUnion::GetBlockFilterHashes(item) => item.as_bytes(),
-            BlockFilterMessageUnion::BlockFilterHashes(item) => item.as_bytes(),
-            BlockFilterMessageUnion::GetBlockFilterCheckPoints(item) => item.as_bytes(),
-            BlockFilterMessageUnion::BlockFilterCheckPoints(item) => item.as_bytes(),
-        }
-    }
-    pub fn as_slice(&self) -> &[u8] {
-        match self {
-            BlockFilterMessageUnion::GetBlockFilters(item) => item.as_slice(),
-            BlockFilterMessageUnion::BlockFilters(item) => item.as_slice(),
-            BlockFilterMessageUnion::GetBlockFilterHashes(item) => item.as_slice(),
-            BlockFilterMessageUnion::BlockFilterHashes(item) => item.as_slice(),
-            BlockFilterMessageUnion::GetBlockFilterCheckPoints(item) => item.as_slice(),
-            BlockFilterMessageUnion::BlockFilterCheckPoints(item) => item.as_slice(),
-        }
-    }
-    pub fn item_id(&self) -> molecule::Number {
-        match self {
-            BlockFilterMessageUnion::GetBlockFilters(_) => 0,
-            BlockFilterMessageUnion::BlockFilters(_) => 1,
-            BlockFilterMessageUnion::GetBlockFilterHashes(_) => 2,
-            BlockFilterMessageUnion::BlockFilterHashes(_) => 3,
-            BlockFilterMessageUnion::GetBlockFilterCheckPoints(_) => 4,
-            BlockFilterMessageUnion::BlockFilterCheckPoints(_) => 5,
-        }
-    }
-    pub fn item_name(&self) -> &str {
-        match self {
-            BlockFilterMessageUnion::GetBlockFilters(_) => "GetBlockFilters",
-            BlockFilterMessageUnion::BlockFilters(_) => "BlockFilters",
-            BlockFilterMessageUnion::GetBlockFilterHashes(_) => "GetBlockFilterHashes",
-            BlockFilterMessageUnion::BlockFilterHashes(_) => "BlockFilterHashes",
-            BlockFilterMessageUnion::GetBlockFilterCheckPoints(_) => "GetBlockFilterCheckPoints",
-            BlockFilterMessageUnion::BlockFilterCheckPoints(_) => "BlockFilterCheckPoints",
-        }
-    }
-    pub fn as_reader<'r>(&'r self) -> BlockFilterMessageUnionReader<'r> {
-        match self {
-            BlockFilterMessageUnion::GetBlockFilters(item) => item.as_reader().into(),
-            BlockFilterMessageUnion::BlockFilters(item) => item.as_reader().into(),
-            BlockFilterMessageUnion::GetBlockFilterHashes(item) => item.as_reader().into(),
-            BlockFilterMessageUnion::BlockFilterHashes(item) => item.as_reader().into(),
-            BlockFilterMessageUnion::GetBlockFilterCheckPoints(item) => item.as_reader().into(),
-            BlockFilterMessageUnion::BlockFilterCheckPoints(item) => item.as_reader().into(),
-        }
-    }
-}
-impl<'r> BlockFilterMessageUnionReader<'r> {
-    pub const NAME: &'r str = "BlockFilterMessageUnionReader";
-    pub fn as_slice(&self) -> &'r [u8] {
-        match self {
-            BlockFilterMessageUnionReader::GetBlockFilters(item) => item.as_slice(),
-            BlockFilterMessageUnionReader::BlockFilters(item) => item.as_slice(),
-            BlockFilterMessageUnionReader::GetBlockFilterHashes(item) => item.as_slice(),
-            BlockFilterMessageUnionReader::BlockFilterHashes(item) => item.as_slice(),
-            BlockFilterMessageUnionReader::GetBlockFilterCheckPoints(item) => item.as_slice(),
-            BlockFilterMessageUnionReader::BlockFilterCheckPoints(item) => item.as_slice(),
-        }
-    }
-    pub fn item_id(&self) -> molecule::Number {
-        match self {
-            BlockFilterMessageUnionReader::GetBlockFilters(_) => 0,
-            BlockFilterMessageUnionReader::BlockFilters(_) => 1,
-            BlockFilterMessageUnionReader::GetBlockFilterHashes(_) => 2,
-            BlockFilterMessageUnionReader::BlockFilterHashes(_) => 3,
-            BlockFilterMessageUnionReader::GetBlockFilterCheckPoints(_) => 4,
-            BlockFilterMessageUnionReader::BlockFilterCheckPoints(_) => 5,
-        }
+impl SaltedShortId {
+    const DEFAULT_VALUE: [u8; 6] = [0, 0, 0, 0, 0, 0];
+    pub const TOTAL_SIZE: usize = 6;
+    pub const ITEM_SIZE: usize = 1;
+    pub const ITEM_COUNT: usize = 6;
+    pub fn nth0(&self) -> Byte {
+        Byte::new_unchecked(self.0.slice(0..1))
     }
-    pub fn item_name(&self) -> &str {
-        match self {
-            BlockFilterMessageUnionReader::GetBlockFilters(_) => "GetBlockFilters",
-            BlockFilterMessageUnionReader::BlockFilters(_) => "BlockFilters",
-            BlockFilterMessageUnionReader::GetBlockFilterHashes(_) => "GetBlockFilterHashes",
-            BlockFilterMessageUnionReader::BlockFilterHashes(_) => "BlockFilterHashes",
-            BlockFilterMessageUnionReader::GetBlockFilterCheckPoints(_) => {
-                "GetBlockFilterCheckPoints"
-            }
-            BlockFilterMessageUnionReader::BlockFilterCheckPoints(_) => "BlockFilterCheckPoints",
-        }
+    pub fn nth1(&self) -> Byte {
+        Byte::new_unchecked(self.0.slice(1..2))
     }
-}
-#[derive(Clone)]
-pub struct GetBlockFilters(molecule::bytes::Bytes);
-impl ::core::fmt::LowerHex for GetBlockFilters {
-    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-        use molecule::hex_string;
-        if f.alternate() {
-            write!(f, "0x")?;
-        }
-        write!(f, "{}", hex_string(self.as_slice()))
+    pub fn nth2(&self) -> Byte {
+        Byte::new_unchecked(self.0.slice(2..3))
     }
-}
-impl ::core::fmt::Debug for GetBlockFilters {
-    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-        write!(f, "{}({:#x})", Self::NAME, self)
+    pub fn nth3(&self) -> Byte {
+        Byte::new_unchecked(self.0.slice(3..4))
     }
-}
-impl ::core::fmt::Display for GetBlockFilters {
-    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-        write!(f, "{} {{ ", Self::NAME)?;
-        write!(f, "{}: {}", "start_number", self.start_number())?;
-        write!(f, " }}")
+    pub fn nth4(&self) -> Byte {
+        Byte::new_unchecked(self.0.slice(4..5))
     }
-}
-impl ::core::default::Default for GetBlockFilters {
-    fn default() -> Self {
-        let v = molecule::bytes::Bytes::from_static(&Self::DEFAULT_VALUE);
-        GetBlockFilters::new_unchecked(v)
+    pub fn nth5(&self) -> Byte {
+        Byte::new_unchecked(self.0.slice(5..6))
     }
-}
-impl GetBlockFilters {
-    const DEFAULT_VALUE: [u8; 8] = [0, 0, 0, 0, 0, 0, 0, 0];
-    pub const TOTAL_SIZE: usize = 8;
-    pub const FIELD_SIZES: [usize; 1] = [8];
-    pub const FIELD_COUNT: usize = 1;
-    pub fn start_number(&self) -> Uint64 {
-        Uint64::new_unchecked(self.0.slice(0..8))
+    pub fn raw_data(&self) -> molecule::bytes::Bytes {
+        self.as_bytes()
     }
-    pub fn as_reader<'r>(&'r self) -> GetBlockFiltersReader<'r> {
-        GetBlockFiltersReader::new_unchecked(self.as_slice())
+    pub fn as_reader<'r>(&'r self) -> SaltedShortIdReader<'r> {
+        SaltedShortIdReader::new_unchecked(self.as_slice())
     }
 }
-impl molecule::prelude::Entity for GetBlockFilters {
-    type Builder = GetBlockFiltersBuilder;
-    const NAME: &'static str = "GetBlockFilters";
+impl molecule::prelude::Entity for SaltedShortId {
+    type Builder = SaltedShortIdBuilder;
+    const NAME: &'static str = "SaltedShortId";
     fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
-        GetBlockFilters(data)
+        SaltedShortId(data)
     }
     fn as_bytes(&self) -> molecule::bytes::Bytes {
         self.0.clone()
@@ -10628,21 +10405,28 @@ impl molecule::prelude::Entity for GetBlockFilters {
         &self.0[..]
     }
     fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
-        GetBlockFiltersReader::from_slice(slice).map(|reader| reader.to_entity())
+        SaltedShortIdReader::from_slice(slice).map(|reader| reader.to_entity())
     }
     fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
-        GetBlockFiltersReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+        SaltedShortIdReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
     }
     fn new_builder() -> Self::Builder {
         ::core::default::Default::default()
     }
     fn as_builder(self) -> Self::Builder {
-        Self::new_builder().start_number(self.start_number())
+        Self::new_builder().set([
+            self.nth0(),
+            self.nth1(),
+            self.nth2(),
+            self.nth3(),
+            self.nth4(),
+            self.nth5(),
+        ])
     }
 }
 #[derive(Clone, Copy)]
-pub struct GetBlockFiltersReader<'r>(&'r [u8]);
-impl<'r> ::core::fmt::LowerHex for GetBlockFiltersReader<'r> {
+pub struct SaltedShortIdReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for SaltedShortIdReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         use molecule::hex_string;
         if f.alternate() {
@@ -10651,39 +10435,57 @@ impl<'r> ::core::fmt::LowerHex for GetBlockFiltersReader<'r> {
         write!(f, "{}", hex_string(self.as_slice()))
     }
 }
-impl<'r> ::core::fmt::Debug for GetBlockFiltersReader<'r> {
+impl<'r> ::core::fmt::Debug for SaltedShortIdReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{}({:#x})", Self::NAME, self)
     }
 }
-impl<'r> ::core::fmt::Display for GetBlockFiltersReader<'r> {
+impl<'r> ::core::fmt::Display for SaltedShortIdReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-        write!(f, "{} {{ ", Self::NAME)?;
-        write!(f, "{}: {}", "start_number", self.start_number())?;
-        write!(f, " }}")
+        use molecule::hex_string;
+        let raw_data = hex_string(&self.raw_data());
+        write!(f, "{}(0x{})", Self::NAME, raw_data)
     }
 }
-impl<'r> GetBlockFiltersReader<'r> {
-    pub const TOTAL_SIZE: usize = 8;
-    pub const FIELD_SIZES: [usize; 1] = [8];
-    pub const FIELD_COUNT: usize = 1;
-    pub fn start_number(&self) -> Uint64Reader<'r> {
-        Uint64Reader::new_unchecked(&self.as_slice()[0..8])
+impl<'r> SaltedShortIdReader<'r> {
+    pub const TOTAL_SIZE: usize = 6;
+    pub const ITEM_SIZE: usize = 1;
+    pub const ITEM_COUNT: usize = 6;
+    pub fn nth0(&self) -> ByteReader<'r> {
+        ByteReader::new_unchecked(&self.as_slice()[0..1])
     }
-}
-impl<'r> molecule::prelude::Reader<'r> for GetBlockFiltersReader<'r> {
-    type Entity = GetBlockFilters;
-    const NAME: &'static str = "GetBlockFiltersReader";
-    fn to_entity(&self) -> Self::Entity {
-        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    pub fn nth1(&self) -> ByteReader<'r> {
+        ByteReader::new_unchecked(&self.as_slice()[1..2])
     }
-    fn new_unchecked(slice: &'r [u8]) -> Self {
-        GetBlockFiltersReader(slice)
+    pub fn nth2(&self) -> ByteReader<'r> {
+        ByteReader::new_unchecked(&self.as_slice()[2..3])
     }
-    fn as_slice(&self) -> &'r [u8] {
-        self.0
+    pub fn nth3(&self) -> ByteReader<'r> {
+        ByteReader::new_unchecked(&self.as_slice()[3..4])
     }
-    fn verify(slice: &[u8], _compatible: bool) -> molecule::error::VerificationResult<()> {
+    pub fn nth4(&self) -> ByteReader<'r> {
+        ByteReader::new_unchecked(&self.as_slice()[4..5])
+    }
+    pub fn nth5(&self) -> ByteReader<'r> {
+        ByteReader::new_unchecked(&self.as_slice()[5..6])
+    }
+    pub fn raw_data(&self) -> &'r [u8] {
+        self.as_slice()
+    }
+}
+impl<'r> molecule::prelude::Reader<'r> for SaltedShortIdReader<'r> {
+    type Entity = SaltedShortId;
+    const NAME: &'static str = "SaltedShortIdReader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        SaltedShortIdReader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], _compatible: bool) -> molecule::error::VerificationResult<()> {
         use molecule::verification_error as ve;
         let slice_len = slice.len();
         if slice_len != Self::TOTAL_SIZE {
@@ -10692,39 +10494,82 @@ impl<'r> molecule::prelude::Reader<'r> for GetBlockFiltersReader<'r> {
         Ok(())
     }
 }
-#[derive(Debug, Default)]
-pub struct GetBlockFiltersBuilder {
-    pub(crate) start_number: Uint64,
+pub struct SaltedShortIdBuilder(pub(crate) [Byte; 6]);
+impl ::core::fmt::Debug for SaltedShortIdBuilder {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:?})", Self::NAME, &self.0[..])
+    }
 }
-impl GetBlockFiltersBuilder {
-    pub const TOTAL_SIZE: usize = 8;
-    pub const FIELD_SIZES: [usize; 1] = [8];
-    pub const FIELD_COUNT: usize = 1;
-    pub fn start_number(mut self, v: Uint64) -> Self {
-        self.start_number = v;
+impl ::core::default::Default for SaltedShortIdBuilder {
+    fn default() -> Self {
+        SaltedShortIdBuilder([
+            Byte::default(),
+            Byte::default(),
+            Byte::default(),
+            Byte::default(),
+            Byte::default(),
+            Byte::default(),
+        ])
+    }
+}
+impl SaltedShortIdBuilder {
+    pub const TOTAL_SIZE: usize = 6;
+    pub const ITEM_SIZE: usize = 1;
+    pub const ITEM_COUNT: usize = 6;
+    pub fn set(mut self, v: [Byte; 6]) -> Self {
+        self.0 = v;
+        self
+    }
+    pub fn nth0(mut self, v: Byte) -> Self {
+        self.0[0] = v;
+        self
+    }
+    pub fn nth1(mut self, v: Byte) -> Self {
+        self.0[1] = v;
+        self
+    }
+    pub fn nth2(mut self, v: Byte) -> Self {
+        self.0[2] = v;
+        self
+    }
+    pub fn nth3(mut self, v: Byte) -> Self {
+        self.0[3] = v;
+        self
+    }
+    pub fn nth4(mut self, v: Byte) -> Self {
+        self.0[4] = v;
+        self
+    }
+    pub fn nth5(mut self, v: Byte) -> Self {
+        self.0[5] = v;
         self
     }
 }
-impl molecule::prelude::Builder for GetBlockFiltersBuilder {
-    type Entity = GetBlockFilters;
-    const NAME: &'static str = "GetBlockFiltersBuilder";
+impl molecule::prelude::Builder for SaltedShortIdBuilder {
+    type Entity = SaltedShortId;
+    const NAME: &'static str = "SaltedShortIdBuilder";
     fn expected_length(&self) -> usize {
         Self::TOTAL_SIZE
     }
     fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
-        writer.write_all(self.start_number.as_slice())?;
+        writer.write_all(self.0[0].as_slice())?;
+        writer.write_all(self.0[1].as_slice())?;
+        writer.write_all(self.0[2].as_slice())?;
+        writer.write_all(self.0[3].as_slice())?;
+        writer.write_all(self.0[4].as_slice())?;
+        writer.write_all(self.0[5].as_slice())?;
         Ok(())
     }
     fn build(&self) -> Self::Entity {
         let mut inner = Vec::with_capacity(self.expected_length());
         self.write(&mut inner)
             .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
-        GetBlockFilters::new_unchecked(inner.into())
+        SaltedShortId::new_unchecked(inner.into())
     }
 }
 #[derive(Clone)]
-pub struct BlockFilters(molecule::bytes::Bytes);
-impl ::core::fmt::LowerHex for BlockFilters {
+pub struct SaltedShortIdVec(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for SaltedShortIdVec {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         use molecule::hex_string;
         if f.alternate() {
@@ -10733,83 +10578,66 @@ impl ::core::fmt::LowerHex for BlockFilters {
         write!(f, "{}", hex_string(self.as_slice()))
     }
 }
-impl ::core::fmt::Debug for BlockFilters {
+impl ::core::fmt::Debug for SaltedShortIdVec {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{}({:#x})", Self::NAME, self)
     }
 }
-impl ::core::fmt::Display for BlockFilters {
+impl ::core::fmt::Display for SaltedShortIdVec {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-        write!(f, "{} {{ ", Self::NAME)?;
-        write!(f, "{}: {}", "start_number", self.start_number())?;
-        write!(f, ", {}: {}", "block_hashes", self.block_hashes())?;
-        write!(f, ", {}: {}", "filters", self.filters())?;
-        let extra_count = self.count_extra_fields();
-        if extra_count != 0 {
-            write!(f, ", .. ({} fields)", extra_count)?;
+        write!(f, "{} [", Self::NAME)?;
+        for i in 0..self.len() {
+            if i == 0 {
+                write!(f, "{}", self.get_unchecked(i))?;
+            } else {
+                write!(f, ", {}", self.get_unchecked(i))?;
+            }
         }
-        write!(f, " }}")
+        write!(f, "]")
     }
 }
-impl ::core::default::Default for BlockFilters {
+impl ::core::default::Default for SaltedShortIdVec {
     fn default() -> Self {
         let v = molecule::bytes::Bytes::from_static(&Self::DEFAULT_VALUE);
-        BlockFilters::new_unchecked(v)
+        SaltedShortIdVec::new_unchecked(v)
     }
 }
-impl BlockFilters {
-    const DEFAULT_VALUE: [u8; 32] = [
-        32, 0, 0, 0, 16, 0, 0, 0, 24, 0, 0, 0, 28, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 4,
-        0, 0, 0,
-    ];
-    pub const FIELD_COUNT: usize = 3;
+impl SaltedShortIdVec {
+    const DEFAULT_VALUE: [u8; 4] = [0, 0, 0, 0];
+    pub const ITEM_SIZE: usize = 6;
     pub fn total_size(&self) -> usize {
-        molecule::unpack_number(self.as_slice()) as usize
-    }
-    pub fn field_count(&self) -> usize {
-        if self.total_size() == molecule::NUMBER_SIZE {
-            0
-        } else {
-            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
-        }
-    }
-    pub fn count_extra_fields(&self) -> usize {
-        self.field_count() - Self::FIELD_COUNT
+        molecule::NUMBER_SIZE + Self::ITEM_SIZE * self.item_count()
     }
-    pub fn has_extra_fields(&self) -> bool {
-        Self::FIELD_COUNT != self.field_count()
+    pub fn item_count(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
     }
-    pub fn start_number(&self) -> Uint64 {
-        let slice = self.as_slice();
-        let start = molecule::unpack_number(&slice[4..]) as usize;
-        let end = molecule::unpack_number(&slice[8..]) as usize;
-        Uint64::new_unchecked(self.0.slice(start..end))
+    pub fn len(&self) -> usize {
+        self.item_count()
     }
-    pub fn block_hashes(&self) -> Byte32Vec {
-        let slice = self.as_slice();
-        let start = molecule::unpack_number(&slice[8..]) as usize;
-        let end = molecule::unpack_number(&slice[12..]) as usize;
-        Byte32Vec::new_unchecked(self.0.slice(start..end))
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
     }
-    pub fn filters(&self) -> BytesVec {
-        let slice = self.as_slice();
-        let start = molecule::unpack_number(&slice[12..]) as usize;
-        if self.has_extra_fields() {
-            let end = molecule::unpack_number(&slice[16..]) as usize;
-            BytesVec::new_unchecked(self.0.slice(start..end))
+    pub fn get(&self, idx: usize) -> Option<SaltedShortId> {
+        if idx >= self.len() {
+            None
         } else {
-            BytesVec::new_unchecked(self.0.slice(start..))
+            Some(self.get_unchecked(idx))
         }
     }
-    pub fn as_reader<'r>(&'r self) -> BlockFiltersReader<'r> {
-        BlockFiltersReader::new_unchecked(self.as_slice())
+    pub fn get_unchecked(&self, idx: usize) -> SaltedShortId {
+        let start = molecule::NUMBER_SIZE + Self::ITEM_SIZE * idx;
+        let end = start + Self::ITEM_SIZE;
+        SaltedShortId::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn as_reader<'r>(&'r self) -> SaltedShortIdVecReader<'r> {
+        SaltedShortIdVecReader::new_unchecked(self.as_slice())
     }
 }
-impl molecule::prelude::Entity for BlockFilters {
-    type Builder = BlockFiltersBuilder;
-    const NAME: &'static str = "BlockFilters";
+impl molecule::prelude::Entity for SaltedShortIdVec {
+    type Builder = SaltedShortIdVecBuilder;
+    const NAME: &'static str = "SaltedShortIdVec";
     fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
-        BlockFilters(data)
+        SaltedShortIdVec(data)
     }
     fn as_bytes(&self) -> molecule::bytes::Bytes {
         self.0.clone()
@@ -10818,24 +10646,21 @@ impl molecule::prelude::Entity for BlockFilters {
         &self.0[..]
     }
     fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
-        BlockFiltersReader::from_slice(slice).map(|reader| reader.to_entity())
+        SaltedShortIdVecReader::from_slice(slice).map(|reader| reader.to_entity())
     }
     fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
-        BlockFiltersReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+        SaltedShortIdVecReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
     }
     fn new_builder() -> Self::Builder {
         ::core::default::Default::default()
     }
     fn as_builder(self) -> Self::Builder {
-        Self::new_builder()
-            .start_number(self.start_number())
-            .block_hashes(self.block_hashes())
-            .filters(self.filters())
+        Self::new_builder().extend(self.into_iter())
     }
 }
 #[derive(Clone, Copy)]
-pub struct BlockFiltersReader<'r>(&'r [u8]);
-impl<'r> ::core::fmt::LowerHex for BlockFiltersReader<'r> {
+pub struct SaltedShortIdVecReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for SaltedShortIdVecReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         use molecule::hex_string;
         if f.alternate() {
@@ -10844,175 +10669,2775 @@ impl<'r> ::core::fmt::LowerHex for BlockFiltersReader<'r> {
         write!(f, "{}", hex_string(self.as_slice()))
     }
 }
-impl<'r> ::core::fmt::Debug for BlockFiltersReader<'r> {
+impl<'r> ::core::fmt::Debug for SaltedShortIdVecReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{}({:#x})", Self::NAME, self)
     }
 }
-impl<'r> ::core::fmt::Display for BlockFiltersReader<'r> {
+impl<'r> ::core::fmt::Display for SaltedShortIdVecReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-        write!(f, "{} {{ ", Self::NAME)?;
-        write!(f, "{}: {}", "start_number", self.start_number())?;
-        write!(f, ", {}: {}", "block_hashes", self.block_hashes())?;
-        write!(f, ", {}: {}", "filters", self.filters())?;
-        let extra_count = self.count_extra_fields();
-        if extra_count != 0 {
-            write!(f, ", .. ({} fields)", extra_count)?;
+        write!(f, "{} [", Self::NAME)?;
+        for i in 0..self.len() {
+            if i == 0 {
+                write!(f, "{}", self.get_unchecked(i))?;
+            } else {
+                write!(f, ", {}", self.get_unchecked(i))?;
+            }
         }
-        write!(f, " }}")
+        write!(f, "]")
     }
 }
-impl<'r> BlockFiltersReader<'r> {
-    pub const FIELD_COUNT: usize = 3;
+impl<'r> SaltedShortIdVecReader<'r> {
+    pub const ITEM_SIZE: usize = 6;
     pub fn total_size(&self) -> usize {
-        molecule::unpack_number(self.as_slice()) as usize
-    }
-    pub fn field_count(&self) -> usize {
-        if self.total_size() == molecule::NUMBER_SIZE {
-            0
-        } else {
-            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
-        }
-    }
-    pub fn count_extra_fields(&self) -> usize {
-        self.field_count() - Self::FIELD_COUNT
+        molecule::NUMBER_SIZE + Self::ITEM_SIZE * self.item_count()
     }
-    pub fn has_extra_fields(&self) -> bool {
-        Self::FIELD_COUNT != self.field_count()
+    pub fn item_count(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
     }
-    pub fn start_number(&self) -> Uint64Reader<'r> {
-        let slice = self.as_slice();
-        let start = molecule::unpack_number(&slice[4..]) as usize;
-        let end = molecule::unpack_number(&slice[8..]) as usize;
-        Uint64Reader::new_unchecked(&self.as_slice()[start..end])
+    pub fn len(&self) -> usize {
+        self.item_count()
     }
-    pub fn block_hashes(&self) -> Byte32VecReader<'r> {
-        let slice = self.as_slice();
-        let start = molecule::unpack_number(&slice[8..]) as usize;
-        let end = molecule::unpack_number(&slice[12..]) as usize;
-        Byte32VecReader::new_unchecked(&self.as_slice()[start..end])
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
     }
-    pub fn filters(&self) -> BytesVecReader<'r> {
-        let slice = self.as_slice();
-        let start = molecule::unpack_number(&slice[12..]) as usize;
-        if self.has_extra_fields() {
-            let end = molecule::unpack_number(&slice[16..]) as usize;
-            BytesVecReader::new_unchecked(&self.as_slice()[start..end])
+    pub fn get(&self, idx: usize) -> Option<SaltedShortIdReader<'r>> {
+        if idx >= self.len() {
+            None
         } else {
-            BytesVecReader::new_unchecked(&self.as_slice()[start..])
+            Some(self.get_unchecked(idx))
         }
     }
+    pub fn get_unchecked(&self, idx: usize) -> SaltedShortIdReader<'r> {
+        let start = molecule::NUMBER_SIZE + Self::ITEM_SIZE * idx;
+        let end = start + Self::ITEM_SIZE;
+        SaltedShortIdReader::new_unchecked(&self.as_slice()[start..end])
+    }
 }
-impl<'r> molecule::prelude::Reader<'r> for BlockFiltersReader<'r> {
-    type Entity = BlockFilters;
-    const NAME: &'static str = "BlockFiltersReader";
+impl<'r> molecule::prelude::Reader<'r> for SaltedShortIdVecReader<'r> {
+    type Entity = SaltedShortIdVec;
+    const NAME: &'static str = "SaltedShortIdVecReader";
     fn to_entity(&self) -> Self::Entity {
         Self::Entity::new_unchecked(self.as_slice().to_owned().into())
     }
     fn new_unchecked(slice: &'r [u8]) -> Self {
-        BlockFiltersReader(slice)
+        SaltedShortIdVecReader(slice)
     }
     fn as_slice(&self) -> &'r [u8] {
         self.0
     }
-    fn verify(slice: &[u8], compatible: bool) -> molecule::error::VerificationResult<()> {
+    fn verify(slice: &[u8], _compatible: bool) -> molecule::error::VerificationResult<()> {
         use molecule::verification_error as ve;
         let slice_len = slice.len();
         if slice_len < molecule::NUMBER_SIZE {
             return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE, slice_len);
         }
-        let total_size = molecule::unpack_number(slice) as usize;
+        let item_count = molecule::unpack_number(slice) as usize;
+        if item_count == 0 {
+            if slice_len != molecule::NUMBER_SIZE {
+                return ve!(Self, TotalSizeNotMatch, molecule::NUMBER_SIZE, slice_len);
+            }
+            return Ok(());
+        }
+        let total_size = molecule::NUMBER_SIZE + Self::ITEM_SIZE * item_count;
         if slice_len != total_size {
             return ve!(Self, TotalSizeNotMatch, total_size, slice_len);
         }
-        if slice_len < molecule::NUMBER_SIZE * 2 {
-            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE * 2, slice_len);
-        }
-        let offset_first = molecule::unpack_number(&slice[molecule::NUMBER_SIZE..]) as usize;
-        if offset_first % molecule::NUMBER_SIZE != 0 || offset_first < molecule::NUMBER_SIZE * 2 {
-            return ve!(Self, OffsetsNotMatch);
-        }
-        if slice_len < offset_first {
-            return ve!(Self, HeaderIsBroken, offset_first, slice_len);
-        }
-        let field_count = offset_first / molecule::NUMBER_SIZE - 1;
-        if field_count < Self::FIELD_COUNT {
-            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
-        } else if !compatible && field_count > Self::FIELD_COUNT {
-            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
-        };
-        let mut offsets: Vec<usize> = slice[molecule::NUMBER_SIZE..offset_first]
-            .chunks_exact(molecule::NUMBER_SIZE)
-            .map(|x| molecule::unpack_number(x) as usize)
-            .collect();
-        offsets.push(total_size);
-        if offsets.windows(2).any(|i| i[0] > i[1]) {
-            return ve!(Self, OffsetsNotMatch);
-        }
-        Uint64Reader::verify(&slice[offsets[0]..offsets[1]], compatible)?;
-        Byte32VecReader::verify(&slice[offsets[1]..offsets[2]], compatible)?;
-        BytesVecReader::verify(&slice[offsets[2]..offsets[3]], compatible)?;
         Ok(())
     }
 }
 #[derive(Debug, Default)]
-pub struct BlockFiltersBuilder {
-    pub(crate) start_number: Uint64,
-    pub(crate) block_hashes: Byte32Vec,
-    pub(crate) filters: BytesVec,
-}
-impl BlockFiltersBuilder {
-    pub const FIELD_COUNT: usize = 3;
-    pub fn start_number(mut self, v: Uint64) -> Self {
-        self.start_number = v;
+pub struct SaltedShortIdVecBuilder(pub(crate) Vec<SaltedShortId>);
+impl SaltedShortIdVecBuilder {
+    pub const ITEM_SIZE: usize = 6;
+    pub fn set(mut self, v: Vec<SaltedShortId>) -> Self {
+        self.0 = v;
         self
     }
-    pub fn block_hashes(mut self, v: Byte32Vec) -> Self {
-        self.block_hashes = v;
+    pub fn push(mut self, v: SaltedShortId) -> Self {
+        self.0.push(v);
         self
     }
-    pub fn filters(mut self, v: BytesVec) -> Self {
-        self.filters = v;
+    pub fn extend<T: ::core::iter::IntoIterator<Item = SaltedShortId>>(mut self, iter: T) -> Self {
+        for elem in iter {
+            self.0.push(elem);
+        }
         self
     }
+    pub fn replace(&mut self, index: usize, v: SaltedShortId) -> Option<SaltedShortId> {
+        self.0
+            .get_mut(index)
+            .map(|item| ::core::mem::replace(item, v))
+    }
 }
-impl molecule::prelude::Builder for BlockFiltersBuilder {
-    type Entity = BlockFilters;
-    const NAME: &'static str = "BlockFiltersBuilder";
+impl molecule::prelude::Builder for SaltedShortIdVecBuilder {
+    type Entity = SaltedShortIdVec;
+    const NAME: &'static str = "SaltedShortIdVecBuilder";
     fn expected_length(&self) -> usize {
-        molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1)
-            + self.start_number.as_slice().len()
-            + self.block_hashes.as_slice().len()
-            + self.filters.as_slice().len()
+        molecule::NUMBER_SIZE + Self::ITEM_SIZE * self.0.len()
     }
     fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
-        let mut total_size = molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1);
-        let mut offsets = Vec::with_capacity(Self::FIELD_COUNT);
-        offsets.push(total_size);
-        total_size += self.start_number.as_slice().len();
-        offsets.push(total_size);
-        total_size += self.block_hashes.as_slice().len();
-        offsets.push(total_size);
-        total_size += self.filters.as_slice().len();
-        writer.write_all(&molecule::pack_number(total_size as molecule::Number))?;
-        for offset in offsets.into_iter() {
-            writer.write_all(&molecule::pack_number(offset as molecule::Number))?;
+        writer.write_all(&molecule::pack_number(self.0.len() as molecule::Number))?;
+        for inner in &self.0[..] {
+            writer.write_all(inner.as_slice())?;
+        }
+        Ok(())
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        SaltedShortIdVec::new_unchecked(inner.into())
+    }
+}
+pub struct SaltedShortIdVecIterator(SaltedShortIdVec, usize, usize);
+impl ::core::iter::Iterator for SaltedShortIdVecIterator {
+    type Item = SaltedShortId;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.1 >= self.2 {
+            None
+        } else {
+            let ret = self.0.get_unchecked(self.1);
+            self.1 += 1;
+            Some(ret)
+        }
+    }
+}
+impl ::core::iter::ExactSizeIterator for SaltedShortIdVecIterator {
+    fn len(&self) -> usize {
+        self.2 - self.1
+    }
+}
+impl ::core::iter::IntoIterator for SaltedShortIdVec {
+    type Item = SaltedShortId;
+    type IntoIter = SaltedShortIdVecIterator;
+    fn into_iter(self) -> Self::IntoIter {
+        let len = self.len();
+        SaltedShortIdVecIterator(self, 0, len)
+    }
+}
+impl<'r> SaltedShortIdVecReader<'r> {
+    pub fn iter<'t>(&'t self) -> SaltedShortIdVecReaderIterator<'t, 'r> {
+        SaltedShortIdVecReaderIterator(&self, 0, self.len())
+    }
+}
+pub struct SaltedShortIdVecReaderIterator<'t, 'r>(&'t SaltedShortIdVecReader<'r>, usize, usize);
+impl<'t: 'r, 'r> ::core::iter::Iterator for SaltedShortIdVecReaderIterator<'t, 'r> {
+    type Item = SaltedShortIdReader<'t>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.1 >= self.2 {
+            None
+        } else {
+            let ret = self.0.get_unchecked(self.1);
+            self.1 += 1;
+            Some(ret)
+        }
+    }
+}
+impl<'t: 'r, 'r> ::core::iter::ExactSizeIterator for SaltedShortIdVecReaderIterator<'t, 'r> {
+    fn len(&self) -> usize {
+        self.2 - self.1
+    }
+}
+#[derive(Clone)]
+pub struct SaltedCompactBlock(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for SaltedCompactBlock {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl ::core::fmt::Debug for SaltedCompactBlock {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl ::core::fmt::Display for SaltedCompactBlock {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "header", self.header())?;
+        write!(f, ", {}: {}", "short_ids", self.short_ids())?;
+        write!(
+            f,
+            ", {}: {}",
+            "prefilled_transactions",
+            self.prefilled_transactions()
+        )?;
+        write!(f, ", {}: {}", "uncles", self.uncles())?;
+        write!(f, ", {}: {}", "proposals", self.proposals())?;
+        write!(f, ", {}: {}", "extension", self.extension())?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ", .. ({} fields)", extra_count)?;
+        }
+        write!(f, " }}")
+    }
+}
+impl ::core::default::Default for SaltedCompactBlock {
+    fn default() -> Self {
+        let v = molecule::bytes::Bytes::from_static(&Self::DEFAULT_VALUE);
+        SaltedCompactBlock::new_unchecked(v)
+    }
+}
+impl SaltedCompactBlock {
+    const DEFAULT_VALUE: [u8; 252] = [
+        252, 0, 0, 0, 28, 0, 0, 0, 236, 0, 0, 0, 240, 0, 0, 0, 244, 0, 0, 0, 248, 0, 0, 0, 252, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    ];
+    pub const FIELD_COUNT: usize = 6;
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
+    }
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+    pub fn header(&self) -> Header {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[4..]) as usize;
+        let end = molecule::unpack_number(&slice[8..]) as usize;
+        Header::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn short_ids(&self) -> SaltedShortIdVec {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[8..]) as usize;
+        let end = molecule::unpack_number(&slice[12..]) as usize;
+        SaltedShortIdVec::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn prefilled_transactions(&self) -> IndexTransactionVec {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[12..]) as usize;
+        let end = molecule::unpack_number(&slice[16..]) as usize;
+        IndexTransactionVec::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn uncles(&self) -> Byte32Vec {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[16..]) as usize;
+        let end = molecule::unpack_number(&slice[20..]) as usize;
+        Byte32Vec::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn proposals(&self) -> ProposalShortIdVec {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[20..]) as usize;
+        let end = molecule::unpack_number(&slice[24..]) as usize;
+        ProposalShortIdVec::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn extension(&self) -> BytesOpt {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[24..]) as usize;
+        if self.has_extra_fields() {
+            let end = molecule::unpack_number(&slice[28..]) as usize;
+            BytesOpt::new_unchecked(self.0.slice(start..end))
+        } else {
+            BytesOpt::new_unchecked(self.0.slice(start..))
+        }
+    }
+    pub fn as_reader<'r>(&'r self) -> SaltedCompactBlockReader<'r> {
+        SaltedCompactBlockReader::new_unchecked(self.as_slice())
+    }
+}
+impl molecule::prelude::Entity for SaltedCompactBlock {
+    type Builder = SaltedCompactBlockBuilder;
+    const NAME: &'static str = "SaltedCompactBlock";
+    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
+        SaltedCompactBlock(data)
+    }
+    fn as_bytes(&self) -> molecule::bytes::Bytes {
+        self.0.clone()
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        SaltedCompactBlockReader::from_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        SaltedCompactBlockReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn new_builder() -> Self::Builder {
+        ::core::default::Default::default()
+    }
+    fn as_builder(self) -> Self::Builder {
+        Self::new_builder()
+            .header(self.header())
+            .short_ids(self.short_ids())
+            .prefilled_transactions(self.prefilled_transactions())
+            .uncles(self.uncles())
+            .proposals(self.proposals())
+            .extension(self.extension())
+    }
+}
+#[derive(Clone, Copy)]
+pub struct SaltedCompactBlockReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for SaltedCompactBlockReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl<'r> ::core::fmt::Debug for SaltedCompactBlockReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl<'r> ::core::fmt::Display for SaltedCompactBlockReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "header", self.header())?;
+        write!(f, ", {}: {}", "short_ids", self.short_ids())?;
+        write!(
+            f,
+            ", {}: {}",
+            "prefilled_transactions",
+            self.prefilled_transactions()
+        )?;
+        write!(f, ", {}: {}", "uncles", self.uncles())?;
+        write!(f, ", {}: {}", "proposals", self.proposals())?;
+        write!(f, ", {}: {}", "extension", self.extension())?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ", .. ({} fields)", extra_count)?;
+        }
+        write!(f, " }}")
+    }
+}
+impl<'r> SaltedCompactBlockReader<'r> {
+    pub const FIELD_COUNT: usize = 6;
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
+    }
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+    pub fn header(&self) -> HeaderReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[4..]) as usize;
+        let end = molecule::unpack_number(&slice[8..]) as usize;
+        HeaderReader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn short_ids(&self) -> SaltedShortIdVecReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[8..]) as usize;
+        let end = molecule::unpack_number(&slice[12..]) as usize;
+        SaltedShortIdVecReader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn prefilled_transactions(&self) -> IndexTransactionVecReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[12..]) as usize;
+        let end = molecule::unpack_number(&slice[16..]) as usize;
+        IndexTransactionVecReader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn uncles(&self) -> Byte32VecReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[16..]) as usize;
+        let end = molecule::unpack_number(&slice[20..]) as usize;
+        Byte32VecReader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn proposals(&self) -> ProposalShortIdVecReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[20..]) as usize;
+        let end = molecule::unpack_number(&slice[24..]) as usize;
+        ProposalShortIdVecReader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn extension(&self) -> BytesOptReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[24..]) as usize;
+        if self.has_extra_fields() {
+            let end = molecule::unpack_number(&slice[28..]) as usize;
+            BytesOptReader::new_unchecked(&self.as_slice()[start..end])
+        } else {
+            BytesOptReader::new_unchecked(&self.as_slice()[start..])
+        }
+    }
+}
+impl<'r> molecule::prelude::Reader<'r> for SaltedCompactBlockReader<'r> {
+    type Entity = SaltedCompactBlock;
+    const NAME: &'static str = "SaltedCompactBlockReader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        SaltedCompactBlockReader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], compatible: bool) -> molecule::error::VerificationResult<()> {
+        use molecule::verification_error as ve;
+        let slice_len = slice.len();
+        if slice_len < molecule::NUMBER_SIZE {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE, slice_len);
+        }
+        let total_size = molecule::unpack_number(slice) as usize;
+        if slice_len != total_size {
+            return ve!(Self, TotalSizeNotMatch, total_size, slice_len);
+        }
+        if slice_len < molecule::NUMBER_SIZE * 2 {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE * 2, slice_len);
+        }
+        let offset_first = molecule::unpack_number(&slice[molecule::NUMBER_SIZE..]) as usize;
+        if offset_first % molecule::NUMBER_SIZE != 0 || offset_first < molecule::NUMBER_SIZE * 2 {
+            return ve!(Self, OffsetsNotMatch);
+        }
+        if slice_len < offset_first {
+            return ve!(Self, HeaderIsBroken, offset_first, slice_len);
+        }
+        let field_count = offset_first / molecule::NUMBER_SIZE - 1;
+        if field_count < Self::FIELD_COUNT {
+            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
+        } else if !compatible && field_count > Self::FIELD_COUNT {
+            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
+        };
+        let mut offsets: Vec<usize> = slice[molecule::NUMBER_SIZE..offset_first]
+            .chunks_exact(molecule::NUMBER_SIZE)
+            .map(|x| molecule::unpack_number(x) as usize)
+            .collect();
+        offsets.push(total_size);
+        if offsets.windows(2).any(|i| i[0] > i[1]) {
+            return ve!(Self, OffsetsNotMatch);
+        }
+        HeaderReader::verify(&slice[offsets[0]..offsets[1]], compatible)?;
+        SaltedShortIdVecReader::verify(&slice[offsets[1]..offsets[2]], compatible)?;
+        IndexTransactionVecReader::verify(&slice[offsets[2]..offsets[3]], compatible)?;
+        Byte32VecReader::verify(&slice[offsets[3]..offsets[4]], compatible)?;
+        ProposalShortIdVecReader::verify(&slice[offsets[4]..offsets[5]], compatible)?;
+        BytesOptReader::verify(&slice[offsets[5]..offsets[6]], compatible)?;
+        Ok(())
+    }
+}
+#[derive(Debug, Default)]
+pub struct SaltedCompactBlockBuilder {
+    pub(crate) header: Header,
+    pub(crate) short_ids: SaltedShortIdVec,
+    pub(crate) prefilled_transactions: IndexTransactionVec,
+    pub(crate) uncles: Byte32Vec,
+    pub(crate) proposals: ProposalShortIdVec,
+    pub(crate) extension: BytesOpt,
+}
+impl SaltedCompactBlockBuilder {
+    pub const FIELD_COUNT: usize = 6;
+    pub fn header(mut self, v: Header) -> Self {
+        self.header = v;
+        self
+    }
+    pub fn short_ids(mut self, v: SaltedShortIdVec) -> Self {
+        self.short_ids = v;
+        self
+    }
+    pub fn prefilled_transactions(mut self, v: IndexTransactionVec) -> Self {
+        self.prefilled_transactions = v;
+        self
+    }
+    pub fn uncles(mut self, v: Byte32Vec) -> Self {
+        self.uncles = v;
+        self
+    }
+    pub fn proposals(mut self, v: ProposalShortIdVec) -> Self {
+        self.proposals = v;
+        self
+    }
+    pub fn extension(mut self, v: BytesOpt) -> Self {
+        self.extension = v;
+        self
+    }
+}
+impl molecule::prelude::Builder for SaltedCompactBlockBuilder {
+    type Entity = SaltedCompactBlock;
+    const NAME: &'static str = "SaltedCompactBlockBuilder";
+    fn expected_length(&self) -> usize {
+        molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1)
+            + self.header.as_slice().len()
+            + self.short_ids.as_slice().len()
+            + self.prefilled_transactions.as_slice().len()
+            + self.uncles.as_slice().len()
+            + self.proposals.as_slice().len()
+            + self.extension.as_slice().len()
+    }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        let mut total_size = molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1);
+        let mut offsets = Vec::with_capacity(Self::FIELD_COUNT);
+        offsets.push(total_size);
+        total_size += self.header.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.short_ids.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.prefilled_transactions.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.uncles.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.proposals.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.extension.as_slice().len();
+        writer.write_all(&molecule::pack_number(total_size as molecule::Number))?;
+        for offset in offsets.into_iter() {
+            writer.write_all(&molecule::pack_number(offset as molecule::Number))?;
+        }
+        writer.write_all(self.header.as_slice())?;
+        writer.write_all(self.short_ids.as_slice())?;
+        writer.write_all(self.prefilled_transactions.as_slice())?;
+        writer.write_all(self.uncles.as_slice())?;
+        writer.write_all(self.proposals.as_slice())?;
+        writer.write_all(self.extension.as_slice())?;
+        Ok(())
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        SaltedCompactBlock::new_unchecked(inner.into())
+    }
+}
+#[derive(Clone)]
+pub struct BlockFilterMessage(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for BlockFilterMessage {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl ::core::fmt::Debug for BlockFilterMessage {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl ::core::fmt::Display for BlockFilterMessage {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}(", Self::NAME)?;
+        self.to_enum().display_inner(f)?;
+        write!(f, ")")
+    }
+}
+impl ::core::default::Default for BlockFilterMessage {
+    fn default() -> Self {
+        let v = molecule::bytes::Bytes::from_static(&Self::DEFAULT_VALUE);
+        BlockFilterMessage::new_unchecked(v)
+    }
+}
+impl BlockFilterMessage {
+    const DEFAULT_VALUE: [u8; 12] = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+    pub const ITEMS_COUNT: usize = 6;
+    pub fn item_id(&self) -> molecule::Number {
+        molecule::unpack_number(self.as_slice())
+    }
+    pub fn to_enum(&self) -> BlockFilterMessageUnion {
+        let inner = self.0.slice(molecule::NUMBER_SIZE..);
+        match self.item_id() {
+            0 => GetBlockFilters::new_unchecked(inner).into(),
+            1 => BlockFilters::new_unchecked(inner).into(),
+            2 => GetBlockFilterHashes::new_unchecked(inner).into(),
+            3 => BlockFilterHashes::new_unchecked(inner).into(),
+            4 => GetBlockFilterCheckPoints::new_unchecked(inner).into(),
+            5 => BlockFilterCheckPoints::new_unchecked(inner).into(),
+            _ => panic!("{}: invalid data", Self::NAME),
+        }
+    }
+    pub fn as_reader<'r>(&'r self) -> BlockFilterMessageReader<'r> {
+        BlockFilterMessageReader::new_unchecked(self.as_slice())
+    }
+}
+impl molecule::prelude::Entity for BlockFilterMessage {
+    type Builder = BlockFilterMessageBuilder;
+    const NAME: &'static str = "BlockFilterMessage";
+    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
+        BlockFilterMessage(data)
+    }
+    fn as_bytes(&self) -> molecule::bytes::Bytes {
+        self.0.clone()
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        BlockFilterMessageReader::from_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        BlockFilterMessageReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn new_builder() -> Self::Builder {
+        ::core::default::Default::default()
+    }
+    fn as_builder(self) -> Self::Builder {
+        Self::new_builder().set(self.to_enum())
+    }
+}
+#[derive(Clone, Copy)]
+pub struct BlockFilterMessageReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for BlockFilterMessageReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl<'r> ::core::fmt::Debug for BlockFilterMessageReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl<'r> ::core::fmt::Display for BlockFilterMessageReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}(", Self::NAME)?;
+        self.to_enum().display_inner(f)?;
+        write!(f, ")")
+    }
+}
+impl<'r> BlockFilterMessageReader<'r> {
+    pub const ITEMS_COUNT: usize = 6;
+    pub fn item_id(&self) -> molecule::Number {
+        molecule::unpack_number(self.as_slice())
+    }
+    pub fn to_enum(&self) -> BlockFilterMessageUnionReader<'r> {
+        let inner = &self.as_slice()[molecule::NUMBER_SIZE..];
+        match self.item_id() {
+            0 => GetBlockFiltersReader::new_unchecked(inner).into(),
+            1 => BlockFiltersReader::new_unchecked(inner).into(),
+            2 => GetBlockFilterHashesReader::new_unchecked(inner).into(),
+            3 => BlockFilterHashesReader::new_unchecked(inner).into(),
+            4 => GetBlockFilterCheckPointsReader::new_unchecked(inner).into(),
+            5 => BlockFilterCheckPointsReader::new_unchecked(inner).into(),
+            _ => panic!("{}: invalid data", Self::NAME),
+        }
+    }
+}
+impl<'r> molecule::prelude::Reader<'r> for BlockFilterMessageReader<'r> {
+    type Entity = BlockFilterMessage;
+    const NAME: &'static str = "BlockFilterMessageReader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        BlockFilterMessageReader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], compatible: bool) -> molecule::error::VerificationResult<()> {
+        use molecule::verification_error as ve;
+        let slice_len = slice.len();
+        if slice_len < molecule::NUMBER_SIZE {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE, slice_len);
+        }
+        let item_id = molecule::unpack_number(slice);
+        let inner_slice = &slice[molecule::NUMBER_SIZE..];
+        match item_id {
+            0 => GetBlockFiltersReader::verify(inner_slice, compatible),
+            1 => BlockFiltersReader::verify(inner_slice, compatible),
+            2 => GetBlockFilterHashesReader::verify(inner_slice, compatible),
+            3 => BlockFilterHashesReader::verify(inner_slice, compatible),
+            4 => GetBlockFilterCheckPointsReader::verify(inner_slice, compatible),
+            5 => BlockFilterCheckPointsReader::verify(inner_slice, compatible),
+            _ => ve!(Self, UnknownItem, Self::ITEMS_COUNT, item_id),
+        }?;
+        Ok(())
+    }
+}
+#[derive(Debug, Default)]
+pub struct BlockFilterMessageBuilder(pub(crate) BlockFilterMessageUnion);
+impl BlockFilterMessageBuilder {
+    pub const ITEMS_COUNT: usize = 6;
+    pub fn set<I>(mut self, v: I) -> Self
+    where
+        I: ::core::convert::Into<BlockFilterMessageUnion>,
+    {
+        self.0 = v.into();
+        self
+    }
+}
+impl molecule::prelude::Builder for BlockFilterMessageBuilder {
+    type Entity = BlockFilterMessage;
+    const NAME: &'static str = "BlockFilterMessageBuilder";
+    fn expected_length(&self) -> usize {
+        molecule::NUMBER_SIZE + self.0.as_slice().len()
+    }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        writer.write_all(&molecule::pack_number(self.0.item_id()))?;
+        writer.write_all(self.0.as_slice())
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        BlockFilterMessage::new_unchecked(inner.into())
+    }
+}
+#[derive(Debug, Clone)]
+pub enum BlockFilterMessageUnion {
+    GetBlockFilters(GetBlockFilters),
+    BlockFilters(BlockFilters),
+    GetBlockFilterHashes(GetBlockFilterHashes),
+    BlockFilterHashes(BlockFilterHashes),
+    GetBlockFilterCheckPoints(GetBlockFilterCheckPoints),
+    BlockFilterCheckPoints(BlockFilterCheckPoints),
+}
+#[derive(Debug, Clone, Copy)]
+pub enum BlockFilterMessageUnionReader<'r> {
+    GetBlockFilters(GetBlockFiltersReader<'r>),
+    BlockFilters(BlockFiltersReader<'r>),
+    GetBlockFilterHashes(GetBlockFilterHashesReader<'r>),
+    BlockFilterHashes(BlockFilterHashesReader<'r>),
+    GetBlockFilterCheckPoints(GetBlockFilterCheckPointsReader<'r>),
+    BlockFilterCheckPoints(BlockFilterCheckPointsReader<'r>),
+}
+impl ::core::default::Default for BlockFilterMessageUnion {
+    fn default() -> Self {
+        BlockFilterMessageUnion::GetBlockFilters(::core::default::Default::default())
+    }
+}
+impl ::core::fmt::Display for BlockFilterMessageUnion {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        match self {
+            BlockFilterMessageUnion::GetBlockFilters(ref item) => {
+                write!(f, "{}::{}({})", Self::NAME, GetBlockFilters::NAME, item)
+            }
+            BlockFilterMessageUnion::BlockFilters(ref item) => {
+                write!(f, "{}::{}({})", Self::NAME, BlockFilters::NAME, item)
+            }
+            BlockFilterMessageUnion::GetBlockFilterHashes(ref item) => {
+                write!(
+                    f,
+                    "{}::{}({})",
+                    Self::NAME,
+                    GetBlockFilterHashes::NAME,
+                    item
+                )
+            }
+            BlockFilterMessageUnion::BlockFilterHashes(ref item) => {
+                write!(f, "{}::{}({})", Self::NAME, BlockFilterHashes::NAME, item)
+            }
+            BlockFilterMessageUnion::GetBlockFilterCheckPoints(ref item) => {
+                write!(
+                    f,
+                    "{}::{}({})",
+                    Self::NAME,
+                    GetBlockFilterCheckPoints::NAME,
+                    item
+                )
+            }
+            BlockFilterMessageUnion::BlockFilterCheckPoints(ref item) => {
+                write!(
+                    f,
+                    "{}::{}({})",
+                    Self::NAME,
+                    BlockFilterCheckPoints::NAME,
+                    item
+                )
+            }
+        }
+    }
+}
+impl<'r> ::core::fmt::Display for BlockFilterMessageUnionReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        match self {
+            BlockFilterMessageUnionReader::GetBlockFilters(ref item) => {
+                write!(f, "{}::{}({})", Self::NAME, GetBlockFilters::NAME, item)
+            }
+            BlockFilterMessageUnionReader::BlockFilters(ref item) => {
+                write!(f, "{}::{}({})", Self::NAME, BlockFilters::NAME, item)
+            }
+            BlockFilterMessageUnionReader::GetBlockFilterHashes(ref item) => {
+                write!(
+                    f,
+                    "{}::{}({})",
+                    Self::NAME,
+                    GetBlockFilterHashes::NAME,
+                    item
+                )
+            }
+            BlockFilterMessageUnionReader::BlockFilterHashes(ref item) => {
+                write!(f, "{}::{}({})", Self::NAME, BlockFilterHashes::NAME, item)
+            }
+            BlockFilterMessageUnionReader::GetBlockFilterCheckPoints(ref item) => {
+                write!(
+                    f,
+                    "{}::{}({})",
+                    Self::NAME,
+                    GetBlockFilterCheckPoints::NAME,
+                    item
+                )
+            }
+            BlockFilterMessageUnionReader::BlockFilterCheckPoints(ref item) => {
+                write!(
+                    f,
+                    "{}::{}({})",
+                    Self::NAME,
+                    BlockFilterCheckPoints::NAME,
+                    item
+                )
+            }
+        }
+    }
+}
+impl BlockFilterMessageUnion {
+    pub(crate) fn display_inner(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        match self {
+            BlockFilterMessageUnion::GetBlockFilters(ref item) => write!(f, "{}", item),
+            BlockFilterMessageUnion::BlockFilters(ref item) => write!(f, "{}", item),
+            BlockFilterMessageUnion::GetBlockFilterHashes(ref item) => write!(f, "{}", item),
+            BlockFilterMessageUnion::BlockFilterHashes(ref item) => write!(f, "{}", item),
+            BlockFilterMessageUnion::GetBlockFilterCheckPoints(ref item) => write!(f, "{}", item),
+            BlockFilterMessageUnion::BlockFilterCheckPoints(ref item) => write!(f, "{}", item),
+        }
+    }
+}
+impl<'r> BlockFilterMessageUnionReader<'r> {
+    pub(crate) fn display_inner(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        match self {
+            BlockFilterMessageUnionReader::GetBlockFilters(ref item) => write!(f, "{}", item),
+            BlockFilterMessageUnionReader::BlockFilters(ref item) => write!(f, "{}", item),
+            BlockFilterMessageUnionReader::GetBlockFilterHashes(ref item) => write!(f, "{}", item),
+            BlockFilterMessageUnionReader::BlockFilterHashes(ref item) => write!(f, "{}", item),
+            BlockFilterMessageUnionReader::GetBlockFilterCheckPoints(ref item) => {
+                write!(f, "{}", item)
+            }
+            BlockFilterMessageUnionReader::BlockFilterCheckPoints(ref item) => {
+                write!(f, "{}", item)
+            }
+        }
+    }
+}
+impl ::core::convert::From<GetBlockFilters> for BlockFilterMessageUnion {
+    fn from(item: GetBlockFilters) -> Self {
+        BlockFilterMessageUnion::GetBlockFilters(item)
+    }
+}
+impl ::core::convert::From<BlockFilters> for BlockFilterMessageUnion {
+    fn from(item: BlockFilters) -> Self {
+        BlockFilterMessageUnion::BlockFilters(item)
+    }
+}
+impl ::core::convert::From<GetBlockFilterHashes> for BlockFilterMessageUnion {
+    fn from(item: GetBlockFilterHashes) -> Self {
+        BlockFilterMessageUnion::GetBlockFilterHashes(item)
+    }
+}
+impl ::core::convert::From<BlockFilterHashes> for BlockFilterMessageUnion {
+    fn from(item: BlockFilterHashes) -> Self {
+        BlockFilterMessageUnion::BlockFilterHashes(item)
+    }
+}
+impl ::core::convert::From<GetBlockFilterCheckPoints> for BlockFilterMessageUnion {
+    fn from(item: GetBlockFilterCheckPoints) -> Self {
+        BlockFilterMessageUnion::GetBlockFilterCheckPoints(item)
+    }
+}
+impl ::core::convert::From<BlockFilterCheckPoints> for BlockFilterMessageUnion {
+    fn from(item: BlockFilterCheckPoints) -> Self {
+        BlockFilterMessageUnion::BlockFilterCheckPoints(item)
+    }
+}
+impl<'r> ::core::convert::From<GetBlockFiltersReader<'r>> for BlockFilterMessageUnionReader<'r> {
+    fn from(item: GetBlockFiltersReader<'r>) -> Self {
+        BlockFilterMessageUnionReader::GetBlockFilters(item)
+    }
+}
+impl<'r> ::core::convert::From<BlockFiltersReader<'r>> for BlockFilterMessageUnionReader<'r> {
+    fn from(item: BlockFiltersReader<'r>) -> Self {
+        BlockFilterMessageUnionReader::BlockFilters(item)
+    }
+}
+impl<'r> ::core::convert::From<GetBlockFilterHashesReader<'r>>
+    for BlockFilterMessageUnionReader<'r>
+{
+    fn from(item: GetBlockFilterHashesReader<'r>) -> Self {
+        BlockFilterMessageUnionReader::GetBlockFilterHashes(item)
+    }
+}
+impl<'r> ::core::convert::From<BlockFilterHashesReader<'r>> for BlockFilterMessageUnionReader<'r> {
+    fn from(item: BlockFilterHashesReader<'r>) -> Self {
+        BlockFilterMessageUnionReader::BlockFilterHashes(item)
+    }
+}
+impl<'r> ::core::convert::From<GetBlockFilterCheckPointsReader<'r>>
+    for BlockFilterMessageUnionReader<'r>
+{
+    fn from(item: GetBlockFilterCheckPointsReader<'r>) -> Self {
+        BlockFilterMessageUnionReader::GetBlockFilterCheckPoints(item)
+    }
+}
+impl<'r> ::core::convert::From<BlockFilterCheckPointsReader<'r>>
+    for BlockFilterMessageUnionReader<'r>
+{
+    fn from(item: BlockFilterCheckPointsReader<'r>) -> Self {
+        BlockFilterMessageUnionReader::BlockFilterCheckPoints(item)
+    }
+}
+impl BlockFilterMessageUnion {
+    pub const NAME: &'static str = "BlockFilterMessageUnion";
+    pub fn as_bytes(&self) -> molecule::bytes::Bytes {
+        match self {
+            BlockFilterMessageUnion::GetBlockFilters(item) => item.as_bytes(),
+            BlockFilterMessageUnion::BlockFilters(item) => item.as_bytes(),
+            BlockFilterMessageUnion::GetBlockFilterHashes(item) => item.as_bytes(),
+            BlockFilterMessageUnion::BlockFilterHashes(item) => item.as_bytes(),
+            BlockFilterMessageUnion::GetBlockFilterCheckPoints(item) => item.as_bytes(),
+            BlockFilterMessageUnion::BlockFilterCheckPoints(item) => item.as_bytes(),
+        }
+    }
+    pub fn as_slice(&self) -> &[u8] {
+        match self {
+            BlockFilterMessageUnion::GetBlockFilters(item) => item.as_slice(),
+            BlockFilterMessageUnion::BlockFilters(item) => item.as_slice(),
+            BlockFilterMessageUnion::GetBlockFilterHashes(item) => item.as_slice(),
+            BlockFilterMessageUnion::BlockFilterHashes(item) => item.as_slice(),
+            BlockFilterMessageUnion::GetBlockFilterCheckPoints(item) => item.as_slice(),
+            BlockFilterMessageUnion::BlockFilterCheckPoints(item) => item.as_slice(),
+        }
+    }
+    pub fn item_id(&self) -> molecule::Number {
+        match self {
+            BlockFilterMessageUnion::GetBlockFilters(_) => 0,
+            BlockFilterMessageUnion::BlockFilters(_) => 1,
+            BlockFilterMessageUnion::GetBlockFilterHashes(_) => 2,
+            BlockFilterMessageUnion::BlockFilterHashes(_) => 3,
+            BlockFilterMessageUnion::GetBlockFilterCheckPoints(_) => 4,
+            BlockFilterMessageUnion::BlockFilterCheckPoints(_) => 5,
+        }
+    }
+    pub fn item_name(&self) -> &str {
+        match self {
+            BlockFilterMessageUnion::GetBlockFilters(_) => "GetBlockFilters",
+            BlockFilterMessageUnion::BlockFilters(_) => "BlockFilters",
+            BlockFilterMessageUnion::GetBlockFilterHashes(_) => "GetBlockFilterHashes",
+            BlockFilterMessageUnion::BlockFilterHashes(_) => "BlockFilterHashes",
+            BlockFilterMessageUnion::GetBlockFilterCheckPoints(_) => "GetBlockFilterCheckPoints",
+            BlockFilterMessageUnion::BlockFilterCheckPoints(_) => "BlockFilterCheckPoints",
+        }
+    }
+    pub fn as_reader<'r>(&'r self) -> BlockFilterMessageUnionReader<'r> {
+        match self {
+            BlockFilterMessageUnion::GetBlockFilters(item) => item.as_reader().into(),
+            BlockFilterMessageUnion::BlockFilters(item) => item.as_reader().into(),
+            BlockFilterMessageUnion::GetBlockFilterHashes(item) => item.as_reader().into(),
+            BlockFilterMessageUnion::BlockFilterHashes(item) => item.as_reader().into(),
+            BlockFilterMessageUnion::GetBlockFilterCheckPoints(item) => item.as_reader().into(),
+            BlockFilterMessageUnion::BlockFilterCheckPoints(item) => item.as_reader().into(),
+        }
+    }
+}
+impl<'r> BlockFilterMessageUnionReader<'r> {
+    pub const NAME: &'r str = "BlockFilterMessageUnionReader";
+    pub fn as_slice(&self) -> &'r [u8] {
+        match self {
+            BlockFilterMessageUnionReader::GetBlockFilters(item) => item.as_slice(),
+            BlockFilterMessageUnionReader::BlockFilters(item) => item.as_slice(),
+            BlockFilterMessageUnionReader::GetBlockFilterHashes(item) => item.as_slice(),
+            BlockFilterMessageUnionReader::BlockFilterHashes(item) => item.as_slice(),
+            BlockFilterMessageUnionReader::GetBlockFilterCheckPoints(item) => item.as_slice(),
+            BlockFilterMessageUnionReader::BlockFilterCheckPoints(item) => item.as_slice(),
+        }
+    }
+    pub fn item_id(&self) -> molecule::Number {
+        match self {
+            BlockFilterMessageUnionReader::GetBlockFilters(_) => 0,
+            BlockFilterMessageUnionReader::BlockFilters(_) => 1,
+            BlockFilterMessageUnionReader::GetBlockFilterHashes(_) => 2,
+            BlockFilterMessageUnionReader::BlockFilterHashes(_) => 3,
+            BlockFilterMessageUnionReader::GetBlockFilterCheckPoints(_) => 4,
+            BlockFilterMessageUnionReader::BlockFilterCheckPoints(_) => 5,
+        }
+    }
+    pub fn item_name(&self) -> &str {
+        match self {
+            BlockFilterMessageUnionReader::GetBlockFilters(_) => "GetBlockFilters",
+            BlockFilterMessageUnionReader::BlockFilters(_) => "BlockFilters",
+            BlockFilterMessageUnionReader::GetBlockFilterHashes(_) => "GetBlockFilterHashes",
+            BlockFilterMessageUnionReader::BlockFilterHashes(_) => "BlockFilterHashes",
+            BlockFilterMessageUnionReader::GetBlockFilterCheckPoints(_) => {
+                "GetBlockFilterCheckPoints"
+            }
+            BlockFilterMessageUnionReader::BlockFilterCheckPoints(_) => "BlockFilterCheckPoints",
+        }
+    }
+}
+#[derive(Clone)]
+pub struct GetBlockFilters(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for GetBlockFilters {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl ::core::fmt::Debug for GetBlockFilters {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl ::core::fmt::Display for GetBlockFilters {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "start_number", self.start_number())?;
+        write!(f, " }}")
+    }
+}
+impl ::core::default::Default for GetBlockFilters {
+    fn default() -> Self {
+        let v = molecule::bytes::Bytes::from_static(&Self::DEFAULT_VALUE);
+        GetBlockFilters::new_unchecked(v)
+    }
+}
+impl GetBlockFilters {
+    const DEFAULT_VALUE: [u8; 8] = [0, 0, 0, 0, 0, 0, 0, 0];
+    pub const TOTAL_SIZE: usize = 8;
+    pub const FIELD_SIZES: [usize; 1] = [8];
+    pub const FIELD_COUNT: usize = 1;
+    pub fn start_number(&self) -> Uint64 {
+        Uint64::new_unchecked(self.0.slice(0..8))
+    }
+    pub fn as_reader<'r>(&'r self) -> GetBlockFiltersReader<'r> {
+        GetBlockFiltersReader::new_unchecked(self.as_slice())
+    }
+}
+impl molecule::prelude::Entity for GetBlockFilters {
+    type Builder = GetBlockFiltersBuilder;
+    const NAME: &'static str = "GetBlockFilters";
+    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
+        GetBlockFilters(data)
+    }
+    fn as_bytes(&self) -> molecule::bytes::Bytes {
+        self.0.clone()
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        GetBlockFiltersReader::from_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        GetBlockFiltersReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn new_builder() -> Self::Builder {
+        ::core::default::Default::default()
+    }
+    fn as_builder(self) -> Self::Builder {
+        Self::new_builder().start_number(self.start_number())
+    }
+}
+#[derive(Clone, Copy)]
+pub struct GetBlockFiltersReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for GetBlockFiltersReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl<'r> ::core::fmt::Debug for GetBlockFiltersReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl<'r> ::core::fmt::Display for GetBlockFiltersReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "start_number", self.start_number())?;
+        write!(f, " }}")
+    }
+}
+impl<'r> GetBlockFiltersReader<'r> {
+    pub const TOTAL_SIZE: usize = 8;
+    pub const FIELD_SIZES: [usize; 1] = [8];
+    pub const FIELD_COUNT: usize = 1;
+    pub fn start_number(&self) -> Uint64Reader<'r> {
+        Uint64Reader::new_unchecked(&self.as_slice()[0..8])
+    }
+}
+impl<'r> molecule::prelude::Reader<'r> for GetBlockFiltersReader<'r> {
+    type Entity = GetBlockFilters;
+    const NAME: &'static str = "GetBlockFiltersReader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        GetBlockFiltersReader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], _compatible: bool) -> molecule::error::VerificationResult<()> {
+        use molecule::verification_error as ve;
+        let slice_len = slice.len();
+        if slice_len != Self::TOTAL_SIZE {
+            return ve!(Self, TotalSizeNotMatch, Self::TOTAL_SIZE, slice_len);
+        }
+        Ok(())
+    }
+}
+#[derive(Debug, Default)]
+pub struct GetBlockFiltersBuilder {
+    pub(crate) start_number: Uint64,
+}
+impl GetBlockFiltersBuilder {
+    pub const TOTAL_SIZE: usize = 8;
+    pub const FIELD_SIZES: [usize; 1] = [8];
+    pub const FIELD_COUNT: usize = 1;
+    pub fn start_number(mut self, v: Uint64) -> Self {
+        self.start_number = v;
+        self
+    }
+}
+impl molecule::prelude::Builder for GetBlockFiltersBuilder {
+    type Entity = GetBlockFilters;
+    const NAME: &'static str = "GetBlockFiltersBuilder";
+    fn expected_length(&self) -> usize {
+        Self::TOTAL_SIZE
+    }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        writer.write_all(self.start_number.as_slice())?;
+        Ok(())
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        GetBlockFilters::new_unchecked(inner.into())
+    }
+}
+#[derive(Clone)]
+pub struct BlockFilters(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for BlockFilters {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl ::core::fmt::Debug for BlockFilters {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl ::core::fmt::Display for BlockFilters {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "start_number", self.start_number())?;
+        write!(f, ", {}: {}", "block_hashes", self.block_hashes())?;
+        write!(f, ", {}: {}", "filters", self.filters())?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ", .. ({} fields)", extra_count)?;
+        }
+        write!(f, " }}")
+    }
+}
+impl ::core::default::Default for BlockFilters {
+    fn default() -> Self {
+        let v = molecule::bytes::Bytes::from_static(&Self::DEFAULT_VALUE);
+        BlockFilters::new_unchecked(v)
+    }
+}
+impl BlockFilters {
+    const DEFAULT_VALUE: [u8; 32] = [
+        32, 0, 0, 0, 16, 0, 0, 0, 24, 0, 0, 0, 28, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 4,
+        0, 0, 0,
+    ];
+    pub const FIELD_COUNT: usize = 3;
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
+    }
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+    pub fn start_number(&self) -> Uint64 {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[4..]) as usize;
+        let end = molecule::unpack_number(&slice[8..]) as usize;
+        Uint64::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn block_hashes(&self) -> Byte32Vec {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[8..]) as usize;
+        let end = molecule::unpack_number(&slice[12..]) as usize;
+        Byte32Vec::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn filters(&self) -> BytesVec {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[12..]) as usize;
+        if self.has_extra_fields() {
+            let end = molecule::unpack_number(&slice[16..]) as usize;
+            BytesVec::new_unchecked(self.0.slice(start..end))
+        } else {
+            BytesVec::new_unchecked(self.0.slice(start..))
+        }
+    }
+    pub fn as_reader<'r>(&'r self) -> BlockFiltersReader<'r> {
+        BlockFiltersReader::new_unchecked(self.as_slice())
+    }
+}
+impl molecule::prelude::Entity for BlockFilters {
+    type Builder = BlockFiltersBuilder;
+    const NAME: &'static str = "BlockFilters";
+    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
+        BlockFilters(data)
+    }
+    fn as_bytes(&self) -> molecule::bytes::Bytes {
+        self.0.clone()
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        BlockFiltersReader::from_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        BlockFiltersReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn new_builder() -> Self::Builder {
+        ::core::default::Default::default()
+    }
+    fn as_builder(self) -> Self::Builder {
+        Self::new_builder()
+            .start_number(self.start_number())
+            .block_hashes(self.block_hashes())
+            .filters(self.filters())
+    }
+}
+#[derive(Clone, Copy)]
+pub struct BlockFiltersReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for BlockFiltersReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl<'r> ::core::fmt::Debug for BlockFiltersReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl<'r> ::core::fmt::Display for BlockFiltersReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "start_number", self.start_number())?;
+        write!(f, ", {}: {}", "block_hashes", self.block_hashes())?;
+        write!(f, ", {}: {}", "filters", self.filters())?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ", .. ({} fields)", extra_count)?;
+        }
+        write!(f, " }}")
+    }
+}
+impl<'r> BlockFiltersReader<'r> {
+    pub const FIELD_COUNT: usize = 3;
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
+    }
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+    pub fn start_number(&self) -> Uint64Reader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[4..]) as usize;
+        let end = molecule::unpack_number(&slice[8..]) as usize;
+        Uint64Reader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn block_hashes(&self) -> Byte32VecReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[8..]) as usize;
+        let end = molecule::unpack_number(&slice[12..]) as usize;
+        Byte32VecReader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn filters(&self) -> BytesVecReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[12..]) as usize;
+        if self.has_extra_fields() {
+            let end = molecule::unpack_number(&slice[16..]) as usize;
+            BytesVecReader::new_unchecked(&self.as_slice()[start..end])
+        } else {
+            BytesVecReader::new_unchecked(&self.as_slice()[start..])
+        }
+    }
+}
+impl<'r> molecule::prelude::Reader<'r> for BlockFiltersReader<'r> {
+    type Entity = BlockFilters;
+    const NAME: &'static str = "BlockFiltersReader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        BlockFiltersReader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], compatible: bool) -> molecule::error::VerificationResult<()> {
+        use molecule::verification_error as ve;
+        let slice_len = slice.len();
+        if slice_len < molecule::NUMBER_SIZE {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE, slice_len);
+        }
+        let total_size = molecule::unpack_number(slice) as usize;
+        if slice_len != total_size {
+            return ve!(Self, TotalSizeNotMatch, total_size, slice_len);
+        }
+        if slice_len < molecule::NUMBER_SIZE * 2 {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE * 2, slice_len);
+        }
+        let offset_first = molecule::unpack_number(&slice[molecule::NUMBER_SIZE..]) as usize;
+        if offset_first % molecule::NUMBER_SIZE != 0 || offset_first < molecule::NUMBER_SIZE * 2 {
+            return ve!(Self, OffsetsNotMatch);
+        }
+        if slice_len < offset_first {
+            return ve!(Self, HeaderIsBroken, offset_first, slice_len);
+        }
+        let field_count = offset_first / molecule::NUMBER_SIZE - 1;
+        if field_count < Self::FIELD_COUNT {
+            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
+        } else if !compatible && field_count > Self::FIELD_COUNT {
+            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
+        };
+        let mut offsets: Vec<usize> = slice[molecule::NUMBER_SIZE..offset_first]
+            .chunks_exact(molecule::NUMBER_SIZE)
+            .map(|x| molecule::unpack_number(x) as usize)
+            .collect();
+        offsets.push(total_size);
+        if offsets.windows(2).any(|i| i[0] > i[1]) {
+            return ve!(Self, OffsetsNotMatch);
+        }
+        Uint64Reader::verify(&slice[offsets[0]..offsets[1]], compatible)?;
+        Byte32VecReader::verify(&slice[offsets[1]..offsets[2]], compatible)?;
+        BytesVecReader::verify(&slice[offsets[2]..offsets[3]], compatible)?;
+        Ok(())
+    }
+}
+#[derive(Debug, Default)]
+pub struct BlockFiltersBuilder {
+    pub(crate) start_number: Uint64,
+    pub(crate) block_hashes: Byte32Vec,
+    pub(crate) filters: BytesVec,
+}
+impl BlockFiltersBuilder {
+    pub const FIELD_COUNT: usize = 3;
+    pub fn start_number(mut self, v: Uint64) -> Self {
+        self.start_number = v;
+        self
+    }
+    pub fn block_hashes(mut self, v: Byte32Vec) -> Self {
+        self.block_hashes = v;
+        self
+    }
+    pub fn filters(mut self, v: BytesVec) -> Self {
+        self.filters = v;
+        self
+    }
+}
+impl molecule::prelude::Builder for BlockFiltersBuilder {
+    type Entity = BlockFilters;
+    const NAME: &'static str = "BlockFiltersBuilder";
+    fn expected_length(&self) -> usize {
+        molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1)
+            + self.start_number.as_slice().len()
+            + self.block_hashes.as_slice().len()
+            + self.filters.as_slice().len()
+    }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        let mut total_size = molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1);
+        let mut offsets = Vec::with_capacity(Self::FIELD_COUNT);
+        offsets.push(total_size);
+        total_size += self.start_number.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.block_hashes.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.filters.as_slice().len();
+        writer.write_all(&molecule::pack_number(total_size as molecule::Number))?;
+        for offset in offsets.into_iter() {
+            writer.write_all(&molecule::pack_number(offset as molecule::Number))?;
+        }
+        writer.write_all(self.start_number.as_slice())?;
+        writer.write_all(self.block_hashes.as_slice())?;
+        writer.write_all(self.filters.as_slice())?;
+        Ok(())
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        BlockFilters::new_unchecked(inner.into())
+    }
+}
+#[derive(Clone)]
+pub struct GetBlockFilterHashes(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for GetBlockFilterHashes {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl ::core::fmt::Debug for GetBlockFilterHashes {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl ::core::fmt::Display for GetBlockFilterHashes {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "start_number", self.start_number())?;
+        write!(f, " }}")
+    }
+}
+impl ::core::default::Default for GetBlockFilterHashes {
+    fn default() -> Self {
+        let v = molecule::bytes::Bytes::from_static(&Self::DEFAULT_VALUE);
+        GetBlockFilterHashes::new_unchecked(v)
+    }
+}
+impl GetBlockFilterHashes {
+    const DEFAULT_VALUE: [u8; 8] = [0, 0, 0, 0, 0, 0, 0, 0];
+    pub const TOTAL_SIZE: usize = 8;
+    pub const FIELD_SIZES: [usize; 1] = [8];
+    pub const FIELD_COUNT: usize = 1;
+    pub fn start_number(&self) -> Uint64 {
+        Uint64::new_unchecked(self.0.slice(0..8))
+    }
+    pub fn as_reader<'r>(&'r self) -> GetBlockFilterHashesReader<'r> {
+        GetBlockFilterHashesReader::new_unchecked(self.as_slice())
+    }
+}
+impl molecule::prelude::Entity for GetBlockFilterHashes {
+    type Builder = GetBlockFilterHashesBuilder;
+    const NAME: &'static str = "GetBlockFilterHashes";
+    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
+        GetBlockFilterHashes(data)
+    }
+    fn as_bytes(&self) -> molecule::bytes::Bytes {
+        self.0.clone()
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        GetBlockFilterHashesReader::from_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        GetBlockFilterHashesReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn new_builder() -> Self::Builder {
+        ::core::default::Default::default()
+    }
+    fn as_builder(self) -> Self::Builder {
+        Self::new_builder().start_number(self.start_number())
+    }
+}
+#[derive(Clone, Copy)]
+pub struct GetBlockFilterHashesReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for GetBlockFilterHashesReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl<'r> ::core::fmt::Debug for GetBlockFilterHashesReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl<'r> ::core::fmt::Display for GetBlockFilterHashesReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "start_number", self.start_number())?;
+        write!(f, " }}")
+    }
+}
+impl<'r> GetBlockFilterHashesReader<'r> {
+    pub const TOTAL_SIZE: usize = 8;
+    pub const FIELD_SIZES: [usize; 1] = [8];
+    pub const FIELD_COUNT: usize = 1;
+    pub fn start_number(&self) -> Uint64Reader<'r> {
+        Uint64Reader::new_unchecked(&self.as_slice()[0..8])
+    }
+}
+impl<'r> molecule::prelude::Reader<'r> for GetBlockFilterHashesReader<'r> {
+    type Entity = GetBlockFilterHashes;
+    const NAME: &'static str = "GetBlockFilterHashesReader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        GetBlockFilterHashesReader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], _compatible: bool) -> molecule::error::VerificationResult<()> {
+        use molecule::verification_error as ve;
+        let slice_len = slice.len();
+        if slice_len != Self::TOTAL_SIZE {
+            return ve!(Self, TotalSizeNotMatch, Self::TOTAL_SIZE, slice_len);
+        }
+        Ok(())
+    }
+}
+#[derive(Debug, Default)]
+pub struct GetBlockFilterHashesBuilder {
+    pub(crate) start_number: Uint64,
+}
+impl GetBlockFilterHashesBuilder {
+    pub const TOTAL_SIZE: usize = 8;
+    pub const FIELD_SIZES: [usize; 1] = [8];
+    pub const FIELD_COUNT: usize = 1;
+    pub fn start_number(mut self, v: Uint64) -> Self {
+        self.start_number = v;
+        self
+    }
+}
+impl molecule::prelude::Builder for GetBlockFilterHashesBuilder {
+    type Entity = GetBlockFilterHashes;
+    const NAME: &'static str = "GetBlockFilterHashesBuilder";
+    fn expected_length(&self) -> usize {
+        Self::TOTAL_SIZE
+    }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        writer.write_all(self.start_number.as_slice())?;
+        Ok(())
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        GetBlockFilterHashes::new_unchecked(inner.into())
+    }
+}
+#[derive(Clone)]
+pub struct BlockFilterHashes(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for BlockFilterHashes {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl ::core::fmt::Debug for BlockFilterHashes {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl ::core::fmt::Display for BlockFilterHashes {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "start_number", self.start_number())?;
+        write!(
+            f,
+            ", {}: {}",
+            "parent_block_filter_hash",
+            self.parent_block_filter_hash()
+        )?;
+        write!(
+            f,
+            ", {}: {}",
+            "block_filter_hashes",
+            self.block_filter_hashes()
+        )?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ", .. ({} fields)", extra_count)?;
+        }
+        write!(f, " }}")
+    }
+}
+impl ::core::default::Default for BlockFilterHashes {
+    fn default() -> Self {
+        let v = molecule::bytes::Bytes::from_static(&Self::DEFAULT_VALUE);
+        BlockFilterHashes::new_unchecked(v)
+    }
+}
+impl BlockFilterHashes {
+    const DEFAULT_VALUE: [u8; 60] = [
+        60, 0, 0, 0, 16, 0, 0, 0, 24, 0, 0, 0, 56, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0,
+    ];
+    pub const FIELD_COUNT: usize = 3;
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
+    }
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+    pub fn start_number(&self) -> Uint64 {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[4..]) as usize;
+        let end = molecule::unpack_number(&slice[8..]) as usize;
+        Uint64::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn parent_block_filter_hash(&self) -> Byte32 {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[8..]) as usize;
+        let end = molecule::unpack_number(&slice[12..]) as usize;
+        Byte32::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn block_filter_hashes(&self) -> Byte32Vec {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[12..]) as usize;
+        if self.has_extra_fields() {
+            let end = molecule::unpack_number(&slice[16..]) as usize;
+            Byte32Vec::new_unchecked(self.0.slice(start..end))
+        } else {
+            Byte32Vec::new_unchecked(self.0.slice(start..))
+        }
+    }
+    pub fn as_reader<'r>(&'r self) -> BlockFilterHashesReader<'r> {
+        BlockFilterHashesReader::new_unchecked(self.as_slice())
+    }
+}
+impl molecule::prelude::Entity for BlockFilterHashes {
+    type Builder = BlockFilterHashesBuilder;
+    const NAME: &'static str = "BlockFilterHashes";
+    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
+        BlockFilterHashes(data)
+    }
+    fn as_bytes(&self) -> molecule::bytes::Bytes {
+        self.0.clone()
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        BlockFilterHashesReader::from_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        BlockFilterHashesReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn new_builder() -> Self::Builder {
+        ::core::default::Default::default()
+    }
+    fn as_builder(self) -> Self::Builder {
+        Self::new_builder()
+            .start_number(self.start_number())
+            .parent_block_filter_hash(self.parent_block_filter_hash())
+            .block_filter_hashes(self.block_filter_hashes())
+    }
+}
+#[derive(Clone, Copy)]
+pub struct BlockFilterHashesReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for BlockFilterHashesReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl<'r> ::core::fmt::Debug for BlockFilterHashesReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl<'r> ::core::fmt::Display for BlockFilterHashesReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "start_number", self.start_number())?;
+        write!(
+            f,
+            ", {}: {}",
+            "parent_block_filter_hash",
+            self.parent_block_filter_hash()
+        )?;
+        write!(
+            f,
+            ", {}: {}",
+            "block_filter_hashes",
+            self.block_filter_hashes()
+        )?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ", .. ({} fields)", extra_count)?;
+        }
+        write!(f, " }}")
+    }
+}
+impl<'r> BlockFilterHashesReader<'r> {
+    pub const FIELD_COUNT: usize = 3;
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
+    }
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+    pub fn start_number(&self) -> Uint64Reader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[4..]) as usize;
+        let end = molecule::unpack_number(&slice[8..]) as usize;
+        Uint64Reader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn parent_block_filter_hash(&self) -> Byte32Reader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[8..]) as usize;
+        let end = molecule::unpack_number(&slice[12..]) as usize;
+        Byte32Reader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn block_filter_hashes(&self) -> Byte32VecReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[12..]) as usize;
+        if self.has_extra_fields() {
+            let end = molecule::unpack_number(&slice[16..]) as usize;
+            Byte32VecReader::new_unchecked(&self.as_slice()[start..end])
+        } else {
+            Byte32VecReader::new_unchecked(&self.as_slice()[start..])
+        }
+    }
+}
+impl<'r> molecule::prelude::Reader<'r> for BlockFilterHashesReader<'r> {
+    type Entity = BlockFilterHashes;
+    const NAME: &'static str = "BlockFilterHashesReader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        BlockFilterHashesReader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], compatible: bool) -> molecule::error::VerificationResult<()> {
+        use molecule::verification_error as ve;
+        let slice_len = slice.len();
+        if slice_len < molecule::NUMBER_SIZE {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE, slice_len);
+        }
+        let total_size = molecule::unpack_number(slice) as usize;
+        if slice_len != total_size {
+            return ve!(Self, TotalSizeNotMatch, total_size, slice_len);
+        }
+        if slice_len < molecule::NUMBER_SIZE * 2 {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE * 2, slice_len);
+        }
+        let offset_first = molecule::unpack_number(&slice[molecule::NUMBER_SIZE..]) as usize;
+        if offset_first % molecule::NUMBER_SIZE != 0 || offset_first < molecule::NUMBER_SIZE * 2 {
+            return ve!(Self, OffsetsNotMatch);
+        }
+        if slice_len < offset_first {
+            return ve!(Self, HeaderIsBroken, offset_first, slice_len);
+        }
+        let field_count = offset_first / molecule::NUMBER_SIZE - 1;
+        if field_count < Self::FIELD_COUNT {
+            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
+        } else if !compatible && field_count > Self::FIELD_COUNT {
+            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
+        };
+        let mut offsets: Vec<usize> = slice[molecule::NUMBER_SIZE..offset_first]
+            .chunks_exact(molecule::NUMBER_SIZE)
+            .map(|x| molecule::unpack_number(x) as usize)
+            .collect();
+        offsets.push(total_size);
+        if offsets.windows(2).any(|i| i[0] > i[1]) {
+            return ve!(Self, OffsetsNotMatch);
+        }
+        Uint64Reader::verify(&slice[offsets[0]..offsets[1]], compatible)?;
+        Byte32Reader::verify(&slice[offsets[1]..offsets[2]], compatible)?;
+        Byte32VecReader::verify(&slice[offsets[2]..offsets[3]], compatible)?;
+        Ok(())
+    }
+}
+#[derive(Debug, Default)]
+pub struct BlockFilterHashesBuilder {
+    pub(crate) start_number: Uint64,
+    pub(crate) parent_block_filter_hash: Byte32,
+    pub(crate) block_filter_hashes: Byte32Vec,
+}
+impl BlockFilterHashesBuilder {
+    pub const FIELD_COUNT: usize = 3;
+    pub fn start_number(mut self, v: Uint64) -> Self {
+        self.start_number = v;
+        self
+    }
+    pub fn parent_block_filter_hash(mut self, v: Byte32) -> Self {
+        self.parent_block_filter_hash = v;
+        self
+    }
+    pub fn block_filter_hashes(mut self, v: Byte32Vec) -> Self {
+        self.block_filter_hashes = v;
+        self
+    }
+}
+impl molecule::prelude::Builder for BlockFilterHashesBuilder {
+    type Entity = BlockFilterHashes;
+    const NAME: &'static str = "BlockFilterHashesBuilder";
+    fn expected_length(&self) -> usize {
+        molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1)
+            + self.start_number.as_slice().len()
+            + self.parent_block_filter_hash.as_slice().len()
+            + self.block_filter_hashes.as_slice().len()
+    }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        let mut total_size = molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1);
+        let mut offsets = Vec::with_capacity(Self::FIELD_COUNT);
+        offsets.push(total_size);
+        total_size += self.start_number.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.parent_block_filter_hash.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.block_filter_hashes.as_slice().len();
+        writer.write_all(&molecule::pack_number(total_size as molecule::Number))?;
+        for offset in offsets.into_iter() {
+            writer.write_all(&molecule::pack_number(offset as molecule::Number))?;
+        }
+        writer.write_all(self.start_number.as_slice())?;
+        writer.write_all(self.parent_block_filter_hash.as_slice())?;
+        writer.write_all(self.block_filter_hashes.as_slice())?;
+        Ok(())
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        BlockFilterHashes::new_unchecked(inner.into())
+    }
+}
+#[derive(Clone)]
+pub struct GetBlockFilterCheckPoints(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for GetBlockFilterCheckPoints {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl ::core::fmt::Debug for GetBlockFilterCheckPoints {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl ::core::fmt::Display for GetBlockFilterCheckPoints {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "start_number", self.start_number())?;
+        write!(f, " }}")
+    }
+}
+impl ::core::default::Default for GetBlockFilterCheckPoints {
+    fn default() -> Self {
+        let v = molecule::bytes::Bytes::from_static(&Self::DEFAULT_VALUE);
+        GetBlockFilterCheckPoints::new_unchecked(v)
+    }
+}
+impl GetBlockFilterCheckPoints {
+    const DEFAULT_VALUE: [u8; 8] = [0, 0, 0, 0, 0, 0, 0, 0];
+    pub const TOTAL_SIZE: usize = 8;
+    pub const FIELD_SIZES: [usize; 1] = [8];
+    pub const FIELD_COUNT: usize = 1;
+    pub fn start_number(&self) -> Uint64 {
+        Uint64::new_unchecked(self.0.slice(0..8))
+    }
+    pub fn as_reader<'r>(&'r self) -> GetBlockFilterCheckPointsReader<'r> {
+        GetBlockFilterCheckPointsReader::new_unchecked(self.as_slice())
+    }
+}
+impl molecule::prelude::Entity for GetBlockFilterCheckPoints {
+    type Builder = GetBlockFilterCheckPointsBuilder;
+    const NAME: &'static str = "GetBlockFilterCheckPoints";
+    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
+        GetBlockFilterCheckPoints(data)
+    }
+    fn as_bytes(&self) -> molecule::bytes::Bytes {
+        self.0.clone()
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        GetBlockFilterCheckPointsReader::from_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        GetBlockFilterCheckPointsReader::from_compatible_slice(slice)
+            .map(|reader| reader.to_entity())
+    }
+    fn new_builder() -> Self::Builder {
+        ::core::default::Default::default()
+    }
+    fn as_builder(self) -> Self::Builder {
+        Self::new_builder().start_number(self.start_number())
+    }
+}
+#[derive(Clone, Copy)]
+pub struct GetBlockFilterCheckPointsReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for GetBlockFilterCheckPointsReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl<'r> ::core::fmt::Debug for GetBlockFilterCheckPointsReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl<'r> ::core::fmt::Display for GetBlockFilterCheckPointsReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "start_number", self.start_number())?;
+        write!(f, " }}")
+    }
+}
+impl<'r> GetBlockFilterCheckPointsReader<'r> {
+    pub const TOTAL_SIZE: usize = 8;
+    pub const FIELD_SIZES: [usize; 1] = [8];
+    pub const FIELD_COUNT: usize = 1;
+    pub fn start_number(&self) -> Uint64Reader<'r> {
+        Uint64Reader::new_unchecked(&self.as_slice()[0..8])
+    }
+}
+impl<'r> molecule::prelude::Reader<'r> for GetBlockFilterCheckPointsReader<'r> {
+    type Entity = GetBlockFilterCheckPoints;
+    const NAME: &'static str = "GetBlockFilterCheckPointsReader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        GetBlockFilterCheckPointsReader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], _compatible: bool) -> molecule::error::VerificationResult<()> {
+        use molecule::verification_error as ve;
+        let slice_len = slice.len();
+        if slice_len != Self::TOTAL_SIZE {
+            return ve!(Self, TotalSizeNotMatch, Self::TOTAL_SIZE, slice_len);
+        }
+        Ok(())
+    }
+}
+#[derive(Debug, Default)]
+pub struct GetBlockFilterCheckPointsBuilder {
+    pub(crate) start_number: Uint64,
+}
+impl GetBlockFilterCheckPointsBuilder {
+    pub const TOTAL_SIZE: usize = 8;
+    pub const FIELD_SIZES: [usize; 1] = [8];
+    pub const FIELD_COUNT: usize = 1;
+    pub fn start_number(mut self, v: Uint64) -> Self {
+        self.start_number = v;
+        self
+    }
+}
+impl molecule::prelude::Builder for GetBlockFilterCheckPointsBuilder {
+    type Entity = GetBlockFilterCheckPoints;
+    const NAME: &'static str = "GetBlockFilterCheckPointsBuilder";
+    fn expected_length(&self) -> usize {
+        Self::TOTAL_SIZE
+    }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        writer.write_all(self.start_number.as_slice())?;
+        Ok(())
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        GetBlockFilterCheckPoints::new_unchecked(inner.into())
+    }
+}
+#[derive(Clone)]
+pub struct BlockFilterCheckPoints(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for BlockFilterCheckPoints {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl ::core::fmt::Debug for BlockFilterCheckPoints {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl ::core::fmt::Display for BlockFilterCheckPoints {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "start_number", self.start_number())?;
+        write!(
+            f,
+            ", {}: {}",
+            "block_filter_hashes",
+            self.block_filter_hashes()
+        )?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ", .. ({} fields)", extra_count)?;
+        }
+        write!(f, " }}")
+    }
+}
+impl ::core::default::Default for BlockFilterCheckPoints {
+    fn default() -> Self {
+        let v = molecule::bytes::Bytes::from_static(&Self::DEFAULT_VALUE);
+        BlockFilterCheckPoints::new_unchecked(v)
+    }
+}
+impl BlockFilterCheckPoints {
+    const DEFAULT_VALUE: [u8; 24] = [
+        24, 0, 0, 0, 12, 0, 0, 0, 20, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    ];
+    pub const FIELD_COUNT: usize = 2;
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
+    }
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+    pub fn start_number(&self) -> Uint64 {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[4..]) as usize;
+        let end = molecule::unpack_number(&slice[8..]) as usize;
+        Uint64::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn block_filter_hashes(&self) -> Byte32Vec {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[8..]) as usize;
+        if self.has_extra_fields() {
+            let end = molecule::unpack_number(&slice[12..]) as usize;
+            Byte32Vec::new_unchecked(self.0.slice(start..end))
+        } else {
+            Byte32Vec::new_unchecked(self.0.slice(start..))
+        }
+    }
+    pub fn as_reader<'r>(&'r self) -> BlockFilterCheckPointsReader<'r> {
+        BlockFilterCheckPointsReader::new_unchecked(self.as_slice())
+    }
+}
+impl molecule::prelude::Entity for BlockFilterCheckPoints {
+    type Builder = BlockFilterCheckPointsBuilder;
+    const NAME: &'static str = "BlockFilterCheckPoints";
+    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
+        BlockFilterCheckPoints(data)
+    }
+    fn as_bytes(&self) -> molecule::bytes::Bytes {
+        self.0.clone()
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        BlockFilterCheckPointsReader::from_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        BlockFilterCheckPointsReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn new_builder() -> Self::Builder {
+        ::core::default::Default::default()
+    }
+    fn as_builder(self) -> Self::Builder {
+        Self::new_builder()
+            .start_number(self.start_number())
+            .block_filter_hashes(self.block_filter_hashes())
+    }
+}
+#[derive(Clone, Copy)]
+pub struct BlockFilterCheckPointsReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for BlockFilterCheckPointsReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl<'r> ::core::fmt::Debug for BlockFilterCheckPointsReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl<'r> ::core::fmt::Display for BlockFilterCheckPointsReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "start_number", self.start_number())?;
+        write!(
+            f,
+            ", {}: {}",
+            "block_filter_hashes",
+            self.block_filter_hashes()
+        )?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ", .. ({} fields)", extra_count)?;
+        }
+        write!(f, " }}")
+    }
+}
+impl<'r> BlockFilterCheckPointsReader<'r> {
+    pub const FIELD_COUNT: usize = 2;
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
+    }
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+    pub fn start_number(&self) -> Uint64Reader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[4..]) as usize;
+        let end = molecule::unpack_number(&slice[8..]) as usize;
+        Uint64Reader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn block_filter_hashes(&self) -> Byte32VecReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[8..]) as usize;
+        if self.has_extra_fields() {
+            let end = molecule::unpack_number(&slice[12..]) as usize;
+            Byte32VecReader::new_unchecked(&self.as_slice()[start..end])
+        } else {
+            Byte32VecReader::new_unchecked(&self.as_slice()[start..])
+        }
+    }
+}
+impl<'r> molecule::prelude::Reader<'r> for BlockFilterCheckPointsReader<'r> {
+    type Entity = BlockFilterCheckPoints;
+    const NAME: &'static str = "BlockFilterCheckPointsReader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        BlockFilterCheckPointsReader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], compatible: bool) -> molecule::error::VerificationResult<()> {
+        use molecule::verification_error as ve;
+        let slice_len = slice.len();
+        if slice_len < molecule::NUMBER_SIZE {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE, slice_len);
+        }
+        let total_size = molecule::unpack_number(slice) as usize;
+        if slice_len != total_size {
+            return ve!(Self, TotalSizeNotMatch, total_size, slice_len);
+        }
+        if slice_len < molecule::NUMBER_SIZE * 2 {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE * 2, slice_len);
+        }
+        let offset_first = molecule::unpack_number(&slice[molecule::NUMBER_SIZE..]) as usize;
+        if offset_first % molecule::NUMBER_SIZE != 0 || offset_first < molecule::NUMBER_SIZE * 2 {
+            return ve!(Self, OffsetsNotMatch);
+        }
+        if slice_len < offset_first {
+            return ve!(Self, HeaderIsBroken, offset_first, slice_len);
+        }
+        let field_count = offset_first / molecule::NUMBER_SIZE - 1;
+        if field_count < Self::FIELD_COUNT {
+            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
+        } else if !compatible && field_count > Self::FIELD_COUNT {
+            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
+        };
+        let mut offsets: Vec<usize> = slice[molecule::NUMBER_SIZE..offset_first]
+            .chunks_exact(molecule::NUMBER_SIZE)
+            .map(|x| molecule::unpack_number(x) as usize)
+            .collect();
+        offsets.push(total_size);
+        if offsets.windows(2).any(|i| i[0] > i[1]) {
+            return ve!(Self, OffsetsNotMatch);
+        }
+        Uint64Reader::verify(&slice[offsets[0]..offsets[1]], compatible)?;
+        Byte32VecReader::verify(&slice[offsets[1]..offsets[2]], compatible)?;
+        Ok(())
+    }
+}
+#[derive(Debug, Default)]
+pub struct BlockFilterCheckPointsBuilder {
+    pub(crate) start_number: Uint64,
+    pub(crate) block_filter_hashes: Byte32Vec,
+}
+impl BlockFilterCheckPointsBuilder {
+    pub const FIELD_COUNT: usize = 2;
+    pub fn start_number(mut self, v: Uint64) -> Self {
+        self.start_number = v;
+        self
+    }
+    pub fn block_filter_hashes(mut self, v: Byte32Vec) -> Self {
+        self.block_filter_hashes = v;
+        self
+    }
+}
+impl molecule::prelude::Builder for BlockFilterCheckPointsBuilder {
+    type Entity = BlockFilterCheckPoints;
+    const NAME: &'static str = "BlockFilterCheckPointsBuilder";
+    fn expected_length(&self) -> usize {
+        molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1)
+            + self.start_number.as_slice().len()
+            + self.block_filter_hashes.as_slice().len()
+    }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        let mut total_size = molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1);
+        let mut offsets = Vec::with_capacity(Self::FIELD_COUNT);
+        offsets.push(total_size);
+        total_size += self.start_number.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.block_filter_hashes.as_slice().len();
+        writer.write_all(&molecule::pack_number(total_size as molecule::Number))?;
+        for offset in offsets.into_iter() {
+            writer.write_all(&molecule::pack_number(offset as molecule::Number))?;
+        }
+        writer.write_all(self.start_number.as_slice())?;
+        writer.write_all(self.block_filter_hashes.as_slice())?;
+        Ok(())
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        BlockFilterCheckPoints::new_unchecked(inner.into())
+    }
+}
+#[derive(Clone)]
+pub struct TxReconciliationMessage(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for TxReconciliationMessage {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl ::core::fmt::Debug for TxReconciliationMessage {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl ::core::fmt::Display for TxReconciliationMessage {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}(", Self::NAME)?;
+        self.to_enum().display_inner(f)?;
+        write!(f, ")")
+    }
+}
+impl ::core::default::Default for TxReconciliationMessage {
+    fn default() -> Self {
+        let v = molecule::bytes::Bytes::from_static(&Self::DEFAULT_VALUE);
+        TxReconciliationMessage::new_unchecked(v)
+    }
+}
+impl TxReconciliationMessage {
+    const DEFAULT_VALUE: [u8; 12] = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+    pub const ITEMS_COUNT: usize = 4;
+    pub fn item_id(&self) -> molecule::Number {
+        molecule::unpack_number(self.as_slice())
+    }
+    pub fn to_enum(&self) -> TxReconciliationMessageUnion {
+        let inner = self.0.slice(molecule::NUMBER_SIZE..);
+        match self.item_id() {
+            0 => SendTxReconciliation::new_unchecked(inner).into(),
+            1 => RequestTxReconciliation::new_unchecked(inner).into(),
+            2 => TxReconciliationSketch::new_unchecked(inner).into(),
+            3 => TxReconciliationDifference::new_unchecked(inner).into(),
+            _ => panic!("{}: invalid data", Self::NAME),
+        }
+    }
+    pub fn as_reader<'r>(&'r self) -> TxReconciliationMessageReader<'r> {
+        TxReconciliationMessageReader::new_unchecked(self.as_slice())
+    }
+}
+impl molecule::prelude::Entity for TxReconciliationMessage {
+    type Builder = TxReconciliationMessageBuilder;
+    const NAME: &'static str = "TxReconciliationMessage";
+    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
+        TxReconciliationMessage(data)
+    }
+    fn as_bytes(&self) -> molecule::bytes::Bytes {
+        self.0.clone()
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        TxReconciliationMessageReader::from_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        TxReconciliationMessageReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn new_builder() -> Self::Builder {
+        ::core::default::Default::default()
+    }
+    fn as_builder(self) -> Self::Builder {
+        Self::new_builder().set(self.to_enum())
+    }
+}
+#[derive(Clone, Copy)]
+pub struct TxReconciliationMessageReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for TxReconciliationMessageReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl<'r> ::core::fmt::Debug for TxReconciliationMessageReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl<'r> ::core::fmt::Display for TxReconciliationMessageReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}(", Self::NAME)?;
+        self.to_enum().display_inner(f)?;
+        write!(f, ")")
+    }
+}
+impl<'r> TxReconciliationMessageReader<'r> {
+    pub const ITEMS_COUNT: usize = 4;
+    pub fn item_id(&self) -> molecule::Number {
+        molecule::unpack_number(self.as_slice())
+    }
+    pub fn to_enum(&self) -> TxReconciliationMessageUnionReader<'r> {
+        let inner = &self.as_slice()[molecule::NUMBER_SIZE..];
+        match self.item_id() {
+            0 => SendTxReconciliationReader::new_unchecked(inner).into(),
+            1 => RequestTxReconciliationReader::new_unchecked(inner).into(),
+            2 => TxReconciliationSketchReader::new_unchecked(inner).into(),
+            3 => TxReconciliationDifferenceReader::new_unchecked(inner).into(),
+            _ => panic!("{}: invalid data", Self::NAME),
+        }
+    }
+}
+impl<'r> molecule::prelude::Reader<'r> for TxReconciliationMessageReader<'r> {
+    type Entity = TxReconciliationMessage;
+    const NAME: &'static str = "TxReconciliationMessageReader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        TxReconciliationMessageReader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], compatible: bool) -> molecule::error::VerificationResult<()> {
+        use molecule::verification_error as ve;
+        let slice_len = slice.len();
+        if slice_len < molecule::NUMBER_SIZE {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE, slice_len);
+        }
+        let item_id = molecule::unpack_number(slice);
+        let inner_slice = &slice[molecule::NUMBER_SIZE..];
+        match item_id {
+            0 => SendTxReconciliationReader::verify(inner_slice, compatible),
+            1 => RequestTxReconciliationReader::verify(inner_slice, compatible),
+            2 => TxReconciliationSketchReader::verify(inner_slice, compatible),
+            3 => TxReconciliationDifferenceReader::verify(inner_slice, compatible),
+            _ => ve!(Self, UnknownItem, Self::ITEMS_COUNT, item_id),
+        }?;
+        Ok(())
+    }
+}
+#[derive(Debug, Default)]
+pub struct TxReconciliationMessageBuilder(pub(crate) TxReconciliationMessageUnion);
+impl TxReconciliationMessageBuilder {
+    pub const ITEMS_COUNT: usize = 4;
+    pub fn set<I>(mut self, v: I) -> Self
+    where
+        I: ::core::convert::Into<TxReconciliationMessageUnion>,
+    {
+        self.0 = v.into();
+        self
+    }
+}
+impl molecule::prelude::Builder for TxReconciliationMessageBuilder {
+    type Entity = TxReconciliationMessage;
+    const NAME: &'static str = "TxReconciliationMessageBuilder";
+    fn expected_length(&self) -> usize {
+        molecule::NUMBER_SIZE + self.0.as_slice().len()
+    }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        writer.write_all(&molecule::pack_number(self.0.item_id()))?;
+        writer.write_all(self.0.as_slice())
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        TxReconciliationMessage::new_unchecked(inner.into())
+    }
+}
+#[derive(Debug, Clone)]
+pub enum TxReconciliationMessageUnion {
+    SendTxReconciliation(SendTxReconciliation),
+    RequestTxReconciliation(RequestTxReconciliation),
+    TxReconciliationSketch(TxReconciliationSketch),
+    TxReconciliationDifference(TxReconciliationDifference),
+}
+#[derive(Debug, Clone, Copy)]
+pub enum TxReconciliationMessageUnionReader<'r> {
+    SendTxReconciliation(SendTxReconciliationReader<'r>),
+    RequestTxReconciliation(RequestTxReconciliationReader<'r>),
+    TxReconciliationSketch(TxReconciliationSketchReader<'r>),
+    TxReconciliationDifference(TxReconciliationDifferenceReader<'r>),
+}
+impl ::core::default::Default for TxReconciliationMessageUnion {
+    fn default() -> Self {
+        TxReconciliationMessageUnion::SendTxReconciliation(::core::default::Default::default())
+    }
+}
+impl ::core::fmt::Display for TxReconciliationMessageUnion {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        match self {
+            TxReconciliationMessageUnion::SendTxReconciliation(ref item) => {
+                write!(
+                    f,
+                    "{}::{}({})",
+                    Self::NAME,
+                    SendTxReconciliation::NAME,
+                    item
+                )
+            }
+            TxReconciliationMessageUnion::RequestTxReconciliation(ref item) => {
+                write!(
+                    f,
+                    "{}::{}({})",
+                    Self::NAME,
+                    RequestTxReconciliation::NAME,
+                    item
+                )
+            }
+            TxReconciliationMessageUnion::TxReconciliationSketch(ref item) => {
+                write!(
+                    f,
+                    "{}::{}({})",
+                    Self::NAME,
+                    TxReconciliationSketch::NAME,
+                    item
+                )
+            }
+            TxReconciliationMessageUnion::TxReconciliationDifference(ref item) => {
+                write!(
+                    f,
+                    "{}::{}({})",
+                    Self::NAME,
+                    TxReconciliationDifference::NAME,
+                    item
+                )
+            }
+        }
+    }
+}
+impl<'r> ::core::fmt::Display for TxReconciliationMessageUnionReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        match self {
+            TxReconciliationMessageUnionReader::SendTxReconciliation(ref item) => {
+                write!(
+                    f,
+                    "{}::{}({})",
+                    Self::NAME,
+                    SendTxReconciliation::NAME,
+                    item
+                )
+            }
+            TxReconciliationMessageUnionReader::RequestTxReconciliation(ref item) => {
+                write!(
+                    f,
+                    "{}::{}({})",
+                    Self::NAME,
+                    RequestTxReconciliation::NAME,
+                    item
+                )
+            }
+            TxReconciliationMessageUnionReader::TxReconciliationSketch(ref item) => {
+                write!(
+                    f,
+                    "{}::{}({})",
+                    Self::NAME,
+                    TxReconciliationSketch::NAME,
+                    item
+                )
+            }
+            TxReconciliationMessageUnionReader::TxReconciliationDifference(ref item) => {
+                write!(
+                    f,
+                    "{}::{}({})",
+                    Self::NAME,
+                    TxReconciliationDifference::NAME,
+                    item
+                )
+            }
+        }
+    }
+}
+impl TxReconciliationMessageUnion {
+    pub(crate) fn display_inner(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        match self {
+            TxReconciliationMessageUnion::SendTxReconciliation(ref item) => write!(f, "{}", item),
+            TxReconciliationMessageUnion::RequestTxReconciliation(ref item) => {
+                write!(f, "{}", item)
+            }
+            TxReconciliationMessageUnion::TxReconciliationSketch(ref item) => write!(f, "{}", item),
+            TxReconciliationMessageUnion::TxReconciliationDifference(ref item) => {
+                write!(f, "{}", item)
+            }
+        }
+    }
+}
+impl<'r> TxReconciliationMessageUnionReader<'r> {
+    pub(crate) fn display_inner(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        match self {
+            TxReconciliationMessageUnionReader::SendTxReconciliation(ref item) => {
+                write!(f, "{}", item)
+            }
+            TxReconciliationMessageUnionReader::RequestTxReconciliation(ref item) => {
+                write!(f, "{}", item)
+            }
+            TxReconciliationMessageUnionReader::TxReconciliationSketch(ref item) => {
+                write!(f, "{}", item)
+            }
+            TxReconciliationMessageUnionReader::TxReconciliationDifference(ref item) => {
+                write!(f, "{}", item)
+            }
+        }
+    }
+}
+impl ::core::convert::From<SendTxReconciliation> for TxReconciliationMessageUnion {
+    fn from(item: SendTxReconciliation) -> Self {
+        TxReconciliationMessageUnion::SendTxReconciliation(item)
+    }
+}
+impl ::core::convert::From<RequestTxReconciliation> for TxReconciliationMessageUnion {
+    fn from(item: RequestTxReconciliation) -> Self {
+        TxReconciliationMessageUnion::RequestTxReconciliation(item)
+    }
+}
+impl ::core::convert::From<TxReconciliationSketch> for TxReconciliationMessageUnion {
+    fn from(item: TxReconciliationSketch) -> Self {
+        TxReconciliationMessageUnion::TxReconciliationSketch(item)
+    }
+}
+impl ::core::convert::From<TxReconciliationDifference> for TxReconciliationMessageUnion {
+    fn from(item: TxReconciliationDifference) -> Self {
+        TxReconciliationMessageUnion::TxReconciliationDifference(item)
+    }
+}
+impl<'r> ::core::convert::From<SendTxReconciliationReader<'r>>
+    for TxReconciliationMessageUnionReader<'r>
+{
+    fn from(item: SendTxReconciliationReader<'r>) -> Self {
+        TxReconciliationMessageUnionReader::SendTxReconciliation(item)
+    }
+}
+impl<'r> ::core::convert::From<RequestTxReconciliationReader<'r>>
+    for TxReconciliationMessageUnionReader<'r>
+{
+    fn from(item: RequestTxReconciliationReader<'r>) -> Self {
+        TxReconciliationMessageUnionReader::RequestTxReconciliation(item)
+    }
+}
+impl<'r> ::core::convert::From<TxReconciliationSketchReader<'r>>
+    for TxReconciliationMessageUnionReader<'r>
+{
+    fn from(item: TxReconciliationSketchReader<'r>) -> Self {
+        TxReconciliationMessageUnionReader::TxReconciliationSketch(item)
+    }
+}
+impl<'r> ::core::convert::From<TxReconciliationDifferenceReader<'r>>
+    for TxReconciliationMessageUnionReader<'r>
+{
+    fn from(item: TxReconciliationDifferenceReader<'r>) -> Self {
+        TxReconciliationMessageUnionReader::TxReconciliationDifference(item)
+    }
+}
+impl TxReconciliationMessageUnion {
+    pub const NAME: &'static str = "TxReconciliationMessageUnion";
+    pub fn as_bytes(&self) -> molecule::bytes::Bytes {
+        match self {
+            TxReconciliationMessageUnion::SendTxReconciliation(item) => item.as_bytes(),
+            TxReconciliationMessageUnion::RequestTxReconciliation(item) => item.as_bytes(),
+            TxReconciliationMessageUnion::TxReconciliationSketch(item) => item.as_bytes(),
+            TxReconciliationMessageUnion::TxReconciliationDifference(item) => item.as_bytes(),
+        }
+    }
+    pub fn as_slice(&self) -> &[u8] {
+        match self {
+            TxReconciliationMessageUnion::SendTxReconciliation(item) => item.as_slice(),
+            TxReconciliationMessageUnion::RequestTxReconciliation(item) => item.as_slice(),
+            TxReconciliationMessageUnion::TxReconciliationSketch(item) => item.as_slice(),
+            TxReconciliationMessageUnion::TxReconciliationDifference(item) => item.as_slice(),
+        }
+    }
+    pub fn item_id(&self) -> molecule::Number {
+        match self {
+            TxReconciliationMessageUnion::SendTxReconciliation(_) => 0,
+            TxReconciliationMessageUnion::RequestTxReconciliation(_) => 1,
+            TxReconciliationMessageUnion::TxReconciliationSketch(_) => 2,
+            TxReconciliationMessageUnion::TxReconciliationDifference(_) => 3,
+        }
+    }
+    pub fn item_name(&self) -> &str {
+        match self {
+            TxReconciliationMessageUnion::SendTxReconciliation(_) => "SendTxReconciliation",
+            TxReconciliationMessageUnion::RequestTxReconciliation(_) => "RequestTxReconciliation",
+            TxReconciliationMessageUnion::TxReconciliationSketch(_) => "TxReconciliationSketch",
+            TxReconciliationMessageUnion::TxReconciliationDifference(_) => {
+                "TxReconciliationDifference"
+            }
+        }
+    }
+    pub fn as_reader<'r>(&'r self) -> TxReconciliationMessageUnionReader<'r> {
+        match self {
+            TxReconciliationMessageUnion::SendTxReconciliation(item) => item.as_reader().into(),
+            TxReconciliationMessageUnion::RequestTxReconciliation(item) => item.as_reader().into(),
+            TxReconciliationMessageUnion::TxReconciliationSketch(item) => item.as_reader().into(),
+            TxReconciliationMessageUnion::TxReconciliationDifference(item) => {
+                item.as_reader().into()
+            }
+        }
+    }
+}
+impl<'r> TxReconciliationMessageUnionReader<'r> {
+    pub const NAME: &'r str = "TxReconciliationMessageUnionReader";
+    pub fn as_slice(&self) -> &'r [u8] {
+        match self {
+            TxReconciliationMessageUnionReader::SendTxReconciliation(item) => item.as_slice(),
+            TxReconciliationMessageUnionReader::RequestTxReconciliation(item) => item.as_slice(),
+            TxReconciliationMessageUnionReader::TxReconciliationSketch(item) => item.as_slice(),
+            TxReconciliationMessageUnionReader::TxReconciliationDifference(item) => item.as_slice(),
+        }
+    }
+    pub fn item_id(&self) -> molecule::Number {
+        match self {
+            TxReconciliationMessageUnionReader::SendTxReconciliation(_) => 0,
+            TxReconciliationMessageUnionReader::RequestTxReconciliation(_) => 1,
+            TxReconciliationMessageUnionReader::TxReconciliationSketch(_) => 2,
+            TxReconciliationMessageUnionReader::TxReconciliationDifference(_) => 3,
         }
-        writer.write_all(self.start_number.as_slice())?;
-        writer.write_all(self.block_hashes.as_slice())?;
-        writer.write_all(self.filters.as_slice())?;
-        Ok(())
     }
-    fn build(&self) -> Self::Entity {
-        let mut inner = Vec::with_capacity(self.expected_length());
-        self.write(&mut inner)
-            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
-        BlockFilters::new_unchecked(inner.into())
+    pub fn item_name(&self) -> &str {
+        match self {
+            TxReconciliationMessageUnionReader::SendTxReconciliation(_) => "SendTxReconciliation",
+            TxReconciliationMessageUnionReader::RequestTxReconciliation(_) => {
+                "RequestTxReconciliation"
+            }
+            TxReconciliationMessageUnionReader::TxReconciliationSketch(_) => {
+                "TxReconciliationSketch"
+            }
+            TxReconciliationMessageUnionReader::TxReconciliationDifference(_) => {
+                "TxReconciliationDifference"
+            }
+        }
     }
 }
 #[derive(Clone)]
-pub struct GetBlockFilterHashes(molecule::bytes::Bytes);
-impl ::core::fmt::LowerHex for GetBlockFilterHashes {
+pub struct SendTxReconciliation(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for SendTxReconciliation {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         use molecule::hex_string;
         if f.alternate() {
@@ -11021,41 +13446,41 @@ impl ::core::fmt::LowerHex for GetBlockFilterHashes {
         write!(f, "{}", hex_string(self.as_slice()))
     }
 }
-impl ::core::fmt::Debug for GetBlockFilterHashes {
+impl ::core::fmt::Debug for SendTxReconciliation {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{}({:#x})", Self::NAME, self)
     }
 }
-impl ::core::fmt::Display for GetBlockFilterHashes {
+impl ::core::fmt::Display for SendTxReconciliation {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{} {{ ", Self::NAME)?;
-        write!(f, "{}: {}", "start_number", self.start_number())?;
+        write!(f, "{}: {}", "salt", self.salt())?;
         write!(f, " }}")
     }
 }
-impl ::core::default::Default for GetBlockFilterHashes {
+impl ::core::default::Default for SendTxReconciliation {
     fn default() -> Self {
         let v = molecule::bytes::Bytes::from_static(&Self::DEFAULT_VALUE);
-        GetBlockFilterHashes::new_unchecked(v)
+        SendTxReconciliation::new_unchecked(v)
     }
 }
-impl GetBlockFilterHashes {
+impl SendTxReconciliation {
     const DEFAULT_VALUE: [u8; 8] = [0, 0, 0, 0, 0, 0, 0, 0];
     pub const TOTAL_SIZE: usize = 8;
     pub const FIELD_SIZES: [usize; 1] = [8];
     pub const FIELD_COUNT: usize = 1;
-    pub fn start_number(&self) -> Uint64 {
+    pub fn salt(&self) -> Uint64 {
         Uint64::new_unchecked(self.0.slice(0..8))
     }
-    pub fn as_reader<'r>(&'r self) -> GetBlockFilterHashesReader<'r> {
-        GetBlockFilterHashesReader::new_unchecked(self.as_slice())
+    pub fn as_reader<'r>(&'r self) -> SendTxReconciliationReader<'r> {
+        SendTxReconciliationReader::new_unchecked(self.as_slice())
     }
 }
-impl molecule::prelude::Entity for GetBlockFilterHashes {
-    type Builder = GetBlockFilterHashesBuilder;
-    const NAME: &'static str = "GetBlockFilterHashes";
+impl molecule::prelude::Entity for SendTxReconciliation {
+    type Builder = SendTxReconciliationBuilder;
+    const NAME: &'static str = "SendTxReconciliation";
     fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
-        GetBlockFilterHashes(data)
+        SendTxReconciliation(data)
     }
     fn as_bytes(&self) -> molecule::bytes::Bytes {
         self.0.clone()
@@ -11064,21 +13489,21 @@ impl molecule::prelude::Entity for GetBlockFilterHashes {
         &self.0[..]
     }
     fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
-        GetBlockFilterHashesReader::from_slice(slice).map(|reader| reader.to_entity())
+        SendTxReconciliationReader::from_slice(slice).map(|reader| reader.to_entity())
     }
     fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
-        GetBlockFilterHashesReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+        SendTxReconciliationReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
     }
     fn new_builder() -> Self::Builder {
         ::core::default::Default::default()
     }
     fn as_builder(self) -> Self::Builder {
-        Self::new_builder().start_number(self.start_number())
+        Self::new_builder().salt(self.salt())
     }
 }
 #[derive(Clone, Copy)]
-pub struct GetBlockFilterHashesReader<'r>(&'r [u8]);
-impl<'r> ::core::fmt::LowerHex for GetBlockFilterHashesReader<'r> {
+pub struct SendTxReconciliationReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for SendTxReconciliationReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         use molecule::hex_string;
         if f.alternate() {
@@ -11087,34 +13512,34 @@ impl<'r> ::core::fmt::LowerHex for GetBlockFilterHashesReader<'r> {
         write!(f, "{}", hex_string(self.as_slice()))
     }
 }
-impl<'r> ::core::fmt::Debug for GetBlockFilterHashesReader<'r> {
+impl<'r> ::core::fmt::Debug for SendTxReconciliationReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{}({:#x})", Self::NAME, self)
     }
 }
-impl<'r> ::core::fmt::Display for GetBlockFilterHashesReader<'r> {
+impl<'r> ::core::fmt::Display for SendTxReconciliationReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{} {{ ", Self::NAME)?;
-        write!(f, "{}: {}", "start_number", self.start_number())?;
+        write!(f, "{}: {}", "salt", self.salt())?;
         write!(f, " }}")
     }
 }
-impl<'r> GetBlockFilterHashesReader<'r> {
+impl<'r> SendTxReconciliationReader<'r> {
     pub const TOTAL_SIZE: usize = 8;
     pub const FIELD_SIZES: [usize; 1] = [8];
     pub const FIELD_COUNT: usize = 1;
-    pub fn start_number(&self) -> Uint64Reader<'r> {
+    pub fn salt(&self) -> Uint64Reader<'r> {
         Uint64Reader::new_unchecked(&self.as_slice()[0..8])
     }
 }
-impl<'r> molecule::prelude::Reader<'r> for GetBlockFilterHashesReader<'r> {
-    type Entity = GetBlockFilterHashes;
-    const NAME: &'static str = "GetBlockFilterHashesReader";
+impl<'r> molecule::prelude::Reader<'r> for SendTxReconciliationReader<'r> {
+    type Entity = SendTxReconciliation;
+    const NAME: &'static str = "SendTxReconciliationReader";
     fn to_entity(&self) -> Self::Entity {
         Self::Entity::new_unchecked(self.as_slice().to_owned().into())
     }
     fn new_unchecked(slice: &'r [u8]) -> Self {
-        GetBlockFilterHashesReader(slice)
+        SendTxReconciliationReader(slice)
     }
     fn as_slice(&self) -> &'r [u8] {
         self.0
@@ -11129,38 +13554,38 @@ impl<'r> molecule::prelude::Reader<'r> for GetBlockFilterHashesReader<'r> {
     }
 }
 #[derive(Debug, Default)]
-pub struct GetBlockFilterHashesBuilder {
-    pub(crate) start_number: Uint64,
+pub struct SendTxReconciliationBuilder {
+    pub(crate) salt: Uint64,
 }
-impl GetBlockFilterHashesBuilder {
+impl SendTxReconciliationBuilder {
     pub const TOTAL_SIZE: usize = 8;
     pub const FIELD_SIZES: [usize; 1] = [8];
     pub const FIELD_COUNT: usize = 1;
-    pub fn start_number(mut self, v: Uint64) -> Self {
-        self.start_number = v;
+    pub fn salt(mut self, v: Uint64) -> Self {
+        self.salt = v;
         self
     }
 }
-impl molecule::prelude::Builder for GetBlockFilterHashesBuilder {
-    type Entity = GetBlockFilterHashes;
-    const NAME: &'static str = "GetBlockFilterHashesBuilder";
+impl molecule::prelude::Builder for SendTxReconciliationBuilder {
+    type Entity = SendTxReconciliation;
+    const NAME: &'static str = "SendTxReconciliationBuilder";
     fn expected_length(&self) -> usize {
         Self::TOTAL_SIZE
     }
     fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
-        writer.write_all(self.start_number.as_slice())?;
+        writer.write_all(self.salt.as_slice())?;
         Ok(())
     }
     fn build(&self) -> Self::Entity {
         let mut inner = Vec::with_capacity(self.expected_length());
         self.write(&mut inner)
             .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
-        GetBlockFilterHashes::new_unchecked(inner.into())
+        SendTxReconciliation::new_unchecked(inner.into())
     }
 }
 #[derive(Clone)]
-pub struct BlockFilterHashes(molecule::bytes::Bytes);
-impl ::core::fmt::LowerHex for BlockFilterHashes {
+pub struct RequestTxReconciliation(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for RequestTxReconciliation {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         use molecule::hex_string;
         if f.alternate() {
@@ -11169,94 +13594,41 @@ impl ::core::fmt::LowerHex for BlockFilterHashes {
         write!(f, "{}", hex_string(self.as_slice()))
     }
 }
-impl ::core::fmt::Debug for BlockFilterHashes {
+impl ::core::fmt::Debug for RequestTxReconciliation {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{}({:#x})", Self::NAME, self)
     }
 }
-impl ::core::fmt::Display for BlockFilterHashes {
+impl ::core::fmt::Display for RequestTxReconciliation {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{} {{ ", Self::NAME)?;
-        write!(f, "{}: {}", "start_number", self.start_number())?;
-        write!(
-            f,
-            ", {}: {}",
-            "parent_block_filter_hash",
-            self.parent_block_filter_hash()
-        )?;
-        write!(
-            f,
-            ", {}: {}",
-            "block_filter_hashes",
-            self.block_filter_hashes()
-        )?;
-        let extra_count = self.count_extra_fields();
-        if extra_count != 0 {
-            write!(f, ", .. ({} fields)", extra_count)?;
-        }
+        write!(f, "{}: {}", "set_size", self.set_size())?;
         write!(f, " }}")
     }
 }
-impl ::core::default::Default for BlockFilterHashes {
+impl ::core::default::Default for RequestTxReconciliation {
     fn default() -> Self {
         let v = molecule::bytes::Bytes::from_static(&Self::DEFAULT_VALUE);
-        BlockFilterHashes::new_unchecked(v)
+        RequestTxReconciliation::new_unchecked(v)
     }
 }
-impl BlockFilterHashes {
-    const DEFAULT_VALUE: [u8; 60] = [
-        60, 0, 0, 0, 16, 0, 0, 0, 24, 0, 0, 0, 56, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-        0,
-    ];
-    pub const FIELD_COUNT: usize = 3;
-    pub fn total_size(&self) -> usize {
-        molecule::unpack_number(self.as_slice()) as usize
-    }
-    pub fn field_count(&self) -> usize {
-        if self.total_size() == molecule::NUMBER_SIZE {
-            0
-        } else {
-            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
-        }
-    }
-    pub fn count_extra_fields(&self) -> usize {
-        self.field_count() - Self::FIELD_COUNT
-    }
-    pub fn has_extra_fields(&self) -> bool {
-        Self::FIELD_COUNT != self.field_count()
-    }
-    pub fn start_number(&self) -> Uint64 {
-        let slice = self.as_slice();
-        let start = molecule::unpack_number(&slice[4..]) as usize;
-        let end = molecule::unpack_number(&slice[8..]) as usize;
-        Uint64::new_unchecked(self.0.slice(start..end))
-    }
-    pub fn parent_block_filter_hash(&self) -> Byte32 {
-        let slice = self.as_slice();
-        let start = molecule::unpack_number(&slice[8..]) as usize;
-        let end = molecule::unpack_number(&slice[12..]) as usize;
-        Byte32::new_unchecked(self.0.slice(start..end))
-    }
-    pub fn block_filter_hashes(&self) -> Byte32Vec {
-        let slice = self.as_slice();
-        let start = molecule::unpack_number(&slice[12..]) as usize;
-        if self.has_extra_fields() {
-            let end = molecule::unpack_number(&slice[16..]) as usize;
-            Byte32Vec::new_unchecked(self.0.slice(start..end))
-        } else {
-            Byte32Vec::new_unchecked(self.0.slice(start..))
-        }
+impl RequestTxReconciliation {
+    const DEFAULT_VALUE: [u8; 4] = [0, 0, 0, 0];
+    pub const TOTAL_SIZE: usize = 4;
+    pub const FIELD_SIZES: [usize; 1] = [4];
+    pub const FIELD_COUNT: usize = 1;
+    pub fn set_size(&self) -> Uint32 {
+        Uint32::new_unchecked(self.0.slice(0..4))
     }
-    pub fn as_reader<'r>(&'r self) -> BlockFilterHashesReader<'r> {
-        BlockFilterHashesReader::new_unchecked(self.as_slice())
+    pub fn as_reader<'r>(&'r self) -> RequestTxReconciliationReader<'r> {
+        RequestTxReconciliationReader::new_unchecked(self.as_slice())
     }
 }
-impl molecule::prelude::Entity for BlockFilterHashes {
-    type Builder = BlockFilterHashesBuilder;
-    const NAME: &'static str = "BlockFilterHashes";
+impl molecule::prelude::Entity for RequestTxReconciliation {
+    type Builder = RequestTxReconciliationBuilder;
+    const NAME: &'static str = "RequestTxReconciliation";
     fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
-        BlockFilterHashes(data)
+        RequestTxReconciliation(data)
     }
     fn as_bytes(&self) -> molecule::bytes::Bytes {
         self.0.clone()
@@ -11265,24 +13637,21 @@ impl molecule::prelude::Entity for BlockFilterHashes {
         &self.0[..]
     }
     fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
-        BlockFilterHashesReader::from_slice(slice).map(|reader| reader.to_entity())
+        RequestTxReconciliationReader::from_slice(slice).map(|reader| reader.to_entity())
     }
     fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
-        BlockFilterHashesReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+        RequestTxReconciliationReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
     }
     fn new_builder() -> Self::Builder {
         ::core::default::Default::default()
     }
     fn as_builder(self) -> Self::Builder {
-        Self::new_builder()
-            .start_number(self.start_number())
-            .parent_block_filter_hash(self.parent_block_filter_hash())
-            .block_filter_hashes(self.block_filter_hashes())
+        Self::new_builder().set_size(self.set_size())
     }
 }
 #[derive(Clone, Copy)]
-pub struct BlockFilterHashesReader<'r>(&'r [u8]);
-impl<'r> ::core::fmt::LowerHex for BlockFilterHashesReader<'r> {
+pub struct RequestTxReconciliationReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for RequestTxReconciliationReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         use molecule::hex_string;
         if f.alternate() {
@@ -11291,185 +13660,80 @@ impl<'r> ::core::fmt::LowerHex for BlockFilterHashesReader<'r> {
         write!(f, "{}", hex_string(self.as_slice()))
     }
 }
-impl<'r> ::core::fmt::Debug for BlockFilterHashesReader<'r> {
+impl<'r> ::core::fmt::Debug for RequestTxReconciliationReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{}({:#x})", Self::NAME, self)
     }
 }
-impl<'r> ::core::fmt::Display for BlockFilterHashesReader<'r> {
+impl<'r> ::core::fmt::Display for RequestTxReconciliationReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{} {{ ", Self::NAME)?;
-        write!(f, "{}: {}", "start_number", self.start_number())?;
-        write!(
-            f,
-            ", {}: {}",
-            "parent_block_filter_hash",
-            self.parent_block_filter_hash()
-        )?;
-        write!(
-            f,
-            ", {}: {}",
-            "block_filter_hashes",
-            self.block_filter_hashes()
-        )?;
-        let extra_count = self.count_extra_fields();
-        if extra_count != 0 {
-            write!(f, ", .. ({} fields)", extra_count)?;
-        }
+        write!(f, "{}: {}", "set_size", self.set_size())?;
         write!(f, " }}")
     }
 }
-impl<'r> BlockFilterHashesReader<'r> {
-    pub const FIELD_COUNT: usize = 3;
-    pub fn total_size(&self) -> usize {
-        molecule::unpack_number(self.as_slice()) as usize
-    }
-    pub fn field_count(&self) -> usize {
-        if self.total_size() == molecule::NUMBER_SIZE {
-            0
-        } else {
-            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
-        }
-    }
-    pub fn count_extra_fields(&self) -> usize {
-        self.field_count() - Self::FIELD_COUNT
-    }
-    pub fn has_extra_fields(&self) -> bool {
-        Self::FIELD_COUNT != self.field_count()
-    }
-    pub fn start_number(&self) -> Uint64Reader<'r> {
-        let slice = self.as_slice();
-        let start = molecule::unpack_number(&slice[4..]) as usize;
-        let end = molecule::unpack_number(&slice[8..]) as usize;
-        Uint64Reader::new_unchecked(&self.as_slice()[start..end])
-    }
-    pub fn parent_block_filter_hash(&self) -> Byte32Reader<'r> {
-        let slice = self.as_slice();
-        let start = molecule::unpack_number(&slice[8..]) as usize;
-        let end = molecule::unpack_number(&slice[12..]) as usize;
-        Byte32Reader::new_unchecked(&self.as_slice()[start..end])
-    }
-    pub fn block_filter_hashes(&self) -> Byte32VecReader<'r> {
-        let slice = self.as_slice();
-        let start = molecule::unpack_number(&slice[12..]) as usize;
-        if self.has_extra_fields() {
-            let end = molecule::unpack_number(&slice[16..]) as usize;
-            Byte32VecReader::new_unchecked(&self.as_slice()[start..end])
-        } else {
-            Byte32VecReader::new_unchecked(&self.as_slice()[start..])
-        }
+impl<'r> RequestTxReconciliationReader<'r> {
+    pub const TOTAL_SIZE: usize = 4;
+    pub const FIELD_SIZES: [usize; 1] = [4];
+    pub const FIELD_COUNT: usize = 1;
+    pub fn set_size(&self) -> Uint32Reader<'r> {
+        Uint32Reader::new_unchecked(&self.as_slice()[0..4])
     }
 }
-impl<'r> molecule::prelude::Reader<'r> for BlockFilterHashesReader<'r> {
-    type Entity = BlockFilterHashes;
-    const NAME: &'static str = "BlockFilterHashesReader";
+impl<'r> molecule::prelude::Reader<'r> for RequestTxReconciliationReader<'r> {
+    type Entity = RequestTxReconciliation;
+    const NAME: &'static str = "RequestTxReconciliationReader";
     fn to_entity(&self) -> Self::Entity {
         Self::Entity::new_unchecked(self.as_slice().to_owned().into())
     }
     fn new_unchecked(slice: &'r [u8]) -> Self {
-        BlockFilterHashesReader(slice)
+        RequestTxReconciliationReader(slice)
     }
     fn as_slice(&self) -> &'r [u8] {
         self.0
     }
-    fn verify(slice: &[u8], compatible: bool) -> molecule::error::VerificationResult<()> {
+    fn verify(slice: &[u8], _compatible: bool) -> molecule::error::VerificationResult<()> {
         use molecule::verification_error as ve;
         let slice_len = slice.len();
-        if slice_len < molecule::NUMBER_SIZE {
-            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE, slice_len);
-        }
-        let total_size = molecule::unpack_number(slice) as usize;
-        if slice_len != total_size {
-            return ve!(Self, TotalSizeNotMatch, total_size, slice_len);
-        }
-        if slice_len < molecule::NUMBER_SIZE * 2 {
-            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE * 2, slice_len);
-        }
-        let offset_first = molecule::unpack_number(&slice[molecule::NUMBER_SIZE..]) as usize;
-        if offset_first % molecule::NUMBER_SIZE != 0 || offset_first < molecule::NUMBER_SIZE * 2 {
-            return ve!(Self, OffsetsNotMatch);
-        }
-        if slice_len < offset_first {
-            return ve!(Self, HeaderIsBroken, offset_first, slice_len);
-        }
-        let field_count = offset_first / molecule::NUMBER_SIZE - 1;
-        if field_count < Self::FIELD_COUNT {
-            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
-        } else if !compatible && field_count > Self::FIELD_COUNT {
-            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
-        };
-        let mut offsets: Vec<usize> = slice[molecule::NUMBER_SIZE..offset_first]
-            .chunks_exact(molecule::NUMBER_SIZE)
-            .map(|x| molecule::unpack_number(x) as usize)
-            .collect();
-        offsets.push(total_size);
-        if offsets.windows(2).any(|i| i[0] > i[1]) {
-            return ve!(Self, OffsetsNotMatch);
+        if slice_len != Self::TOTAL_SIZE {
+            return ve!(Self, TotalSizeNotMatch, Self::TOTAL_SIZE, slice_len);
         }
-        Uint64Reader::verify(&slice[offsets[0]..offsets[1]], compatible)?;
-        Byte32Reader::verify(&slice[offsets[1]..offsets[2]], compatible)?;
-        Byte32VecReader::verify(&slice[offsets[2]..offsets[3]], compatible)?;
         Ok(())
     }
 }
 #[derive(Debug, Default)]
-pub struct BlockFilterHashesBuilder {
-    pub(crate) start_number: Uint64,
-    pub(crate) parent_block_filter_hash: Byte32,
-    pub(crate) block_filter_hashes: Byte32Vec,
-}
-impl BlockFilterHashesBuilder {
-    pub const FIELD_COUNT: usize = 3;
-    pub fn start_number(mut self, v: Uint64) -> Self {
-        self.start_number = v;
-        self
-    }
-    pub fn parent_block_filter_hash(mut self, v: Byte32) -> Self {
-        self.parent_block_filter_hash = v;
-        self
-    }
-    pub fn block_filter_hashes(mut self, v: Byte32Vec) -> Self {
-        self.block_filter_hashes = v;
+pub struct RequestTxReconciliationBuilder {
+    pub(crate) set_size: Uint32,
+}
+impl RequestTxReconciliationBuilder {
+    pub const TOTAL_SIZE: usize = 4;
+    pub const FIELD_SIZES: [usize; 1] = [4];
+    pub const FIELD_COUNT: usize = 1;
+    pub fn set_size(mut self, v: Uint32) -> Self {
+        self.set_size = v;
         self
     }
 }
-impl molecule::prelude::Builder for BlockFilterHashesBuilder {
-    type Entity = BlockFilterHashes;
-    const NAME: &'static str = "BlockFilterHashesBuilder";
+impl molecule::prelude::Builder for RequestTxReconciliationBuilder {
+    type Entity = RequestTxReconciliation;
+    const NAME: &'static str = "RequestTxReconciliationBuilder";
     fn expected_length(&self) -> usize {
-        molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1)
-            + self.start_number.as_slice().len()
-            + self.parent_block_filter_hash.as_slice().len()
-            + self.block_filter_hashes.as_slice().len()
+        Self::TOTAL_SIZE
     }
     fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
-        let mut total_size = molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1);
-        let mut offsets = Vec::with_capacity(Self::FIELD_COUNT);
-        offsets.push(total_size);
-        total_size += self.start_number.as_slice().len();
-        offsets.push(total_size);
-        total_size += self.parent_block_filter_hash.as_slice().len();
-        offsets.push(total_size);
-        total_size += self.block_filter_hashes.as_slice().len();
-        writer.write_all(&molecule::pack_number(total_size as molecule::Number))?;
-        for offset in offsets.into_iter() {
-            writer.write_all(&molecule::pack_number(offset as molecule::Number))?;
-        }
-        writer.write_all(self.start_number.as_slice())?;
-        writer.write_all(self.parent_block_filter_hash.as_slice())?;
-        writer.write_all(self.block_filter_hashes.as_slice())?;
+        writer.write_all(self.set_size.as_slice())?;
         Ok(())
     }
     fn build(&self) -> Self::Entity {
         let mut inner = Vec::with_capacity(self.expected_length());
         self.write(&mut inner)
             .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
-        BlockFilterHashes::new_unchecked(inner.into())
+        RequestTxReconciliation::new_unchecked(inner.into())
     }
 }
 #[derive(Clone)]
-pub struct GetBlockFilterCheckPoints(molecule::bytes::Bytes);
-impl ::core::fmt::LowerHex for GetBlockFilterCheckPoints {
+pub struct TxReconciliationSketch(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for TxReconciliationSketch {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         use molecule::hex_string;
         if f.alternate() {
@@ -11478,41 +13742,66 @@ impl ::core::fmt::LowerHex for GetBlockFilterCheckPoints {
         write!(f, "{}", hex_string(self.as_slice()))
     }
 }
-impl ::core::fmt::Debug for GetBlockFilterCheckPoints {
+impl ::core::fmt::Debug for TxReconciliationSketch {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{}({:#x})", Self::NAME, self)
     }
 }
-impl ::core::fmt::Display for GetBlockFilterCheckPoints {
+impl ::core::fmt::Display for TxReconciliationSketch {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{} {{ ", Self::NAME)?;
-        write!(f, "{}: {}", "start_number", self.start_number())?;
+        write!(f, "{}: {}", "sketch", self.sketch())?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ", .. ({} fields)", extra_count)?;
+        }
         write!(f, " }}")
     }
 }
-impl ::core::default::Default for GetBlockFilterCheckPoints {
+impl ::core::default::Default for TxReconciliationSketch {
     fn default() -> Self {
         let v = molecule::bytes::Bytes::from_static(&Self::DEFAULT_VALUE);
-        GetBlockFilterCheckPoints::new_unchecked(v)
+        TxReconciliationSketch::new_unchecked(v)
     }
 }
-impl GetBlockFilterCheckPoints {
-    const DEFAULT_VALUE: [u8; 8] = [0, 0, 0, 0, 0, 0, 0, 0];
-    pub const TOTAL_SIZE: usize = 8;
-    pub const FIELD_SIZES: [usize; 1] = [8];
+impl TxReconciliationSketch {
+    const DEFAULT_VALUE: [u8; 12] = [12, 0, 0, 0, 8, 0, 0, 0, 0, 0, 0, 0];
     pub const FIELD_COUNT: usize = 1;
-    pub fn start_number(&self) -> Uint64 {
-        Uint64::new_unchecked(self.0.slice(0..8))
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
     }
-    pub fn as_reader<'r>(&'r self) -> GetBlockFilterCheckPointsReader<'r> {
-        GetBlockFilterCheckPointsReader::new_unchecked(self.as_slice())
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
+    }
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+    pub fn sketch(&self) -> Bytes {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[4..]) as usize;
+        if self.has_extra_fields() {
+            let end = molecule::unpack_number(&slice[8..]) as usize;
+            Bytes::new_unchecked(self.0.slice(start..end))
+        } else {
+            Bytes::new_unchecked(self.0.slice(start..))
+        }
+    }
+    pub fn as_reader<'r>(&'r self) -> TxReconciliationSketchReader<'r> {
+        TxReconciliationSketchReader::new_unchecked(self.as_slice())
     }
 }
-impl molecule::prelude::Entity for GetBlockFilterCheckPoints {
-    type Builder = GetBlockFilterCheckPointsBuilder;
-    const NAME: &'static str = "GetBlockFilterCheckPoints";
+impl molecule::prelude::Entity for TxReconciliationSketch {
+    type Builder = TxReconciliationSketchBuilder;
+    const NAME: &'static str = "TxReconciliationSketch";
     fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
-        GetBlockFilterCheckPoints(data)
+        TxReconciliationSketch(data)
     }
     fn as_bytes(&self) -> molecule::bytes::Bytes {
         self.0.clone()
@@ -11521,22 +13810,21 @@ impl molecule::prelude::Entity for GetBlockFilterCheckPoints {
         &self.0[..]
     }
     fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
-        GetBlockFilterCheckPointsReader::from_slice(slice).map(|reader| reader.to_entity())
+        TxReconciliationSketchReader::from_slice(slice).map(|reader| reader.to_entity())
     }
     fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
-        GetBlockFilterCheckPointsReader::from_compatible_slice(slice)
-            .map(|reader| reader.to_entity())
+        TxReconciliationSketchReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
     }
     fn new_builder() -> Self::Builder {
         ::core::default::Default::default()
     }
     fn as_builder(self) -> Self::Builder {
-        Self::new_builder().start_number(self.start_number())
+        Self::new_builder().sketch(self.sketch())
     }
 }
 #[derive(Clone, Copy)]
-pub struct GetBlockFilterCheckPointsReader<'r>(&'r [u8]);
-impl<'r> ::core::fmt::LowerHex for GetBlockFilterCheckPointsReader<'r> {
+pub struct TxReconciliationSketchReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for TxReconciliationSketchReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         use molecule::hex_string;
         if f.alternate() {
@@ -11545,80 +13833,140 @@ impl<'r> ::core::fmt::LowerHex for GetBlockFilterCheckPointsReader<'r> {
         write!(f, "{}", hex_string(self.as_slice()))
     }
 }
-impl<'r> ::core::fmt::Debug for GetBlockFilterCheckPointsReader<'r> {
+impl<'r> ::core::fmt::Debug for TxReconciliationSketchReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{}({:#x})", Self::NAME, self)
     }
 }
-impl<'r> ::core::fmt::Display for GetBlockFilterCheckPointsReader<'r> {
+impl<'r> ::core::fmt::Display for TxReconciliationSketchReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{} {{ ", Self::NAME)?;
-        write!(f, "{}: {}", "start_number", self.start_number())?;
+        write!(f, "{}: {}", "sketch", self.sketch())?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ", .. ({} fields)", extra_count)?;
+        }
         write!(f, " }}")
     }
 }
-impl<'r> GetBlockFilterCheckPointsReader<'r> {
-    pub const TOTAL_SIZE: usize = 8;
-    pub const FIELD_SIZES: [usize; 1] = [8];
+impl<'r> TxReconciliationSketchReader<'r> {
     pub const FIELD_COUNT: usize = 1;
-    pub fn start_number(&self) -> Uint64Reader<'r> {
-        Uint64Reader::new_unchecked(&self.as_slice()[0..8])
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
+    }
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+    pub fn sketch(&self) -> BytesReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[4..]) as usize;
+        if self.has_extra_fields() {
+            let end = molecule::unpack_number(&slice[8..]) as usize;
+            BytesReader::new_unchecked(&self.as_slice()[start..end])
+        } else {
+            BytesReader::new_unchecked(&self.as_slice()[start..])
+        }
     }
 }
-impl<'r> molecule::prelude::Reader<'r> for GetBlockFilterCheckPointsReader<'r> {
-    type Entity = GetBlockFilterCheckPoints;
-    const NAME: &'static str = "GetBlockFilterCheckPointsReader";
+impl<'r> molecule::prelude::Reader<'r> for TxReconciliationSketchReader<'r> {
+    type Entity = TxReconciliationSketch;
+    const NAME: &'static str = "TxReconciliationSketchReader";
     fn to_entity(&self) -> Self::Entity {
         Self::Entity::new_unchecked(self.as_slice().to_owned().into())
     }
     fn new_unchecked(slice: &'r [u8]) -> Self {
-        GetBlockFilterCheckPointsReader(slice)
+        TxReconciliationSketchReader(slice)
     }
     fn as_slice(&self) -> &'r [u8] {
         self.0
     }
-    fn verify(slice: &[u8], _compatible: bool) -> molecule::error::VerificationResult<()> {
+    fn verify(slice: &[u8], compatible: bool) -> molecule::error::VerificationResult<()> {
         use molecule::verification_error as ve;
         let slice_len = slice.len();
-        if slice_len != Self::TOTAL_SIZE {
-            return ve!(Self, TotalSizeNotMatch, Self::TOTAL_SIZE, slice_len);
+        if slice_len < molecule::NUMBER_SIZE {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE, slice_len);
+        }
+        let total_size = molecule::unpack_number(slice) as usize;
+        if slice_len != total_size {
+            return ve!(Self, TotalSizeNotMatch, total_size, slice_len);
+        }
+        if slice_len < molecule::NUMBER_SIZE * 2 {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE * 2, slice_len);
+        }
+        let offset_first = molecule::unpack_number(&slice[molecule::NUMBER_SIZE..]) as usize;
+        if offset_first % molecule::NUMBER_SIZE != 0 || offset_first < molecule::NUMBER_SIZE * 2 {
+            return ve!(Self, OffsetsNotMatch);
+        }
+        if slice_len < offset_first {
+            return ve!(Self, HeaderIsBroken, offset_first, slice_len);
+        }
+        let field_count = offset_first / molecule::NUMBER_SIZE - 1;
+        if field_count < Self::FIELD_COUNT {
+            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
+        } else if !compatible && field_count > Self::FIELD_COUNT {
+            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
+        };
+        let mut offsets: Vec<usize> = slice[molecule::NUMBER_SIZE..offset_first]
+            .chunks_exact(molecule::NUMBER_SIZE)
+            .map(|x| molecule::unpack_number(x) as usize)
+            .collect();
+        offsets.push(total_size);
+        if offsets.windows(2).any(|i| i[0] > i[1]) {
+            return ve!(Self, OffsetsNotMatch);
         }
+        BytesReader::verify(&slice[offsets[0]..offsets[1]], compatible)?;
         Ok(())
     }
 }
 #[derive(Debug, Default)]
-pub struct GetBlockFilterCheckPointsBuilder {
-    pub(crate) start_number: Uint64,
+pub struct TxReconciliationSketchBuilder {
+    pub(crate) sketch: Bytes,
 }
-impl GetBlockFilterCheckPointsBuilder {
-    pub const TOTAL_SIZE: usize = 8;
-    pub const FIELD_SIZES: [usize; 1] = [8];
+impl TxReconciliationSketchBuilder {
     pub const FIELD_COUNT: usize = 1;
-    pub fn start_number(mut self, v: Uint64) -> Self {
-        self.start_number = v;
+    pub fn sketch(mut self, v: Bytes) -> Self {
+        self.sketch = v;
         self
     }
 }
-impl molecule::prelude::Builder for GetBlockFilterCheckPointsBuilder {
-    type Entity = GetBlockFilterCheckPoints;
-    const NAME: &'static str = "GetBlockFilterCheckPointsBuilder";
+impl molecule::prelude::Builder for TxReconciliationSketchBuilder {
+    type Entity = TxReconciliationSketch;
+    const NAME: &'static str = "TxReconciliationSketchBuilder";
     fn expected_length(&self) -> usize {
-        Self::TOTAL_SIZE
+        molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1) + self.sketch.as_slice().len()
     }
     fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
-        writer.write_all(self.start_number.as_slice())?;
+        let mut total_size = molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1);
+        let mut offsets = Vec::with_capacity(Self::FIELD_COUNT);
+        offsets.push(total_size);
+        total_size += self.sketch.as_slice().len();
+        writer.write_all(&molecule::pack_number(total_size as molecule::Number))?;
+        for offset in offsets.into_iter() {
+            writer.write_all(&molecule::pack_number(offset as molecule::Number))?;
+        }
+        writer.write_all(self.sketch.as_slice())?;
         Ok(())
     }
     fn build(&self) -> Self::Entity {
         let mut inner = Vec::with_capacity(self.expected_length());
         self.write(&mut inner)
             .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
-        GetBlockFilterCheckPoints::new_unchecked(inner.into())
+        TxReconciliationSketch::new_unchecked(inner.into())
     }
 }
 #[derive(Clone)]
-pub struct BlockFilterCheckPoints(molecule::bytes::Bytes);
-impl ::core::fmt::LowerHex for BlockFilterCheckPoints {
+pub struct TxReconciliationDifference(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for TxReconciliationDifference {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         use molecule::hex_string;
         if f.alternate() {
@@ -11627,21 +13975,16 @@ impl ::core::fmt::LowerHex for BlockFilterCheckPoints {
         write!(f, "{}", hex_string(self.as_slice()))
     }
 }
-impl ::core::fmt::Debug for BlockFilterCheckPoints {
+impl ::core::fmt::Debug for TxReconciliationDifference {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{}({:#x})", Self::NAME, self)
     }
 }
-impl ::core::fmt::Display for BlockFilterCheckPoints {
+impl ::core::fmt::Display for TxReconciliationDifference {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{} {{ ", Self::NAME)?;
-        write!(f, "{}: {}", "start_number", self.start_number())?;
-        write!(
-            f,
-            ", {}: {}",
-            "block_filter_hashes",
-            self.block_filter_hashes()
-        )?;
+        write!(f, "{}: {}", "success", self.success())?;
+        write!(f, ", {}: {}", "missing", self.missing())?;
         let extra_count = self.count_extra_fields();
         if extra_count != 0 {
             write!(f, ", .. ({} fields)", extra_count)?;
@@ -11649,16 +13992,14 @@ impl ::core::fmt::Display for BlockFilterCheckPoints {
         write!(f, " }}")
     }
 }
-impl ::core::default::Default for BlockFilterCheckPoints {
+impl ::core::default::Default for TxReconciliationDifference {
     fn default() -> Self {
         let v = molecule::bytes::Bytes::from_static(&Self::DEFAULT_VALUE);
-        BlockFilterCheckPoints::new_unchecked(v)
+        TxReconciliationDifference::new_unchecked(v)
     }
 }
-impl BlockFilterCheckPoints {
-    const DEFAULT_VALUE: [u8; 24] = [
-        24, 0, 0, 0, 12, 0, 0, 0, 20, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-    ];
+impl TxReconciliationDifference {
+    const DEFAULT_VALUE: [u8; 17] = [17, 0, 0, 0, 12, 0, 0, 0, 13, 0, 0, 0, 0, 0, 0, 0, 0];
     pub const FIELD_COUNT: usize = 2;
     pub fn total_size(&self) -> usize {
         molecule::unpack_number(self.as_slice()) as usize
@@ -11676,31 +14017,31 @@ impl BlockFilterCheckPoints {
     pub fn has_extra_fields(&self) -> bool {
         Self::FIELD_COUNT != self.field_count()
     }
-    pub fn start_number(&self) -> Uint64 {
+    pub fn success(&self) -> Bool {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[4..]) as usize;
         let end = molecule::unpack_number(&slice[8..]) as usize;
-        Uint64::new_unchecked(self.0.slice(start..end))
+        Bool::new_unchecked(self.0.slice(start..end))
     }
-    pub fn block_filter_hashes(&self) -> Byte32Vec {
+    pub fn missing(&self) -> Uint32Vec {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[8..]) as usize;
         if self.has_extra_fields() {
             let end = molecule::unpack_number(&slice[12..]) as usize;
-            Byte32Vec::new_unchecked(self.0.slice(start..end))
+            Uint32Vec::new_unchecked(self.0.slice(start..end))
         } else {
-            Byte32Vec::new_unchecked(self.0.slice(start..))
+            Uint32Vec::new_unchecked(self.0.slice(start..))
         }
     }
-    pub fn as_reader<'r>(&'r self) -> BlockFilterCheckPointsReader<'r> {
-        BlockFilterCheckPointsReader::new_unchecked(self.as_slice())
+    pub fn as_reader<'r>(&'r self) -> TxReconciliationDifferenceReader<'r> {
+        TxReconciliationDifferenceReader::new_unchecked(self.as_slice())
     }
 }
-impl molecule::prelude::Entity for BlockFilterCheckPoints {
-    type Builder = BlockFilterCheckPointsBuilder;
-    const NAME: &'static str = "BlockFilterCheckPoints";
+impl molecule::prelude::Entity for TxReconciliationDifference {
+    type Builder = TxReconciliationDifferenceBuilder;
+    const NAME: &'static str = "TxReconciliationDifference";
     fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
-        BlockFilterCheckPoints(data)
+        TxReconciliationDifference(data)
     }
     fn as_bytes(&self) -> molecule::bytes::Bytes {
         self.0.clone()
@@ -11709,23 +14050,24 @@ impl molecule::prelude::Entity for BlockFilterCheckPoints {
         &self.0[..]
     }
     fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
-        BlockFilterCheckPointsReader::from_slice(slice).map(|reader| reader.to_entity())
+        TxReconciliationDifferenceReader::from_slice(slice).map(|reader| reader.to_entity())
     }
     fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
-        BlockFilterCheckPointsReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+        TxReconciliationDifferenceReader::from_compatible_slice(slice)
+            .map(|reader| reader.to_entity())
     }
     fn new_builder() -> Self::Builder {
         ::core::default::Default::default()
     }
     fn as_builder(self) -> Self::Builder {
         Self::new_builder()
-            .start_number(self.start_number())
-            .block_filter_hashes(self.block_filter_hashes())
+            .success(self.success())
+            .missing(self.missing())
     }
 }
 #[derive(Clone, Copy)]
-pub struct BlockFilterCheckPointsReader<'r>(&'r [u8]);
-impl<'r> ::core::fmt::LowerHex for BlockFilterCheckPointsReader<'r> {
+pub struct TxReconciliationDifferenceReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for TxReconciliationDifferenceReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         use molecule::hex_string;
         if f.alternate() {
@@ -11734,21 +14076,16 @@ impl<'r> ::core::fmt::LowerHex for BlockFilterCheckPointsReader<'r> {
         write!(f, "{}", hex_string(self.as_slice()))
     }
 }
-impl<'r> ::core::fmt::Debug for BlockFilterCheckPointsReader<'r> {
+impl<'r> ::core::fmt::Debug for TxReconciliationDifferenceReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{}({:#x})", Self::NAME, self)
     }
 }
-impl<'r> ::core::fmt::Display for BlockFilterCheckPointsReader<'r> {
+impl<'r> ::core::fmt::Display for TxReconciliationDifferenceReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{} {{ ", Self::NAME)?;
-        write!(f, "{}: {}", "start_number", self.start_number())?;
-        write!(
-            f,
-            ", {}: {}",
-            "block_filter_hashes",
-            self.block_filter_hashes()
-        )?;
+        write!(f, "{}: {}", "success", self.success())?;
+        write!(f, ", {}: {}", "missing", self.missing())?;
         let extra_count = self.count_extra_fields();
         if extra_count != 0 {
             write!(f, ", .. ({} fields)", extra_count)?;
@@ -11756,7 +14093,7 @@ impl<'r> ::core::fmt::Display for BlockFilterCheckPointsReader<'r> {
         write!(f, " }}")
     }
 }
-impl<'r> BlockFilterCheckPointsReader<'r> {
+impl<'r> TxReconciliationDifferenceReader<'r> {
     pub const FIELD_COUNT: usize = 2;
     pub fn total_size(&self) -> usize {
         molecule::unpack_number(self.as_slice()) as usize
@@ -11774,31 +14111,31 @@ impl<'r> BlockFilterCheckPointsReader<'r> {
     pub fn has_extra_fields(&self) -> bool {
         Self::FIELD_COUNT != self.field_count()
     }
-    pub fn start_number(&self) -> Uint64Reader<'r> {
+    pub fn success(&self) -> BoolReader<'r> {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[4..]) as usize;
         let end = molecule::unpack_number(&slice[8..]) as usize;
-        Uint64Reader::new_unchecked(&self.as_slice()[start..end])
+        BoolReader::new_unchecked(&self.as_slice()[start..end])
     }
-    pub fn block_filter_hashes(&self) -> Byte32VecReader<'r> {
+    pub fn missing(&self) -> Uint32VecReader<'r> {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[8..]) as usize;
         if self.has_extra_fields() {
             let end = molecule::unpack_number(&slice[12..]) as usize;
-            Byte32VecReader::new_unchecked(&self.as_slice()[start..end])
+            Uint32VecReader::new_unchecked(&self.as_slice()[start..end])
         } else {
-            Byte32VecReader::new_unchecked(&self.as_slice()[start..])
+            Uint32VecReader::new_unchecked(&self.as_slice()[start..])
         }
     }
 }
-impl<'r> molecule::prelude::Reader<'r> for BlockFilterCheckPointsReader<'r> {
-    type Entity = BlockFilterCheckPoints;
-    const NAME: &'static str = "BlockFilterCheckPointsReader";
+impl<'r> molecule::prelude::Reader<'r> for TxReconciliationDifferenceReader<'r> {
+    type Entity = TxReconciliationDifference;
+    const NAME: &'static str = "TxReconciliationDifferenceReader";
     fn to_entity(&self) -> Self::Entity {
         Self::Entity::new_unchecked(self.as_slice().to_owned().into())
     }
     fn new_unchecked(slice: &'r [u8]) -> Self {
-        BlockFilterCheckPointsReader(slice)
+        TxReconciliationDifferenceReader(slice)
     }
     fn as_slice(&self) -> &'r [u8] {
         self.0
@@ -11837,55 +14174,55 @@ impl<'r> molecule::prelude::Reader<'r> for BlockFilterCheckPointsReader<'r> {
         if offsets.windows(2).any(|i| i[0] > i[1]) {
             return ve!(Self, OffsetsNotMatch);
         }
-        Uint64Reader::verify(&slice[offsets[0]..offsets[1]], compatible)?;
-        Byte32VecReader::verify(&slice[offsets[1]..offsets[2]], compatible)?;
+        BoolReader::verify(&slice[offsets[0]..offsets[1]], compatible)?;
+        Uint32VecReader::verify(&slice[offsets[1]..offsets[2]], compatible)?;
         Ok(())
     }
 }
 #[derive(Debug, Default)]
-pub struct BlockFilterCheckPointsBuilder {
-    pub(crate) start_number: Uint64,
-    pub(crate) block_filter_hashes: Byte32Vec,
+pub struct TxReconciliationDifferenceBuilder {
+    pub(crate) success: Bool,
+    pub(crate) missing: Uint32Vec,
 }
-impl BlockFilterCheckPointsBuilder {
+impl TxReconciliationDifferenceBuilder {
     pub const FIELD_COUNT: usize = 2;
-    pub fn start_number(mut self, v: Uint64) -> Self {
-        self.start_number = v;
+    pub fn success(mut self, v: Bool) -> Self {
+        self.success = v;
         self
     }
-    pub fn block_filter_hashes(mut self, v: Byte32Vec) -> Self {
-        self.block_filter_hashes = v;
+    pub fn missing(mut self, v: Uint32Vec) -> Self {
+        self.missing = v;
         self
     }
 }
-impl molecule::prelude::Builder for BlockFilterCheckPointsBuilder {
-    type Entity = BlockFilterCheckPoints;
-    const NAME: &'static str = "BlockFilterCheckPointsBuilder";
+impl molecule::prelude::Builder for TxReconciliationDifferenceBuilder {
+    type Entity = TxReconciliationDifference;
+    const NAME: &'static str = "TxReconciliationDifferenceBuilder";
     fn expected_length(&self) -> usize {
         molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1)
-            + self.start_number.as_slice().len()
-            + self.block_filter_hashes.as_slice().len()
+            + self.success.as_slice().len()
+            + self.missing.as_slice().len()
     }
     fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
         let mut total_size = molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1);
         let mut offsets = Vec::with_capacity(Self::FIELD_COUNT);
         offsets.push(total_size);
-        total_size += self.start_number.as_slice().len();
+        total_size += self.success.as_slice().len();
         offsets.push(total_size);
-        total_size += self.block_filter_hashes.as_slice().len();
+        total_size += self.missing.as_slice().len();
         writer.write_all(&molecule::pack_number(total_size as molecule::Number))?;
         for offset in offsets.into_iter() {
             writer.write_all(&molecule::pack_number(offset as molecule::Number))?;
         }
-        writer.write_all(self.start_number.as_slice())?;
-        writer.write_all(self.block_filter_hashes.as_slice())?;
+        writer.write_all(self.success.as_slice())?;
+        writer.write_all(self.missing.as_slice())?;
         Ok(())
     }
     fn build(&self) -> Self::Entity {
         let mut inner = Vec::with_capacity(self.expected_length());
         self.write(&mut inner)
             .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
-        BlockFilterCheckPoints::new_unchecked(inner.into())
+        TxReconciliationDifference::new_unchecked(inner.into())
     }
 }
 #[derive(Clone)]
@@ -10,6 +10,9 @@ pub enum Error {
     /// Invalid params error
     #[error("Invalid params {0}")]
     Params(String),
+    /// Arithmetic overflow error
+    #[error("Overflow {0}")]
+    Overflow(String),
 }
 
 impl Error {
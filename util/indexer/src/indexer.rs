@@ -3,9 +3,10 @@ use crate::{
     pool::Pool,
     store::{Batch, IteratorDirection, Store},
 };
+use ckb_notify::{ChangedCell, IndexerCellsChanged};
 use ckb_types::{
     core::{BlockNumber, BlockView},
-    packed::{Byte32, Bytes, CellOutput, OutPoint, Script},
+    packed::{Byte, Byte32, Bytes, CellOutput, OutPoint, Script},
     prelude::*,
 };
 use numext_fixed_uint::U256;
@@ -16,6 +17,9 @@ use std::{
     sync::{Arc, RwLock},
 };
 
+/// The live cells indexed in a write batch when the UDT amounts are indexed at the startup
+const UDT_AMOUNT_INDEXING_CHUNK_SIZE: usize = 10_000;
+
 /// Tx index alias
 pub type TxIndex = u32;
 /// Output index alias
@@ -42,6 +46,10 @@ pub enum CellType {
 /// | 160          | TxTypeScript       | TxHash                   |
 /// | 192          | TxHash             | TransactionInputs        | * rollback and prune
 /// | 224          | Header             | Transactions             |
+/// | 232          | LockUdtAmount      | UdtAmount                |
+/// | 240          | TypeUdtAmount      | UdtAmount                |
+/// | 248          | UdtAmountIndexed   |                          |
+/// | 249          | UdtAmountIndexing  | OutPoint key             | * removed once indexed
 /// +--------------+--------------------+--------------------------+
 /// Storage indexer key type enum
 pub enum Key<'a> {
@@ -61,6 +69,14 @@ pub enum Key<'a> {
     TxHash(&'a Byte32),
     /// (block_number, block_hash, txs_filtered)
     Header(BlockNumber, &'a Byte32, bool),
+    /// (lock_script, type_script), used by get_cells_udt_amount
+    LockUdtAmount(&'a Script, &'a Script),
+    /// TypeScript, used by get_cells_udt_amount
+    TypeUdtAmount(&'a Script),
+    /// Marks that the UDT amounts of the live cells are indexed
+    UdtAmountIndexed,
+    /// The progress of indexing the UDT amounts of the live cells, the last indexed `OutPoint` key
+    UdtAmountIndexing,
 }
 
 /// Storage value
@@ -73,6 +89,8 @@ pub enum Value<'a> {
     TransactionInputs(Vec<OutPoint>),
     /// (tx_hash, outputs_len, tx_index)
     Transactions(Vec<(Byte32, u32, Option<u32>)>),
+    /// UDT amount
+    UdtAmount(&'a UdtAmount),
 }
 
 /// Key prefix
@@ -94,6 +112,14 @@ pub enum KeyPrefix {
     TxHash = 192,
     /// Header
     Header = 224,
+    /// LockScript and TypeScript UDT amount
+    LockUdtAmount = 232,
+    /// TypeScript UDT amount
+    TypeUdtAmount = 240,
+    /// UDT amount indexed marker
+    UdtAmountIndexed = 248,
+    /// UDT amount indexing progress
+    UdtAmountIndexing = 249,
 }
 
 impl<'a> Key<'a> {
@@ -153,6 +179,24 @@ impl<'a> From<Key<'a>> for Vec<u8> {
                     encoded.push(1);
                 }
             }
+            Key::LockUdtAmount(lock_script, type_script) => {
+                encoded.push(KeyPrefix::LockUdtAmount as u8);
+                let lock_script_raw_data = extract_raw_data(lock_script);
+                encoded.extend_from_slice(&lock_script_raw_data);
+                encoded.extend_from_slice(&extract_raw_data(type_script));
+                // the lock script len, to tell the lock script from the type script
+                encoded.extend_from_slice(&(lock_script_raw_data.len() as u32).to_be_bytes());
+            }
+            Key::TypeUdtAmount(type_script) => {
+                encoded.push(KeyPrefix::TypeUdtAmount as u8);
+                encoded.extend_from_slice(&extract_raw_data(type_script));
+            }
+            Key::UdtAmountIndexed => {
+                encoded.push(KeyPrefix::UdtAmountIndexed as u8);
+            }
+            Key::UdtAmountIndexing => {
+                encoded.push(KeyPrefix::UdtAmountIndexing as u8);
+            }
         }
         encoded
     }
@@ -181,6 +225,23 @@ pub fn extract_raw_data(script: &Script) -> Vec<u8> {
     .concat()
 }
 
+/// helper fn builds the script from the raw data extracted by `extract_raw_data`
+pub fn parse_raw_data(raw_data: &[u8]) -> Script {
+    Script::new_builder()
+        .code_hash(Byte32::from_slice(&raw_data[0..32]).expect("script code_hash raw data"))
+        .hash_type(Byte::new(raw_data[32]))
+        .args(raw_data[33..].pack())
+        .build()
+}
+
+/// helper fn extracts the UDT amount from cell data, sUDT and xUDT both store the amount in the
+/// first 16 bytes as little-endian u128
+pub fn extract_udt_amount(output_data: &[u8]) -> Option<u128> {
+    output_data
+        .get(0..16)
+        .map(|amount| u128::from_le_bytes(amount.try_into().expect("checked len")))
+}
+
 impl<'a> From<Value<'a>> for Vec<u8> {
     fn from(value: Value<'a>) -> Vec<u8> {
         let mut encoded = Vec::new();
@@ -208,6 +269,11 @@ impl<'a> From<Value<'a>> for Vec<u8> {
                     }
                 });
             }
+            Value::UdtAmount(udt_amount) => {
+                encoded.extend_from_slice(&udt_amount.amount.to_le_bytes());
+                encoded.extend_from_slice(&udt_amount.amount_high.to_le_bytes());
+                encoded.extend_from_slice(&udt_amount.cells_count.to_le_bytes());
+            }
         }
         encoded
     }
@@ -256,6 +322,121 @@ impl<'a> Value<'a> {
             })
             .collect()
     }
+
+    /// Decode UDT amount from binary
+    pub fn parse_udt_amount_value(slice: &[u8]) -> UdtAmount {
+        UdtAmount {
+            amount: u128::from_le_bytes(slice[0..16].try_into().expect("stored udt amount")),
+            amount_high: u64::from_le_bytes(
+                slice[16..24].try_into().expect("stored udt amount_high"),
+            ),
+            cells_count: u64::from_le_bytes(
+                slice[24..32].try_into().expect("stored udt cells_count"),
+            ),
+        }
+    }
+}
+
+/// The total UDT amount of live cells.
+///
+/// The amount is kept 192 bits wide, so adding up the cells never overflows, and the total is
+/// checked against u128::MAX only when it is read.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UdtAmount {
+    amount: u128,
+    amount_high: u64,
+    cells_count: u64,
+}
+
+impl UdtAmount {
+    /// Add the amount of a live cell
+    pub fn add_cell(&mut self, amount: u128) {
+        self.add(&UdtAmount {
+            amount,
+            amount_high: 0,
+            cells_count: 1,
+        });
+    }
+
+    /// Add up another total
+    pub fn add(&mut self, other: &UdtAmount) {
+        let (amount, carry) = self.amount.overflowing_add(other.amount);
+        self.amount = amount;
+        self.amount_high += other.amount_high + u64::from(carry);
+        self.cells_count += other.cells_count;
+    }
+
+    /// Subtract another total, which must be a part of this one
+    pub fn sub(&mut self, other: &UdtAmount) {
+        let (amount, borrow) = self.amount.overflowing_sub(other.amount);
+        self.amount = amount;
+        self.amount_high -= other.amount_high + u64::from(borrow);
+        self.cells_count -= other.cells_count;
+    }
+
+    /// Return the total amount, or `None` if it exceeds u128::MAX
+    pub fn amount(&self) -> Option<u128> {
+        (self.amount_high == 0).then_some(self.amount)
+    }
+
+    /// Return the number of the live cells
+    pub fn cells_count(&self) -> u64 {
+        self.cells_count
+    }
+}
+
+/// The UDT amounts changed by a block, applied to the stored totals when the block is committed
+#[derive(Default)]
+struct UdtAmountsChanged {
+    // keyed by the encoded `LockUdtAmount` / `TypeUdtAmount` key, (added, removed)
+    changed: HashMap<Vec<u8>, (UdtAmount, UdtAmount)>,
+}
+
+impl UdtAmountsChanged {
+    fn keys(output: &CellOutput, output_data: &Bytes) -> Option<([Vec<u8>; 2], u128)> {
+        let type_script = output.type_().to_opt()?;
+        let amount = extract_udt_amount(&output_data.raw_data())?;
+        Some((
+            [
+                Key::LockUdtAmount(&output.lock(), &type_script).into_vec(),
+                Key::TypeUdtAmount(&type_script).into_vec(),
+            ],
+            amount,
+        ))
+    }
+
+    fn cell_added(&mut self, output: &CellOutput, output_data: &Bytes) {
+        if let Some((keys, amount)) = Self::keys(output, output_data) {
+            for key in keys {
+                self.changed.entry(key).or_default().0.add_cell(amount);
+            }
+        }
+    }
+
+    fn cell_removed(&mut self, output: &CellOutput, output_data: &Bytes) {
+        if let Some((keys, amount)) = Self::keys(output, output_data) {
+            for key in keys {
+                self.changed.entry(key).or_default().1.add_cell(amount);
+            }
+        }
+    }
+
+    fn write<S: Store>(self, store: &S, batch: &mut S::Batch) -> Result<(), Error> {
+        for (key, (added, removed)) in self.changed {
+            let mut udt_amount = store
+                .get(&key)?
+                .map(|value| Value::parse_udt_amount_value(&value))
+                .unwrap_or_default();
+            udt_amount.add(&added);
+            udt_amount.sub(&removed);
+            if udt_amount.cells_count() == 0 {
+                batch.delete(key)?;
+            } else {
+                batch.put_kv(key, Value::UdtAmount(&udt_amount))?;
+            }
+        }
+        Ok(())
+    }
 }
 
 /// Live cell with information:
@@ -317,17 +498,27 @@ where
     S: Store,
 {
     /// Parse the block, store the Cell Transaction etc. contained in the block with the designed index
-    pub(crate) fn append(&self, block: &BlockView) -> Result<(), Error> {
+    ///
+    /// Returns the live cells changed by the block.
+    pub(crate) fn append(&self, block: &BlockView) -> Result<IndexerCellsChanged, Error> {
+        let mut cells_changed = IndexerCellsChanged {
+            block_number: block.number(),
+            block_hash: block.hash(),
+            rollback: false,
+            created: Vec::new(),
+            consumed: Vec::new(),
+        };
         let mut batch = self.store.batch()?;
         if !self.custom_filters.is_block_filter_match(block) {
             batch.put_kv(Key::Header(block.number(), &block.hash(), true), vec![])?;
             batch.commit()?;
-            return Ok(());
+            return Ok(cells_changed);
         }
 
         let block_number = block.number();
         let transactions = block.transactions();
         let mut matched_txs = vec![];
+        let mut udt_amounts_changed = UdtAmountsChanged::default();
         let pool = self.pool.as_ref().map(|p| p.write().expect("acquire lock"));
         for (tx_index, tx) in transactions.iter().enumerate() {
             let tx_index = tx_index as u32;
@@ -418,6 +609,14 @@ where
                             Key::ConsumedOutPoint(block_number, &out_point),
                             stored_live_cell,
                         )?;
+                        udt_amounts_changed.cell_removed(&output, &output_data);
+                        cells_changed.consumed.push(ChangedCell {
+                            out_point,
+                            output,
+                            output_data,
+                            block_number: generated_by_block_number,
+                            tx_index: generated_by_tx_index,
+                        });
                     }
                 }
             }
@@ -475,6 +674,14 @@ where
                     Key::OutPoint(&out_point),
                     Value::Cell(block_number, tx_index, &output, &output_data),
                 )?;
+                udt_amounts_changed.cell_added(&output, &output_data);
+                cells_changed.created.push(ChangedCell {
+                    out_point,
+                    output,
+                    output_data,
+                    block_number,
+                    tx_index,
+                });
             }
 
             if tx_matched {
@@ -509,6 +716,7 @@ where
                 Value::Transactions(matched_txs),
             )?;
         }
+        udt_amounts_changed.write(&self.store, &mut batch)?;
         batch.commit()?;
 
         if let Some(mut pool) = pool {
@@ -518,11 +726,13 @@ where
         if block_number % self.prune_interval == 0 {
             self.prune()?;
         }
-        Ok(())
+        Ok(cells_changed)
     }
 
     /// Rollback the current tip
-    pub(crate) fn rollback(&self) -> Result<(), Error> {
+    ///
+    /// Returns the live cells changed by the rolled back block, or `None` if there is no tip.
+    pub(crate) fn rollback(&self) -> Result<Option<IndexerCellsChanged>, Error> {
         let mut iter = self
             .store
             .iter([KeyPrefix::Header as u8 + 1], IteratorDirection::Reverse)?;
@@ -535,7 +745,15 @@ where
                 Value::parse_transactions_value(&value, filtered),
             )
         }) {
+            let mut cells_changed = IndexerCellsChanged {
+                block_number,
+                block_hash: block_hash.clone(),
+                rollback: true,
+                created: Vec::new(),
+                consumed: Vec::new(),
+            };
             let mut batch = self.store.batch()?;
+            let mut udt_amounts_changed = UdtAmountsChanged::default();
             for (tx_index, (tx_hash, outputs_len, stored_tx_index)) in
                 txs.into_iter().enumerate().rev()
             {
@@ -545,14 +763,15 @@ where
                     let out_point = OutPoint::new(tx_hash.clone(), output_index);
                     let out_point_key = Key::OutPoint(&out_point).into_vec();
 
-                    if let Some(output) = match self.store.get(&out_point_key)? {
-                        Some(stored_live_cell) => {
-                            Some(Value::parse_cell_value(&stored_live_cell).2)
-                        }
+                    if let Some((_block_number, _tx_index, output, output_data)) = match self
+                        .store
+                        .get(&out_point_key)?
+                    {
+                        Some(stored_live_cell) => Some(Value::parse_cell_value(&stored_live_cell)),
                         None => self
                             .store
                             .get(Key::ConsumedOutPoint(block_number, &out_point).into_vec())?
-                            .map(|consumed_cell| Value::parse_cell_value(&consumed_cell).2),
+                            .map(|consumed_cell| Value::parse_cell_value(&consumed_cell)),
                     } {
                         batch.delete(
                             Key::CellLockScript(
@@ -590,6 +809,14 @@ where
                             )?;
                         };
                         batch.delete(out_point_key)?;
+                        udt_amounts_changed.cell_removed(&output, &output_data);
+                        cells_changed.created.push(ChangedCell {
+                            out_point,
+                            output,
+                            output_data,
+                            block_number,
+                            tx_index,
+                        });
                     }
                 }
 
@@ -618,7 +845,7 @@ where
                                 generated_by_block_number,
                                 generated_by_tx_index,
                                 output,
-                                _output_data,
+                                output_data,
                             ) = Value::parse_cell_value(&stored_consumed_cell);
                             let input_index = input_index as u32;
 
@@ -663,6 +890,14 @@ where
                                 )?;
                             }
                             batch.put_kv(Key::OutPoint(&out_point), stored_consumed_cell)?;
+                            udt_amounts_changed.cell_added(&output, &output_data);
+                            cells_changed.consumed.push(ChangedCell {
+                                out_point,
+                                output,
+                                output_data,
+                                block_number: generated_by_block_number,
+                                tx_index: generated_by_tx_index,
+                            });
                         }
                     }
                 }
@@ -671,10 +906,13 @@ where
             }
             // delete block transactions
             batch.delete(Key::Header(block_number, &block_hash, filtered).into_vec())?;
+            udt_amounts_changed.write(&self.store, &mut batch)?;
 
             batch.commit()?;
+            Ok(Some(cells_changed))
+        } else {
+            Ok(None)
         }
-        Ok(())
     }
 
     /// Return the current tip
@@ -690,6 +928,107 @@ where
         }))
     }
 
+    /// Index the UDT amounts of the live cells stored before the UDT amount index was added,
+    /// does nothing if they are already indexed
+    pub(crate) fn index_udt_amounts(&self) -> Result<(), Error> {
+        self.index_udt_amounts_in_chunks(UDT_AMOUNT_INDEXING_CHUNK_SIZE)
+    }
+
+    /// The live cells are indexed in chunks of `chunk_size`, each chunk is committed along with
+    /// its last `OutPoint` key as the progress, so the indexing resumes from it after a restart.
+    pub(crate) fn index_udt_amounts_in_chunks(&self, chunk_size: usize) -> Result<(), Error> {
+        if self.store.exists(Key::UdtAmountIndexed.into_vec())? {
+            return Ok(());
+        }
+        let mut progress = match self.store.get(Key::UdtAmountIndexing.into_vec())? {
+            Some(progress) => progress,
+            None => {
+                self.reset_udt_amounts(chunk_size)?;
+                Vec::new()
+            }
+        };
+        let key_prefix_out_point = [KeyPrefix::OutPoint as u8];
+        loop {
+            let from_key = if progress.is_empty() {
+                &key_prefix_out_point[..]
+            } else {
+                &progress[..]
+            };
+            let mut udt_amounts: HashMap<Vec<u8>, UdtAmount> = HashMap::new();
+            let mut last_key = None;
+            for (key, value) in self
+                .store
+                .iter(from_key, IteratorDirection::Forward)?
+                .take_while(|(key, _value)| key.starts_with(&key_prefix_out_point))
+                // the iteration starts from the last indexed key
+                .filter(|(key, _value)| key.as_ref() != progress.as_slice())
+                .take(chunk_size)
+            {
+                let (_block_number, _tx_index, output, output_data) =
+                    Value::parse_cell_value(&value);
+                if let Some((keys, amount)) = UdtAmountsChanged::keys(&output, &output_data) {
+                    for key in keys {
+                        udt_amounts.entry(key).or_default().add_cell(amount);
+                    }
+                }
+                last_key = Some(key);
+            }
+
+            let mut batch = self.store.batch()?;
+            for (key, added) in udt_amounts {
+                let mut udt_amount = self
+                    .store
+                    .get(&key)?
+                    .map(|value| Value::parse_udt_amount_value(&value))
+                    .unwrap_or_default();
+                udt_amount.add(&added);
+                batch.put_kv(key, Value::UdtAmount(&udt_amount))?;
+            }
+            match last_key {
+                Some(key) => {
+                    progress = key.to_vec();
+                    batch.put_kv(Key::UdtAmountIndexing, progress.clone())?;
+                    batch.commit()?;
+                }
+                None => {
+                    batch.delete(Key::UdtAmountIndexing.into_vec())?;
+                    batch.put_kv(Key::UdtAmountIndexed, vec![])?;
+                    return batch.commit();
+                }
+            }
+        }
+    }
+
+    // Delete the UDT amounts updated by the blocks appended before the live cells are indexed,
+    // they're counted again from the live cells, then mark the indexing as started
+    fn reset_udt_amounts(&self, chunk_size: usize) -> Result<(), Error> {
+        for key_prefix in [
+            [KeyPrefix::LockUdtAmount as u8],
+            [KeyPrefix::TypeUdtAmount as u8],
+        ] {
+            loop {
+                let keys: Vec<_> = self
+                    .store
+                    .iter(key_prefix, IteratorDirection::Forward)?
+                    .take_while(|(key, _value)| key.starts_with(&key_prefix))
+                    .take(chunk_size)
+                    .map(|(key, _value)| key)
+                    .collect();
+                if keys.is_empty() {
+                    break;
+                }
+                let mut batch = self.store.batch()?;
+                for key in keys {
+                    batch.delete(key)?;
+                }
+                batch.commit()?;
+            }
+        }
+        let mut batch = self.store.batch()?;
+        batch.put_kv(Key::UdtAmountIndexing, vec![])?;
+        batch.commit()
+    }
+
     /// Return block hash by specified block_number
     #[cfg(test)]
     pub(crate) fn get_block_hash(
@@ -1221,7 +1560,21 @@ mod tests {
             )
             .build();
 
-        indexer.append(&block1).unwrap();
+        let cells_changed = indexer.append(&block1).unwrap();
+        assert!(!cells_changed.rollback);
+        assert_eq!(block1.hash(), cells_changed.block_hash);
+        assert_eq!(
+            3, // cellbase1, tx10, tx11
+            cells_changed.created.len()
+        );
+        assert_eq!(
+            vec![OutPoint::new(tx00.hash(), 0), OutPoint::new(tx01.hash(), 0)],
+            cells_changed
+                .consumed
+                .iter()
+                .map(|cell| cell.out_point.clone())
+                .collect::<Vec<_>>()
+        );
         let (tip_number, tip_hash) = indexer.tip().unwrap().unwrap();
         assert_eq!(1, tip_number);
         assert_eq!(block1.hash(), tip_hash);
@@ -1240,7 +1593,11 @@ mod tests {
                 .len()
         );
 
-        indexer.rollback().unwrap();
+        let rollback_cells_changed = indexer.rollback().unwrap().unwrap();
+        assert!(rollback_cells_changed.rollback);
+        assert_eq!(block1.hash(), rollback_cells_changed.block_hash);
+        assert_eq!(3, rollback_cells_changed.created.len());
+        assert_eq!(2, rollback_cells_changed.consumed.len());
         let (tip_number, tip_hash) = indexer.tip().unwrap().unwrap();
         assert_eq!(0, tip_number);
         assert_eq!(block0.hash(), tip_hash);
@@ -1260,6 +1617,185 @@ mod tests {
         );
     }
 
+    // Appends a block creating `tx00` and `tx01`, and then a block consuming their outputs.
+    fn append_cells_changed_blocks(
+        indexer: &Indexer<RocksdbStore>,
+        lock_script: &Script,
+        type_script: &Script,
+    ) -> (BlockView, BlockView) {
+        let tx00 = TransactionBuilder::default()
+            .output(
+                CellOutputBuilder::default()
+                    .capacity(capacity_bytes!(1000).pack())
+                    .lock(lock_script.clone())
+                    .type_(Some(type_script.clone()).pack())
+                    .build(),
+            )
+            .output_data(Bytes::from(b"tx00".to_vec()).pack())
+            .build();
+        let tx01 = TransactionBuilder::default()
+            .output(
+                CellOutputBuilder::default()
+                    .capacity(capacity_bytes!(2000).pack())
+                    .lock(lock_script.clone())
+                    .build(),
+            )
+            .output_data(Default::default())
+            .build();
+        let block0 = BlockBuilder::default()
+            .transaction(
+                TransactionBuilder::default()
+                    .input(CellInput::new_cellbase_input(0))
+                    .witness(Script::default().into_witness())
+                    .build(),
+            )
+            .transaction(tx00.clone())
+            .transaction(tx01.clone())
+            .header(HeaderBuilder::default().number(0.pack()).build())
+            .build();
+        indexer.append(&block0).unwrap();
+
+        let tx10 = TransactionBuilder::default()
+            .input(CellInput::new(OutPoint::new(tx00.hash(), 0), 0))
+            .input(CellInput::new(OutPoint::new(tx01.hash(), 0), 0))
+            .output(
+                CellOutputBuilder::default()
+                    .capacity(capacity_bytes!(3000).pack())
+                    .lock(lock_script.clone())
+                    .build(),
+            )
+            .output_data(Default::default())
+            .build();
+        let block1 = BlockBuilder::default()
+            .transaction(
+                TransactionBuilder::default()
+                    .input(CellInput::new_cellbase_input(1))
+                    .witness(Script::default().into_witness())
+                    .build(),
+            )
+            .transaction(tx10)
+            .header(
+                HeaderBuilder::default()
+                    .number(1.pack())
+                    .parent_hash(block0.hash())
+                    .epoch(EpochNumberWithFraction::new(1, 0, 1000).pack())
+                    .build(),
+            )
+            .build();
+        (block0, block1)
+    }
+
+    fn sorted_out_points(cells: &[ChangedCell]) -> Vec<OutPoint> {
+        let mut out_points: Vec<_> = cells.iter().map(|cell| cell.out_point.clone()).collect();
+        out_points.sort_by_key(|out_point| out_point.as_bytes());
+        out_points
+    }
+
+    #[test]
+    fn append_reports_cells_changed() {
+        let indexer = new_indexer::<RocksdbStore>("append_reports_cells_changed");
+
+        let lock_script = ScriptBuilder::default()
+            .code_hash(H256(rand::random()).pack())
+            .hash_type(ScriptHashType::Type.into())
+            .args(Bytes::from(b"lock_script".to_vec()).pack())
+            .build();
+        let type_script = ScriptBuilder::default()
+            .code_hash(H256(rand::random()).pack())
+            .hash_type(ScriptHashType::Data.into())
+            .args(Bytes::from(b"type_script".to_vec()).pack())
+            .build();
+        let (block0, block1) = append_cells_changed_blocks(&indexer, &lock_script, &type_script);
+        let tx00 = block0.transaction(1).unwrap();
+        let tx01 = block0.transaction(2).unwrap();
+        let tx10 = block1.transaction(1).unwrap();
+
+        let cells_changed = indexer.append(&block1).unwrap();
+        assert!(!cells_changed.rollback);
+        assert_eq!(1, cells_changed.block_number);
+        assert_eq!(block1.hash(), cells_changed.block_hash);
+        // the cellbase has no outputs
+        assert_eq!(
+            vec![ChangedCell {
+                out_point: OutPoint::new(tx10.hash(), 0),
+                output: tx10.outputs().get(0).unwrap(),
+                output_data: tx10.outputs_data().get(0).unwrap(),
+                block_number: 1,
+                tx_index: 1,
+            }],
+            cells_changed.created
+        );
+        // the consumed cells are reported as they were created
+        assert_eq!(
+            vec![
+                ChangedCell {
+                    out_point: OutPoint::new(tx00.hash(), 0),
+                    output: tx00.outputs().get(0).unwrap(),
+                    output_data: tx00.outputs_data().get(0).unwrap(),
+                    block_number: 0,
+                    tx_index: 1,
+                },
+                ChangedCell {
+                    out_point: OutPoint::new(tx01.hash(), 0),
+                    output: tx01.outputs().get(0).unwrap(),
+                    output_data: tx01.outputs_data().get(0).unwrap(),
+                    block_number: 0,
+                    tx_index: 2,
+                },
+            ],
+            cells_changed.consumed
+        );
+    }
+
+    #[test]
+    fn rollback_reports_cells_changed() {
+        let indexer = new_indexer::<RocksdbStore>("rollback_reports_cells_changed");
+
+        let lock_script = ScriptBuilder::default()
+            .code_hash(H256(rand::random()).pack())
+            .hash_type(ScriptHashType::Type.into())
+            .args(Bytes::from(b"lock_script".to_vec()).pack())
+            .build();
+        let type_script = ScriptBuilder::default()
+            .code_hash(H256(rand::random()).pack())
+            .hash_type(ScriptHashType::Data.into())
+            .args(Bytes::from(b"type_script".to_vec()).pack())
+            .build();
+        let (block0, block1) = append_cells_changed_blocks(&indexer, &lock_script, &type_script);
+        let appended = indexer.append(&block1).unwrap();
+
+        // rolling back a block removes the cells it created and revives the cells it consumed,
+        // so subscribers undo exactly what the append reported
+        let rollback_cells_changed = indexer.rollback().unwrap().unwrap();
+        assert!(rollback_cells_changed.rollback);
+        assert_eq!(1, rollback_cells_changed.block_number);
+        assert_eq!(block1.hash(), rollback_cells_changed.block_hash);
+        assert_eq!(
+            sorted_out_points(&appended.created),
+            sorted_out_points(&rollback_cells_changed.created)
+        );
+        assert_eq!(
+            sorted_out_points(&appended.consumed),
+            sorted_out_points(&rollback_cells_changed.consumed)
+        );
+        for cell in &appended.consumed {
+            assert!(rollback_cells_changed.consumed.contains(cell));
+        }
+        assert_eq!(
+            2, // tx00, tx01
+            indexer
+                .get_live_cells_by_lock_script(&lock_script)
+                .unwrap()
+                .len()
+        );
+
+        let rollback_cells_changed = indexer.rollback().unwrap().unwrap();
+        assert_eq!(block0.hash(), rollback_cells_changed.block_hash);
+        assert_eq!(2, rollback_cells_changed.created.len());
+        assert!(rollback_cells_changed.consumed.is_empty());
+        assert!(indexer.rollback().unwrap().is_none());
+    }
+
     #[test]
     fn consume_output_in_same_block() {
         let indexer = new_indexer::<RocksdbStore>("consume_output_in_same_block");
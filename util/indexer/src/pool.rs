@@ -37,6 +37,11 @@ impl Pool {
         self.dead_cells.contains(out_point)
     }
 
+    /// Return the out points referred cells consumed by pooled transactions
+    pub fn dead_cells(&self) -> impl Iterator<Item = &OutPoint> {
+        self.dead_cells.iter()
+    }
+
     /// the txs has been committed in a block, it should be removed from pending dead cells
     pub fn transactions_committed(&mut self, txs: &[TransactionView]) {
         for tx in txs {
@@ -1,6 +1,9 @@
 //！The indexer service.
 
-use crate::indexer::{self, extract_raw_data, CustomFilters, Indexer, Key, KeyPrefix, Value};
+use crate::indexer::{
+    self, extract_raw_data, extract_udt_amount, parse_raw_data, CustomFilters, Indexer, Key,
+    KeyPrefix, UdtAmount, Value,
+};
 use crate::pool::Pool;
 use crate::store::{IteratorDirection, RocksdbStore, SecondaryDB, Store};
 
@@ -12,16 +15,18 @@ use ckb_async_runtime::{
 };
 use ckb_db_schema::{COLUMN_BLOCK_BODY, COLUMN_BLOCK_HEADER, COLUMN_INDEX, COLUMN_META};
 use ckb_jsonrpc_types::{
-    IndexerCell, IndexerCellType, IndexerCellsCapacity, IndexerOrder, IndexerPagination,
+    IndexerCell, IndexerCellType, IndexerCellsCapacity, IndexerCellsCapacityAtBlock,
+    IndexerCellsUdtAmount, IndexerFilterExpression, IndexerOrder, IndexerPagination,
     IndexerScriptSearchMode, IndexerScriptType, IndexerSearchKey, IndexerTip, IndexerTx,
-    IndexerTxWithCell, IndexerTxWithCells, JsonBytes, Uint32,
+    IndexerTxWithCell, IndexerTxWithCells, IndexerUdtAmount, JsonBytes, Uint32,
 };
 use ckb_logger::{error, info};
 use ckb_notify::NotifyController;
 use ckb_stop_handler::{new_tokio_exit_rx, CancellationToken};
 use ckb_store::ChainStore;
 use ckb_types::{core, packed, prelude::*, H256};
-use rocksdb::{prelude::*, Direction, IteratorMode};
+use rocksdb::{prelude::*, Direction, IteratorMode, Snapshot};
+use std::collections::{BTreeMap, HashMap};
 use std::convert::TryInto;
 use std::num::NonZeroUsize;
 use std::sync::{Arc, RwLock};
@@ -30,6 +35,9 @@ use std::time::Duration;
 const SUBSCRIBER_NAME: &str = "Indexer";
 const DEFAULT_LOG_KEEP_NUM: usize = 1;
 const DEFAULT_MAX_BACKGROUND_JOBS: usize = 6;
+/// The maximum number of the indexed transaction records of a script replayed by
+/// `get_cells_capacity_at_block`.
+pub const MAX_RECORDS_AT_BLOCK: usize = 100_000;
 
 /// Indexer service
 #[derive(Clone)]
@@ -127,7 +135,7 @@ impl IndexerService {
         });
     }
 
-    fn try_loop_sync(&self) {
+    fn try_loop_sync(&self, notify_controller: &NotifyController) {
         // assume that long fork will not happen >= 100 blocks.
         let keep_num = 100;
         if let Err(e) = self.secondary_db.try_catch_up_with_primary() {
@@ -140,25 +148,51 @@ impl IndexerService {
             self.pool.clone(),
             CustomFilters::new(self.block_filter.as_deref(), self.cell_filter.as_deref()),
         );
+        indexer
+            .index_udt_amounts()
+            .expect("index udt amounts should be OK");
         loop {
             if let Some((tip_number, tip_hash)) = indexer.tip().expect("get tip should be OK") {
                 match self.get_block_by_number(tip_number + 1) {
                     Some(block) => {
                         if block.parent_hash() == tip_hash {
                             info!("Append {}, {}", block.number(), block.hash());
-                            indexer.append(&block).expect("append block should be OK");
+                            let cells_changed =
+                                indexer.append(&block).expect("append block should be OK");
+                            if !cells_changed.is_empty() {
+                                notify_controller.notify_indexer_cells_changed(cells_changed);
+                            }
                         } else {
                             info!("Rollback {}, {}", tip_number, tip_hash);
-                            indexer.rollback().expect("rollback block should be OK");
+                            if let Some(cells_changed) =
+                                indexer.rollback().expect("rollback block should be OK")
+                            {
+                                if !cells_changed.is_empty() {
+                                    notify_controller.notify_indexer_cells_changed(cells_changed);
+                                }
+                            }
                         }
                     }
                     None => {
+                        if self.secondary_db.get_block_hash(tip_number + 1).is_some() {
+                            error!(
+                                "The body of block {} is pruned, the indexer can't catch up with the pruned node, \
+                                rebuild the indexer from a node which is not pruned",
+                                tip_number + 1
+                            );
+                        }
                         break;
                     }
                 }
             } else {
                 match self.get_block_by_number(0) {
-                    Some(block) => indexer.append(&block).expect("append block should be OK"),
+                    Some(block) => {
+                        let cells_changed =
+                            indexer.append(&block).expect("append block should be OK");
+                        if !cells_changed.is_empty() {
+                            notify_controller.notify_indexer_cells_changed(cells_changed);
+                        }
+                    }
                     None => {
                         error!("ckb node returns an empty genesis block");
                         break;
@@ -171,9 +205,10 @@ impl IndexerService {
     /// Processes that handle block cell and expect to be spawned to run in tokio runtime
     pub fn spawn_poll(&self, notify_controller: NotifyController) {
         let initial_service = self.clone();
+        let initial_notify_controller = notify_controller.clone();
         let initial_syncing = self
             .async_handle
-            .spawn_blocking(move || initial_service.try_loop_sync());
+            .spawn_blocking(move || initial_service.try_loop_sync(&initial_notify_controller));
         let stop: CancellationToken = new_tokio_exit_rx();
         let async_handle = self.async_handle.clone();
         let poll_service = self.clone();
@@ -188,8 +223,9 @@ impl IndexerService {
                 tokio::select! {
                     Ok(_) = new_block_watcher.changed() => {
                         let service = poll_service.clone();
+                        let notify_controller = notify_controller.clone();
                         if let Err(e) = async_handle.spawn_blocking(move || {
-                            service.try_loop_sync()
+                            service.try_loop_sync(&notify_controller)
                         }).await {
                             error!("ckb indexer syncing join error {:?}", e);
                         }
@@ -197,8 +233,9 @@ impl IndexerService {
                     },
                     _ = interval.tick() => {
                         let service = poll_service.clone();
+                        let notify_controller = notify_controller.clone();
                         if let Err(e) = async_handle.spawn_blocking(move || {
-                            service.try_loop_sync()
+                            service.try_loop_sync(&notify_controller)
                         }).await {
                             error!("ckb indexer syncing join error {:?}", e);
                         }
@@ -342,66 +379,13 @@ impl IndexerHandle {
                         .expect("stored OutPoint"),
                 );
 
-                if let Some(prefix) = filter_options.script_prefix.as_ref() {
-                    match filter_script_type {
-                        IndexerScriptType::Lock => {
-                            if !extract_raw_data(&output.lock())
-                                .as_slice()
-                                .starts_with(prefix)
-                            {
-                                return None;
-                            }
-                        }
-                        IndexerScriptType::Type => {
-                            if output.type_().is_none()
-                                || !extract_raw_data(&output.type_().to_opt().unwrap())
-                                    .as_slice()
-                                    .starts_with(prefix)
-                            {
-                                return None;
-                            }
-                        }
-                    }
-                }
-
-                if let Some([r0, r1]) = filter_options.script_len_range {
-                    match filter_script_type {
-                        IndexerScriptType::Lock => {
-                            let script_len = extract_raw_data(&output.lock()).len();
-                            if script_len < r0 || script_len >= r1 {
-                                return None;
-                            }
-                        }
-                        IndexerScriptType::Type => {
-                            let script_len = output
-                                .type_()
-                                .to_opt()
-                                .map(|script| extract_raw_data(&script).len())
-                                .unwrap_or_default();
-                            if script_len < r0 || script_len >= r1 {
-                                return None;
-                            }
-                        }
-                    }
-                }
-
-                if let Some([r0, r1]) = filter_options.output_data_len_range {
-                    if output_data.len() < r0 || output_data.len() >= r1 {
-                        return None;
-                    }
-                }
-
-                if let Some([r0, r1]) = filter_options.output_capacity_range {
-                    let capacity: core::Capacity = output.capacity().unpack();
-                    if capacity < r0 || capacity >= r1 {
-                        return None;
-                    }
-                }
-
-                if let Some([r0, r1]) = filter_options.block_range {
-                    if block_number < r0 || block_number >= r1 {
-                        return None;
-                    }
+                if !filter_options.match_cell(
+                    &filter_script_type,
+                    &output,
+                    &output_data,
+                    block_number,
+                ) {
+                    return None;
                 }
 
                 last_key = key.to_vec();
@@ -471,6 +455,11 @@ impl IndexerHandle {
             (None, None)
         };
 
+        let filter_expression = search_key
+            .filter_expression
+            .map(|expression| FilterExpression::new(expression, true, 0))
+            .transpose()?;
+
         let filter_script_type = match search_key.script_type {
             IndexerScriptType::Lock => IndexerScriptType::Type,
             IndexerScriptType::Type => IndexerScriptType::Lock,
@@ -483,6 +472,29 @@ impl IndexerHandle {
         let mode = IteratorMode::From(from_key.as_ref(), direction);
         let snapshot = self.store.inner().snapshot();
         let iter = snapshot.iterator(mode).skip(skip);
+        let tx_script_exists = |script_type: &IndexerScriptType,
+                                script: &packed::Script,
+                                block_number: core::BlockNumber,
+                                tx_index: u32,
+                                io_index: u32,
+                                io_type: &IndexerCellType| {
+            let cell_type = match io_type {
+                IndexerCellType::Input => indexer::CellType::Input,
+                IndexerCellType::Output => indexer::CellType::Output,
+            };
+            let key = match script_type {
+                IndexerScriptType::Lock => {
+                    Key::TxLockScript(script, block_number, tx_index, io_index, cell_type)
+                }
+                IndexerScriptType::Type => {
+                    Key::TxTypeScript(script, block_number, tx_index, io_index, cell_type)
+                }
+            };
+            snapshot
+                .get(key.into_vec())
+                .expect("get TxScript should be OK")
+                .is_some()
+        };
 
         if search_key.group_by_transaction.unwrap_or_default() {
             let mut tx_with_cells: Vec<IndexerTxWithCells> = Vec::new();
@@ -570,6 +582,21 @@ impl IndexerHandle {
                     }
                 }
 
+                if let Some(expression) = filter_expression.as_ref() {
+                    if !expression.match_tx(block_number, &|script_type, script| {
+                        tx_script_exists(
+                            script_type,
+                            script,
+                            block_number,
+                            tx_index,
+                            io_index,
+                            &io_type,
+                        )
+                    }) {
+                        continue;
+                    }
+                }
+
                 let last_tx_hash_is_same = tx_with_cells
                     .last_mut()
                     .map(|last| {
@@ -678,6 +705,21 @@ impl IndexerHandle {
                         }
                     }
 
+                    if let Some(expression) = filter_expression.as_ref() {
+                        if !expression.match_tx(block_number, &|script_type, script| {
+                            tx_script_exists(
+                                script_type,
+                                script,
+                                block_number,
+                                tx_index,
+                                io_index,
+                                &io_type,
+                            )
+                        }) {
+                            return None;
+                        }
+                    }
+
                     last_key = key.to_vec();
                     Some(IndexerTx::Ungrouped(IndexerTxWithCell {
                         tx_hash: tx_hash.unpack(),
@@ -812,6 +854,12 @@ impl IndexerHandle {
                     }
                 }
 
+                if let Some(expression) = filter_options.expression.as_ref() {
+                    if !expression.match_cell(&output, &output_data, block_number) {
+                        return None;
+                    }
+                }
+
                 Some(Unpack::<core::Capacity>::unpack(&output.capacity()).as_u64())
             })
             .sum();
@@ -829,6 +877,424 @@ impl IndexerHandle {
             .into(),
         }))
     }
+
+    /// Get cells_capacity by specified search_key at a past block.
+    ///
+    /// The live cells at the block are reconstructed by replaying the `TxLockScript` /
+    /// `TxTypeScript` records of the script, at most `MAX_RECORDS_AT_BLOCK` of them. The consumed
+    /// cells are resolved by the `TxHash` and `ConsumedOutPoint` rollback data, or by the
+    /// transactions in the chain store once the rollback data has been pruned.
+    pub fn get_cells_capacity_at_block<CS: ChainStore>(
+        &self,
+        chain_store: &CS,
+        search_key: IndexerSearchKey,
+        block_number: core::BlockNumber,
+        with_cells: bool,
+    ) -> Result<Option<IndexerCellsCapacityAtBlock>, Error> {
+        self.cells_capacity_at_block(
+            chain_store,
+            search_key,
+            block_number,
+            with_cells,
+            MAX_RECORDS_AT_BLOCK,
+        )
+    }
+
+    fn cells_capacity_at_block<CS: ChainStore>(
+        &self,
+        chain_store: &CS,
+        search_key: IndexerSearchKey,
+        block_number: core::BlockNumber,
+        with_cells: bool,
+        max_records: usize,
+    ) -> Result<Option<IndexerCellsCapacityAtBlock>, Error> {
+        if search_key.filter.is_some() || search_key.filter_expression.is_some() {
+            return Err(Error::invalid_params(
+                "search_key.filter and search_key.filter_expression are not supported at a past block",
+            ));
+        }
+        let tip_number = match self.get_indexer_tip()? {
+            Some(tip) => tip.block_number.value(),
+            None => return Ok(None),
+        };
+        if block_number > tip_number {
+            return Err(Error::invalid_params(format!(
+                "block_number should be less than or equal to the indexer tip {tip_number}"
+            )));
+        }
+        let block_hash = chain_store.get_block_hash(block_number).ok_or_else(|| {
+            Error::DB(format!("block {block_number} not found in the chain store"))
+        })?;
+
+        let (prefix, from_key, direction, skip) = build_query_options(
+            &search_key,
+            KeyPrefix::TxLockScript,
+            KeyPrefix::TxTypeScript,
+            IndexerOrder::Asc,
+            None,
+        )?;
+        let script_search_exact = matches!(
+            search_key.script_search_mode,
+            Some(IndexerScriptSearchMode::Exact)
+        );
+        let mode = IteratorMode::From(from_key.as_ref(), direction);
+        let snapshot = self.store.inner().snapshot();
+        let iter = snapshot.iterator(mode).skip(skip);
+
+        // the out point consumed by the input, read from the rollback data, or from the chain
+        // store if the rollback data has been pruned
+        let get_consumed_out_point = |tx_hash: &packed::Byte32, io_index: u32| match snapshot
+            .get(Key::TxHash(tx_hash).into_vec())
+            .expect("get TxHash should be OK")
+        {
+            Some(inputs) => inputs
+                .chunks_exact(packed::OutPoint::TOTAL_SIZE)
+                .nth(io_index as usize)
+                .map(|slice| {
+                    packed::OutPoint::from_slice(slice)
+                        .expect("stored transaction inputs out_point slice")
+                }),
+            None => chain_store
+                .get_transaction_with_info(tx_hash)
+                .and_then(|(tx, _tx_info)| tx.inputs().get(io_index as usize))
+                .map(|input| input.previous_output()),
+        };
+        // the capacity of the cell, read from the output of the transaction which created it
+        let get_output_capacity = |out_point: &packed::OutPoint| {
+            let index: u32 = out_point.index().unpack();
+            chain_store
+                .get_transaction_with_info(&out_point.tx_hash())
+                .and_then(|(tx, _tx_info)| tx.outputs().get(index as usize))
+                .map(|output| {
+                    let capacity: core::Capacity = output.capacity().unpack();
+                    capacity.as_u64()
+                })
+        };
+
+        // out_point => (created order, capacity), the capacity of a cell consumed after the block
+        // is resolved when its consumption record is replayed
+        let mut live_cells: HashMap<packed::OutPoint, (usize, Option<u64>)> = HashMap::new();
+        for (order, (key, value)) in iter
+            .take_while(|(key, _value)| key.starts_with(&prefix))
+            .enumerate()
+        {
+            if order >= max_records {
+                return Err(Error::invalid_params(format!(
+                    "the script has more than {max_records} indexed transaction records, which is not supported at a past block"
+                )));
+            }
+            if script_search_exact {
+                // Exact match mode, check key length is equal to full script len + BlockNumber (8) + TxIndex (4) + CellIndex (4) + CellType (1)
+                if key.len() != prefix.len() + 17 {
+                    continue;
+                }
+            }
+            let cell_block_number = u64::from_be_bytes(
+                key[key.len() - 17..key.len() - 9]
+                    .try_into()
+                    .expect("stored block_number"),
+            );
+            let io_index = u32::from_be_bytes(
+                key[key.len() - 5..key.len() - 1]
+                    .try_into()
+                    .expect("stored io_index"),
+            );
+            let tx_hash = packed::Byte32::from_slice(&value).expect("stored tx hash");
+
+            if *key.last().expect("stored io_type") == 0 {
+                // the records are sorted by block number, tx index and cell index, the consumed
+                // cell must have been created by a former record
+                if cell_block_number <= block_number {
+                    let out_point = get_consumed_out_point(&tx_hash, io_index).ok_or_else(|| {
+                        Error::DB(format!(
+                            "the inputs of transaction {tx_hash} are found neither in the indexer rollback data nor in the chain store"
+                        ))
+                    })?;
+                    live_cells.remove(&out_point);
+                } else if let Some(out_point) = get_consumed_out_point(&tx_hash, io_index) {
+                    // the cell is live at the block but has been consumed later
+                    if let Some((_order, capacity)) = live_cells
+                        .get_mut(&out_point)
+                        .filter(|(_order, capacity)| capacity.is_none())
+                    {
+                        *capacity = snapshot
+                            .get(Key::ConsumedOutPoint(cell_block_number, &out_point).into_vec())
+                            .expect("get ConsumedOutPoint should be OK")
+                            .map(|stored_cell| {
+                                let capacity: core::Capacity =
+                                    Value::parse_cell_value(&stored_cell).2.capacity().unpack();
+                                capacity.as_u64()
+                            });
+                    }
+                }
+            } else if cell_block_number <= block_number {
+                let out_point = packed::OutPoint::new(tx_hash, io_index);
+                let capacity = snapshot
+                    .get(Key::OutPoint(&out_point).into_vec())
+                    .expect("get OutPoint should be OK")
+                    .map(|stored_live_cell| {
+                        let capacity: core::Capacity = Value::parse_cell_value(&stored_live_cell)
+                            .2
+                            .capacity()
+                            .unpack();
+                        capacity.as_u64()
+                    });
+                live_cells.insert(out_point, (order, capacity));
+            }
+        }
+
+        let mut capacity = 0u64;
+        for (out_point, (_order, cell_capacity)) in live_cells.iter() {
+            let cell_capacity = cell_capacity
+                .or_else(|| get_output_capacity(out_point))
+                .ok_or_else(|| {
+                    Error::DB(format!(
+                        "the consumed cell {out_point} is found neither in the indexer rollback data nor in the chain store"
+                    ))
+                })?;
+            capacity = capacity.checked_add(cell_capacity).ok_or_else(|| {
+                Error::Overflow("the total capacity exceeds u64::MAX".to_string())
+            })?;
+        }
+        let cells = if with_cells {
+            let mut cells = live_cells.into_iter().collect::<Vec<_>>();
+            cells.sort_unstable_by_key(|(_out_point, (order, _capacity))| *order);
+            Some(
+                cells
+                    .into_iter()
+                    .map(|(out_point, _)| out_point.into())
+                    .collect(),
+            )
+        } else {
+            None
+        };
+
+        Ok(Some(IndexerCellsCapacityAtBlock {
+            capacity: capacity.into(),
+            cells,
+            block_hash: block_hash.unpack(),
+            block_number: block_number.into(),
+        }))
+    }
+
+    /// Get the UDT amounts of live cells by specified search_key, grouped by type script.
+    ///
+    /// Cells without type script or with less than 16 bytes data are skipped. The amounts are read
+    /// from the UDT amount index, unless the search_key has a filter, which has to be checked on
+    /// each cell.
+    pub fn get_cells_udt_amount(
+        &self,
+        search_key: IndexerSearchKey,
+    ) -> Result<Option<IndexerCellsUdtAmount>, Error> {
+        let snapshot = self.store.inner().snapshot();
+        let indexed = snapshot
+            .get(Key::UdtAmountIndexed.into_vec())
+            .expect("get UdtAmountIndexed should be OK")
+            .is_some();
+        // keyed by the type script raw data, to keep the same order as the indexer keys
+        let udt_amounts =
+            if indexed && search_key.filter.is_none() && search_key.filter_expression.is_none() {
+                self.get_indexed_udt_amounts(&snapshot, search_key)?
+            } else {
+                self.scan_udt_amounts(&snapshot, search_key)?
+            };
+
+        let tip_mode = IteratorMode::From(&[KeyPrefix::Header as u8 + 1], Direction::Reverse);
+        let mut tip_iter = snapshot.iterator(tip_mode);
+        let (key, _value) = match tip_iter.next() {
+            Some(tip) => tip,
+            None => return Ok(None),
+        };
+        Ok(Some(IndexerCellsUdtAmount {
+            udt_amounts: udt_amounts
+                .into_iter()
+                .map(|(type_script_raw_data, udt_amount)| {
+                    let type_script = parse_raw_data(&type_script_raw_data);
+                    let amount = udt_amount.amount().ok_or_else(|| {
+                        Error::Overflow(format!(
+                            "the total amount of the UDT {type_script} exceeds u128::MAX"
+                        ))
+                    })?;
+                    Ok(IndexerUdtAmount {
+                        type_script: type_script.into(),
+                        amount: amount.into(),
+                        cells_count: udt_amount.cells_count().into(),
+                    })
+                })
+                .collect::<Result<_, Error>>()?,
+            block_hash: packed::Byte32::from_slice(&key[9..41])
+                .expect("stored block key")
+                .unpack(),
+            block_number: core::BlockNumber::from_be_bytes(
+                key[1..9].try_into().expect("stored block key"),
+            )
+            .into(),
+        }))
+    }
+
+    // Reads the UDT amounts maintained by the indexer, minus the cells consumed by pooled txs.
+    fn get_indexed_udt_amounts(
+        &self,
+        snapshot: &Snapshot,
+        search_key: IndexerSearchKey,
+    ) -> Result<BTreeMap<Vec<u8>, UdtAmount>, Error> {
+        let (prefix, from_key, direction, _skip) = build_query_options(
+            &search_key,
+            KeyPrefix::LockUdtAmount,
+            KeyPrefix::TypeUdtAmount,
+            IndexerOrder::Asc,
+            None,
+        )?;
+        let script_raw_data_len = prefix.len() - 1;
+        let script_search_exact = matches!(
+            search_key.script_search_mode,
+            Some(IndexerScriptSearchMode::Exact)
+        );
+
+        let mut udt_amounts: BTreeMap<Vec<u8>, UdtAmount> = BTreeMap::new();
+        let mode = IteratorMode::From(from_key.as_ref(), direction);
+        for (key, value) in snapshot
+            .iterator(mode)
+            .take_while(|(key, _value)| key.starts_with(&prefix))
+        {
+            let type_script_raw_data = match search_key.script_type {
+                IndexerScriptType::Lock => {
+                    let lock_script_len = u32::from_be_bytes(
+                        key[key.len() - 4..]
+                            .try_into()
+                            .expect("stored lock script len"),
+                    ) as usize;
+                    // the searched prefix must not run into the type script
+                    if lock_script_len < script_raw_data_len
+                        || (script_search_exact && lock_script_len != script_raw_data_len)
+                    {
+                        continue;
+                    }
+                    &key[1 + lock_script_len..key.len() - 4]
+                }
+                IndexerScriptType::Type => {
+                    if script_search_exact && key.len() != prefix.len() {
+                        continue;
+                    }
+                    &key[1..]
+                }
+            };
+            udt_amounts
+                .entry(type_script_raw_data.to_vec())
+                .or_default()
+                .add(&Value::parse_udt_amount_value(&value));
+        }
+
+        if let Some(pool) = self.pool.as_ref() {
+            let pool = pool.read().expect("acquire lock");
+            for out_point in pool.dead_cells() {
+                let (_block_number, _tx_index, output, output_data) = match snapshot
+                    .get(Key::OutPoint(out_point).into_vec())
+                    .expect("get OutPoint should be OK")
+                {
+                    Some(stored_live_cell) => Value::parse_cell_value(&stored_live_cell),
+                    None => continue,
+                };
+                let (type_script, amount) = match (
+                    output.type_().to_opt(),
+                    extract_udt_amount(&output_data.raw_data()),
+                ) {
+                    (Some(type_script), Some(amount)) => (type_script, amount),
+                    _ => continue,
+                };
+                let script_raw_data = match search_key.script_type {
+                    IndexerScriptType::Lock => extract_raw_data(&output.lock()),
+                    IndexerScriptType::Type => extract_raw_data(&type_script),
+                };
+                let matched = if script_search_exact {
+                    script_raw_data == prefix[1..]
+                } else {
+                    script_raw_data.starts_with(&prefix[1..])
+                };
+                if !matched {
+                    continue;
+                }
+                if let Some(udt_amount) = udt_amounts.get_mut(&extract_raw_data(&type_script)) {
+                    let mut consumed = UdtAmount::default();
+                    consumed.add_cell(amount);
+                    udt_amount.sub(&consumed);
+                }
+            }
+        }
+        udt_amounts.retain(|_type_script, udt_amount| udt_amount.cells_count() > 0);
+        Ok(udt_amounts)
+    }
+
+    // Adds up the UDT amounts of the live cells matching the search_key one by one.
+    fn scan_udt_amounts(
+        &self,
+        snapshot: &Snapshot,
+        search_key: IndexerSearchKey,
+    ) -> Result<BTreeMap<Vec<u8>, UdtAmount>, Error> {
+        let (prefix, from_key, direction, skip) = build_query_options(
+            &search_key,
+            KeyPrefix::CellLockScript,
+            KeyPrefix::CellTypeScript,
+            IndexerOrder::Asc,
+            None,
+        )?;
+        let filter_script_type = match search_key.script_type {
+            IndexerScriptType::Lock => IndexerScriptType::Type,
+            IndexerScriptType::Type => IndexerScriptType::Lock,
+        };
+        let script_search_exact = matches!(
+            search_key.script_search_mode,
+            Some(IndexerScriptSearchMode::Exact)
+        );
+        let filter_options: FilterOptions = search_key.try_into()?;
+        let mode = IteratorMode::From(from_key.as_ref(), direction);
+        let iter = snapshot.iterator(mode).skip(skip);
+        let pool = self
+            .pool
+            .as_ref()
+            .map(|pool| pool.read().expect("acquire lock"));
+
+        let mut udt_amounts: BTreeMap<Vec<u8>, UdtAmount> = BTreeMap::new();
+        for (key, value) in iter.take_while(|(key, _value)| key.starts_with(&prefix)) {
+            if script_search_exact {
+                // Exact match mode, check key length is equal to full script len + BlockNumber (8) + TxIndex (4) + OutputIndex (4)
+                if key.len() != prefix.len() + 16 {
+                    continue;
+                }
+            }
+            let tx_hash = packed::Byte32::from_slice(value.as_ref()).expect("stored tx hash");
+            let index = u32::from_be_bytes(key[key.len() - 4..].try_into().expect("stored index"));
+            let out_point = packed::OutPoint::new(tx_hash, index);
+            if pool
+                .as_ref()
+                .map(|pool| pool.is_consumed_by_pool_tx(&out_point))
+                .unwrap_or_default()
+            {
+                continue;
+            }
+            let (block_number, _tx_index, output, output_data) = Value::parse_cell_value(
+                &snapshot
+                    .get(Key::OutPoint(&out_point).into_vec())
+                    .expect("get OutPoint should be OK")
+                    .expect("stored OutPoint"),
+            );
+            if !filter_options.match_cell(&filter_script_type, &output, &output_data, block_number)
+            {
+                continue;
+            }
+
+            if let (Some(type_script), Some(amount)) = (
+                output.type_().to_opt(),
+                extract_udt_amount(&output_data.raw_data()),
+            ) {
+                udt_amounts
+                    .entry(extract_raw_data(&type_script))
+                    .or_default()
+                    .add_cell(amount);
+            }
+        }
+        Ok(udt_amounts)
+    }
 }
 
 const MAX_PREFIX_SEARCH_SIZE: usize = u16::max_value() as usize;
@@ -884,15 +1350,100 @@ struct FilterOptions {
     output_data_len_range: Option<[usize; 2]>,
     output_capacity_range: Option<[core::Capacity; 2]>,
     block_range: Option<[core::BlockNumber; 2]>,
+    expression: Option<FilterExpression>,
     with_data: bool,
 }
 
+impl FilterOptions {
+    // Checks the filter conditions on the cell, except the script searched by the key prefix.
+    fn match_cell(
+        &self,
+        filter_script_type: &IndexerScriptType,
+        output: &packed::CellOutput,
+        output_data: &packed::Bytes,
+        block_number: core::BlockNumber,
+    ) -> bool {
+        if let Some(prefix) = self.script_prefix.as_ref() {
+            match filter_script_type {
+                IndexerScriptType::Lock => {
+                    if !extract_raw_data(&output.lock())
+                        .as_slice()
+                        .starts_with(prefix)
+                    {
+                        return false;
+                    }
+                }
+                IndexerScriptType::Type => {
+                    if output.type_().is_none()
+                        || !extract_raw_data(&output.type_().to_opt().unwrap())
+                            .as_slice()
+                            .starts_with(prefix)
+                    {
+                        return false;
+                    }
+                }
+            }
+        }
+
+        if let Some([r0, r1]) = self.script_len_range {
+            match filter_script_type {
+                IndexerScriptType::Lock => {
+                    let script_len = extract_raw_data(&output.lock()).len();
+                    if script_len < r0 || script_len >= r1 {
+                        return false;
+                    }
+                }
+                IndexerScriptType::Type => {
+                    let script_len = output
+                        .type_()
+                        .to_opt()
+                        .map(|script| extract_raw_data(&script).len())
+                        .unwrap_or_default();
+                    if script_len < r0 || script_len >= r1 {
+                        return false;
+                    }
+                }
+            }
+        }
+
+        if let Some([r0, r1]) = self.output_data_len_range {
+            if output_data.len() < r0 || output_data.len() >= r1 {
+                return false;
+            }
+        }
+
+        if let Some([r0, r1]) = self.output_capacity_range {
+            let capacity: core::Capacity = output.capacity().unpack();
+            if capacity < r0 || capacity >= r1 {
+                return false;
+            }
+        }
+
+        if let Some([r0, r1]) = self.block_range {
+            if block_number < r0 || block_number >= r1 {
+                return false;
+            }
+        }
+
+        if let Some(expression) = self.expression.as_ref() {
+            if !expression.match_cell(output, output_data, block_number) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
 impl TryInto<FilterOptions> for IndexerSearchKey {
     type Error = Error;
 
     fn try_into(self) -> Result<FilterOptions, Error> {
         let IndexerSearchKey {
-            filter, with_data, ..
+            filter,
+            filter_expression,
+            with_data,
+            ..
         } = self;
         let filter = filter.unwrap_or_default();
         let script_prefix = if let Some(script) = filter.script {
@@ -932,22 +1483,202 @@ impl TryInto<FilterOptions> for IndexerSearchKey {
             .block_range
             .map(|r| [r.start().into(), r.end().into()]);
 
-        Ok(FilterOptions {
-            script_prefix,
-            script_len_range,
-            output_data_len_range,
-            output_capacity_range,
-            block_range,
-            with_data: with_data.unwrap_or(true),
-        })
+        let expression = filter_expression
+            .map(|expression| FilterExpression::new(expression, false, 0))
+            .transpose()?;
+
+        Ok(FilterOptions {
+            script_prefix,
+            script_len_range,
+            output_data_len_range,
+            output_capacity_range,
+            block_range,
+            expression,
+            with_data: with_data.unwrap_or(true),
+        })
+    }
+}
+
+const MAX_FILTER_EXPRESSION_DEPTH: usize = 16;
+
+// The validated form of `IndexerFilterExpression`
+enum FilterExpression {
+    And(Vec<FilterExpression>),
+    Or(Vec<FilterExpression>),
+    Not(Box<FilterExpression>),
+    Script {
+        script_type: IndexerScriptType,
+        script: Option<packed::Script>,
+        exact: bool,
+    },
+    OutputDataLenRange([usize; 2]),
+    OutputCapacityRange([core::Capacity; 2]),
+    BlockRange([core::BlockNumber; 2]),
+}
+
+impl FilterExpression {
+    // `for_transactions` restricts the expression to the conditions which can be checked against
+    // the `TxLockScript` / `TxTypeScript` records, since the inputs' cells are not kept.
+    fn new(
+        expression: IndexerFilterExpression,
+        for_transactions: bool,
+        depth: usize,
+    ) -> Result<Self, Error> {
+        if depth >= MAX_FILTER_EXPRESSION_DEPTH {
+            return Err(Error::invalid_params(format!(
+                "search_key.filter_expression depth should be less than {MAX_FILTER_EXPRESSION_DEPTH}"
+            )));
+        }
+        let new_all = |expressions: Vec<IndexerFilterExpression>| {
+            expressions
+                .into_iter()
+                .map(|expression| Self::new(expression, for_transactions, depth + 1))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let expression = match expression {
+            IndexerFilterExpression::And(expressions) => Self::And(new_all(expressions)?),
+            IndexerFilterExpression::Or(expressions) => Self::Or(new_all(expressions)?),
+            IndexerFilterExpression::Not(expression) => Self::Not(Box::new(Self::new(
+                *expression,
+                for_transactions,
+                depth + 1,
+            )?)),
+            IndexerFilterExpression::Script(filter) => {
+                let script: Option<packed::Script> = filter.script.map(Into::into);
+                if let Some(script) = script.as_ref() {
+                    if script.args().len() > MAX_PREFIX_SEARCH_SIZE {
+                        return Err(Error::invalid_params(format!(
+                            "search_key.filter_expression script.args len should be less than {MAX_PREFIX_SEARCH_SIZE}"
+                        )));
+                    }
+                }
+                let exact = matches!(
+                    filter.script_search_mode,
+                    Some(IndexerScriptSearchMode::Exact)
+                );
+                if for_transactions && (script.is_none() || !exact) {
+                    return Err(Error::invalid_params(
+                        "search_key.filter_expression only supports non-null script with exact search mode in get_transactions",
+                    ));
+                }
+                Self::Script {
+                    script_type: filter.script_type,
+                    script,
+                    exact,
+                }
+            }
+            IndexerFilterExpression::OutputDataLenRange(range) => {
+                if for_transactions {
+                    return Err(Error::invalid_params(
+                        "search_key.filter_expression doesn't support output_data_len_range in get_transactions",
+                    ));
+                }
+                Self::OutputDataLenRange([
+                    Into::<u64>::into(range.start()) as usize,
+                    Into::<u64>::into(range.end()) as usize,
+                ])
+            }
+            IndexerFilterExpression::OutputCapacityRange(range) => {
+                if for_transactions {
+                    return Err(Error::invalid_params(
+                        "search_key.filter_expression doesn't support output_capacity_range in get_transactions",
+                    ));
+                }
+                Self::OutputCapacityRange([
+                    core::Capacity::shannons(range.start().into()),
+                    core::Capacity::shannons(range.end().into()),
+                ])
+            }
+            IndexerFilterExpression::BlockRange(range) => {
+                Self::BlockRange([range.start().into(), range.end().into()])
+            }
+        };
+        Ok(expression)
+    }
+
+    fn match_cell(
+        &self,
+        output: &packed::CellOutput,
+        output_data: &packed::Bytes,
+        block_number: core::BlockNumber,
+    ) -> bool {
+        match self {
+            Self::And(expressions) => expressions
+                .iter()
+                .all(|expression| expression.match_cell(output, output_data, block_number)),
+            Self::Or(expressions) => expressions
+                .iter()
+                .any(|expression| expression.match_cell(output, output_data, block_number)),
+            Self::Not(expression) => !expression.match_cell(output, output_data, block_number),
+            Self::Script {
+                script_type,
+                script,
+                exact,
+            } => {
+                let cell_script = match script_type {
+                    IndexerScriptType::Lock => Some(output.lock()),
+                    IndexerScriptType::Type => output.type_().to_opt(),
+                };
+                match (cell_script, script) {
+                    (None, None) => true,
+                    (Some(cell_script), Some(script)) => {
+                        if *exact {
+                            cell_script.as_slice() == script.as_slice()
+                        } else {
+                            extract_raw_data(&cell_script).starts_with(&extract_raw_data(script))
+                        }
+                    }
+                    _ => false,
+                }
+            }
+            Self::OutputDataLenRange([r0, r1]) => {
+                output_data.len() >= *r0 && output_data.len() < *r1
+            }
+            Self::OutputCapacityRange([r0, r1]) => {
+                let capacity: core::Capacity = output.capacity().unpack();
+                capacity >= *r0 && capacity < *r1
+            }
+            Self::BlockRange([r0, r1]) => block_number >= *r0 && block_number < *r1,
+        }
+    }
+
+    // `script_exists` checks whether the transaction input or output being matched has the script
+    fn match_tx<F>(&self, block_number: core::BlockNumber, script_exists: &F) -> bool
+    where
+        F: Fn(&IndexerScriptType, &packed::Script) -> bool,
+    {
+        match self {
+            Self::And(expressions) => expressions
+                .iter()
+                .all(|expression| expression.match_tx(block_number, script_exists)),
+            Self::Or(expressions) => expressions
+                .iter()
+                .any(|expression| expression.match_tx(block_number, script_exists)),
+            Self::Not(expression) => !expression.match_tx(block_number, script_exists),
+            Self::Script {
+                script_type,
+                script: Some(script),
+                ..
+            } => script_exists(script_type, script),
+            Self::BlockRange([r0, r1]) => block_number >= *r0 && block_number < *r1,
+            // rejected when building the expression for transactions
+            Self::Script { script: None, .. }
+            | Self::OutputDataLenRange(_)
+            | Self::OutputCapacityRange(_) => false,
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::store::RocksdbStore;
-    use ckb_jsonrpc_types::{IndexerRange, IndexerSearchKeyFilter};
+    use crate::store::{Batch, RocksdbStore};
+    use ckb_db::RocksDB;
+    use ckb_db_schema::COLUMNS;
+    use ckb_jsonrpc_types::{
+        IndexerFilterExpression, IndexerRange, IndexerScriptFilter, IndexerSearchKeyFilter,
+    };
+    use ckb_store::ChainDB;
     use ckb_types::{
         bytes::Bytes,
         core::{
@@ -1267,6 +1998,100 @@ mod tests {
             "total size should be cellbase cells count (empty type script)"
         );
 
+        // lock = lock_script1 and (type = type_script1 or type = null) and capacity >= 1000 CKB
+        // and block number in [100, 200)
+        let expression = || {
+            IndexerFilterExpression::And(vec![
+                IndexerFilterExpression::Or(vec![
+                    IndexerFilterExpression::Script(IndexerScriptFilter {
+                        script_type: IndexerScriptType::Type,
+                        script: Some(type_script1.clone().into()),
+                        script_search_mode: Some(IndexerScriptSearchMode::Exact),
+                    }),
+                    IndexerFilterExpression::Script(IndexerScriptFilter {
+                        script_type: IndexerScriptType::Type,
+                        script: None,
+                        script_search_mode: None,
+                    }),
+                ]),
+                IndexerFilterExpression::OutputCapacityRange(IndexerRange::new(
+                    capacity_bytes!(1000).as_u64(),
+                    u64::MAX,
+                )),
+                IndexerFilterExpression::BlockRange(IndexerRange::new(100, 200)),
+            ])
+        };
+        let expression_cells_page_1 = rpc
+            .get_cells(
+                IndexerSearchKey {
+                    script: lock_script1.clone().into(),
+                    filter_expression: Some(expression()),
+                    ..Default::default()
+                },
+                IndexerOrder::Asc,
+                60.into(),
+                None,
+            )
+            .unwrap();
+        let expression_cells_page_2 = rpc
+            .get_cells(
+                IndexerSearchKey {
+                    script: lock_script1.clone().into(),
+                    filter_expression: Some(expression()),
+                    ..Default::default()
+                },
+                IndexerOrder::Asc,
+                60.into(),
+                Some(expression_cells_page_1.last_cursor),
+            )
+            .unwrap();
+
+        assert_eq!(
+            100,
+            expression_cells_page_1.objects.len() + expression_cells_page_2.objects.len(),
+            "total size should be filtered cellbase cells (100~199)"
+        );
+
+        let not_null_type_script_cells = rpc
+            .get_cells(
+                IndexerSearchKey {
+                    script: lock_script1.clone().into(),
+                    filter_expression: Some(IndexerFilterExpression::Not(Box::new(
+                        IndexerFilterExpression::Script(IndexerScriptFilter {
+                            script_type: IndexerScriptType::Type,
+                            script: None,
+                            script_search_mode: None,
+                        }),
+                    ))),
+                    ..Default::default()
+                },
+                IndexerOrder::Asc,
+                150.into(),
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(
+            1,
+            not_null_type_script_cells.objects.len(),
+            "total size should be the last block live cell with type script"
+        );
+
+        let capacity = rpc
+            .get_cells_capacity(IndexerSearchKey {
+                script: lock_script1.clone().into(),
+                filter_expression: Some(expression()),
+                ..Default::default()
+            })
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            100 * 1000 * 100000000,
+            capacity.capacity.value(),
+            "cells capacity should be filtered cellbase cells capacity (100~199)"
+        );
+
         // test get_transactions rpc
         let txs_page_1 = rpc
             .get_transactions(
@@ -1360,6 +2185,66 @@ mod tests {
             "total size should be filtered blocks count * 3 (100~199 * 3)"
         );
 
+        let expression = || {
+            IndexerFilterExpression::And(vec![
+                IndexerFilterExpression::Script(IndexerScriptFilter {
+                    script_type: IndexerScriptType::Type,
+                    script: Some(type_script1.clone().into()),
+                    script_search_mode: Some(IndexerScriptSearchMode::Exact),
+                }),
+                IndexerFilterExpression::BlockRange(IndexerRange::new(100, 200)),
+            ])
+        };
+        let expression_txs_page_1 = rpc
+            .get_transactions(
+                IndexerSearchKey {
+                    script: lock_script1.clone().into(),
+                    filter_expression: Some(expression()),
+                    ..Default::default()
+                },
+                IndexerOrder::Asc,
+                150.into(),
+                None,
+            )
+            .unwrap();
+        let expression_txs_page_2 = rpc
+            .get_transactions(
+                IndexerSearchKey {
+                    script: lock_script1.clone().into(),
+                    filter_expression: Some(expression()),
+                    ..Default::default()
+                },
+                IndexerOrder::Asc,
+                150.into(),
+                Some(expression_txs_page_1.last_cursor),
+            )
+            .unwrap();
+
+        assert_eq!(
+            200,
+            expression_txs_page_1.objects.len() + expression_txs_page_2.objects.len(),
+            "total size should be filtered blocks count * 2 (100~199 * 2, type script tx input and output)"
+        );
+
+        assert!(
+            rpc.get_transactions(
+                IndexerSearchKey {
+                    script: lock_script1.clone().into(),
+                    filter_expression: Some(IndexerFilterExpression::Script(IndexerScriptFilter {
+                        script_type: IndexerScriptType::Type,
+                        script: None,
+                        script_search_mode: None,
+                    })),
+                    ..Default::default()
+                },
+                IndexerOrder::Asc,
+                150.into(),
+                None,
+            )
+            .is_err(),
+            "null script is not supported by get_transactions"
+        );
+
         // test get_transactions rpc group by tx hash
         let txs_page_1 = rpc
             .get_transactions(
@@ -1797,4 +2682,431 @@ mod tests {
             capacity.capacity.value()
         );
     }
+
+    #[test]
+    fn cells_capacity_at_block_rpc() {
+        let store = new_store("cells_capacity_at_block_rpc");
+        let indexer = Indexer::new(store.clone(), 10, 1, None, CustomFilters::new(None, None));
+        let rpc = IndexerHandle { store, pool: None };
+        // keep 1 block of rollback data only, the data of the blocks older than keep_num is
+        // pruned, the consumed cells are resolved by the chain store
+        let pruned_store = new_store("cells_capacity_at_block_rpc_pruned");
+        let pruned_indexer = Indexer::new(
+            pruned_store.clone(),
+            1,
+            1,
+            None,
+            CustomFilters::new(None, None),
+        );
+        let pruned_rpc = IndexerHandle {
+            store: pruned_store,
+            pool: None,
+        };
+        let tmp_dir = tempfile::TempDir::new().unwrap();
+        let chain_db = ChainDB::new(RocksDB::open_in(&tmp_dir, COLUMNS), Default::default());
+
+        // setup test data
+        let lock_script1 = ScriptBuilder::default()
+            .code_hash(H256(rand::random()).pack())
+            .hash_type(ScriptHashType::Type.into())
+            .args(Bytes::from(b"lock_script1".to_vec()).pack())
+            .build();
+
+        let lock_script2 = ScriptBuilder::default()
+            .code_hash(H256(rand::random()).pack())
+            .hash_type(ScriptHashType::Type.into())
+            .args(Bytes::from(b"lock_script2".to_vec()).pack())
+            .build();
+
+        let output = |capacity: Capacity, lock: &Script| {
+            CellOutputBuilder::default()
+                .capacity(capacity.pack())
+                .lock(lock.clone())
+                .build()
+        };
+
+        // block 0: lock_script1 1000
+        // block 1: lock_script1 1000 -> lock_script1 300 + lock_script2 700
+        // block 2: lock_script1 300 -> lock_script2 300
+        // block 3 ~ 5: empty
+        let tx0 = TransactionBuilder::default()
+            .input(CellInput::new_cellbase_input(0))
+            .output(output(capacity_bytes!(1000), &lock_script1))
+            .output_data(Default::default())
+            .build();
+        let tx1 = TransactionBuilder::default()
+            .input(CellInput::new(OutPoint::new(tx0.hash(), 0), 0))
+            .output(output(capacity_bytes!(300), &lock_script1))
+            .output(output(capacity_bytes!(700), &lock_script2))
+            .outputs_data(vec![Default::default(); 2])
+            .build();
+        let tx2 = TransactionBuilder::default()
+            .input(CellInput::new(OutPoint::new(tx1.hash(), 0), 0))
+            .output(output(capacity_bytes!(300), &lock_script2))
+            .output_data(Default::default())
+            .build();
+
+        let mut blocks: Vec<core::BlockView> = Vec::new();
+        for (number, tx) in [Some(tx0), Some(tx1), Some(tx2), None, None, None]
+            .into_iter()
+            .enumerate()
+        {
+            let cellbase = TransactionBuilder::default()
+                .input(CellInput::new_cellbase_input(number as u64))
+                .witness(Script::default().into_witness())
+                .build();
+            let header = HeaderBuilder::default()
+                .number((number as u64).pack())
+                .parent_hash(blocks.last().map(|block| block.hash()).unwrap_or_default())
+                .build();
+            let block = BlockBuilder::default()
+                .transaction(cellbase)
+                .transactions(tx)
+                .header(header)
+                .build();
+            let txn = chain_db.begin_transaction();
+            txn.insert_block(&block).unwrap();
+            txn.attach_block(&block).unwrap();
+            txn.commit().unwrap();
+            indexer.append(&block).unwrap();
+            pruned_indexer.append(&block).unwrap();
+            blocks.push(block);
+        }
+
+        let search_key = |script: &Script| IndexerSearchKey {
+            script: script.clone().into(),
+            ..Default::default()
+        };
+        for (block_number, lock1_capacity, lock2_capacity) in [
+            (0, capacity_bytes!(1000), Capacity::zero()),
+            (1, capacity_bytes!(300), capacity_bytes!(700)),
+            (2, Capacity::zero(), capacity_bytes!(1000)),
+            (5, Capacity::zero(), capacity_bytes!(1000)),
+        ] {
+            let capacity = rpc
+                .get_cells_capacity_at_block(
+                    &chain_db,
+                    search_key(&lock_script1),
+                    block_number,
+                    false,
+                )
+                .unwrap()
+                .unwrap();
+            assert_eq!(lock1_capacity.as_u64(), capacity.capacity.value());
+            assert_eq!(
+                Unpack::<H256>::unpack(&blocks[block_number as usize].hash()),
+                capacity.block_hash
+            );
+            assert!(capacity.cells.is_none());
+
+            let capacity = rpc
+                .get_cells_capacity_at_block(
+                    &chain_db,
+                    search_key(&lock_script2),
+                    block_number,
+                    false,
+                )
+                .unwrap()
+                .unwrap();
+            assert_eq!(lock2_capacity.as_u64(), capacity.capacity.value());
+
+            for (script, script_capacity) in [
+                (&lock_script1, lock1_capacity),
+                (&lock_script2, lock2_capacity),
+            ] {
+                let capacity = pruned_rpc
+                    .get_cells_capacity_at_block(&chain_db, search_key(script), block_number, false)
+                    .unwrap()
+                    .unwrap();
+                assert_eq!(script_capacity.as_u64(), capacity.capacity.value());
+            }
+        }
+
+        let capacity = rpc
+            .get_cells_capacity_at_block(&chain_db, search_key(&lock_script2), 2, true)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            Some(vec![
+                OutPoint::new(blocks[1].transactions()[1].hash(), 1).into(),
+                OutPoint::new(blocks[2].transactions()[1].hash(), 0).into(),
+            ]),
+            capacity.cells
+        );
+
+        // the block is higher than the indexer tip
+        assert!(rpc
+            .get_cells_capacity_at_block(&chain_db, search_key(&lock_script1), 6, false)
+            .is_err());
+
+        // lock_script1 has 4 records: 2 outputs and 2 inputs
+        assert!(rpc
+            .cells_capacity_at_block(&chain_db, search_key(&lock_script1), 5, false, 4)
+            .is_ok());
+        assert!(matches!(
+            rpc.cells_capacity_at_block(&chain_db, search_key(&lock_script1), 5, false, 3),
+            Err(Error::Params(_))
+        ));
+
+        // the rollback data of block 0 has been pruned, the cell consumed by tx1 is read from the
+        // chain store
+        let capacity = pruned_rpc
+            .get_cells_capacity_at_block(&chain_db, search_key(&lock_script1), 0, true)
+            .unwrap()
+            .unwrap();
+        assert_eq!(capacity_bytes!(1000).as_u64(), capacity.capacity.value());
+        assert_eq!(
+            Some(vec![
+                OutPoint::new(blocks[0].transactions()[1].hash(), 0).into()
+            ]),
+            capacity.cells
+        );
+
+        // the rollback data has been pruned and tx2 is not in the chain store
+        let txn = chain_db.begin_transaction();
+        txn.detach_block(&blocks[2]).unwrap();
+        txn.commit().unwrap();
+        assert!(matches!(
+            pruned_rpc.get_cells_capacity_at_block(&chain_db, search_key(&lock_script1), 3, false),
+            Err(Error::DB(_))
+        ));
+        // the rollback data of tx2 is still kept by the indexer
+        let capacity = rpc
+            .get_cells_capacity_at_block(&chain_db, search_key(&lock_script1), 3, false)
+            .unwrap()
+            .unwrap();
+        assert_eq!(0, capacity.capacity.value());
+    }
+
+    #[test]
+    fn udt_amount_rpc() {
+        let store = new_store("udt_amount_rpc");
+        let indexer = Indexer::new(store.clone(), 10, 100, None, CustomFilters::new(None, None));
+        let rpc = IndexerHandle { store, pool: None };
+
+        // setup test data
+        let lock_script1 = ScriptBuilder::default()
+            .code_hash(H256(rand::random()).pack())
+            .hash_type(ScriptHashType::Type.into())
+            .args(Bytes::from(b"lock_script1".to_vec()).pack())
+            .build();
+
+        let udt_script1 = ScriptBuilder::default()
+            .code_hash(H256(rand::random()).pack())
+            .hash_type(ScriptHashType::Type.into())
+            .args(Bytes::from(b"udt_script1".to_vec()).pack())
+            .build();
+
+        let udt_script2 = ScriptBuilder::default()
+            .code_hash(udt_script1.code_hash())
+            .hash_type(ScriptHashType::Type.into())
+            .args(Bytes::from(b"udt_script2".to_vec()).pack())
+            .build();
+
+        let udt_cell = |type_script: &Script, data: Vec<u8>| {
+            (
+                CellOutputBuilder::default()
+                    .capacity(capacity_bytes!(142).pack())
+                    .lock(lock_script1.clone())
+                    .type_(Some(type_script.clone()).pack())
+                    .build(),
+                Bytes::from(data).pack(),
+            )
+        };
+
+        let mut pre_block = BlockBuilder::default()
+            .transaction(
+                TransactionBuilder::default()
+                    .input(CellInput::new_cellbase_input(0))
+                    .witness(Script::default().into_witness())
+                    .build(),
+            )
+            .header(HeaderBuilder::default().number(0.pack()).build())
+            .build();
+        indexer.append(&pre_block).unwrap();
+
+        let total_blocks = 10u64;
+        for i in 1..=total_blocks {
+            // xUDT cells may have extra data after the amount
+            let (outputs, outputs_data): (Vec<_>, Vec<_>) = vec![
+                udt_cell(&udt_script1, 100u128.to_le_bytes().to_vec()),
+                udt_cell(
+                    &udt_script2,
+                    [(u128::MAX / total_blocks as u128).to_le_bytes(), [0; 16]].concat(),
+                ),
+                // less than 16 bytes data, skipped
+                udt_cell(&udt_script1, vec![0; 15]),
+            ]
+            .into_iter()
+            .unzip();
+            let tx = TransactionBuilder::default()
+                .input(CellInput::new(
+                    OutPoint::new(H256(rand::random()).pack(), 0),
+                    0,
+                ))
+                .outputs(outputs)
+                .outputs_data(outputs_data)
+                .build();
+
+            pre_block = BlockBuilder::default()
+                .transaction(
+                    TransactionBuilder::default()
+                        .input(CellInput::new_cellbase_input(i))
+                        .witness(Script::default().into_witness())
+                        .build(),
+                )
+                .transaction(tx)
+                .header(
+                    HeaderBuilder::default()
+                        .number(i.pack())
+                        .parent_hash(pre_block.hash())
+                        .build(),
+                )
+                .build();
+            indexer.append(&pre_block).unwrap();
+        }
+        // index the cells appended before the UDT amount index is marked as built, in chunks
+        indexer.index_udt_amounts_in_chunks(4).unwrap();
+
+        // resume from the last indexed cell, the indexed cells are not counted again
+        let udt_amount_key = Key::TypeUdtAmount(&udt_script1).into_vec();
+        let indexed_udt_amount = rpc.store.get(&udt_amount_key).unwrap();
+        assert!(indexed_udt_amount.is_some());
+        let key_prefix_out_point = [KeyPrefix::OutPoint as u8];
+        let (last_out_point_key, _value) = rpc
+            .store
+            .iter(key_prefix_out_point, IteratorDirection::Forward)
+            .unwrap()
+            .take_while(|(key, _value)| key.starts_with(&key_prefix_out_point))
+            .last()
+            .unwrap();
+        let mut batch = rpc.store.batch().unwrap();
+        batch.delete(Key::UdtAmountIndexed.into_vec()).unwrap();
+        batch
+            .put_kv(Key::UdtAmountIndexing, last_out_point_key.to_vec())
+            .unwrap();
+        batch.commit().unwrap();
+        indexer.index_udt_amounts().unwrap();
+        assert_eq!(indexed_udt_amount, rpc.store.get(&udt_amount_key).unwrap());
+        assert!(rpc
+            .store
+            .get(Key::UdtAmountIndexing.into_vec())
+            .unwrap()
+            .is_none());
+
+        // test get_cells_udt_amount rpc by lock script, grouped by type script
+        let udt_amount = rpc
+            .get_cells_udt_amount(IndexerSearchKey {
+                script: lock_script1.clone().into(),
+                ..Default::default()
+            })
+            .unwrap()
+            .unwrap();
+        assert_eq!(total_blocks, udt_amount.block_number.value());
+        assert_eq!(2, udt_amount.udt_amounts.len());
+        assert_eq!(
+            ckb_jsonrpc_types::Script::from(udt_script1.clone()),
+            udt_amount.udt_amounts[0].type_script
+        );
+        assert_eq!(
+            100 * total_blocks as u128,
+            udt_amount.udt_amounts[0].amount.value()
+        );
+        assert_eq!(total_blocks, udt_amount.udt_amounts[0].cells_count.value());
+        assert_eq!(
+            u128::MAX / total_blocks as u128 * total_blocks as u128,
+            udt_amount.udt_amounts[1].amount.value()
+        );
+
+        // test get_cells_udt_amount rpc by type script with block range filter
+        let udt_amount = rpc
+            .get_cells_udt_amount(IndexerSearchKey {
+                script: udt_script1.into(),
+                script_type: IndexerScriptType::Type,
+                script_search_mode: Some(IndexerScriptSearchMode::Exact),
+                filter: Some(IndexerSearchKeyFilter {
+                    block_range: Some(IndexerRange::new(3, 8)),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            })
+            .unwrap()
+            .unwrap();
+        assert_eq!(1, udt_amount.udt_amounts.len());
+        assert_eq!(500, udt_amount.udt_amounts[0].amount.value());
+        assert_eq!(5, udt_amount.udt_amounts[0].cells_count.value());
+
+        // test get_cells_udt_amount rpc by type script prefix, grouped by type script
+        let udt_amount = rpc
+            .get_cells_udt_amount(IndexerSearchKey {
+                script: ScriptBuilder::default()
+                    .code_hash(udt_script1.code_hash())
+                    .hash_type(ScriptHashType::Type.into())
+                    .build()
+                    .into(),
+                script_type: IndexerScriptType::Type,
+                ..Default::default()
+            })
+            .unwrap()
+            .unwrap();
+        assert_eq!(2, udt_amount.udt_amounts.len());
+        assert_eq!(
+            100 * total_blocks as u128,
+            udt_amount.udt_amounts[0].amount.value()
+        );
+        assert_eq!(total_blocks, udt_amount.udt_amounts[1].cells_count.value());
+
+        // the total amount of udt_script2 exceeds u128::MAX
+        let (outputs, outputs_data): (Vec<_>, Vec<_>) =
+            vec![udt_cell(&udt_script2, u128::MAX.to_le_bytes().to_vec())]
+                .into_iter()
+                .unzip();
+        let tx = TransactionBuilder::default()
+            .input(CellInput::new(
+                OutPoint::new(H256(rand::random()).pack(), 0),
+                0,
+            ))
+            .outputs(outputs)
+            .outputs_data(outputs_data)
+            .build();
+        let block = BlockBuilder::default()
+            .transaction(
+                TransactionBuilder::default()
+                    .input(CellInput::new_cellbase_input(total_blocks + 1))
+                    .witness(Script::default().into_witness())
+                    .build(),
+            )
+            .transaction(tx)
+            .header(
+                HeaderBuilder::default()
+                    .number((total_blocks + 1).pack())
+                    .parent_hash(pre_block.hash())
+                    .build(),
+            )
+            .build();
+        indexer.append(&block).unwrap();
+        assert!(matches!(
+            rpc.get_cells_udt_amount(IndexerSearchKey {
+                script: lock_script1.clone().into(),
+                ..Default::default()
+            }),
+            Err(Error::Overflow(_))
+        ));
+
+        // rollback subtracts the amounts of the block
+        indexer.rollback().unwrap();
+        let udt_amount = rpc
+            .get_cells_udt_amount(IndexerSearchKey {
+                script: lock_script1.into(),
+                script_search_mode: Some(IndexerScriptSearchMode::Exact),
+                ..Default::default()
+            })
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            u128::MAX / total_blocks as u128 * total_blocks as u128,
+            udt_amount.udt_amounts[1].amount.value()
+        );
+        assert_eq!(total_blocks, udt_amount.udt_amounts[1].cells_count.value());
+    }
 }
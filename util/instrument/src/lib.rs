@@ -4,11 +4,15 @@
 //!
 //! - [`Export`] provides block data export function.
 //! - [`Import`] imports block data which export from `Export`.
+//! - [`ExportSnapshot`] exports the cell set snapshot at the tip.
+//! - [`ImportSnapshot`] imports the cell set snapshot which export from `ExportSnapshot`.
 
 mod export;
 mod import;
+mod snapshot;
 
 pub use crate::export::Export;
 pub use crate::import::Import;
+pub use crate::snapshot::{ExportSnapshot, ImportSnapshot};
 #[cfg(feature = "progress_bar")]
 pub use indicatif::{ProgressBar, ProgressStyle};
@@ -0,0 +1,65 @@
+use ckb_shared::shared::Shared;
+use ckb_store::{export_cell_set_snapshot, import_cell_set_snapshot};
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+/// Export the cell set snapshot at the tip from database to specify file.
+pub struct ExportSnapshot {
+    /// export target path
+    pub target: PathBuf,
+    /// CKB shared data.
+    pub shared: Shared,
+}
+
+impl ExportSnapshot {
+    /// Creates the snapshot export job.
+    pub fn new(shared: Shared, target: PathBuf) -> Self {
+        ExportSnapshot { shared, target }
+    }
+
+    /// export file name
+    fn file_name(&self) -> String {
+        format!("{}.{}", self.shared.consensus().id, "snapshot")
+    }
+
+    /// Executes the snapshot export job, prints the checkpoint to be pinned in the chain spec.
+    pub fn execute(self) -> Result<(), Box<dyn Error>> {
+        fs::create_dir_all(&self.target)?;
+        let path = self.target.join(self.file_name());
+        let checkpoint =
+            export_cell_set_snapshot(self.shared.store(), self.shared.consensus(), &path)?;
+        println!("Exported the cell set snapshot into {}", path.display());
+        println!("Pin it in the chain spec by:\n");
+        println!("[params.assume_utxo]");
+        println!("number = {}", checkpoint.number);
+        println!("block_hash = \"{:#x}\"", checkpoint.block_hash);
+        println!("snapshot_hash = \"{:#x}\"", checkpoint.snapshot_hash);
+        Ok(())
+    }
+}
+
+/// Import the cell set snapshot exported by `ExportSnapshot` into a new database.
+pub struct ImportSnapshot {
+    /// source file contains the cell set snapshot
+    source: PathBuf,
+    shared: Shared,
+}
+
+impl ImportSnapshot {
+    /// Creates the snapshot import job.
+    pub fn new(shared: Shared, source: PathBuf) -> Self {
+        ImportSnapshot { shared, source }
+    }
+
+    /// Executes the snapshot import job.
+    pub fn execute(self) -> Result<(), Box<dyn Error>> {
+        let checkpoint =
+            import_cell_set_snapshot(self.shared.store(), self.shared.consensus(), &self.source)?;
+        println!(
+            "Imported the cell set snapshot at block#{} {:#x}, the historical blocks will be back-filled after the node starts",
+            checkpoint.number, checkpoint.block_hash
+        );
+        Ok(())
+    }
+}
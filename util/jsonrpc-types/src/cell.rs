@@ -1,4 +1,4 @@
-use crate::{CellOutput, JsonBytes};
+use crate::{BlockNumber, CellOutput, JsonBytes};
 use ckb_types::{
     core::cell::{CellMeta, CellStatus},
     prelude::Unpack,
@@ -115,6 +115,89 @@ pub struct CellData {
     pub hash: H256,
 }
 
+/// Specifies a block in the canonical chain either by its number or by its hash.
+///
+/// `BlockNumberOrHash` is equivalent to [`H256`] `|` [`BlockNumber`].
+///
+/// [`H256`]: struct.H256.html
+/// [`BlockNumber`]: type.BlockNumber.html
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+#[serde(untagged)]
+pub enum BlockNumberOrHash {
+    /// The block hash.
+    Hash(H256),
+    /// The block number.
+    Number(BlockNumber),
+}
+
+/// The status of a cell as of a specified block in the canonical chain.
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum CellStatusAtBlock {
+    /// The transaction creating this cell is committed at or before the specified block, and the
+    /// cell is not consumed by any transaction committed at or before that block.
+    Live,
+    /// The cell is consumed by a transaction committed at or before the specified block.
+    Dead,
+    /// The transaction creating this cell is not in the canonical chain as of the specified block,
+    /// or the out point index is out of the range of its outputs.
+    Unknown,
+}
+
+/// The JSON view of a cell and its status as of a specified block.
+///
+/// ## Examples
+///
+/// ```
+/// # serde_json::from_str::<ckb_jsonrpc_types::CellWithStatusAtBlock>(r#"
+/// {
+///   "cell": {
+///     "data": null,
+///     "output": {
+///       "capacity": "0x802665800",
+///       "lock": {
+///         "args": "0x",
+///         "code_hash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+///         "hash_type": "data"
+///       },
+///       "type": null
+///     }
+///   },
+///   "status": "dead",
+///   "created_block_number": "0x0",
+///   "consumed_block_number": "0x3"
+/// }
+/// # "#).unwrap();
+/// ```
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CellWithStatusAtBlock {
+    /// The cell information.
+    ///
+    /// It is null only when the status is `unknown`. Unlike `get_live_cell`, the cell
+    /// information of dead cells is also returned.
+    pub cell: Option<CellInfo>,
+    /// Status of the cell as of the specified block.
+    pub status: CellStatusAtBlock,
+    /// The number of the block which commits the transaction creating this cell.
+    pub created_block_number: Option<BlockNumber>,
+    /// The number of the block which commits the transaction consuming this cell.
+    ///
+    /// It is null unless the status is `dead`, and it is also null when the consuming block is
+    /// not within the scanned blocks.
+    pub consumed_block_number: Option<BlockNumber>,
+}
+
+/// The JSON view of a batch of cells and their statuses as of a specified block.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CellsWithStatusAtBlock {
+    /// The number of the specified block.
+    pub block_number: BlockNumber,
+    /// The hash of the specified block.
+    pub block_hash: H256,
+    /// The cells in the same order as the requested out points.
+    pub cells: Vec<CellWithStatusAtBlock>,
+}
+
 impl From<CellMeta> for CellInfo {
     fn from(cell_meta: CellMeta) -> Self {
         let data = cell_meta.mem_cell_data;
@@ -1,4 +1,4 @@
-use crate::{Cycle, OutPoint};
+use crate::{Cycle, OutPoint, Uint64};
 use ckb_types::H256;
 use serde::{Deserialize, Serialize};
 
@@ -7,6 +7,114 @@ use serde::{Deserialize, Serialize};
 pub struct EstimateCycles {
     /// The count of cycles that the VM has consumed to verify this transaction.
     pub cycles: Cycle,
+    /// The execution traces of the script groups which have run.
+    ///
+    /// It is only present when the trace mode is requested in `dry_run_transaction`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace: Option<Vec<ScriptGroupTrace>>,
+}
+
+/// The script group type.
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ScriptGroupType {
+    /// Lock script group.
+    Lock,
+    /// Type script group.
+    Type,
+}
+
+/// The execution trace of a script group.
+///
+/// ## Examples
+///
+/// ```
+/// # serde_json::from_str::<ckb_jsonrpc_types::ScriptGroupTrace>(r#"
+/// {
+///   "cycles": "0x219",
+///   "debug_messages": [
+///     "debugger print utf-8 string"
+///   ],
+///   "dropped_events": "0x0",
+///   "error": null,
+///   "exit_code": 0,
+///   "group_type": "type",
+///   "script_hash": "0x9c6dfd4d0ae25c5cdf1fa3b8a5ab8b2a1f2c76ab6a1ea2b39a4a6b7cb2b2d8b1",
+///   "syscalls": [
+///     {
+///       "args": ["0x3ffff4b0", "0x0", "0x0", "0x0", "0x0", "0x0"],
+///       "cycles": "0x1f4",
+///       "number": "0x881",
+///       "return_code": "0x0",
+///       "vm_id": "0x0"
+///     }
+///   ],
+///   "vms": [
+///     {
+///       "cycles": "0x219",
+///       "exit_code": 0,
+///       "id": "0x0",
+///       "parent": null
+///     }
+///   ]
+/// }
+/// # "#).unwrap();
+/// ```
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+pub struct ScriptGroupTrace {
+    /// The script group type.
+    pub group_type: ScriptGroupType,
+    /// The script hash of the group.
+    pub script_hash: H256,
+    /// The exit code of the root VM.
+    ///
+    /// Null if the group is aborted or it is not run in a VM, such as the type id script.
+    pub exit_code: Option<i8>,
+    /// The cycles consumed by the group.
+    ///
+    /// Null if the group is aborted, e.g., it exceeds the cycles limit.
+    pub cycles: Option<Cycle>,
+    /// The error failing the group.
+    pub error: Option<String>,
+    /// The syscalls in the returning order, so the ones of a spawned VM precede the spawn syscall.
+    pub syscalls: Vec<SyscallTrace>,
+    /// The messages printed by the debug syscall.
+    pub debug_messages: Vec<String>,
+    /// The VMs in the creating order, the first one is the root VM.
+    pub vms: Vec<VmTrace>,
+    /// The number of syscalls and debug messages not recorded because of the limit of 10000
+    /// records each.
+    pub dropped_events: Uint64,
+}
+
+/// A syscall invoked by a script.
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+pub struct SyscallTrace {
+    /// The id of the VM invoking the syscall.
+    pub vm_id: Uint64,
+    /// The syscall number.
+    pub number: Uint64,
+    /// The arguments in registers A0 to A5.
+    pub args: Vec<Uint64>,
+    /// The return code in register A0 after the syscall.
+    pub return_code: Uint64,
+    /// The cycles consumed by the syscall, including the ones of the VM it spawns.
+    pub cycles: Cycle,
+}
+
+/// A VM created to run a script group, the root one or a spawned one.
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+pub struct VmTrace {
+    /// The index of the VM in the group, the root VM is 0.
+    pub id: Uint64,
+    /// The id of the VM which spawns this one, null for the root VM.
+    pub parent: Option<Uint64>,
+    /// The exit code, null if the VM is aborted.
+    pub exit_code: Option<i8>,
+    /// The cycles consumed by the VM, including the ones of the VMs it spawns.
+    ///
+    /// Null if the VM is aborted.
+    pub cycles: Option<Cycle>,
 }
 
 /// An enum to represent the two kinds of dao withdrawal amount calculation option.
@@ -1,4 +1,6 @@
-use crate::{BlockNumber, Capacity, CellOutput, JsonBytes, OutPoint, Script, Uint32, Uint64};
+use crate::{
+    BlockNumber, Capacity, CellOutput, JsonBytes, OutPoint, Script, Uint128, Uint32, Uint64,
+};
 use ckb_types::H256;
 use serde::{Deserialize, Serialize};
 
@@ -56,6 +58,8 @@ pub struct IndexerSearchKey {
     pub script_search_mode: Option<IndexerScriptSearchMode>,
     /// filter cells by following conditions, all conditions are optional
     pub filter: Option<IndexerSearchKeyFilter>,
+    /// filter cells by a boolean expression, optional, it is combined with `filter` by `and`
+    pub filter_expression: Option<IndexerFilterExpression>,
     /// bool, optional default is `true`, if with_data is set to false, the field of returning cell.output_data is null in the result
     pub with_data: Option<bool>,
     /// bool, optional default is `false`, if group_by_transaction is set to true, the returning objects will be grouped by the tx hash
@@ -69,6 +73,7 @@ impl Default for IndexerSearchKey {
             script_type: IndexerScriptType::Lock,
             script_search_mode: None,
             filter: None,
+            filter_expression: None,
             with_data: None,
             group_by_transaction: None,
         }
@@ -143,6 +148,67 @@ pub struct IndexerSearchKeyFilter {
     pub block_range: Option<IndexerRange>,
 }
 
+/// IndexerFilterExpression represent indexer params `filter_expression`, a boolean expression
+/// tree evaluated against every cell while iterating.
+///
+/// ## Examples
+///
+/// Cells whose type script is `X` or null, and whose capacity is at least 100 CKB:
+///
+/// ```
+/// # serde_json::from_str::<ckb_jsonrpc_types::IndexerFilterExpression>(r#"
+/// {
+///   "and": [
+///     {
+///       "or": [
+///         {
+///           "script": {
+///             "script_type": "type",
+///             "script": {
+///               "code_hash": "0x5e7a36a77e68eecc013dfa2fe6a23f3b6c344b04005808694ae6dd45eea4cfd5",
+///               "hash_type": "type",
+///               "args": "0x"
+///             }
+///           }
+///         },
+///         { "script": { "script_type": "type", "script": null } }
+///       ]
+///     },
+///     { "output_capacity_range": ["0x2540be400", "0xffffffffffffffff"] }
+///   ]
+/// }
+/// # "#).unwrap();
+/// ```
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IndexerFilterExpression {
+    /// matches if all the sub-expressions match, an empty array always matches
+    And(Vec<IndexerFilterExpression>),
+    /// matches if any of the sub-expressions matches, an empty array never matches
+    Or(Vec<IndexerFilterExpression>),
+    /// matches if the sub-expression does not match
+    Not(Box<IndexerFilterExpression>),
+    /// matches cells by lock or type script
+    Script(IndexerScriptFilter),
+    /// matches cells by output data len range
+    OutputDataLenRange(IndexerRange),
+    /// matches cells by output capacity range
+    OutputCapacityRange(IndexerRange),
+    /// matches cells by block number range
+    BlockRange(IndexerRange),
+}
+
+/// IndexerScriptFilter represent the script condition in `IndexerFilterExpression`
+#[derive(Deserialize)]
+pub struct IndexerScriptFilter {
+    /// Script Type
+    pub script_type: IndexerScriptType,
+    /// Script, null matches cells without the script, which only makes sense for the type script
+    pub script: Option<Script>,
+    /// Script search mode, optional default is `prefix`, means search script with prefix
+    pub script_search_mode: Option<IndexerScriptSearchMode>,
+}
+
 /// ScriptType `Lock` | `Type`
 #[derive(Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -174,6 +240,69 @@ pub struct IndexerCellsCapacity {
     pub block_number: BlockNumber,
 }
 
+/// Cells capacity at a past block
+#[derive(Serialize)]
+pub struct IndexerCellsCapacityAtBlock {
+    /// total capacity of the live cells at the block
+    pub capacity: Capacity,
+    /// the out points of the live cells at the block, null if `with_cells` is not set
+    pub cells: Option<Vec<OutPoint>>,
+    /// the queried block hash
+    pub block_hash: H256,
+    /// the queried block number
+    pub block_number: BlockNumber,
+}
+
+/// Cells UDT amount, grouped by type script
+#[derive(Serialize)]
+pub struct IndexerCellsUdtAmount {
+    /// UDT amounts of the matched cells, grouped by type script and sorted by it
+    pub udt_amounts: Vec<IndexerUdtAmount>,
+    /// indexed tip block hash
+    pub block_hash: H256,
+    /// indexed tip block number
+    pub block_number: BlockNumber,
+}
+
+/// Total UDT amount of the live cells with the same type script
+#[derive(Serialize)]
+pub struct IndexerUdtAmount {
+    /// the type script of the UDT
+    pub type_script: Script,
+    /// total amount, the sum of the first 16 bytes of cells data as little-endian u128
+    pub amount: Uint128,
+    /// the number of cells summed up
+    pub cells_count: Uint64,
+}
+
+/// IndexerCellsChangedFilter represent the params of the `cells_changed` subscription topic
+#[derive(Deserialize)]
+pub struct IndexerCellsChangedFilter {
+    /// Script
+    pub script: Script,
+    /// Script Type
+    pub script_type: IndexerScriptType,
+    /// Script search mode, optional default is `prefix`, means search script with prefix
+    pub script_search_mode: Option<IndexerScriptSearchMode>,
+}
+
+/// Live cells of the subscribed script which are changed by a block appended to or rolled back
+/// from the indexer
+#[derive(Serialize)]
+pub struct IndexerCellsChanged {
+    /// the appended or rolled back block number
+    pub block_number: BlockNumber,
+    /// the appended or rolled back block hash
+    pub block_hash: H256,
+    /// whether the block is rolled back because of a chain reorganization, the `created` cells
+    /// are removed and the `consumed` cells are live again if it is true
+    pub rollback: bool,
+    /// cells created by the block
+    pub created: Vec<IndexerCell>,
+    /// cells consumed by the block
+    pub consumed: Vec<IndexerCell>,
+}
+
 /// Indexer Transaction Object
 #[derive(Serialize)]
 #[serde(untagged)]
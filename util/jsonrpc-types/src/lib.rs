@@ -33,27 +33,39 @@ pub use self::blockchain::{
     TransactionWithStatusResponse, TxStatus, UncleBlock, UncleBlockView,
 };
 pub use self::bytes::JsonBytes;
-pub use self::cell::{CellData, CellInfo, CellWithStatus};
+pub use self::cell::{
+    BlockNumberOrHash, CellData, CellInfo, CellStatusAtBlock, CellWithStatus,
+    CellWithStatusAtBlock, CellsWithStatusAtBlock,
+};
 pub use self::debug::{ExtraLoggerConfig, MainLoggerConfig};
-pub use self::experiment::{DaoWithdrawingCalculationKind, EstimateCycles};
+pub use self::experiment::{
+    DaoWithdrawingCalculationKind, EstimateCycles, ScriptGroupTrace, ScriptGroupType, SyscallTrace,
+    VmTrace,
+};
 pub use self::fee_rate::FeeRateDef;
 pub use self::fixed_bytes::Byte32;
 pub use self::info::{ChainInfo, DeploymentInfo, DeploymentPos, DeploymentState, DeploymentsInfo};
 pub use self::net::{
-    BannedAddr, LocalNode, LocalNodeProtocol, NodeAddress, PeerSyncState, RemoteNode,
-    RemoteNodeProtocol, SyncState,
+    BannedAddr, LocalNode, LocalNodeProtocol, NodeAddress, PeerDownloadStats, PeerSyncState,
+    RemoteNode, RemoteNodeProtocol, SyncState,
 };
 pub use self::pool::{
-    AncestorsScoreSortKey, OutputsValidator, PoolTransactionEntry, PoolTransactionReject,
-    PoolTxDetailInfo, RawTxPool, TxPoolEntries, TxPoolEntry, TxPoolIds, TxPoolInfo,
+    AncestorsScoreSortKey, EstimateMode, OutputsValidator, PoolTransactionEntry,
+    PoolTransactionReject, PoolTxDetailInfo, RawTxPool, TxPoolEntries, TxPoolEntry, TxPoolIds,
+    TxPoolInfo,
 };
 pub use self::proposal_short_id::ProposalShortId;
-pub use self::subscription::Topic;
+pub use self::subscription::{
+    SubscriptionFilter, Topic, TransactionStatusChanged, TransactionStatusFilter,
+    TransactionStatusTransition,
+};
 pub use self::uints::{Uint128, Uint32, Uint64};
 pub use indexer::{
-    IndexerCell, IndexerCellType, IndexerCellsCapacity, IndexerOrder, IndexerPagination,
-    IndexerRange, IndexerScriptSearchMode, IndexerScriptType, IndexerSearchKey,
-    IndexerSearchKeyFilter, IndexerTip, IndexerTx, IndexerTxWithCell, IndexerTxWithCells,
+    IndexerCell, IndexerCellType, IndexerCellsCapacity, IndexerCellsCapacityAtBlock,
+    IndexerCellsChanged, IndexerCellsChangedFilter, IndexerCellsUdtAmount, IndexerFilterExpression,
+    IndexerOrder, IndexerPagination, IndexerRange, IndexerScriptFilter, IndexerScriptSearchMode,
+    IndexerScriptType, IndexerSearchKey, IndexerSearchKeyFilter, IndexerTip, IndexerTx,
+    IndexerTxWithCell, IndexerTxWithCells, IndexerUdtAmount,
 };
 pub use primitive::{
     AsEpochNumberWithFraction, BlockNumber, Capacity, Cycle, EpochNumber, EpochNumberWithFraction,
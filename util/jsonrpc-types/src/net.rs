@@ -100,6 +100,7 @@ pub struct LocalNodeProtocol {
 ///   "connected_duration": "0x2f",
 ///   "is_outbound": true,
 ///   "last_ping_duration": "0x1a",
+///   "misbehavior_score": "0x0",
 ///   "node_id": "QmXwUgF48ULy6hkgfqrEwEfuHW7WyWyWauueRDAYQHNDfN",
 ///   "protocols": [
 ///     {
@@ -135,6 +136,8 @@ pub struct LocalNodeProtocol {
 ///       "version": "0.0.1"
 ///     }
 ///   ],
+///   "received_bytes": "0x2b7c",
+///   "sent_bytes": "0x1e4a",
 ///   "sync_state": {
 ///     "best_known_header_hash": null,
 ///     "best_known_header_number": null,
@@ -166,6 +169,16 @@ pub struct RemoteNode {
     ///
     /// Null means no ping responses have been received yet.
     pub last_ping_duration: Option<Uint64>,
+    /// The decaying score of the offenses reported against the remote node.
+    ///
+    /// The score halves every 10 minutes. The remote node is disconnected when the score reaches
+    /// 50 and banned at 100. Besides, a protocol of the remote node is throttled when the offenses
+    /// in that protocol alone score 20.
+    pub misbehavior_score: Uint64,
+    /// Bytes of the protocol messages received from the remote node.
+    pub received_bytes: Uint64,
+    /// Bytes of the protocol messages sent to the remote node.
+    pub sent_bytes: Uint64,
     /// Chain synchronization state.
     ///
     /// Null means chain sync has not started with this remote node yet.
@@ -283,4 +296,32 @@ pub struct SyncState {
     pub normal_time: Uint64,
     /// The download scheduler's time analysis data, the low is the 9/10 of the cut-off point, unit ms
     pub low_time: Uint64,
+    /// The block download statistics of the peers which the local node downloads blocks from.
+    pub download_peers: Vec<PeerDownloadStats>,
+}
+
+/// The block download statistics of a remote peer.
+#[derive(Clone, Default, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+pub struct PeerDownloadStats {
+    /// The remote node ID which is derived from its P2P private key.
+    pub node_id: String,
+    /// Count of blocks being downloaded from this peer.
+    pub inflight_count: Uint64,
+    /// The max count of blocks which can be downloaded from this peer concurrently.
+    ///
+    /// The download scheduler adjusts it by the response time of the peer.
+    pub task_count: Uint64,
+    /// Count of blocks received from this peer.
+    pub received_count: Uint64,
+    /// Count of blocks which timed out or were re-assigned to other peers because the download
+    /// from this peer stalled.
+    pub stalled_count: Uint64,
+    /// The moving average of the block response time, unit ms.
+    ///
+    /// It is null when no block has been received from this peer.
+    pub avg_response_time: Option<Uint64>,
+    /// The measured throughput in blocks per second.
+    ///
+    /// It is null when no block has been received from this peer.
+    pub throughput: Option<Uint64>,
 }
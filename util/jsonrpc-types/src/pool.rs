@@ -1,8 +1,9 @@
 use crate::{BlockNumber, Capacity, Cycle, Timestamp, TransactionView, Uint64};
 use ckb_types::core::service::PoolTransactionEntry as CorePoolTransactionEntry;
 use ckb_types::core::tx_pool::{
-    AncestorsScoreSortKey as CoreAncestorsScoreSortKey, PoolTxDetailInfo as CorePoolTxDetailInfo,
-    Reject, TxEntryInfo, TxPoolEntryInfo, TxPoolIds as CoreTxPoolIds, TxPoolInfo as CoreTxPoolInfo,
+    AncestorsScoreSortKey as CoreAncestorsScoreSortKey, EstimateMode as CoreEstimateMode,
+    PoolTxDetailInfo as CorePoolTxDetailInfo, Reject, TxEntryInfo, TxPoolEntryInfo,
+    TxPoolIds as CoreTxPoolIds, TxPoolInfo as CoreTxPoolInfo,
 };
 use ckb_types::prelude::Unpack;
 use ckb_types::H256;
@@ -122,6 +123,28 @@ impl OutputsValidator {
     }
 }
 
+/// The estimate mode of the fee estimator.
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum EstimateMode {
+    /// "economical": the default mode, requires a lower success rate and weights the recent
+    /// blocks more, so the estimation responds faster when the congestion goes down.
+    #[default]
+    Economical,
+    /// "conservative": requires a higher success rate and takes a longer history into account,
+    /// so the estimation is less likely to be too low during congestion.
+    Conservative,
+}
+
+impl From<EstimateMode> for CoreEstimateMode {
+    fn from(mode: EstimateMode) -> Self {
+        match mode {
+            EstimateMode::Economical => CoreEstimateMode::Economical,
+            EstimateMode::Conservative => CoreEstimateMode::Conservative,
+        }
+    }
+}
+
 /// Array of transaction ids
 #[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
 pub struct TxPoolIds {
@@ -311,6 +334,9 @@ pub enum PoolTransactionReject {
 
     /// RBF rejected
     RBFRejected(String),
+
+    /// Transaction exceeded the limits of an input lock script
+    ExceededLockScriptLimit(String),
 }
 
 impl From<Reject> for PoolTransactionReject {
@@ -331,6 +357,9 @@ impl From<Reject> for PoolTransactionReject {
             Reject::Verification(_) => Self::Verification(format!("{reject}")),
             Reject::Expiry(_) => Self::Expiry(format!("{reject}")),
             Reject::RBFRejected(_) => Self::RBFRejected(format!("{reject}")),
+            Reject::ExceededLockScriptLimit(..) => {
+                Self::ExceededLockScriptLimit(format!("{reject}"))
+            }
         }
     }
 }
@@ -1,3 +1,5 @@
+use crate::{BlockNumber, IndexerCellsChangedFilter, PoolTransactionReject};
+use ckb_types::H256;
 use serde::{Deserialize, Serialize};
 
 /// Specifies the topic which to be added as active subscription.
@@ -14,4 +16,63 @@ pub enum Topic {
     ProposedTransaction,
     /// Subscribe transactions which are abandoned by tx-pool.
     RejectedTransaction,
+    /// Subscribe live cells of a script which are changed by the blocks appended to or rolled
+    /// back from the indexer.
+    CellsChanged,
+    /// Subscribe status transitions of the transactions with the given hashes.
+    TransactionStatus,
+}
+
+/// Specifies the filter of the `cells_changed` or `transaction_status` subscription topic.
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum SubscriptionFilter {
+    /// The filter of the `cells_changed` topic.
+    CellsChanged(IndexerCellsChangedFilter),
+    /// The filter of the `transaction_status` topic.
+    TransactionStatus(TransactionStatusFilter),
+}
+
+/// TransactionStatusFilter represent the params of the `transaction_status` subscription topic
+#[derive(Deserialize)]
+pub struct TransactionStatusFilter {
+    /// The hashes of the transactions to watch.
+    pub tx_hashes: Vec<H256>,
+}
+
+/// A status transition of a transaction.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionStatusTransition {
+    /// The transaction is added to the pool and not proposed yet.
+    Pending,
+    /// The transaction is proposed on chain.
+    Proposed,
+    /// The transaction is committed in a block of the canonical chain.
+    Committed,
+    /// The transaction is abandoned by the pool, or rejected on submission.
+    Rejected,
+    /// The transaction is removed from the pool because a conflicting transaction replaces it by
+    /// RBF.
+    Replaced,
+    /// The block which commits the transaction is detached from the canonical chain by a chain
+    /// reorganization.
+    Detached,
+}
+
+/// The push message of the `transaction_status` subscription topic.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct TransactionStatusChanged {
+    /// The transaction hash.
+    pub tx_hash: H256,
+    /// The new status of the transaction.
+    pub status: TransactionStatusTransition,
+    /// The block which commits the transaction or is detached, only present when the status is
+    /// `committed` or `detached`.
+    pub block_hash: Option<H256>,
+    /// The number of the block in `block_hash`.
+    pub block_number: Option<BlockNumber>,
+    /// The reason why the transaction is abandoned, only present when the status is `rejected`
+    /// or `replaced`.
+    pub reason: Option<PoolTransactionReject>,
 }
@@ -1,3 +1,4 @@
 mod blockchain;
 mod bytes;
 mod pool;
+mod subscription;
@@ -0,0 +1,30 @@
+use crate::{IndexerScriptType, SubscriptionFilter};
+use serde_json::json;
+
+#[test]
+fn test_subscription_filter_deserialize() {
+    let filter: SubscriptionFilter = serde_json::from_value(json!({
+        "script": {
+            "code_hash": "0x9bd7e06f3ecf4be0f2fcd2188b23f1b9fcc88e5d4b65a8637b17723bbda3cce8",
+            "hash_type": "type",
+            "args": "0x8211f1b938a107cd53b6302cc752a6fc3965638d"
+        },
+        "script_type": "lock"
+    }))
+    .unwrap();
+    assert!(matches!(
+        filter,
+        SubscriptionFilter::CellsChanged(filter) if matches!(filter.script_type, IndexerScriptType::Lock)
+    ));
+
+    let filter: SubscriptionFilter = serde_json::from_value(json!({
+        "tx_hashes": ["0xa0ef4eb5f4ceeb08a4c8524d84c5da95dce2f608e0ca2ec8091191b0f330c6e3"]
+    }))
+    .unwrap();
+    assert!(matches!(
+        filter,
+        SubscriptionFilter::TransactionStatus(filter) if filter.tx_hashes.len() == 1
+    ));
+
+    assert!(serde_json::from_value::<SubscriptionFilter>(json!({})).is_err());
+}
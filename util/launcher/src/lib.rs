@@ -26,7 +26,7 @@ use ckb_shared::Shared;
 
 use ckb_shared::shared_builder::{SharedBuilder, SharedPackage};
 use ckb_store::{ChainDB, ChainStore};
-use ckb_sync::{BlockFilter, NetTimeProtocol, Relayer, SyncShared, Synchronizer};
+use ckb_sync::{BlockFilter, NetTimeProtocol, Relayer, SyncShared, Synchronizer, TxReconciliation};
 use ckb_tx_pool::service::TxVerificationResult;
 use ckb_types::prelude::*;
 use ckb_verification::GenesisVerifier;
@@ -226,7 +226,10 @@ impl Launcher {
 
     /// Start chain service, return ChainController
     pub fn start_chain_service(&self, shared: &Shared, table: ProposalTable) -> ChainController {
-        let chain_service = ChainService::new(shared.clone(), table);
+        let mut chain_service = ChainService::new(shared.clone(), table);
+        if self.args.config.tx_pool.parallel_script_verification {
+            chain_service.enable_parallel_script_verification();
+        }
         let chain_controller = chain_service.start(Some("ChainService"));
         info!("chain genesis hash: {:#x}", shared.genesis_hash());
         chain_controller
@@ -292,6 +295,12 @@ impl Launcher {
         let support_protocols = &self.args.config.network.support_protocols;
         let mut flags = Flags::all();
 
+        if self.args.config.store.prune_retention_depth.is_none()
+            || shared.store().freezer().is_none()
+        {
+            flags.remove(Flags::PRUNED);
+        }
+
         if support_protocols.contains(&SupportProtocol::Relay) {
             let relayer = Relayer::new(chain_controller.clone(), Arc::clone(&sync_shared));
 
@@ -307,6 +316,14 @@ impl Launcher {
                     Arc::clone(&network_state),
                 ))
             }
+            if support_protocols.contains(&SupportProtocol::TxReconciliation) {
+                let tx_reconciliation = TxReconciliation::new(Arc::clone(&sync_shared));
+                protocols.push(CKBProtocol::new_with_support_protocol(
+                    SupportProtocols::TxReconciliation,
+                    Box::new(tx_reconciliation),
+                    Arc::clone(&network_state),
+                ));
+            }
         } else {
             flags.remove(Flags::RELAY);
         }
@@ -322,6 +339,11 @@ impl Launcher {
         } else {
             flags.remove(Flags::BLOCK_FILTER);
         }
+        // the filter data is built after the block bodies are back-filled, it's advertised after
+        // the node restarts then
+        if shared.store().get_backfill_range().is_some() {
+            flags.remove(Flags::BLOCK_FILTER);
+        }
 
         if support_protocols.contains(&SupportProtocol::Time) {
             let net_timer = NetTimeProtocol::default();
@@ -1,7 +1,3 @@
-use std::time::Duration;
-
-pub const BAD_MESSAGE_BAN_TIME: Duration = Duration::from_secs(5 * 60);
-
 pub const GET_BLOCKS_PROOF_LIMIT: usize = 1000;
 pub const GET_LAST_STATE_PROOF_LIMIT: usize = 1000;
 pub const GET_TRANSACTIONS_PROOF_LIMIT: usize = 1000;
@@ -4,8 +4,10 @@
 
 use std::sync::Arc;
 
-use ckb_logger::{debug, error, info, trace, warn};
-use ckb_network::{async_trait, bytes::Bytes, CKBProtocolContext, CKBProtocolHandler, PeerIndex};
+use ckb_logger::{debug, info, trace, warn};
+use ckb_network::{
+    async_trait, bytes::Bytes, Behaviour, CKBProtocolContext, CKBProtocolHandler, PeerIndex,
+};
 use ckb_shared::Shared;
 use ckb_store::ChainStore;
 use ckb_types::{core, packed, prelude::*};
@@ -67,23 +69,19 @@ impl CKBProtocolHandler for LightClientProtocol {
                     "LightClient.received a malformed message from Peer({})",
                     peer
                 );
-                nc.ban_peer(
-                    peer,
-                    constant::BAD_MESSAGE_BAN_TIME,
-                    String::from("send us a malformed message"),
-                );
+                nc.report_peer(peer, Behaviour::MalformedMessage);
                 return;
             }
         };
 
         let item_name = msg.item_name();
         let status = self.try_process(nc.as_ref(), peer, msg);
-        if let Some(ban_time) = status.should_ban() {
-            error!(
-                "process {} from {}; ban {:?} since result is {}",
-                item_name, peer, ban_time, status
+        if let Some(behaviour) = status.should_report() {
+            debug!(
+                "process {} from {}; report {:?} since result is {}",
+                item_name, peer, behaviour, status
             );
-            nc.ban_peer(peer, ban_time, status.to_string());
+            nc.report_peer(peer, behaviour);
         } else if status.should_warn() {
             warn!("process {} from {}; result is {}", item_name, peer, status);
         } else if !status.is_ok() {
@@ -1,6 +1,6 @@
-use std::{fmt, time::Duration};
+use std::fmt;
 
-use crate::constant;
+use ckb_network::Behaviour;
 
 /// StatusCodes indicate whether a specific operation has been successfully completed.
 ///
@@ -91,13 +91,15 @@ impl Status {
         self.code == StatusCode::OK
     }
 
-    /// Whether the session should be banned.
-    pub fn should_ban(&self) -> Option<Duration> {
-        let code = self.code as u16;
-        if !(400..500).contains(&code) {
-            None
-        } else {
-            Some(constant::BAD_MESSAGE_BAN_TIME)
+    /// The misbehavior to report to the network if the peer causes the status.
+    pub fn should_report(&self) -> Option<Behaviour> {
+        match self.code {
+            StatusCode::UnexpectedProtocolMessage => Some(Behaviour::UnexpectedMessage),
+            StatusCode::InvalidLastBlock | StatusCode::InvalidUnconfirmedBlock => {
+                Some(Behaviour::InvalidBlock)
+            }
+            code if (400..500).contains(&(code as u16)) => Some(Behaviour::MalformedMessage),
+            _ => None,
         }
     }
 
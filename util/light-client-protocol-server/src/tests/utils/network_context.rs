@@ -14,6 +14,7 @@ struct MockProtocolContext {
     protocol: SupportProtocols,
     sent_messages: RefCell<Vec<(ProtocolId, PeerIndex, P2pBytes)>>,
     banned_peers: RefCell<Vec<(PeerIndex, Duration, String)>>,
+    reported_peers: RefCell<Vec<(PeerIndex, Behaviour)>>,
     connected_peers: RefCell<HashSet<PeerIndex>>,
 }
 
@@ -31,6 +32,7 @@ impl MockProtocolContext {
             protocol,
             sent_messages: Default::default(),
             banned_peers: Default::default(),
+            reported_peers: Default::default(),
             connected_peers: Default::default(),
         }
     }
@@ -59,7 +61,20 @@ impl MockNetworkContext {
             .map(|(_, duration, reason)| (*duration, reason.clone()))
     }
 
+    pub(crate) fn has_reported(&self, target: PeerIndex) -> Option<Behaviour> {
+        self.inner
+            .reported_peers
+            .borrow()
+            .iter()
+            .find(|(peer, _)| *peer == target)
+            .map(|(_, behaviour)| *behaviour)
+    }
+
     pub(crate) fn not_banned(&self, target: PeerIndex) -> bool {
+        if let Some(behaviour) = self.has_reported(target) {
+            eprintln!("Reported due to {behaviour:?}");
+            return false;
+        }
         self.has_banned(target)
             .map(|(_, reason)| {
                 eprintln!("Banned due to {reason}");
@@ -196,8 +211,10 @@ impl CKBProtocolContext for MockProtocolContext {
     fn connected_peers(&self) -> Vec<PeerIndex> {
         self.connected_peers.borrow().iter().cloned().collect()
     }
-    fn report_peer(&self, _peer_index: PeerIndex, _behaviour: Behaviour) {
-        unimplemented!();
+    fn report_peer(&self, peer_index: PeerIndex, behaviour: Behaviour) {
+        self.reported_peers
+            .borrow_mut()
+            .push((peer_index, behaviour));
     }
     fn ban_peer(&self, peer_index: PeerIndex, duration: Duration, reason: String) {
         self.banned_peers
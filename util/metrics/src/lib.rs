@@ -7,8 +7,9 @@
 //! [`ckb-metrics-service`]: ../ckb_metrics_service/index.html
 
 use prometheus::{
-    register_histogram, register_histogram_vec, register_int_counter, register_int_gauge,
-    register_int_gauge_vec, Histogram, HistogramVec, IntCounter, IntGauge, IntGaugeVec,
+    register_histogram, register_histogram_vec, register_int_counter, register_int_counter_vec,
+    register_int_gauge, register_int_gauge_vec, Histogram, HistogramVec, IntCounter, IntCounterVec,
+    IntGauge, IntGaugeVec,
 };
 use prometheus_static_metric::make_static_metric;
 use std::cell::Cell;
@@ -38,6 +39,35 @@ make_static_metric! {
         },
     }
 
+    // Struct for the CKB relay transaction announcement mode label
+    struct CkbRelayTxAnnouncementStatistics: IntCounter{
+        "mode" => {
+            flooding,
+            reconciliation,
+        },
+    }
+
+    // Struct for the CKB transaction reconciliation result label
+    struct CkbTxReconciliationStatistics: IntCounter{
+        "result" => {
+            success,
+            failure,
+        },
+    }
+
+    // Struct for the CKB relay compact block reconstruction short id and result labels
+    struct CkbRelayCbReconstructStatistics: IntCounter{
+        "short_id" => {
+            legacy,
+            salted,
+        },
+        "result" => {
+            ok,
+            missing,
+            collided,
+        },
+    }
+
     // Struct for CKB tx-pool entry status statistics type label
     struct CkbTxPoolEntryStatistics: IntGauge{
         "type" => {
@@ -67,6 +97,14 @@ pub struct Metrics {
     pub ckb_relay_cb_fresh_tx_cnt: IntCounter,
     /// Counter for relay compact block reconstruct fail
     pub ckb_relay_cb_reconstruct_fail: IntCounter,
+    /// Counter for relay compact block reconstruction results, by short id kind
+    pub ckb_relay_cb_reconstruct: CkbRelayCbReconstructStatistics,
+    /// Gauge for relay compact block high-bandwidth peers selected by us
+    pub ckb_relay_cb_high_bandwidth_peers: IntGauge,
+    /// Counter for bytes sent to announce transactions, by flooding or set reconciliation
+    pub ckb_relay_tx_announcement_bytes: CkbRelayTxAnnouncementStatistics,
+    /// Counter for transaction reconciliation rounds, by whether the difference was decoded
+    pub ckb_tx_reconciliation: CkbTxReconciliationStatistics,
     // Gauge for CKB shared best number
     pub ckb_shared_best_number: IntGauge,
     // GaugeVec for CKB system memory process statistics
@@ -81,6 +119,8 @@ pub struct Metrics {
     pub ckb_sys_mem_rocksdb: IntGaugeVec,
     /// Counter for CKB network ban peers
     pub ckb_network_ban_peer: IntCounter,
+    /// Counter for CKB network message bytes, by protocol and direction
+    pub ckb_network_protocol_bytes: IntCounterVec,
 }
 
 static METRICS: once_cell::sync::Lazy<Metrics> = once_cell::sync::Lazy::new(|| Metrics {
@@ -117,6 +157,35 @@ static METRICS: once_cell::sync::Lazy<Metrics> = once_cell::sync::Lazy::new(|| M
         "The CKB relay compact block reconstruct fail count"
     )
     .unwrap(),
+    ckb_relay_cb_reconstruct: CkbRelayCbReconstructStatistics::from(
+        &register_int_counter_vec!(
+            "ckb_relay_cb_reconstruct",
+            "The CKB relay compact block reconstruction results",
+            &["short_id", "result"]
+        )
+        .unwrap(),
+    ),
+    ckb_relay_cb_high_bandwidth_peers: register_int_gauge!(
+        "ckb_relay_cb_high_bandwidth_peers",
+        "The CKB relay compact block high-bandwidth peers count"
+    )
+    .unwrap(),
+    ckb_relay_tx_announcement_bytes: CkbRelayTxAnnouncementStatistics::from(
+        &register_int_counter_vec!(
+            "ckb_relay_tx_announcement_bytes",
+            "The CKB relay bytes sent to announce transactions",
+            &["mode"]
+        )
+        .unwrap(),
+    ),
+    ckb_tx_reconciliation: CkbTxReconciliationStatistics::from(
+        &register_int_counter_vec!(
+            "ckb_tx_reconciliation",
+            "The CKB transaction reconciliation rounds",
+            &["result"]
+        )
+        .unwrap(),
+    ),
     ckb_shared_best_number: register_int_gauge!(
         "ckb_shared_best_number",
         "The CKB shared best header number"
@@ -167,6 +236,12 @@ static METRICS: once_cell::sync::Lazy<Metrics> = once_cell::sync::Lazy::new(|| M
         "CKB network baned peer count"
     )
     .unwrap(),
+    ckb_network_protocol_bytes: register_int_counter_vec!(
+        "ckb_network_protocol_bytes",
+        "CKB network message bytes by protocol",
+        &["protocol_name", "direction"]
+    )
+    .unwrap(),
 });
 
 /// Indicate whether the metrics service is enabled.
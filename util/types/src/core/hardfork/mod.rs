@@ -8,6 +8,9 @@ mod ckb2023;
 pub use ckb2021::{CKB2021Builder, CKB2021};
 pub use ckb2023::{CKB2023Builder, CKB2023};
 
+use crate::core::EpochNumber;
+use std::collections::BTreeMap;
+
 /// Hardfork-related configuration
 #[derive(Debug, Clone)]
 pub struct HardForks {
@@ -15,6 +18,10 @@ pub struct HardForks {
     pub ckb2021: CKB2021,
     /// ckb 2023 configuration
     pub ckb2023: CKB2023,
+    /// The first epoch numbers of the syscall extensions by the extension names
+    ///
+    /// The syscall extensions are only available on custom chains.
+    pub syscall_extensions: BTreeMap<String, EpochNumber>,
 }
 
 impl HardForks {
@@ -23,6 +30,7 @@ impl HardForks {
         HardForks {
             ckb2021: CKB2021::new_mirana(),
             ckb2023: CKB2023::new_mirana(),
+            syscall_extensions: BTreeMap::new(),
         }
     }
 
@@ -31,6 +39,15 @@ impl HardForks {
         HardForks {
             ckb2021: CKB2021::new_dev_default(),
             ckb2023: CKB2023::new_dev_default(),
+            syscall_extensions: BTreeMap::new(),
         }
     }
+
+    /// If the syscall extension is enabled at the provided epoch.
+    pub fn is_syscall_extension_enabled(&self, name: &str, epoch_number: EpochNumber) -> bool {
+        self.syscall_extensions
+            .get(name)
+            .map(|start| epoch_number >= *start)
+            .unwrap_or(false)
+    }
 }
@@ -59,6 +59,10 @@ pub enum Reject {
     /// RBF rejected
     #[error("RBF rejected: {0}")]
     RBFRejected(String),
+
+    /// Transaction exceeded the limits of an input lock script
+    #[error("Transaction exceeded the limits of the input lock script {0:#x}, {1}")]
+    ExceededLockScriptLimit(Byte32, String),
 }
 
 fn is_malformed_from_verification(error: &Error) -> bool {
@@ -388,3 +392,15 @@ impl PoolTxDetailInfo {
         }
     }
 }
+
+/// The estimate mode of the fee estimator.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum EstimateMode {
+    /// Requires a lower success rate and weights the recent blocks more, the estimated fee rate
+    /// responds faster when the congestion goes down.
+    #[default]
+    Economical,
+    /// Requires a higher success rate and takes a longer history into account, the estimated fee
+    /// rate is less likely to be too low during congestion.
+    Conservative,
+}
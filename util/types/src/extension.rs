@@ -4,7 +4,7 @@ use crate::{
     core::{self},
     packed,
     prelude::*,
-    utilities::{compact_to_difficulty, merkle_root},
+    utilities::{compact_to_difficulty, merkle_root, ShortIdKey},
     U256,
 };
 
@@ -70,30 +70,10 @@ impl BuildCompactBlock for packed::CompactBlock {
         block: &core::BlockView,
         prefilled_transactions_indexes: &HashSet<usize>,
     ) -> packed::CompactBlock {
-        // always prefill cellbase
-        let prefilled_transactions_len = prefilled_transactions_indexes.len() + 1;
-        let mut short_ids: Vec<packed::ProposalShortId> = Vec::with_capacity(
-            block
-                .data()
-                .transactions()
-                .len()
-                .saturating_sub(prefilled_transactions_len),
-        );
-        let mut prefilled_transactions = Vec::with_capacity(prefilled_transactions_len);
-
-        for (transaction_index, transaction) in block.transactions().into_iter().enumerate() {
-            if prefilled_transactions_indexes.contains(&transaction_index)
-                || transaction.is_cellbase()
-            {
-                let prefilled_tx = packed::IndexTransaction::new_builder()
-                    .index((transaction_index as u32).pack())
-                    .transaction(transaction.data())
-                    .build();
-                prefilled_transactions.push(prefilled_tx);
-            } else {
-                short_ids.push(transaction.proposal_short_id());
-            }
-        }
+        let (short_ids, prefilled_transactions) =
+            split_prefilled_transactions(block, prefilled_transactions_indexes, |tx| {
+                tx.proposal_short_id()
+            });
 
         if let Some(extension) = block.data().extension() {
             packed::CompactBlockV1::new_builder()
@@ -153,6 +133,69 @@ impl BuildCompactBlock for packed::CompactBlock {
     }
 }
 
+impl BuildSaltedCompactBlock for packed::SaltedCompactBlock {
+    /// Builds a `SaltedCompactBlock` whose short ids are salted by the connection salt.
+    fn build_salted_from_block(
+        block: &core::BlockView,
+        prefilled_transactions_indexes: &HashSet<usize>,
+        salt: u64,
+    ) -> packed::SaltedCompactBlock {
+        let key = ShortIdKey::new(&block.hash(), salt);
+        let (short_ids, prefilled_transactions) =
+            split_prefilled_transactions(block, prefilled_transactions_indexes, |tx| {
+                key.short_id(&tx.hash())
+            });
+
+        packed::SaltedCompactBlock::new_builder()
+            .header(block.data().header())
+            .short_ids(short_ids.pack())
+            .prefilled_transactions(prefilled_transactions.pack())
+            .uncles(block.uncle_hashes.clone())
+            .proposals(block.data().proposals())
+            .extension(
+                packed::BytesOpt::new_builder()
+                    .set(block.data().extension())
+                    .build(),
+            )
+            .build()
+    }
+}
+
+fn split_prefilled_transactions<F, T>(
+    block: &core::BlockView,
+    prefilled_transactions_indexes: &HashSet<usize>,
+    short_id: F,
+) -> (Vec<T>, Vec<packed::IndexTransaction>)
+where
+    F: Fn(&core::TransactionView) -> T,
+{
+    // always prefill cellbase
+    let prefilled_transactions_len = prefilled_transactions_indexes.len() + 1;
+    let mut short_ids: Vec<T> = Vec::with_capacity(
+        block
+            .data()
+            .transactions()
+            .len()
+            .saturating_sub(prefilled_transactions_len),
+    );
+    let mut prefilled_transactions = Vec::with_capacity(prefilled_transactions_len);
+
+    for (transaction_index, transaction) in block.transactions().into_iter().enumerate() {
+        if prefilled_transactions_indexes.contains(&transaction_index) || transaction.is_cellbase()
+        {
+            let prefilled_tx = packed::IndexTransaction::new_builder()
+                .index((transaction_index as u32).pack())
+                .transaction(transaction.data())
+                .build();
+            prefilled_transactions.push(prefilled_tx);
+        } else {
+            short_ids.push(short_id(&transaction));
+        }
+    }
+
+    (short_ids, prefilled_transactions)
+}
+
 impl<'r> CalcExtraHash for packed::BlockReader<'r> {
     /// Calculates the extra hash, which is a combination of the uncles hash and
     /// the extension hash.
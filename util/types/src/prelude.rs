@@ -92,6 +92,16 @@ pub trait BuildCompactBlock {
     fn short_id_indexes(&self) -> Vec<usize>;
 }
 
+/// Trait for building a salted compact block from a `BlockView`.
+pub trait BuildSaltedCompactBlock {
+    /// Builds a compact block whose short IDs are salted by the connection salt.
+    fn build_salted_from_block(
+        block: &BlockView,
+        prefilled_transactions_indexes: &HashSet<usize>,
+        salt: u64,
+    ) -> packed::SaltedCompactBlock;
+}
+
 /// Trait for resetting the header of a packed block.
 pub trait ResetBlock {
     /// Resets the header of the packed block.
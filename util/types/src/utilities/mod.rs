@@ -3,6 +3,7 @@ mod block_filter;
 mod difficulty;
 pub mod merkle_mountain_range;
 mod merkle_tree;
+mod short_id;
 
 #[cfg(test)]
 mod tests;
@@ -12,3 +13,4 @@ pub use difficulty::{
     compact_to_difficulty, compact_to_target, difficulty_to_compact, target_to_compact, DIFF_TWO,
 };
 pub use merkle_tree::{merkle_root, MergeByte32, MerkleProof, CBMT};
+pub use short_id::ShortIdKey;
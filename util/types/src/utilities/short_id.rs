@@ -0,0 +1,35 @@
+use ckb_hash::blake2b_256;
+use siphasher::sip128::SipHasher24;
+
+use crate::{packed, prelude::*};
+
+/// Key of the salted short transaction ids in compact blocks.
+///
+/// The key is derived from the block hash and a salt negotiated per connection, so short id
+/// collisions can't be crafted for all peers at once.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ShortIdKey([u8; 16]);
+
+impl ShortIdKey {
+    /// Derives the key from the block hash and the connection salt.
+    pub fn new(block_hash: &packed::Byte32, salt: u64) -> Self {
+        let hash = blake2b_256([block_hash.as_slice(), &salt.to_le_bytes()[..]].concat());
+        let mut key = [0u8; 16];
+        key.copy_from_slice(&hash[..16]);
+        ShortIdKey(key)
+    }
+
+    /// Calculates the salted short id of a transaction.
+    ///
+    /// It is the SipHash-2-4 128 bits hash of the transaction hash truncated to 6 bytes. The key
+    /// changes with every block and connection, so the shorter id is still collision resistant
+    /// enough for the transactions of one block.
+    pub fn short_id(&self, tx_hash: &packed::Byte32) -> packed::SaltedShortId {
+        let hash = SipHasher24::new_with_key(&self.0)
+            .hash(tx_hash.as_slice())
+            .as_bytes();
+        let mut inner = [0u8; 6];
+        inner.copy_from_slice(&hash[..6]);
+        inner.pack()
+    }
+}
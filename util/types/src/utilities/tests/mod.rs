@@ -1 +1,2 @@
 mod difficulty;
+mod short_id;
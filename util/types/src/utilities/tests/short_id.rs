@@ -0,0 +1,65 @@
+use std::collections::HashSet;
+
+use crate::{
+    bytes::Bytes,
+    core::{BlockBuilder, TransactionBuilder},
+    h256,
+    packed::{self, CellInput, CellOutput},
+    prelude::*,
+    utilities::ShortIdKey,
+};
+
+#[test]
+fn test_short_id_depends_on_block_and_salt() {
+    let block_hash: packed::Byte32 = h256!("0x1").pack();
+    let other_block_hash: packed::Byte32 = h256!("0x2").pack();
+    let tx_hash: packed::Byte32 = h256!("0x3").pack();
+
+    let key = ShortIdKey::new(&block_hash, 42);
+    assert_eq!(key, ShortIdKey::new(&block_hash, 42));
+    assert_eq!(key.short_id(&tx_hash), key.short_id(&tx_hash));
+
+    let short_id = key.short_id(&tx_hash);
+    assert_ne!(
+        short_id,
+        ShortIdKey::new(&block_hash, 43).short_id(&tx_hash)
+    );
+    assert_ne!(
+        short_id,
+        ShortIdKey::new(&other_block_hash, 42).short_id(&tx_hash)
+    );
+}
+
+#[test]
+fn test_salted_compact_block() {
+    let tx = TransactionBuilder::default()
+        .output(CellOutput::new_builder().build())
+        .output_data(Bytes::new().pack())
+        .build();
+    let cellbase = TransactionBuilder::default()
+        .input(CellInput::new_cellbase_input(1))
+        .witness(Bytes::new().pack())
+        .build();
+    let block = BlockBuilder::default()
+        .transaction(cellbase)
+        .transaction(tx.clone())
+        .extension(Some(Bytes::from(vec![1u8; 8]).pack()))
+        .build();
+
+    let salted = packed::SaltedCompactBlock::build_salted_from_block(&block, &HashSet::new(), 42);
+    // 4 bytes of length header and one 6 bytes short id
+    assert_eq!(salted.short_ids().as_slice().len(), 4 + 6);
+
+    let key = ShortIdKey::new(&block.hash(), 42);
+    let compact = salted.to_compact_block();
+    assert_eq!(
+        compact.header().as_slice(),
+        block.data().header().as_slice()
+    );
+    assert_eq!(compact.prefilled_transactions().len(), 1);
+    assert_eq!(compact.extension(), block.data().extension());
+    assert_eq!(
+        compact.short_ids().get(0),
+        Some(key.short_id(&tx.hash()).to_proposal_short_id())
+    );
+}
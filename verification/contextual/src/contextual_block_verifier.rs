@@ -333,6 +333,7 @@ struct BlockTxsVerifier<'a, 'b, CS> {
     handle: &'a Handle,
     txs_verify_cache: &'a Arc<RwLock<TxVerificationCache>>,
     parent: &'b HeaderView,
+    parallel_script_verification: bool,
 }
 
 impl<'a, 'b, CS: ChainStore + VersionbitsIndexer + 'static> BlockTxsVerifier<'a, 'b, CS> {
@@ -342,6 +343,7 @@ impl<'a, 'b, CS: ChainStore + VersionbitsIndexer + 'static> BlockTxsVerifier<'a,
         handle: &'a Handle,
         txs_verify_cache: &'a Arc<RwLock<TxVerificationCache>>,
         parent: &'b HeaderView,
+        parallel_script_verification: bool,
     ) -> Self {
         BlockTxsVerifier {
             context,
@@ -349,6 +351,7 @@ impl<'a, 'b, CS: ChainStore + VersionbitsIndexer + 'static> BlockTxsVerifier<'a,
             handle,
             txs_verify_cache,
             parent,
+            parallel_script_verification,
         }
     }
 
@@ -429,34 +432,43 @@ impl<'a, 'b, CS: ChainStore + VersionbitsIndexer + 'static> BlockTxsVerifier<'a,
                             .into()
                         })
                         .map(|_| (tx_hash, *completed)),
-                        CacheEntry::Suspended(suspended) => ContextualTransactionVerifier::new(
-                            Arc::clone(tx),
-                            Arc::clone(&self.context.consensus),
-                            self.context.store.as_data_loader(),
-                            Arc::clone(&tx_env),
-                        )
-                        .complete(
-                            self.context.consensus.max_block_cycles(),
-                            skip_script_verify,
-                            &suspended.snap,
-                        )
-                        .map_err(|error| {
-                            BlockTransactionsError {
-                                index: index as u32,
-                                error,
+                        CacheEntry::Suspended(suspended) => {
+                            let mut verifier = ContextualTransactionVerifier::new(
+                                Arc::clone(tx),
+                                Arc::clone(&self.context.consensus),
+                                self.context.store.as_data_loader(),
+                                Arc::clone(&tx_env),
+                            );
+                            if self.parallel_script_verification {
+                                verifier.enable_parallel_script_verification();
                             }
-                            .into()
-                        })
-                        .map(|completed| (tx_hash, completed)),
+                            verifier
+                                .complete(
+                                    self.context.consensus.max_block_cycles(),
+                                    skip_script_verify,
+                                    &suspended.snap,
+                                )
+                                .map_err(|error| {
+                                    BlockTransactionsError {
+                                        index: index as u32,
+                                        error,
+                                    }
+                                    .into()
+                                })
+                                .map(|completed| (tx_hash, completed))
+                        }
                     }
                 } else {
-                    ContextualTransactionVerifier::new(
+                    let mut verifier = ContextualTransactionVerifier::new(
                         Arc::clone(tx),
                         Arc::clone(&self.context.consensus),
                         self.context.store.as_data_loader(),
                         Arc::clone(&tx_env),
-                    )
-                    .verify(
+                    );
+                    if self.parallel_script_verification {
+                        verifier.enable_parallel_script_verification();
+                    }
+                    verifier.verify(
                         self.context.consensus.max_block_cycles(),
                         skip_script_verify,
                     )
@@ -631,6 +643,7 @@ pub struct ContextualBlockVerifier<'a, CS, MS> {
     handle: &'a Handle,
     txs_verify_cache: Arc<RwLock<TxVerificationCache>>,
     chain_root_mmr: &'a ChainRootMMR<MS>,
+    parallel_script_verification: bool,
 }
 
 impl<'a, CS: ChainStore + VersionbitsIndexer + 'static, MS: MMRStore<HeaderDigest>>
@@ -650,9 +663,15 @@ impl<'a, CS: ChainStore + VersionbitsIndexer + 'static, MS: MMRStore<HeaderDiges
             switch,
             txs_verify_cache,
             chain_root_mmr,
+            parallel_script_verification: false,
         }
     }
 
+    /// Verify the independent script groups of each transaction in parallel
+    pub fn enable_parallel_script_verification(&mut self) {
+        self.parallel_script_verification = true;
+    }
+
     /// Perform context-dependent verification checks for block
     pub fn verify(
         &'a self,
@@ -710,6 +729,7 @@ impl<'a, CS: ChainStore + VersionbitsIndexer + 'static, MS: MMRStore<HeaderDiges
             self.handle,
             &self.txs_verify_cache,
             &parent,
+            self.parallel_script_verification,
         )
         .verify(resolved, self.switch.disable_script())?;
         Ok(ret)
@@ -35,6 +35,7 @@ pub fn test_version() {
             .rfc_0048(10)
             .build()
             .unwrap(),
+        syscall_extensions: Default::default(),
     };
     let consensus = ConsensusBuilder::default()
         .hardfork_switch(hardfork_switch)
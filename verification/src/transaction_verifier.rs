@@ -5,7 +5,10 @@ use ckb_chain_spec::consensus::Consensus;
 use ckb_dao::DaoCalculator;
 use ckb_dao_utils::DaoError;
 use ckb_error::Error;
-use ckb_script::{TransactionScriptsVerifier, TransactionSnapshot, TransactionState, VerifyResult};
+use ckb_script::{
+    ScriptGroupTrace, TransactionScriptsVerifier, TransactionSnapshot, TransactionState,
+    VerifyResult,
+};
 use ckb_traits::{
     CellDataProvider, EpochProvider, ExtensionProvider, HeaderFieldsProvider, HeaderProvider,
 };
@@ -154,6 +157,11 @@ where
         }
     }
 
+    /// Verify the script groups of the transaction in parallel
+    pub fn enable_parallel_script_verification(&mut self) {
+        self.script.enable_parallel_verification();
+    }
+
     /// Perform resumable context-dependent verification, return a `Result` to `CacheEntry`
     pub fn resumable_verify(&self, limit_cycles: Cycle) -> Result<(VerifyResult, Capacity), Error> {
         self.compatible.verify()?;
@@ -352,6 +360,22 @@ impl<DL: CellDataProvider + HeaderProvider + ExtensionProvider + Send + Sync + C
         Ok(cycle)
     }
 
+    /// Enable recording the syscalls, debug messages and spawned VMs in `trace`
+    pub fn enable_trace(&mut self) {
+        self.inner.enable_trace();
+    }
+
+    /// Verify the script groups in parallel, with the same cycles and errors as verifying them
+    /// one by one
+    pub fn enable_parallel_verification(&mut self) {
+        self.inner.enable_parallel_verification();
+    }
+
+    /// Perform script verification and return the execution traces of the script groups
+    pub fn trace(&self, max_cycles: Cycle) -> Vec<ScriptGroupTrace> {
+        self.inner.trace(max_cycles)
+    }
+
     /// Perform resumable script verification
     pub fn resumable_verify(&self, limit_cycles: Cycle) -> Result<VerifyResult, Error> {
         let ret = self.inner.resumable_verify(limit_cycles)?;